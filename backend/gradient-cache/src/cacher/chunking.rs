@@ -0,0 +1,290 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! NAR chunking: with `--nar-chunking` on, converts flat NARs to the
+//! deduplicated chunked layout (`gradient_storage::nar_chunk`), and always
+//! releases chunked NARs whose path is gone so their chunks' refcounts drop.
+//! Runs in the cache-maintenance sweep after cache eviction, or on demand via
+//! `POST /admin/maintenance/nar-chunking`; every run is recorded in a
+//! `nar_chunking` admin task, so conversions and releases never overlap.
+
+use anyhow::{Context, Result};
+use gradient_core::ServerState;
+use gradient_db::admin_tasks::{self, InsertPendingError};
+use gradient_db::{ChunkRecord, ChunkingCandidate};
+use gradient_entity::ids::AdminTaskId;
+use gradient_types::constants::NAR_ZSTD_LEVEL;
+use gradient_types::*;
+use sea_orm::TransactionTrait;
+use serde::Serialize;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+/// Candidates fetched per conversion round.
+const CHUNKING_BATCH: u64 = 100;
+/// NARs converted per run, so one pass cannot hold the maintenance sweep for
+/// hours; the next pass picks up where the layout is still flat.
+const MAX_NARS_PER_RUN: u64 = 2000;
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct NarChunkingReport {
+    pub released_nars: u64,
+    pub released_chunks: u64,
+    pub converted_nars: u64,
+    /// Candidates left flat: the object was missing or the path changed
+    /// while it was being converted.
+    pub skipped_nars: u64,
+    /// Compressed bytes of the converted NARs before and after conversion.
+    pub flat_bytes: i64,
+    pub chunked_bytes: i64,
+    pub new_chunks: u64,
+    pub reused_chunks: u64,
+    /// Nothing ran because a deep GC task was active.
+    pub deferred: bool,
+}
+
+impl NarChunkingReport {
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_else(|e| {
+            warn!(error = ?e, "nar_chunking: report serialization failed");
+            serde_json::Value::Null
+        })
+    }
+}
+
+fn threshold(state: &ServerState) -> i64 {
+    i64::try_from(state.config.storage.nar_chunking_threshold_bytes).unwrap_or(i64::MAX)
+}
+
+/// Maintenance-sweep step: when there are flat NARs to convert or chunked
+/// NARs to release, record a `nar_chunking` admin task and run it inline.
+/// Skipped while another chunking task is active.
+pub async fn chunk_nars(state: Arc<ServerState>) -> Result<()> {
+    let convert = state.config.storage.nar_chunking
+        && !gradient_db::chunking_candidates(&state.worker_db, threshold(&state), "", 1)
+            .await
+            .context("nar_chunking: query candidates")?
+            .is_empty();
+    let release = gradient_db::has_dead_chunked_nars(&state.worker_db)
+        .await
+        .context("nar_chunking: query dead chunked NARs")?;
+    if !convert && !release {
+        return Ok(());
+    }
+
+    match admin_tasks::insert_pending(&state.worker_db, AdminTaskKind::NarChunking, None).await {
+        Ok(task) => {
+            run_nar_chunking(state, task.id).await;
+            Ok(())
+        }
+        Err(InsertPendingError::AlreadyActive(id)) => {
+            info!(task_id = %id, "nar_chunking: task already active; skipping");
+            Ok(())
+        }
+        Err(InsertPendingError::Db(e)) => Err(e),
+    }
+}
+
+/// Entry point for one `nar_chunking` admin task.
+pub async fn run_nar_chunking(state: Arc<ServerState>, task_id: AdminTaskId) {
+    if let Err(e) = admin_tasks::mark_running(&state.worker_db, task_id).await {
+        error!(error = ?e, %task_id, "nar_chunking: mark_running failed");
+        return;
+    }
+
+    let mut report = NarChunkingReport::default();
+    match admin_tasks::find_active(&state.worker_db, AdminTaskKind::DeepGc).await {
+        // Deep GC deletes unrecorded chunk frames that a conversion would
+        // reuse; checked after this task's own row exists, so deep GC's chunk
+        // pass sees it in turn.
+        Ok(Some(gc)) => {
+            info!(%task_id, deep_gc_task = %gc.id, "nar_chunking: deep GC active; deferring");
+            report.deferred = true;
+            if let Err(e) =
+                admin_tasks::mark_completed(&state.worker_db, task_id, report.to_json()).await
+            {
+                error!(error = ?e, %task_id, "nar_chunking: mark_completed failed");
+            }
+            return;
+        }
+        Ok(None) => {}
+        Err(e) => {
+            let msg = format!("{e:#}");
+            error!(%task_id, error = %msg, "nar_chunking: deep GC check failed");
+            if let Err(e) = admin_tasks::mark_failed(&state.worker_db, task_id, msg, None).await {
+                error!(error = ?e, %task_id, "nar_chunking: mark_failed failed");
+            }
+            return;
+        }
+    }
+    if let Err(e) = chunk_all(&state, &mut report).await {
+        let msg = format!("{e:#}");
+        error!(%task_id, error = %msg, "nar_chunking failed");
+        if let Err(e) =
+            admin_tasks::mark_failed(&state.worker_db, task_id, msg, Some(report.to_json())).await
+        {
+            error!(error = ?e, %task_id, "nar_chunking: mark_failed failed");
+        }
+        return;
+    }
+
+    if let Err(e) = admin_tasks::mark_completed(&state.worker_db, task_id, report.to_json()).await {
+        error!(error = ?e, %task_id, "nar_chunking: mark_completed failed");
+    } else {
+        info!(?report, %task_id, "nar_chunking completed");
+    }
+}
+
+async fn chunk_all(state: &Arc<ServerState>, report: &mut NarChunkingReport) -> Result<()> {
+    release_dead(state, report).await?;
+    if !state.config.storage.nar_chunking {
+        return Ok(());
+    }
+
+    let mut after = String::new();
+    let mut attempted = 0;
+    while attempted < MAX_NARS_PER_RUN {
+        let candidates = gradient_db::chunking_candidates(
+            &state.worker_db,
+            threshold(state),
+            &after,
+            CHUNKING_BATCH.min(MAX_NARS_PER_RUN - attempted),
+        )
+        .await
+        .context("nar_chunking: query candidates")?;
+        let Some(last) = candidates.last() else {
+            break;
+        };
+        after = last.hash.clone();
+        attempted += candidates.len() as u64;
+
+        for candidate in &candidates {
+            convert(state, candidate, report).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Drop the bookkeeping of chunked NARs whose path is gone, then their
+/// manifests and every chunk left unreferenced.
+async fn release_dead(state: &Arc<ServerState>, report: &mut NarChunkingReport) -> Result<()> {
+    let txn = state.worker_db.inner().begin().await?;
+    let released = gradient_db::release_dead_chunked_nars(&txn)
+        .await
+        .context("nar_chunking: release dead chunked NARs")?;
+    txn.commit().await?;
+
+    for hash in &released.nars {
+        if let Err(e) = state.nar_storage.delete_manifest(hash).await {
+            warn!(error = %e, %hash, "nar_chunking: failed to remove chunk manifest");
+        }
+    }
+    for chunk in &released.chunks {
+        if let Err(e) = state.nar_storage.delete_chunk(chunk).await {
+            warn!(error = %e, %chunk, "nar_chunking: failed to remove chunk");
+        }
+    }
+    report.released_nars += released.nars.len() as u64;
+    report.released_chunks += released.chunks.len() as u64;
+    Ok(())
+}
+
+/// Convert one flat NAR. The chunks and manifest are written first, the
+/// database then switches the path over, and only then does the flat object
+/// go; a path that changed meanwhile keeps its flat object and the
+/// conversion is undone. The flat object also stays when a re-upload took
+/// the path back to flat after the switch, since it is the re-uploaded NAR.
+async fn convert(
+    state: &Arc<ServerState>,
+    candidate: &ChunkingCandidate,
+    report: &mut NarChunkingReport,
+) -> Result<()> {
+    let hash = &candidate.hash;
+    let chunked = match state.nar_storage.chunk_nar(hash, NAR_ZSTD_LEVEL).await {
+        Ok(Some(chunked)) => chunked,
+        Ok(None) => {
+            report.skipped_nars += 1;
+            return Ok(());
+        }
+        Err(e) => {
+            warn!(error = %e, %hash, "nar_chunking: conversion failed; leaving NAR flat");
+            report.skipped_nars += 1;
+            return Ok(());
+        }
+    };
+    let records: Vec<ChunkRecord> = chunked
+        .manifest
+        .chunks
+        .iter()
+        .map(|c| ChunkRecord {
+            hash: c.hash.clone(),
+            size: c.size as i64,
+            raw_size: c.raw_size as i64,
+        })
+        .collect();
+
+    let txn = state.worker_db.inner().begin().await?;
+    let recorded = gradient_db::record_chunked_nar(&txn, candidate, &chunked.file_hash, &records)
+        .await
+        .context("nar_chunking: record chunked NAR")?;
+    if !recorded {
+        txn.rollback().await?;
+        discard(state, hash, &chunked.new_chunks).await?;
+        report.skipped_nars += 1;
+        return Ok(());
+    }
+    txn.commit().await?;
+
+    let still_chunked = gradient_db::serves_file_hash(&state.worker_db, hash, &chunked.file_hash)
+        .await
+        .context("nar_chunking: recheck chunked NAR")?;
+    if !still_chunked {
+        info!(%hash, "nar_chunking: path re-uploaded during conversion; keeping flat NAR");
+    } else if let Err(e) = state.nar_storage.delete_flat(hash).await {
+        warn!(error = %e, %hash, "nar_chunking: failed to remove flat NAR");
+    }
+    report.converted_nars += 1;
+    report.flat_bytes += candidate.file_size;
+    report.chunked_bytes += chunked.manifest.size() as i64;
+    report.new_chunks += chunked.new_chunks.len() as u64;
+    report.reused_chunks += (records.len() - chunked.new_chunks.len()) as u64;
+    Ok(())
+}
+
+/// Undo an unrecorded conversion: the manifest and whichever freshly
+/// uploaded chunks no recorded NAR references.
+async fn discard(state: &Arc<ServerState>, hash: &str, new_chunks: &[String]) -> Result<()> {
+    if let Err(e) = state.nar_storage.delete_manifest(hash).await {
+        warn!(error = %e, %hash, "nar_chunking: failed to remove discarded manifest");
+    }
+    let unused = gradient_db::unrecorded_chunks(&state.worker_db, new_chunks)
+        .await
+        .context("nar_chunking: query unrecorded chunks")?;
+    for chunk in &unused {
+        if let Err(e) = state.nar_storage.delete_chunk(chunk).await {
+            warn!(error = %e, %chunk, "nar_chunking: failed to remove discarded chunk");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_serialises_snake_case_counters() {
+        let report = NarChunkingReport {
+            converted_nars: 3,
+            reused_chunks: 40,
+            ..Default::default()
+        };
+        let json = report.to_json();
+        assert_eq!(json["converted_nars"], 3);
+        assert_eq!(json["reused_chunks"], 40);
+        assert_eq!(json["released_chunks"], 0);
+    }
+}
@@ -165,6 +165,13 @@ pub async fn cleanup_stale_cached_nars(state: Arc<ServerState>) -> Result<()> {
         ))
         .await
         .context("Failed to query stale cache_derivation rows")?;
+    if rows.is_empty() {
+        return Ok(());
+    }
+
+    let pinned = gradient_db::pinned_paths(&state.worker_db)
+        .await
+        .context("TTL GC: failed to query pinned paths")?;
 
     for row in rows {
         let cd_id: Uuid = match row.try_get("", "id") {
@@ -192,6 +199,15 @@ pub async fn cleanup_stale_cached_nars(state: Arc<ServerState>) -> Result<()> {
             .map(|o| o.hash)
             .collect();
 
+        // A pin of this cache keeps the whole row; a pin elsewhere keeps only
+        // the shared NAR file (below).
+        if output_hashes
+            .iter()
+            .any(|h| pinned.contains_in(CacheId::new(cache_id), h))
+        {
+            continue;
+        }
+
         // Drop the cache_derivation row first; revocation of dependents follows.
         ECacheDerivation::delete_many()
             .filter(CCacheDerivation::Id.eq(cd_id))
@@ -251,7 +267,7 @@ pub async fn cleanup_stale_cached_nars(state: Arc<ServerState>) -> Result<()> {
             .context("TTL GC: failed to check surviving cache_derivation rows")?
             .is_some();
         if !still_held {
-            for hash in &output_hashes {
+            for hash in output_hashes.iter().filter(|h| !pinned.contains(h)) {
                 if let Err(e) = state.nar_storage.delete(hash).await {
                     warn!(error = %e, %hash, "Failed to remove stale compressed NAR");
                 }
@@ -382,7 +398,11 @@ async fn purge_zombie_cached_paths(
 /// build status - they are rebuildable and TTL-evicted by `cleanup_stale_cached_nars`.
 /// The `.drv` (clause 4) and input sources (clause 3) are producerless and kept
 /// for any anchor regardless of status; only `gc_orphan_derivations` reclaims them.
-const ACTIVE_HASHES_SELECT: &str = r#"
+/// Clause 5 is the closure of every live cache pin.
+fn active_hashes_select() -> String {
+    format!(
+        r#"
+    WITH RECURSIVE {pinned}
     SELECT DISTINCT dout.hash AS hash
     FROM derivation_output dout
     JOIN derivation_build b ON b.derivation = dout.derivation
@@ -399,7 +419,13 @@ const ACTIVE_HASHES_SELECT: &str = r#"
     SELECT d.hash AS hash
     FROM derivation d
     JOIN derivation_build b ON b.derivation = d.id
-"#;
+    UNION
+    SELECT pn.hash AS hash
+    FROM pinned pn
+"#,
+        pinned = gradient_db::pinned_closure_cte_body()
+    )
+}
 
 /// Returns the set of NAR-storage hashes that must NOT be garbage-collected by
 /// the orphan-files pass. A hash is kept when either:
@@ -419,6 +445,8 @@ const ACTIVE_HASHES_SELECT: &str = r#"
 ///    purged the `.drv`/sources of a failed-but-requeueable build, dead-ending its
 ///    retry on `InputsUnavailable`. Genuinely dead ones are reclaimed by
 ///    `gc_orphan_derivations` when the derivation row goes orphan.
+/// 4. it is in the runtime closure of a live cache pin, in any cache. Deep GC
+///    reuses this pass, so pins hold there too.
 ///
 /// Note: this is intentionally more permissive than the old `is_cached=true`
 /// check. `gc_orphan_derivations` and `cleanup_stale_cached_nars` are the
@@ -429,7 +457,7 @@ async fn active_hashes(state: &Arc<ServerState>) -> Result<HashSet<String>> {
         .worker_db
         .query_all(Statement::from_sql_and_values(
             DatabaseBackend::Postgres,
-            ACTIVE_HASHES_SELECT,
+            active_hashes_select(),
             [
                 sea_orm::Value::Int(Some(BuildStatus::FailedPermanent as i32)),
                 sea_orm::Value::Int(Some(BuildStatus::Aborted as i32)),
@@ -521,7 +549,7 @@ mod tests {
     /// requeued terminal-failed build can still fetch its `.drv`.
     #[test]
     fn keep_set_protects_drv_and_sources_for_any_anchor() {
-        let sql = ACTIVE_HASHES_SELECT
+        let sql = active_hashes_select()
             .split_whitespace()
             .collect::<Vec<_>>()
             .join(" ");
@@ -538,6 +566,10 @@ mod tests {
             sql.contains("FROM derivation d JOIN derivation_build b ON b.derivation = d.id"),
             "drv kept for any anchor (no status gate): {sql}"
         );
+        assert!(
+            sql.contains("UNION SELECT pn.hash AS hash FROM pinned pn"),
+            "pinned closures kept: {sql}"
+        );
     }
 
     /// A NAR referenced only by a `cached_path` row (e.g. a `.drv` file) must
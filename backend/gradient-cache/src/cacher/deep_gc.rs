@@ -30,6 +30,10 @@ pub struct DeepGcReport {
     pub blob_check_errors: u64,
     pub logs_scanned: u64,
     pub orphan_logs_removed: u64,
+    pub chunks_scanned: u64,
+    pub orphan_chunks_removed: u64,
+    /// The chunk pass did not run because a `nar_chunking` task was active.
+    pub chunk_pass_skipped: bool,
 }
 
 impl DeepGcReport {
@@ -63,6 +67,11 @@ pub async fn run_deep_gc(state: Arc<ServerState>, task_id: AdminTaskId) {
     if let Err(e) = pass_logs(Arc::clone(&state), &mut report).await {
         return finish_failed(state, task_id, e, report).await;
     }
+    flush_progress(&state, task_id, &report).await;
+
+    if let Err(e) = pass_chunks(Arc::clone(&state), &mut report).await {
+        return finish_failed(state, task_id, e, report).await;
+    }
 
     if let Err(e) = admin_tasks::mark_completed(&state.worker_db, task_id, report.to_json()).await {
         error!(error = ?e, %task_id, "deep_gc: mark_completed failed");
@@ -188,6 +197,59 @@ async fn pass_logs(state: Arc<ServerState>, report: &mut DeepGcReport) -> Result
     Ok(())
 }
 
+/// Chunk frames per `nar_chunk` lookup.
+const CHUNK_LOOKUP_BATCH: usize = 1000;
+
+/// Removes `nar-chunks/` frames without a `nar_chunk` row, left behind when a
+/// conversion or a release died between storage and the database. Frames
+/// younger than the upload grace window are spared: a running conversion
+/// uploads its chunks before recording them. Skipped while a `nar_chunking`
+/// task is active, since a conversion reuses any chunk frame already stored,
+/// recorded or not; that task in turn does not convert while deep GC runs.
+async fn pass_chunks(state: Arc<ServerState>, report: &mut DeepGcReport) -> Result<()> {
+    if let Some(task) = admin_tasks::find_active(&state.worker_db, AdminTaskKind::NarChunking)
+        .await
+        .context("find active nar_chunking task")?
+    {
+        info!(task_id = %task.id, "deep_gc: nar_chunking active; skipping chunk pass");
+        report.chunk_pass_skipped = true;
+        return Ok(());
+    }
+
+    let on_disk = state
+        .nar_storage
+        .list_chunks_with_modified()
+        .await
+        .context("list_chunks")?;
+    report.chunks_scanned = on_disk.len() as u64;
+
+    let grace_secs = state.config.storage.nar_upload_grace_hours.max(0) * 3600;
+    let cutoff = if grace_secs > 0 {
+        now().and_utc().timestamp() - grace_secs
+    } else {
+        i64::MAX
+    };
+    let settled: Vec<String> = on_disk
+        .into_iter()
+        .filter(|(_, modified)| *modified < cutoff)
+        .map(|(chunk, _)| chunk)
+        .collect();
+
+    for batch in settled.chunks(CHUNK_LOOKUP_BATCH) {
+        let orphans = gradient_db::unrecorded_chunks(&state.worker_db, batch)
+            .await
+            .context("query unrecorded chunks")?;
+        for chunk in &orphans {
+            if let Err(e) = state.nar_storage.delete_chunk(chunk).await {
+                warn!(error = %e, %chunk, "deep_gc: failed to delete orphan chunk");
+            } else {
+                report.orphan_chunks_removed += 1;
+            }
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -289,6 +351,62 @@ mod tests {
         assert_eq!(report.orphan_logs_removed, 1);
     }
 
+    #[tokio::test]
+    async fn pass_chunks_removes_only_unrecorded_chunks() {
+        let tmp = tempfile::tempdir().unwrap();
+        for chunk in ["aa11", "bb22"] {
+            let dir = tmp.path().join("nar-chunks").join(&chunk[..2]);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join(format!("{chunk}.zst")), b"frame").unwrap();
+        }
+        let nar = NarStore::local(tmp.path().to_str().unwrap()).unwrap();
+
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([Vec::<MAdminTask>::new()])
+            .append_query_results([vec![std::collections::BTreeMap::from([(
+                "hash",
+                sea_orm::Value::from("bb22"),
+            )])]])
+            .into_connection();
+        let state = test_server_state_with_log(nar, Arc::new(NoopLogStorage), db, |c| {
+            c.storage.nar_upload_grace_hours = 0;
+        });
+
+        let mut report = DeepGcReport::default();
+        pass_chunks(Arc::clone(&state), &mut report).await.unwrap();
+        assert_eq!(report.chunks_scanned, 2);
+        assert_eq!(report.orphan_chunks_removed, 1);
+        assert!(tmp.path().join("nar-chunks/aa/aa11.zst").exists());
+        assert!(!tmp.path().join("nar-chunks/bb/bb22.zst").exists());
+    }
+
+    #[tokio::test]
+    async fn pass_chunks_skips_while_nar_chunking_is_active() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("nar-chunks/aa");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("aa11.zst"), b"frame").unwrap();
+        let nar = NarStore::local(tmp.path().to_str().unwrap()).unwrap();
+
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([vec![MAdminTask {
+                id: AdminTaskId::now_v7(),
+                kind: AdminTaskKind::NarChunking,
+                status: AdminTaskStatus::Running,
+                ..Default::default()
+            }]])
+            .into_connection();
+        let state = test_server_state_with_log(nar, Arc::new(NoopLogStorage), db, |c| {
+            c.storage.nar_upload_grace_hours = 0;
+        });
+
+        let mut report = DeepGcReport::default();
+        pass_chunks(Arc::clone(&state), &mut report).await.unwrap();
+        assert!(report.chunk_pass_skipped);
+        assert_eq!(report.chunks_scanned, 0);
+        assert!(tmp.path().join("nar-chunks/aa/aa11.zst").exists());
+    }
+
     #[test]
     fn report_serialises_with_snake_case_keys() {
         let r = DeepGcReport {
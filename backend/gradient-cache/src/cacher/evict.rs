@@ -0,0 +1,239 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Cache eviction: frees caches that reached their `max_storage_gb` by their
+//! `eviction_policy`, instead of leaving evaluations parked under
+//! `CacheStorageFull`. Runs in the cache-maintenance sweep ahead of the
+//! storage-full unpark, or on demand via `POST /admin/maintenance/cache-eviction`;
+//! every run is recorded in a `cache_eviction` admin task.
+
+use anyhow::{Context, Result};
+use gradient_core::ServerState;
+use gradient_db::admin_tasks::{self, InsertPendingError};
+use gradient_entity::cache::CacheEvictionPolicy;
+use gradient_entity::ids::AdminTaskId;
+use gradient_types::*;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, TransactionTrait};
+use serde::Serialize;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+/// Candidates fetched per eviction round.
+const EVICTION_BATCH: u64 = 500;
+/// Store paths listed per cache in the task report; the counters stay exact.
+const REPORTED_PATHS_PER_CACHE: usize = 1000;
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct CacheEvictionReport {
+    pub caches: Vec<CacheEvictionEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheEvictionEntry {
+    pub cache: CacheId,
+    pub cache_name: String,
+    pub policy: CacheEvictionPolicy,
+    pub bytes_needed: i64,
+    pub evicted_paths: u64,
+    pub evicted_bytes: i64,
+    pub nars_removed: u64,
+    /// `false` when every candidate was evicted and the cache is still over
+    /// its low-water mark: the rest is held by retained evaluations.
+    pub satisfied: bool,
+    pub paths: Vec<String>,
+}
+
+impl CacheEvictionReport {
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_else(|e| {
+            warn!(error = ?e, "cache_eviction: report serialization failed");
+            serde_json::Value::Null
+        })
+    }
+}
+
+/// Active caches with an eviction policy that are over their limit, with the
+/// bytes each must shed.
+async fn caches_over_limit(state: &Arc<ServerState>) -> Result<Vec<(MCache, i64)>> {
+    let caches = ECache::find()
+        .filter(CCache::Active.eq(true))
+        .filter(CCache::MaxStorageGb.gt(0))
+        .filter(CCache::EvictionPolicy.ne(CacheEvictionPolicy::Disabled))
+        .all(&state.worker_db)
+        .await
+        .context("cache_eviction: query caches")?;
+
+    let mut over = Vec::new();
+    for cache in caches {
+        let used = gradient_db::cache_used_bytes(&state.worker_db, cache.id)
+            .await
+            .context("cache_eviction: cache usage")?;
+        if let Some(needed) = gradient_db::eviction_bytes_needed(cache.max_storage_gb, used) {
+            over.push((cache, needed));
+        }
+    }
+    Ok(over)
+}
+
+/// Maintenance-sweep step: when any cache is over its limit, record a
+/// `cache_eviction` admin task and run it inline. Skipped while another
+/// eviction task is active.
+pub async fn evict_full_caches(state: Arc<ServerState>) -> Result<()> {
+    if caches_over_limit(&state).await?.is_empty() {
+        return Ok(());
+    }
+
+    match admin_tasks::insert_pending(&state.worker_db, AdminTaskKind::CacheEviction, None).await {
+        Ok(task) => {
+            run_cache_eviction(state, task.id).await;
+            Ok(())
+        }
+        Err(InsertPendingError::AlreadyActive(id)) => {
+            info!(task_id = %id, "cache_eviction: task already active; skipping");
+            Ok(())
+        }
+        Err(InsertPendingError::Db(e)) => Err(e),
+    }
+}
+
+/// Entry point for one `cache_eviction` admin task.
+pub async fn run_cache_eviction(state: Arc<ServerState>, task_id: AdminTaskId) {
+    if let Err(e) = admin_tasks::mark_running(&state.worker_db, task_id).await {
+        error!(error = ?e, %task_id, "cache_eviction: mark_running failed");
+        return;
+    }
+
+    let mut report = CacheEvictionReport::default();
+    if let Err(e) = evict_all(&state, &mut report).await {
+        let msg = format!("{e:#}");
+        error!(%task_id, error = %msg, "cache_eviction failed");
+        if let Err(e) =
+            admin_tasks::mark_failed(&state.worker_db, task_id, msg, Some(report.to_json())).await
+        {
+            error!(error = ?e, %task_id, "cache_eviction: mark_failed failed");
+        }
+        return;
+    }
+
+    if let Err(e) = admin_tasks::mark_completed(&state.worker_db, task_id, report.to_json()).await {
+        error!(error = ?e, %task_id, "cache_eviction: mark_completed failed");
+    } else {
+        info!(?report, %task_id, "cache_eviction completed");
+    }
+}
+
+async fn evict_all(state: &Arc<ServerState>, report: &mut CacheEvictionReport) -> Result<()> {
+    for (cache, needed) in caches_over_limit(state).await? {
+        let mut entry = CacheEvictionEntry {
+            cache: cache.id,
+            cache_name: cache.name.clone(),
+            policy: cache.eviction_policy,
+            bytes_needed: needed,
+            evicted_paths: 0,
+            evicted_bytes: 0,
+            nars_removed: 0,
+            satisfied: false,
+            paths: Vec::new(),
+        };
+        let result = evict_cache(state, &cache, &mut entry).await;
+        report.caches.push(entry);
+        result?;
+    }
+    Ok(())
+}
+
+/// Evict `cache`'s candidates in policy order until `entry.bytes_needed` is
+/// freed or none are left. Each round re-queries, so the references of an
+/// evicted root become candidates in the next one.
+async fn evict_cache(
+    state: &Arc<ServerState>,
+    cache: &MCache,
+    entry: &mut CacheEvictionEntry,
+) -> Result<()> {
+    while entry.evicted_bytes < entry.bytes_needed {
+        let candidates = gradient_db::eviction_candidates(
+            &state.worker_db,
+            cache.id,
+            cache.eviction_policy,
+            EVICTION_BATCH,
+        )
+        .await
+        .context("cache_eviction: query candidates")?;
+        if candidates.is_empty() {
+            break;
+        }
+
+        let mut batch = Vec::new();
+        let mut batch_bytes = 0;
+        for candidate in candidates {
+            if entry.evicted_bytes + batch_bytes >= entry.bytes_needed {
+                break;
+            }
+            batch_bytes += candidate.file_size;
+            batch.push(candidate);
+        }
+        let hashes: Vec<String> = batch.iter().map(|c| c.hash.clone()).collect();
+
+        let txn = state.worker_db.inner().begin().await?;
+        let dropped = gradient_db::evict_cached_paths(&txn, cache.id, &hashes)
+            .await
+            .context("cache_eviction: evict paths")?;
+        txn.commit().await?;
+
+        for hash in &dropped {
+            if let Err(e) = state.nar_storage.delete(hash).await {
+                warn!(error = %e, %hash, "cache_eviction: failed to remove NAR");
+            } else {
+                entry.nars_removed += 1;
+            }
+        }
+
+        entry.evicted_paths += batch.len() as u64;
+        entry.evicted_bytes += batch_bytes;
+        let room = REPORTED_PATHS_PER_CACHE.saturating_sub(entry.paths.len());
+        entry.paths.extend(
+            batch
+                .iter()
+                .take(room)
+                .map(|c| StorePath::from_parts(c.hash.clone(), c.package.clone()).full()),
+        );
+    }
+
+    entry.satisfied = entry.evicted_bytes >= entry.bytes_needed;
+    info!(
+        cache = %cache.name,
+        evicted_paths = entry.evicted_paths,
+        evicted_bytes = entry.evicted_bytes,
+        satisfied = entry.satisfied,
+        "cache_eviction: cache evicted"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_serialises_policy_in_snake_case() {
+        let report = CacheEvictionReport {
+            caches: vec![CacheEvictionEntry {
+                cache: CacheId::now_v7(),
+                cache_name: "main".into(),
+                policy: CacheEvictionPolicy::OldestFirst,
+                bytes_needed: 10,
+                evicted_paths: 1,
+                evicted_bytes: 12,
+                nars_removed: 1,
+                satisfied: true,
+                paths: vec!["/nix/store/aaaa-hello".into()],
+            }],
+        };
+        let json = report.to_json();
+        assert_eq!(json["caches"][0]["policy"], "oldest_first");
+        assert_eq!(json["caches"][0]["evicted_bytes"], 12);
+    }
+}
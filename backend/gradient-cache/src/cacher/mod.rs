@@ -11,16 +11,20 @@
 //! per-cache signatures attached. This module only runs periodic cleanup /
 //! GC passes against the cache's DB and NAR store.
 
+mod chunking;
 mod cleanup;
 mod deep_gc;
 mod eval_cache_sweep;
+mod evict;
 mod invalidate;
 mod sign_sweep;
 #[cfg(test)]
 pub(crate) mod test_support;
 
+pub use self::chunking::{NarChunkingReport, chunk_nars, run_nar_chunking};
 pub use self::deep_gc::{DeepGcReport, run_deep_gc};
 pub use self::eval_cache_sweep::{eval_cache_sweep_loop, evict_eval_cache};
+pub use self::evict::{CacheEvictionReport, evict_full_caches, run_cache_eviction};
 
 pub use self::cleanup::{
     CleanupReport, cleanup_expired_upload_sessions, cleanup_old_evaluations,
@@ -58,12 +62,12 @@ impl Sweep {
     }
 }
 
-/// The registered sweeps. "cache-maintenance" bundles the 9 order-sensitive
+/// The registered sweeps. "cache-maintenance" bundles the 11 order-sensitive
 /// GC/reconcile steps that used to live in the monolithic `cache_loop`
 /// (orphan-files, eval GC, derivation GC, NAR TTL, demote-unbacked,
-/// unpark-storage-full, build-request blobs, upload sessions, partial-store
-/// GC); "sign-sweep" is the signature backfill. Each runs on its own
-/// interval and its own spawned loop.
+/// cache eviction, unpark-storage-full, NAR chunking, build-request blobs,
+/// upload sessions, partial-store GC); "sign-sweep" is the signature backfill.
+/// Each runs on its own interval and its own spawned loop.
 fn sweeps(state: &ServerState) -> Vec<Sweep> {
     vec![
         Sweep::new(
@@ -126,7 +130,7 @@ pub fn spawn_sweeps(state: &Arc<ServerState>) {
     }
 }
 
-/// The 9 order-sensitive cache-maintenance steps, run sequentially every
+/// The 11 order-sensitive cache-maintenance steps, run sequentially every
 /// `cache_maintenance_interval_secs`. No per-output work here - the worker
 /// uploads+signs; this is GC and self-heal reconciliation only.
 async fn run_cache_maintenance(state: Arc<ServerState>) -> anyhow::Result<()> {
@@ -166,12 +170,23 @@ async fn run_cache_maintenance(state: Arc<ServerState>) -> anyhow::Result<()> {
         Ok(_) => {}
         Err(e) => error!(error = ?e, "Cache-trust reconcile failed"),
     }
+    // Evict caches at their limit by policy before unparking, so evaluations
+    // parked on a full cache resume in this same pass.
+    if let Err(e) = evict_full_caches(Arc::clone(&state)).await {
+        error!(error = ?e, "Cache eviction failed");
+    }
     if let Err(e) =
         gradient_ci::unpark_storage_full_all(&state.worker_db, state.config.storage.max_storage_gb)
             .await
     {
         error!(error = ?e, "Failed to unpark storage-full evaluations after cleanup");
     }
+    // After eviction, so NARs evicted in this pass release their chunks here
+    // and are never converted first; after the unpark, since a long
+    // conversion run must not hold parked evaluations back.
+    if let Err(e) = chunk_nars(Arc::clone(&state)).await {
+        error!(error = ?e, "NAR chunking failed");
+    }
     if let Err(e) = cleanup_stale_build_request_blobs(Arc::clone(&state)).await {
         error!(error = ?e, "Build-request blob GC failed");
     }
@@ -0,0 +1,474 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Digest mode for `send_mail` actions. Instead of one mail per event, a
+//! matched event is parked in `project_action_digest_entry` until the action's
+//! window closes; the digest loop then drains the window, resolves each
+//! evaluation's failed attributes and first error lines from the build logs,
+//! and hands the summary to [`execute_action`] as a single [`DIGEST_EVENT`]
+//! delivery, so digests get the usual delivery log. The buffered entries are
+//! the digest's retry state: they are deleted only once it was sent, and a
+//! failed digest is rebuilt from them - joined by any newer events - on a
+//! later pass rather than replayed by the delivery retry loop.
+
+use super::executor::execute_action;
+use super::message::{message_fields, payload_evaluation_id};
+use crate::context::CiContext;
+use anyhow::{Context, Result};
+use chrono::Duration;
+use gradient_entity::evaluation_message::MessageLevel;
+use gradient_types::*;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseBackend, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, Statement,
+};
+use serde_json::{Value as JsonValue, json};
+use std::collections::{BTreeMap, HashMap};
+use tracing::{debug, warn};
+
+/// Event name of a flushed digest delivery.
+pub(super) const DIGEST_EVENT: &str = "digest";
+
+/// How often the loop looks for closed windows.
+const DIGEST_POLL_SECS: u64 = 30;
+
+/// Caps keeping one digest readable (and its stored payload small) when a
+/// whole package set breaks at once.
+const MAX_FAILURES_PER_EVALUATION: usize = 20;
+const MAX_EVALUATION_ERRORS: u64 = 5;
+const MAX_ERROR_LINES: usize = 10;
+const MAX_ERROR_LINE_CHARS: usize = 400;
+
+/// Bytes read from the end of a failed build's log for its error lines.
+const MAX_LOG_TAIL_BYTES: usize = 64 * 1024;
+
+/// Claimed entries' `flush_at` is pushed this far out while their digest is
+/// sent, so a crash or failed send retries the window later instead of
+/// losing it.
+const CLAIM_LEASE_SECS: i64 = 300;
+
+/// The digest window of a `send_mail` action, or `None` when it mails every
+/// event on its own.
+pub(super) fn digest_window(action: &MProjectAction) -> Option<u32> {
+    match serde_json::from_value(action.config.clone()) {
+        Ok(ActionConfig::SendMail {
+            digest_window_secs, ..
+        }) => digest_window_secs,
+        _ => None,
+    }
+}
+
+/// Park `event` for the action's next digest. The first event of a window
+/// fixes its `flush_at`; later ones join it.
+pub(super) async fn buffer_event(
+    ctx: &CiContext,
+    action: &MProjectAction,
+    window_secs: u32,
+    event: &str,
+    payload: JsonValue,
+) -> Result<()> {
+    let now = gradient_types::now();
+    let flush_at = now + Duration::seconds(i64::from(window_secs));
+    ctx.db
+        .worker_db
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            "INSERT INTO project_action_digest_entry \
+                 (id, action_id, event, payload, flush_at, created_at) \
+             VALUES ($1, $2, $3, $4, \
+                 COALESCE((SELECT MIN(flush_at) FROM project_action_digest_entry \
+                           WHERE action_id = $2), $5), $6)",
+            [
+                sea_orm::Value::Uuid(Some(Box::new(
+                    ProjectActionDigestEntryId::now_v7().into_inner(),
+                ))),
+                sea_orm::Value::Uuid(Some(Box::new(action.id.into_inner()))),
+                event.into(),
+                payload.into(),
+                flush_at.into(),
+                now.into(),
+            ],
+        ))
+        .await
+        .context("buffering digest event")?;
+    Ok(())
+}
+
+pub fn start_digest_loop(ctx: CiContext) {
+    let shutdown = ctx.db.shutdown.clone();
+    shutdown.spawn(async move { digest_loop(ctx).await });
+}
+
+async fn digest_loop(ctx: CiContext) {
+    let period = std::time::Duration::from_secs(DIGEST_POLL_SECS);
+    let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+    let cancel = ctx.db.shutdown.token();
+    loop {
+        tokio::select! {
+            _ = cancel.cancelled() => return,
+            _ = interval.tick() => {}
+        }
+        if let Err(e) = flush_due_digests(&ctx).await {
+            warn!(error = %e, "action digest pass failed");
+        }
+    }
+}
+
+/// Claim every closed window and send one digest per action. Claiming
+/// leases the entries rather than deleting them; they are deleted once the
+/// digest was sent (or the action deactivated), and otherwise come due again
+/// when the lease runs out.
+async fn flush_due_digests(ctx: &CiContext) -> Result<()> {
+    let now = gradient_types::now();
+    let entries = EProjectActionDigestEntry::find()
+        .from_raw_sql(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            "UPDATE project_action_digest_entry SET flush_at = $1 \
+             WHERE flush_at <= $2 RETURNING *",
+            [
+                (now + Duration::seconds(CLAIM_LEASE_SECS)).into(),
+                now.into(),
+            ],
+        ))
+        .all(&ctx.db.worker_db)
+        .await
+        .context("claiming due digest entries")?;
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let mut by_action: HashMap<ProjectActionId, Vec<MProjectActionDigestEntry>> = HashMap::new();
+    for entry in entries {
+        by_action.entry(entry.action_id).or_default().push(entry);
+    }
+    let actions = EProjectAction::find()
+        .filter(CProjectAction::Id.is_in(by_action.keys().copied()))
+        .all(&ctx.db.worker_db)
+        .await
+        .context("loading digest actions")?;
+    debug!(count = actions.len(), "sending action digests");
+
+    for action in actions {
+        let Some(mut entries) = by_action.remove(&action.id) else {
+            continue;
+        };
+        let ids: Vec<ProjectActionDigestEntryId> = entries.iter().map(|e| e.id).collect();
+        // Deactivated mid-window: drop the buffered events like any other
+        // event the action would no longer receive.
+        if !action.active {
+            delete_entries(ctx, ids).await?;
+            continue;
+        }
+        entries.sort_by_key(|e| e.created_at);
+        let payload = build_digest(ctx, &entries).await;
+        let action_id = action.id;
+        match execute_action(ctx, action, DIGEST_EVENT, payload).await {
+            Ok(()) => delete_entries(ctx, ids).await?,
+            Err(e) => {
+                warn!(error = %e, %action_id, "Action digest delivery failed; retrying after the lease")
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn delete_entries(ctx: &CiContext, ids: Vec<ProjectActionDigestEntryId>) -> Result<()> {
+    EProjectActionDigestEntry::delete_many()
+        .filter(CProjectActionDigestEntry::Id.is_in(ids))
+        .exec(&ctx.db.worker_db)
+        .await
+        .context("deleting sent digest entries")?;
+    Ok(())
+}
+
+/// Summarise a drained window, grouped by evaluation in order of first event.
+async fn build_digest(ctx: &CiContext, entries: &[MProjectActionDigestEntry]) -> JsonValue {
+    let mut order: Vec<Option<EvaluationId>> = Vec::new();
+    let mut groups: HashMap<Option<EvaluationId>, Vec<&MProjectActionDigestEntry>> = HashMap::new();
+    for entry in entries {
+        let evaluation_id = payload_evaluation_id(ctx, &entry.payload)
+            .await
+            .unwrap_or_else(|e| {
+                warn!(error = %e, "Failed to resolve digest entry evaluation");
+                None
+            });
+        if !groups.contains_key(&evaluation_id) {
+            order.push(evaluation_id);
+        }
+        groups.entry(evaluation_id).or_default().push(entry);
+    }
+
+    let mut evaluations = Vec::with_capacity(order.len());
+    for evaluation_id in order {
+        let group = &groups[&evaluation_id];
+        match summarise_evaluation(ctx, evaluation_id, group).await {
+            Ok(summary) => evaluations.push(summary),
+            Err(e) => warn!(error = %e, ?evaluation_id, "Failed to summarise digest evaluation"),
+        }
+    }
+
+    let first = entries.first().map(|e| e.created_at);
+    let last = entries.last().map(|e| e.created_at);
+    let pick = |k: &str| {
+        evaluations
+            .iter()
+            .find_map(|e| e.get(k).and_then(|v| v.as_str()).filter(|s| !s.is_empty()))
+            .unwrap_or("")
+            .to_owned()
+    };
+    json!({
+        "digest": true,
+        "org": pick("org"),
+        "project": pick("project"),
+        "status": "digest",
+        "event_count": entries.len(),
+        "first_event_at": first,
+        "last_event_at": last,
+        "evaluations": evaluations,
+    })
+}
+
+async fn summarise_evaluation(
+    ctx: &CiContext,
+    evaluation_id: Option<EvaluationId>,
+    entries: &[&MProjectActionDigestEntry],
+) -> Result<JsonValue> {
+    let mut events: BTreeMap<&str, usize> = BTreeMap::new();
+    for entry in entries {
+        *events.entry(entry.event.as_str()).or_default() += 1;
+    }
+    let fields = message_fields(ctx, &entries[0].payload).await?;
+
+    let mut failures = Vec::new();
+    let mut failed_builds = 0usize;
+    for entry in entries.iter().filter(|e| e.event == "build.failed") {
+        failed_builds += 1;
+        if failures.len() >= MAX_FAILURES_PER_EVALUATION {
+            continue;
+        }
+        match build_failure(ctx, &entry.payload).await {
+            Ok(Some(f)) => failures.push(f),
+            Ok(None) => {}
+            Err(e) => warn!(error = %e, "Failed to load digest build failure"),
+        }
+    }
+
+    let errors = match evaluation_id {
+        Some(id) if events.contains_key("evaluation.failed") => {
+            evaluation_errors(ctx, id).await.unwrap_or_else(|e| {
+                warn!(error = %e, evaluation_id = %id, "Failed to load evaluation errors");
+                Vec::new()
+            })
+        }
+        _ => Vec::new(),
+    };
+
+    Ok(json!({
+        "evaluation_id": evaluation_id,
+        "org": fields.org,
+        "project": fields.project,
+        "status": fields.status,
+        "link": fields.link,
+        "events": events,
+        "failed_builds": failed_builds,
+        "failures": failures,
+        "errors": errors,
+    }))
+}
+
+/// Attribute, derivation and first error lines of one `build.failed` event.
+async fn build_failure(ctx: &CiContext, payload: &JsonValue) -> Result<Option<JsonValue>> {
+    let Some(build_id) = payload
+        .get("build_id")
+        .and_then(|v| v.as_str())
+        .and_then(|s| s.parse::<BuildJobId>().ok())
+    else {
+        return Ok(None);
+    };
+    let Some(job) = EBuildJob::find_by_id(build_id)
+        .one(&ctx.db.worker_db)
+        .await
+        .context("loading build_job")?
+    else {
+        return Ok(None);
+    };
+
+    let attr = EEntryPoint::find()
+        .filter(CEntryPoint::Evaluation.eq(job.evaluation))
+        .filter(CEntryPoint::Derivation.eq(job.derivation))
+        .one(&ctx.db.worker_db)
+        .await
+        .context("loading entry point")?
+        .map(|ep| ep.eval);
+    let derivation_path = payload
+        .get("derivation_path")
+        .and_then(|v| v.as_str())
+        .map(str::to_owned);
+
+    let attempt = gradient_db::latest_attempt(&ctx.db.worker_db, job.derivation_build)
+        .await
+        .context("loading latest build attempt")?;
+    let mut error_lines = match &attempt {
+        Some(a) => {
+            let log = ctx
+                .db
+                .storage
+                .log_storage
+                .read_tail(a.id, MAX_LOG_TAIL_BYTES)
+                .await
+                .unwrap_or_default();
+            first_error_lines(&log, MAX_ERROR_LINES)
+        }
+        None => Vec::new(),
+    };
+    if error_lines.is_empty()
+        && let Some(msg) = attempt.and_then(|a| a.failure_message)
+    {
+        error_lines = first_error_lines(&msg, MAX_ERROR_LINES);
+    }
+
+    Ok(Some(json!({
+        "build_id": build_id,
+        "attr": attr,
+        "derivation_path": derivation_path,
+        "error_lines": error_lines,
+    })))
+}
+
+async fn evaluation_errors(ctx: &CiContext, evaluation_id: EvaluationId) -> Result<Vec<String>> {
+    Ok(EEvaluationMessage::find()
+        .filter(CEvaluationMessage::Evaluation.eq(evaluation_id))
+        .filter(CEvaluationMessage::Level.eq(MessageLevel::Error))
+        .order_by_asc(CEvaluationMessage::CreatedAt)
+        .limit(MAX_EVALUATION_ERRORS)
+        .all(&ctx.db.worker_db)
+        .await
+        .context("loading evaluation messages")?
+        .into_iter()
+        .map(|m| first_error_lines(&m.message, MAX_ERROR_LINES).join("\n"))
+        .collect())
+}
+
+/// The first `max` lines of a build log starting at its first line that
+/// mentions an error, or its last `max` lines when none does. Each line is
+/// cut to [`MAX_ERROR_LINE_CHARS`].
+pub(super) fn first_error_lines(log: &str, max: usize) -> Vec<String> {
+    let lines: Vec<&str> = log.lines().filter(|l| !l.trim().is_empty()).collect();
+    let start = lines
+        .iter()
+        .position(|l| l.to_ascii_lowercase().contains("error"))
+        .unwrap_or_else(|| lines.len().saturating_sub(max));
+    lines[start..]
+        .iter()
+        .take(max)
+        .map(|l| match l.char_indices().nth(MAX_ERROR_LINE_CHARS) {
+            Some((end, _)) => format!("{}...", &l[..end]),
+            None => (*l).to_owned(),
+        })
+        .collect()
+}
+
+/// Default subject of a digest mail.
+pub(super) fn digest_subject(payload: &JsonValue) -> String {
+    let evaluations = payload
+        .get("evaluations")
+        .and_then(|v| v.as_array())
+        .map_or(0, Vec::len);
+    format!(
+        "[Gradient] {}/{}: {} event(s) in {} evaluation(s)",
+        payload.get("org").and_then(|v| v.as_str()).unwrap_or(""),
+        payload
+            .get("project")
+            .and_then(|v| v.as_str())
+            .unwrap_or(""),
+        payload
+            .get("event_count")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0),
+        evaluations,
+    )
+}
+
+/// Plain-text body of a digest mail.
+pub(super) fn render_digest_body(payload: &JsonValue) -> String {
+    let s = |v: &JsonValue, k: &str| v.get(k).and_then(|v| v.as_str()).unwrap_or("").to_owned();
+    let mut out = format!(
+        "{} event(s) for {}/{} between {} and {}.\n",
+        payload
+            .get("event_count")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0),
+        s(payload, "org"),
+        s(payload, "project"),
+        s(payload, "first_event_at"),
+        s(payload, "last_event_at"),
+    );
+
+    let empty = Vec::new();
+    for eval in payload
+        .get("evaluations")
+        .and_then(|v| v.as_array())
+        .unwrap_or(&empty)
+    {
+        out.push_str(&format!(
+            "\nEvaluation {} ({})\n",
+            s(eval, "evaluation_id"),
+            s(eval, "status"),
+        ));
+        if let Some(events) = eval.get("events").and_then(|v| v.as_object()) {
+            let counts: Vec<String> = events
+                .iter()
+                .map(|(event, n)| format!("{} x{}", event, n))
+                .collect();
+            out.push_str(&format!("Events: {}\n", counts.join(", ")));
+        }
+        let link = s(eval, "link");
+        if !link.is_empty() {
+            out.push_str(&format!("Link: {}\n", link));
+        }
+
+        for error in eval
+            .get("errors")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty)
+        {
+            out.push_str("\n  Evaluation error:\n");
+            for line in error.as_str().unwrap_or("").lines() {
+                out.push_str(&format!("    {}\n", line));
+            }
+        }
+
+        let failures = eval
+            .get("failures")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty);
+        for failure in failures {
+            let attr = s(failure, "attr");
+            let name = if attr.is_empty() {
+                s(failure, "derivation_path")
+            } else {
+                attr
+            };
+            out.push_str(&format!("\n  Failed: {}\n", name));
+            for line in failure
+                .get("error_lines")
+                .and_then(|v| v.as_array())
+                .unwrap_or(&empty)
+            {
+                out.push_str(&format!("    {}\n", line.as_str().unwrap_or("")));
+            }
+        }
+        let failed = eval
+            .get("failed_builds")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+        let hidden = failed.saturating_sub(failures.len() as u64);
+        if hidden > 0 {
+            out.push_str(&format!("\n  ... and {} more failed build(s)\n", hidden));
+        }
+    }
+    out
+}
@@ -4,55 +4,156 @@
  * SPDX-License-Identifier: AGPL-3.0-only
  */
 
+use super::digest::{DIGEST_EVENT, digest_subject, render_digest_body};
+use super::message::{message_fields, render_message};
+use super::payload::{render_default_body, render_subject};
+use super::retry::schedule_after;
 use super::send::{
-    execute_forge_status_report, execute_open_pr, execute_send_mail, execute_send_web_request,
+    WebRequestTarget, execute_forge_status_report, execute_open_pr, execute_send_mail,
+    execute_send_matrix, execute_send_slack, execute_send_web_request, render_web_request_body,
 };
-use super::{MAX_BODY_BYTES, truncate};
+use super::{ExecutorOk, MAX_BODY_BYTES, truncate};
 use crate::context::CiContext;
 use anyhow::{Context, Result};
+use gradient_entity::project_action_delivery::DeliveryState;
+use gradient_forge::reporter::ForgeHttpError;
 use gradient_types::{
-    ActionConfig, MProjectAction, MProjectActionDelivery, ProjectActionDeliveryId,
+    ActionConfig, MProjectAction, MProjectActionDelivery, ProjectActionDeliveryId, ProjectActionId,
 };
 use sea_orm::{ActiveModelTrait, ConnectionTrait, DatabaseBackend, IntoActiveModel, Statement};
 use serde_json::Value as JsonValue;
 use std::time::Instant;
 use tracing::warn;
 
-pub async fn execute_action(
+/// Result of one delivery attempt, flattened into the columns written onto the
+/// `project_action_delivery` row.
+pub(super) struct AttemptOutcome {
+    pub(super) success: bool,
+    /// A network error or a 408/429/5xx answer: the receiver may accept the
+    /// same payload later, so the attempt is worth retrying. Any other 4xx,
+    /// including one a forge reporter surfaces as an error, is permanent.
+    pub(super) transient: bool,
+    pub(super) response_status: Option<i32>,
+    pub(super) response_body: Option<String>,
+    pub(super) error_message: Option<String>,
+    pub(super) duration_ms: i32,
+}
+
+impl AttemptOutcome {
+    pub(super) fn from_result(result: &Result<ExecutorOk>, started: Instant) -> Self {
+        let duration_ms = i32::try_from(started.elapsed().as_millis()).unwrap_or(i32::MAX);
+        match result {
+            Ok(ok) => AttemptOutcome {
+                success: ok
+                    .status_code
+                    .map(|c| (200..300).contains(&c))
+                    .unwrap_or(true),
+                transient: ok.status_code.is_some_and(is_transient_status),
+                response_status: ok.status_code,
+                response_body: ok
+                    .response_body
+                    .clone()
+                    .map(|s| truncate(s, MAX_BODY_BYTES)),
+                error_message: None,
+                duration_ms,
+            },
+            Err(e) => {
+                let status = ForgeHttpError::status_of(e).map(|s| i32::from(s.as_u16()));
+                AttemptOutcome {
+                    success: false,
+                    transient: status.is_none_or(is_transient_status),
+                    response_status: status,
+                    response_body: None,
+                    error_message: Some(format!("{:#}", e)),
+                    duration_ms,
+                }
+            }
+        }
+    }
+}
+
+fn is_transient_status(code: i32) -> bool {
+    code == 408 || code == 429 || (500..600).contains(&code)
+}
+
+/// Run one delivery of `event` through the executor matching the action's
+/// config, without recording anything.
+pub(super) async fn run_executor(
     ctx: &CiContext,
-    action: MProjectAction,
+    action: &MProjectAction,
+    delivery_id: ProjectActionDeliveryId,
     event: &str,
-    payload: JsonValue,
-) -> Result<()> {
+    payload: &JsonValue,
+) -> Result<ExecutorOk> {
     let cfg: ActionConfig =
         serde_json::from_value(action.config.clone()).context("decoding action config")?;
-    let action_id_for_pr = action.id;
-    let project_for_pr = action.project;
-    let started = Instant::now();
-    let request_body = truncate(
-        serde_json::to_string(&payload).unwrap_or_default(),
-        MAX_BODY_BYTES,
-    );
 
-    let result = match cfg {
+    match cfg {
         ActionConfig::SendMail {
             recipients,
             subject_template,
+            ..
+        } => {
+            let (subject, body) = if event == DIGEST_EVENT {
+                let subject = match subject_template.as_deref() {
+                    Some(t) => render_subject(Some(t), event, payload),
+                    None => digest_subject(payload),
+                };
+                (subject, render_digest_body(payload))
+            } else {
+                (
+                    render_subject(subject_template.as_deref(), event, payload),
+                    render_default_body(event, payload),
+                )
+            };
+            execute_send_mail(ctx, &recipients, &subject, &body).await
+        }
+        ActionConfig::SendWebRequest {
+            url,
+            token,
+            signing_secret,
+            method,
+            headers,
+            body_template,
         } => {
-            execute_send_mail(
+            let body = render_web_request_body(event, payload, body_template.as_deref())?;
+            let target = WebRequestTarget {
+                url: &url,
+                method,
+                headers: &headers,
+                token: token.as_deref(),
+                signing_secret: signing_secret.as_deref(),
+            };
+            execute_send_web_request(ctx, delivery_id, event, &body, target).await
+        }
+        ActionConfig::ForgeStatusReport { integration_id } => {
+            execute_forge_status_report(ctx, event, payload, integration_id).await
+        }
+        ActionConfig::SendMatrix {
+            homeserver,
+            room_id,
+            access_token,
+            message_template,
+        } => {
+            let fields = message_fields(ctx, payload).await?;
+            let text = render_message(message_template.as_deref(), event, &fields);
+            execute_send_matrix(
                 ctx,
-                event,
-                &payload,
-                &recipients,
-                subject_template.as_deref(),
+                delivery_id,
+                &homeserver,
+                &room_id,
+                access_token.as_deref(),
+                &text,
             )
             .await
         }
-        ActionConfig::SendWebRequest { url, token } => {
-            execute_send_web_request(ctx, event, &payload, &url, token.as_deref()).await
-        }
-        ActionConfig::ForgeStatusReport { integration_id } => {
-            execute_forge_status_report(ctx, event, &payload, integration_id).await
+        ActionConfig::SendSlack {
+            url,
+            message_template,
+        } => {
+            let fields = message_fields(ctx, payload).await?;
+            let text = render_message(message_template.as_deref(), event, &fields);
+            execute_send_slack(ctx, &url, &text).await
         }
         ActionConfig::OpenPr {
             integration_id,
@@ -65,9 +166,9 @@ pub async fn execute_action(
             execute_open_pr(
                 ctx,
                 event,
-                &payload,
-                action_id_for_pr,
-                project_for_pr,
+                payload,
+                action.id,
+                action.project,
                 integration_id,
                 &branch_pattern,
                 title_template.as_deref(),
@@ -76,33 +177,65 @@ pub async fn execute_action(
             )
             .await
         }
-    };
+    }
+}
+
+/// Run one attempt and fold its result into an [`AttemptOutcome`], passing
+/// the executor error through for the caller to surface.
+pub(super) async fn attempt_delivery(
+    ctx: &CiContext,
+    action: &MProjectAction,
+    delivery_id: ProjectActionDeliveryId,
+    event: &str,
+    payload: &JsonValue,
+) -> (AttemptOutcome, Result<()>) {
+    let started = Instant::now();
+    let result = run_executor(ctx, action, delivery_id, event, payload).await;
+    let outcome = AttemptOutcome::from_result(&result, started);
+    if outcome.success {
+        touch_last_fired(ctx, action.id).await;
+    }
+
+    (outcome, result.map(|_| ()))
+}
+
+pub async fn execute_action(
+    ctx: &CiContext,
+    action: MProjectAction,
+    event: &str,
+    payload: JsonValue,
+) -> Result<()> {
+    let request_body = truncate(
+        serde_json::to_string(&payload).unwrap_or_default(),
+        MAX_BODY_BYTES,
+    );
+    // Allocated up front so webhook receivers see the same delivery id on
+    // every retry of this event.
+    let delivery_id = ProjectActionDeliveryId::now_v7();
+    let (outcome, result) = attempt_delivery(ctx, &action, delivery_id, event, &payload).await;
 
-    let duration_ms = i32::try_from(started.elapsed().as_millis()).unwrap_or(i32::MAX);
-    let success = match &result {
-        Ok(ok) => ok
-            .status_code
-            .map(|c| (200..300).contains(&c))
-            .unwrap_or(true),
-        Err(_) => false,
-    };
-    let (response_status, response_body, error_message) = match &result {
-        Ok(ok) => (ok.status_code, ok.response_body.clone(), None),
-        Err(e) => (None, None, Some(format!("{:#}", e))),
-    };
+    let now = gradient_types::now();
+    // A failed digest is retried from its buffered entries, not replayed.
+    let one_shot = is_synthetic(&payload) || event == DIGEST_EVENT;
+    let (state, next_retry_at) = schedule_after(action.action_type, one_shot, 1, &outcome, now);
+    let keep_payload = matches!(state, DeliveryState::Retrying | DeliveryState::Dead);
 
     let action_id = action.id;
     let delivery = MProjectActionDelivery {
-        id: ProjectActionDeliveryId::now_v7(),
+        id: delivery_id,
         action_id,
         event: event.to_string(),
         request_body,
-        response_status,
-        response_body: response_body.map(|s| truncate(s, MAX_BODY_BYTES)),
-        error_message,
-        success,
-        duration_ms,
-        delivered_at: gradient_types::now(),
+        response_status: outcome.response_status,
+        response_body: outcome.response_body,
+        error_message: outcome.error_message,
+        success: outcome.success,
+        duration_ms: outcome.duration_ms,
+        delivered_at: now,
+        attempt: 1,
+        state,
+        next_retry_at,
+        payload: keep_payload.then_some(payload),
     }
     .into_active_model();
 
@@ -110,24 +243,30 @@ pub async fn execute_action(
         warn!(error = %e, %action_id, "Failed to record action delivery");
     }
 
-    if success {
-        // Bookkeeping only: a concurrent burst of firings all writes an
-        // equivalent timestamp to this one row, so skip when another writer
-        // holds the lock instead of convoying pool connections behind it.
-        let stamp = gradient_types::now();
-        let update = Statement::from_sql_and_values(
-            DatabaseBackend::Postgres,
-            "UPDATE project_action SET last_fired_at = $1, updated_at = $1 \
-             WHERE id IN (SELECT id FROM project_action WHERE id = $2 FOR UPDATE SKIP LOCKED)",
-            [
-                stamp.into(),
-                sea_orm::Value::Uuid(Some(Box::new(action_id.into_inner()))),
-            ],
-        );
-        if let Err(e) = ctx.db.worker_db.execute(update).await {
-            warn!(error = %e, %action_id, "Failed to update action last_fired_at");
-        }
-    }
+    result
+}
 
-    result.map(|_| ())
+/// Test fires from the API carry `"synthetic": true`; they are recorded but
+/// never enter the retry schedule.
+pub(super) fn is_synthetic(payload: &JsonValue) -> bool {
+    payload.get("synthetic").and_then(|v| v.as_bool()) == Some(true)
+}
+
+async fn touch_last_fired(ctx: &CiContext, action_id: ProjectActionId) {
+    // Bookkeeping only: a concurrent burst of firings all writes an
+    // equivalent timestamp to this one row, so skip when another writer
+    // holds the lock instead of convoying pool connections behind it.
+    let stamp = gradient_types::now();
+    let update = Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        "UPDATE project_action SET last_fired_at = $1, updated_at = $1 \
+         WHERE id IN (SELECT id FROM project_action WHERE id = $2 FOR UPDATE SKIP LOCKED)",
+        [
+            stamp.into(),
+            sea_orm::Value::Uuid(Some(Box::new(action_id.into_inner()))),
+        ],
+    );
+    if let Err(e) = ctx.db.worker_db.execute(update).await {
+        warn!(error = %e, %action_id, "Failed to update action last_fired_at");
+    }
 }
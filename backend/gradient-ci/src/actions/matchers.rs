@@ -14,6 +14,7 @@ pub const FORGE_STATUS_EVENTS: &[&str] = &[
     "build.completed",
     "build.failed",
     "build.substituted",
+    "aggregate.updated",
     "evaluation.queued",
     "evaluation.started",
     "evaluation.building",
@@ -44,7 +45,9 @@ pub fn matches_event(action: &MProjectAction, event: &str) -> bool {
 /// no fresh build, so no `build.completed` ever fires, yet the eval still reaches
 /// `Building`/`Completed`. `Build` waits for `evaluation.completed` (every build
 /// succeeded, else the eval is `Failed` and emits nothing); `Eval`/`None` open at
-/// `evaluation.building` (the flake evaluated, builds not awaited).
+/// `evaluation.building` (the flake evaluated, builds not awaited). An
+/// evaluation with aggregate jobs can also pass the `Build` gate on
+/// `evaluation.failed`, see [`open_pr_aggregate_gate`].
 pub fn open_pr_gate_events(action: &MProjectAction) -> Option<&'static [&'static str]> {
     const BUILD_GATE: &[&str] = &["evaluation.completed"];
     const EVAL_GATE: &[&str] = &["evaluation.building"];
@@ -60,6 +63,24 @@ pub fn open_pr_gate_events(action: &MProjectAction) -> Option<&'static [&'static
     })
 }
 
+/// Whether an `OpenPr` action on the `Build` gate should still fire on
+/// `evaluation.failed` once the evaluation's aggregates are green: aggregates
+/// are the release gate, so a failed job outside them does not hold the
+/// update back. The dispatcher checks the aggregates themselves.
+pub fn open_pr_aggregate_gate(action: &MProjectAction, event: &str) -> bool {
+    if action.action_type != ActionType::OpenPr || event != "evaluation.failed" {
+        return false;
+    }
+
+    matches!(
+        serde_json::from_value(action.config.clone()),
+        Ok(ActionConfig::OpenPr {
+            verify_gate: VerifyGate::Build,
+            ..
+        })
+    )
+}
+
 pub fn forge_status_for_event(event: &str) -> Option<CiStatus> {
     match event {
         "build.created" => Some(CiStatus::Pending),
@@ -68,6 +89,9 @@ pub fn forge_status_for_event(event: &str) -> Option<CiStatus> {
         "build.completed" => Some(CiStatus::Success),
         "build.failed" => Some(CiStatus::Failure),
         "build.substituted" => Some(CiStatus::Success),
+        // Placeholder: the report replaces it with the aggregate's combined
+        // status, which the event name alone cannot carry.
+        "aggregate.updated" => Some(CiStatus::Pending),
         "evaluation.queued" => Some(CiStatus::Pending),
         "evaluation.started" => Some(CiStatus::Running),
         // The evaluation phase is done the moment builds start; the Evaluation
@@ -0,0 +1,172 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Human-readable chat messages for `send_matrix` / `send_slack`. The event
+//! payloads only carry ids, so the fields a message template can reference
+//! are resolved from the evaluation at send time.
+
+use super::executor::is_synthetic;
+use crate::context::CiContext;
+use anyhow::{Context, Result, anyhow};
+use gradient_entity::evaluation::EvaluationStatus;
+use gradient_types::{BuildJobId, EBuildJob, EEvaluation, EOrganization, EProject, EvaluationId};
+use sea_orm::EntityTrait;
+use serde_json::Value as JsonValue;
+
+pub(super) const DEFAULT_MESSAGE_TEMPLATE: &str =
+    "[{org}/{project}] {event}: evaluation {status}, {failed_builds} failed build(s) - {link}";
+
+/// Values substituted into a message template.
+#[derive(Debug, Default, PartialEq)]
+pub(super) struct MessageFields {
+    pub(super) org: String,
+    pub(super) project: String,
+    pub(super) status: String,
+    pub(super) failed_builds: i64,
+    pub(super) link: String,
+}
+
+impl MessageFields {
+    /// Fields straight from the payload, for test fires and payloads that do
+    /// not reference an evaluation.
+    fn from_payload(payload: &JsonValue) -> Self {
+        let get = |k: &str| {
+            payload
+                .get(k)
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .to_string()
+        };
+        MessageFields {
+            org: get("org"),
+            project: get("project"),
+            status: get("status"),
+            failed_builds: payload
+                .get("failed_builds")
+                .and_then(|v| v.as_i64())
+                .unwrap_or(0),
+            link: get("link"),
+        }
+    }
+}
+
+/// Placeholders: `{event}`, `{org}`, `{project}`, `{status}`,
+/// `{failed_builds}`, `{link}`.
+pub(super) fn render_message(
+    template: Option<&str>,
+    event: &str,
+    fields: &MessageFields,
+) -> String {
+    template
+        .unwrap_or(DEFAULT_MESSAGE_TEMPLATE)
+        .replace("{event}", event)
+        .replace("{org}", &fields.org)
+        .replace("{project}", &fields.project)
+        .replace("{status}", &fields.status)
+        .replace("{failed_builds}", &fields.failed_builds.to_string())
+        .replace("{link}", &fields.link)
+}
+
+/// The evaluation an event payload belongs to: its `evaluation_id`, or the
+/// evaluation of its `build_id`. `None` for payloads carrying neither.
+pub(super) async fn payload_evaluation_id(
+    ctx: &CiContext,
+    payload: &JsonValue,
+) -> Result<Option<EvaluationId>> {
+    let s = |k: &str| payload.get(k).and_then(|v| v.as_str());
+
+    if let Some(eid) = s("evaluation_id") {
+        return eid
+            .parse()
+            .map(Some)
+            .map_err(|_| anyhow!("invalid evaluation_id"));
+    }
+    let Some(bid) = s("build_id") else {
+        return Ok(None);
+    };
+    let build_job_id: BuildJobId = bid.parse().map_err(|_| anyhow!("invalid build_id"))?;
+    Ok(Some(
+        EBuildJob::find_by_id(build_job_id)
+            .one(&ctx.db.worker_db)
+            .await
+            .context("loading build_job")?
+            .ok_or_else(|| anyhow!("build_job {} not found", build_job_id))?
+            .evaluation,
+    ))
+}
+
+/// Resolve the template fields for an event payload. Build events are mapped
+/// to their evaluation so every message reports the evaluation's state; a
+/// test fire's ids name no real evaluation, so it carries the fields itself.
+pub(super) async fn message_fields(ctx: &CiContext, payload: &JsonValue) -> Result<MessageFields> {
+    if is_synthetic(payload) {
+        return Ok(MessageFields::from_payload(payload));
+    }
+    let Some(evaluation_id) = payload_evaluation_id(ctx, payload).await? else {
+        return Ok(MessageFields::from_payload(payload));
+    };
+
+    let evaluation = EEvaluation::find_by_id(evaluation_id)
+        .one(&ctx.db.worker_db)
+        .await
+        .context("loading evaluation")?
+        .ok_or_else(|| anyhow!("evaluation {} not found", evaluation_id))?;
+    let project_id = evaluation
+        .project
+        .ok_or_else(|| anyhow!("evaluation has no project (direct build)"))?;
+    let project = EProject::find_by_id(project_id)
+        .one(&ctx.db.worker_db)
+        .await
+        .context("loading project")?
+        .ok_or_else(|| anyhow!("project {} not found", project_id))?;
+    let org = EOrganization::find_by_id(project.organization)
+        .one(&ctx.db.worker_db)
+        .await
+        .context("loading organization")?
+        .map(|o| o.name)
+        .unwrap_or_default();
+
+    let counts = gradient_db::project_board::build_status_counts_by_evaluation(
+        &ctx.db.worker_db,
+        &[evaluation_id],
+    )
+    .await
+    .context("counting failed builds")?;
+    let failed_builds = counts
+        .get(&evaluation_id)
+        .map(|by_status| {
+            by_status
+                .iter()
+                .filter(|(status, _)| status.is_terminal_failure())
+                .map(|(_, n)| n)
+                .sum()
+        })
+        .unwrap_or(0);
+
+    Ok(MessageFields {
+        link: format!(
+            "{}/organization/{}/log/{}",
+            ctx.db.config.server.frontend_url, org, evaluation.id
+        ),
+        org,
+        project: project.name,
+        status: evaluation_status_label(evaluation.status).to_string(),
+        failed_builds,
+    })
+}
+
+fn evaluation_status_label(status: EvaluationStatus) -> &'static str {
+    match status {
+        EvaluationStatus::Queued => "queued",
+        EvaluationStatus::Fetching => "fetching",
+        EvaluationStatus::EvaluatingFlake | EvaluationStatus::EvaluatingDerivation => "evaluating",
+        EvaluationStatus::Building => "building",
+        EvaluationStatus::Waiting => "waiting",
+        EvaluationStatus::Completed => "completed",
+        EvaluationStatus::Failed => "failed",
+        EvaluationStatus::Aborted => "aborted",
+    }
+}
@@ -5,18 +5,25 @@
  */
 
 //! Project Actions dispatch and execution. This module fans build/evaluation
-//! events out to the configured actions ([`dispatch_event`]); the execution and
-//! per-config executors live in [`executor`] and [`send`].
+//! events out to the configured actions ([`dispatch_event`]) and to users'
+//! own notification subscriptions ([`subscriptions`]); the execution and
+//! per-config executors live in [`executor`] and [`send`], failed
+//! deliveries are replayed on a backoff schedule by [`retry`], and
+//! `send_mail` actions in digest mode are batched by [`digest`].
 
 mod crypto;
+mod digest;
 mod executor;
 mod matchers;
+mod message;
 mod payload;
 mod report;
+mod retry;
 mod send;
+mod subscriptions;
 
 use crate::context::CiContext;
-use gradient_types::{ActionType, CProjectAction, EProjectAction, ProjectId};
+use gradient_types::{ActionType, CProjectAction, EProjectAction, EvaluationId, ProjectId};
 use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
 use serde_json::Value as JsonValue;
 use tracing::{error, warn};
@@ -25,10 +32,14 @@ pub use crypto::{
     decrypt_action_secret, decrypt_secret_with_file, encrypt_action_secret,
     encrypt_secret_with_file,
 };
+pub use digest::start_digest_loop;
 pub use executor::execute_action;
-pub use matchers::{FORGE_STATUS_EVENTS, forge_status_for_event, matches_event};
+pub use matchers::{
+    FORGE_STATUS_EVENTS, forge_status_for_event, matches_event, open_pr_aggregate_gate,
+};
 pub use payload::forge_status_payload;
-pub use send::{reporter_for_project, verify_forge_action};
+pub use retry::{MAX_DELIVERY_ATTEMPTS, redeliver, start_delivery_retry_loop};
+pub use send::{render_web_request_body, reporter_for_project, verify_forge_action};
 
 pub const MAX_BODY_BYTES: usize = 64 * 1024;
 
@@ -103,6 +114,10 @@ pub async fn dispatch_evaluation_created(ctx: &CiContext, eval: &gradient_types:
             "evaluation.queued",
             Some("Waiting for cache storage to free up before this evaluation can run."),
         ),
+        (EvaluationStatus::Waiting, Some(WaitingReason::Quota { .. })) => (
+            "evaluation.queued",
+            Some("Waiting for the organization to get back under its build quota."),
+        ),
         (
             EvaluationStatus::Waiting,
             Some(WaitingReason::Workers {
@@ -139,6 +154,16 @@ pub async fn dispatch_build_event(
 }
 
 async fn dispatch_event(ctx: &CiContext, project_id: ProjectId, event: &str, payload: JsonValue) {
+    {
+        let ctx = ctx.clone();
+        let payload = payload.clone();
+        let event = event.to_string();
+        let shutdown = ctx.db.shutdown.clone();
+        shutdown.spawn(async move {
+            subscriptions::notify_subscribers(&ctx, project_id, &event, &payload).await;
+        });
+    }
+
     let actions = match EProjectAction::find()
         .filter(CProjectAction::Project.eq(project_id))
         .filter(CProjectAction::Active.eq(true))
@@ -152,12 +177,22 @@ async fn dispatch_event(ctx: &CiContext, project_id: ProjectId, event: &str, pay
         }
     };
 
+    let is_input_update =
+        payload.get("evaluation_kind").and_then(|v| v.as_str()) == Some("input_update");
+    let mut aggregates_green = None;
+
     for action in actions {
         if !matches_event(&action, event) {
-            continue;
+            if !(is_input_update && open_pr_aggregate_gate(&action, event)) {
+                continue;
+            }
+            if aggregates_green.is_none() {
+                aggregates_green = Some(aggregates_green_for(ctx, &payload).await);
+            }
+            if aggregates_green != Some(true) {
+                continue;
+            }
         }
-        let is_input_update =
-            payload.get("evaluation_kind").and_then(|v| v.as_str()) == Some("input_update");
 
         // `OpenPr` fires on a normal gate event (build/eval completed) but must
         // only act on `input_update` evaluations, never regular CI runs.
@@ -174,13 +209,20 @@ async fn dispatch_event(ctx: &CiContext, project_id: ProjectId, event: &str, pay
         let ctx = ctx.clone();
         let payload = payload.clone();
         let event = event.to_string();
-        tokio::spawn(async move {
+        let shutdown = ctx.db.shutdown.clone();
+        shutdown.spawn(async move {
             // A mass status-transition wave (promotion, thaw, requeue) fires
             // one event per anchor; unbounded execution exhausted the DB pool
             // and convoyed on the per-action bookkeeping row.
             let Ok(_permit) = std::sync::Arc::clone(&ACTION_PERMITS).acquire_owned().await else {
                 return;
             };
+            if let Some(window) = digest::digest_window(&action) {
+                if let Err(e) = digest::buffer_event(&ctx, &action, window, &event, payload).await {
+                    warn!(error = %e, action_id = %action.id, "Failed to buffer digest event");
+                }
+                return;
+            }
             if let Err(e) = execute_action(&ctx, action, &event, payload).await {
                 warn!(error = %e, "Action execution failed");
             }
@@ -188,6 +230,26 @@ async fn dispatch_event(ctx: &CiContext, project_id: ProjectId, event: &str, pay
     }
 }
 
+/// Whether the evaluation named in `payload` has aggregate jobs, all of them
+/// succeeded, and it recorded no evaluation errors.
+async fn aggregates_green_for(ctx: &CiContext, payload: &JsonValue) -> bool {
+    let Some(evaluation_id) = payload
+        .get("evaluation_id")
+        .and_then(|v| v.as_str())
+        .and_then(|s| s.parse::<EvaluationId>().ok())
+    else {
+        return false;
+    };
+
+    match gradient_db::release_gate_green(&ctx.db.worker_db, evaluation_id).await {
+        Ok(green) => green,
+        Err(e) => {
+            warn!(error = %e, %evaluation_id, "Failed to load aggregate statuses");
+            false
+        }
+    }
+}
+
 /// Process-wide bound on concurrently executing project actions.
 static ACTION_PERMITS: std::sync::LazyLock<std::sync::Arc<tokio::sync::Semaphore>> =
     std::sync::LazyLock::new(|| std::sync::Arc::new(tokio::sync::Semaphore::new(8)));
@@ -74,7 +74,7 @@ pub(super) async fn build_ci_report_from_payload(
     ctx: &CiContext,
     event: &str,
     payload: &JsonValue,
-    status: CiStatus,
+    mut status: CiStatus,
 ) -> Result<Option<CiReport>> {
     let s = |k: &str| payload.get(k).and_then(|v| v.as_str()).map(String::from);
 
@@ -171,6 +171,19 @@ pub(super) async fn build_ci_report_from_payload(
     // one check per derivation would spam the PR with per-dependency noise.
     let entry_point_eval = entry_points.first().map(|ep| ep.eval.clone());
 
+    // An aggregate's check reports its combined status, whichever of its own
+    // build or its constituents fired the event.
+    if let Some(aggregate) = entry_points.iter().find(|ep| ep.aggregate) {
+        let combined = gradient_db::aggregate_statuses(&ctx.db.worker_db, evaluation.id)
+            .await
+            .context("loading aggregate status")?
+            .into_iter()
+            .find(|a| a.entry_point.id == aggregate.id);
+        if let Some(combined) = combined {
+            status = reporting::ci_status_for_aggregate(combined.status);
+        }
+    }
+
     let org_name = EOrganization::find_by_id(project.organization)
         .one(&ctx.db.worker_db)
         .await
@@ -0,0 +1,324 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Persisted retry schedule for action deliveries. A failed notification
+//! delivery (every action type except `open_pr`) is parked as `Retrying` with
+//! an exponentially backed-off `next_retry_at`; the retry loop replays the
+//! stored payload until it succeeds or the attempt budget runs out, at which
+//! point the row is `Dead` and only a manual redelivery ([`redeliver`])
+//! touches it again. A forge status retry is dropped instead once a newer
+//! status for the same commit and check has been sent.
+
+use super::executor::{AttemptOutcome, attempt_delivery};
+use super::matchers::forge_status_for_event;
+use super::report::build_ci_report_from_payload;
+use crate::context::CiContext;
+use anyhow::{Context, Result, anyhow};
+use chrono::{Duration, NaiveDateTime};
+use gradient_entity::project_action_delivery::DeliveryState;
+use gradient_types::{
+    ActionType, CProjectActionDelivery, EProjectAction, EProjectActionDelivery, MProjectAction,
+    MProjectActionDelivery,
+};
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseBackend, EntityTrait, IntoActiveModel,
+    QueryFilter, QueryOrder, QuerySelect, Statement,
+};
+use serde_json::Value as JsonValue;
+use tracing::{debug, warn};
+
+/// Attempts (including the first) before a delivery is declared dead.
+pub const MAX_DELIVERY_ATTEMPTS: i32 = 8;
+
+/// Delay before the first retry; doubles per attempt up to [`RETRY_MAX_SECS`].
+/// With eight attempts the schedule spans roughly two hours.
+const RETRY_BASE_SECS: i64 = 30;
+const RETRY_MAX_SECS: i64 = 3600;
+
+/// How often the loop looks for due retries, and how many it claims per pass.
+const RETRY_POLL_SECS: u64 = 15;
+const RETRY_BATCH: i64 = 50;
+
+/// A claimed row's `next_retry_at` is pushed this far out while the attempt
+/// runs, so a crash mid-attempt delays the retry instead of losing it.
+const CLAIM_LEASE_SECS: i64 = 300;
+
+/// Newer deliveries of the same action inspected when deciding whether a
+/// forge status retry has been overtaken.
+const SUPERSEDE_SCAN: u64 = 50;
+
+/// Only the notification actions are replayable; `open_pr` pushes commits and
+/// keeps its own lifecycle in `open_pr_state`.
+fn is_retryable(action_type: ActionType) -> bool {
+    matches!(
+        action_type,
+        ActionType::SendMail
+            | ActionType::SendWebRequest
+            | ActionType::ForgeStatusReport
+            | ActionType::SendMatrix
+            | ActionType::SendSlack
+    )
+}
+
+/// Backoff before retry number `attempt` (the attempt that just failed is
+/// `attempt`, so the first retry waits [`RETRY_BASE_SECS`]).
+pub(crate) fn retry_delay(attempt: i32) -> Duration {
+    let exp = u32::try_from(attempt.saturating_sub(1))
+        .unwrap_or(0)
+        .min(16);
+    Duration::seconds((RETRY_BASE_SECS << exp).min(RETRY_MAX_SECS))
+}
+
+/// State and next retry for a delivery after its `attempt`-th attempt.
+/// One-shot deliveries (synthetic test fires, digests) and non-replayable
+/// action types fail immediately; a
+/// non-transient rejection (e.g. a 4xx) goes straight to `Dead` since
+/// replaying the same payload cannot help.
+pub(super) fn schedule_after(
+    action_type: ActionType,
+    one_shot: bool,
+    attempt: i32,
+    outcome: &AttemptOutcome,
+    now: NaiveDateTime,
+) -> (DeliveryState, Option<NaiveDateTime>) {
+    if outcome.success {
+        return (DeliveryState::Delivered, None);
+    }
+    if one_shot || !is_retryable(action_type) {
+        return (DeliveryState::Failed, None);
+    }
+    if !outcome.transient || attempt >= MAX_DELIVERY_ATTEMPTS {
+        return (DeliveryState::Dead, None);
+    }
+
+    (DeliveryState::Retrying, Some(now + retry_delay(attempt)))
+}
+
+pub fn start_delivery_retry_loop(ctx: CiContext) {
+    let shutdown = ctx.db.shutdown.clone();
+    shutdown.spawn(async move { retry_loop(ctx).await });
+}
+
+async fn retry_loop(ctx: CiContext) {
+    let period = std::time::Duration::from_secs(RETRY_POLL_SECS);
+    let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+    let cancel = ctx.db.shutdown.token();
+    loop {
+        tokio::select! {
+            _ = cancel.cancelled() => return,
+            _ = interval.tick() => {}
+        }
+        if let Err(e) = retry_due_deliveries(&ctx).await {
+            warn!(error = %e, "action delivery retry pass failed");
+        }
+    }
+}
+
+/// Claim every due `Retrying` row (bounded by [`RETRY_BATCH`]) and run one
+/// more attempt for each.
+async fn retry_due_deliveries(ctx: &CiContext) -> Result<()> {
+    let now = gradient_types::now();
+    let rows = ctx
+        .db
+        .worker_db
+        .query_all(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            "UPDATE project_action_delivery SET next_retry_at = $1 \
+             WHERE id IN (SELECT id FROM project_action_delivery \
+                          WHERE state = $2 AND next_retry_at <= $3 \
+                          ORDER BY next_retry_at LIMIT $4 FOR UPDATE SKIP LOCKED) \
+             RETURNING id",
+            [
+                (now + Duration::seconds(CLAIM_LEASE_SECS)).into(),
+                sea_orm::Value::SmallInt(Some(i16::from(DeliveryState::Retrying))),
+                now.into(),
+                RETRY_BATCH.into(),
+            ],
+        ))
+        .await
+        .context("claiming due action deliveries")?;
+
+    let ids: Vec<uuid::Uuid> = rows
+        .iter()
+        .filter_map(|r| r.try_get::<uuid::Uuid>("", "id").ok())
+        .collect();
+    if ids.is_empty() {
+        return Ok(());
+    }
+
+    let deliveries = EProjectActionDelivery::find()
+        .filter(CProjectActionDelivery::Id.is_in(ids))
+        .all(&ctx.db.worker_db)
+        .await
+        .context("loading claimed action deliveries")?;
+    debug!(count = deliveries.len(), "retrying action deliveries");
+
+    for delivery in deliveries {
+        let delivery_id = delivery.id;
+        let action = match EProjectAction::find_by_id(delivery.action_id)
+            .one(&ctx.db.worker_db)
+            .await
+        {
+            Ok(Some(a)) => a,
+            // Cascade-deleted with its action between claim and load.
+            Ok(None) => continue,
+            Err(e) => {
+                warn!(error = %e, %delivery_id, "Failed to load action for retry");
+                continue;
+            }
+        };
+
+        if !action.active {
+            if let Err(e) = mark_dead(ctx, delivery, "action is inactive").await {
+                warn!(error = %e, %delivery_id, "Failed to park delivery of inactive action");
+            }
+            continue;
+        }
+
+        if action.action_type == ActionType::ForgeStatusReport {
+            match superseded_status(ctx, &delivery).await {
+                Ok(true) => {
+                    if let Err(e) =
+                        mark_dead(ctx, delivery, "superseded by a newer status for the commit")
+                            .await
+                    {
+                        warn!(error = %e, %delivery_id, "Failed to park superseded delivery");
+                    }
+                    continue;
+                }
+                Ok(false) => {}
+                Err(e) => {
+                    warn!(error = %e, %delivery_id, "Failed to check for a newer forge status");
+                }
+            }
+        }
+
+        if let Err(e) = reattempt(ctx, &action, delivery, false).await {
+            warn!(error = %e, %delivery_id, "Action delivery retry failed");
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether a later delivery of the same forge status action already targets
+/// the same commit and check. Replaying the older status after it would roll
+/// the check back (e.g. a late `pending` over a posted `success`).
+async fn superseded_status(ctx: &CiContext, delivery: &MProjectActionDelivery) -> Result<bool> {
+    let Some(target) = status_target(ctx, &delivery.event, delivery.payload.as_ref()).await else {
+        return Ok(false);
+    };
+
+    // Delivery ids are UUIDv7, so a larger id is a later event.
+    let newer = EProjectActionDelivery::find()
+        .filter(CProjectActionDelivery::ActionId.eq(delivery.action_id))
+        .filter(CProjectActionDelivery::Id.gt(delivery.id))
+        .order_by_asc(CProjectActionDelivery::Id)
+        .limit(SUPERSEDE_SCAN)
+        .all(&ctx.db.worker_db)
+        .await
+        .context("loading newer action deliveries")?;
+
+    for later in newer {
+        let payload = match later.payload {
+            Some(p) => Some(p),
+            None => serde_json::from_str(&later.request_body).ok(),
+        };
+        if status_target(ctx, &later.event, payload.as_ref())
+            .await
+            .as_ref()
+            == Some(&target)
+        {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// The `(sha, context)` check a forge status delivery writes to, if it
+/// resolves to one.
+async fn status_target(
+    ctx: &CiContext,
+    event: &str,
+    payload: Option<&JsonValue>,
+) -> Option<(String, String)> {
+    let status = forge_status_for_event(event)?;
+    let report = build_ci_report_from_payload(ctx, event, payload?, status)
+        .await
+        .ok()??;
+    Some((report.sha, report.context))
+}
+
+/// Manually replay a `Dead` delivery once. A success marks it delivered; a
+/// failure leaves it dead with the new error and a bumped attempt count.
+pub async fn redeliver(
+    ctx: &CiContext,
+    action: &MProjectAction,
+    delivery: MProjectActionDelivery,
+) -> Result<MProjectActionDelivery> {
+    if delivery.state != DeliveryState::Dead {
+        anyhow::bail!("only dead deliveries can be redelivered");
+    }
+
+    reattempt(ctx, action, delivery, true).await
+}
+
+/// One more attempt for an existing row, updated in place.
+async fn reattempt(
+    ctx: &CiContext,
+    action: &MProjectAction,
+    delivery: MProjectActionDelivery,
+    manual: bool,
+) -> Result<MProjectActionDelivery> {
+    let Some(payload) = delivery.payload.clone() else {
+        return mark_dead(ctx, delivery, "payload was not retained").await;
+    };
+
+    let event = delivery.event.clone();
+    let attempt = delivery.attempt.saturating_add(1);
+    let (outcome, _) = attempt_delivery(ctx, action, delivery.id, &event, &payload).await;
+
+    let now = gradient_types::now();
+    let (state, next_retry_at) = if manual && !outcome.success {
+        (DeliveryState::Dead, None)
+    } else {
+        schedule_after(action.action_type, false, attempt, &outcome, now)
+    };
+
+    let mut am = delivery.into_active_model();
+    am.attempt = Set(attempt);
+    am.state = Set(state);
+    am.next_retry_at = Set(next_retry_at);
+    am.success = Set(outcome.success);
+    am.response_status = Set(outcome.response_status);
+    am.response_body = Set(outcome.response_body);
+    am.error_message = Set(outcome.error_message);
+    am.duration_ms = Set(outcome.duration_ms);
+    am.delivered_at = Set(now);
+    if state == DeliveryState::Delivered {
+        am.payload = Set(None);
+    }
+
+    am.update(&ctx.db.worker_db)
+        .await
+        .map_err(|e| anyhow!("recording delivery attempt: {}", e))
+}
+
+async fn mark_dead(
+    ctx: &CiContext,
+    delivery: MProjectActionDelivery,
+    reason: &str,
+) -> Result<MProjectActionDelivery> {
+    let mut am = delivery.into_active_model();
+    am.state = Set(DeliveryState::Dead);
+    am.next_retry_at = Set(None);
+    am.error_message = Set(Some(reason.to_owned()));
+    am.update(&ctx.db.worker_db)
+        .await
+        .map_err(|e| anyhow!("marking delivery dead: {}", e))
+}
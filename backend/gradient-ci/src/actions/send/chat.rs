@@ -0,0 +1,83 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+use crate::actions::crypto::decrypt_action_secret;
+use crate::actions::{ExecutorOk, MAX_BODY_BYTES, truncate};
+use crate::context::CiContext;
+use anyhow::{Context, Result, anyhow};
+use gradient_types::ProjectActionDeliveryId;
+use gradient_types::input::load_secret_bytes;
+
+/// `PUT /_matrix/client/v3/rooms/{room_id}/send/m.room.message/{txn_id}`.
+/// The delivery id doubles as the transaction id, so the homeserver drops a
+/// retried delivery it already accepted instead of posting it twice.
+pub(crate) async fn execute_send_matrix(
+    ctx: &CiContext,
+    delivery_id: ProjectActionDeliveryId,
+    homeserver: &str,
+    room_id: &str,
+    access_token: Option<&str>,
+    text: &str,
+) -> Result<ExecutorOk> {
+    let mut url = gradient_util::http_validation::validate_webhook_url(homeserver)
+        .map_err(|e| anyhow!("homeserver URL rejected: {}", e))?;
+    let txn_id = delivery_id.to_string();
+    url.path_segments_mut()
+        .map_err(|_| anyhow!("homeserver URL cannot carry a path"))?
+        .pop_if_empty()
+        .extend([
+            "_matrix",
+            "client",
+            "v3",
+            "rooms",
+            room_id,
+            "send",
+            "m.room.message",
+            &txn_id,
+        ]);
+
+    let token = access_token.ok_or_else(|| anyhow!("send_matrix action has no access token"))?;
+    let key =
+        load_secret_bytes(&ctx.db.config.secrets.crypt_secret_file).context("loading crypt key")?;
+    let token = decrypt_action_secret(token, key.expose())?;
+
+    let resp = ctx
+        .http
+        .put(url)
+        .bearer_auth(token)
+        .json(&serde_json::json!({ "msgtype": "m.notice", "body": text }))
+        .send()
+        .await
+        .context("HTTP send failed")?;
+    response_ok(resp).await
+}
+
+/// Slack-style incoming webhook: `POST {"text": ...}`.
+pub(crate) async fn execute_send_slack(
+    ctx: &CiContext,
+    url: &str,
+    text: &str,
+) -> Result<ExecutorOk> {
+    gradient_util::http_validation::validate_webhook_url(url)
+        .map_err(|e| anyhow!("URL rejected: {}", e))?;
+    let resp = ctx
+        .http
+        .post(url)
+        .json(&serde_json::json!({ "text": text }))
+        .send()
+        .await
+        .context("HTTP send failed")?;
+    response_ok(resp).await
+}
+
+async fn response_ok(resp: reqwest::Response) -> Result<ExecutorOk> {
+    let status = resp.status().as_u16() as i32;
+    let body = resp.text().await.unwrap_or_default();
+    Ok(ExecutorOk {
+        status_code: Some(status),
+        response_body: Some(truncate(body, MAX_BODY_BYTES)),
+    })
+}
@@ -5,26 +5,21 @@
  */
 
 use crate::actions::ExecutorOk;
-use crate::actions::payload::{render_default_body, render_subject};
 use crate::context::CiContext;
 use anyhow::{Result, anyhow};
-use serde_json::Value as JsonValue;
 
 pub(crate) async fn execute_send_mail(
     ctx: &CiContext,
-    event: &str,
-    payload: &JsonValue,
     recipients: &[String],
-    subject_template: Option<&str>,
+    subject: &str,
+    body: &str,
 ) -> Result<ExecutorOk> {
     if recipients.is_empty() {
         return Err(anyhow!("send_mail action has no recipients"));
     }
-    let subject = render_subject(subject_template, event, payload);
-    let body = render_default_body(event, payload);
     let r = ctx
         .email
-        .send_action_mail(recipients, &subject, &body)
+        .send_action_mail(recipients, subject, body)
         .await?;
     Ok(ExecutorOk {
         status_code: Some(r.status_code),
@@ -4,16 +4,19 @@
  * SPDX-License-Identifier: AGPL-3.0-only
  */
 
-//! The three action executors plus the forge-reporter construction shared by
-//! the forge-status executor and the PR-approval trust probe.
+//! The action executors plus the forge-reporter construction shared by the
+//! forge-status executor and the PR-approval trust probe.
 
+mod chat;
 mod forge_status;
 mod mail;
 mod open_pr;
 mod web_request;
 
+pub(crate) use chat::{execute_send_matrix, execute_send_slack};
 pub(crate) use forge_status::execute_forge_status_report;
 pub use forge_status::{reporter_for_project, verify_forge_action};
 pub(crate) use mail::execute_send_mail;
 pub(crate) use open_pr::execute_open_pr;
-pub(crate) use web_request::execute_send_web_request;
+pub use web_request::render_web_request_body;
+pub(crate) use web_request::{WebRequestTarget, execute_send_web_request};
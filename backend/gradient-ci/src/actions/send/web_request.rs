@@ -9,31 +9,94 @@ use crate::actions::{ExecutorOk, MAX_BODY_BYTES, truncate};
 use crate::context::CiContext;
 use anyhow::{Context, Result, anyhow};
 use gradient_types::input::load_secret_bytes;
+use gradient_types::{ProjectActionDeliveryId, WebRequestMethod, body_template};
+use gradient_util::webhook_signature::{
+    DELIVERY_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER, sign_payload,
+};
+use reqwest::Method;
+use reqwest::header::{CONTENT_TYPE, HeaderName, HeaderValue};
 use serde_json::Value as JsonValue;
+use std::collections::BTreeMap;
+
+/// The request-shaping half of an `ActionConfig::SendWebRequest`.
+pub(crate) struct WebRequestTarget<'a> {
+    pub(crate) url: &'a str,
+    pub(crate) method: WebRequestMethod,
+    pub(crate) headers: &'a BTreeMap<String, String>,
+    pub(crate) token: Option<&'a str>,
+    pub(crate) signing_secret: Option<&'a str>,
+}
+
+/// The body a `send_web_request` delivery carries: `body_template` rendered
+/// against the payload, or the payload itself when no template is set.
+pub fn render_web_request_body(
+    event: &str,
+    payload: &JsonValue,
+    template: Option<&str>,
+) -> Result<JsonValue> {
+    match template {
+        Some(template) => body_template::render(template, event, payload)
+            .map_err(|e| anyhow!("rendering body_template: {}", e)),
+        None => Ok(payload.clone()),
+    }
+}
 
 pub(crate) async fn execute_send_web_request(
     ctx: &CiContext,
+    delivery_id: ProjectActionDeliveryId,
     event: &str,
-    payload: &JsonValue,
-    url: &str,
-    token: Option<&str>,
+    body: &JsonValue,
+    target: WebRequestTarget<'_>,
 ) -> Result<ExecutorOk> {
+    let WebRequestTarget {
+        url,
+        method,
+        headers,
+        token,
+        signing_secret,
+    } = target;
     gradient_util::http_validation::validate_webhook_url(url)
         .map_err(|e| anyhow!("URL rejected: {}", e))?;
-    let body = serde_json::to_string(payload).context("serializing webhook payload")?;
+    let body = serde_json::to_string(body).context("serializing webhook body")?;
+    let method = match method {
+        WebRequestMethod::Post => Method::POST,
+        WebRequestMethod::Put => Method::PUT,
+        WebRequestMethod::Patch => Method::PATCH,
+    };
     let mut req = ctx
         .http
-        .post(url)
-        .header("Content-Type", "application/json")
+        .request(method, url)
         .header("X-Gradient-Event", event)
-        .body(body);
-    if let Some(tok) = token {
+        .header(DELIVERY_HEADER, delivery_id.to_string());
+    if token.is_some() || signing_secret.is_some() {
         let key = load_secret_bytes(&ctx.db.config.secrets.crypt_secret_file)
             .context("loading crypt key")?;
-        let decrypted = decrypt_action_secret(tok, key.expose())?;
-        req = req.bearer_auth(decrypted);
+        if let Some(tok) = token {
+            let decrypted = decrypt_action_secret(tok, key.expose())?;
+            req = req.bearer_auth(decrypted);
+        }
+        if let Some(secret) = signing_secret {
+            let secret = decrypt_action_secret(secret, key.expose())?;
+            let timestamp = chrono::Utc::now().timestamp();
+            req = req.header(TIMESTAMP_HEADER, timestamp.to_string()).header(
+                SIGNATURE_HEADER,
+                sign_payload(secret.as_bytes(), timestamp, body.as_bytes()),
+            );
+        }
+    }
+    let mut req = req.body(body).build().context("building webhook request")?;
+    // Configured headers replace rather than append, so a configured
+    // Content-Type overrides the JSON default instead of sending two.
+    let req_headers = req.headers_mut();
+    req_headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    for (name, value) in headers {
+        let name = HeaderName::from_bytes(name.as_bytes())
+            .with_context(|| format!("invalid header name {name:?}"))?;
+        let value = HeaderValue::from_str(value)
+            .with_context(|| format!("invalid value for header {name}"))?;
+        req_headers.insert(name, value);
     }
-    let resp = req.send().await.context("HTTP send failed")?;
+    let resp = ctx.http.execute(req).await.context("HTTP send failed")?;
     let status = resp.status().as_u16() as i32;
     let body = resp.text().await.unwrap_or_default();
     Ok(ExecutorOk {
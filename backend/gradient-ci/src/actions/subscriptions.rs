@@ -0,0 +1,206 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Per-user notification subscriptions. Alongside the project's actions, every
+//! dispatched event is matched against the users subscribed to the project (or
+//! to all of their organizations' projects) and mailed through the same
+//! `send_action_mail` path as `send_mail` actions. Subscribers must still be
+//! members of the project's organization when the event fires.
+
+use super::matchers::FORGE_STATUS_EVENTS;
+use super::message::{message_fields, payload_evaluation_id};
+use super::payload::{render_default_body, render_subject};
+use crate::context::CiContext;
+use anyhow::{Context, Result, anyhow};
+use gradient_types::*;
+use gradient_util::glob::glob_match;
+use sea_orm::{ColumnTrait, Condition, EntityTrait, QueryFilter};
+use serde_json::Value as JsonValue;
+use std::collections::HashSet;
+use tracing::warn;
+
+/// Mail every user whose subscription matches `event` on `project_id`.
+/// Failures are logged; they never affect the project's own actions.
+pub(super) async fn notify_subscribers(
+    ctx: &CiContext,
+    project_id: ProjectId,
+    event: &str,
+    payload: &JsonValue,
+) {
+    if let Err(e) = try_notify_subscribers(ctx, project_id, event, payload).await {
+        warn!(error = %e, %project_id, event, "Failed to notify subscribed users");
+    }
+}
+
+async fn try_notify_subscribers(
+    ctx: &CiContext,
+    project_id: ProjectId,
+    event: &str,
+    payload: &JsonValue,
+) -> Result<()> {
+    if !FORGE_STATUS_EVENTS.contains(&event) || !ctx.email.is_enabled() {
+        return Ok(());
+    }
+
+    let subs: Vec<MUserNotificationSubscription> = EUserNotificationSubscription::find()
+        .filter(CUserNotificationSubscription::Active.eq(true))
+        .filter(
+            Condition::any()
+                .add(CUserNotificationSubscription::Project.eq(project_id))
+                .add(CUserNotificationSubscription::Project.is_null()),
+        )
+        .all(&ctx.db.worker_db)
+        .await
+        .context("loading notification subscriptions")?
+        .into_iter()
+        .filter(|s| subscribes_to(s, event))
+        .collect();
+    if subs.is_empty() {
+        return Ok(());
+    }
+
+    let project = EProject::find_by_id(project_id)
+        .one(&ctx.db.worker_db)
+        .await
+        .context("loading project")?
+        .ok_or_else(|| anyhow!("project {} not found", project_id))?;
+    let members: HashSet<UserId> = EOrganizationUser::find()
+        .filter(COrganizationUser::Organization.eq(project.organization))
+        .filter(COrganizationUser::User.is_in(subs.iter().map(|s| s.user)))
+        .all(&ctx.db.worker_db)
+        .await
+        .context("loading organization members")?
+        .into_iter()
+        .map(|m| m.user)
+        .collect();
+
+    let source = EventSource::load(ctx, payload).await?;
+    let recipients: HashSet<UserId> = subs
+        .iter()
+        .filter(|s| members.contains(&s.user) && source.matches(s))
+        .map(|s| s.user)
+        .collect();
+    if recipients.is_empty() {
+        return Ok(());
+    }
+
+    let users = EUser::find()
+        .filter(CUser::Id.is_in(recipients))
+        .all(&ctx.db.worker_db)
+        .await
+        .context("loading subscribed users")?;
+
+    let payload = with_message_fields(ctx, payload).await;
+    let subject = render_subject(None, event, &payload);
+    let body = render_default_body(event, &payload);
+    for user in users.into_iter().filter(|u| !u.email.is_empty()) {
+        if let Err(e) = ctx
+            .email
+            .send_action_mail(std::slice::from_ref(&user.email), &subject, &body)
+            .await
+        {
+            warn!(error = %e, user_id = %user.id, event, "Failed to mail subscribed user");
+        }
+    }
+    Ok(())
+}
+
+pub(super) fn subscribes_to(sub: &MUserNotificationSubscription, event: &str) -> bool {
+    sub.events
+        .as_array()
+        .is_some_and(|list| list.iter().any(|v| v.as_str() == Some(event)))
+}
+
+/// Branch and authors of the evaluation an event belongs to, matched against
+/// a subscription's `branch` glob and `author` filter.
+#[derive(Debug, Default)]
+pub(super) struct EventSource {
+    pub(super) branch: Option<String>,
+    /// Commit author name and, for pull requests, the PR author login.
+    pub(super) authors: Vec<String>,
+}
+
+impl EventSource {
+    async fn load(ctx: &CiContext, payload: &JsonValue) -> Result<Self> {
+        let Some(evaluation_id) = payload_evaluation_id(ctx, payload).await? else {
+            return Ok(Self::default());
+        };
+        let Some(evaluation) = EEvaluation::find_by_id(evaluation_id)
+            .one(&ctx.db.worker_db)
+            .await
+            .context("loading evaluation")?
+        else {
+            return Ok(Self::default());
+        };
+
+        let mut authors = Vec::new();
+        if let Some(commit) = ECommit::find_by_id(evaluation.commit)
+            .one(&ctx.db.worker_db)
+            .await
+            .context("loading commit")?
+            && !commit.author_name.is_empty()
+        {
+            authors.push(commit.author_name);
+        }
+        if let Some(pr_author) = evaluation
+            .source_comment
+            .as_ref()
+            .and_then(|c| c.get("pr_author"))
+            .and_then(|v| v.as_str())
+        {
+            authors.push(pr_author.to_owned());
+        }
+
+        Ok(Self {
+            branch: evaluation.branch,
+            authors,
+        })
+    }
+
+    pub(super) fn matches(&self, sub: &MUserNotificationSubscription) -> bool {
+        let branch_ok = sub.branch.as_deref().is_none_or(|glob| {
+            self.branch
+                .as_deref()
+                .is_some_and(|branch| glob_match(glob, branch))
+        });
+        let author_ok = sub
+            .author
+            .as_deref()
+            .is_none_or(|want| self.authors.iter().any(|a| a.eq_ignore_ascii_case(want)));
+        branch_ok && author_ok
+    }
+}
+
+/// Event payloads only carry ids; fill in the org/project/status/link the
+/// mail body prints.
+async fn with_message_fields(ctx: &CiContext, payload: &JsonValue) -> JsonValue {
+    let mut payload = payload.clone();
+    let fields = match message_fields(ctx, &payload).await {
+        Ok(f) => f,
+        Err(e) => {
+            warn!(error = %e, "Failed to resolve notification message fields");
+            return payload;
+        }
+    };
+    if let JsonValue::Object(map) = &mut payload {
+        let id = map
+            .get("evaluation_id")
+            .or_else(|| map.get("build_id"))
+            .cloned();
+        for (key, value) in [
+            ("org", JsonValue::from(fields.org)),
+            ("project", JsonValue::from(fields.project)),
+            ("status", JsonValue::from(fields.status)),
+            ("link", JsonValue::from(fields.link)),
+        ] {
+            map.entry(key).or_insert(value);
+        }
+        if let Some(id) = id {
+            map.entry("id").or_insert(id);
+        }
+    }
+    payload
+}
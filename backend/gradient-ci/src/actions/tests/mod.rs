@@ -6,14 +6,22 @@
 
 mod fixtures;
 
-use super::matchers::{forge_status_for_event, matches_event};
+use super::digest::{digest_subject, first_error_lines, render_digest_body};
+use super::executor::AttemptOutcome;
+use super::matchers::{forge_status_for_event, matches_event, open_pr_aggregate_gate};
+use super::message::{MessageFields, message_fields, render_message};
 use super::payload::{forge_status_payload, render_default_body, render_subject};
 use super::report::build_ci_report_from_payload;
+use super::retry::{MAX_DELIVERY_ATTEMPTS, retry_delay, schedule_after};
+use super::subscriptions::{EventSource, subscribes_to};
 use super::truncate;
 use fixtures::{action_with, make_ctx, run};
-use gradient_forge::reporter::CiStatus;
-use gradient_types::ActionType;
+use gradient_entity::project_action_delivery::DeliveryState;
+use gradient_forge::reporter::{CiStatus, ForgeHttpError};
+use gradient_types::{ActionType, MUserNotificationSubscription};
+use reqwest::StatusCode;
 use serde_json::json;
+use std::time::Instant;
 
 #[test]
 fn forge_status_mapping() {
@@ -92,11 +100,15 @@ fn matches_event_open_pr_fires_only_on_gate_event() {
     assert!(!matches_event(&build_gate, "evaluation.building"));
     assert!(!matches_event(&build_gate, "build.completed"));
     assert!(!matches_event(&build_gate, "evaluation.failed"));
+    // A failed eval may still pass through its green aggregates.
+    assert!(open_pr_aggregate_gate(&build_gate, "evaluation.failed"));
+    assert!(!open_pr_aggregate_gate(&build_gate, "evaluation.aborted"));
 
     let eval_gate = open_pr(VerifyGate::Eval);
     assert!(matches_event(&eval_gate, "evaluation.building"));
     assert!(!matches_event(&eval_gate, "evaluation.completed"));
     assert!(!matches_event(&eval_gate, "build.completed"));
+    assert!(!open_pr_aggregate_gate(&eval_gate, "evaluation.failed"));
 }
 
 #[test]
@@ -232,3 +244,233 @@ fn build_ci_report_errors_on_invalid_build_id() {
         assert!(err.to_string().contains("invalid build_id"), "error: {err}");
     });
 }
+
+fn failed_attempt(transient: bool) -> AttemptOutcome {
+    AttemptOutcome {
+        success: false,
+        transient,
+        response_status: Some(if transient { 503 } else { 404 }),
+        response_body: None,
+        error_message: None,
+        duration_ms: 1,
+    }
+}
+
+#[test]
+fn retry_delay_doubles_and_caps() {
+    assert_eq!(retry_delay(1).num_seconds(), 30);
+    assert_eq!(retry_delay(2).num_seconds(), 60);
+    assert_eq!(retry_delay(3).num_seconds(), 120);
+    assert_eq!(retry_delay(20).num_seconds(), 3600);
+}
+
+#[test]
+fn transient_failure_is_scheduled_for_retry() {
+    let now = gradient_types::now();
+    let (state, next) = schedule_after(
+        ActionType::SendWebRequest,
+        false,
+        1,
+        &failed_attempt(true),
+        now,
+    );
+    assert_eq!(state, DeliveryState::Retrying);
+    assert_eq!(next, Some(now + retry_delay(1)));
+}
+
+#[test]
+fn exhausted_budget_is_dead() {
+    let now = gradient_types::now();
+    let (state, next) = schedule_after(
+        ActionType::SendMail,
+        false,
+        MAX_DELIVERY_ATTEMPTS,
+        &failed_attempt(true),
+        now,
+    );
+    assert_eq!(state, DeliveryState::Dead);
+    assert!(next.is_none());
+}
+
+#[test]
+fn permanent_rejection_skips_retries() {
+    let (state, _) = schedule_after(
+        ActionType::ForgeStatusReport,
+        false,
+        1,
+        &failed_attempt(false),
+        gradient_types::now(),
+    );
+    assert_eq!(state, DeliveryState::Dead);
+}
+
+#[test]
+fn forge_client_errors_are_permanent() {
+    let forge_err = |status| {
+        Err(anyhow::Error::new(ForgeHttpError::new(
+            status,
+            format!("GitHub returned {}", status),
+        ))
+        .context("forge status report failed"))
+    };
+    let rejected =
+        AttemptOutcome::from_result(&forge_err(StatusCode::UNPROCESSABLE_ENTITY), Instant::now());
+    assert!(!rejected.transient);
+    assert_eq!(rejected.response_status, Some(422));
+    let outage = AttemptOutcome::from_result(&forge_err(StatusCode::BAD_GATEWAY), Instant::now());
+    assert!(outage.transient);
+    let network =
+        AttemptOutcome::from_result(&Err(anyhow::anyhow!("connect refused")), Instant::now());
+    assert!(network.transient);
+    assert_eq!(network.response_status, None);
+}
+
+#[test]
+fn open_pr_and_test_fires_are_never_retried() {
+    let now = gradient_types::now();
+    let (state, _) = schedule_after(ActionType::OpenPr, false, 1, &failed_attempt(true), now);
+    assert_eq!(state, DeliveryState::Failed);
+    let (state, _) = schedule_after(
+        ActionType::SendWebRequest,
+        true,
+        1,
+        &failed_attempt(true),
+        now,
+    );
+    assert_eq!(state, DeliveryState::Failed);
+}
+
+#[test]
+fn render_message_with_default_template() {
+    let fields = MessageFields {
+        org: "acme".into(),
+        project: "web".into(),
+        status: "failed".into(),
+        failed_builds: 3,
+        link: "https://gradient.example/organization/acme/log/1".into(),
+    };
+    assert_eq!(
+        render_message(None, "evaluation.failed", &fields),
+        "[acme/web] evaluation.failed: evaluation failed, 3 failed build(s) - \
+         https://gradient.example/organization/acme/log/1"
+    );
+}
+
+#[test]
+fn render_message_with_custom_template() {
+    let fields = MessageFields {
+        project: "web".into(),
+        failed_builds: 0,
+        ..Default::default()
+    };
+    assert_eq!(
+        render_message(
+            Some("{project}: {failed_builds} broken ({event})"),
+            "build.failed",
+            &fields
+        ),
+        "web: 0 broken (build.failed)"
+    );
+}
+
+#[test]
+fn message_fields_fall_back_to_payload_without_evaluation() {
+    run(async {
+        let ctx = make_ctx();
+        let payload = json!({
+            "synthetic": true,
+            "org": "acme",
+            "project": "web",
+            "status": "ok",
+            "link": "https://gradient.example/projects/acme/web",
+        });
+        let fields = message_fields(&ctx, &payload).await.unwrap();
+        assert_eq!(fields.org, "acme");
+        assert_eq!(fields.project, "web");
+        assert_eq!(fields.status, "ok");
+        assert_eq!(fields.failed_builds, 0);
+        assert_eq!(fields.link, "https://gradient.example/projects/acme/web");
+    });
+}
+
+#[test]
+fn subscription_matches_event_branch_and_author() {
+    let sub = MUserNotificationSubscription {
+        events: json!(["evaluation.failed"]),
+        branch: Some("release/*".into()),
+        author: Some("Alice".into()),
+        active: true,
+        ..Default::default()
+    };
+    assert!(subscribes_to(&sub, "evaluation.failed"));
+    assert!(!subscribes_to(&sub, "evaluation.completed"));
+
+    let source = EventSource {
+        branch: Some("release/1.2".into()),
+        authors: vec!["Bob".into(), "alice".into()],
+    };
+    assert!(source.matches(&sub));
+
+    let other_branch = EventSource {
+        branch: Some("main".into()),
+        ..source
+    };
+    assert!(!other_branch.matches(&sub));
+    assert!(!EventSource::default().matches(&sub));
+
+    let any = MUserNotificationSubscription {
+        events: json!(["evaluation.failed"]),
+        ..Default::default()
+    };
+    assert!(EventSource::default().matches(&any));
+}
+
+#[test]
+fn first_error_lines_start_at_first_error() {
+    let log = "unpacking sources\nbuilding\n\nerror: builder failed\n  at foo.c:3\nnote: bar\n";
+    assert_eq!(
+        first_error_lines(log, 2),
+        vec!["error: builder failed", "  at foo.c:3"]
+    );
+    // No error marker: fall back to the tail of the log.
+    assert_eq!(first_error_lines("a\nb\nc", 2), vec!["b", "c"]);
+    assert!(first_error_lines("", 5).is_empty());
+    // Overlong lines are cut.
+    let long = format!("error: {}", "x".repeat(1000));
+    let cut = &first_error_lines(&long, 1)[0];
+    assert!(cut.ends_with("...") && cut.len() < 500);
+}
+
+#[test]
+fn digest_body_groups_failures_by_evaluation() {
+    let payload = json!({
+        "digest": true,
+        "org": "acme",
+        "project": "web",
+        "event_count": 4,
+        "first_event_at": "2026-07-13T10:00:00",
+        "last_event_at": "2026-07-13T10:04:00",
+        "evaluations": [{
+            "evaluation_id": "e1",
+            "status": "failed",
+            "link": "https://ci/e1",
+            "events": { "build.failed": 3, "evaluation.failed": 1 },
+            "failed_builds": 3,
+            "failures": [
+                { "attr": "packages.x86_64-linux.hello", "error_lines": ["error: hello broke"] },
+                { "attr": null, "derivation_path": "/nix/store/x-dep.drv", "error_lines": [] },
+            ],
+            "errors": [],
+        }],
+    });
+    assert_eq!(
+        digest_subject(&payload),
+        "[Gradient] acme/web: 4 event(s) in 1 evaluation(s)"
+    );
+    let body = render_digest_body(&payload);
+    assert!(body.contains("Evaluation e1 (failed)"));
+    assert!(body.contains("build.failed x3"));
+    assert!(body.contains("Failed: packages.x86_64-linux.hello\n    error: hello broke"));
+    assert!(body.contains("Failed: /nix/store/x-dep.drv"));
+    assert!(body.contains("... and 1 more failed build(s)"));
+}
@@ -5,7 +5,7 @@
  */
 
 use crate::abort::{AbortKind, abort_evaluation};
-use gradient_types::triggers::ConcurrencyPolicy;
+use gradient_types::triggers::{ConcurrencyPolicy, TriggerType};
 use gradient_types::*;
 use sea_orm::ConnectionTrait;
 
@@ -22,16 +22,21 @@ pub(super) struct ConcurrencyDecision {
 /// when the `Skip` policy says to drop this trigger entirely; otherwise a
 /// [`ConcurrencyDecision`] describing the abort side-effects and the
 /// `concurrent` flag to pass through to `trigger_evaluation`.
+///
+/// Merge-queue runs are always concurrent: a queue tests several entries at
+/// once, and aborting or skipping one would stall the queue.
 pub(super) async fn resolve_concurrency<C: ConnectionTrait>(
     db: &C,
     project: &MProject,
+    trigger_type: TriggerType,
     in_flight: Option<MEvaluation>,
 ) -> Result<Option<ConcurrencyDecision>, sea_orm::DbErr> {
     let concurrency = project.concurrency;
 
     let mut aborted_evaluation: Option<EvaluationId> = None;
     let mut aborted_anchors: Vec<DerivationBuildId> = Vec::new();
-    let concurrent_flag = matches!(concurrency, ConcurrencyPolicy::All);
+    let concurrent_flag = matches!(concurrency, ConcurrencyPolicy::All)
+        || trigger_type == TriggerType::ReporterMergeQueue;
 
     if !concurrent_flag && let Some(running) = in_flight {
         match concurrency {
@@ -78,6 +78,9 @@ pub struct ApplyInput {
     /// in `evaluation.source_comment` so the terminal-status reporter can
     /// react with thumbs-up / thumbs-down once the build resolves.
     pub source_comment: Option<serde_json::Value>,
+    /// Branch the push or PR came from, persisted on `evaluation.branch` for
+    /// branch-filtered notification subscriptions.
+    pub branch: Option<String>,
     /// Instance-wide `max_storage_gb` limit (`GRADIENT_MAX_STORAGE_GB`), used by
     /// the storage-full gate. `0` disables the instance-wide limit.
     pub instance_max_storage_gb: i32,
@@ -118,7 +121,9 @@ pub async fn apply_trigger<C: ConnectionTrait>(
         return Ok(ApplyOutcome::SkippedSameCommit);
     }
 
-    let Some(decision) = concurrency::resolve_concurrency(db, project, in_flight).await? else {
+    let Some(decision) =
+        concurrency::resolve_concurrency(db, project, input.trigger_type, in_flight).await?
+    else {
         return Ok(ApplyOutcome::SkippedConcurrency);
     };
 
@@ -134,6 +139,7 @@ pub async fn apply_trigger<C: ConnectionTrait>(
         input.wildcard_override,
         input.source_comment,
         None,
+        input.branch,
     )
     .await
     {
@@ -78,6 +78,7 @@ pub fn input(
         repository_override: None,
         wildcard_override: None,
         source_comment: None,
+        branch: None,
         instance_max_storage_gb: 0,
     }
 }
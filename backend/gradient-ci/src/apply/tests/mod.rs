@@ -252,6 +252,67 @@ async fn all_concurrency_creates_evaluation_alongside_running() {
     assert!(aborted_anchors.is_empty());
 }
 
+#[tokio::test]
+async fn merge_queue_runs_alongside_running_eval_despite_skip_policy() {
+    let project = make_project_with_concurrency(None, ConcurrencyPolicy::Skip);
+    let running_commit_id = CommitId::now_v7();
+    let running_eval = make_eval(
+        EvaluationId::now_v7(),
+        project.id,
+        running_commit_id,
+        EvaluationStatus::Building,
+    );
+    let new_eval_id = EvaluationId::now_v7();
+    let new_commit_id = CommitId::now_v7();
+    let trig = ProjectTriggerId::now_v7();
+    let queue_hash = vec![4u8; 20];
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        // in_flight lookup returns the running branch eval
+        .append_query_results([vec![running_eval]])
+        // dedup against the running eval's commit - a different hash
+        .append_query_results([vec![make_commit(running_commit_id, vec![3u8; 20])]])
+        // merge-queue runs are concurrent: no abort, no in-progress guard
+        .append_query_results([vec![make_commit(new_commit_id, queue_hash.clone())]])
+        .append_query_results([vec![{
+            let mut m = make_eval(
+                new_eval_id,
+                project.id,
+                new_commit_id,
+                EvaluationStatus::Queued,
+            );
+            m.trigger = Some(trig);
+            m.concurrent = true;
+            m
+        }]])
+        .append_query_results([Vec::<gradient_entity::project_flake_input_override::Model>::new()])
+        .append_query_results([vec![project.clone()]])
+        .append_exec_results([MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }]);
+    let db = with_eval_worker(with_storage_not_full(with_writable_cache(db))).into_connection();
+
+    let res = apply_trigger(
+        &db,
+        &project,
+        input(trig, TriggerType::ReporterMergeQueue, queue_hash, false),
+    )
+    .await
+    .unwrap();
+
+    let ApplyOutcome::Created {
+        evaluation,
+        aborted_evaluation,
+        ..
+    } = res
+    else {
+        panic!("expected Created, got {res:?}");
+    };
+    assert!(evaluation.concurrent, "merge-queue eval must be concurrent");
+    assert_eq!(aborted_evaluation, None);
+}
+
 #[tokio::test]
 async fn unique_constraint_violation_returns_skipped_concurrency() {
     let project = make_project_with_last_eval(None);
@@ -507,6 +568,7 @@ async fn gate_approval_parks_pr_evaluation_in_waiting_approval() {
         repository_override: None,
         wildcard_override: None,
         source_comment: None,
+        branch: None,
         instance_max_storage_gb: 0,
     };
     let res = apply_trigger(&db, &project, applied).await.unwrap();
@@ -704,6 +766,7 @@ async fn no_eval_capable_worker_parks_evaluation_in_waiting_workers() {
             connected_workers,
             unmet,
             available_architectures,
+            ..
         } => {
             assert_eq!(connected_workers, 0);
             assert!(unmet.is_empty());
@@ -0,0 +1,440 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! PR evaluation diff against the target branch: which entry points changed
+//! derivation, were added or removed, or newly fail compared to the latest
+//! finished evaluation of the branch the PR merges into. Served by
+//! `GET /evals/{evaluation}/diff` and kept as one collapsible PR comment per
+//! project, edited in place each time a PR evaluation finishes.
+//!
+//! The base is the newest finished, non-PR `Normal` evaluation of the same
+//! project on `source_comment.base_branch`. When the PR payload carried no
+//! target branch (comment-triggered runs), or that branch has no evaluation,
+//! the newest one without a branch (default-branch polling, cron, manual) is
+//! used instead.
+
+use crate::context::CiContext;
+use crate::parse_owner_repo;
+use gradient_entity::build::BuildStatus;
+use gradient_entity::evaluation::{EvaluationKind, EvaluationStatus};
+use gradient_types::*;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use tracing::warn;
+
+/// Attribute-level difference between a PR evaluation and its base.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct EvalDiff {
+    pub evaluation: EvaluationId,
+    /// Evaluation compared against; `None` when the target branch has no
+    /// finished evaluation yet, in which case every list below is empty.
+    pub base_evaluation: Option<EvaluationId>,
+    pub base_branch: Option<String>,
+    /// Attributes present in the PR but not in the base.
+    pub added: Vec<String>,
+    /// Attributes present in the base but not in the PR.
+    pub removed: Vec<String>,
+    /// Attributes in both whose derivation differs.
+    pub changed: Vec<String>,
+    /// Attributes that fail in the PR but did not fail (or did not exist) in
+    /// the base.
+    pub newly_failed: Vec<String>,
+    /// Attributes with the same derivation on both sides.
+    pub unchanged: usize,
+}
+
+/// One entry point with the status of its derivation's build.
+#[derive(Debug, Clone)]
+pub struct EntryPointState {
+    pub attr: String,
+    pub derivation: DerivationId,
+    /// `None` while the derivation has no build yet.
+    pub status: Option<BuildStatus>,
+}
+
+/// Compare the entry points of `head` against `base`. Output lists are sorted
+/// by attribute.
+pub fn diff_entry_points(head: &[EntryPointState], base: &[EntryPointState]) -> EvalDiff {
+    let head: BTreeMap<&str, &EntryPointState> =
+        head.iter().map(|e| (e.attr.as_str(), e)).collect();
+    let base: BTreeMap<&str, &EntryPointState> =
+        base.iter().map(|e| (e.attr.as_str(), e)).collect();
+
+    let mut diff = EvalDiff::default();
+    for (attr, ep) in &head {
+        let failed = ep.status.is_some_and(BuildStatus::is_terminal_failure);
+        match base.get(attr) {
+            None => {
+                diff.added.push(attr.to_string());
+                if failed {
+                    diff.newly_failed.push(attr.to_string());
+                }
+            }
+            Some(prev) => {
+                if prev.derivation == ep.derivation {
+                    diff.unchanged += 1;
+                } else {
+                    diff.changed.push(attr.to_string());
+                }
+                if failed && !prev.status.is_some_and(BuildStatus::is_failure) {
+                    diff.newly_failed.push(attr.to_string());
+                }
+            }
+        }
+    }
+    diff.removed = base
+        .keys()
+        .filter(|attr| !head.contains_key(*attr))
+        .map(|attr| attr.to_string())
+        .collect();
+    diff
+}
+
+/// Target branch stamped on a PR evaluation's `source_comment`.
+pub fn pr_base_branch(evaluation: &MEvaluation) -> Option<String> {
+    evaluation
+        .source_comment
+        .as_ref()?
+        .get("base_branch")?
+        .as_str()
+        .map(str::to_owned)
+}
+
+/// Newest finished non-PR evaluation of the project to compare `evaluation`
+/// against. See the module docs for the branch fallback.
+pub async fn find_base_evaluation<C: ConnectionTrait>(
+    db: &C,
+    evaluation: &MEvaluation,
+    base_branch: Option<&str>,
+) -> Result<Option<MEvaluation>, DbErr> {
+    let Some(project_id) = evaluation.project else {
+        return Ok(None);
+    };
+    let candidates = || {
+        EEvaluation::find()
+            .filter(CEvaluation::Project.eq(project_id))
+            .filter(CEvaluation::Id.ne(evaluation.id))
+            .filter(CEvaluation::Kind.eq(EvaluationKind::Normal))
+            .filter(
+                CEvaluation::Status.is_in([EvaluationStatus::Completed, EvaluationStatus::Failed]),
+            )
+            .filter(Expr::cust("(source_comment->>'pr_number') IS NULL"))
+            .order_by_desc(CEvaluation::CreatedAt)
+    };
+
+    if let Some(branch) = base_branch
+        && let Some(base) = candidates()
+            .filter(CEvaluation::Branch.eq(branch))
+            .one(db)
+            .await?
+    {
+        return Ok(Some(base));
+    }
+    candidates()
+        .filter(CEvaluation::Branch.is_null())
+        .one(db)
+        .await
+}
+
+/// Entry points of `evaluation` with their anchor build status.
+async fn entry_point_states<C: ConnectionTrait>(
+    db: &C,
+    evaluation: EvaluationId,
+) -> Result<Vec<EntryPointState>, DbErr> {
+    let entry_points: Vec<(String, DerivationId)> = EEntryPoint::find()
+        .select_only()
+        .column(CEntryPoint::Eval)
+        .column(CEntryPoint::Derivation)
+        .filter(CEntryPoint::Evaluation.eq(evaluation))
+        .into_tuple()
+        .all(db)
+        .await?;
+    let drv_ids: Vec<DerivationId> = entry_points.iter().map(|(_, d)| *d).collect();
+    let anchors = gradient_db::fetch_in_chunks(&drv_ids, |chunk| async move {
+        EDerivationBuild::find()
+            .filter(CDerivationBuild::Derivation.is_in(chunk))
+            .all(db)
+            .await
+    })
+    .await?;
+    let status_by_drv: HashMap<DerivationId, BuildStatus> = anchors
+        .into_iter()
+        .map(|b| (b.derivation, b.status))
+        .collect();
+    Ok(entry_points
+        .into_iter()
+        .map(|(attr, derivation)| EntryPointState {
+            status: status_by_drv.get(&derivation).copied(),
+            attr,
+            derivation,
+        })
+        .collect())
+}
+
+/// Diff `evaluation` against its base evaluation.
+pub async fn evaluation_diff<C: ConnectionTrait>(
+    db: &C,
+    evaluation: &MEvaluation,
+) -> Result<EvalDiff, DbErr> {
+    let base_branch = pr_base_branch(evaluation);
+    let base = find_base_evaluation(db, evaluation, base_branch.as_deref()).await?;
+    let mut diff = match &base {
+        Some(base) => {
+            let head = entry_point_states(db, evaluation.id).await?;
+            let prev = entry_point_states(db, base.id).await?;
+            diff_entry_points(&head, &prev)
+        }
+        None => EvalDiff::default(),
+    };
+    diff.evaluation = evaluation.id;
+    diff.base_evaluation = base.as_ref().map(|b| b.id);
+    diff.base_branch = base_branch;
+    Ok(diff)
+}
+
+/// Forges cap comment bodies (GitHub at 65536 characters). Attribute lines
+/// past this budget are elided with a count; the details link has the rest.
+const MAX_COMMENT_BYTES: usize = 60_000;
+
+/// Hidden marker identifying a project's diff comment on a PR, so the next
+/// evaluation edits it instead of posting another.
+pub fn diff_comment_marker(project_id: ProjectId) -> String {
+    format!("<!-- gradient-eval-diff:{project_id} -->")
+}
+
+/// Render the diff as a PR comment: a summary line plus one collapsible
+/// section per non-empty category, in the spirit of `nixpkgs-review`. Lists
+/// are cut to stay within [`MAX_COMMENT_BYTES`].
+pub fn format_diff_comment(
+    project_name: &str,
+    diff: &EvalDiff,
+    details_url: Option<&str>,
+) -> String {
+    let against = diff
+        .base_branch
+        .as_deref()
+        .map(|b| format!("`{b}`"))
+        .unwrap_or_else(|| "the default branch".to_string());
+    let mut out = format!("**{project_name}**: changes against {against}");
+    if let Some(url) = details_url {
+        out.push_str(&format!(" ([details]({url}))"));
+    }
+    out.push_str(&format!(
+        "\n\n{} changed, {} added, {} removed, {} newly failing, {} unchanged",
+        diff.changed.len(),
+        diff.added.len(),
+        diff.removed.len(),
+        diff.newly_failed.len(),
+        diff.unchanged,
+    ));
+    for (title, attrs) in [
+        ("newly failing", &diff.newly_failed),
+        ("changed", &diff.changed),
+        ("added", &diff.added),
+        ("removed", &diff.removed),
+    ] {
+        if attrs.is_empty() {
+            continue;
+        }
+        out.push_str(&format!(
+            "\n\n<details>\n<summary>{} {title}</summary>\n\n",
+            attrs.len()
+        ));
+        let mut shown = 0;
+        for attr in attrs {
+            let line = format!("- `{attr}`\n");
+            if out.len() + line.len() > MAX_COMMENT_BYTES {
+                break;
+            }
+            out.push_str(&line);
+            shown += 1;
+        }
+        if shown < attrs.len() {
+            out.push_str(&format!("- ... {} more\n", attrs.len() - shown));
+        }
+        out.push_str("</details>");
+    }
+    out
+}
+
+/// Post the diff of a finished PR evaluation on its pull request, replacing
+/// the project's previous diff comment there. Best-effort: skipped silently
+/// for non-PR evaluations, projects without a reporter, and when no base
+/// evaluation exists yet.
+pub async fn post_pr_diff_comment(
+    ctx: &CiContext,
+    project_id: ProjectId,
+    evaluation: &MEvaluation,
+) {
+    let Some(pr_number) = evaluation
+        .source_comment
+        .as_ref()
+        .and_then(|c| c.get("pr_number")?.as_u64())
+    else {
+        return;
+    };
+    let diff = match evaluation_diff(&ctx.db.worker_db, evaluation).await {
+        Ok(d) => d,
+        Err(e) => {
+            warn!(error = %e, evaluation_id = %evaluation.id, "computing PR evaluation diff");
+            return;
+        }
+    };
+    if diff.base_evaluation.is_none() {
+        return;
+    }
+    let project = match EProject::find_by_id(project_id)
+        .one(&ctx.db.worker_db)
+        .await
+    {
+        Ok(Some(p)) => p,
+        Ok(None) => return,
+        Err(e) => {
+            warn!(error = %e, %project_id, "loading project for PR diff comment");
+            return;
+        }
+    };
+    let Some((owner, repo)) = parse_owner_repo(&project.repository) else {
+        return;
+    };
+    let reporter = match crate::actions::reporter_for_project(ctx, project_id).await {
+        Ok(Some(r)) => r,
+        Ok(None) => return,
+        Err(e) => {
+            warn!(error = %e, %project_id, "resolving reporter for PR diff comment");
+            return;
+        }
+    };
+    let org_name = EOrganization::find_by_id(project.organization)
+        .one(&ctx.db.worker_db)
+        .await
+        .ok()
+        .flatten()
+        .map(|o| o.name);
+    let details_url = org_name.map(|org| {
+        format!(
+            "{}/organization/{}/log/{}",
+            ctx.db.config.server.frontend_url, org, evaluation.id
+        )
+    });
+    let marker = diff_comment_marker(project_id);
+    let body = format!(
+        "{marker}\n{}",
+        format_diff_comment(&project.name, &diff, details_url.as_deref())
+    );
+    if let Err(e) = reporter
+        .upsert_pr_comment(&owner, &repo, pr_number, &marker, &body)
+        .await
+    {
+        warn!(error = %e, %project_id, pr_number, "PR diff comment post failed");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ep(attr: &str, drv: DerivationId, status: Option<BuildStatus>) -> EntryPointState {
+        EntryPointState {
+            attr: attr.to_string(),
+            derivation: drv,
+            status,
+        }
+    }
+
+    #[test]
+    fn diff_classifies_added_removed_changed_and_newly_failed() {
+        let same = DerivationId::now_v7();
+        let base = vec![
+            ep("hello", same, Some(BuildStatus::Completed)),
+            ep("tool", DerivationId::now_v7(), Some(BuildStatus::Completed)),
+            ep(
+                "flaky",
+                DerivationId::now_v7(),
+                Some(BuildStatus::FailedPermanent),
+            ),
+            ep("gone", DerivationId::now_v7(), Some(BuildStatus::Completed)),
+        ];
+        let head = vec![
+            ep("hello", same, Some(BuildStatus::Completed)),
+            ep(
+                "tool",
+                DerivationId::now_v7(),
+                Some(BuildStatus::FailedPermanent),
+            ),
+            ep(
+                "flaky",
+                DerivationId::now_v7(),
+                Some(BuildStatus::FailedPermanent),
+            ),
+            ep(
+                "fresh",
+                DerivationId::now_v7(),
+                Some(BuildStatus::DependencyFailed),
+            ),
+        ];
+        let diff = diff_entry_points(&head, &base);
+        assert_eq!(diff.added, vec!["fresh"]);
+        assert_eq!(diff.removed, vec!["gone"]);
+        assert_eq!(diff.changed, vec!["flaky", "tool"]);
+        assert_eq!(diff.newly_failed, vec!["fresh", "tool"]);
+        assert_eq!(diff.unchanged, 1);
+    }
+
+    #[test]
+    fn diff_ignores_pending_builds_for_failures() {
+        let base = vec![ep(
+            "a",
+            DerivationId::now_v7(),
+            Some(BuildStatus::Completed),
+        )];
+        let head = vec![ep("a", DerivationId::now_v7(), None)];
+        let diff = diff_entry_points(&head, &base);
+        assert_eq!(diff.changed, vec!["a"]);
+        assert!(diff.newly_failed.is_empty());
+    }
+
+    #[test]
+    fn comment_collapses_each_non_empty_category() {
+        let diff = EvalDiff {
+            base_branch: Some("main".into()),
+            changed: vec!["packages.x86_64-linux.default".into()],
+            newly_failed: vec!["checks.x86_64-linux.fmt".into()],
+            unchanged: 3,
+            ..EvalDiff::default()
+        };
+        let out = format_diff_comment("widgets", &diff, None);
+        assert_eq!(
+            out,
+            "**widgets**: changes against `main`\n\n\
+             1 changed, 0 added, 0 removed, 1 newly failing, 3 unchanged\n\n\
+             <details>\n<summary>1 newly failing</summary>\n\n\
+             - `checks.x86_64-linux.fmt`\n\
+             </details>\n\n\
+             <details>\n<summary>1 changed</summary>\n\n\
+             - `packages.x86_64-linux.default`\n\
+             </details>"
+        );
+    }
+
+    #[test]
+    fn comment_elides_attributes_past_the_size_budget() {
+        let diff = EvalDiff {
+            changed: (0..5000)
+                .map(|i| format!("packages.x86_64-linux.pkg{i}"))
+                .collect(),
+            ..EvalDiff::default()
+        };
+        let out = format_diff_comment("widgets", &diff, None);
+        assert!(out.len() <= MAX_COMMENT_BYTES + 64, "len {}", out.len());
+        assert!(out.contains(" more\n</details>"));
+        assert!(out.contains("- `packages.x86_64-linux.pkg0`"));
+    }
+}
@@ -56,6 +56,7 @@ pub fn build_manifest(serve_url: &str) -> Value {
             "checks": "write",
             "pull_requests": "write",
             "issues": "write",
+            "merge_queues": "read",
         },
         "default_events": [
             "push",
@@ -63,6 +64,7 @@ pub fn build_manifest(serve_url: &str) -> Value {
             "release",
             "check_run",
             "issue_comment",
+            "merge_group",
         ],
     })
 }
@@ -172,6 +174,7 @@ mod tests {
         assert_eq!(m["default_permissions"]["checks"], "write");
         assert_eq!(m["default_permissions"]["pull_requests"], "write");
         assert_eq!(m["default_permissions"]["issues"], "write");
+        assert_eq!(m["default_permissions"]["merge_queues"], "read");
         assert_eq!(
             m["default_events"],
             json!([
@@ -179,7 +182,8 @@ mod tests {
                 "pull_request",
                 "release",
                 "check_run",
-                "issue_comment"
+                "issue_comment",
+                "merge_group"
             ])
         );
     }
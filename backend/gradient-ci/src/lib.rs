@@ -9,6 +9,7 @@ pub mod action_crypto;
 pub mod actions;
 pub mod apply;
 pub mod context;
+pub mod eval_diff;
 pub mod github_app_manifest;
 pub mod integration_lookup;
 pub mod manifest_state;
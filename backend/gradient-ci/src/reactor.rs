@@ -12,7 +12,7 @@
 use async_trait::async_trait;
 use gradient_entity::build::BuildStatus;
 use gradient_entity::evaluation::{EvaluationKind, EvaluationStatus};
-use sea_orm::EntityTrait;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
 use tracing::{error, warn};
 
 /// Snake-case tag of an evaluation kind, surfaced in action payloads so the
@@ -101,15 +101,69 @@ impl StatusReactor for CiStatusReactor {
             .flatten()
             .map(|d| d.store_path());
 
-        let payload = serde_json::json!({
+        let mut payload = serde_json::json!({
             "build_id": build_job.id,
             "evaluation_id": build_job.evaluation,
             "derivation_path": derivation_path,
             "status": event,
             "evaluation_kind": eval_kind_str(evaluation.kind),
         });
+        if event == "build.failed"
+            && let Some(cause) = gradient_db::latest_failure_cause(
+                &ctx.db.worker_db,
+                build_job.derivation_build,
+            )
+            .await
+            .unwrap_or_else(|e| {
+                warn!(error = %e, build_id = %build_job.id, "Failed to load build failure cause");
+                None
+            })
+        {
+            payload["failure_summary"] = cause.summary().into();
+            payload["failure_cause"] = serde_json::json!(cause);
+        }
 
         dispatch_build_event(&ctx, project_id, event, payload).await;
+
+        // A constituent's transition changes the combined status of every
+        // aggregate listing it; re-announce those so their checks follow.
+        let aggregates = gradient_db::aggregates_containing(
+            &ctx.db.worker_db,
+            build_job.evaluation,
+            build_job.derivation,
+        )
+        .await
+        .unwrap_or_else(|e| {
+            warn!(error = %e, build_id = %build_job.id, "Failed to load aggregates for build");
+            Vec::new()
+        });
+        for aggregate in aggregates {
+            if aggregate.derivation == build_job.derivation {
+                continue;
+            }
+            let aggregate_job = match EBuildJob::find()
+                .filter(CBuildJob::Evaluation.eq(build_job.evaluation))
+                .filter(CBuildJob::Derivation.eq(aggregate.derivation))
+                .one(&ctx.db.worker_db)
+                .await
+            {
+                Ok(Some(j)) => j,
+                Ok(None) => continue,
+                Err(e) => {
+                    warn!(error = %e, entry_point = %aggregate.id, "Failed to load aggregate build job");
+                    continue;
+                }
+            };
+            let payload = serde_json::json!({
+                "build_id": aggregate_job.id,
+                "evaluation_id": build_job.evaluation,
+                "aggregate": aggregate.eval,
+                "constituent_build_id": build_job.id,
+                "status": "aggregate.updated",
+                "evaluation_kind": eval_kind_str(evaluation.kind),
+            });
+            dispatch_build_event(&ctx, project_id, "aggregate.updated", payload).await;
+        }
     }
 
     async fn on_eval_terminal(
@@ -148,6 +202,17 @@ impl StatusReactor for CiStatusReactor {
         dispatch_evaluation_event(&ctx, project_id, event, payload).await;
 
         react_to_source_comment_on_terminal(&ctx, project_id, &evaluation, status).await;
+
+        if matches!(
+            status,
+            EvaluationStatus::Completed | EvaluationStatus::Failed
+        ) {
+            // Several forge round-trips; keep them off the reactor.
+            let shutdown = ctx.db.shutdown.clone();
+            shutdown.spawn(async move {
+                crate::eval_diff::post_pr_diff_comment(&ctx, project_id, &evaluation).await;
+            });
+        }
     }
 }
 
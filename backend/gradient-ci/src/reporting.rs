@@ -68,7 +68,7 @@ pub fn check_context_kind_for_event(event: &str) -> Option<CheckContextKind> {
         | "evaluation.failed"
         | "evaluation.aborted" => Some(CheckContextKind::Evaluation),
         "build.created" | "build.queued" | "build.started" | "build.completed" | "build.failed"
-        | "build.substituted" => Some(CheckContextKind::Build),
+        | "build.substituted" | "aggregate.updated" => Some(CheckContextKind::Build),
         _ => None,
     }
 }
@@ -141,6 +141,14 @@ pub fn build_event_for_status(status: BuildStatus) -> Option<&'static str> {
     })
 }
 
+/// Forge status of an aggregate's combined build status. A transient failure
+/// is retried, so it still reads as running.
+pub fn ci_status_for_aggregate(status: BuildStatus) -> CiStatus {
+    build_event_for_status(status)
+        .and_then(crate::actions::forge_status_for_event)
+        .unwrap_or(CiStatus::Running)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -321,4 +329,28 @@ mod tests {
             Some(CiStatus::Pending)
         );
     }
+
+    #[test]
+    fn aggregate_update_reports_combined_status_on_build_check() {
+        assert_eq!(
+            check_context_kind_for_event("aggregate.updated"),
+            Some(CheckContextKind::Build)
+        );
+        assert_eq!(
+            ci_status_for_aggregate(BuildStatus::Completed),
+            CiStatus::Success
+        );
+        assert_eq!(
+            ci_status_for_aggregate(BuildStatus::DependencyFailed),
+            CiStatus::Failure
+        );
+        assert_eq!(
+            ci_status_for_aggregate(BuildStatus::Queued),
+            CiStatus::Pending
+        );
+        assert_eq!(
+            ci_status_for_aggregate(BuildStatus::FailedTransient),
+            CiStatus::Running
+        );
+    }
 }
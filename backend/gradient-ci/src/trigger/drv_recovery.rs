@@ -45,6 +45,7 @@ pub async fn trigger_drv_recovery<C: ConnectionTrait>(
         created_at: now,
         updated_at: now,
         flake_source: stuck.flake_source.clone(),
+        branch: stuck.branch.clone(),
         ..Default::default()
     }
     .into_active_model();
@@ -19,7 +19,7 @@ use thiserror::Error;
 pub use drv_recovery::trigger_drv_recovery;
 pub use input_update::{fan_out_expansion, maybe_trigger_input_update};
 pub use new_evaluation::trigger_evaluation;
-pub use restart::trigger_restart_builds;
+pub use restart::{trigger_restart_builds, trigger_restart_evaluation};
 
 #[derive(Debug, Error)]
 pub enum TriggerError {
@@ -23,8 +23,30 @@ pub(super) async fn ensure_no_active_evaluation<C: ConnectionTrait>(
     db: &C,
     project_id: ProjectId,
 ) -> Result<(), TriggerError> {
-    let in_progress = EEvaluation::find()
-        .filter(CEvaluation::Project.eq(project_id))
+    ensure_none_active(db, project_id, None).await
+}
+
+/// Like [`ensure_no_active_evaluation`], but only a non-terminal evaluation of
+/// `commit` blocks. A PR retry re-runs the PR's own commit and must not wait
+/// for evaluations of unrelated branches of the same project.
+pub(super) async fn ensure_no_active_evaluation_of_commit<C: ConnectionTrait>(
+    db: &C,
+    project_id: ProjectId,
+    commit: CommitId,
+) -> Result<(), TriggerError> {
+    ensure_none_active(db, project_id, Some(commit)).await
+}
+
+async fn ensure_none_active<C: ConnectionTrait>(
+    db: &C,
+    project_id: ProjectId,
+    commit: Option<CommitId>,
+) -> Result<(), TriggerError> {
+    let mut query = EEvaluation::find().filter(CEvaluation::Project.eq(project_id));
+    if let Some(commit) = commit {
+        query = query.filter(CEvaluation::Commit.eq(commit));
+    }
+    let in_progress = query
         .filter(
             Condition::any()
                 .add(CEvaluation::Status.eq(EvaluationStatus::Queued))
@@ -71,6 +93,7 @@ pub async fn trigger_evaluation<C: ConnectionTrait>(
     wildcard_override: Option<String>,
     source_comment: Option<serde_json::Value>,
     started_by: Option<gradient_types::ids::UserId>,
+    branch: Option<String>,
 ) -> Result<MEvaluation, TriggerError> {
     if !concurrent {
         ensure_no_active_evaluation(db, project.id).await?;
@@ -114,6 +137,7 @@ pub async fn trigger_evaluation<C: ConnectionTrait>(
         concurrent,
         source_comment,
         started_by,
+        branch,
         ..Default::default()
     }
     .into_active_model();
@@ -7,11 +7,14 @@
 use crate::trigger::TriggerError;
 use chrono::NaiveDateTime;
 use gradient_types::*;
-use sea_orm::{ActiveModelTrait, ConnectionTrait, IntoActiveModel};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, IntoActiveModel, QueryFilter,
+};
 
 /// Copies the previous evaluation's entry points onto `new_eval_id`, carrying
 /// each one's `derivation` straight across. The new eval re-resolves anchors
-/// for those derivations when it runs.
+/// for those derivations when it runs. Aggregates keep their constituent
+/// links, since a restart does not re-evaluate.
 pub(super) async fn copy_entry_points<C: ConnectionTrait>(
     db: &C,
     prev_entry_points: &[MEntryPoint],
@@ -25,12 +28,33 @@ pub(super) async fn copy_entry_points<C: ConnectionTrait>(
             evaluation: new_eval_id,
             derivation: prev_ep.derivation,
             eval: prev_ep.eval.clone(),
+            aggregate: prev_ep.aggregate,
+            unresolved_constituents: prev_ep.unresolved_constituents,
             created_at: now,
             ..Default::default()
         }
         .into_active_model();
 
-        aep.insert(db).await?;
+        let new_ep = aep.insert(db).await?;
+
+        if !prev_ep.aggregate {
+            continue;
+        }
+
+        let constituents = EEntryPointConstituent::find()
+            .filter(CEntryPointConstituent::EntryPoint.eq(prev_ep.id))
+            .all(db)
+            .await?;
+        for constituent in constituents {
+            MEntryPointConstituent {
+                id: EntryPointConstituentId::now_v7(),
+                entry_point: new_ep.id,
+                derivation: constituent.derivation,
+            }
+            .into_active_model()
+            .insert(db)
+            .await?;
+        }
     }
 
     Ok(())
@@ -9,7 +9,7 @@ mod previous_lookup;
 
 use super::TriggerError;
 use super::flake_snapshot::snapshot_flake_input_overrides;
-use super::new_evaluation::ensure_no_active_evaluation;
+use super::new_evaluation::{ensure_no_active_evaluation, ensure_no_active_evaluation_of_commit};
 use gradient_entity::build::BuildStatus;
 use gradient_entity::evaluation::EvaluationStatus;
 use gradient_types::*;
@@ -35,8 +35,58 @@ pub async fn trigger_restart_builds<C: ConnectionTrait>(
     let (prev_eval, prev_entry_points) =
         previous_lookup::previous_evaluation_with_entry_points(db, project.id).await?;
 
+    restart_from(db, project, &prev_eval, &prev_entry_points, None, None).await
+}
+
+/// Like [`trigger_restart_builds`], but re-runs a specific evaluation rather
+/// than the project's latest one (`/gradient retry-failed` on a PR). The new
+/// evaluation inherits `prev_eval`'s trigger and takes `source_comment`, or
+/// `prev_eval`'s own, so it still reports onto the pull request. Only an
+/// active evaluation of the same commit blocks the restart. A given
+/// `source_comment` keeps `prev_eval`'s PR `base_branch` for the eval diff.
+pub async fn trigger_restart_evaluation<C: ConnectionTrait>(
+    db: &C,
+    project: &MProject,
+    prev_eval: &MEvaluation,
+    source_comment: Option<serde_json::Value>,
+) -> Result<MEvaluation, TriggerError> {
+    ensure_no_active_evaluation_of_commit(db, project.id, prev_eval.commit).await?;
+
+    let prev_entry_points = previous_lookup::entry_points_of(db, prev_eval.id).await?;
+    let source_comment = match source_comment {
+        Some(mut sc) => {
+            if let Some(obj) = sc.as_object_mut()
+                && !obj.contains_key("base_branch")
+                && let Some(base) = crate::eval_diff::pr_base_branch(prev_eval)
+            {
+                obj.insert("base_branch".into(), base.into());
+            }
+            Some(sc)
+        }
+        None => prev_eval.source_comment.clone(),
+    };
+
+    restart_from(
+        db,
+        project,
+        prev_eval,
+        &prev_entry_points,
+        prev_eval.trigger,
+        source_comment,
+    )
+    .await
+}
+
+async fn restart_from<C: ConnectionTrait>(
+    db: &C,
+    project: &MProject,
+    prev_eval: &MEvaluation,
+    prev_entry_points: &[MEntryPoint],
+    trigger: Option<ProjectTriggerId>,
+    source_comment: Option<serde_json::Value>,
+) -> Result<MEvaluation, TriggerError> {
     let now = gradient_types::now();
-    let initial_status = restart_initial_status(db, &prev_entry_points).await?;
+    let initial_status = restart_initial_status(db, prev_entry_points).await?;
 
     let new_eval_id = EvaluationId::now_v7();
     let aevaluation = MEvaluation {
@@ -50,6 +100,9 @@ pub async fn trigger_restart_builds<C: ConnectionTrait>(
         created_at: now,
         updated_at: now,
         flake_source: prev_eval.flake_source.clone(),
+        branch: prev_eval.branch.clone(),
+        trigger,
+        source_comment,
         ..Default::default()
     }
     .into_active_model();
@@ -58,7 +111,7 @@ pub async fn trigger_restart_builds<C: ConnectionTrait>(
 
     snapshot_flake_input_overrides(db, project.id, new_eval.id).await?;
 
-    entry_points::copy_entry_points(db, &prev_entry_points, new_eval_id, now).await?;
+    entry_points::copy_entry_points(db, prev_entry_points, new_eval_id, now).await?;
 
     let mut aproject: AProject = project.clone().into();
     aproject.last_evaluation = Set(Some(new_eval_id));
@@ -23,10 +23,18 @@ pub(super) async fn previous_evaluation_with_entry_points<C: ConnectionTrait>(
         .await?
         .ok_or(TriggerError::NoPreviousEvaluation)?;
 
-    let prev_entry_points = EEntryPoint::find()
-        .filter(CEntryPoint::Evaluation.eq(prev_eval.id))
-        .all(db)
-        .await?;
+    let prev_entry_points = entry_points_of(db, prev_eval.id).await?;
 
     Ok((prev_eval, prev_entry_points))
 }
+
+/// Entry points recorded for `evaluation`.
+pub(super) async fn entry_points_of<C: ConnectionTrait>(
+    db: &C,
+    evaluation: EvaluationId,
+) -> Result<Vec<MEntryPoint>, TriggerError> {
+    Ok(EEntryPoint::find()
+        .filter(CEntryPoint::Evaluation.eq(evaluation))
+        .all(db)
+        .await?)
+}
@@ -53,6 +53,7 @@ async fn trigger_creates_queued_eval() {
         None,
         None,
         None,
+        None,
     )
     .await;
     assert!(result.is_ok(), "expected Ok, got: {:?}", result.err());
@@ -104,6 +105,7 @@ async fn trigger_drops_dangling_last_evaluation_pointer() {
         None,
         None,
         None,
+        None,
     )
     .await;
     assert!(result.is_ok(), "expected Ok, got: {:?}", result.err());
@@ -131,6 +133,7 @@ async fn trigger_already_in_progress() {
         None,
         None,
         None,
+        None,
     )
     .await;
     assert!(matches!(result, Err(TriggerError::AlreadyInProgress)));
@@ -163,6 +166,7 @@ async fn trigger_each_active_status_blocks() {
             None,
             None,
             None,
+            None,
         )
         .await;
         assert!(
@@ -208,6 +212,7 @@ async fn trigger_terminal_does_not_block() {
         None,
         None,
         None,
+        None,
     )
     .await;
     assert!(result.is_ok(), "terminal eval should not block new trigger");
@@ -252,6 +257,7 @@ async fn trigger_records_trigger_id() {
         None,
         None,
         None,
+        None,
     )
     .await;
     assert!(result.is_ok());
@@ -370,6 +376,63 @@ async fn restart_with_one_failed_inserts_building_eval() {
     assert_eq!(result.unwrap().status, EvaluationStatus::Building);
 }
 
+/// `/gradient retry-failed` restarts the PR's evaluation, not the project's
+/// latest, and keeps it attached to the pull request.
+#[tokio::test]
+async fn restart_evaluation_reuses_given_eval_and_pr_context() {
+    let project = make_project();
+    let trig = ProjectTriggerId::now_v7();
+    let prev_eval_id = EvaluationId::now_v7();
+    let prev_eval = {
+        let mut e = make_eval(prev_eval_id, EvaluationStatus::Failed);
+        e.trigger = Some(trig);
+        e.source_comment = Some(serde_json::json!({ "pr_number": 7 }));
+        e
+    };
+    let new_eval_id = EvaluationId::now_v7();
+    let drv = DerivationId::now_v7();
+
+    let inserted_eval = {
+        let mut e = make_eval(new_eval_id, EvaluationStatus::Building);
+        e.previous = Some(prev_eval_id);
+        e.trigger = Some(trig);
+        e.source_comment = prev_eval.source_comment.clone();
+        e
+    };
+
+    // No "latest evaluation" lookup: the entry points come straight from the
+    // given evaluation.
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([Vec::<evaluation::Model>::new()])
+        .append_query_results([vec![make_entry_point(prev_eval_id, drv)]])
+        .append_query_results([vec![make_anchor(drv, BuildStatus::FailedPermanent)]])
+        .append_query_results([vec![inserted_eval]])
+        .append_query_results([Vec::<gradient_entity::project_flake_input_override::Model>::new()])
+        .append_query_results([vec![make_entry_point(new_eval_id, drv)]])
+        .append_query_results([vec![project.clone()]])
+        .append_exec_results([MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }])
+        .into_connection();
+
+    let new_eval = trigger_restart_evaluation(&db, &project, &prev_eval, None)
+        .await
+        .expect("restart");
+    assert_eq!(new_eval.status, EvaluationStatus::Building);
+    assert_eq!(new_eval.previous, Some(prev_eval_id));
+    assert_eq!(new_eval.trigger, Some(trig));
+    assert_eq!(
+        new_eval.source_comment,
+        Some(serde_json::json!({ "pr_number": 7 }))
+    );
+
+    // The in-flight guard only looks at evaluations of the PR's commit.
+    let log = db.into_transaction_log();
+    let guard = format!("{:?}", log[0]);
+    assert!(guard.contains(r#""commit" = $"#), "guard query: {guard}");
+}
+
 fn open_pr_action(project_id: ProjectId) -> MProjectAction {
     MProjectAction {
         id: ProjectActionId::now_v7(),
@@ -0,0 +1,261 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Hydra-style aggregate entry points. An aggregate (`_hydraAggregate`) is a
+//! release gate over its `constituents`: it reports one status that is green
+//! only when its own build and every constituent succeeded. Constituents
+//! passed as derivations are also build inputs; constituents named by job are
+//! not, so the combined status is computed here rather than read off the
+//! aggregate's own anchor.
+
+use std::collections::{HashMap, HashSet};
+
+use gradient_entity::build::BuildStatus;
+use gradient_entity::derivation_build;
+use gradient_entity::entry_point::{self, Model as EntryPoint};
+use gradient_entity::entry_point_constituent::{self, Column as ConstituentColumn};
+use gradient_entity::evaluation_message::{self, MessageLevel};
+use gradient_entity::ids::{DerivationId, EntryPointId, EvaluationId};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseBackend, DbErr, EntityTrait, PaginatorTrait, QueryFilter,
+    Statement,
+};
+
+/// An aggregate entry point with its combined status.
+#[derive(Debug, Clone)]
+pub struct AggregateStatus {
+    pub entry_point: EntryPoint,
+    /// Combined status of the aggregate's own build and its constituents.
+    pub status: BuildStatus,
+    /// Each constituent derivation with its anchor status.
+    pub constituents: Vec<(DerivationId, BuildStatus)>,
+}
+
+/// Fold an aggregate's own status with its constituents' into one. The
+/// aggregate's own failure or abort wins; a failed constituent fails it as
+/// `DependencyFailed`; anything still running keeps it `Building`/pending;
+/// otherwise it reports its own success.
+pub fn combine_aggregate_status(own: BuildStatus, constituents: &[BuildStatus]) -> BuildStatus {
+    if own.is_terminal_failure() || own == BuildStatus::Aborted {
+        return own;
+    }
+    if constituents.iter().any(|s| s.is_terminal_failure()) {
+        return BuildStatus::DependencyFailed;
+    }
+    if constituents.contains(&BuildStatus::Aborted) {
+        return BuildStatus::Aborted;
+    }
+    if own == BuildStatus::Building || constituents.contains(&BuildStatus::Building) {
+        return BuildStatus::Building;
+    }
+    if own.is_in_progress() {
+        return own;
+    }
+    if constituents.iter().any(|s| s.is_in_progress()) {
+        return BuildStatus::Queued;
+    }
+
+    own
+}
+
+/// The status of an evaluation's release gate: every aggregate combined as
+/// the constituents of a succeeded pseudo-aggregate. `None` when the
+/// evaluation has no aggregates.
+pub fn evaluation_aggregate_status(aggregates: &[AggregateStatus]) -> Option<BuildStatus> {
+    if aggregates.is_empty() {
+        return None;
+    }
+
+    let statuses: Vec<BuildStatus> = aggregates.iter().map(|a| a.status).collect();
+    Some(combine_aggregate_status(BuildStatus::Completed, &statuses))
+}
+
+/// Every aggregate entry point of `evaluation_id` with its combined status.
+/// A derivation without an anchor yet counts as `Queued`; a constituent that
+/// never resolved to a derivation counts as failed.
+pub async fn aggregate_statuses<C: ConnectionTrait>(
+    db: &C,
+    evaluation_id: EvaluationId,
+) -> Result<Vec<AggregateStatus>, DbErr> {
+    let entry_points = entry_point::Entity::find()
+        .filter(entry_point::Column::Evaluation.eq(evaluation_id))
+        .filter(entry_point::Column::Aggregate.eq(true))
+        .all(db)
+        .await?;
+    if entry_points.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let ids: Vec<EntryPointId> = entry_points.iter().map(|ep| ep.id).collect();
+    let links = crate::fetch_in_chunks(&ids, |chunk| async move {
+        entry_point_constituent::Entity::find()
+            .filter(ConstituentColumn::EntryPoint.is_in(chunk))
+            .all(db)
+            .await
+    })
+    .await?;
+
+    let drv_ids: Vec<DerivationId> = entry_points
+        .iter()
+        .map(|ep| ep.derivation)
+        .chain(links.iter().map(|l| l.derivation))
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let status_by_drv: HashMap<DerivationId, BuildStatus> =
+        crate::fetch_in_chunks(&drv_ids, |chunk| async move {
+            derivation_build::Entity::find()
+                .filter(derivation_build::Column::Derivation.is_in(chunk))
+                .all(db)
+                .await
+        })
+        .await?
+        .into_iter()
+        .map(|a| (a.derivation, a.status))
+        .collect();
+    let status_of = |drv: DerivationId| {
+        status_by_drv
+            .get(&drv)
+            .copied()
+            .unwrap_or(BuildStatus::Queued)
+    };
+
+    let mut by_entry_point: HashMap<EntryPointId, Vec<DerivationId>> = HashMap::new();
+    for link in links {
+        by_entry_point
+            .entry(link.entry_point)
+            .or_default()
+            .push(link.derivation);
+    }
+
+    Ok(entry_points
+        .into_iter()
+        .map(|ep| {
+            let constituents: Vec<(DerivationId, BuildStatus)> = by_entry_point
+                .remove(&ep.id)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|drv| (drv, status_of(drv)))
+                .collect();
+            let unresolved = usize::try_from(ep.unresolved_constituents).unwrap_or(0);
+            let statuses: Vec<BuildStatus> = constituents
+                .iter()
+                .map(|&(_, s)| s)
+                .chain(std::iter::repeat_n(
+                    BuildStatus::FailedPermanent,
+                    unresolved,
+                ))
+                .collect();
+            AggregateStatus {
+                status: combine_aggregate_status(status_of(ep.derivation), &statuses),
+                entry_point: ep,
+                constituents,
+            }
+        })
+        .collect())
+}
+
+/// Whether `evaluation_id` has aggregates and its release gate is green:
+/// every aggregate succeeded and the evaluation recorded no errors. An
+/// evaluation error may have dropped an aggregate or one of its constituents
+/// before it ever got a row, so the aggregates alone cannot prove the gate.
+pub async fn release_gate_green<C: ConnectionTrait>(
+    db: &C,
+    evaluation_id: EvaluationId,
+) -> Result<bool, DbErr> {
+    let aggregates = aggregate_statuses(db, evaluation_id).await?;
+    if !matches!(
+        evaluation_aggregate_status(&aggregates),
+        Some(BuildStatus::Completed | BuildStatus::Substituted)
+    ) {
+        return Ok(false);
+    }
+
+    let errors = evaluation_message::Entity::find()
+        .filter(evaluation_message::Column::Evaluation.eq(evaluation_id))
+        .filter(evaluation_message::Column::Level.eq(MessageLevel::Error))
+        .count(db)
+        .await?;
+    Ok(errors == 0)
+}
+
+/// The aggregate entry points of `evaluation_id` whose status depends on
+/// `derivation`: the aggregate built from it, or any listing it as a
+/// constituent. Constituent links are matched through their entry point, so
+/// the lookup stays within the evaluation however many evaluations share the
+/// derivation.
+pub async fn aggregates_containing<C: ConnectionTrait>(
+    db: &C,
+    evaluation_id: EvaluationId,
+    derivation: DerivationId,
+) -> Result<Vec<EntryPoint>, DbErr> {
+    entry_point::Entity::find()
+        .from_raw_sql(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"SELECT ep.* FROM entry_point ep
+               WHERE ep.evaluation = $1
+                 AND ep.aggregate
+                 AND (ep.derivation = $2
+                      OR EXISTS (SELECT 1 FROM entry_point_constituent c
+                                 WHERE c.entry_point = ep.id AND c.derivation = $2))"#,
+            [
+                evaluation_id.into_inner().into(),
+                derivation.into_inner().into(),
+            ],
+        ))
+        .all(db)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aggregate_is_green_only_when_every_constituent_is() {
+        use BuildStatus::*;
+        assert_eq!(
+            combine_aggregate_status(Completed, &[Completed, Substituted]),
+            Completed
+        );
+        assert_eq!(
+            combine_aggregate_status(Completed, &[Completed, FailedPermanent]),
+            DependencyFailed
+        );
+        assert_eq!(
+            combine_aggregate_status(FailedTimeout, &[Completed]),
+            FailedTimeout
+        );
+        assert_eq!(combine_aggregate_status(Completed, &[Aborted]), Aborted);
+        assert_eq!(combine_aggregate_status(Created, &[Building]), Building);
+        assert_eq!(combine_aggregate_status(Completed, &[Queued]), Queued);
+        assert_eq!(combine_aggregate_status(Substituted, &[]), Substituted);
+    }
+
+    #[test]
+    fn evaluation_gate_needs_every_aggregate() {
+        let aggregate = |status| AggregateStatus {
+            entry_point: EntryPoint::default(),
+            status,
+            constituents: Vec::new(),
+        };
+        assert_eq!(evaluation_aggregate_status(&[]), None);
+        assert_eq!(
+            evaluation_aggregate_status(&[
+                aggregate(BuildStatus::Completed),
+                aggregate(BuildStatus::Substituted)
+            ]),
+            Some(BuildStatus::Completed)
+        );
+        assert_eq!(
+            evaluation_aggregate_status(&[
+                aggregate(BuildStatus::Completed),
+                aggregate(BuildStatus::DependencyFailed)
+            ]),
+            Some(BuildStatus::DependencyFailed)
+        );
+    }
+}
@@ -14,21 +14,24 @@ use gradient_entity::build_attempt::{AttemptFailureReason, AttemptOutcome, Colum
 use gradient_entity::ids::{
     BuildAttemptId, BuildJobId, DerivationBuildId, DispatchedJobId, EvaluationId,
 };
+use gradient_types::FailureCause;
 use sea_orm::ActiveValue::Set;
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseBackend, DbErr, EntityTrait,
-    IntoActiveModel, PaginatorTrait, QueryFilter, QueryOrder, Statement,
+    IntoActiveModel, PaginatorTrait, QueryFilter, QueryOrder, Statement, sea_query::Expr,
 };
 use uuid::Uuid;
 
 /// Open a new attempt for an anchor (`derivation_build`), attributed to
-/// `build_job`, under `dispatched_job`.
+/// `build_job`, under `dispatched_job`. `flaky_rerun` marks the re-run of a
+/// failure judged flaky.
 pub async fn open_attempt<C: ConnectionTrait>(
     db: &C,
     build_job: BuildJobId,
     derivation_build: DerivationBuildId,
     dispatched_job: DispatchedJobId,
     substitute: bool,
+    flaky_rerun: bool,
     build_context: serde_json::Value,
 ) -> Result<Model, DbErr> {
     Model {
@@ -37,6 +40,7 @@ pub async fn open_attempt<C: ConnectionTrait>(
         derivation_build,
         dispatched_job,
         substitute,
+        flaky_rerun,
         outcome: AttemptOutcome::Running,
         build_context,
         created_at: gradient_types::now(),
@@ -201,6 +205,34 @@ pub async fn fail_latest_attempt<C: ConnectionTrait>(
     Ok(())
 }
 
+/// Record the structured cause recognised in attempt `attempt_id`'s log.
+pub async fn set_attempt_failure_cause<C: ConnectionTrait>(
+    db: &C,
+    attempt_id: BuildAttemptId,
+    cause: &FailureCause,
+) -> Result<(), DbErr> {
+    let value = serde_json::to_value(cause).map_err(|e| DbErr::Custom(e.to_string()))?;
+    Entity::update_many()
+        .col_expr(Column::FailureCause, Expr::value(value))
+        .filter(Column::Id.eq(attempt_id))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+/// The failure cause recorded on the anchor's latest attempt, if any. A
+/// value that no longer parses (written by a newer server) reads as `None`.
+pub async fn latest_failure_cause<C: ConnectionTrait>(
+    db: &C,
+    derivation_build: DerivationBuildId,
+) -> Result<Option<FailureCause>, DbErr> {
+    Ok(latest_attempt(db, derivation_build)
+        .await?
+        .and_then(|a| a.failure_cause)
+        .and_then(|v| serde_json::from_value(v).ok()))
+}
+
 /// Count `InputsUnavailable` attempts recorded against an anchor across its whole
 /// history (every driving evaluation). Feeds the self-heal circuit breaker: each
 /// failed eval reconciles the cache and the next one retries, so the count is the
@@ -0,0 +1,179 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Policy-based eviction for caches at their `max_storage_gb`. Candidates are
+//! the cache's fully-cached paths that no retained evaluation references and
+//! that no other path still signed by the cache references at runtime and no
+//! pin of the cache keeps (see [`crate::pins`]), so a
+//! closure is always evicted from its roots downward and never left with a
+//! hole. Eviction drops the cache's signature; the `cached_path` row (and the
+//! NAR object, removed by the caller) goes only once no cache signs it.
+
+use gradient_entity::cache::CacheEvictionPolicy;
+use gradient_types::ids::CacheId;
+use sea_orm::{ConnectionTrait, DatabaseBackend, DbErr, FromQueryResult, Statement};
+
+/// Fraction of `max_storage_gb` an eviction pass frees down to, so a full
+/// cache is not re-evicted on every push.
+pub const EVICTION_LOW_WATER_PERCENT: i64 = 90;
+
+/// A path eviction may remove from a cache.
+#[derive(Debug, Clone, PartialEq, FromQueryResult)]
+pub struct EvictionCandidate {
+    pub hash: String,
+    pub package: String,
+    pub file_size: i64,
+}
+
+/// Bytes a cache must shed, or `None` when it is within its limit or has
+/// none. A cache over its limit frees down to the low-water mark.
+pub fn eviction_bytes_needed(max_storage_gb: i32, used: i64) -> Option<i64> {
+    if max_storage_gb <= 0 {
+        return None;
+    }
+    let limit = max_storage_gb as i64 * 1024 * 1024 * 1024;
+    if limit - used >= crate::STORAGE_HEADROOM_BYTES {
+        return None;
+    }
+
+    Some(used - limit * EVICTION_LOW_WATER_PERCENT / 100)
+}
+
+/// Candidate query. `$1` is the cache, `$2` the batch size; the policy picks
+/// the ordering. A path is retained while any evaluation still has a build
+/// job producing it, built from it (`.drv`) or using it as an input source,
+/// or while it is in the closure of a live pin of the cache.
+fn candidates_sql(policy: CacheEvictionPolicy) -> Option<String> {
+    let order = match policy {
+        CacheEvictionPolicy::Disabled => return None,
+        CacheEvictionPolicy::Lru => "COALESCE(cp.last_accessed_at, cp.created_at), cp.created_at",
+        CacheEvictionPolicy::OldestFirst => "cp.created_at",
+    };
+
+    let pinned = crate::pins::pinned_closure_cte_body();
+    Some(format!(
+        r#"
+        WITH RECURSIVE {pinned}
+        SELECT cp.hash, cp.package, COALESCE(cp.file_size, 0) AS file_size
+        FROM cached_path cp
+        JOIN cached_path_signature s ON s.cached_path = cp.id AND s.cache = $1
+        WHERE cp.file_hash IS NOT NULL
+          AND NOT EXISTS (
+              SELECT 1 FROM derivation_output dout
+              JOIN build_job bj ON bj.derivation = dout.derivation
+              WHERE dout.hash = cp.hash)
+          AND NOT EXISTS (
+              SELECT 1 FROM derivation d
+              JOIN build_job bj ON bj.derivation = d.id
+              WHERE d.hash = cp.hash)
+          AND NOT EXISTS (
+              SELECT 1 FROM derivation_input_source src
+              JOIN build_job bj ON bj.derivation = src.derivation
+              WHERE src.hash = cp.hash)
+          AND NOT EXISTS (
+              SELECT 1 FROM cached_path_reference r
+              JOIN cached_path referrer ON referrer.hash = r.referrer
+              JOIN cached_path_signature rs ON rs.cached_path = referrer.id AND rs.cache = $1
+              WHERE r.reference_hash = cp.hash AND r.referrer <> cp.hash)
+          AND NOT EXISTS (
+              SELECT 1 FROM pinned p WHERE p.cache = $1 AND p.hash = cp.hash)
+        ORDER BY {order}
+        LIMIT $2
+        "#
+    ))
+}
+
+/// Up to `limit` eviction candidates for `cache` in `policy` order. Empty for
+/// [`CacheEvictionPolicy::Disabled`].
+pub async fn eviction_candidates<C: ConnectionTrait>(
+    db: &C,
+    cache: CacheId,
+    policy: CacheEvictionPolicy,
+    limit: u64,
+) -> Result<Vec<EvictionCandidate>, DbErr> {
+    let Some(sql) = candidates_sql(policy) else {
+        return Ok(Vec::new());
+    };
+
+    EvictionCandidate::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        sql,
+        [cache.into_inner().into(), (limit as i64).into()],
+    ))
+    .all(db)
+    .await
+}
+
+/// Drop `cache`'s signatures on `hashes`, then every `cached_path` among them
+/// that no cache signs anymore, clearing the gate flags those rows backed.
+/// Run it inside a transaction. Returns the hashes whose row was deleted; the
+/// caller removes their NAR objects.
+pub async fn evict_cached_paths<C: ConnectionTrait>(
+    db: &C,
+    cache: CacheId,
+    hashes: &[String],
+) -> Result<Vec<String>, DbErr> {
+    if hashes.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        r#"
+        DELETE FROM cached_path_signature s
+        USING cached_path cp
+        WHERE s.cached_path = cp.id AND s.cache = $1 AND cp.hash = ANY($2)
+        "#,
+        [cache.into_inner().into(), hashes.to_vec().into()],
+    ))
+    .await?;
+
+    let dropped: Vec<String> = db
+        .query_all(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"
+            DELETE FROM cached_path cp
+            WHERE cp.hash = ANY($1)
+              AND NOT EXISTS (
+                SELECT 1 FROM cached_path_signature s WHERE s.cached_path = cp.id)
+            RETURNING cp.hash
+            "#,
+            [hashes.to_vec().into()],
+        ))
+        .await?
+        .into_iter()
+        .filter_map(|r| r.try_get::<String>("", "hash").ok())
+        .collect();
+
+    crate::clear_gate_flags_for_hashes(db, &dropped).await?;
+    Ok(dropped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GIB: i64 = 1024 * 1024 * 1024;
+
+    #[test]
+    fn eviction_frees_down_to_low_water() {
+        assert_eq!(eviction_bytes_needed(0, 100 * GIB), None);
+        assert_eq!(eviction_bytes_needed(10, 5 * GIB), None);
+        assert_eq!(eviction_bytes_needed(10, 10 * GIB), Some(GIB));
+        assert_eq!(eviction_bytes_needed(10, 12 * GIB), Some(3 * GIB));
+    }
+
+    #[test]
+    fn policy_picks_candidate_order() {
+        assert!(candidates_sql(CacheEvictionPolicy::Disabled).is_none());
+        let lru = candidates_sql(CacheEvictionPolicy::Lru).unwrap();
+        assert!(lru.contains("ORDER BY COALESCE(cp.last_accessed_at, cp.created_at)"));
+        let oldest = candidates_sql(CacheEvictionPolicy::OldestFirst).unwrap();
+        assert!(oldest.contains("ORDER BY cp.created_at"));
+        assert!(!oldest.contains("last_accessed_at"));
+        assert!(oldest.contains("FROM pinned p WHERE p.cache = $1"));
+    }
+}
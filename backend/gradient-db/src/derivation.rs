@@ -150,6 +150,25 @@ impl Derivation {
         .unwrap_or(true)
     }
 
+    /// The raw `constituents` of a Hydra-style aggregate (`_hydraAggregate`
+    /// set), or `None` for any other derivation. Each entry is either a store
+    /// path (a derivation passed as constituent, stringified to its output) or
+    /// a job name.
+    pub fn hydra_constituents(&self) -> Option<Vec<String>> {
+        let attrs = self.structured_attrs();
+        let env = |key: &str| self.environment.get(key);
+        if !Self::attr_bool(attrs.as_ref(), env("_hydraAggregate"), "_hydraAggregate")
+            .unwrap_or(false)
+        {
+            return None;
+        }
+        Some(Self::attr_strings(
+            attrs.as_ref(),
+            env("constituents"),
+            "constituents",
+        ))
+    }
+
     /// Extract all build-relevant attributes in one pass.
     pub fn build_meta(&self) -> BuildMeta {
         let attrs = self.structured_attrs();
@@ -476,6 +495,16 @@ mod tests {
         assert_eq!(parse_drv(bad).unwrap().build_meta().timeout_secs, None);
     }
 
+    #[test]
+    fn hydra_constituents_only_for_aggregates() {
+        let aggregate = br#"Derive([("out","/nix/store/abc-release","","")],[("/nix/store/xyz-hello.drv",["out"])],[],"x86_64-linux","/nix/store/bash",[],[("_hydraAggregate","1"),("constituents","/nix/store/def-hello tests.unit"),("name","release")])"#;
+        assert_eq!(
+            parse_drv(aggregate).unwrap().hydra_constituents(),
+            Some(vec!["/nix/store/def-hello".into(), "tests.unit".into()])
+        );
+        assert_eq!(parse_drv(EXAMPLE).unwrap().hydra_constituents(), None);
+    }
+
     #[test]
     fn pname_prefers_env_then_strips_version() {
         assert_eq!(
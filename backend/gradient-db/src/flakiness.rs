@@ -0,0 +1,233 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Flaky-build history. Outcomes are aggregated per `(project, name)` in
+//! `build_flakiness`, where `name` is the derivation's `pname` (else its
+//! name) so the history survives across evaluations and input changes. The
+//! per-build side lives on `build_attempt`: `flaky` marks a failure that was
+//! re-run, `flaky_rerun` marks the re-run itself.
+
+use std::collections::HashMap;
+
+use gradient_entity::build_flakiness::{Column, Entity, Model};
+use gradient_entity::ids::{BuildFlakinessId, DerivationBuildId, ProjectId};
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseBackend, DbErr, EntityTrait,
+    IntoActiveModel, QueryFilter, QueryOrder, Statement,
+};
+use uuid::Uuid;
+
+/// The `(project, name)` key a build's outcomes are recorded under.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlakySubject {
+    pub project: ProjectId,
+    pub name: String,
+}
+
+/// Resolve the flakiness key of an anchor from its latest attempt's driving
+/// evaluation. `None` when the anchor was never dispatched or its evaluation
+/// has no project (or was GC'd).
+pub async fn flaky_subject<C: ConnectionTrait>(
+    db: &C,
+    derivation_build: DerivationBuildId,
+) -> Result<Option<FlakySubject>, DbErr> {
+    let row = db
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"SELECT e.project AS project, COALESCE(d.pname, d.name) AS name
+               FROM build_attempt ba
+               JOIN build_job bj ON bj.id = ba.build_job
+               JOIN evaluation e ON e.id = bj.evaluation
+               JOIN derivation_build anchor ON anchor.id = ba.derivation_build
+               JOIN derivation d ON d.id = anchor.derivation
+               WHERE ba.derivation_build = $1 AND e.project IS NOT NULL
+               ORDER BY ba.created_at DESC
+               LIMIT 1"#,
+            [derivation_build.into_inner().into()],
+        ))
+        .await?;
+
+    let Some(r) = row else {
+        return Ok(None);
+    };
+    Ok(Some(FlakySubject {
+        project: ProjectId::new(r.try_get::<Uuid>("", "project")?),
+        name: r.try_get::<String>("", "name")?,
+    }))
+}
+
+/// The recorded history of `subject`, if any.
+pub async fn flakiness_of<C: ConnectionTrait>(
+    db: &C,
+    subject: &FlakySubject,
+) -> Result<Option<Model>, DbErr> {
+    Entity::find()
+        .filter(Column::Project.eq(subject.project))
+        .filter(Column::Name.eq(&subject.name))
+        .one(db)
+        .await
+}
+
+/// Every recorded history of a project, flakiest first.
+pub async fn project_flakiness<C: ConnectionTrait>(
+    db: &C,
+    project: ProjectId,
+) -> Result<Vec<Model>, DbErr> {
+    Entity::find()
+        .filter(Column::Project.eq(project))
+        .order_by_desc(Column::RerunPasses)
+        .order_by_desc(Column::Flips)
+        .order_by_desc(Column::Failures)
+        .order_by_asc(Column::Name)
+        .all(db)
+        .await
+}
+
+/// Record one final outcome of `subject`. A flip is counted when it differs
+/// from the previous final outcome; `rerun_passed` marks a pass that came from
+/// a flaky re-run.
+pub async fn record_flaky_outcome<C: ConnectionTrait>(
+    db: &C,
+    subject: &FlakySubject,
+    passed: bool,
+    rerun_passed: bool,
+) -> Result<(), DbErr> {
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        r#"INSERT INTO build_flakiness
+             (id, project, name, runs, failures, flips, reruns, rerun_passes, last_passed, updated_at)
+           VALUES ($1, $2, $3, 1, $4, 0, 0, $5, $6, $7)
+           ON CONFLICT (project, name) DO UPDATE SET
+             runs = build_flakiness.runs + 1,
+             failures = build_flakiness.failures + EXCLUDED.failures,
+             flips = build_flakiness.flips
+               + CASE WHEN build_flakiness.last_passed <> EXCLUDED.last_passed THEN 1 ELSE 0 END,
+             rerun_passes = build_flakiness.rerun_passes + EXCLUDED.rerun_passes,
+             last_passed = EXCLUDED.last_passed,
+             updated_at = EXCLUDED.updated_at"#,
+        [
+            BuildFlakinessId::now_v7().into_inner().into(),
+            subject.project.into_inner().into(),
+            subject.name.clone().into(),
+            i64::from(!passed).into(),
+            i64::from(rerun_passed).into(),
+            passed.into(),
+            gradient_types::now().into(),
+        ],
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Count one flaky re-run of `subject`. Does not touch the outcome counters:
+/// the re-run's own result is recorded once it is final.
+pub async fn record_flaky_rerun<C: ConnectionTrait>(
+    db: &C,
+    subject: &FlakySubject,
+) -> Result<(), DbErr> {
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        r#"INSERT INTO build_flakiness
+             (id, project, name, runs, failures, flips, reruns, rerun_passes, last_passed, updated_at)
+           VALUES ($1, $2, $3, 0, 0, 0, 1, 0, NULL, $4)
+           ON CONFLICT (project, name) DO UPDATE SET
+             reruns = build_flakiness.reruns + 1,
+             updated_at = EXCLUDED.updated_at"#,
+        [
+            BuildFlakinessId::now_v7().into_inner().into(),
+            subject.project.into_inner().into(),
+            subject.name.clone().into(),
+            gradient_types::now().into(),
+        ],
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Number of failures of an anchor already re-run as flaky for the
+/// evaluation that drove its latest attempt. The anchor is shared across
+/// evaluations, so each evaluation gets its own re-run budget instead of one
+/// that runs out for good.
+pub async fn flaky_attempt_count<C: ConnectionTrait>(
+    db: &C,
+    derivation_build: DerivationBuildId,
+) -> Result<i64, DbErr> {
+    let row = db
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"WITH latest AS (
+                 SELECT bj.evaluation
+                 FROM build_attempt ba
+                 JOIN build_job bj ON bj.id = ba.build_job
+                 WHERE ba.derivation_build = $1
+                 ORDER BY ba.created_at DESC
+                 LIMIT 1
+               )
+               SELECT COUNT(*) AS n
+               FROM build_attempt ba
+               JOIN build_job bj ON bj.id = ba.build_job
+               JOIN latest ON latest.evaluation = bj.evaluation
+               WHERE ba.derivation_build = $1 AND ba.flaky"#,
+            [derivation_build.into_inner().into()],
+        ))
+        .await?;
+    Ok(row
+        .map(|r| r.try_get::<i64>("", "n"))
+        .transpose()?
+        .unwrap_or(0))
+}
+
+/// Mark the anchor's latest (failed) attempt as flaky: a re-run follows.
+pub async fn mark_latest_attempt_flaky<C: ConnectionTrait>(
+    db: &C,
+    derivation_build: DerivationBuildId,
+) -> Result<(), DbErr> {
+    if let Some(att) = crate::latest_attempt(db, derivation_build).await? {
+        let mut a = att.into_active_model();
+        a.flaky = Set(true);
+        a.update(db).await?;
+    }
+
+    Ok(())
+}
+
+/// For each anchor whose latest attempt failed as flaky, the worker that ran
+/// that attempt - the one its re-run should avoid. Anchors without a pending
+/// flaky re-run are absent.
+pub async fn flaky_rerun_avoid_workers<C: ConnectionTrait>(
+    db: &C,
+    anchors: &[DerivationBuildId],
+) -> Result<HashMap<DerivationBuildId, String>, DbErr> {
+    let rows = crate::fetch_in_chunks(anchors, |chunk| {
+        let ids: Vec<Uuid> = chunk.iter().map(|a| a.into_inner()).collect();
+        async move {
+            db.query_all(Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"SELECT latest.derivation_build AS anchor, dj.worker_id AS worker_id
+                   FROM (
+                     SELECT DISTINCT ON (derivation_build) derivation_build, dispatched_job, flaky
+                     FROM build_attempt
+                     WHERE derivation_build = ANY($1)
+                     ORDER BY derivation_build, created_at DESC
+                   ) latest
+                   JOIN dispatched_job dj ON dj.id = latest.dispatched_job
+                   WHERE latest.flaky"#,
+                [ids.into()],
+            ))
+            .await
+        }
+    })
+    .await?;
+
+    let mut out = HashMap::new();
+    for r in rows {
+        let anchor = DerivationBuildId::new(r.try_get::<Uuid>("", "anchor")?);
+        out.insert(anchor, r.try_get::<String>("", "worker_id")?);
+    }
+
+    Ok(out)
+}
@@ -23,7 +23,8 @@ use gradient_types::*;
 ///
 /// Handles DB deletion, build log removal, NAR cache files, and GC root symlinks.
 /// Skipped entirely while the project has any active evaluation, so an in-flight
-/// run never loses NARs it is about to reuse.
+/// run never loses NARs it is about to reuse. Evaluations held by a live cache
+/// pin are never deleted.
 pub async fn gc_project_evaluations(
     ctx: &DbContext,
     project_id: ProjectId,
@@ -43,12 +44,18 @@ pub async fn gc_project_evaluations(
 
     let evals: Vec<(EvaluationStatus, chrono::NaiveDateTime)> =
         all_evals.iter().map(|e| (e.status, e.updated_at)).collect();
-    let delete_indices = evaluations_to_gc(
+    let pinned = crate::pinned_evaluations(&ctx.worker_db)
+        .await
+        .context("GC: failed to query pinned evaluations")?;
+    let delete_indices: Vec<usize> = evaluations_to_gc(
         &evals,
         keep,
         ctx.config.storage.gc_wedged_eval_hours,
         gradient_types::now(),
-    );
+    )
+    .into_iter()
+    .filter(|&i| !pinned.contains(&all_evals[i].id))
+    .collect();
     if delete_indices.is_empty() {
         return Ok(());
     }
@@ -374,12 +381,20 @@ pub async fn gc_orphan_derivations(ctx: &DbContext, grace_hours: i64) -> Result<
         .collect()
     };
 
-    let to_delete = reclaimable_after_delete(
+    // A pinned closure keeps its NARs and `cached_path` rows even once the
+    // derivations that produced them are gone.
+    let pinned = crate::pinned_paths(db)
+        .await
+        .context("GC: failed to query pinned paths")?;
+    let to_delete: Vec<String> = reclaimable_after_delete(
         deleted_hashes,
         &still_referenced,
         deleted_drv_hashes,
         &surviving_drv_hashes,
-    );
+    )
+    .into_iter()
+    .filter(|hash| !pinned.contains(hash))
+    .collect();
 
     for hash in &to_delete {
         if let Err(e) = ctx.storage.nar_storage.delete(hash).await {
@@ -5,8 +5,10 @@
  */
 
 pub mod admin_tasks;
+pub mod aggregate;
 pub mod base_workers;
 pub mod build_attempt;
+pub mod cache_eviction;
 pub mod cache_reach;
 pub mod cache_storage;
 pub mod cache_upstream;
@@ -20,16 +22,20 @@ pub mod dependency_graph;
 pub mod derivation;
 pub mod draining;
 pub mod drv_output_spec;
+pub mod flakiness;
 pub mod gc;
 pub mod graph_sql;
+pub mod nar_chunks;
 pub mod org_cache;
 pub mod org_derivations;
 pub mod org_workers;
 pub mod permissions;
+pub mod pins;
 pub mod pool;
 pub mod project_board;
 pub mod promotion;
 pub mod reachability;
+pub mod realisations;
 pub mod reconcile;
 pub mod recovery;
 pub mod retention;
@@ -40,7 +46,12 @@ pub mod status;
 pub mod status_reactor;
 pub mod status_sql;
 
+pub use self::aggregate::*;
 pub use self::build_attempt::*;
+pub use self::cache_eviction::{
+    EVICTION_LOW_WATER_PERCENT, EvictionCandidate, evict_cached_paths, eviction_bytes_needed,
+    eviction_candidates,
+};
 pub use self::cache_reach::*;
 pub use self::cache_storage::{
     MissingInputDiagnosis, STORAGE_HEADROOM_BYTES, cache_used_bytes,
@@ -63,13 +74,20 @@ pub use self::dependency_graph::*;
 pub use self::derivation::*;
 pub use self::draining::{park_active_evals, unpark_draining_evals};
 pub use self::drv_output_spec::DrvOutputSpec;
+pub use self::flakiness::*;
 pub use self::gc::*;
 pub use self::graph_sql::{
     ClosureDirection, dependency_closure_cte, eval_closure_cte, reachable_derivations_cte,
 };
-pub use self::org_cache::org_has_writable_cache;
+pub use self::nar_chunks::{
+    ChunkRecord, ChunkingCandidate, NarDedupStats, ReleasedChunks, chunked_nar_by_flat_file_hash,
+    chunking_candidates, has_dead_chunked_nars, nar_dedup_stats, record_chunked_nar,
+    release_dead_chunked_nars, serves_file_hash, unrecorded_chunks,
+};
+pub use self::org_cache::{cache_writer_organizations, org_has_writable_cache};
 pub use self::org_derivations::derivation_ids_for_org;
 pub use self::org_workers::org_has_eval_capable_worker_registration;
+pub use self::pins::{PinnedPaths, pinned_closure_cte_body, pinned_evaluations, pinned_paths};
 pub use self::pool::{CacheDb, WebDb, WorkerDb};
 pub use self::project_board::*;
 pub use self::promotion::{
@@ -82,6 +100,7 @@ pub use self::reachability::{
     build_jobs_for_derivation, build_jobs_for_derivations, derivation_is_reachable,
     eval_anchor_statuses, evals_referencing_derivation,
 };
+pub use self::realisations::{realise_floating_outputs, record_realised_output};
 pub use self::reconcile::{ReconcileReport, ReconcileScope, reconcile_build_graph};
 pub use self::recovery::recover_interrupted_work;
 pub use self::runtime_closure::*;
@@ -0,0 +1,372 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Bookkeeping for the chunked NAR layout (`gradient_storage::nar_chunk`).
+//! `nar_chunked` records which NARs were converted and the `file_hash` of
+//! their chunked form, `nar_chunk_ref` their chunks in order, and
+//! `nar_chunk.refcount` how many references each stored chunk has. A chunked
+//! NAR is released once no `cached_path` serves that exact `file_hash`
+//! anymore - the path was deleted, or re-uploaded flat.
+
+use gradient_types::ids::CacheId;
+use sea_orm::{ConnectionTrait, DatabaseBackend, DbErr, FromQueryResult, Statement};
+
+/// A fully-cached flat NAR the chunking pass may convert.
+#[derive(Debug, Clone, PartialEq, FromQueryResult)]
+pub struct ChunkingCandidate {
+    pub hash: String,
+    pub file_hash: String,
+    pub file_size: i64,
+}
+
+/// One chunk of a converted NAR, in serving order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChunkRecord {
+    pub hash: String,
+    pub size: i64,
+    pub raw_size: i64,
+}
+
+/// Chunked NARs released by [`release_dead_chunked_nars`]: their manifests
+/// and the chunks nothing references anymore are for the caller to delete.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReleasedChunks {
+    pub nars: Vec<String>,
+    pub chunks: Vec<String>,
+}
+
+/// Deduplication figures of one cache for `/caches/{cache}/stats`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, FromQueryResult)]
+pub struct NarDedupStats {
+    /// Paths of the cache stored chunked.
+    pub chunked_nars: i64,
+    /// Compressed bytes of the cache's flat NARs.
+    pub flat_bytes: i64,
+    /// Compressed bytes of the distinct chunks the cache's chunked NARs use,
+    /// shared chunks counted once.
+    pub chunk_bytes: i64,
+}
+
+impl NarDedupStats {
+    /// Bytes the cache actually occupies in storage.
+    pub fn stored_bytes(&self) -> i64 {
+        self.flat_bytes + self.chunk_bytes
+    }
+
+    /// `logical_bytes` (the summed `file_size` of the cache's paths) over
+    /// [`Self::stored_bytes`]; `1.0` when nothing is stored.
+    pub fn dedup_ratio(&self, logical_bytes: i64) -> f64 {
+        let stored = self.stored_bytes();
+        if stored <= 0 || logical_bytes <= 0 {
+            return 1.0;
+        }
+        logical_bytes as f64 / stored as f64
+    }
+}
+
+const CANDIDATES_SQL: &str = r#"
+    SELECT cp.hash, cp.file_hash, cp.file_size
+    FROM cached_path cp
+    WHERE cp.file_hash IS NOT NULL
+      AND cp.file_size >= $1
+      AND NOT EXISTS (SELECT 1 FROM nar_chunked nc WHERE nc.hash = cp.hash)
+      AND cp.hash > $3
+    ORDER BY cp.hash
+    LIMIT $2
+"#;
+
+/// Up to `limit` flat NARs of at least `min_bytes` whose hash sorts after
+/// `after`, in hash order, so a pass pages through them once even when some
+/// cannot be converted.
+pub async fn chunking_candidates<C: ConnectionTrait>(
+    db: &C,
+    min_bytes: i64,
+    after: &str,
+    limit: u64,
+) -> Result<Vec<ChunkingCandidate>, DbErr> {
+    ChunkingCandidate::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        CANDIDATES_SQL,
+        [min_bytes.into(), (limit as i64).into(), after.into()],
+    ))
+    .all(db)
+    .await
+}
+
+/// Records the chunked form of `candidate`: points its `cached_path` at the
+/// new `file_hash`/`file_size` and takes a reference on every chunk. Run it
+/// inside a transaction. Returns `false` (and writes nothing) when the path
+/// changed since it was picked - deleted or re-uploaded - so the caller
+/// discards the conversion.
+pub async fn record_chunked_nar<C: ConnectionTrait>(
+    db: &C,
+    candidate: &ChunkingCandidate,
+    file_hash: &str,
+    chunks: &[ChunkRecord],
+) -> Result<bool, DbErr> {
+    let file_size: i64 = chunks.iter().map(|c| c.size).sum();
+    let updated = db
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"
+            UPDATE cached_path SET file_hash = $3, file_size = $4
+            WHERE hash = $1 AND file_hash = $2
+            "#,
+            [
+                candidate.hash.clone().into(),
+                candidate.file_hash.clone().into(),
+                file_hash.into(),
+                file_size.into(),
+            ],
+        ))
+        .await?;
+    if updated.rows_affected() == 0 {
+        return Ok(false);
+    }
+
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        r#"
+        INSERT INTO nar_chunked
+            (hash, file_hash, file_size, flat_file_hash, flat_file_size, chunk_count, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, (now() AT TIME ZONE 'UTC'))
+        "#,
+        [
+            candidate.hash.clone().into(),
+            file_hash.into(),
+            file_size.into(),
+            candidate.file_hash.clone().into(),
+            candidate.file_size.into(),
+            (chunks.len() as i32).into(),
+        ],
+    ))
+    .await?;
+
+    let hashes: Vec<String> = chunks.iter().map(|c| c.hash.clone()).collect();
+    let sizes: Vec<i64> = chunks.iter().map(|c| c.size).collect();
+    let raw_sizes: Vec<i64> = chunks.iter().map(|c| c.raw_size).collect();
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        r#"
+        INSERT INTO nar_chunk (hash, size, raw_size, refcount, created_at)
+        SELECT t.hash, MIN(t.size), MIN(t.raw_size), COUNT(*), (now() AT TIME ZONE 'UTC')
+        FROM unnest($1::text[], $2::bigint[], $3::bigint[]) AS t(hash, size, raw_size)
+        GROUP BY t.hash
+        ON CONFLICT (hash) DO UPDATE SET refcount = nar_chunk.refcount + EXCLUDED.refcount
+        "#,
+        [hashes.clone().into(), sizes.into(), raw_sizes.into()],
+    ))
+    .await?;
+
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        r#"
+        INSERT INTO nar_chunk_ref (nar, idx, chunk)
+        SELECT $1, (t.idx - 1)::int, t.chunk
+        FROM unnest($2::text[]) WITH ORDINALITY AS t(chunk, idx)
+        "#,
+        [candidate.hash.clone().into(), hashes.into()],
+    ))
+    .await?;
+
+    Ok(true)
+}
+
+/// Chunked NARs whose `cached_path` no longer serves their chunked
+/// `file_hash`.
+const DEAD_CHUNKED_WHERE: &str = r#"
+    NOT EXISTS (
+        SELECT 1 FROM cached_path cp
+        WHERE cp.hash = nc.hash AND cp.file_hash = nc.file_hash)
+"#;
+
+/// Whether [`release_dead_chunked_nars`] has anything to do.
+pub async fn has_dead_chunked_nars<C: ConnectionTrait>(db: &C) -> Result<bool, DbErr> {
+    Ok(db
+        .query_one(Statement::from_string(
+            DatabaseBackend::Postgres,
+            format!("SELECT 1 AS one FROM nar_chunked nc WHERE {DEAD_CHUNKED_WHERE} LIMIT 1"),
+        ))
+        .await?
+        .is_some())
+}
+
+/// Drops every dead chunked NAR and its chunk references, then every chunk
+/// left without references. Run it inside a transaction; the caller deletes
+/// the returned manifests and chunk objects after commit.
+pub async fn release_dead_chunked_nars<C: ConnectionTrait>(
+    db: &C,
+) -> Result<ReleasedChunks, DbErr> {
+    let nars: Vec<String> = db
+        .query_all(Statement::from_string(
+            DatabaseBackend::Postgres,
+            format!(
+                "SELECT nc.hash FROM nar_chunked nc WHERE {DEAD_CHUNKED_WHERE} FOR UPDATE OF nc"
+            ),
+        ))
+        .await?
+        .into_iter()
+        .filter_map(|r| r.try_get::<String>("", "hash").ok())
+        .collect();
+    if nars.is_empty() {
+        return Ok(ReleasedChunks::default());
+    }
+
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        r#"
+        UPDATE nar_chunk c SET refcount = c.refcount - r.n
+        FROM (
+            SELECT chunk, COUNT(*) AS n FROM nar_chunk_ref
+            WHERE nar = ANY($1) GROUP BY chunk
+        ) r
+        WHERE c.hash = r.chunk
+        "#,
+        [nars.clone().into()],
+    ))
+    .await?;
+
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        "DELETE FROM nar_chunked WHERE hash = ANY($1)",
+        [nars.clone().into()],
+    ))
+    .await?;
+
+    let chunks: Vec<String> = db
+        .query_all(Statement::from_string(
+            DatabaseBackend::Postgres,
+            "DELETE FROM nar_chunk WHERE refcount <= 0 RETURNING hash",
+        ))
+        .await?
+        .into_iter()
+        .filter_map(|r| r.try_get::<String>("", "hash").ok())
+        .collect();
+
+    Ok(ReleasedChunks { nars, chunks })
+}
+
+/// Of `chunks`, those with no `nar_chunk` row: what a discarded conversion
+/// uploaded for nothing and may delete again.
+pub async fn unrecorded_chunks<C: ConnectionTrait>(
+    db: &C,
+    chunks: &[String],
+) -> Result<Vec<String>, DbErr> {
+    if chunks.is_empty() {
+        return Ok(Vec::new());
+    }
+    Ok(db
+        .query_all(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"
+            SELECT t.hash FROM unnest($1::text[]) AS t(hash)
+            WHERE NOT EXISTS (SELECT 1 FROM nar_chunk c WHERE c.hash = t.hash)
+            "#,
+            [chunks.to_vec().into()],
+        ))
+        .await?
+        .into_iter()
+        .filter_map(|r| r.try_get::<String>("", "hash").ok())
+        .collect())
+}
+
+/// Whether `hash`'s `cached_path` still serves `file_hash`, i.e. no re-upload
+/// replaced the chunked form since it was recorded.
+pub async fn serves_file_hash<C: ConnectionTrait>(
+    db: &C,
+    hash: &str,
+    file_hash: &str,
+) -> Result<bool, DbErr> {
+    Ok(db
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            "SELECT 1 AS one FROM cached_path WHERE hash = $1 AND file_hash = $2",
+            [hash.into(), file_hash.into()],
+        ))
+        .await?
+        .is_some())
+}
+
+/// Store hash of the chunked NAR whose flat form had `file_hash` (any of the
+/// given spellings), so a `nar/...` URL from a narinfo fetched before the
+/// conversion still resolves.
+pub async fn chunked_nar_by_flat_file_hash<C: ConnectionTrait>(
+    db: &C,
+    file_hashes: &[String],
+) -> Result<Option<String>, DbErr> {
+    Ok(db
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            "SELECT hash FROM nar_chunked WHERE flat_file_hash = ANY($1) LIMIT 1",
+            [file_hashes.to_vec().into()],
+        ))
+        .await?
+        .and_then(|r| r.try_get::<String>("", "hash").ok()))
+}
+
+const DEDUP_STATS_SQL: &str = r#"
+    WITH paths AS (
+        SELECT cp.hash, cp.file_size,
+               EXISTS (SELECT 1 FROM nar_chunked nc
+                       WHERE nc.hash = cp.hash AND nc.file_hash = cp.file_hash) AS chunked
+        FROM cached_path_signature cps
+        JOIN cached_path cp ON cp.id = cps.cached_path
+        WHERE cps.cache = $1 AND cp.file_hash IS NOT NULL
+    )
+    SELECT
+        (SELECT COUNT(*) FROM paths WHERE chunked)::bigint AS chunked_nars,
+        (SELECT COALESCE(SUM(file_size), 0) FROM paths WHERE NOT chunked)::bigint AS flat_bytes,
+        (SELECT COALESCE(SUM(c.size), 0) FROM nar_chunk c
+         WHERE c.hash IN (
+            SELECT r.chunk FROM nar_chunk_ref r
+            JOIN paths p ON p.hash = r.nar AND p.chunked))::bigint AS chunk_bytes
+"#;
+
+pub async fn nar_dedup_stats<C: ConnectionTrait>(
+    db: &C,
+    cache: CacheId,
+) -> Result<NarDedupStats, DbErr> {
+    Ok(
+        NarDedupStats::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            DEDUP_STATS_SQL,
+            [cache.into_inner().into()],
+        ))
+        .one(db)
+        .await?
+        .unwrap_or_default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn candidates_skip_converted_and_small_nars() {
+        assert!(CANDIDATES_SQL.contains("cp.file_size >= $1"));
+        assert!(CANDIDATES_SQL.contains("NOT EXISTS (SELECT 1 FROM nar_chunked nc"));
+        assert!(CANDIDATES_SQL.contains("cp.hash > $3"));
+    }
+
+    #[test]
+    fn chunked_nar_dies_with_its_file_hash() {
+        assert!(DEAD_CHUNKED_WHERE.contains("cp.file_hash = nc.file_hash"));
+        assert!(DEDUP_STATS_SQL.contains("nc.file_hash = cp.file_hash"));
+    }
+
+    #[test]
+    fn dedup_ratio_over_stored_bytes() {
+        let stats = NarDedupStats {
+            chunked_nars: 2,
+            flat_bytes: 100,
+            chunk_bytes: 150,
+        };
+        assert_eq!(stats.stored_bytes(), 250);
+        assert_eq!(stats.dedup_ratio(500), 2.0);
+        assert_eq!(NarDedupStats::default().dedup_ratio(0), 1.0);
+    }
+}
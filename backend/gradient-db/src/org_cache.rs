@@ -5,10 +5,11 @@
  */
 
 //! Organisation ↔ cache subscription helpers consumed by the trigger
-//! pipeline (no-cache gate) and the cache-create reconcile path.
+//! pipeline (no-cache gate), the cache-create reconcile path and realisation
+//! serving.
 
 use gradient_entity::organization_cache::CacheSubscriptionMode;
-use gradient_types::ids::OrganizationId;
+use gradient_types::ids::{CacheId, OrganizationId};
 use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter};
 
 /// Returns `true` when the organisation has at least one active cache
@@ -21,7 +22,6 @@ pub async fn org_has_writable_cache<C: ConnectionTrait>(
     organization: OrganizationId,
 ) -> Result<bool, sea_orm::DbErr> {
     use gradient_entity::cache::{Column as CCache, Entity as ECache};
-    use gradient_entity::ids::CacheId;
     use gradient_entity::organization_cache::{Column as COC, Entity as EOC};
 
     let cache_ids: Vec<CacheId> = EOC::find()
@@ -47,3 +47,24 @@ pub async fn org_has_writable_cache<C: ConnectionTrait>(
         .await?;
     Ok(row.is_some())
 }
+
+/// Organisations whose builds push into `cache`: those subscribed ReadWrite
+/// or WriteOnly.
+pub async fn cache_writer_organizations<C: ConnectionTrait>(
+    db: &C,
+    cache: CacheId,
+) -> Result<Vec<OrganizationId>, sea_orm::DbErr> {
+    use gradient_entity::organization_cache::{Column as COC, Entity as EOC};
+
+    Ok(EOC::find()
+        .filter(COC::Cache.eq(cache))
+        .filter(COC::Mode.is_in([
+            CacheSubscriptionMode::ReadWrite,
+            CacheSubscriptionMode::WriteOnly,
+        ]))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|r| r.organization)
+        .collect())
+}
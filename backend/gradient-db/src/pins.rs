@@ -0,0 +1,155 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Cache pins: named GC roots. A live pin (no `expires_at`, or one in the
+//! future) protects the runtime closure of its store path, or of every
+//! entry-point output of its evaluation, from every GC pass. The closure walk
+//! is defined once here and shared by the SQL passes (cache eviction) and the
+//! Rust-side ones (evaluation, derivation, NAR TTL and orphan-file GC) so they
+//! can never disagree on what a pin keeps.
+
+use std::collections::{HashMap, HashSet};
+
+use gradient_types::*;
+use sea_orm::{
+    ColumnTrait, Condition, ConnectionTrait, DatabaseBackend, DbErr, EntityTrait, FromQueryResult,
+    QueryFilter, Statement,
+};
+
+/// Whether pin `{alias}` is live at statement time.
+fn live_pin_predicate(alias: &str) -> String {
+    format!("({alias}.expires_at IS NULL OR {alias}.expires_at > NOW() AT TIME ZONE 'UTC')")
+}
+
+/// The bare `pinned(cache, hash) AS (...)` CTE body (plus its `pin_root`
+/// seed), without the `WITH RECURSIVE` prefix: every store hash in the runtime
+/// closure of a live pin, per cache holding the pin. Paths not cached yet are
+/// absent from `cached_path_reference` and simply end the walk.
+pub fn pinned_closure_cte_body() -> String {
+    let live = live_pin_predicate("p");
+    format!(
+        "pin_root(cache, hash) AS (
+             SELECT p.cache, p.hash FROM cache_pin p
+             WHERE p.hash IS NOT NULL AND {live}
+             UNION
+             SELECT p.cache, dout.hash FROM cache_pin p
+             JOIN entry_point ep ON ep.evaluation = p.evaluation
+             JOIN derivation_output dout ON dout.derivation = ep.derivation
+             WHERE p.evaluation IS NOT NULL AND {live}
+         ),
+         pinned(cache, hash) AS (
+             SELECT cache, hash FROM pin_root
+             UNION
+             SELECT pd.cache, r.reference_hash FROM cached_path_reference r
+             JOIN pinned pd ON r.referrer = pd.hash
+         )"
+    )
+}
+
+#[derive(FromQueryResult)]
+struct PinnedRow {
+    cache: uuid::Uuid,
+    hash: String,
+}
+
+/// The hashes live pins protect, with the caches pinning each.
+#[derive(Debug, Clone, Default)]
+pub struct PinnedPaths {
+    by_hash: HashMap<String, HashSet<CacheId>>,
+}
+
+impl PinnedPaths {
+    /// Pinned in any cache. NAR objects and `cached_path` rows are shared by
+    /// every cache, so the passes reclaiming them honour every pin.
+    pub fn contains(&self, hash: &str) -> bool {
+        self.by_hash.contains_key(hash)
+    }
+
+    /// Pinned in `cache`, for the passes that drop one cache's copy.
+    pub fn contains_in(&self, cache: CacheId, hash: &str) -> bool {
+        self.by_hash
+            .get(hash)
+            .is_some_and(|caches| caches.contains(&cache))
+    }
+
+    pub fn hashes(&self) -> impl Iterator<Item = &String> {
+        self.by_hash.keys()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_hash.is_empty()
+    }
+}
+
+impl FromIterator<(CacheId, String)> for PinnedPaths {
+    fn from_iter<I: IntoIterator<Item = (CacheId, String)>>(iter: I) -> Self {
+        let mut by_hash: HashMap<String, HashSet<CacheId>> = HashMap::new();
+        for (cache, hash) in iter {
+            by_hash.entry(hash).or_default().insert(cache);
+        }
+        Self { by_hash }
+    }
+}
+
+/// Every hash in the runtime closure of a live pin.
+pub async fn pinned_paths<C: ConnectionTrait>(db: &C) -> Result<PinnedPaths, DbErr> {
+    let sql = format!(
+        "WITH RECURSIVE {} SELECT DISTINCT cache, hash FROM pinned",
+        pinned_closure_cte_body()
+    );
+    Ok(
+        PinnedRow::find_by_statement(Statement::from_string(DatabaseBackend::Postgres, sql))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|r| (CacheId::new(r.cache), r.hash))
+            .collect(),
+    )
+}
+
+/// Evaluations a live pin keeps, which the evaluation GC must not delete.
+pub async fn pinned_evaluations<C: ConnectionTrait>(
+    db: &C,
+) -> Result<HashSet<EvaluationId>, DbErr> {
+    Ok(ECachePin::find()
+        .filter(CCachePin::Evaluation.is_not_null())
+        .filter(
+            Condition::any()
+                .add(CCachePin::ExpiresAt.is_null())
+                .add(CCachePin::ExpiresAt.gt(now())),
+        )
+        .all(db)
+        .await?
+        .into_iter()
+        .filter_map(|p| p.evaluation)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closure_walks_references_from_live_roots() {
+        let cte = pinned_closure_cte_body();
+        assert!(cte.contains("p.expires_at IS NULL OR p.expires_at > NOW()"));
+        assert!(cte.contains("JOIN entry_point ep ON ep.evaluation = p.evaluation"));
+        assert!(cte.contains("JOIN pinned pd ON r.referrer = pd.hash"));
+    }
+
+    #[test]
+    fn pinned_paths_scope_by_cache() {
+        let a = CacheId::now_v7();
+        let b = CacheId::now_v7();
+        let pinned: PinnedPaths = [(a, "aaaa".to_string()), (b, "bbbb".to_string())]
+            .into_iter()
+            .collect();
+        assert!(pinned.contains("aaaa"));
+        assert!(pinned.contains_in(a, "aaaa"));
+        assert!(!pinned.contains_in(b, "aaaa"));
+        assert!(!pinned.contains("cccc"));
+    }
+}
@@ -0,0 +1,291 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Realisations of floating content-addressed outputs. Such an output has no
+//! store path until built, so it gets no `derivation_output` row at eval time;
+//! the derivation records its `floating_outputs` and their realisation key
+//! instead. A build records the path it produced as a `realisation` of its
+//! organization, and a later evaluation by the same organization of any
+//! derivation with the same key turns realisations back into
+//! `derivation_output` rows, so the by-hash cache checks apply to them like to
+//! any other output. Another organization's realisations are never used: a
+//! floating output is whatever its builder produced, so trusting one is a
+//! decision of the organization that built it.
+
+use std::collections::{HashMap, HashSet};
+
+use gradient_types::*;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ColumnTrait, ConnectionTrait, DbErr, EntityTrait, IntoActiveModel, QueryFilter};
+
+use crate::chunked::fetch_in_chunks;
+
+/// Record that `organization`'s build of `derivation` realised its floating
+/// output `output_name` at `store_path`: the organization's `realisation`
+/// (first build wins) and the output's `derivation_output` row. Returns the
+/// row that ends up stored, which is a concurrent build's when it got there
+/// first. `None` when `output_name` is not a floating output of the
+/// derivation.
+pub async fn record_realised_output<C: ConnectionTrait>(
+    db: &C,
+    organization: OrganizationId,
+    derivation: DerivationId,
+    output_name: &str,
+    store_path: &StorePath,
+) -> Result<Option<MDerivationOutput>, DbErr> {
+    let Some(drv) = EDerivation::find_by_id(derivation).one(db).await? else {
+        return Ok(None);
+    };
+    if !drv.floating_outputs.iter().any(|o| o == output_name) {
+        return Ok(None);
+    }
+
+    let now = gradient_types::now();
+    if let Some(drv_hash) = drv.realisation_hash {
+        let realisation = MRealisation {
+            id: RealisationId::now_v7(),
+            organization,
+            drv_hash,
+            output_name: output_name.to_string(),
+            hash: store_path.hash().to_string(),
+            package: store_path.name().to_string(),
+            created_at: now,
+        };
+        let res = ERealisation::insert(realisation.into_active_model())
+            .on_conflict(
+                OnConflict::columns([
+                    CRealisation::Organization,
+                    CRealisation::DrvHash,
+                    CRealisation::OutputName,
+                ])
+                .do_nothing()
+                .to_owned(),
+            )
+            .exec(db)
+            .await;
+        if let Err(e) = res
+            && !matches!(e, DbErr::RecordNotInserted)
+        {
+            return Err(e);
+        }
+    }
+
+    let row = MDerivationOutput {
+        id: DerivationOutputId::now_v7(),
+        derivation,
+        name: output_name.to_string(),
+        hash: store_path.hash().to_string(),
+        package: store_path.name().to_string(),
+        created_at: now,
+        ..Default::default()
+    };
+    let res = EDerivationOutput::insert(row.into_active_model())
+        .on_conflict(output_conflict())
+        .exec(db)
+        .await;
+    if let Err(e) = res
+        && !matches!(e, DbErr::RecordNotInserted)
+    {
+        return Err(e);
+    }
+
+    EDerivationOutput::find()
+        .filter(CDerivationOutput::Derivation.eq(derivation))
+        .filter(CDerivationOutput::Name.eq(output_name))
+        .one(db)
+        .await
+}
+
+/// Another build or evaluation may have stored the same output row first;
+/// whichever landed stays.
+fn output_conflict() -> OnConflict {
+    OnConflict::columns([CDerivationOutput::Derivation, CDerivationOutput::Name])
+        .do_nothing()
+        .to_owned()
+}
+
+/// Give every floating output of `drv_ids` that a realisation recorded by
+/// `organization` covers its `derivation_output` row. Returns the derivations
+/// left with a floating output the organization has not realised yet: their
+/// `derivation_output` rows are incomplete, so they must never count as
+/// substituted.
+pub async fn realise_floating_outputs<C: ConnectionTrait>(
+    db: &C,
+    organization: OrganizationId,
+    drv_ids: &[DerivationId],
+) -> Result<HashSet<DerivationId>, DbErr> {
+    let derivations: Vec<MDerivation> = fetch_in_chunks(drv_ids, |chunk| async move {
+        EDerivation::find()
+            .filter(CDerivation::Id.is_in(chunk))
+            .all(db)
+            .await
+    })
+    .await?
+    .into_iter()
+    .filter(|d| !d.floating_outputs.is_empty())
+    .collect();
+    if derivations.is_empty() {
+        return Ok(HashSet::new());
+    }
+
+    let ids: Vec<DerivationId> = derivations.iter().map(|d| d.id).collect();
+    let existing: HashSet<(DerivationId, String)> = fetch_in_chunks(&ids, |chunk| async move {
+        EDerivationOutput::find()
+            .filter(CDerivationOutput::Derivation.is_in(chunk))
+            .all(db)
+            .await
+    })
+    .await?
+    .into_iter()
+    .map(|o| (o.derivation, o.name))
+    .collect();
+
+    let keys: Vec<String> = derivations
+        .iter()
+        .filter_map(|d| d.realisation_hash.clone())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let realisations: HashMap<(String, String), MRealisation> =
+        fetch_in_chunks(&keys, |chunk| async move {
+            ERealisation::find()
+                .filter(CRealisation::Organization.eq(organization))
+                .filter(CRealisation::DrvHash.is_in(chunk))
+                .all(db)
+                .await
+        })
+        .await?
+        .into_iter()
+        .map(|r| ((r.drv_hash.clone(), r.output_name.clone()), r))
+        .collect();
+
+    let now = gradient_types::now();
+    let mut rows: Vec<ADerivationOutput> = Vec::new();
+    let mut unrealised = HashSet::new();
+    for drv in &derivations {
+        let plan = plan_floating_outputs(drv, &existing, &realisations);
+        if !plan.complete {
+            unrealised.insert(drv.id);
+        }
+        rows.extend(plan.realised.into_iter().map(|(name, r)| {
+            MDerivationOutput {
+                id: DerivationOutputId::now_v7(),
+                derivation: drv.id,
+                name,
+                hash: r.hash.clone(),
+                package: r.package.clone(),
+                created_at: now,
+                ..Default::default()
+            }
+            .into_active_model()
+        }));
+    }
+
+    for chunk in rows.chunks(crate::IN_CHUNK_SIZE) {
+        let res = EDerivationOutput::insert_many(chunk.to_vec())
+            .on_conflict(output_conflict())
+            .exec(db)
+            .await;
+        if let Err(e) = res
+            && !matches!(e, DbErr::RecordNotInserted)
+        {
+            return Err(e);
+        }
+    }
+    Ok(unrealised)
+}
+
+/// What [`realise_floating_outputs`] does for one derivation.
+#[derive(Debug)]
+struct FloatingPlan<'a> {
+    /// Floating outputs without a row that a realisation covers.
+    realised: Vec<(String, &'a MRealisation)>,
+    /// Whether every floating output has a row once `realised` is inserted.
+    complete: bool,
+}
+
+fn plan_floating_outputs<'a>(
+    drv: &MDerivation,
+    existing: &HashSet<(DerivationId, String)>,
+    realisations: &'a HashMap<(String, String), MRealisation>,
+) -> FloatingPlan<'a> {
+    let mut plan = FloatingPlan {
+        realised: Vec::new(),
+        complete: true,
+    };
+    for name in &drv.floating_outputs {
+        if existing.contains(&(drv.id, name.clone())) {
+            continue;
+        }
+        let found = drv
+            .realisation_hash
+            .as_ref()
+            .and_then(|key| realisations.get(&(key.clone(), name.clone())));
+        match found {
+            Some(r) => plan.realised.push((name.clone(), r)),
+            None => plan.complete = false,
+        }
+    }
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn floating_drv(key: Option<&str>) -> MDerivation {
+        MDerivation {
+            id: DerivationId::now_v7(),
+            realisation_hash: key.map(str::to_string),
+            floating_outputs: vec!["dev".into(), "out".into()],
+            ..Default::default()
+        }
+    }
+
+    fn realisation(key: &str, output: &str) -> ((String, String), MRealisation) {
+        (
+            (key.to_string(), output.to_string()),
+            MRealisation {
+                drv_hash: key.to_string(),
+                output_name: output.to_string(),
+                hash: "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".into(),
+                package: "ca-thing".into(),
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn realisations_complete_floating_outputs() {
+        let drv = floating_drv(Some("sha256:ab"));
+        let realisations = HashMap::from([realisation("sha256:ab", "dev")]);
+        let existing = HashSet::from([(drv.id, "out".to_string())]);
+
+        let plan = plan_floating_outputs(&drv, &existing, &realisations);
+        assert!(plan.complete);
+        assert_eq!(plan.realised.len(), 1);
+        assert_eq!(plan.realised[0].0, "dev");
+        assert_eq!(
+            plan.realised[0].1.out_path(),
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa-ca-thing"
+        );
+    }
+
+    #[test]
+    fn missing_realisation_leaves_derivation_unrealised() {
+        let realisations = HashMap::from([realisation("sha256:ab", "dev")]);
+        let drv = floating_drv(Some("sha256:ab"));
+        let plan = plan_floating_outputs(&drv, &HashSet::new(), &realisations);
+        assert!(!plan.complete);
+        assert_eq!(plan.realised.len(), 1);
+
+        // Without a key nothing can be looked up.
+        let keyless = floating_drv(None);
+        let plan = plan_floating_outputs(&keyless, &HashSet::new(), &realisations);
+        assert!(!plan.complete);
+        assert!(plan.realised.is_empty());
+    }
+}
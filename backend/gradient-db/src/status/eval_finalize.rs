@@ -11,7 +11,6 @@
 
 use super::evaluation_status::update_evaluation_status;
 use crate::DbContext;
-use gradient_entity::build::BuildStatus;
 use gradient_entity::evaluation::EvaluationStatus;
 use gradient_types::*;
 use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter};
@@ -29,15 +28,7 @@ pub async fn check_evaluation_done(
 ) -> Result<(), DbErr> {
     let statuses = crate::reachability::eval_anchor_statuses(&ctx.worker_db, evaluation_id).await?;
 
-    let any_active = statuses.iter().any(|s| {
-        matches!(
-            s,
-            BuildStatus::Created
-                | BuildStatus::Queued
-                | BuildStatus::Building
-                | BuildStatus::FailedTransient
-        )
-    });
+    let any_active = statuses.iter().any(|s| s.is_in_progress());
     if any_active {
         return Ok(());
     }
@@ -53,14 +44,7 @@ pub async fn check_evaluation_done(
         return Ok(());
     }
 
-    let any_failed = statuses.iter().any(|s| {
-        matches!(
-            s,
-            BuildStatus::FailedPermanent
-                | BuildStatus::FailedTimeout
-                | BuildStatus::DependencyFailed
-        )
-    });
+    let any_failed = statuses.iter().any(|s| s.is_terminal_failure());
 
     let eval_error_messages = EEvaluationMessage::find()
         .filter(CEvaluationMessage::Evaluation.eq(evaluation_id))
@@ -31,12 +31,18 @@ pub enum AdminTaskKind {
     #[default]
     #[sea_orm(num_value = 0)]
     DeepGc = 0,
+    #[sea_orm(num_value = 1)]
+    CacheEviction = 1,
+    #[sea_orm(num_value = 2)]
+    NarChunking = 2,
 }
 
 impl AdminTaskKind {
     pub const fn as_str(self) -> &'static str {
         match self {
             Self::DeepGc => "deep_gc",
+            Self::CacheEviction => "cache_eviction",
+            Self::NarChunking => "nar_chunking",
         }
     }
 }
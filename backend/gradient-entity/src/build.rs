@@ -90,6 +90,14 @@ impl BuildStatus {
         )
     }
 
+    /// Not settled yet: waiting, building, or failed and pending a retry.
+    pub const fn is_in_progress(self) -> bool {
+        matches!(
+            self,
+            Self::Created | Self::Queued | Self::Building | Self::FailedTransient
+        )
+    }
+
     /// Build-once success states, never re-queued by a new evaluation.
     pub const fn is_terminal_success(self) -> bool {
         matches!(self, Self::Completed | Self::Substituted)
@@ -157,6 +165,16 @@ mod tests {
         assert!(!BuildStatus::Building.is_failure());
     }
 
+    #[test]
+    fn every_status_is_in_progress_or_settled() {
+        for status in BuildStatus::iter() {
+            let settled = status.is_terminal_success()
+                || status.is_terminal_failure()
+                || status == BuildStatus::Aborted;
+            assert_ne!(status.is_in_progress(), settled, "{status:?}");
+        }
+    }
+
     #[test]
     fn terminal_failure_excludes_transient() {
         assert!(BuildStatus::FailedPermanent.is_terminal_failure());
@@ -92,6 +92,13 @@ pub struct Model {
     pub outcome: AttemptOutcome,
     pub reason: Option<AttemptFailureReason>,
     pub failure_message: Option<String>,
+    /// This failure was judged flaky and the build re-run on another worker.
+    pub flaky: bool,
+    /// This attempt is the re-run of a flaky failure.
+    pub flaky_rerun: bool,
+    /// Structured cause recognised in the failed attempt's log
+    /// (`gradient_types::FailureCause`); `None` when nothing matched.
+    pub failure_cause: Option<Json>,
     pub build_context: Json,
     pub build_started_at: Option<NaiveDateTime>,
     pub build_finished_at: Option<NaiveDateTime>,
@@ -0,0 +1,54 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Pass/fail history of one derivation name within a project, aggregated
+//! across evaluations. Drives flaky-build detection and targeted re-runs.
+
+use chrono::NaiveDateTime;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::ids::{BuildFlakinessId, ProjectId};
+
+#[derive(Clone, Debug, Default, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "build_flakiness")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: BuildFlakinessId,
+    pub project: ProjectId,
+    /// `derivation.pname`, falling back to `derivation.name`.
+    pub name: String,
+    /// Final outcomes recorded (one per build, re-runs folded in).
+    pub runs: i64,
+    pub failures: i64,
+    /// Consecutive final outcomes that differed (pass after fail or vice versa).
+    pub flips: i64,
+    /// Failures re-run on another worker instead of failing outright.
+    pub reruns: i64,
+    /// Re-runs that then passed - the strongest flakiness signal.
+    pub rerun_passes: i64,
+    pub last_passed: Option<bool>,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::project::Entity",
+        from = "Column::Project",
+        to = "super::project::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Project,
+}
+
+impl Related<super::project::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Project.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -5,11 +5,104 @@
  */
 
 use chrono::NaiveDateTime;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::ids::{CacheId, UserId};
 
+/// What the cache does when it reaches `max_storage_gb`: evict unreferenced
+/// paths to make room, or stay full and park evaluations until space is freed.
+#[repr(i16)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    DeriveActiveEnum,
+    EnumIter,
+    Deserialize,
+    Serialize,
+    IntoPrimitive,
+    TryFromPrimitive,
+)]
+#[sea_orm(rs_type = "i16", db_type = "SmallInteger")]
+#[serde(rename_all = "snake_case")]
+pub enum CacheEvictionPolicy {
+    /// Never evict; evaluations park under `CacheStorageFull`. The default:
+    /// deleting paths is opt-in.
+    #[default]
+    #[sea_orm(num_value = 0)]
+    Disabled = 0,
+    /// Evict the least recently accessed paths first.
+    #[sea_orm(num_value = 1)]
+    Lru = 1,
+    /// Evict the oldest paths first, regardless of access.
+    #[sea_orm(num_value = 2)]
+    OldestFirst = 2,
+}
+
+/// Compression a cache serves NARs in. NARs are stored as zstd; any other
+/// format is transcoded while streaming.
+#[repr(i16)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    DeriveActiveEnum,
+    EnumIter,
+    Deserialize,
+    Serialize,
+    IntoPrimitive,
+    TryFromPrimitive,
+)]
+#[sea_orm(rs_type = "i16", db_type = "SmallInteger")]
+#[serde(rename_all = "snake_case")]
+pub enum NarCompression {
+    /// Served as stored.
+    #[default]
+    #[sea_orm(num_value = 0)]
+    Zstd = 0,
+    /// For old Nix and tools without zstd support.
+    #[sea_orm(num_value = 1)]
+    Xz = 1,
+    /// Plain NARs.
+    #[sea_orm(num_value = 2)]
+    None = 2,
+}
+
+impl NarCompression {
+    /// The narinfo `Compression:` value.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Zstd => "zstd",
+            Self::Xz => "xz",
+            Self::None => "none",
+        }
+    }
+
+    /// File extension of a NAR in this compression.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Zstd => ".nar.zst",
+            Self::Xz => ".nar.xz",
+            Self::None => ".nar",
+        }
+    }
+
+    /// The compression a `nar/<hash><extension>` URL asks for.
+    pub fn from_nar_path(path: &str) -> Option<Self> {
+        [Self::Zstd, Self::Xz, Self::None]
+            .into_iter()
+            .find(|c| path.ends_with(c.extension()))
+    }
+}
+
 #[derive(Clone, Default, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
 #[sea_orm(table_name = "cache")]
 pub struct Model {
@@ -32,6 +125,8 @@ pub struct Model {
     pub managed: bool,
     #[sea_orm(default_value = "0")]
     pub max_storage_gb: i32,
+    pub eviction_policy: CacheEvictionPolicy,
+    pub nar_compression: NarCompression,
 }
 
 impl std::fmt::Debug for Model {
@@ -51,6 +146,8 @@ impl std::fmt::Debug for Model {
             .field("created_at", &self.created_at)
             .field("managed", &self.managed)
             .field("max_storage_gb", &self.max_storage_gb)
+            .field("eviction_policy", &self.eviction_policy)
+            .field("nar_compression", &self.nar_compression)
             .finish()
     }
 }
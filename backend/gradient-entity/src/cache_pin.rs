@@ -0,0 +1,66 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::ids::{CacheId, CachePinId, EvaluationId, UserId};
+use crate::store_path::StorePath;
+
+/// A named GC root in a cache: one store path (`hash` + `package`) or a whole
+/// `evaluation`. Until `expires_at`, no GC pass removes the runtime closure
+/// of the pinned path or of the evaluation's entry-point outputs, and the
+/// evaluation GC keeps a pinned evaluation.
+#[derive(Clone, Debug, Default, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "cache_pin")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: CachePinId,
+    pub cache: CacheId,
+    pub name: String,
+    pub hash: Option<String>,
+    pub package: Option<String>,
+    pub evaluation: Option<EvaluationId>,
+    /// Provisioned from declarative state; replaced on every state apply and
+    /// not removable through the API.
+    pub managed: bool,
+    pub created_by: Option<UserId>,
+    pub created_at: NaiveDateTime,
+    pub expires_at: Option<NaiveDateTime>,
+}
+
+impl Model {
+    /// The pinned store path, for a path pin.
+    pub fn store_path(&self) -> Option<StorePath> {
+        Some(StorePath::from_parts(
+            self.hash.clone()?,
+            self.package.clone()?,
+        ))
+    }
+
+    /// Whether the pin still protects its closure at `now`.
+    pub fn is_live(&self, now: NaiveDateTime) -> bool {
+        self.expires_at.is_none_or(|at| at > now)
+    }
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::cache::Entity",
+        from = "Column::Cache",
+        to = "super::cache::Column::Id"
+    )]
+    Cache,
+    #[sea_orm(
+        belongs_to = "super::evaluation::Entity",
+        from = "Column::Evaluation",
+        to = "super::evaluation::Column::Id"
+    )]
+    Evaluation,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -47,6 +47,9 @@ pub struct Model {
     /// Full `.drv` path that produced this output, if known.
     pub deriver: Option<String>,
     pub created_at: NaiveDateTime,
+    /// Last time a narinfo or NAR for this path was served; drives LRU
+    /// eviction. `None` until first served.
+    pub last_accessed_at: Option<NaiveDateTime>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
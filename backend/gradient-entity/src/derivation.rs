@@ -24,6 +24,13 @@ pub struct Model {
     pub allow_substitutes: bool,
     pub closure_size: Option<i64>,
     pub dep_closure_count: Option<i64>,
+    /// Realisation key (hash modulo, `sha256:<base16>`) of a derivation with
+    /// floating content-addressed outputs.
+    pub realisation_hash: Option<String>,
+    /// Outputs whose paths are only known once built; they have no
+    /// `derivation_output` row until realised.
+    #[sea_orm(column_type = "Array(std::sync::Arc::new(ColumnType::Text))")]
+    pub floating_outputs: Vec<String>,
     pub created_at: NaiveDateTime,
 }
 
@@ -21,6 +21,15 @@ pub struct Model {
     pub eval: String,
     pub created_at: NaiveDateTime,
     pub repo_check_id: Option<i64>,
+    /// Hydra-style aggregate (`_hydraAggregate`): its status combines its own
+    /// build with its `entry_point_constituent` derivations.
+    pub aggregate: bool,
+    /// Constituents of an aggregate that resolved to no derivation. Any fails
+    /// the aggregate's combined status.
+    pub unresolved_constituents: i32,
+    /// Constituent drv paths of an aggregate not yet linked. Cleared once
+    /// they are resolved at the end of the evaluation stream.
+    pub pending_constituents: Vec<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
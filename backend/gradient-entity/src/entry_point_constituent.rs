@@ -0,0 +1,39 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::ids::{DerivationId, EntryPointConstituentId, EntryPointId};
+
+/// Join table: the derivations named in an aggregate entry point's
+/// `constituents`. The aggregate succeeds only when all of them do.
+#[derive(Clone, Debug, Default, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "entry_point_constituent")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: EntryPointConstituentId,
+    pub entry_point: EntryPointId,
+    pub derivation: DerivationId,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::entry_point::Entity",
+        from = "Column::EntryPoint",
+        to = "super::entry_point::Column::Id"
+    )]
+    EntryPoint,
+    #[sea_orm(
+        belongs_to = "super::derivation::Entity",
+        from = "Column::Derivation",
+        to = "super::derivation::Column::Id"
+    )]
+    Derivation,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -208,6 +208,9 @@ pub struct Model {
     pub started_by: Option<UserId>,
     pub concurrent: bool,
     pub source_comment: Option<Json>,
+    /// Branch the triggering push or pull request came from (PR head
+    /// branch); `None` for tags, releases, time and manual runs.
+    pub branch: Option<String>,
     pub fetch_started_at: Option<NaiveDateTime>,
     pub eval_flake_started_at: Option<NaiveDateTime>,
     pub eval_drv_started_at: Option<NaiveDateTime>,
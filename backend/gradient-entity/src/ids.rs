@@ -89,6 +89,7 @@ id_newtype!(BuildLogChunkId);
 id_newtype!(BuildProductId);
 id_newtype!(BuildRequestBlobId);
 id_newtype!(CacheId);
+id_newtype!(CachePinId);
 id_newtype!(CacheDerivationId);
 id_newtype!(CacheMetricId);
 id_newtype!(CacheUpstreamId);
@@ -108,6 +109,7 @@ id_newtype!(DerivationBuildId);
 id_newtype!(DerivationOutputSignatureId);
 id_newtype!(EntryPointId);
 id_newtype!(EntryPointDepCountId);
+id_newtype!(EntryPointConstituentId);
 id_newtype!(EntryPointMessageId);
 id_newtype!(EvalCacheStoreId);
 id_newtype!(EvaluationId);
@@ -128,14 +130,18 @@ id_newtype!(OrganizationUserId);
 id_newtype!(ProjectId);
 id_newtype!(ProjectActionId);
 id_newtype!(ProjectActionDeliveryId);
+id_newtype!(ProjectActionDigestEntryId);
 id_newtype!(ProjectTriggerId);
+id_newtype!(RealisationId);
 id_newtype!(RoleId);
 id_newtype!(UserId);
+id_newtype!(UserNotificationSubscriptionId);
 id_newtype!(SessionId);
 id_newtype!(UploadSessionId);
 id_newtype!(AuditLogId);
 id_newtype!(WorkerRegistrationId);
 id_newtype!(CliDeviceAuthorizationId);
+id_newtype!(BuildFlakinessId);
 id_newtype!(BuildAttemptId);
 id_newtype!(BuildJobId);
 id_newtype!(DispatchedJobId);
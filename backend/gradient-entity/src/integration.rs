@@ -68,6 +68,13 @@ pub enum ForgeType {
     GitLab = 2,
     #[sea_orm(num_value = 3)]
     GitHub = 3,
+    /// Bitbucket Cloud (bitbucket.org).
+    #[sea_orm(num_value = 4)]
+    Bitbucket = 4,
+    /// Self-hosted Bitbucket Server / Data Center.
+    #[sea_orm(num_value = 5)]
+    #[serde(rename = "bitbucket-server")]
+    BitbucketServer = 5,
 }
 
 impl ForgeType {
@@ -77,6 +84,8 @@ impl ForgeType {
             "forgejo" => Some(Self::Forgejo),
             "gitlab" => Some(Self::GitLab),
             "github" => Some(Self::GitHub),
+            "bitbucket" => Some(Self::Bitbucket),
+            "bitbucket-server" => Some(Self::BitbucketServer),
             _ => None,
         }
     }
@@ -89,6 +98,8 @@ impl ForgeType {
             Self::Forgejo => "forgejo",
             Self::GitLab => "gitlab",
             Self::GitHub => "github",
+            Self::Bitbucket => "bitbucket",
+            Self::BitbucketServer => "bitbucket-server",
         }
     }
 }
@@ -15,6 +15,7 @@ pub mod audit_log;
 pub mod base_worker;
 pub mod build;
 pub mod build_attempt;
+pub mod build_flakiness;
 pub mod build_job;
 pub mod build_log_chunk;
 pub mod build_product;
@@ -22,6 +23,7 @@ pub mod build_request_blob;
 pub mod cache;
 pub mod cache_derivation;
 pub mod cache_metric;
+pub mod cache_pin;
 pub mod cache_role;
 pub mod cache_upstream;
 pub mod cache_user;
@@ -38,6 +40,7 @@ pub mod derivation_input_source;
 pub mod derivation_metric;
 pub mod derivation_output;
 pub mod entry_point;
+pub mod entry_point_constituent;
 pub mod entry_point_dep_count;
 pub mod entry_point_message;
 pub mod eval_cache_store;
@@ -59,14 +62,17 @@ pub mod organization_user;
 pub mod project;
 pub mod project_action;
 pub mod project_action_delivery;
+pub mod project_action_digest_entry;
 pub mod project_flake_input_override;
 pub mod project_trigger;
+pub mod realisation;
 pub mod role;
 pub mod server;
 pub mod session;
 pub mod upload_session;
 pub mod upstream_metric;
 pub mod user;
+pub mod user_notification_subscription;
 pub mod worker_registration;
 
 pub mod dispatched_job;
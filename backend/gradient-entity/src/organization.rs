@@ -27,6 +27,12 @@ pub struct Model {
     pub created_by: UserId,
     pub created_at: NaiveDateTime,
     pub managed: bool,
+    /// Hard cap on builds in flight at once. `None` is unlimited.
+    pub max_concurrent_builds: Option<i32>,
+    /// Hard cap on evaluations running at once. `None` is unlimited.
+    pub max_concurrent_evaluations: Option<i32>,
+    /// Build minutes the org may spend per rolling 24 hours. `None` is unlimited.
+    pub build_minutes_budget: Option<i32>,
 }
 
 impl std::fmt::Debug for Model {
@@ -42,6 +48,12 @@ impl std::fmt::Debug for Model {
             .field("hide_build_requests", &self.hide_build_requests)
             .field("created_by", &self.created_by)
             .field("created_at", &self.created_at)
+            .field("max_concurrent_builds", &self.max_concurrent_builds)
+            .field(
+                "max_concurrent_evaluations",
+                &self.max_concurrent_evaluations,
+            )
+            .field("build_minutes_budget", &self.build_minutes_budget)
             .finish()
     }
 }
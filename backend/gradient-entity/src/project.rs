@@ -64,6 +64,18 @@ pub struct Model {
     pub keep_evaluations: i32,
     pub concurrency: ConcurrencyPolicy,
     pub sign_cache: bool,
+    /// Worker label selector (`{"pool": "secure"}`) every build of this
+    /// project must match. NULL means any worker.
+    pub worker_selector: Option<Json>,
+    /// Wall-clock build limit in seconds. NULL uses the server default, `0`
+    /// means no limit; a derivation's own `timeout` or `meta.timeout` wins.
+    pub build_timeout_secs: Option<i64>,
+    /// Silent-output build limit in seconds, resolved like
+    /// `build_timeout_secs`.
+    pub build_max_silent_secs: Option<i64>,
+    /// Largest NAR, in bytes, a single build output may have. NULL or `0`
+    /// means no limit.
+    pub max_output_size: Option<i64>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -39,6 +39,10 @@ pub enum ActionType {
     ForgeStatusReport = 2,
     #[sea_orm(num_value = 3)]
     OpenPr = 3,
+    #[sea_orm(num_value = 4)]
+    SendMatrix = 4,
+    #[sea_orm(num_value = 5)]
+    SendSlack = 5,
 }
 
 #[derive(Clone, Debug, Default, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
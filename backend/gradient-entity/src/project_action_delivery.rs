@@ -5,11 +5,56 @@
  */
 
 use chrono::NaiveDateTime;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::ids::{ProjectActionDeliveryId, ProjectActionId};
 
+/// Where a delivery sits in the retry schedule. `Retrying` rows carry a
+/// `next_retry_at` and are picked up by the retry loop; `Dead` rows exhausted
+/// their attempt budget (or were rejected outright) and wait for a manual
+/// redelivery.
+#[repr(i16)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    DeriveActiveEnum,
+    EnumIter,
+    Deserialize,
+    Serialize,
+    IntoPrimitive,
+    TryFromPrimitive,
+)]
+#[sea_orm(rs_type = "i16", db_type = "SmallInteger")]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryState {
+    #[default]
+    #[sea_orm(num_value = 0)]
+    Delivered = 0,
+    #[sea_orm(num_value = 1)]
+    Failed = 1,
+    #[sea_orm(num_value = 2)]
+    Retrying = 2,
+    #[sea_orm(num_value = 3)]
+    Dead = 3,
+}
+
+impl DeliveryState {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Delivered => "delivered",
+            Self::Failed => "failed",
+            Self::Retrying => "retrying",
+            Self::Dead => "dead",
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
 #[sea_orm(table_name = "project_action_delivery")]
 pub struct Model {
@@ -27,6 +72,13 @@ pub struct Model {
     pub success: bool,
     pub duration_ms: i32,
     pub delivered_at: NaiveDateTime,
+    /// Attempts made so far, including the first one.
+    pub attempt: i32,
+    pub state: DeliveryState,
+    pub next_retry_at: Option<NaiveDateTime>,
+    /// Full event payload, kept while the delivery can still be retried or
+    /// redelivered; `request_body` is only a truncated display copy.
+    pub payload: Option<Json>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -0,0 +1,47 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! One event buffered by a `send_mail` action in digest mode, waiting to be
+//! mailed as part of the action's next summary.
+
+use chrono::NaiveDateTime;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::ids::{ProjectActionDigestEntryId, ProjectActionId};
+
+#[derive(Clone, Debug, Default, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "project_action_digest_entry")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: ProjectActionDigestEntryId,
+    pub action_id: ProjectActionId,
+    pub event: String,
+    pub payload: Json,
+    /// When the action's current window closes; every entry buffered during
+    /// one window shares the window's `flush_at`.
+    pub flush_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::project_action::Entity",
+        from = "Column::ActionId",
+        to = "super::project_action::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Action,
+}
+
+impl Related<super::project_action::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Action.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -11,8 +11,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::ids::{ProjectId, ProjectTriggerId};
 
-/// What fires an evaluation: repo polling, a forge push/PR webhook, or a cron
-/// schedule. Tags the polymorphic `config` jsonb column.
+/// What fires an evaluation: repo polling, a forge push/PR/merge-queue webhook,
+/// or a cron schedule. Tags the polymorphic `config` jsonb column.
 #[repr(i16)]
 #[derive(
     Debug,
@@ -40,6 +40,8 @@ pub enum TriggerType {
     ReporterPullRequest = 2,
     #[sea_orm(num_value = 3)]
     Time = 3,
+    #[sea_orm(num_value = 4)]
+    ReporterMergeQueue = 4,
 }
 
 #[derive(Clone, Debug, Default, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
@@ -52,6 +54,9 @@ pub struct Model {
     pub config: Json,
     pub active: bool,
     pub last_fired_at: Option<NaiveDateTime>,
+    /// Worker label selector layered over the project's for evaluations this
+    /// trigger fires; a key set here overrides the project's. NULL adds none.
+    pub worker_selector: Option<Json>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
 }
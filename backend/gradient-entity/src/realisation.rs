@@ -0,0 +1,47 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+use chrono::NaiveDateTime;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::ids::{OrganizationId, RealisationId};
+
+/// The store path a build of a floating content-addressed derivation
+/// produced for one output, keyed like Nix's `DrvOutput`
+/// (`sha256:<hash modulo>!<output>`). Scoped to the organization whose build
+/// produced it; its first recorded build wins.
+#[derive(Clone, Debug, Default, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "realisation")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: RealisationId,
+    pub organization: OrganizationId,
+    /// Hash modulo of the derivation, `sha256:<base16>`.
+    pub drv_hash: String,
+    pub output_name: String,
+    /// Store hash and name of the realised output path.
+    pub hash: String,
+    pub package: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    /// Nix's `DrvOutput` id, `sha256:<base16>!<output>`.
+    pub fn id_string(&self) -> String {
+        format!("{}!{}", self.drv_hash, self.output_name)
+    }
+
+    /// Realised output path in `<hash>-<name>` form.
+    pub fn out_path(&self) -> String {
+        format!("{}-{}", self.hash, self.package)
+    }
+}
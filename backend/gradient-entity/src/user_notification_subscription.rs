@@ -0,0 +1,54 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! A user's own subscription to evaluation/build events, delivered by email
+//! independently of the project's actions.
+
+use chrono::NaiveDateTime;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::ids::{ProjectId, UserId, UserNotificationSubscriptionId};
+
+#[derive(Clone, Debug, Default, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "user_notification_subscription")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: UserNotificationSubscriptionId,
+    pub user: UserId,
+    /// `None` subscribes to every project in the user's organizations.
+    pub project: Option<ProjectId>,
+    /// JSON array of event names, e.g. `["evaluation.failed"]`.
+    pub events: Json,
+    /// Branch glob (`main`, `release/*`); `None` matches any branch.
+    pub branch: Option<String>,
+    /// Commit author name or PR author login, compared case-insensitively;
+    /// `None` matches any author.
+    pub author: Option<String>,
+    pub active: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::User",
+        to = "super::user::Column::Id",
+        on_delete = "Cascade"
+    )]
+    User,
+    #[sea_orm(
+        belongs_to = "super::project::Entity",
+        from = "Column::Project",
+        to = "super::project::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Project,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -42,6 +42,16 @@ pub struct Model {
     pub enable_build: bool,
     /// Human-readable display name for this worker (empty string if not set).
     pub display_name: String,
+    /// Labels the registering peer assigns to this worker (`{"pool": "secure"}`).
+    /// They override the worker's self-advertised labels for that peer's jobs
+    /// only. NULL means none.
+    pub labels: Option<Json>,
+    /// When true the worker takes no new jobs of the registering peer; jobs
+    /// already running finish.
+    pub cordoned: bool,
+    /// Recurring windows (`[{"schedule": "<cron>", "duration_secs": N}]`)
+    /// during which the registration is cordoned as well. NULL means none.
+    pub maintenance_windows: Option<Json>,
     /// User who created this registration. NULL for legacy rows registered
     /// before this column was introduced.
     pub created_by: Option<UserId>,
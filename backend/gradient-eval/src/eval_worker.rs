@@ -322,19 +322,24 @@ fn stream_resolve<'ev, W: Write>(
     match walker_result {
         Ok(walker) => {
             for attr in attrs {
-                let (result, warnings) = capture_warnings_during(|| walker.resolve(&attr));
+                let (result, warnings) =
+                    capture_warnings_during(|| walker.resolve_with_meta(&attr));
                 all_warnings.extend(warnings);
                 let item = match result {
-                    Ok((drv, references)) => ResolvedItem {
+                    Ok((drv, references, meta)) => ResolvedItem {
                         attr,
                         drv_path: Some(drv),
                         references,
+                        timeout_secs: meta.timeout_secs,
+                        max_silent_secs: meta.max_silent_secs,
                         error: None,
                     },
                     Err(e) => ResolvedItem {
                         attr,
                         drv_path: None,
                         references: vec![],
+                        timeout_secs: None,
+                        max_silent_secs: None,
                         error: Some(format!("{e:#}")),
                     },
                 };
@@ -353,6 +358,8 @@ fn stream_resolve<'ev, W: Write>(
                         attr,
                         drv_path: None,
                         references: vec![],
+                        timeout_secs: None,
+                        max_silent_secs: None,
                         error: Some(msg.clone()),
                     },
                 );
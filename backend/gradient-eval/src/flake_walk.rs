@@ -88,6 +88,21 @@ impl<'a> FlakeWalker<'a> {
     }
 
     pub fn resolve(&self, attr_path: &str) -> Result<(String, Vec<String>)> {
+        let cursor = self.cursor_at(attr_path)?;
+
+        self.drv_path_of(&cursor, attr_path)
+    }
+
+    /// [`Self::resolve`] plus the attribute's `meta.timeout`/`meta.maxSilent`,
+    /// read from the same cursor.
+    pub fn resolve_with_meta(&self, attr_path: &str) -> Result<(String, Vec<String>, MetaLimits)> {
+        let cursor = self.cursor_at(attr_path)?;
+        let (drv, references) = self.drv_path_of(&cursor, attr_path)?;
+
+        Ok((drv, references, self.meta_limits(&cursor)))
+    }
+
+    fn cursor_at(&self, attr_path: &str) -> Result<AttrCursor> {
         let (_, segs) = wildcard_walk::parse_pattern(attr_path);
         let mut cursor = self.cache.root()?;
         for seg in &segs {
@@ -96,6 +111,10 @@ impl<'a> FlakeWalker<'a> {
                 .ok_or_else(|| anyhow!("attribute '{seg}' not found in '{attr_path}'"))?;
         }
 
+        Ok(cursor)
+    }
+
+    fn drv_path_of(&self, cursor: &AttrCursor, attr_path: &str) -> Result<(String, Vec<String>)> {
         let drv = cursor
             .drv_path(self.state)
             .with_context(|| format!("resolving drvPath of '{attr_path}'"))?;
@@ -103,6 +122,20 @@ impl<'a> FlakeWalker<'a> {
         Ok((strip_nix_store_prefix(&drv), vec![]))
     }
 
+    /// Read `meta.timeout`/`meta.maxSilent`. A missing `meta`, a missing key
+    /// or a value that is not a non-negative integer reads as `None`, so a
+    /// malformed meta never fails the attribute.
+    fn meta_limits(&self, cursor: &AttrCursor) -> MetaLimits {
+        let Ok(Some(meta)) = cursor.maybe_get_attr("meta") else {
+            return MetaLimits::default();
+        };
+
+        MetaLimits {
+            timeout_secs: meta_secs(&meta, "timeout", self.state),
+            max_silent_secs: meta_secs(&meta, "maxSilent", self.state),
+        }
+    }
+
     /// Commit eval-cache entries written during this walk to the WAL (no
     /// checkpoint), so concurrent shard workers don't deadlock on the WAL
     /// read-slot locks. The writes are durable; [`Self::checkpoint_cache`]
@@ -164,6 +197,23 @@ pub fn fingerprint(
     Ok(locked.fingerprint(store, fetch)?)
 }
 
+/// Build limits an attribute declares in its `meta`, in seconds, as Hydra
+/// reads them: `meta.timeout` (wall clock) and `meta.maxSilent` (no output).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MetaLimits {
+    pub timeout_secs: Option<u64>,
+    pub max_silent_secs: Option<u64>,
+}
+
+fn meta_secs(meta: &AttrCursor, name: &str, state: &EvalState) -> Option<u64> {
+    let value = meta.maybe_get_attr(name).ok()??;
+
+    value
+        .get_int(state)
+        .ok()
+        .and_then(|v| u64::try_from(v).ok())
+}
+
 /// An eval-cache cursor adapted to the pure [`WalkNode`] traversal.
 struct CursorNode<'a> {
     cursor: AttrCursor,
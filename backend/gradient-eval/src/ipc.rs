@@ -34,7 +34,7 @@ use crate::stats::StatsDelta;
 /// changes. Parent and subprocess are the same re-exec'd binary, so a mismatch
 /// only happens when the binary is replaced mid-run; the handshake turns that
 /// from undecodable frames into one clear error.
-pub const EVAL_IPC_VERSION: u8 = 3;
+pub const EVAL_IPC_VERSION: u8 = 4;
 
 /// Upper bound on a single frame's payload. Far above any real message (a
 /// discovery response for a huge flake is a few MiB); its job is to turn a
@@ -62,7 +62,8 @@ pub enum EvalRequest {
         #[serde(default)]
         input_overrides: Vec<(String, String)>,
     },
-    /// Resolve a batch of attribute paths to `(drv_path, references)` tuples.
+    /// Resolve a batch of attribute paths to `(drv_path, references)` tuples
+    /// plus each attr's `meta.timeout`/`meta.maxSilent`.
     /// Answered by a `ResolveItem` stream terminated with `ResolveEnd`;
     /// per-attr failures ride inside their item, not as a top-level `Err`.
     Resolve {
@@ -134,6 +135,12 @@ pub struct ResolvedItem {
     pub drv_path: Option<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub references: Vec<String>,
+    /// The attr's `meta.timeout` in seconds, when it declares one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// The attr's `meta.maxSilent` in seconds, when it declares one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_silent_secs: Option<u64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
 }
@@ -278,6 +285,8 @@ mod tests {
                     attr: "packages.x86_64-linux.hello".into(),
                     drv_path: Some("aaaa-hello.drv".into()),
                     references: vec!["bbbb-dep".into()],
+                    timeout_secs: Some(7200),
+                    max_silent_secs: None,
                     error: None,
                 },
             },
@@ -24,6 +24,7 @@ pub use provider::ForgeProvider;
 pub use registry::ForgeRegistry;
 pub use reporter::*;
 pub use webhook::{
-    ParsedPullRequestEvent, ParsedPullRequestReviewEvent, ParsedPushEvent, ParsedReleaseEvent,
-    PushCommit, PushOutcome, WebhookEventKind,
+    AbbreviatedCommit, MergeQueueOutcome, ParsedMergeQueueEvent, ParsedPullRequestEvent,
+    ParsedPullRequestReviewEvent, ParsedPushEvent, ParsedReleaseEvent, PushCommit, PushOutcome,
+    WebhookEventKind,
 };
@@ -14,6 +14,7 @@
     reason = "arg-heavy; refactor tracked in #503"
 )]
 
+use crate::reporter::ForgeHttpError;
 use anyhow::{Context, Result, bail};
 use base64::Engine as _;
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
@@ -77,12 +78,15 @@ fn b64(bytes: &[u8]) -> String {
     base64::engine::general_purpose::STANDARD.encode(bytes)
 }
 
-async fn send_json<T: DeserializeOwned>(req: reqwest::RequestBuilder, ctx: &str) -> Result<T> {
+pub(crate) async fn send_json<T: DeserializeOwned>(
+    req: reqwest::RequestBuilder,
+    ctx: &str,
+) -> Result<T> {
     let resp = req.send().await.with_context(|| ctx.to_owned())?;
     let status = resp.status();
     if !status.is_success() {
         let body = resp.text().await.unwrap_or_default();
-        bail!("{ctx}: {status}: {body}");
+        return Err(ForgeHttpError::new(status, format!("{ctx}: {status}: {body}")).into());
     }
 
     resp.json::<T>()
@@ -90,12 +94,15 @@ async fn send_json<T: DeserializeOwned>(req: reqwest::RequestBuilder, ctx: &str)
         .with_context(|| format!("{ctx}: decoding response"))
 }
 
-async fn send_ok(req: reqwest::RequestBuilder, ctx: &str) -> Result<reqwest::StatusCode> {
+pub(crate) async fn send_ok(
+    req: reqwest::RequestBuilder,
+    ctx: &str,
+) -> Result<reqwest::StatusCode> {
     let resp = req.send().await.with_context(|| ctx.to_owned())?;
     let status = resp.status();
     if !status.is_success() {
         let body = resp.text().await.unwrap_or_default();
-        bail!("{ctx}: {status}: {body}");
+        return Err(ForgeHttpError::new(status, format!("{ctx}: {status}: {body}")).into());
     }
 
     Ok(status)
@@ -766,3 +773,434 @@ pub(crate) mod gitlab {
         description: &'a str,
     }
 }
+
+// ── Bitbucket Cloud (2.0 REST API) ──────────────────────────────────────────
+
+pub(crate) mod bitbucket {
+    use super::*;
+
+    /// Bitbucket tokens are either a bearer access token or an
+    /// `username:app_password` pair sent as basic auth.
+    pub(crate) fn auth(req: reqwest::RequestBuilder, token: &str) -> reqwest::RequestBuilder {
+        let req = req.header("Content-Type", "application/json");
+        match token.split_once(':') {
+            Some((user, password)) => req.basic_auth(user, Some(password)),
+            None => req.bearer_auth(token),
+        }
+    }
+
+    /// `value` as a double-quoted BBQL string literal for a `q=` filter, with
+    /// `\` and `"` escaped so it cannot end the literal early.
+    pub(crate) fn bbql_string(value: &str) -> String {
+        let mut out = String::with_capacity(value.len() + 2);
+        out.push('"');
+        for c in value.chars() {
+            if matches!(c, '"' | '\\') {
+                out.push('\\');
+            }
+            out.push(c);
+        }
+        out.push('"');
+        out
+    }
+
+    /// HTTPS basic-auth pair git accepts for the same token: the app-password
+    /// pair as-is, or `x-token-auth` for an access token.
+    pub(crate) fn git_credentials(token: &str) -> (String, String) {
+        match token.split_once(':') {
+            Some((user, password)) => (user.to_owned(), password.to_owned()),
+            None => ("x-token-auth".to_owned(), token.to_owned()),
+        }
+    }
+
+    pub async fn open_or_update_pr(
+        client: &reqwest::Client,
+        api: &str,
+        token: &str,
+        owner: &str,
+        repo: &str,
+        head: &str,
+        base: &str,
+        title: &str,
+        body: &str,
+    ) -> Result<super::PrRef> {
+        let query: String = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("state", "OPEN")
+            .append_pair("q", &format!("source.branch.name={}", bbql_string(head)))
+            .finish();
+        let open: Page<Pull> = send_json(
+            auth(
+                client.get(format!(
+                    "{api}/repositories/{owner}/{repo}/pullrequests?{query}"
+                )),
+                token,
+            ),
+            "bitbucket list pull requests",
+        )
+        .await?;
+
+        if let Some(pr) = open.values.into_iter().next() {
+            send_ok(
+                auth(
+                    client.put(format!(
+                        "{api}/repositories/{owner}/{repo}/pullrequests/{}",
+                        pr.id
+                    )),
+                    token,
+                )
+                .json(&EditPull {
+                    title,
+                    description: body,
+                }),
+                "bitbucket update pull request",
+            )
+            .await?;
+
+            return Ok(pr.into_ref());
+        }
+
+        let created: Pull = send_json(
+            auth(
+                client.post(format!("{api}/repositories/{owner}/{repo}/pullrequests")),
+                token,
+            )
+            .json(&CreatePull {
+                title,
+                description: body,
+                source: Endpoint {
+                    branch: Branch { name: head },
+                },
+                destination: Endpoint {
+                    branch: Branch { name: base },
+                },
+            }),
+            "bitbucket create pull request",
+        )
+        .await?;
+
+        Ok(created.into_ref())
+    }
+
+    pub async fn default_branch(
+        client: &reqwest::Client,
+        api: &str,
+        token: &str,
+        owner: &str,
+        repo: &str,
+    ) -> Result<String> {
+        let info: RepoInfo = send_json(
+            auth(
+                client.get(format!("{api}/repositories/{owner}/{repo}")),
+                token,
+            ),
+            "bitbucket get repository",
+        )
+        .await?;
+
+        Ok(info.mainbranch.name)
+    }
+
+    /// The token owner, used as the commit identity libgit2 requires for the
+    /// force-push path. Repository/workspace access tokens have no user, so
+    /// callers fall back to the Gradient bot.
+    pub async fn authenticated_user(
+        client: &reqwest::Client,
+        api: &str,
+        token: &str,
+        git_base: &str,
+    ) -> Result<super::CommitIdent> {
+        let user: User = send_json(
+            auth(client.get(format!("{api}/user")), token),
+            "bitbucket get user",
+        )
+        .await?;
+        let name = Some(user.display_name)
+            .filter(|n| !n.trim().is_empty())
+            .unwrap_or(user.nickname.clone());
+        // Bitbucket never returns the address on `/user`.
+        let email = format!(
+            "{}@users.noreply.{}",
+            user.nickname,
+            super::host_of(git_base)
+        );
+
+        Ok(super::CommitIdent { name, email })
+    }
+
+    #[derive(Deserialize)]
+    struct Page<T> {
+        #[serde(default = "Vec::new")]
+        values: Vec<T>,
+    }
+    #[derive(Deserialize)]
+    struct RepoInfo {
+        mainbranch: MainBranch,
+    }
+    #[derive(Deserialize)]
+    struct MainBranch {
+        name: String,
+    }
+    #[derive(Deserialize)]
+    struct User {
+        nickname: String,
+        #[serde(default)]
+        display_name: String,
+    }
+    #[derive(Deserialize)]
+    struct Pull {
+        id: i64,
+        #[serde(default)]
+        links: Option<PullLinks>,
+    }
+    #[derive(Deserialize)]
+    struct PullLinks {
+        html: Option<Href>,
+    }
+    #[derive(Deserialize)]
+    struct Href {
+        href: String,
+    }
+    impl Pull {
+        fn into_ref(self) -> super::PrRef {
+            super::PrRef {
+                number: self.id,
+                url: self.links.and_then(|l| l.html).map(|h| h.href),
+            }
+        }
+    }
+    #[derive(Serialize)]
+    struct CreatePull<'a> {
+        title: &'a str,
+        description: &'a str,
+        source: Endpoint<'a>,
+        destination: Endpoint<'a>,
+    }
+    #[derive(Serialize)]
+    struct Endpoint<'a> {
+        branch: Branch<'a>,
+    }
+    #[derive(Serialize)]
+    struct Branch<'a> {
+        name: &'a str,
+    }
+    #[derive(Serialize)]
+    struct EditPull<'a> {
+        title: &'a str,
+        description: &'a str,
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn bbql_string_escapes_quotes_and_backslashes() {
+            assert_eq!(bbql_string("feature/x"), r#""feature/x""#);
+            assert_eq!(
+                bbql_string(r#"a" OR user.nickname="b\"#),
+                r#""a\" OR user.nickname=\"b\\""#
+            );
+        }
+    }
+}
+
+// ── Bitbucket Server / Data Center (1.0 REST API) ───────────────────────────
+
+pub(crate) mod bitbucket_server {
+    use super::*;
+
+    pub(crate) use super::bitbucket::{auth, git_credentials};
+
+    pub async fn open_or_update_pr(
+        client: &reqwest::Client,
+        base_url: &str,
+        token: &str,
+        project: &str,
+        slug: &str,
+        head: &str,
+        base: &str,
+        title: &str,
+        body: &str,
+    ) -> Result<super::PrRef> {
+        let repo_api = format!("{base_url}/rest/api/1.0/projects/{project}/repos/{slug}");
+        let head_ref = format!("refs/heads/{head}");
+        let query: String = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("state", "OPEN")
+            .append_pair("direction", "OUTGOING")
+            .append_pair("at", &head_ref)
+            .finish();
+        let open: Page<Pull> = send_json(
+            auth(
+                client.get(format!("{repo_api}/pull-requests?{query}")),
+                token,
+            ),
+            "bitbucket server list pull requests",
+        )
+        .await?;
+
+        if let Some(pr) = open.values.into_iter().next() {
+            send_ok(
+                auth(
+                    client.put(format!("{repo_api}/pull-requests/{}", pr.id)),
+                    token,
+                )
+                .json(&EditPull {
+                    version: pr.version,
+                    title,
+                    description: body,
+                }),
+                "bitbucket server update pull request",
+            )
+            .await?;
+
+            return Ok(pr.into_ref());
+        }
+
+        let created: Pull = send_json(
+            auth(client.post(format!("{repo_api}/pull-requests")), token).json(&CreatePull {
+                title,
+                description: body,
+                from_ref: RefId { id: &head_ref },
+                to_ref: RefId {
+                    id: &format!("refs/heads/{base}"),
+                },
+            }),
+            "bitbucket server create pull request",
+        )
+        .await?;
+
+        Ok(created.into_ref())
+    }
+
+    pub async fn default_branch(
+        client: &reqwest::Client,
+        base_url: &str,
+        token: &str,
+        project: &str,
+        slug: &str,
+    ) -> Result<String> {
+        let branch: DefaultBranch = send_json(
+            auth(
+                client.get(format!(
+                    "{base_url}/rest/api/1.0/projects/{project}/repos/{slug}/default-branch"
+                )),
+                token,
+            ),
+            "bitbucket server get default branch",
+        )
+        .await?;
+
+        Ok(branch.display_id)
+    }
+
+    /// The token owner, used as the commit identity libgit2 requires for the
+    /// force-push path. `whoami` answers with the bare username.
+    pub async fn authenticated_user(
+        client: &reqwest::Client,
+        base_url: &str,
+        token: &str,
+    ) -> Result<super::CommitIdent> {
+        let resp = auth(
+            client.get(format!("{base_url}/plugins/servlet/applinks/whoami")),
+            token,
+        )
+        .send()
+        .await
+        .context("bitbucket server whoami")?;
+        let username = resp
+            .error_for_status()
+            .context("bitbucket server whoami")?
+            .text()
+            .await
+            .context("bitbucket server whoami: decoding response")?
+            .trim()
+            .to_owned();
+        if username.is_empty() {
+            bail!("bitbucket server whoami: token has no user");
+        }
+        let user: User = send_json(
+            auth(
+                client.get(format!("{base_url}/rest/api/1.0/users/{username}")),
+                token,
+            ),
+            "bitbucket server get user",
+        )
+        .await?;
+        let name = user
+            .display_name
+            .filter(|n| !n.trim().is_empty())
+            .unwrap_or_else(|| user.name.clone());
+        let email = user
+            .email_address
+            .filter(|e| !e.trim().is_empty())
+            .unwrap_or_else(|| format!("{}@users.noreply.{}", user.name, super::host_of(base_url)));
+
+        Ok(super::CommitIdent { name, email })
+    }
+
+    #[derive(Deserialize)]
+    struct Page<T> {
+        #[serde(default = "Vec::new")]
+        values: Vec<T>,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct DefaultBranch {
+        display_id: String,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct User {
+        name: String,
+        #[serde(default)]
+        display_name: Option<String>,
+        #[serde(default)]
+        email_address: Option<String>,
+    }
+    #[derive(Deserialize)]
+    struct Pull {
+        id: i64,
+        #[serde(default)]
+        version: i64,
+        #[serde(default)]
+        links: Option<PullLinks>,
+    }
+    #[derive(Deserialize)]
+    struct PullLinks {
+        #[serde(rename = "self", default)]
+        self_links: Vec<Href>,
+    }
+    #[derive(Deserialize)]
+    struct Href {
+        href: String,
+    }
+    impl Pull {
+        fn into_ref(self) -> super::PrRef {
+            super::PrRef {
+                number: self.id,
+                url: self
+                    .links
+                    .and_then(|l| l.self_links.into_iter().next())
+                    .map(|h| h.href),
+            }
+        }
+    }
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct CreatePull<'a> {
+        title: &'a str,
+        description: &'a str,
+        from_ref: RefId<'a>,
+        to_ref: RefId<'a>,
+    }
+    #[derive(Serialize)]
+    struct RefId<'a> {
+        id: &'a str,
+    }
+    #[derive(Serialize)]
+    struct EditPull<'a> {
+        version: i64,
+        title: &'a str,
+        description: &'a str,
+    }
+}
@@ -13,7 +13,9 @@
 use std::sync::Arc;
 
 use crate::reporter::CiReporter;
-use crate::webhook::{ParsedPullRequestEvent, ParsedReleaseEvent, PushOutcome, WebhookEventKind};
+use crate::webhook::{
+    MergeQueueOutcome, ParsedPullRequestEvent, ParsedReleaseEvent, PushOutcome, WebhookEventKind,
+};
 use gradient_types::ForgeType;
 
 pub trait ForgeProvider: Send + Sync + std::fmt::Debug {
@@ -46,7 +48,7 @@ pub trait ForgeProvider: Send + Sync + std::fmt::Debug {
     /// present wins), passed to [`verify_signature`](Self::verify_signature).
     fn signature_headers(&self) -> &'static [&'static str];
 
-    /// Verify a webhook signature/token (HMAC for Gitea/GitHub, constant-time
+    /// Verify a webhook signature/token (HMAC for Gitea/GitHub/Bitbucket, constant-time
     /// token equality for GitLab) against the integration secret.
     fn verify_signature(&self, secret: &str, signature: &str, body: &[u8]) -> bool;
 
@@ -56,7 +58,19 @@ pub trait ForgeProvider: Send + Sync + std::fmt::Debug {
     /// Map a raw forge event string onto the shared [`WebhookEventKind`].
     fn classify_event(&self, event: &str) -> WebhookEventKind;
 
+    /// PR action implied by the raw event name, for forges whose payload does
+    /// not carry one (Bitbucket). Overrides the parsed `action` when `Some`.
+    fn pull_request_action(&self, _event: &str) -> Option<&'static str> {
+        None
+    }
+
     fn parse_push_event(&self, body: &[u8]) -> Option<PushOutcome>;
     fn parse_pull_request_event(&self, body: &[u8]) -> Option<ParsedPullRequestEvent>;
     fn parse_release_event(&self, body: &[u8]) -> Option<ParsedReleaseEvent>;
+
+    /// Parse a [`WebhookEventKind::MergeQueue`] delivery. Forges without a
+    /// merge queue never classify an event that way, so the default ignores it.
+    fn parse_merge_queue_event(&self, _body: &[u8]) -> Option<MergeQueueOutcome> {
+        Some(MergeQueueOutcome::Ignored)
+    }
 }
@@ -0,0 +1,117 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Bitbucket Cloud provider. Webhooks are signed like GitHub's
+//! (`X-Hub-Signature: sha256=<hex>`) and name the event in `X-Event-Key`.
+//! Bitbucket has no releases; tags arrive as `repo:push` changes.
+
+use std::sync::Arc;
+
+use crate::github_app::verify_github_signature;
+use crate::provider::ForgeProvider;
+use crate::reporter::{BitbucketReporter, CiReporter};
+use crate::webhook::{
+    ParsedPullRequestEvent, ParsedPushEvent, ParsedReleaseEvent, PushOutcome, WebhookEventKind,
+    bitbucket_pr_action,
+};
+use anyhow::anyhow;
+use gradient_types::ForgeType;
+
+#[derive(Debug)]
+pub struct BitbucketProvider;
+
+impl ForgeProvider for BitbucketProvider {
+    fn forge_type(&self) -> ForgeType {
+        ForgeType::Bitbucket
+    }
+
+    fn build_reporter(
+        &self,
+        http: reqwest::Client,
+        endpoint_url: Option<&str>,
+        token: Option<&str>,
+    ) -> anyhow::Result<Arc<dyn CiReporter>> {
+        let token = token.ok_or_else(|| anyhow!("Bitbucket integration missing token"))?;
+
+        Ok(Arc::new(BitbucketReporter::new(
+            http,
+            endpoint_url.unwrap_or_default(),
+            token,
+        )?))
+    }
+
+    fn signature_headers(&self) -> &'static [&'static str] {
+        &["X-Hub-Signature"]
+    }
+
+    fn verify_signature(&self, secret: &str, signature: &str, body: &[u8]) -> bool {
+        verify_github_signature(secret, signature, body)
+    }
+
+    fn event_headers(&self) -> &'static [&'static str] {
+        &["X-Event-Key"]
+    }
+
+    fn classify_event(&self, event: &str) -> WebhookEventKind {
+        match event {
+            "repo:push" => WebhookEventKind::Push,
+            "pullrequest:created"
+            | "pullrequest:updated"
+            | "pullrequest:fulfilled"
+            | "pullrequest:rejected" => WebhookEventKind::PullRequest,
+            "pullrequest:comment_created" => WebhookEventKind::Comment,
+            "pullrequest:approved" => WebhookEventKind::Review,
+            other => WebhookEventKind::Unknown(other.to_string()),
+        }
+    }
+
+    fn pull_request_action(&self, event: &str) -> Option<&'static str> {
+        bitbucket_pr_action(event)
+    }
+
+    fn parse_push_event(&self, body: &[u8]) -> Option<PushOutcome> {
+        ParsedPushEvent::from_bitbucket(body)
+    }
+
+    fn parse_pull_request_event(&self, body: &[u8]) -> Option<ParsedPullRequestEvent> {
+        ParsedPullRequestEvent::from_bitbucket(body)
+    }
+
+    fn parse_release_event(&self, _body: &[u8]) -> Option<ParsedReleaseEvent> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_unprefixed_signature() {
+        assert!(!BitbucketProvider.verify_signature("s3cret", "deadbeef", b"body"));
+    }
+
+    #[test]
+    fn classifies_cloud_event_keys() {
+        let p = BitbucketProvider;
+        assert!(matches!(
+            p.classify_event("repo:push"),
+            WebhookEventKind::Push
+        ));
+        assert!(matches!(
+            p.classify_event("pullrequest:updated"),
+            WebhookEventKind::PullRequest
+        ));
+        assert!(matches!(
+            p.classify_event("pullrequest:approved"),
+            WebhookEventKind::Review
+        ));
+        assert_eq!(
+            p.pull_request_action("pullrequest:updated"),
+            Some("synchronize")
+        );
+    }
+}
@@ -0,0 +1,122 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Bitbucket Server / Data Center provider. Same signing scheme as Bitbucket
+//! Cloud (`X-Hub-Signature: sha256=<hex>`), but a different payload shape and
+//! `pr:*` / `repo:refs_changed` event keys.
+
+use std::sync::Arc;
+
+use crate::github_app::verify_github_signature;
+use crate::provider::ForgeProvider;
+use crate::reporter::{BitbucketServerReporter, CiReporter};
+use crate::webhook::{
+    ParsedPullRequestEvent, ParsedPushEvent, ParsedReleaseEvent, PushOutcome, WebhookEventKind,
+    bitbucket_pr_action,
+};
+use anyhow::anyhow;
+use gradient_types::ForgeType;
+
+#[derive(Debug)]
+pub struct BitbucketServerProvider;
+
+impl ForgeProvider for BitbucketServerProvider {
+    fn forge_type(&self) -> ForgeType {
+        ForgeType::BitbucketServer
+    }
+
+    fn build_reporter(
+        &self,
+        http: reqwest::Client,
+        endpoint_url: Option<&str>,
+        token: Option<&str>,
+    ) -> anyhow::Result<Arc<dyn CiReporter>> {
+        let base_url = endpoint_url
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| anyhow!("Bitbucket Server integration missing endpoint_url"))?;
+        let token = token.ok_or_else(|| anyhow!("Bitbucket Server integration missing token"))?;
+
+        Ok(Arc::new(BitbucketServerReporter::new(
+            http, base_url, token,
+        )?))
+    }
+
+    fn signature_headers(&self) -> &'static [&'static str] {
+        &["X-Hub-Signature"]
+    }
+
+    fn verify_signature(&self, secret: &str, signature: &str, body: &[u8]) -> bool {
+        verify_github_signature(secret, signature, body)
+    }
+
+    fn event_headers(&self) -> &'static [&'static str] {
+        &["X-Event-Key"]
+    }
+
+    fn classify_event(&self, event: &str) -> WebhookEventKind {
+        match event {
+            "repo:refs_changed" => WebhookEventKind::Push,
+            "pr:opened" | "pr:from_ref_updated" | "pr:merged" | "pr:declined" => {
+                WebhookEventKind::PullRequest
+            }
+            "pr:comment:added" => WebhookEventKind::Comment,
+            "pr:reviewer:approved" => WebhookEventKind::Review,
+            other => WebhookEventKind::Unknown(other.to_string()),
+        }
+    }
+
+    fn pull_request_action(&self, event: &str) -> Option<&'static str> {
+        bitbucket_pr_action(event)
+    }
+
+    fn parse_push_event(&self, body: &[u8]) -> Option<PushOutcome> {
+        ParsedPushEvent::from_bitbucket_server(body)
+    }
+
+    fn parse_pull_request_event(&self, body: &[u8]) -> Option<ParsedPullRequestEvent> {
+        ParsedPullRequestEvent::from_bitbucket_server(body)
+    }
+
+    fn parse_release_event(&self, _body: &[u8]) -> Option<ParsedReleaseEvent> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requires_endpoint_url() {
+        let http = gradient_util::http::build_client().unwrap();
+        assert!(
+            BitbucketServerProvider
+                .build_reporter(http, None, Some("tok"))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn classifies_server_event_keys() {
+        let p = BitbucketServerProvider;
+        assert!(matches!(
+            p.classify_event("repo:refs_changed"),
+            WebhookEventKind::Push
+        ));
+        assert!(matches!(
+            p.classify_event("pr:from_ref_updated"),
+            WebhookEventKind::PullRequest
+        ));
+        assert!(matches!(
+            p.classify_event("pr:comment:added"),
+            WebhookEventKind::Comment
+        ));
+        assert!(matches!(
+            p.classify_event("mirror:repo_synchronized"),
+            WebhookEventKind::Unknown(_)
+        ));
+    }
+}
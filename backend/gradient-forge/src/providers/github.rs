@@ -18,7 +18,8 @@ use crate::github_app::verify_github_signature;
 use crate::provider::ForgeProvider;
 use crate::reporter::{CiReporter, GithubReporter};
 use crate::webhook::{
-    ParsedPullRequestEvent, ParsedPushEvent, ParsedReleaseEvent, PushOutcome, WebhookEventKind,
+    MergeQueueOutcome, ParsedMergeQueueEvent, ParsedPullRequestEvent, ParsedPushEvent,
+    ParsedReleaseEvent, PushOutcome, WebhookEventKind,
 };
 use gradient_types::ForgeType;
 
@@ -80,4 +81,8 @@ impl ForgeProvider for GithubProvider {
     fn parse_release_event(&self, body: &[u8]) -> Option<ParsedReleaseEvent> {
         ParsedReleaseEvent::from_github(body)
     }
+
+    fn parse_merge_queue_event(&self, body: &[u8]) -> Option<MergeQueueOutcome> {
+        ParsedMergeQueueEvent::from_github(body)
+    }
 }
@@ -15,7 +15,8 @@ use subtle::ConstantTimeEq;
 use crate::provider::ForgeProvider;
 use crate::reporter::{CiReporter, GitlabReporter};
 use crate::webhook::{
-    ParsedPullRequestEvent, ParsedPushEvent, ParsedReleaseEvent, PushOutcome, WebhookEventKind,
+    MergeQueueOutcome, ParsedMergeQueueEvent, ParsedPullRequestEvent, ParsedPushEvent,
+    ParsedReleaseEvent, PushOutcome, WebhookEventKind,
 };
 use gradient_types::ForgeType;
 
@@ -59,6 +60,7 @@ impl ForgeProvider for GitlabProvider {
             "Merge Request Hook" => WebhookEventKind::PullRequest,
             "Release Hook" => WebhookEventKind::Release,
             "Note Hook" => WebhookEventKind::Comment,
+            "Pipeline Hook" => WebhookEventKind::MergeQueue,
             other => WebhookEventKind::Unknown(other.to_string()),
         }
     }
@@ -74,6 +76,10 @@ impl ForgeProvider for GitlabProvider {
     fn parse_release_event(&self, body: &[u8]) -> Option<ParsedReleaseEvent> {
         ParsedReleaseEvent::from_gitlab(body)
     }
+
+    fn parse_merge_queue_event(&self, body: &[u8]) -> Option<MergeQueueOutcome> {
+        ParsedMergeQueueEvent::from_gitlab(body)
+    }
 }
 
 #[cfg(test)]
@@ -94,4 +100,12 @@ mod tests {
     fn rejects_missing_token() {
         assert!(!GitlabProvider.verify_signature("s3cret", "", b""));
     }
+
+    #[test]
+    fn classifies_pipeline_hook_as_merge_queue() {
+        assert_eq!(
+            GitlabProvider.classify_event("Pipeline Hook"),
+            WebhookEventKind::MergeQueue
+        );
+    }
 }
@@ -5,8 +5,10 @@
  */
 
 //! One [`ForgeProvider`](crate::ForgeProvider) impl per forge. `gitea`
-//! serves both Gitea and Forgejo (identical APIs).
+//! serves both Gitea and Forgejo (identical APIs, Codeberg included).
 
+pub mod bitbucket;
+pub mod bitbucket_server;
 pub mod gitea;
 pub mod github;
 pub mod gitlab;
@@ -11,7 +11,10 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::provider::ForgeProvider;
-use crate::providers::{gitea::GiteaProvider, github::GithubProvider, gitlab::GitlabProvider};
+use crate::providers::{
+    bitbucket::BitbucketProvider, bitbucket_server::BitbucketServerProvider, gitea::GiteaProvider,
+    github::GithubProvider, gitlab::GitlabProvider,
+};
 use gradient_types::ForgeType;
 
 #[derive(Clone, Debug)]
@@ -34,6 +37,11 @@ impl ForgeRegistry {
         );
         providers.insert(ForgeType::GitLab, Arc::new(GitlabProvider));
         providers.insert(ForgeType::GitHub, Arc::new(GithubProvider));
+        providers.insert(ForgeType::Bitbucket, Arc::new(BitbucketProvider));
+        providers.insert(
+            ForgeType::BitbucketServer,
+            Arc::new(BitbucketServerProvider),
+        );
 
         Self {
             providers: Arc::new(providers),
@@ -4,7 +4,7 @@
  * SPDX-License-Identifier: AGPL-3.0-only
  */
 
-use crate::pr::{BranchCommit, CommitIdent, PrRef};
+use crate::pr::{BranchCommit, CommitIdent, PrRef, bitbucket, send_json, send_ok};
 use crate::registry::ForgeRegistry;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
@@ -33,6 +33,35 @@ fn validate_safe_outbound_url(url: &str) -> Result<(), WebhookUrlError> {
     validate_webhook_url(url).map(|_| ())
 }
 
+/// A non-2xx forge API answer. Carried inside the `anyhow` chain so callers
+/// can tell a rejection (4xx) from an outage without parsing the message.
+#[derive(Debug)]
+pub struct ForgeHttpError {
+    pub status: reqwest::StatusCode,
+    message: String,
+}
+
+impl ForgeHttpError {
+    pub fn new(status: reqwest::StatusCode, message: String) -> Self {
+        Self { status, message }
+    }
+
+    /// The HTTP status of the first [`ForgeHttpError`] in `err`'s chain.
+    pub fn status_of(err: &anyhow::Error) -> Option<reqwest::StatusCode> {
+        err.chain()
+            .find_map(|e| e.downcast_ref::<Self>())
+            .map(|e| e.status)
+    }
+}
+
+impl std::fmt::Display for ForgeHttpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ForgeHttpError {}
+
 /// The lifecycle state of a CI check.
 ///
 /// Maps to both the GitHub Checks API (`queued` / `in_progress` / conclusion)
@@ -129,6 +158,8 @@ pub struct PullRequestSnapshot {
     pub head_sha: String,
     /// PR head branch name (without `refs/heads/` prefix).
     pub head_branch: String,
+    /// Branch the PR merges into, when the forge reports it.
+    pub base_branch: Option<String>,
     /// Clone URL of the PR head repo when the PR is from a fork; `None` for
     /// same-repo PRs. Mirrors the `head_repo_clone_url` extracted from
     /// `pull_request` webhook payloads so the existing PR-trigger fanout can
@@ -181,6 +212,8 @@ pub struct CiReport {
 /// - `GiteaReporter` - Gitea Commit Status API.
 /// - `GitlabReporter` - GitLab Commit Status API.
 /// - `GithubReporter` - GitHub Commit Status API (also works with GitHub Enterprise Server).
+/// - `BitbucketReporter` - Bitbucket Cloud build status API.
+/// - `BitbucketServerReporter` - Bitbucket Server / Data Center build status API.
 #[async_trait]
 pub trait CiReporter: Send + Sync + std::fmt::Debug + 'static {
     /// Report or update a CI status for the given commit.
@@ -216,6 +249,23 @@ pub trait CiReporter: Send + Sync + std::fmt::Debug + 'static {
         Ok(())
     }
 
+    /// Edit the PR/MR comment this reporter's account wrote whose body
+    /// contains `marker`, or post `body` as a new comment when there is none. Keeps a recurring report (the eval
+    /// diff) to one comment per PR instead of one per evaluation; `body`
+    /// must itself contain `marker` so the next call finds it.
+    ///
+    /// Default impl always posts, for reporters that cannot edit comments.
+    async fn upsert_pr_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        _marker: &str,
+        body: &str,
+    ) -> Result<()> {
+        self.post_pr_comment(owner, repo, pr_number, body).await
+    }
+
     /// Fetch the current head metadata of an open pull/merge request.
     /// Used by the `/gradient run` comment handler so it can create a fresh
     /// evaluation when no parked approval gate exists for the PR.
@@ -232,6 +282,15 @@ pub trait CiReporter: Send + Sync + std::fmt::Debug + 'static {
         Ok(None)
     }
 
+    /// Expand an abbreviated commit `hash` of the `owner/repo` named by
+    /// `full_name` to the full SHA. Used for webhook payloads that only
+    /// carry the short form (Bitbucket Cloud pull requests).
+    ///
+    /// Default impl returns `Ok(None)`.
+    async fn expand_commit_hash(&self, _full_name: &str, _hash: &str) -> Result<Option<String>> {
+        Ok(None)
+    }
+
     /// Attach a reaction to a PR/MR comment so the commenter gets visual
     /// feedback on the lifecycle of their `/gradient` command (eyes on
     /// receipt, thumbs-up/down on terminal eval status, confused on
@@ -342,6 +401,150 @@ struct ForgeCommentPayload<'a> {
     body: &'a str,
 }
 
+/// Comments fetched per page while looking for a marked comment. Gitea caps
+/// pages at 50 by default, so larger pages would end the scan early.
+const COMMENT_PAGE_SIZE: usize = 50;
+/// Pages scanned before giving up and posting a fresh comment.
+const MAX_COMMENT_PAGES: u32 = 20;
+
+#[derive(Debug, Deserialize)]
+struct ForgeComment {
+    id: i64,
+    #[serde(default)]
+    body: Option<String>,
+    /// Author on GitHub and Gitea.
+    #[serde(default)]
+    user: Option<ForgeAccount>,
+    /// Author on GitLab.
+    #[serde(default)]
+    author: Option<ForgeAccount>,
+    /// GitHub: the App the comment was posted through.
+    #[serde(default)]
+    performed_via_github_app: Option<ForgeAccount>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ForgeAccount {
+    id: i64,
+}
+
+/// The account whose comments [`upsert_marked_comment`] may edit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CommentOwner {
+    /// The token's own user, read from this endpoint (`{id, ...}`).
+    TokenUser(String),
+    /// Comments posted through this GitHub App; an installation token cannot
+    /// read `/user`.
+    GithubApp(i64),
+}
+
+/// [`CommentOwner`] with the token user's id resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OwnAccount {
+    User(i64),
+    GithubApp(i64),
+}
+
+impl ForgeComment {
+    /// Whether `own` wrote this comment. Anyone on the PR can paste the
+    /// marker, and their comment is not ours to edit.
+    fn written_by(&self, own: OwnAccount) -> bool {
+        match own {
+            OwnAccount::User(id) => self
+                .user
+                .as_ref()
+                .or(self.author.as_ref())
+                .is_some_and(|a| a.id == id),
+            OwnAccount::GithubApp(id) => self
+                .performed_via_github_app
+                .as_ref()
+                .is_some_and(|a| a.id == id),
+        }
+    }
+}
+
+/// A forge's PR-comment API as used by [`upsert_marked_comment`]. GitHub,
+/// Gitea and GitLab all list and edit `{id, body}` comments.
+struct CommentApi {
+    /// Comments of the PR; also the create endpoint.
+    url: String,
+    /// Query parameter carrying the page size.
+    page_size_param: &'static str,
+    /// Edit endpoint prefix; the comment id is appended.
+    edit_prefix: String,
+    edit_method: reqwest::Method,
+    owner: CommentOwner,
+    forge: &'static str,
+}
+
+/// Edit the marked comment `api.owner` wrote, else post a new one. When the
+/// owner cannot be resolved no comment is edited.
+async fn upsert_marked_comment<A>(
+    client: &reqwest::Client,
+    auth: A,
+    api: CommentApi,
+    marker: &str,
+    body: &str,
+) -> Result<()>
+where
+    A: Fn(reqwest::RequestBuilder) -> reqwest::RequestBuilder,
+{
+    let payload = ForgeCommentPayload { body };
+    let own = match &api.owner {
+        CommentOwner::GithubApp(id) => Some(OwnAccount::GithubApp(*id)),
+        CommentOwner::TokenUser(url) => {
+            match send_json::<ForgeAccount>(
+                auth(client.get(url)),
+                &format!("{} token user", api.forge),
+            )
+            .await
+            {
+                Ok(user) => Some(OwnAccount::User(user.id)),
+                Err(e) => {
+                    warn!(forge = api.forge, error = %e, "PR comment upsert: token user lookup failed; posting a new comment");
+                    None
+                }
+            }
+        }
+    };
+    let mut existing = None;
+    let pages = if own.is_some() { MAX_COMMENT_PAGES } else { 0 };
+    for page in 1..=pages {
+        let url = format!(
+            "{}?{}={}&page={}",
+            api.url, api.page_size_param, COMMENT_PAGE_SIZE, page
+        );
+        let comments: Vec<ForgeComment> = send_json(
+            auth(client.get(&url)),
+            &format!("{} PR comments", api.forge),
+        )
+        .await?;
+        existing = comments
+            .iter()
+            .filter(|c| own.is_some_and(|own| c.written_by(own)))
+            .find(|c| c.body.as_deref().is_some_and(|b| b.contains(marker)))
+            .map(|c| c.id);
+        if existing.is_some() || comments.len() < COMMENT_PAGE_SIZE {
+            break;
+        }
+    }
+
+    let req = match existing {
+        Some(id) => client.request(api.edit_method, format!("{}{}", api.edit_prefix, id)),
+        None => client.post(&api.url),
+    };
+    if let Err(e) = send_ok(
+        auth(req).json(&payload),
+        &format!("{} PR comment upsert", api.forge),
+    )
+    .await
+    {
+        warn!(forge = api.forge, error = %e, "PR comment upsert failed");
+        return Err(e);
+    }
+    Ok(())
+}
+
 impl GiteaReporter {
     pub fn new(
         client: reqwest::Client,
@@ -424,7 +627,11 @@ impl CiReporter for GiteaReporter {
                 body = %body,
                 "Gitea CI status report failed"
             );
-            anyhow::bail!("Gitea returned {}: {}", status, body);
+            return Err(ForgeHttpError::new(
+                status,
+                format!("Gitea returned {}: {}", status, body),
+            )
+            .into());
         }
 
         Ok(None)
@@ -498,6 +705,31 @@ impl CiReporter for GiteaReporter {
         Ok(())
     }
 
+    async fn upsert_pr_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        marker: &str,
+        body: &str,
+    ) -> Result<()> {
+        let api = CommentApi {
+            url: gitea_comment_url(&self.base_url, owner, repo, pr_number),
+            page_size_param: "limit",
+            edit_prefix: format!(
+                "{}/api/v1/repos/{}/{}/issues/comments/",
+                self.base_url, owner, repo
+            ),
+            edit_method: reqwest::Method::PATCH,
+            owner: CommentOwner::TokenUser(format!("{}/api/v1/user", self.base_url)),
+            forge: "gitea",
+        };
+        let auth = |req: reqwest::RequestBuilder| {
+            req.header("Authorization", format!("token {}", self.token))
+        };
+        upsert_marked_comment(&self.client, auth, api, marker, body).await
+    }
+
     async fn add_reaction(&self, target: &ReactionTarget, kind: ReactionKind) -> Result<()> {
         let url = format!(
             "{}/api/v1/repos/{}/{}/issues/comments/{}/reactions",
@@ -583,6 +815,7 @@ impl CiReporter for GiteaReporter {
             .await
             .context("Failed to parse Gitea pull request response")?;
         let head_branch = pr.head.ref_.clone().unwrap_or_default();
+        let base_branch = pr.base.as_ref().and_then(|b| b.ref_.clone());
         let head_full = pr.head.repo.as_ref().and_then(|r| r.full_name.clone());
         let base_full = pr
             .base
@@ -599,6 +832,7 @@ impl CiReporter for GiteaReporter {
         Ok(Some(PullRequestSnapshot {
             head_sha: pr.head.sha,
             head_branch,
+            base_branch,
             head_clone_url,
             is_fork,
         }))
@@ -782,7 +1016,11 @@ impl CiReporter for GitlabReporter {
                 body = %body,
                 "GitLab CI status report failed"
             );
-            anyhow::bail!("GitLab returned {}: {}", status, body);
+            return Err(ForgeHttpError::new(
+                status,
+                format!("GitLab returned {}: {}", status, body),
+            )
+            .into());
         }
 
         Ok(None)
@@ -856,6 +1094,27 @@ impl CiReporter for GitlabReporter {
         Ok(())
     }
 
+    async fn upsert_pr_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        marker: &str,
+        body: &str,
+    ) -> Result<()> {
+        let url = gitlab_comment_url(&self.base_url, owner, repo, pr_number);
+        let api = CommentApi {
+            edit_prefix: format!("{}/", url),
+            url,
+            page_size_param: "per_page",
+            edit_method: reqwest::Method::PUT,
+            owner: CommentOwner::TokenUser(format!("{}/api/v4/user", self.base_url)),
+            forge: "gitlab",
+        };
+        let auth = |req: reqwest::RequestBuilder| req.header("PRIVATE-TOKEN", &self.token);
+        upsert_marked_comment(&self.client, auth, api, marker, body).await
+    }
+
     async fn add_reaction(&self, target: &ReactionTarget, kind: ReactionKind) -> Result<()> {
         let project_id = gitlab_project_id(&target.owner, &target.repo);
         let url = format!(
@@ -927,6 +1186,8 @@ impl CiReporter for GitlabReporter {
             sha: String,
             source_branch: String,
             #[serde(default)]
+            target_branch: Option<String>,
+            #[serde(default)]
             source_project_id: Option<u64>,
             #[serde(default)]
             target_project_id: Option<u64>,
@@ -948,6 +1209,7 @@ impl CiReporter for GitlabReporter {
         Ok(Some(PullRequestSnapshot {
             head_sha: mr.sha,
             head_branch: mr.source_branch,
+            base_branch: mr.target_branch,
             head_clone_url: None,
             is_fork,
         }))
@@ -1038,6 +1300,31 @@ fn github_comment_url(base_url: &str, owner: &str, repo: &str, pr_number: u64) -
     )
 }
 
+/// Issue-comment API of a PR, shared by the PAT and App reporters, editing
+/// only comments of `author`.
+fn github_comment_api(
+    base_url: &str,
+    owner: &str,
+    repo: &str,
+    pr_number: u64,
+    author: CommentOwner,
+) -> CommentApi {
+    CommentApi {
+        url: github_comment_url(base_url, owner, repo, pr_number),
+        page_size_param: "per_page",
+        edit_prefix: format!("{}/repos/{}/{}/issues/comments/", base_url, owner, repo),
+        edit_method: reqwest::Method::PATCH,
+        owner: author,
+        forge: "github",
+    }
+}
+
+fn github_auth(req: reqwest::RequestBuilder, token: &str) -> reqwest::RequestBuilder {
+    req.header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+}
+
 fn github_reviews_url(base_url: &str, owner: &str, repo: &str, pr_number: u64) -> String {
     format!(
         "{}/repos/{}/{}/pulls/{}/reviews",
@@ -1181,7 +1468,11 @@ impl CiReporter for GithubReporter {
                 body = %body,
                 "GitHub CI status report failed"
             );
-            anyhow::bail!("GitHub returned {}: {}", status, body);
+            return Err(ForgeHttpError::new(
+                status,
+                format!("GitHub returned {}: {}", status, body),
+            )
+            .into());
         }
 
         Ok(None)
@@ -1251,6 +1542,20 @@ impl CiReporter for GithubReporter {
         Ok(())
     }
 
+    async fn upsert_pr_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        marker: &str,
+        body: &str,
+    ) -> Result<()> {
+        let author = CommentOwner::TokenUser(format!("{}/user", self.base_url));
+        let api = github_comment_api(&self.base_url, owner, repo, pr_number, author);
+        let auth = |req| github_auth(req, &self.token);
+        upsert_marked_comment(&self.client, auth, api, marker, body).await
+    }
+
     async fn approve_pull_request(
         &self,
         owner: &str,
@@ -1452,6 +1757,7 @@ fn github_pr_response_to_snapshot(pr: GithubPrResponse) -> PullRequestSnapshot {
     PullRequestSnapshot {
         head_sha: pr.head.sha,
         head_branch: pr.head.ref_,
+        base_branch: pr.base.map(|b| b.ref_),
         head_clone_url,
         is_fork,
     }
@@ -1651,7 +1957,11 @@ impl CiReporter for GithubAppReporter {
                     installation_id = self.installation_id,
                     "GitHub App check-run PATCH failed"
                 );
-                anyhow::bail!("GitHub App returned {}: {}", status, body);
+                return Err(ForgeHttpError::new(
+                    status,
+                    format!("GitHub App returned {}: {}", status, body),
+                )
+                .into());
             }
             Ok(None)
         } else {
@@ -1688,7 +1998,11 @@ impl CiReporter for GithubAppReporter {
                     installation_id = self.installation_id,
                     "GitHub App check-run POST failed"
                 );
-                anyhow::bail!("GitHub App returned {}: {}", status, body);
+                return Err(ForgeHttpError::new(
+                    status,
+                    format!("GitHub App returned {}: {}", status, body),
+                )
+                .into());
             }
             let parsed: CheckRunCreateResponse = resp
                 .json()
@@ -1781,6 +2095,21 @@ impl CiReporter for GithubAppReporter {
         Ok(())
     }
 
+    async fn upsert_pr_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        marker: &str,
+        body: &str,
+    ) -> Result<()> {
+        let token = self.installation_token().await?;
+        let author = CommentOwner::GithubApp(self.app_id as i64);
+        let api = github_comment_api(&self.api_base_url, owner, repo, pr_number, author);
+        let auth = |req| github_auth(req, &token);
+        upsert_marked_comment(&self.client, auth, api, marker, body).await
+    }
+
     async fn approve_pull_request(
         &self,
         owner: &str,
@@ -1923,103 +2252,747 @@ impl CiReporter for GithubAppReporter {
     }
 }
 
-// ── factory ──────────────────────────────────────────────────────────────────
+// ── BitbucketReporter ─────────────────────────────────────────────────────────
 
-/// Builds a `CiReporter` from a project's CI configuration fields.
+/// CI reporter that posts build statuses to Bitbucket Cloud.
 ///
-/// Returns `NoopCiReporter` when CI reporting is not configured or the
-/// reporter type is unrecognised.
-pub fn reporter_for_project(
-    http: reqwest::Client,
-    ci_type: Option<&str>,
-    ci_url: Option<&str>,
-    ci_token: Option<&str>,
-) -> Arc<dyn CiReporter> {
-    let Some(token) = ci_token else {
-        return Arc::new(NoopCiReporter);
-    };
+/// Uses the commit build status API:
+/// `POST {base_url}/repositories/{workspace}/{repo}/commit/{sha}/statuses/build`
+///
+/// `base_url` defaults to `https://api.bitbucket.org/2.0`. The token is a
+/// repository or workspace access token (bearer), or a `username:app_password`
+/// pair (basic auth).
+#[derive(Debug)]
+pub struct BitbucketReporter {
+    base_url: String,
+    token: String,
+    client: reqwest::Client,
+}
 
-    let forge = match ci_type.and_then(ForgeType::from_path_segment) {
-        Some(forge) => forge,
-        None => {
-            if let Some(unknown) = ci_type {
-                warn!(ci_type = %unknown, "Unknown CI reporter type, using noop");
-            }
-            return Arc::new(NoopCiReporter);
-        }
-    };
+impl BitbucketReporter {
+    const DEFAULT_API_URL: &'static str = "https://api.bitbucket.org/2.0";
 
-    let registry = ForgeRegistry::with_builtin();
-    let Some(provider) = registry.get(forge) else {
-        return Arc::new(NoopCiReporter);
-    };
+    pub fn new(
+        client: reqwest::Client,
+        base_url: impl Into<String>,
+        token: impl Into<String>,
+    ) -> Result<Self> {
+        let raw = base_url.into();
+        let base_url = if raw.is_empty() {
+            Self::DEFAULT_API_URL.to_string()
+        } else {
+            validate_safe_outbound_url(&raw)
+                .map_err(|e| anyhow::anyhow!("Rejected Bitbucket base_url: {}", e))?;
+            raw.trim_end_matches('/').to_string()
+        };
 
-    match provider.build_reporter(http, ci_url, Some(token)) {
-        Ok(reporter) => reporter,
-        Err(e) => {
-            warn!(error = %e, ?forge, "Failed to build reporter, falling back to noop");
-            Arc::new(NoopCiReporter)
-        }
+        Ok(Self {
+            base_url,
+            token: token.into(),
+            client,
+        })
     }
-}
 
-/// Parses `owner` and `repo` out of a repository URL.
-///
-/// Supports HTTPS (`https://host/owner/repo.git`) and SCP-style SSH
-/// (`git@host:owner/repo.git`).  Returns `None` if the URL cannot be parsed.
-pub fn parse_owner_repo(repository_url: &str) -> Option<(String, String)> {
-    // Normalise: strip git+ prefix added by RepositoryUrl
-    let url = repository_url
-        .strip_prefix("git+")
-        .unwrap_or(repository_url);
+    /// Web/git origin behind the API URL
+    /// (`https://api.bitbucket.org/2.0` → `https://bitbucket.org`).
+    fn git_base(&self) -> String {
+        let (scheme, rest) = self
+            .base_url
+            .split_once("://")
+            .unwrap_or(("https", self.base_url.as_str()));
+        let host = rest.split('/').next().unwrap_or(rest);
+        format!("{}://{}", scheme, host.strip_prefix("api.").unwrap_or(host))
+    }
+}
 
-    let path = if let Some(rest) = url
-        .strip_prefix("https://")
-        .or_else(|| url.strip_prefix("http://"))
-        .or_else(|| url.strip_prefix("git://"))
-    {
-        // https://host/owner/repo.git → "host/owner/repo.git" → take after first '/'
-        rest.split_once('/')?.1
-    } else {
-        // git@host:owner/repo.git
-        let colon_pos = url.find(':')?;
-        &url[colon_pos + 1..]
-    };
+/// Bitbucket build status states, shared by Cloud and Server.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+enum BitbucketState {
+    Inprogress,
+    Successful,
+    Failed,
+}
 
-    let path = path.trim_end_matches(".git");
-    let mut parts = path.splitn(2, '/');
-    let owner = parts.next()?.to_string();
-    let repo = parts.next()?.to_string();
-    Some((owner, repo))
+impl From<&CiStatus> for BitbucketState {
+    fn from(s: &CiStatus) -> Self {
+        match s {
+            CiStatus::Pending | CiStatus::Running | CiStatus::ActionRequired => {
+                BitbucketState::Inprogress
+            }
+            CiStatus::Success => BitbucketState::Successful,
+            CiStatus::Failure | CiStatus::Error => BitbucketState::Failed,
+        }
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Build status body; both Bitbucket flavours require `url`, so callers fall
+/// back to the repository page when the report has no details link.
+#[derive(Debug, Serialize)]
+struct BitbucketStatusPayload<'a> {
+    state: BitbucketState,
+    key: &'a str,
+    name: &'a str,
+    url: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+}
 
-    fn test_client() -> reqwest::Client {
-        gradient_util::http::build_client().expect("build test http client")
+impl<'a> BitbucketStatusPayload<'a> {
+    fn new(report: &'a CiReport, fallback_url: &'a str) -> Self {
+        Self {
+            state: BitbucketState::from(&report.status),
+            key: &report.context,
+            name: &report.context,
+            url: report.details_url.as_deref().unwrap_or(fallback_url),
+            description: report.description.as_deref(),
+        }
     }
+}
 
-    #[test]
-    fn author_or_bot_keeps_resolved_identity() {
-        let ident = CommitIdent {
-            name: "Bot".to_owned(),
-            email: "bot@example.com".to_owned(),
-        };
-        assert_eq!(
-            author_or_bot(Ok(ident.clone()), "https://codeberg.org"),
-            ident
+#[async_trait]
+impl CiReporter for BitbucketReporter {
+    async fn report(&self, report: &CiReport) -> Result<Option<i64>> {
+        let url = format!(
+            "{}/repositories/{}/{}/commit/{}/statuses/build",
+            self.base_url, report.owner, report.repo, report.sha
         );
+        let repo_page = format!("{}/{}/{}", self.git_base(), report.owner, report.repo);
+        let payload = BitbucketStatusPayload::new(report, &repo_page);
+
+        if let Err(e) = send_ok(
+            bitbucket::auth(self.client.post(&url), &self.token).json(&payload),
+            "bitbucket build status",
+        )
+        .await
+        {
+            warn!(bitbucket_url = %url, error = %e, "Bitbucket CI status report failed");
+            return Err(e);
+        }
+        Ok(None)
     }
 
-    #[test]
-    fn author_or_bot_falls_back_to_gradient_bot_on_error() {
-        assert_eq!(
-            author_or_bot(
-                Err(anyhow::anyhow!("403 read:user")),
-                "https://codeberg.org"
-            ),
+    async fn is_repo_writer(&self, owner: &str, repo: &str, username: &str) -> Result<bool> {
+        let query: String = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair(
+                "q",
+                &format!("user.nickname={}", bitbucket::bbql_string(username)),
+            )
+            .finish();
+        let url = format!(
+            "{}/workspaces/{}/permissions/repositories/{}?{}",
+            self.base_url, owner, repo, query
+        );
+        #[derive(Deserialize)]
+        struct Page {
+            #[serde(default)]
+            values: Vec<Permission>,
+        }
+        #[derive(Deserialize)]
+        struct Permission {
+            permission: String,
+        }
+        let page: Page = send_json(
+            bitbucket::auth(self.client.get(&url), &self.token),
+            "bitbucket repository permissions",
+        )
+        .await?;
+        Ok(page
+            .values
+            .iter()
+            .any(|p| matches!(p.permission.as_str(), "admin" | "write")))
+    }
+
+    async fn post_pr_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        body: &str,
+    ) -> Result<()> {
+        let url = format!(
+            "{}/repositories/{}/{}/pullrequests/{}/comments",
+            self.base_url, owner, repo, pr_number
+        );
+        let payload = serde_json::json!({ "content": { "raw": body } });
+        if let Err(e) = send_ok(
+            bitbucket::auth(self.client.post(&url), &self.token).json(&payload),
+            "bitbucket pull request comment",
+        )
+        .await
+        {
+            warn!(bitbucket_url = %url, error = %e, "Bitbucket PR comment post failed");
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    async fn get_pull_request(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> Result<Option<PullRequestSnapshot>> {
+        let url = format!(
+            "{}/repositories/{}/{}/pullrequests/{}",
+            self.base_url, owner, repo, pr_number
+        );
+        let resp = bitbucket::auth(self.client.get(&url), &self.token)
+            .send()
+            .await
+            .context("Failed to query Bitbucket pull request")?;
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            anyhow::bail!("Bitbucket pull request query returned {}: {}", status, body);
+        }
+        #[derive(Deserialize)]
+        struct PrResponse {
+            source: Endpoint,
+            destination: Endpoint,
+        }
+        #[derive(Deserialize)]
+        struct Endpoint {
+            branch: Named,
+            commit: Commit,
+            #[serde(default)]
+            repository: Option<Repo>,
+        }
+        #[derive(Deserialize)]
+        struct Named {
+            name: String,
+        }
+        #[derive(Deserialize)]
+        struct Commit {
+            hash: String,
+        }
+        #[derive(Deserialize)]
+        struct Repo {
+            full_name: String,
+        }
+        let pr: PrResponse = resp
+            .json()
+            .await
+            .context("Failed to parse Bitbucket pull request response")?;
+        let head_full = pr
+            .source
+            .repository
+            .map(|r| r.full_name)
+            .unwrap_or_else(|| format!("{}/{}", owner, repo));
+        let base_full = pr
+            .destination
+            .repository
+            .map(|r| r.full_name)
+            .unwrap_or_else(|| format!("{}/{}", owner, repo));
+        let is_fork = head_full != base_full;
+
+        // The pull request API abbreviates commit hashes; expand through the
+        // commit endpoint of the repo the head actually lives in.
+        let mut head_sha = pr.source.commit.hash;
+        if head_sha.len() < 40
+            && let Some(full) = self.expand_commit_hash(&head_full, &head_sha).await?
+        {
+            head_sha = full;
+        }
+
+        Ok(Some(PullRequestSnapshot {
+            head_sha,
+            head_branch: pr.source.branch.name,
+            base_branch: Some(pr.destination.branch.name),
+            head_clone_url: is_fork.then(|| format!("{}/{}.git", self.git_base(), head_full)),
+            is_fork,
+        }))
+    }
+
+    async fn expand_commit_hash(&self, full_name: &str, hash: &str) -> Result<Option<String>> {
+        #[derive(Deserialize)]
+        struct Commit {
+            hash: String,
+        }
+        let commit: Commit = send_json(
+            bitbucket::auth(
+                self.client.get(format!(
+                    "{}/repositories/{}/commit/{}",
+                    self.base_url, full_name, hash
+                )),
+                &self.token,
+            ),
+            "bitbucket expand commit hash",
+        )
+        .await?;
+        Ok(Some(commit.hash))
+    }
+
+    async fn upsert_branch(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+        base: &str,
+        commit: &BranchCommit,
+    ) -> Result<String> {
+        // Force-push like Gitea/GitLab: Bitbucket's `src` endpoint can only
+        // stack commits, never reset the branch onto the current base.
+        let git_base = self.git_base();
+        let mut commit = commit.clone();
+        if commit.author.is_none() {
+            let resolved = crate::pr::bitbucket::authenticated_user(
+                &self.client,
+                &self.base_url,
+                &self.token,
+                &git_base,
+            )
+            .await;
+            commit.author = Some(author_or_bot(resolved, &git_base));
+        }
+        let (user, pass) = bitbucket::git_credentials(&self.token);
+        crate::git_push::force_push_lock_commit(
+            format!("{}/{}/{}.git", git_base, owner, repo),
+            user,
+            pass,
+            branch.to_owned(),
+            base.to_owned(),
+            commit,
+        )
+        .await
+    }
+
+    async fn open_or_update_pr(
+        &self,
+        owner: &str,
+        repo: &str,
+        head: &str,
+        base: &str,
+        title: &str,
+        body: &str,
+    ) -> Result<PrRef> {
+        crate::pr::bitbucket::open_or_update_pr(
+            &self.client,
+            &self.base_url,
+            &self.token,
+            owner,
+            repo,
+            head,
+            base,
+            title,
+            body,
+        )
+        .await
+    }
+
+    async fn default_branch(&self, owner: &str, repo: &str) -> Result<String> {
+        crate::pr::bitbucket::default_branch(&self.client, &self.base_url, &self.token, owner, repo)
+            .await
+    }
+}
+
+// ── BitbucketServerReporter ───────────────────────────────────────────────────
+
+/// CI reporter that posts build statuses to Bitbucket Server / Data Center.
+///
+/// Uses the build status API:
+/// `POST {base_url}/rest/build-status/1.0/commits/{sha}`
+///
+/// Authenticates with an HTTP access token (bearer), or a `username:token`
+/// pair (basic auth) for instances that require the owning user.
+#[derive(Debug)]
+pub struct BitbucketServerReporter {
+    base_url: String,
+    token: String,
+    client: reqwest::Client,
+}
+
+impl BitbucketServerReporter {
+    pub fn new(
+        client: reqwest::Client,
+        base_url: impl Into<String>,
+        token: impl Into<String>,
+    ) -> Result<Self> {
+        let raw = base_url.into();
+        validate_safe_outbound_url(&raw)
+            .map_err(|e| anyhow::anyhow!("Rejected Bitbucket Server base_url: {}", e))?;
+        Ok(Self {
+            base_url: raw.trim_end_matches('/').to_string(),
+            token: token.into(),
+            client,
+        })
+    }
+
+    fn repo_api(&self, project: &str, slug: &str) -> String {
+        format!(
+            "{}/rest/api/1.0/projects/{}/repos/{}",
+            self.base_url, project, slug
+        )
+    }
+}
+
+/// `(PROJECT, slug)` of a Bitbucket Server repository from a parsed
+/// `owner/repo`. Clone URLs carry an `scm/` prefix (`/scm/proj/repo.git`
+/// parses as `scm` + `proj/repo`), so only the last two segments count;
+/// project keys are upper-case in the REST API.
+fn bitbucket_server_repo(owner: &str, repo: &str) -> (String, String) {
+    let path = format!("{}/{}", owner, repo);
+    let mut segments = path.rsplit('/').filter(|s| !s.is_empty());
+    let slug = segments.next().unwrap_or_default().to_string();
+    let project = segments.next().unwrap_or_default().to_ascii_uppercase();
+    (project, slug)
+}
+
+#[async_trait]
+impl CiReporter for BitbucketServerReporter {
+    async fn report(&self, report: &CiReport) -> Result<Option<i64>> {
+        let url = format!(
+            "{}/rest/build-status/1.0/commits/{}",
+            self.base_url, report.sha
+        );
+        let payload = BitbucketStatusPayload::new(report, &self.base_url);
+
+        if let Err(e) = send_ok(
+            bitbucket::auth(self.client.post(&url), &self.token).json(&payload),
+            "bitbucket server build status",
+        )
+        .await
+        {
+            warn!(bitbucket_url = %url, error = %e, "Bitbucket Server CI status report failed");
+            return Err(e);
+        }
+        Ok(None)
+    }
+
+    async fn is_repo_writer(&self, owner: &str, repo: &str, username: &str) -> Result<bool> {
+        let (project, slug) = bitbucket_server_repo(owner, repo);
+        let filter: String = url::form_urlencoded::byte_serialize(username.as_bytes()).collect();
+        #[derive(Deserialize)]
+        struct Page {
+            #[serde(default)]
+            values: Vec<Grant>,
+        }
+        #[derive(Deserialize)]
+        struct Grant {
+            user: GrantUser,
+            permission: String,
+        }
+        #[derive(Deserialize)]
+        struct GrantUser {
+            name: String,
+        }
+        // Write access is granted on the repository or inherited from its
+        // project; either level is enough.
+        for url in [
+            format!(
+                "{}/permissions/users?filter={}",
+                self.repo_api(&project, &slug),
+                filter
+            ),
+            format!(
+                "{}/rest/api/1.0/projects/{}/permissions/users?filter={}",
+                self.base_url, project, filter
+            ),
+        ] {
+            let page: Page = send_json(
+                bitbucket::auth(self.client.get(&url), &self.token),
+                "bitbucket server permissions",
+            )
+            .await?;
+            if page.values.iter().any(|g| {
+                g.user.name.eq_ignore_ascii_case(username)
+                    && matches!(
+                        g.permission.as_str(),
+                        "REPO_WRITE" | "REPO_ADMIN" | "PROJECT_WRITE" | "PROJECT_ADMIN"
+                    )
+            }) {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    async fn post_pr_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        body: &str,
+    ) -> Result<()> {
+        let (project, slug) = bitbucket_server_repo(owner, repo);
+        let url = format!(
+            "{}/pull-requests/{}/comments",
+            self.repo_api(&project, &slug),
+            pr_number
+        );
+        let payload = serde_json::json!({ "text": body });
+        if let Err(e) = send_ok(
+            bitbucket::auth(self.client.post(&url), &self.token).json(&payload),
+            "bitbucket server pull request comment",
+        )
+        .await
+        {
+            warn!(bitbucket_url = %url, error = %e, "Bitbucket Server PR comment post failed");
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    async fn get_pull_request(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> Result<Option<PullRequestSnapshot>> {
+        let (project, slug) = bitbucket_server_repo(owner, repo);
+        let url = format!(
+            "{}/pull-requests/{}",
+            self.repo_api(&project, &slug),
+            pr_number
+        );
+        let resp = bitbucket::auth(self.client.get(&url), &self.token)
+            .send()
+            .await
+            .context("Failed to query Bitbucket Server pull request")?;
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "Bitbucket Server pull request query returned {}: {}",
+                status,
+                body
+            );
+        }
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct PrResponse {
+            from_ref: PrRefJson,
+            to_ref: PrRefJson,
+        }
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct PrRefJson {
+            display_id: String,
+            latest_commit: String,
+            repository: Repo,
+        }
+        #[derive(Deserialize)]
+        struct Repo {
+            slug: String,
+            project: Project,
+            #[serde(default)]
+            links: Option<Links>,
+        }
+        #[derive(Deserialize)]
+        struct Project {
+            key: String,
+        }
+        #[derive(Deserialize)]
+        struct Links {
+            #[serde(default)]
+            clone: Vec<CloneLink>,
+        }
+        #[derive(Deserialize)]
+        struct CloneLink {
+            href: String,
+            #[serde(default)]
+            name: Option<String>,
+        }
+        let pr: PrResponse = resp
+            .json()
+            .await
+            .context("Failed to parse Bitbucket Server pull request response")?;
+        let from = &pr.from_ref.repository;
+        let to = &pr.to_ref.repository;
+        let is_fork = from.project.key != to.project.key || from.slug != to.slug;
+        let head_clone_url = if is_fork {
+            from.links
+                .as_ref()
+                .and_then(|l| l.clone.iter().find(|c| c.name.as_deref() == Some("http")))
+                .map(|c| c.href.clone())
+        } else {
+            None
+        };
+        Ok(Some(PullRequestSnapshot {
+            head_sha: pr.from_ref.latest_commit,
+            head_branch: pr.from_ref.display_id,
+            base_branch: Some(pr.to_ref.display_id),
+            head_clone_url,
+            is_fork,
+        }))
+    }
+
+    async fn upsert_branch(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+        base: &str,
+        commit: &BranchCommit,
+    ) -> Result<String> {
+        let (project, slug) = bitbucket_server_repo(owner, repo);
+        let mut commit = commit.clone();
+        if commit.author.is_none() {
+            let resolved = crate::pr::bitbucket_server::authenticated_user(
+                &self.client,
+                &self.base_url,
+                &self.token,
+            )
+            .await;
+            commit.author = Some(author_or_bot(resolved, &self.base_url));
+        }
+        let (user, pass) = bitbucket::git_credentials(&self.token);
+        crate::git_push::force_push_lock_commit(
+            format!(
+                "{}/scm/{}/{}.git",
+                self.base_url,
+                project.to_ascii_lowercase(),
+                slug
+            ),
+            user,
+            pass,
+            branch.to_owned(),
+            base.to_owned(),
+            commit,
+        )
+        .await
+    }
+
+    async fn open_or_update_pr(
+        &self,
+        owner: &str,
+        repo: &str,
+        head: &str,
+        base: &str,
+        title: &str,
+        body: &str,
+    ) -> Result<PrRef> {
+        let (project, slug) = bitbucket_server_repo(owner, repo);
+        crate::pr::bitbucket_server::open_or_update_pr(
+            &self.client,
+            &self.base_url,
+            &self.token,
+            &project,
+            &slug,
+            head,
+            base,
+            title,
+            body,
+        )
+        .await
+    }
+
+    async fn default_branch(&self, owner: &str, repo: &str) -> Result<String> {
+        let (project, slug) = bitbucket_server_repo(owner, repo);
+        crate::pr::bitbucket_server::default_branch(
+            &self.client,
+            &self.base_url,
+            &self.token,
+            &project,
+            &slug,
+        )
+        .await
+    }
+}
+
+// ── factory ──────────────────────────────────────────────────────────────────
+
+/// Builds a `CiReporter` from a project's CI configuration fields.
+///
+/// Returns `NoopCiReporter` when CI reporting is not configured or the
+/// reporter type is unrecognised.
+pub fn reporter_for_project(
+    http: reqwest::Client,
+    ci_type: Option<&str>,
+    ci_url: Option<&str>,
+    ci_token: Option<&str>,
+) -> Arc<dyn CiReporter> {
+    let Some(token) = ci_token else {
+        return Arc::new(NoopCiReporter);
+    };
+
+    let forge = match ci_type.and_then(ForgeType::from_path_segment) {
+        Some(forge) => forge,
+        None => {
+            if let Some(unknown) = ci_type {
+                warn!(ci_type = %unknown, "Unknown CI reporter type, using noop");
+            }
+            return Arc::new(NoopCiReporter);
+        }
+    };
+
+    let registry = ForgeRegistry::with_builtin();
+    let Some(provider) = registry.get(forge) else {
+        return Arc::new(NoopCiReporter);
+    };
+
+    match provider.build_reporter(http, ci_url, Some(token)) {
+        Ok(reporter) => reporter,
+        Err(e) => {
+            warn!(error = %e, ?forge, "Failed to build reporter, falling back to noop");
+            Arc::new(NoopCiReporter)
+        }
+    }
+}
+
+/// Parses `owner` and `repo` out of a repository URL.
+///
+/// Supports HTTPS (`https://host/owner/repo.git`) and SCP-style SSH
+/// (`git@host:owner/repo.git`).  Returns `None` if the URL cannot be parsed.
+pub fn parse_owner_repo(repository_url: &str) -> Option<(String, String)> {
+    // Normalise: strip git+ prefix added by RepositoryUrl
+    let url = repository_url
+        .strip_prefix("git+")
+        .unwrap_or(repository_url);
+
+    let path = if let Some(rest) = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .or_else(|| url.strip_prefix("git://"))
+    {
+        // https://host/owner/repo.git → "host/owner/repo.git" → take after first '/'
+        rest.split_once('/')?.1
+    } else {
+        // git@host:owner/repo.git
+        let colon_pos = url.find(':')?;
+        &url[colon_pos + 1..]
+    };
+
+    let path = path.trim_end_matches(".git");
+    let mut parts = path.splitn(2, '/');
+    let owner = parts.next()?.to_string();
+    let repo = parts.next()?.to_string();
+    Some((owner, repo))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_client() -> reqwest::Client {
+        gradient_util::http::build_client().expect("build test http client")
+    }
+
+    #[test]
+    fn author_or_bot_keeps_resolved_identity() {
+        let ident = CommitIdent {
+            name: "Bot".to_owned(),
+            email: "bot@example.com".to_owned(),
+        };
+        assert_eq!(
+            author_or_bot(Ok(ident.clone()), "https://codeberg.org"),
+            ident
+        );
+    }
+
+    #[test]
+    fn author_or_bot_falls_back_to_gradient_bot_on_error() {
+        assert_eq!(
+            author_or_bot(
+                Err(anyhow::anyhow!("403 read:user")),
+                "https://codeberg.org"
+            ),
             CommitIdent::gradient_bot("https://codeberg.org")
         );
     }
@@ -2160,8 +3133,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn bitbucket_state_serializes_uppercase() {
+        assert_eq!(
+            serde_json::to_string(&BitbucketState::from(&CiStatus::Running)).unwrap(),
+            "\"INPROGRESS\""
+        );
+        assert_eq!(
+            serde_json::to_string(&BitbucketState::from(&CiStatus::Success)).unwrap(),
+            "\"SUCCESSFUL\""
+        );
+        assert_eq!(
+            serde_json::to_string(&BitbucketState::from(&CiStatus::Error)).unwrap(),
+            "\"FAILED\""
+        );
+    }
+
     // ── Reporter constructors ────────────────────────────────────────────────
 
+    #[test]
+    fn bitbucket_reporter_defaults_to_cloud_api() {
+        let r = BitbucketReporter::new(test_client(), "", "tok").unwrap();
+        assert_eq!(r.base_url, "https://api.bitbucket.org/2.0");
+        assert_eq!(r.git_base(), "https://bitbucket.org");
+    }
+
+    #[test]
+    fn bitbucket_server_reporter_requires_safe_url() {
+        let r =
+            BitbucketServerReporter::new(test_client(), "https://bitbucket.example.com/", "tok")
+                .unwrap();
+        assert_eq!(r.base_url, "https://bitbucket.example.com");
+        assert!(BitbucketServerReporter::new(test_client(), "", "tok").is_err());
+    }
+
+    #[test]
+    fn bitbucket_server_repo_strips_scm_prefix() {
+        assert_eq!(
+            bitbucket_server_repo("scm", "proj/widgets"),
+            ("PROJ".to_owned(), "widgets".to_owned())
+        );
+        assert_eq!(
+            bitbucket_server_repo("PROJ", "widgets"),
+            ("PROJ".to_owned(), "widgets".to_owned())
+        );
+    }
+
     #[test]
     fn gitea_reporter_trims_trailing_slash() {
         let r = GiteaReporter::new(test_client(), "https://gitea.example.com/", "tok").unwrap();
@@ -2308,7 +3325,7 @@ mod tests {
 
     #[test]
     fn reporter_for_project_unknown_type_is_noop() {
-        let r = reporter_for_project(test_client(), Some("bitbucket"), None, Some("tok"));
+        let r = reporter_for_project(test_client(), Some("sourcehut"), None, Some("tok"));
         assert!(is_noop(&r));
     }
 
@@ -2340,6 +3357,19 @@ mod tests {
         assert!(format!("{:?}", r).contains("GithubReporter"));
     }
 
+    #[test]
+    fn reporter_for_project_bitbucket_builds_bitbucket() {
+        let r = reporter_for_project(test_client(), Some("bitbucket"), None, Some("tok"));
+        assert!(format!("{:?}", r).contains("BitbucketReporter"));
+        let r = reporter_for_project(
+            test_client(),
+            Some("bitbucket-server"),
+            Some("https://bitbucket.example.com"),
+            Some("tok"),
+        );
+        assert!(format!("{:?}", r).contains("BitbucketServerReporter"));
+    }
+
     // ── parse_owner_repo ─────────────────────────────────────────────────────
 
     #[test]
@@ -2418,6 +3448,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn github_comment_api_edits_issue_comments() {
+        let author = CommentOwner::GithubApp(7);
+        let api = github_comment_api("https://api.github.com", "octo", "demo", 42, author);
+        assert_eq!(
+            api.url,
+            "https://api.github.com/repos/octo/demo/issues/42/comments"
+        );
+        assert_eq!(
+            api.edit_prefix,
+            "https://api.github.com/repos/octo/demo/issues/comments/"
+        );
+        assert_eq!(api.edit_method, reqwest::Method::PATCH);
+        assert_eq!(api.owner, CommentOwner::GithubApp(7));
+    }
+
+    #[test]
+    fn marked_comment_is_ours_only_when_our_account_wrote_it() {
+        let comment = |v: serde_json::Value| serde_json::from_value::<ForgeComment>(v).unwrap();
+        let github_user = comment(serde_json::json!({
+            "id": 1, "body": "m", "user": { "id": 10, "login": "gradient-bot" },
+            "performed_via_github_app": null,
+        }));
+        let github_app = comment(serde_json::json!({
+            "id": 2, "body": "m", "user": { "id": 99, "login": "gradient[bot]" },
+            "performed_via_github_app": { "id": 7, "slug": "gradient" },
+        }));
+        let gitlab = comment(serde_json::json!({
+            "id": 3, "body": "m", "author": { "id": 10, "username": "gradient-bot" },
+        }));
+
+        assert!(github_user.written_by(OwnAccount::User(10)));
+        assert!(!github_user.written_by(OwnAccount::User(11)));
+        assert!(!github_user.written_by(OwnAccount::GithubApp(7)));
+        assert!(github_app.written_by(OwnAccount::GithubApp(7)));
+        assert!(!github_app.written_by(OwnAccount::GithubApp(8)));
+        assert!(gitlab.written_by(OwnAccount::User(10)));
+    }
+
     #[test]
     fn gitea_comment_url_targets_issues_endpoint() {
         let url = gitea_comment_url("https://gitea.example.com", "octo", "demo", 42);
@@ -19,9 +19,12 @@ pub enum WebhookEventKind {
     Release,
     Comment,
     /// A pull-request review submission (GitHub `pull_request_review`,
-    /// Gitea/Forgejo `pull_request_review`). Used to release an approval-gated
+    /// Gitea/Forgejo `pull_request_review`, Bitbucket approvals). Used to release an approval-gated
     /// run when a maintainer approves the PR natively (#369).
     Review,
+    /// A merge-queue entry (GitHub `merge_group`, GitLab merge-train
+    /// pipelines) asking for, or no longer needing, checks on its head commit.
+    MergeQueue,
     Unknown(String),
 }
 
@@ -47,7 +50,11 @@ pub struct WebhookCommitAuthor {
 
 /// First, trimmed line of a commit message - the subject shown in the frontend.
 fn commit_subject(commit: &WebhookCommit) -> Option<String> {
-    let subject = commit.message.as_deref()?.lines().next()?.trim();
+    subject_line(commit.message.as_deref()?)
+}
+
+fn subject_line(message: &str) -> Option<String> {
+    let subject = message.lines().next()?.trim();
     (!subject.is_empty()).then(|| subject.to_string())
 }
 
@@ -251,6 +258,8 @@ pub struct GitLabMergeRequestPayload {
 pub struct GitLabMRAttributes {
     pub action: String,
     pub source_branch: String,
+    #[serde(default)]
+    pub target_branch: Option<String>,
     pub last_commit: GitLabCommit,
     #[serde(default)]
     pub title: Option<String>,
@@ -342,6 +351,293 @@ pub struct GiteaReviewPr {
     pub number: Option<u64>,
 }
 
+// ── Bitbucket Cloud payloads ───────────────────────────────────────────────
+
+#[derive(Deserialize)]
+pub struct BitbucketRepository {
+    #[serde(default)]
+    pub full_name: Option<String>,
+    #[serde(default)]
+    pub links: Option<BitbucketRepoLinks>,
+}
+
+#[derive(Deserialize)]
+pub struct BitbucketRepoLinks {
+    #[serde(default)]
+    pub html: Option<BitbucketLink>,
+}
+
+#[derive(Deserialize)]
+pub struct BitbucketLink {
+    pub href: String,
+}
+
+#[derive(Deserialize)]
+pub struct BitbucketUser {
+    #[serde(default)]
+    pub nickname: Option<String>,
+    #[serde(default)]
+    pub display_name: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct BitbucketPushPayload {
+    pub push: BitbucketPush,
+    pub repository: BitbucketRepository,
+}
+
+#[derive(Deserialize)]
+pub struct BitbucketPush {
+    #[serde(default)]
+    pub changes: Vec<BitbucketPushChange>,
+}
+
+#[derive(Deserialize)]
+pub struct BitbucketPushChange {
+    /// `None` when the change deleted the ref.
+    #[serde(default)]
+    pub new: Option<BitbucketPushRef>,
+}
+
+#[derive(Deserialize)]
+pub struct BitbucketPushRef {
+    /// `branch` or `tag`.
+    #[serde(rename = "type")]
+    pub ref_type: String,
+    pub name: String,
+    pub target: BitbucketCommit,
+}
+
+#[derive(Deserialize)]
+pub struct BitbucketCommit {
+    pub hash: String,
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub author: Option<BitbucketCommitAuthor>,
+}
+
+#[derive(Deserialize)]
+pub struct BitbucketCommitAuthor {
+    /// `Name <email>` exactly as recorded in the commit.
+    #[serde(default)]
+    pub raw: Option<String>,
+    #[serde(default)]
+    pub user: Option<BitbucketUser>,
+}
+
+#[derive(Deserialize)]
+pub struct BitbucketPullRequestPayload {
+    pub pullrequest: BitbucketPullRequest,
+    pub repository: BitbucketRepository,
+    #[serde(default)]
+    pub actor: Option<BitbucketUser>,
+}
+
+#[derive(Deserialize)]
+pub struct BitbucketPullRequest {
+    #[serde(default)]
+    pub id: Option<u64>,
+    #[serde(default)]
+    pub title: Option<String>,
+    /// `OPEN`, `MERGED`, `DECLINED` or `SUPERSEDED`.
+    #[serde(default)]
+    pub state: Option<String>,
+    #[serde(default)]
+    pub author: Option<BitbucketUser>,
+    pub source: BitbucketPrEndpoint,
+    #[serde(default)]
+    pub destination: Option<BitbucketPrEndpoint>,
+}
+
+#[derive(Deserialize)]
+pub struct BitbucketPrEndpoint {
+    pub branch: BitbucketBranch,
+    pub commit: BitbucketCommit,
+    #[serde(default)]
+    pub repository: Option<BitbucketRepository>,
+}
+
+#[derive(Deserialize)]
+pub struct BitbucketBranch {
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+pub struct BitbucketApprovalPayload {
+    pub approval: BitbucketApproval,
+    pub pullrequest: BitbucketPrNumber,
+    pub repository: BitbucketRepository,
+}
+
+#[derive(Deserialize)]
+pub struct BitbucketApproval {
+    #[serde(default)]
+    pub user: Option<BitbucketUser>,
+}
+
+#[derive(Deserialize)]
+pub struct BitbucketPrNumber {
+    #[serde(default)]
+    pub id: Option<u64>,
+}
+
+// ── Bitbucket Server / Data Center payloads ────────────────────────────────
+
+#[derive(Deserialize)]
+pub struct BitbucketServerRepository {
+    pub slug: String,
+    pub project: BitbucketServerProject,
+    #[serde(default)]
+    pub links: Option<BitbucketServerLinks>,
+}
+
+#[derive(Deserialize)]
+pub struct BitbucketServerProject {
+    pub key: String,
+}
+
+#[derive(Deserialize)]
+pub struct BitbucketServerLinks {
+    #[serde(default)]
+    pub clone: Vec<BitbucketLink>,
+    #[serde(rename = "self", default)]
+    pub self_links: Vec<BitbucketLink>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BitbucketServerUser {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub display_name: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct BitbucketServerPushPayload {
+    pub repository: BitbucketServerRepository,
+    #[serde(default)]
+    pub changes: Vec<BitbucketServerRefChange>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BitbucketServerRefChange {
+    pub ref_id: String,
+    pub to_hash: String,
+    /// `ADD`, `UPDATE` or `DELETE`.
+    #[serde(rename = "type")]
+    pub change_type: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BitbucketServerPullRequestPayload {
+    #[serde(default)]
+    pub event_key: Option<String>,
+    pub pull_request: BitbucketServerPullRequest,
+    #[serde(default)]
+    pub actor: Option<BitbucketServerUser>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BitbucketServerPullRequest {
+    #[serde(default)]
+    pub id: Option<u64>,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub author: Option<BitbucketServerParticipant>,
+    pub from_ref: BitbucketServerPrRef,
+    pub to_ref: BitbucketServerPrRef,
+}
+
+#[derive(Deserialize)]
+pub struct BitbucketServerParticipant {
+    #[serde(default)]
+    pub user: Option<BitbucketServerUser>,
+    /// `APPROVED`, `NEEDS_WORK` or `UNAPPROVED`.
+    #[serde(default)]
+    pub status: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BitbucketServerPrRef {
+    pub display_id: String,
+    pub latest_commit: String,
+    pub repository: BitbucketServerRepository,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BitbucketServerReviewPayload {
+    pub pull_request: BitbucketServerPullRequest,
+    #[serde(default)]
+    pub participant: Option<BitbucketServerParticipant>,
+    #[serde(default)]
+    pub actor: Option<BitbucketServerUser>,
+}
+
+// ── GitHub merge_group payload ─────────────────────────────────────────────
+
+#[derive(Deserialize)]
+pub struct GitHubMergeGroupPayload {
+    /// `checks_requested` or `destroyed`.
+    pub action: String,
+    pub merge_group: GitHubMergeGroup,
+    pub repository: GitHubRepository,
+}
+
+#[derive(Deserialize)]
+pub struct GitHubMergeGroup {
+    pub head_sha: String,
+    /// `refs/heads/gh-readonly-queue/<base>/pr-<number>-<sha>`.
+    pub head_ref: String,
+    #[serde(default)]
+    pub base_ref: Option<String>,
+    #[serde(default)]
+    pub head_commit: Option<WebhookCommit>,
+}
+
+// ── GitLab pipeline payload (merge trains) ─────────────────────────────────
+
+#[derive(Deserialize)]
+pub struct GitLabPipelinePayload {
+    pub object_attributes: GitLabPipelineAttributes,
+    pub project: GitLabPipelineProject,
+    #[serde(default)]
+    pub merge_request: Option<GitLabPipelineMergeRequest>,
+    #[serde(default)]
+    pub commit: Option<WebhookCommit>,
+}
+
+#[derive(Deserialize)]
+pub struct GitLabPipelineAttributes {
+    /// `refs/merge-requests/<iid>/train` for a merge-train pipeline.
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+    pub sha: String,
+    pub status: String,
+}
+
+#[derive(Deserialize)]
+pub struct GitLabPipelineProject {
+    pub git_http_url: String,
+    #[serde(default)]
+    pub git_ssh_url: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct GitLabPipelineMergeRequest {
+    #[serde(default)]
+    pub iid: Option<u64>,
+    #[serde(default)]
+    pub target_branch: Option<String>,
+}
+
 // ── Normalised push event ──────────────────────────────────────────────────
 
 /// Forge-agnostic push event extracted from any of the supported webhook
@@ -367,6 +663,14 @@ pub enum PushOutcome {
     Ignored,
 }
 
+/// Head commit of a PR payload that only carries an abbreviated hash.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbbreviatedCommit {
+    /// `owner/repo` the commit lives in (the fork for fork PRs).
+    pub repo_full_name: String,
+    pub hash: String,
+}
+
 /// Pull-request event normalised across forges. `commit_hash` is the PR head SHA.
 pub struct ParsedPullRequestEvent {
     pub commit_hash: Vec<u8>,
@@ -375,6 +679,8 @@ pub struct ParsedPullRequestEvent {
     pub action: String,
     /// PR head branch name (without `refs/heads/` prefix), if available.
     pub branch: Option<String>,
+    /// Target branch the PR merges into, if available.
+    pub base_branch: Option<String>,
     /// PR / MR number as the forge knows it (GitHub `pull_request.number`,
     /// Gitea/Forgejo `pull_request.number`, GitLab `object_attributes.iid`).
     /// `None` when the payload omits it.
@@ -399,6 +705,10 @@ pub struct ParsedPullRequestEvent {
     /// PR / MR title. PR webhook payloads don't carry the head commit message,
     /// so this is used as the evaluation's display message for PR triggers.
     pub title: Option<String>,
+    /// Set when the payload names the head only by an abbreviated hash
+    /// (Bitbucket Cloud always does). `commit_hash` is empty until the
+    /// webhook handler expands it through the forge API.
+    pub abbreviated_head: Option<AbbreviatedCommit>,
 }
 
 /// Release/tag event. `commit_hash` is the SHA the tag points at.
@@ -424,6 +734,35 @@ pub struct ParsedPullRequestReviewEvent {
     pub repository_full_name: Option<String>,
 }
 
+/// Merge-queue entry, normalised across forges. `commit_hash` is the temporary
+/// merge commit the queue tests.
+pub struct ParsedMergeQueueEvent {
+    pub commit_hash: Vec<u8>,
+    pub repository_urls: Vec<String>,
+    /// Ref under test, persisted as the evaluation's branch:
+    /// `gh-readonly-queue/main/pr-42-<sha>` on GitHub,
+    /// `refs/merge-requests/42/train` on GitLab.
+    pub head_ref: String,
+    /// Branch the queue merges into, matched against the trigger's `branches`.
+    pub base_branch: Option<String>,
+    /// PR / MR the entry belongs to, when the forge exposes it.
+    pub pr_number: Option<u64>,
+    pub commit_message: Option<String>,
+    pub author_name: Option<String>,
+}
+
+/// Outcome of parsing a merge-queue webhook payload.
+pub enum MergeQueueOutcome {
+    /// The queue wants checks on the entry's head commit.
+    ChecksRequested(ParsedMergeQueueEvent),
+    /// The entry left the queue (merged, ejected or cancelled); its checks are
+    /// no longer needed.
+    Destroyed(ParsedMergeQueueEvent),
+    /// A well-formed delivery that concerns no queue entry, e.g. a GitLab
+    /// pipeline event for a regular branch pipeline.
+    Ignored,
+}
+
 // ── Helpers ────────────────────────────────────────────────────────────────
 
 /// Validated push commit info.
@@ -473,6 +812,11 @@ fn decode_sha_hex(s: &str, forge: &str, context: &str) -> Option<Vec<u8>> {
     }
 }
 
+/// A short hex commit hash as forges abbreviate them (7 to 39 characters).
+fn is_abbreviated_sha(s: &str) -> bool {
+    (7..40).contains(&s.len()) && s.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
 /// Normalise GitLab MR action strings to GitHub vocabulary.
 fn normalise_gitlab_mr_action(action: &str) -> String {
     match action {
@@ -502,6 +846,112 @@ fn gitlab_project_urls(project: &GitLabProject) -> Vec<String> {
     urls
 }
 
+/// GitHub-vocabulary PR action for a Bitbucket Cloud or Server event key.
+/// Bitbucket names the action in `X-Event-Key`; Cloud payloads don't repeat it.
+pub fn bitbucket_pr_action(event: &str) -> Option<&'static str> {
+    match event {
+        "pullrequest:created" | "pr:opened" => Some("opened"),
+        "pullrequest:updated" | "pr:from_ref_updated" => Some("synchronize"),
+        "pullrequest:fulfilled" | "pr:merged" => Some("merged"),
+        "pullrequest:rejected" | "pr:declined" => Some("closed"),
+        _ => None,
+    }
+}
+
+/// PR action implied by a Bitbucket Cloud `pullrequest.state`, used until the
+/// event key refines it (an `OPEN` PR may have been created or updated).
+fn bitbucket_state_action(state: Option<&str>) -> String {
+    match state {
+        Some("MERGED") => "merged",
+        Some("DECLINED") | Some("SUPERSEDED") => "closed",
+        _ => "opened",
+    }
+    .to_string()
+}
+
+/// HTTPS and SSH clone URLs of a Bitbucket Cloud repository. Cloud payloads
+/// carry no clone links, so both derive from `full_name` and the web link.
+fn bitbucket_repo_urls(repo: &BitbucketRepository) -> Vec<String> {
+    let Some(full_name) = repo.full_name.as_deref() else {
+        return Vec::new();
+    };
+    let html = repo
+        .links
+        .as_ref()
+        .and_then(|l| l.html.as_ref())
+        .map(|l| l.href.trim_end_matches('/').to_string())
+        .unwrap_or_else(|| format!("https://bitbucket.org/{full_name}"));
+    let host = html
+        .split_once("://")
+        .map_or(html.as_str(), |(_, rest)| rest)
+        .split('/')
+        .next()
+        .unwrap_or("bitbucket.org")
+        .to_string();
+    vec![format!("{html}.git"), format!("git@{host}:{full_name}.git")]
+}
+
+/// Clone URLs of a Bitbucket Server repository. Payloads without `clone` links
+/// fall back to the `scm/` URL derived from the browse (`self`) link.
+fn bitbucket_server_repo_urls(repo: &BitbucketServerRepository) -> Vec<String> {
+    let Some(links) = repo.links.as_ref() else {
+        return Vec::new();
+    };
+    if !links.clone.is_empty() {
+        return links.clone.iter().map(|l| l.href.clone()).collect();
+    }
+    links
+        .self_links
+        .first()
+        .and_then(|l| l.href.split_once("/projects/"))
+        .map(|(base, _)| {
+            vec![format!(
+                "{}/scm/{}/{}.git",
+                base,
+                repo.project.key.to_ascii_lowercase(),
+                repo.slug
+            )]
+        })
+        .unwrap_or_default()
+}
+
+/// PR number encoded in a GitHub merge-queue branch
+/// (`gh-readonly-queue/<base>/pr-<number>-<sha>`).
+fn merge_queue_pr_number(head_ref: &str) -> Option<u64> {
+    let entry = head_ref.rsplit('/').next()?.strip_prefix("pr-")?;
+    entry.split('-').next()?.parse().ok()
+}
+
+/// MR iid of a GitLab merge-train ref (`refs/merge-requests/<iid>/train`);
+/// `None` for any other ref.
+fn merge_train_iid(git_ref: &str) -> Option<u64> {
+    git_ref
+        .strip_prefix("refs/merge-requests/")?
+        .strip_suffix("/train")?
+        .parse()
+        .ok()
+}
+
+/// `PROJECT/slug` of a Bitbucket Server repository.
+fn bitbucket_server_full_name(repo: &BitbucketServerRepository) -> String {
+    format!("{}/{}", repo.project.key, repo.slug)
+}
+
+/// Commit author name from a Bitbucket Cloud commit: the linked account's
+/// display name, else the name part of the raw `Name <email>`.
+fn bitbucket_author_name(commit: &BitbucketCommit) -> Option<String> {
+    let author = commit.author.as_ref()?;
+    author
+        .user
+        .as_ref()
+        .and_then(|u| u.display_name.clone())
+        .or_else(|| {
+            let raw = author.raw.as_deref()?;
+            let name = raw.split('<').next().unwrap_or(raw).trim();
+            (!name.is_empty()).then(|| name.to_string())
+        })
+}
+
 // ── ParsedPushEvent impl ───────────────────────────────────────────────────
 
 impl ParsedPushEvent {
@@ -590,6 +1040,63 @@ impl ParsedPushEvent {
             is_tag: pc.is_tag,
         }))
     }
+
+    /// Bitbucket Cloud `repo:push`. Only the first change that still points
+    /// at a commit is built; a push deleting every ref it touches is ignored.
+    pub fn from_bitbucket(body: &[u8]) -> Option<PushOutcome> {
+        let payload: BitbucketPushPayload = match serde_json::from_slice(body) {
+            Ok(p) => p,
+            Err(e) => {
+                warn!(error = %e, "Failed to parse Bitbucket push payload");
+                return None;
+            }
+        };
+        let Some(new) = payload.push.changes.iter().find_map(|c| c.new.as_ref()) else {
+            return Some(PushOutcome::Ignored);
+        };
+        let git_ref = match new.ref_type.as_str() {
+            "tag" => format!("refs/tags/{}", new.name),
+            _ => format!("refs/heads/{}", new.name),
+        };
+        let Some(pc) = decode_push_commit(&git_ref, &new.target.hash, "bitbucket") else {
+            return Some(PushOutcome::Ignored);
+        };
+        Some(PushOutcome::Build(Self {
+            commit_hash: pc.hash,
+            repository_urls: bitbucket_repo_urls(&payload.repository),
+            commit_message: new.target.message.as_deref().and_then(subject_line),
+            author_name: bitbucket_author_name(&new.target),
+            ref_name: pc.ref_name,
+            is_tag: pc.is_tag,
+        }))
+    }
+
+    /// Bitbucket Server `repo:refs_changed`. The payload carries no commit
+    /// details, so message and author stay empty.
+    pub fn from_bitbucket_server(body: &[u8]) -> Option<PushOutcome> {
+        let payload: BitbucketServerPushPayload = match serde_json::from_slice(body) {
+            Ok(p) => p,
+            Err(e) => {
+                warn!(error = %e, "Failed to parse Bitbucket Server push payload");
+                return None;
+            }
+        };
+        let Some(change) = payload.changes.iter().find(|c| c.change_type != "DELETE") else {
+            return Some(PushOutcome::Ignored);
+        };
+        let Some(pc) = decode_push_commit(&change.ref_id, &change.to_hash, "bitbucket-server")
+        else {
+            return Some(PushOutcome::Ignored);
+        };
+        Some(PushOutcome::Build(Self {
+            commit_hash: pc.hash,
+            repository_urls: bitbucket_server_repo_urls(&payload.repository),
+            commit_message: None,
+            author_name: None,
+            ref_name: pc.ref_name,
+            is_tag: pc.is_tag,
+        }))
+    }
 }
 
 // ── ParsedPullRequestEvent impl ────────────────────────────────────────────
@@ -645,12 +1152,14 @@ impl ParsedPullRequestEvent {
             repository_urls,
             action: payload.action,
             branch: Some(payload.pull_request.head.branch),
+            base_branch: payload.pull_request.base.map(|b| b.branch),
             pr_number: payload.pull_request.number,
             pr_author,
             sender,
             is_fork,
             head_repo_clone_url,
             title: payload.pull_request.title,
+            abbreviated_head: None,
         })
     }
 
@@ -711,12 +1220,14 @@ impl ParsedPullRequestEvent {
             repository_urls,
             action: payload.action,
             branch,
+            base_branch: payload.pull_request.base.and_then(|b| b.branch.or(b.name)),
             pr_number: payload.pull_request.number,
             pr_author,
             sender,
             is_fork,
             head_repo_clone_url,
             title: payload.pull_request.title,
+            abbreviated_head: None,
         })
     }
 
@@ -760,12 +1271,130 @@ impl ParsedPullRequestEvent {
             repository_urls,
             action,
             branch: Some(payload.object_attributes.source_branch),
+            base_branch: payload.object_attributes.target_branch,
             pr_number: payload.object_attributes.iid,
             pr_author,
             sender,
             is_fork,
             head_repo_clone_url,
             title: payload.object_attributes.title,
+            abbreviated_head: None,
+        })
+    }
+
+    /// Bitbucket Cloud `pullrequest:*`. The body has no action field; it is
+    /// approximated from `state` here and refined from the event key by
+    /// [`bitbucket_pr_action`]. The payload abbreviates the head hash to 12
+    /// characters; it is returned as [`Self::abbreviated_head`] for the
+    /// webhook handler to expand, since evaluations need the full SHA.
+    pub fn from_bitbucket(body: &[u8]) -> Option<Self> {
+        let payload: BitbucketPullRequestPayload = match serde_json::from_slice(body) {
+            Ok(p) => p,
+            Err(e) => {
+                warn!(error = %e, "Failed to parse Bitbucket pullrequest payload");
+                return None;
+            }
+        };
+        let pr = payload.pullrequest;
+        let head_hash = pr.source.commit.hash.as_str();
+        let full_hash = head_hash.len() == 40;
+        let commit_hash = if full_hash {
+            decode_sha_hex(head_hash, "bitbucket", "pullrequest.source.commit.hash")?
+        } else if is_abbreviated_sha(head_hash) {
+            Vec::new()
+        } else {
+            warn!(sha = %head_hash, "Bitbucket pullrequest: invalid head commit hash");
+            return None;
+        };
+        let base_full = pr
+            .destination
+            .as_ref()
+            .and_then(|d| d.repository.as_ref())
+            .and_then(|r| r.full_name.clone())
+            .or_else(|| payload.repository.full_name.clone());
+        let head_repo = pr.source.repository.as_ref();
+        let head_full = head_repo.and_then(|r| r.full_name.clone());
+        let is_fork = match (head_full.as_deref(), base_full.as_deref()) {
+            (Some(h), Some(b)) => Some(h != b),
+            _ => None,
+        };
+        let mut repository_urls = Vec::with_capacity(4);
+        let mut head_repo_clone_url: Option<String> = None;
+        if let (Some(true), Some(repo)) = (is_fork, head_repo) {
+            let urls = bitbucket_repo_urls(repo);
+            head_repo_clone_url = urls.first().cloned();
+            repository_urls.extend(urls);
+        }
+        repository_urls.extend(bitbucket_repo_urls(&payload.repository));
+        let abbreviated_head = if full_hash {
+            None
+        } else {
+            Some(AbbreviatedCommit {
+                repo_full_name: head_full.or(base_full)?,
+                hash: head_hash.to_string(),
+            })
+        };
+        Some(Self {
+            commit_hash,
+            repository_urls,
+            action: bitbucket_state_action(pr.state.as_deref()),
+            branch: Some(pr.source.branch.name),
+            base_branch: pr.destination.map(|d| d.branch.name),
+            pr_number: pr.id,
+            pr_author: pr.author.and_then(|u| u.nickname),
+            sender: payload.actor.and_then(|u| u.nickname),
+            is_fork,
+            head_repo_clone_url,
+            title: pr.title,
+            abbreviated_head,
+        })
+    }
+
+    /// Bitbucket Server `pr:*`, normalised through the body's `eventKey`.
+    pub fn from_bitbucket_server(body: &[u8]) -> Option<Self> {
+        let payload: BitbucketServerPullRequestPayload = match serde_json::from_slice(body) {
+            Ok(p) => p,
+            Err(e) => {
+                warn!(error = %e, "Failed to parse Bitbucket Server pull request payload");
+                return None;
+            }
+        };
+        let pr = payload.pull_request;
+        let commit_hash = decode_sha_hex(
+            &pr.from_ref.latest_commit,
+            "bitbucket-server",
+            "pullRequest.fromRef.latestCommit",
+        )?;
+        let head_full = bitbucket_server_full_name(&pr.from_ref.repository);
+        let base_full = bitbucket_server_full_name(&pr.to_ref.repository);
+        let is_fork = head_full != base_full;
+        let mut repository_urls = Vec::with_capacity(4);
+        let mut head_repo_clone_url: Option<String> = None;
+        if is_fork {
+            let urls = bitbucket_server_repo_urls(&pr.from_ref.repository);
+            head_repo_clone_url = urls.iter().find(|u| u.starts_with("http")).cloned();
+            repository_urls.extend(urls);
+        }
+        repository_urls.extend(bitbucket_server_repo_urls(&pr.to_ref.repository));
+        let action = payload
+            .event_key
+            .as_deref()
+            .and_then(bitbucket_pr_action)
+            .unwrap_or("opened")
+            .to_string();
+        Some(Self {
+            commit_hash,
+            repository_urls,
+            action,
+            branch: Some(pr.from_ref.display_id),
+            base_branch: Some(pr.to_ref.display_id),
+            pr_number: pr.id,
+            pr_author: pr.author.and_then(|a| a.user).and_then(|u| u.name),
+            sender: payload.actor.and_then(|u| u.name),
+            is_fork: Some(is_fork),
+            head_repo_clone_url,
+            title: pr.title,
+            abbreviated_head: None,
         })
     }
 }
@@ -890,6 +1519,138 @@ impl ParsedPullRequestReviewEvent {
             repository_full_name: payload.repository.full_name,
         })
     }
+
+    /// Bitbucket Cloud `pullrequest:approved`; the event only fires for an
+    /// approval, so every parsed delivery is approving.
+    pub fn from_bitbucket(body: &[u8]) -> Option<Self> {
+        let payload: BitbucketApprovalPayload = match serde_json::from_slice(body) {
+            Ok(p) => p,
+            Err(e) => {
+                warn!(error = %e, "Failed to parse Bitbucket pullrequest:approved payload");
+                return None;
+            }
+        };
+        Some(Self {
+            approved: true,
+            reviewer: payload.approval.user.and_then(|u| u.nickname),
+            pr_number: payload.pullrequest.id,
+            repository_full_name: payload.repository.full_name,
+        })
+    }
+
+    /// Bitbucket Server `pr:reviewer:approved`.
+    pub fn from_bitbucket_server(body: &[u8]) -> Option<Self> {
+        let payload: BitbucketServerReviewPayload = match serde_json::from_slice(body) {
+            Ok(p) => p,
+            Err(e) => {
+                warn!(error = %e, "Failed to parse Bitbucket Server reviewer payload");
+                return None;
+            }
+        };
+        let participant = payload.participant;
+        let approved = participant
+            .as_ref()
+            .and_then(|p| p.status.as_deref())
+            .is_some_and(|s| s == "APPROVED");
+        let reviewer = participant
+            .and_then(|p| p.user)
+            .and_then(|u| u.name)
+            .or_else(|| payload.actor.and_then(|u| u.name));
+        Some(Self {
+            approved,
+            reviewer,
+            pr_number: payload.pull_request.id,
+            repository_full_name: Some(bitbucket_server_full_name(
+                &payload.pull_request.to_ref.repository,
+            )),
+        })
+    }
+}
+
+// ── ParsedMergeQueueEvent impl ─────────────────────────────────────────────
+
+impl ParsedMergeQueueEvent {
+    pub fn from_github(body: &[u8]) -> Option<MergeQueueOutcome> {
+        let payload: GitHubMergeGroupPayload = match serde_json::from_slice(body) {
+            Ok(p) => p,
+            Err(e) => {
+                warn!(error = %e, "Failed to parse GitHub merge_group payload");
+                return None;
+            }
+        };
+        let group = payload.merge_group;
+        let commit_hash = decode_sha_hex(&group.head_sha, "github", "merge_group.head_sha")?;
+        let head_ref = group
+            .head_ref
+            .strip_prefix("refs/heads/")
+            .unwrap_or(&group.head_ref)
+            .to_string();
+        let head = group.head_commit.as_ref();
+        let event = Self {
+            commit_hash,
+            repository_urls: vec![payload.repository.clone_url, payload.repository.ssh_url],
+            pr_number: merge_queue_pr_number(&head_ref),
+            base_branch: group
+                .base_ref
+                .as_deref()
+                .map(|r| r.strip_prefix("refs/heads/").unwrap_or(r).to_string()),
+            head_ref,
+            commit_message: head.and_then(commit_subject),
+            author_name: head
+                .and_then(|c| c.author.as_ref())
+                .and_then(|a| a.name.clone()),
+        };
+        Some(match payload.action.as_str() {
+            "checks_requested" => MergeQueueOutcome::ChecksRequested(event),
+            "destroyed" => MergeQueueOutcome::Destroyed(event),
+            _ => MergeQueueOutcome::Ignored,
+        })
+    }
+
+    /// GitLab `Pipeline Hook`. Only merge-train pipelines are queue entries:
+    /// `pending` requests checks, `canceled`/`skipped` drops the entry.
+    pub fn from_gitlab(body: &[u8]) -> Option<MergeQueueOutcome> {
+        let payload: GitLabPipelinePayload = match serde_json::from_slice(body) {
+            Ok(p) => p,
+            Err(e) => {
+                warn!(error = %e, "Failed to parse GitLab pipeline payload");
+                return None;
+            }
+        };
+        let attrs = payload.object_attributes;
+        let Some(iid) = merge_train_iid(&attrs.git_ref) else {
+            return Some(MergeQueueOutcome::Ignored);
+        };
+        let commit_hash = decode_sha_hex(&attrs.sha, "gitlab", "object_attributes.sha")?;
+        let mut repository_urls = vec![payload.project.git_http_url];
+        if let Some(ssh) = payload.project.git_ssh_url {
+            repository_urls.push(ssh);
+        }
+        let commit = payload.commit.as_ref();
+        let event = Self {
+            commit_hash,
+            repository_urls,
+            head_ref: attrs.git_ref.clone(),
+            base_branch: payload
+                .merge_request
+                .as_ref()
+                .and_then(|mr| mr.target_branch.clone()),
+            pr_number: payload
+                .merge_request
+                .as_ref()
+                .and_then(|mr| mr.iid)
+                .or(Some(iid)),
+            commit_message: commit.and_then(commit_subject),
+            author_name: commit
+                .and_then(|c| c.author.as_ref())
+                .and_then(|a| a.name.clone()),
+        };
+        Some(match attrs.status.as_str() {
+            "pending" => MergeQueueOutcome::ChecksRequested(event),
+            "canceled" | "skipped" => MergeQueueOutcome::Destroyed(event),
+            _ => MergeQueueOutcome::Ignored,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -963,6 +1724,7 @@ mod tests {
         let ev = ParsedPullRequestEvent::from_github(body.as_bytes()).unwrap();
         assert_eq!(ev.action, "opened");
         assert_eq!(ev.branch, Some("feature-x".to_string()));
+        assert_eq!(ev.base_branch, None);
         assert_eq!(ev.commit_hash, hex::decode(VALID_SHA).unwrap());
         assert_eq!(ev.repository_urls.len(), 2);
         // PR title becomes the evaluation's display message (PR payloads carry no
@@ -1000,6 +1762,7 @@ mod tests {
         assert_eq!(ev.pr_number, Some(42));
         assert_eq!(ev.pr_author.as_deref(), Some("external-contrib"));
         assert_eq!(ev.is_fork, Some(true));
+        assert_eq!(ev.base_branch.as_deref(), Some("main"));
     }
 
     #[test]
@@ -1192,6 +1955,7 @@ mod tests {
                 "object_attributes": {{
                     "action": "open",
                     "source_branch": "feature-z",
+                    "target_branch": "main",
                     "last_commit": {{ "id": "{VALID_SHA}" }}
                 }},
                 "project": {{
@@ -1203,6 +1967,7 @@ mod tests {
         let ev = ParsedPullRequestEvent::from_gitlab(body.as_bytes()).unwrap();
         assert_eq!(ev.action, "opened");
         assert_eq!(ev.branch, Some("feature-z".to_string()));
+        assert_eq!(ev.base_branch.as_deref(), Some("main"));
         assert_eq!(ev.commit_hash, hex::decode(VALID_SHA).unwrap());
     }
 
@@ -1500,4 +2265,418 @@ mod tests {
             Some(PushOutcome::Ignored)
         ));
     }
+
+    // ── Bitbucket Cloud ───────────────────────────────────────────────────
+
+    #[test]
+    fn bitbucket_push_builds_branch_with_derived_clone_urls() {
+        let body = format!(
+            r#"{{
+                "push": {{ "changes": [{{
+                    "new": {{
+                        "type": "branch",
+                        "name": "main",
+                        "target": {{
+                            "hash": "{VALID_SHA}",
+                            "message": "Fix the widget\n\nLonger body",
+                            "author": {{ "raw": "Jane Doe <jane@example.com>" }}
+                        }}
+                    }}
+                }}] }},
+                "repository": {{
+                    "full_name": "acme/widgets",
+                    "links": {{ "html": {{ "href": "https://bitbucket.org/acme/widgets" }} }}
+                }}
+            }}"#
+        );
+        let ev = build(ParsedPushEvent::from_bitbucket(body.as_bytes()));
+        assert_eq!(ev.commit_hash, hex::decode(VALID_SHA).unwrap());
+        assert_eq!(ev.ref_name, "main");
+        assert!(!ev.is_tag);
+        assert_eq!(ev.commit_message.as_deref(), Some("Fix the widget"));
+        assert_eq!(ev.author_name.as_deref(), Some("Jane Doe"));
+        assert_eq!(
+            ev.repository_urls,
+            vec![
+                "https://bitbucket.org/acme/widgets.git".to_string(),
+                "git@bitbucket.org:acme/widgets.git".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn bitbucket_push_tag_and_deletion() {
+        let tag = format!(
+            r#"{{
+                "push": {{ "changes": [{{ "new": {{ "type": "tag", "name": "v1.0.0", "target": {{ "hash": "{VALID_SHA}" }} }} }}] }},
+                "repository": {{ "full_name": "acme/widgets" }}
+            }}"#
+        );
+        let ev = build(ParsedPushEvent::from_bitbucket(tag.as_bytes()));
+        assert_eq!(ev.ref_name, "v1.0.0");
+        assert!(ev.is_tag);
+
+        let deleted = r#"{
+            "push": { "changes": [{ "new": null }] },
+            "repository": { "full_name": "acme/widgets" }
+        }"#;
+        assert!(matches!(
+            ParsedPushEvent::from_bitbucket(deleted.as_bytes()),
+            Some(PushOutcome::Ignored)
+        ));
+    }
+
+    #[test]
+    fn bitbucket_pr_from_fork() {
+        let body = format!(
+            r#"{{
+                "pullrequest": {{
+                    "id": 12,
+                    "title": "Add the widget",
+                    "state": "OPEN",
+                    "author": {{ "nickname": "contrib" }},
+                    "source": {{
+                        "branch": {{ "name": "feature-x" }},
+                        "commit": {{ "hash": "{VALID_SHA}" }},
+                        "repository": {{ "full_name": "contrib/widgets" }}
+                    }},
+                    "destination": {{
+                        "branch": {{ "name": "main" }},
+                        "commit": {{ "hash": "{VALID_SHA}" }},
+                        "repository": {{ "full_name": "acme/widgets" }}
+                    }}
+                }},
+                "repository": {{ "full_name": "acme/widgets" }},
+                "actor": {{ "nickname": "maintainer" }}
+            }}"#
+        );
+        let ev = ParsedPullRequestEvent::from_bitbucket(body.as_bytes()).unwrap();
+        assert_eq!(ev.action, "opened");
+        assert_eq!(ev.branch.as_deref(), Some("feature-x"));
+        assert_eq!(ev.pr_number, Some(12));
+        assert_eq!(ev.pr_author.as_deref(), Some("contrib"));
+        assert_eq!(ev.sender.as_deref(), Some("maintainer"));
+        assert_eq!(ev.is_fork, Some(true));
+        assert_eq!(
+            ev.head_repo_clone_url.as_deref(),
+            Some("https://bitbucket.org/contrib/widgets.git")
+        );
+        assert!(
+            ev.repository_urls
+                .contains(&"https://bitbucket.org/acme/widgets.git".to_string())
+        );
+    }
+
+    /// Bitbucket Cloud `pullrequest:created` as delivered: the head and
+    /// merge-base hashes are abbreviated to 12 characters.
+    const BITBUCKET_PR_CREATED: &str = r#"{
+        "pullrequest": {
+            "type": "pullrequest",
+            "id": 7,
+            "title": "Bump widget",
+            "state": "OPEN",
+            "author": { "type": "user", "nickname": "contrib", "display_name": "Contributor" },
+            "source": {
+                "branch": { "name": "bump-widget" },
+                "commit": {
+                    "type": "commit",
+                    "hash": "1a2b3c4d5e6f",
+                    "links": { "self": { "href": "https://api.bitbucket.org/2.0/repositories/contrib/widgets/commit/1a2b3c4d5e6f" } }
+                },
+                "repository": { "type": "repository", "full_name": "contrib/widgets", "name": "widgets" }
+            },
+            "destination": {
+                "branch": { "name": "main" },
+                "commit": { "type": "commit", "hash": "0f9e8d7c6b5a" },
+                "repository": { "type": "repository", "full_name": "acme/widgets", "name": "widgets" }
+            },
+            "merge_commit": null,
+            "close_source_branch": false
+        },
+        "repository": { "type": "repository", "full_name": "acme/widgets", "name": "widgets" },
+        "actor": { "type": "user", "nickname": "contrib" }
+    }"#;
+
+    #[test]
+    fn bitbucket_pr_abbreviated_hash_is_left_for_expansion() {
+        let ev = ParsedPullRequestEvent::from_bitbucket(BITBUCKET_PR_CREATED.as_bytes()).unwrap();
+        assert!(ev.commit_hash.is_empty());
+        assert_eq!(
+            ev.abbreviated_head,
+            Some(AbbreviatedCommit {
+                repo_full_name: "contrib/widgets".into(),
+                hash: "1a2b3c4d5e6f".into(),
+            })
+        );
+        assert_eq!(ev.pr_number, Some(7));
+        assert_eq!(ev.is_fork, Some(true));
+
+        let garbage = BITBUCKET_PR_CREATED.replace("1a2b3c4d5e6f\"", "not-a-hash\"");
+        assert!(ParsedPullRequestEvent::from_bitbucket(garbage.as_bytes()).is_none());
+    }
+
+    #[test]
+    fn bitbucket_pr_action_maps_cloud_and_server_keys() {
+        assert_eq!(bitbucket_pr_action("pullrequest:created"), Some("opened"));
+        assert_eq!(
+            bitbucket_pr_action("pullrequest:updated"),
+            Some("synchronize")
+        );
+        assert_eq!(bitbucket_pr_action("pullrequest:fulfilled"), Some("merged"));
+        assert_eq!(bitbucket_pr_action("pullrequest:rejected"), Some("closed"));
+        assert_eq!(bitbucket_pr_action("pr:opened"), Some("opened"));
+        assert_eq!(
+            bitbucket_pr_action("pr:from_ref_updated"),
+            Some("synchronize")
+        );
+        assert_eq!(bitbucket_pr_action("pr:merged"), Some("merged"));
+        assert_eq!(bitbucket_pr_action("pr:declined"), Some("closed"));
+        assert_eq!(bitbucket_pr_action("repo:push"), None);
+    }
+
+    #[test]
+    fn bitbucket_approval_is_approving() {
+        let body = r#"{
+            "approval": { "user": { "nickname": "maintainer" } },
+            "pullrequest": { "id": 12 },
+            "repository": { "full_name": "acme/widgets" }
+        }"#;
+        let ev = ParsedPullRequestReviewEvent::from_bitbucket(body.as_bytes()).unwrap();
+        assert!(ev.approved);
+        assert_eq!(ev.reviewer.as_deref(), Some("maintainer"));
+        assert_eq!(ev.pr_number, Some(12));
+        assert_eq!(ev.repository_full_name.as_deref(), Some("acme/widgets"));
+    }
+
+    // ── Bitbucket Server ──────────────────────────────────────────────────
+
+    fn server_repo(key: &str, slug: &str) -> String {
+        format!(
+            r#"{{
+                "slug": "{slug}",
+                "project": {{ "key": "{key}" }},
+                "links": {{ "clone": [
+                    {{ "href": "https://bitbucket.example.com/scm/{lower}/{slug}.git", "name": "http" }},
+                    {{ "href": "ssh://git@bitbucket.example.com:7999/{lower}/{slug}.git", "name": "ssh" }}
+                ] }}
+            }}"#,
+            lower = key.to_ascii_lowercase()
+        )
+    }
+
+    #[test]
+    fn bitbucket_server_push_skips_deleted_refs() {
+        let body = format!(
+            r#"{{
+                "eventKey": "repo:refs_changed",
+                "repository": {repo},
+                "changes": [
+                    {{ "refId": "refs/heads/old", "toHash": "{ZERO_SHA}", "type": "DELETE" }},
+                    {{ "refId": "refs/tags/v2", "toHash": "{VALID_SHA}", "type": "ADD" }}
+                ]
+            }}"#,
+            repo = server_repo("ACME", "widgets")
+        );
+        let ev = build(ParsedPushEvent::from_bitbucket_server(body.as_bytes()));
+        assert_eq!(ev.ref_name, "v2");
+        assert!(ev.is_tag);
+        assert_eq!(
+            ev.repository_urls[0],
+            "https://bitbucket.example.com/scm/acme/widgets.git"
+        );
+    }
+
+    #[test]
+    fn bitbucket_server_push_derives_clone_url_from_browse_link() {
+        let body = format!(
+            r#"{{
+                "repository": {{
+                    "slug": "widgets",
+                    "project": {{ "key": "ACME" }},
+                    "links": {{ "self": [{{ "href": "https://bitbucket.example.com/projects/ACME/repos/widgets/browse" }}] }}
+                }},
+                "changes": [{{ "refId": "refs/heads/main", "toHash": "{VALID_SHA}", "type": "UPDATE" }}]
+            }}"#
+        );
+        let ev = build(ParsedPushEvent::from_bitbucket_server(body.as_bytes()));
+        assert_eq!(
+            ev.repository_urls,
+            vec!["https://bitbucket.example.com/scm/acme/widgets.git".to_string()]
+        );
+    }
+
+    #[test]
+    fn bitbucket_server_pr_uses_event_key_action() {
+        let body = format!(
+            r#"{{
+                "eventKey": "pr:from_ref_updated",
+                "actor": {{ "name": "jdoe" }},
+                "pullRequest": {{
+                    "id": 3,
+                    "title": "Bump inputs",
+                    "author": {{ "user": {{ "name": "jdoe" }} }},
+                    "fromRef": {{ "displayId": "bump", "latestCommit": "{VALID_SHA}", "repository": {repo} }},
+                    "toRef": {{ "displayId": "main", "latestCommit": "{VALID_SHA}", "repository": {repo} }}
+                }}
+            }}"#,
+            repo = server_repo("ACME", "widgets")
+        );
+        let ev = ParsedPullRequestEvent::from_bitbucket_server(body.as_bytes()).unwrap();
+        assert_eq!(ev.action, "synchronize");
+        assert_eq!(ev.branch.as_deref(), Some("bump"));
+        assert_eq!(ev.base_branch.as_deref(), Some("main"));
+        assert_eq!(ev.pr_number, Some(3));
+        assert_eq!(ev.pr_author.as_deref(), Some("jdoe"));
+        assert_eq!(ev.is_fork, Some(false));
+        assert!(ev.head_repo_clone_url.is_none());
+        assert_eq!(ev.title.as_deref(), Some("Bump inputs"));
+    }
+
+    #[test]
+    fn bitbucket_server_review_requires_approved_status() {
+        let body = |status: &str| {
+            format!(
+                r#"{{
+                    "pullRequest": {{
+                        "id": 3,
+                        "fromRef": {{ "displayId": "bump", "latestCommit": "{VALID_SHA}", "repository": {repo} }},
+                        "toRef": {{ "displayId": "main", "latestCommit": "{VALID_SHA}", "repository": {repo} }}
+                    }},
+                    "participant": {{ "user": {{ "name": "lead" }}, "status": "{status}" }}
+                }}"#,
+                repo = server_repo("ACME", "widgets")
+            )
+        };
+        let ev = ParsedPullRequestReviewEvent::from_bitbucket_server(body("APPROVED").as_bytes())
+            .unwrap();
+        assert!(ev.approved);
+        assert_eq!(ev.reviewer.as_deref(), Some("lead"));
+        assert_eq!(ev.repository_full_name.as_deref(), Some("ACME/widgets"));
+
+        let ev = ParsedPullRequestReviewEvent::from_bitbucket_server(body("NEEDS_WORK").as_bytes())
+            .unwrap();
+        assert!(!ev.approved);
+    }
+
+    // ── Merge queues ──────────────────────────────────────────────────────
+
+    fn github_merge_group(action: &str) -> String {
+        format!(
+            r#"{{
+                "action": "{action}",
+                "merge_group": {{
+                    "head_sha": "{VALID_SHA}",
+                    "head_ref": "refs/heads/gh-readonly-queue/main/pr-42-{VALID_SHA}",
+                    "base_ref": "refs/heads/main",
+                    "head_commit": {{
+                        "id": "{VALID_SHA}",
+                        "message": "Merge pull request #42\n\nbody",
+                        "author": {{ "name": "Octo Cat" }}
+                    }}
+                }},
+                "repository": {{
+                    "clone_url": "https://github.com/org/repo.git",
+                    "ssh_url": "git@github.com:org/repo.git"
+                }}
+            }}"#
+        )
+    }
+
+    #[test]
+    fn github_merge_group_checks_requested() {
+        let body = github_merge_group("checks_requested");
+        let Some(MergeQueueOutcome::ChecksRequested(ev)) =
+            ParsedMergeQueueEvent::from_github(body.as_bytes())
+        else {
+            panic!("expected checks_requested");
+        };
+        assert_eq!(ev.commit_hash, hex::decode(VALID_SHA).unwrap());
+        assert_eq!(
+            ev.head_ref,
+            format!("gh-readonly-queue/main/pr-42-{VALID_SHA}")
+        );
+        assert_eq!(ev.base_branch.as_deref(), Some("main"));
+        assert_eq!(ev.pr_number, Some(42));
+        assert_eq!(ev.commit_message.as_deref(), Some("Merge pull request #42"));
+        assert_eq!(ev.author_name.as_deref(), Some("Octo Cat"));
+        assert_eq!(ev.repository_urls.len(), 2);
+    }
+
+    #[test]
+    fn github_merge_group_destroyed() {
+        let body = github_merge_group("destroyed");
+        assert!(matches!(
+            ParsedMergeQueueEvent::from_github(body.as_bytes()),
+            Some(MergeQueueOutcome::Destroyed(_))
+        ));
+    }
+
+    fn gitlab_pipeline(git_ref: &str, status: &str) -> String {
+        format!(
+            r#"{{
+                "object_kind": "pipeline",
+                "object_attributes": {{ "ref": "{git_ref}", "sha": "{VALID_SHA}", "status": "{status}" }},
+                "merge_request": {{ "iid": 7, "target_branch": "main" }},
+                "project": {{
+                    "git_http_url": "https://gitlab.com/group/proj.git",
+                    "git_ssh_url": "git@gitlab.com:group/proj.git"
+                }},
+                "commit": {{ "id": "{VALID_SHA}", "message": "Merge branch 'feature'", "author": {{ "name": "GL User" }} }}
+            }}"#
+        )
+    }
+
+    #[test]
+    fn gitlab_merge_train_pipeline_pending_requests_checks() {
+        let body = gitlab_pipeline("refs/merge-requests/7/train", "pending");
+        let Some(MergeQueueOutcome::ChecksRequested(ev)) =
+            ParsedMergeQueueEvent::from_gitlab(body.as_bytes())
+        else {
+            panic!("expected checks requested");
+        };
+        assert_eq!(ev.head_ref, "refs/merge-requests/7/train");
+        assert_eq!(ev.base_branch.as_deref(), Some("main"));
+        assert_eq!(ev.pr_number, Some(7));
+        assert_eq!(
+            ev.repository_urls,
+            vec![
+                "https://gitlab.com/group/proj.git".to_string(),
+                "git@gitlab.com:group/proj.git".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn gitlab_merge_train_pipeline_canceled_is_destroyed() {
+        let body = gitlab_pipeline("refs/merge-requests/7/train", "canceled");
+        assert!(matches!(
+            ParsedMergeQueueEvent::from_gitlab(body.as_bytes()),
+            Some(MergeQueueOutcome::Destroyed(_))
+        ));
+        let body = gitlab_pipeline("refs/merge-requests/7/train", "running");
+        assert!(matches!(
+            ParsedMergeQueueEvent::from_gitlab(body.as_bytes()),
+            Some(MergeQueueOutcome::Ignored)
+        ));
+    }
+
+    #[test]
+    fn gitlab_branch_pipeline_is_not_a_queue_entry() {
+        for git_ref in ["main", "refs/merge-requests/7/head"] {
+            let body = gitlab_pipeline(git_ref, "pending");
+            assert!(matches!(
+                ParsedMergeQueueEvent::from_gitlab(body.as_bytes()),
+                Some(MergeQueueOutcome::Ignored)
+            ));
+        }
+    }
+
+    #[test]
+    fn merge_queue_pr_number_parses_queue_branch() {
+        assert_eq!(
+            merge_queue_pr_number("gh-readonly-queue/release/v2/pr-118-abcdef"),
+            Some(118)
+        );
+        assert_eq!(merge_queue_pr_number("feature/pr-review"), None);
+    }
 }
@@ -33,6 +33,25 @@ mod m20260705_000000_upstream_metric_by_url;
 mod m20260706_000000_build_attempt_build_job_set_null;
 mod m20260706_000001_disable_jit;
 mod m20260709_000000_input_update_discover_only;
+mod m20260710_000000_action_delivery_retry;
+mod m20260712_000000_evaluation_branch;
+mod m20260712_000001_user_notification_subscription;
+mod m20260713_000000_project_action_digest_entry;
+mod m20260714_000000_organization_quotas;
+mod m20260715_000000_worker_labels;
+mod m20260716_000000_worker_maintenance;
+mod m20260717_000000_build_flakiness;
+mod m20260718_000000_build_failure_cause;
+mod m20260719_000000_project_build_limits;
+mod m20260720_000000_aggregate_entry_points;
+mod m20260721_000000_cache_eviction;
+mod m20260722_000000_cache_pins;
+mod m20260723_000000_nar_chunks;
+mod m20260724_000000_realisations;
+mod m20260725_000000_cache_nar_compression;
+mod m20260726_000000_aggregate_unresolved_constituents;
+mod m20260727_000000_realisation_organization;
+mod m20260728_000000_entry_point_pending_constituents;
 
 pub struct Migrator;
 
@@ -67,6 +86,25 @@ impl MigratorTrait for Migrator {
             Box::new(m20260706_000000_build_attempt_build_job_set_null::Migration),
             Box::new(m20260706_000001_disable_jit::Migration),
             Box::new(m20260709_000000_input_update_discover_only::Migration),
+            Box::new(m20260710_000000_action_delivery_retry::Migration),
+            Box::new(m20260712_000000_evaluation_branch::Migration),
+            Box::new(m20260712_000001_user_notification_subscription::Migration),
+            Box::new(m20260713_000000_project_action_digest_entry::Migration),
+            Box::new(m20260714_000000_organization_quotas::Migration),
+            Box::new(m20260715_000000_worker_labels::Migration),
+            Box::new(m20260716_000000_worker_maintenance::Migration),
+            Box::new(m20260717_000000_build_flakiness::Migration),
+            Box::new(m20260718_000000_build_failure_cause::Migration),
+            Box::new(m20260719_000000_project_build_limits::Migration),
+            Box::new(m20260720_000000_aggregate_entry_points::Migration),
+            Box::new(m20260721_000000_cache_eviction::Migration),
+            Box::new(m20260722_000000_cache_pins::Migration),
+            Box::new(m20260723_000000_nar_chunks::Migration),
+            Box::new(m20260724_000000_realisations::Migration),
+            Box::new(m20260725_000000_cache_nar_compression::Migration),
+            Box::new(m20260726_000000_aggregate_unresolved_constituents::Migration),
+            Box::new(m20260727_000000_realisation_organization::Migration),
+            Box::new(m20260728_000000_entry_point_pending_constituents::Migration),
         ]
     }
 }
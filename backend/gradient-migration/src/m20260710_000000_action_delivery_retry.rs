@@ -0,0 +1,68 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Retry bookkeeping on `project_action_delivery`: attempt count, delivery
+//! state (delivered / failed / retrying / dead), the next scheduled retry, and
+//! the full event payload so a retry or manual redelivery can replay it.
+//! Existing rows are backfilled as `delivered` or `failed` from `success`.
+
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            "ALTER TABLE project_action_delivery
+               ADD COLUMN IF NOT EXISTS attempt integer NOT NULL DEFAULT 1,
+               ADD COLUMN IF NOT EXISTS state smallint NOT NULL DEFAULT 0,
+               ADD COLUMN IF NOT EXISTS next_retry_at timestamp without time zone,
+               ADD COLUMN IF NOT EXISTS payload jsonb",
+        )
+        .await?;
+
+        db.execute_unprepared("UPDATE project_action_delivery SET state = 1 WHERE NOT success")
+            .await?;
+
+        db.execute_unprepared(
+            "CREATE INDEX IF NOT EXISTS idx_project_action_delivery_retry_due
+               ON project_action_delivery (next_retry_at)
+               WHERE state = 2",
+        )
+        .await?;
+
+        db.execute_unprepared(
+            "CREATE INDEX IF NOT EXISTS idx_project_action_delivery_dead
+               ON project_action_delivery (action_id, delivered_at DESC)
+               WHERE state = 3",
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared("DROP INDEX IF EXISTS idx_project_action_delivery_dead")
+            .await?;
+        db.execute_unprepared("DROP INDEX IF EXISTS idx_project_action_delivery_retry_due")
+            .await?;
+        db.execute_unprepared(
+            "ALTER TABLE project_action_delivery
+               DROP COLUMN IF EXISTS payload,
+               DROP COLUMN IF EXISTS next_retry_at,
+               DROP COLUMN IF EXISTS state,
+               DROP COLUMN IF EXISTS attempt",
+        )
+        .await?;
+
+        Ok(())
+    }
+}
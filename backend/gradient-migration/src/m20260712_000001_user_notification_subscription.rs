@@ -0,0 +1,62 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Per-user notification subscriptions: a user subscribes themselves to
+//! evaluation/build events of one project (or every project they are a member
+//! of), optionally narrowed to a branch glob and a commit/PR author, and is
+//! emailed when a matching event fires.
+
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r#"
+            CREATE TABLE IF NOT EXISTS user_notification_subscription (
+                id UUID PRIMARY KEY,
+                "user" UUID NOT NULL REFERENCES "user" (id) ON DELETE CASCADE,
+                project UUID REFERENCES project (id) ON DELETE CASCADE,
+                events JSONB NOT NULL DEFAULT '[]'::jsonb,
+                branch TEXT,
+                author TEXT,
+                active BOOLEAN NOT NULL DEFAULT TRUE,
+                created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL,
+                updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL
+            )
+            "#,
+        )
+        .await?;
+
+        db.execute_unprepared(
+            r#"CREATE INDEX IF NOT EXISTS "idx-user_notification_subscription-user"
+               ON user_notification_subscription ("user")"#,
+        )
+        .await?;
+
+        db.execute_unprepared(
+            r#"CREATE INDEX IF NOT EXISTS "idx-user_notification_subscription-project"
+               ON user_notification_subscription (project)
+               WHERE active"#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS user_notification_subscription")
+            .await?;
+        Ok(())
+    }
+}
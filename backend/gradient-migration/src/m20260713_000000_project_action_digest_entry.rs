@@ -0,0 +1,58 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Buffered events of `send_mail` actions in digest mode. Each row is one
+//! matched event waiting for its action's window to close (`flush_at`); the
+//! digest loop drains all rows of an action at once and mails them as a single
+//! summary.
+
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r#"
+            CREATE TABLE IF NOT EXISTS project_action_digest_entry (
+                id UUID PRIMARY KEY,
+                action_id UUID NOT NULL REFERENCES project_action (id) ON DELETE CASCADE,
+                event TEXT NOT NULL,
+                payload JSONB NOT NULL,
+                flush_at TIMESTAMP WITHOUT TIME ZONE NOT NULL,
+                created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL
+            )
+            "#,
+        )
+        .await?;
+
+        db.execute_unprepared(
+            r#"CREATE INDEX IF NOT EXISTS "idx-project_action_digest_entry-action"
+               ON project_action_digest_entry (action_id, flush_at)"#,
+        )
+        .await?;
+
+        db.execute_unprepared(
+            r#"CREATE INDEX IF NOT EXISTS "idx-project_action_digest_entry-flush_at"
+               ON project_action_digest_entry (flush_at)"#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS project_action_digest_entry")
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Hard per-organization quotas: `max_concurrent_builds`,
+//! `max_concurrent_evaluations` and a rolling `build_minutes_budget`. All
+//! NULL (unlimited) for existing organizations.
+
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE organization \
+                   ADD COLUMN IF NOT EXISTS max_concurrent_builds INTEGER, \
+                   ADD COLUMN IF NOT EXISTS max_concurrent_evaluations INTEGER, \
+                   ADD COLUMN IF NOT EXISTS build_minutes_budget INTEGER",
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE organization \
+                   DROP COLUMN IF EXISTS max_concurrent_builds, \
+                   DROP COLUMN IF EXISTS max_concurrent_evaluations, \
+                   DROP COLUMN IF EXISTS build_minutes_budget",
+            )
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Worker labels and selectors: `worker_registration.labels` plus
+//! `project.worker_selector` and `project_trigger.worker_selector`. All NULL
+//! (no labels, any worker) for existing rows.
+
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+        conn.execute_unprepared(
+            "ALTER TABLE worker_registration ADD COLUMN IF NOT EXISTS labels JSONB",
+        )
+        .await?;
+        conn.execute_unprepared(
+            "ALTER TABLE project ADD COLUMN IF NOT EXISTS worker_selector JSONB",
+        )
+        .await?;
+        conn.execute_unprepared(
+            "ALTER TABLE project_trigger ADD COLUMN IF NOT EXISTS worker_selector JSONB",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+        conn.execute_unprepared(
+            "ALTER TABLE project_trigger DROP COLUMN IF EXISTS worker_selector",
+        )
+        .await?;
+        conn.execute_unprepared("ALTER TABLE project DROP COLUMN IF EXISTS worker_selector")
+            .await?;
+        conn.execute_unprepared("ALTER TABLE worker_registration DROP COLUMN IF EXISTS labels")
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Per-worker cordon and maintenance windows on `worker_registration`.
+//! Existing rows stay uncordoned with no windows.
+
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+        conn.execute_unprepared(
+            "ALTER TABLE worker_registration ADD COLUMN IF NOT EXISTS cordoned BOOLEAN NOT NULL DEFAULT FALSE",
+        )
+        .await?;
+        conn.execute_unprepared(
+            "ALTER TABLE worker_registration ADD COLUMN IF NOT EXISTS maintenance_windows JSONB",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+        conn.execute_unprepared(
+            "ALTER TABLE worker_registration DROP COLUMN IF EXISTS maintenance_windows",
+        )
+        .await?;
+        conn.execute_unprepared("ALTER TABLE worker_registration DROP COLUMN IF EXISTS cordoned")
+            .await?;
+        Ok(())
+    }
+}
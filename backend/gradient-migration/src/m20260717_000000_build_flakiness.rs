@@ -0,0 +1,68 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Flaky-build tracking. `build_flakiness` aggregates the pass/fail history of
+//! one derivation name within a project across evaluations; `build_attempt`
+//! gains `flaky` (this failure was judged flaky and re-run) and `flaky_rerun`
+//! (this attempt is that re-run). Existing attempts are neither.
+
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r#"
+            CREATE TABLE IF NOT EXISTS build_flakiness (
+                id UUID PRIMARY KEY,
+                project UUID NOT NULL REFERENCES project (id) ON DELETE CASCADE,
+                name TEXT NOT NULL,
+                runs BIGINT NOT NULL DEFAULT 0,
+                failures BIGINT NOT NULL DEFAULT 0,
+                flips BIGINT NOT NULL DEFAULT 0,
+                reruns BIGINT NOT NULL DEFAULT 0,
+                rerun_passes BIGINT NOT NULL DEFAULT 0,
+                last_passed BOOLEAN,
+                updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL
+            )
+            "#,
+        )
+        .await?;
+
+        db.execute_unprepared(
+            r#"CREATE UNIQUE INDEX IF NOT EXISTS "idx-build_flakiness-project-name"
+               ON build_flakiness (project, name)"#,
+        )
+        .await?;
+
+        db.execute_unprepared(
+            "ALTER TABLE build_attempt \
+               ADD COLUMN IF NOT EXISTS flaky BOOLEAN NOT NULL DEFAULT FALSE, \
+               ADD COLUMN IF NOT EXISTS flaky_rerun BOOLEAN NOT NULL DEFAULT FALSE",
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            "ALTER TABLE build_attempt \
+               DROP COLUMN IF EXISTS flaky_rerun, \
+               DROP COLUMN IF EXISTS flaky",
+        )
+        .await?;
+        db.execute_unprepared("DROP TABLE IF EXISTS build_flakiness")
+            .await?;
+        Ok(())
+    }
+}
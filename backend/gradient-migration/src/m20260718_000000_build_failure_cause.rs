@@ -0,0 +1,37 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Structured failure analysis. `build_attempt.failure_cause` holds the
+//! `gradient_types::FailureCause` the log matchers recognised for a failed
+//! attempt (JSON, `kind`-tagged). Attempts failed before this, and failures no
+//! matcher recognises, keep it `NULL`.
+
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE build_attempt ADD COLUMN IF NOT EXISTS failure_cause JSONB",
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE build_attempt DROP COLUMN IF EXISTS failure_cause")
+            .await?;
+        Ok(())
+    }
+}
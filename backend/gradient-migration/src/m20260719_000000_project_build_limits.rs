@@ -0,0 +1,45 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Per-project build limits: `project.build_timeout_secs`,
+//! `project.build_max_silent_secs` and `project.max_output_size`. NULL keeps
+//! the server default (no output size cap) for existing projects; `0` means
+//! no limit.
+
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE project \
+                 ADD COLUMN IF NOT EXISTS build_timeout_secs BIGINT, \
+                 ADD COLUMN IF NOT EXISTS build_max_silent_secs BIGINT, \
+                 ADD COLUMN IF NOT EXISTS max_output_size BIGINT",
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE project \
+                 DROP COLUMN IF EXISTS max_output_size, \
+                 DROP COLUMN IF EXISTS build_max_silent_secs, \
+                 DROP COLUMN IF EXISTS build_timeout_secs",
+            )
+            .await?;
+        Ok(())
+    }
+}
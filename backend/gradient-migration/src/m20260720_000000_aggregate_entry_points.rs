@@ -0,0 +1,61 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Hydra-style aggregate jobs. `entry_point.aggregate` marks an entry point
+//! whose derivation sets `_hydraAggregate`; `entry_point_constituent` links it
+//! to the derivations of its `constituents`. Existing entry points are not
+//! aggregates.
+
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            "ALTER TABLE entry_point ADD COLUMN IF NOT EXISTS aggregate BOOLEAN NOT NULL DEFAULT FALSE",
+        )
+        .await?;
+
+        db.execute_unprepared(
+            r#"
+            CREATE TABLE IF NOT EXISTS entry_point_constituent (
+                id UUID PRIMARY KEY,
+                entry_point UUID NOT NULL REFERENCES entry_point (id) ON DELETE CASCADE,
+                derivation UUID NOT NULL REFERENCES derivation (id) ON DELETE CASCADE
+            )
+            "#,
+        )
+        .await?;
+
+        db.execute_unprepared(
+            r#"CREATE UNIQUE INDEX IF NOT EXISTS "idx-entry_point_constituent-entry_point-derivation"
+               ON entry_point_constituent (entry_point, derivation)"#,
+        )
+        .await?;
+
+        db.execute_unprepared(
+            r#"CREATE INDEX IF NOT EXISTS "idx-entry_point_constituent-derivation"
+               ON entry_point_constituent (derivation)"#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared("DROP TABLE IF EXISTS entry_point_constituent")
+            .await?;
+        db.execute_unprepared("ALTER TABLE entry_point DROP COLUMN IF EXISTS aggregate")
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Cache eviction: `cache.eviction_policy` (`0` disabled, `1` LRU, `2`
+//! oldest-first; eviction is opt-in, so every cache defaults to disabled) and
+//! `cached_path.last_accessed_at`, stamped on every served narinfo/NAR.
+
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            "ALTER TABLE cache \
+             ADD COLUMN IF NOT EXISTS eviction_policy SMALLINT NOT NULL DEFAULT 0",
+        )
+        .await?;
+        db.execute_unprepared(
+            "ALTER TABLE cached_path \
+             ADD COLUMN IF NOT EXISTS last_accessed_at TIMESTAMP",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared("ALTER TABLE cached_path DROP COLUMN IF EXISTS last_accessed_at")
+            .await?;
+        db.execute_unprepared("ALTER TABLE cache DROP COLUMN IF EXISTS eviction_policy")
+            .await?;
+        Ok(())
+    }
+}
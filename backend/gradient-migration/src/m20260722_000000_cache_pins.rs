@@ -0,0 +1,69 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Named cache pins: GC roots on a store path or a whole evaluation, with an
+//! optional expiry. A pin names exactly one of the two targets.
+
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r#"
+            CREATE TABLE IF NOT EXISTS cache_pin (
+                id UUID PRIMARY KEY,
+                cache UUID NOT NULL REFERENCES cache (id) ON DELETE CASCADE,
+                name TEXT NOT NULL,
+                hash TEXT,
+                package TEXT,
+                evaluation UUID REFERENCES evaluation (id) ON DELETE CASCADE,
+                managed BOOLEAN NOT NULL DEFAULT FALSE,
+                created_by UUID REFERENCES "user" (id) ON DELETE SET NULL,
+                created_at TIMESTAMP NOT NULL,
+                expires_at TIMESTAMP,
+                CONSTRAINT "chk-cache_pin-target" CHECK (
+                    (hash IS NOT NULL AND package IS NOT NULL AND evaluation IS NULL)
+                    OR (hash IS NULL AND package IS NULL AND evaluation IS NOT NULL)
+                )
+            )
+            "#,
+        )
+        .await?;
+
+        db.execute_unprepared(
+            r#"CREATE UNIQUE INDEX IF NOT EXISTS "idx-cache_pin-cache-name"
+               ON cache_pin (cache, name)"#,
+        )
+        .await?;
+
+        db.execute_unprepared(
+            r#"CREATE INDEX IF NOT EXISTS "idx-cache_pin-hash"
+               ON cache_pin (hash) WHERE hash IS NOT NULL"#,
+        )
+        .await?;
+
+        db.execute_unprepared(
+            r#"CREATE INDEX IF NOT EXISTS "idx-cache_pin-evaluation"
+               ON cache_pin (evaluation) WHERE evaluation IS NOT NULL"#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared("DROP TABLE IF EXISTS cache_pin")
+            .await?;
+        Ok(())
+    }
+}
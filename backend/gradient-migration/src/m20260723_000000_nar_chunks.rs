@@ -0,0 +1,86 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Chunked NAR layout: `nar_chunk` holds every stored chunk with a reference
+//! count, `nar_chunked` every NAR converted from the flat layout and
+//! `nar_chunk_ref` its ordered chunk list.
+
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r#"
+            CREATE TABLE IF NOT EXISTS nar_chunk (
+                hash TEXT PRIMARY KEY,
+                size BIGINT NOT NULL,
+                raw_size BIGINT NOT NULL,
+                refcount BIGINT NOT NULL,
+                created_at TIMESTAMP NOT NULL
+            )
+            "#,
+        )
+        .await?;
+
+        db.execute_unprepared(
+            r#"
+            CREATE TABLE IF NOT EXISTS nar_chunked (
+                hash TEXT PRIMARY KEY,
+                file_hash TEXT NOT NULL,
+                file_size BIGINT NOT NULL,
+                flat_file_hash TEXT NOT NULL,
+                flat_file_size BIGINT NOT NULL,
+                chunk_count INTEGER NOT NULL,
+                created_at TIMESTAMP NOT NULL
+            )
+            "#,
+        )
+        .await?;
+
+        db.execute_unprepared(
+            r#"CREATE INDEX IF NOT EXISTS "idx-nar_chunked-flat_file_hash"
+               ON nar_chunked (flat_file_hash)"#,
+        )
+        .await?;
+
+        db.execute_unprepared(
+            r#"
+            CREATE TABLE IF NOT EXISTS nar_chunk_ref (
+                nar TEXT NOT NULL REFERENCES nar_chunked (hash) ON DELETE CASCADE,
+                idx INTEGER NOT NULL,
+                chunk TEXT NOT NULL REFERENCES nar_chunk (hash),
+                PRIMARY KEY (nar, idx)
+            )
+            "#,
+        )
+        .await?;
+
+        db.execute_unprepared(
+            r#"CREATE INDEX IF NOT EXISTS "idx-nar_chunk_ref-chunk"
+               ON nar_chunk_ref (chunk)"#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared("DROP TABLE IF EXISTS nar_chunk_ref")
+            .await?;
+        db.execute_unprepared("DROP TABLE IF EXISTS nar_chunked")
+            .await?;
+        db.execute_unprepared("DROP TABLE IF EXISTS nar_chunk")
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,70 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Realisations of floating content-addressed derivations. `realisation` maps
+//! a derivation's hash modulo and output name to the store path a build
+//! produced; `derivation.realisation_hash` and `derivation.floating_outputs`
+//! record which outputs of a derivation are looked up that way. Existing
+//! derivations have no floating outputs recorded.
+
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            "ALTER TABLE derivation ADD COLUMN IF NOT EXISTS realisation_hash TEXT",
+        )
+        .await?;
+        db.execute_unprepared(
+            "ALTER TABLE derivation ADD COLUMN IF NOT EXISTS floating_outputs TEXT[] NOT NULL DEFAULT '{}'",
+        )
+        .await?;
+
+        db.execute_unprepared(
+            r#"
+            CREATE TABLE IF NOT EXISTS realisation (
+                id UUID PRIMARY KEY,
+                drv_hash TEXT NOT NULL,
+                output_name TEXT NOT NULL,
+                hash TEXT NOT NULL,
+                package TEXT NOT NULL,
+                created_at TIMESTAMP NOT NULL
+            )
+            "#,
+        )
+        .await?;
+
+        db.execute_unprepared(
+            r#"CREATE UNIQUE INDEX IF NOT EXISTS "idx-realisation-drv_hash-output_name"
+               ON realisation (drv_hash, output_name)"#,
+        )
+        .await?;
+
+        db.execute_unprepared(
+            r#"CREATE INDEX IF NOT EXISTS "idx-realisation-hash" ON realisation (hash)"#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared("DROP TABLE IF EXISTS realisation")
+            .await?;
+        db.execute_unprepared("ALTER TABLE derivation DROP COLUMN IF EXISTS floating_outputs")
+            .await?;
+        db.execute_unprepared("ALTER TABLE derivation DROP COLUMN IF EXISTS realisation_hash")
+            .await?;
+        Ok(())
+    }
+}
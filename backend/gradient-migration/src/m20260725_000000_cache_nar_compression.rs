@@ -0,0 +1,36 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! `cache.nar_compression`: the compression a cache serves NARs in (`0` zstd
+//! as stored, `1` xz, `2` none; existing caches keep zstd).
+
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE cache \
+                 ADD COLUMN IF NOT EXISTS nar_compression SMALLINT NOT NULL DEFAULT 0",
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE cache DROP COLUMN IF EXISTS nar_compression")
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! `entry_point.unresolved_constituents` counts the constituents of an
+//! aggregate that could not be linked to a derivation. A non-zero count fails
+//! the aggregate's combined status, so a constituent that never evaluated
+//! cannot leave the release gate green.
+
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE entry_point ADD COLUMN IF NOT EXISTS unresolved_constituents INTEGER NOT NULL DEFAULT 0",
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE entry_point DROP COLUMN IF EXISTS unresolved_constituents",
+            )
+            .await?;
+        Ok(())
+    }
+}
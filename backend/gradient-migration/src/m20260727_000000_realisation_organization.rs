@@ -0,0 +1,67 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Scope `realisation` to the organization whose build produced it. A
+//! realisation is only served from caches that organization writes to and only
+//! fed into that organization's evaluations, so the key is now
+//! `(organization, drv_hash, output_name)`. Rows recorded without an owner are
+//! dropped; the next build of each output records it again.
+
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r#"ALTER TABLE realisation ADD COLUMN IF NOT EXISTS organization UUID
+               REFERENCES organization (id) ON DELETE CASCADE"#,
+        )
+        .await?;
+        db.execute_unprepared("DELETE FROM realisation WHERE organization IS NULL")
+            .await?;
+        db.execute_unprepared("ALTER TABLE realisation ALTER COLUMN organization SET NOT NULL")
+            .await?;
+
+        db.execute_unprepared(r#"DROP INDEX IF EXISTS "idx-realisation-drv_hash-output_name""#)
+            .await?;
+        db.execute_unprepared(
+            r#"CREATE UNIQUE INDEX IF NOT EXISTS "idx-realisation-organization-drv_hash-output_name"
+               ON realisation (organization, drv_hash, output_name)"#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r#"DROP INDEX IF EXISTS "idx-realisation-organization-drv_hash-output_name""#,
+        )
+        .await?;
+        // Several organizations may have realised the same output; keep the
+        // first so the global key holds again.
+        db.execute_unprepared(
+            r#"DELETE FROM realisation r USING realisation o
+               WHERE r.drv_hash = o.drv_hash AND r.output_name = o.output_name
+                 AND r.id > o.id"#,
+        )
+        .await?;
+        db.execute_unprepared(
+            r#"CREATE UNIQUE INDEX IF NOT EXISTS "idx-realisation-drv_hash-output_name"
+               ON realisation (drv_hash, output_name)"#,
+        )
+        .await?;
+        db.execute_unprepared("ALTER TABLE realisation DROP COLUMN IF EXISTS organization")
+            .await?;
+        Ok(())
+    }
+}
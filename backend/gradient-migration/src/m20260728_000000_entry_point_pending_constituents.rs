@@ -0,0 +1,38 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! `entry_point.pending_constituents` holds the constituent drv paths an
+//! aggregate was evaluated with until they are linked at stream completion, so
+//! a scheduler restart mid-evaluation does not lose them.
+
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE entry_point ADD COLUMN IF NOT EXISTS pending_constituents TEXT[] NOT NULL DEFAULT '{}'",
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE entry_point DROP COLUMN IF EXISTS pending_constituents",
+            )
+            .await?;
+        Ok(())
+    }
+}
@@ -14,8 +14,18 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use gradient_db::Derivation;
-/// Result of resolving one flake attribute path: `(attr_path, Result<(drv_path, references)>)`.
-pub type ResolvedDerivation = (String, Result<(String, Vec<String>)>);
+/// Result of resolving one flake attribute path:
+/// `(attr_path, Result<(drv_path, references, meta_limits)>)`.
+pub type ResolvedDerivation = (String, Result<(String, Vec<String>, MetaLimits)>);
+
+/// Build limits an attribute declares in its `meta` (`meta.timeout`,
+/// `meta.maxSilent`, in seconds). They override the derivation's own
+/// `timeout`/`maxSilent` attributes and every project or server default.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MetaLimits {
+    pub timeout_secs: Option<u64>,
+    pub max_silent_secs: Option<u64>,
+}
 
 /// Outcome of discovering a flake's derivation attr paths: the matched paths,
 /// nix warnings surfaced during the walk, and errors for attributes that threw
@@ -43,7 +53,7 @@ pub trait DerivationResolver: Send + Sync + std::fmt::Debug + 'static {
         overrides: &[(String, String)],
     ) -> Result<FlakeDiscovery>;
 
-    /// Resolve a batch of attribute paths into `(drv_path, references)` tuples.
+    /// Resolve a batch of attribute paths into `(drv_path, references, meta_limits)` tuples.
     /// The result preserves the input order of `attrs`. `overrides` are applied
     /// at lock time so resolved drvPaths reflect them.
     /// Returns `(resolved, warnings)`.
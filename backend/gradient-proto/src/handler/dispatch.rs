@@ -12,6 +12,7 @@ use std::sync::Arc;
 use gradient_core::ServerState;
 use gradient_exec::strip_nix_store_prefix;
 use gradient_types::ids::{DerivationId, OrganizationId};
+use gradient_types::worker_labels::validate_label;
 use gradient_types::*;
 use tokio::sync::Semaphore;
 use tracing::{debug, error, info, warn};
@@ -85,6 +86,7 @@ impl<'a> DispatchContext<'a> {
                 cpu_count,
                 ram_total_mb,
                 cpu_core_score,
+                labels,
             } => {
                 self.on_worker_capabilities(
                     architectures,
@@ -93,6 +95,7 @@ impl<'a> DispatchContext<'a> {
                     cpu_count,
                     ram_total_mb,
                     cpu_core_score,
+                    labels,
                 )
                 .await;
                 true
@@ -449,8 +452,19 @@ impl<'a> DispatchContext<'a> {
         cpu_count: u32,
         ram_total_mb: u64,
         cpu_core_score: u32,
+        labels: Vec<(String, String)>,
     ) {
-        debug!(peer_id = %self.peer_id, ?architectures, ?system_features, max_concurrent_builds, cpu_count, ram_total_mb, cpu_core_score, "WorkerCapabilities");
+        debug!(peer_id = %self.peer_id, ?architectures, ?system_features, max_concurrent_builds, cpu_count, ram_total_mb, cpu_core_score, ?labels, "WorkerCapabilities");
+        let labels = labels
+            .into_iter()
+            .filter(|(key, value)| match validate_label(key, value) {
+                Ok(()) => true,
+                Err(e) => {
+                    warn!(peer_id = %self.peer_id, error = %e, "ignoring invalid worker label");
+                    false
+                }
+            })
+            .collect();
         self.scheduler
             .update_worker_capabilities(
                 self.peer_id,
@@ -460,6 +474,7 @@ impl<'a> DispatchContext<'a> {
                 cpu_count,
                 ram_total_mb,
                 cpu_core_score,
+                labels,
             )
             .await;
     }
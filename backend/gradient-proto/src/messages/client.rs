@@ -54,6 +54,9 @@ pub enum ClientMessage {
         ram_total_mb: u64,
         /// Relative single-core performance score (higher is faster).
         cpu_core_score: u32,
+        /// Free-form `key=value` labels matched against project worker
+        /// selectors. Registration labels override these per organization.
+        labels: Vec<(String, String)>,
     },
 
     /// Live resource-utilisation heartbeat. Sent periodically while connected so
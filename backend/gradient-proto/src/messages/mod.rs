@@ -16,8 +16,8 @@ pub use gradient_types::proto::{
     BumpedInputWire, CacheInfo, CachedPath, CandidateScore, CredentialKind, DerivationOutput,
     DiscoveredDerivation, EvalAttrCost, EvalCachePullOutcome, EvalCachePushMode, EvalMessageLevel,
     EvalStatsReport, FlakeInputOverride, FlakeJob, FlakeOutputNode, FlakeSource, FlakeTask,
-    GradientCapabilities, InputUpdateSpec, Job, JobCandidate, JobKind, JobUpdateKind, QueryMode,
-    RequiredPath,
+    FloatingOutputs, GradientCapabilities, InputUpdateSpec, Job, JobCandidate, JobKind,
+    JobUpdateKind, QueryMode, RequiredPath,
 };
 pub use server::{FailedPeer, ServerMessage};
 pub use wire::{decode_client_message, decode_server_message};
@@ -26,7 +26,11 @@ pub use wire::{decode_client_message, decode_server_message};
 /// v5: dropped `PresignedUpload`/`PresignedDownload` and `AssignJob.timeout_secs`.
 /// v7: `CacheQuery`/`CacheStatus`/`CacheError` carry a per-query `query_id`;
 ///     `NarUploaded` carries the path's content address (`ca`).
-pub const PROTO_VERSION: u16 = 7;
+/// v8: `WorkerCapabilities` carries the worker's advertised `labels`.
+/// v9: `BuildTask` carries the project's `max_output_size`.
+/// v10: `DiscoveredDerivation` carries an aggregate's `constituents`.
+/// v11: `DiscoveredDerivation` carries its `floating_outputs`.
+pub const PROTO_VERSION: u16 = 11;
 
 pub use gradient_types::constants::{NAR_ZSTD_LEVEL, PRESIGN_TTL};
 
@@ -0,0 +1,175 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Where scaling decisions go. The production backends POST them to a webhook
+//! or pipe them into a local command; tests record them in memory.
+
+use std::process::Stdio;
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use async_trait::async_trait;
+use tokio::io::AsyncWriteExt;
+
+use super::plan::ScaleDecision;
+
+/// Upper bound on one webhook request or command run; a hung provisioner
+/// must not stall the next tick forever.
+const APPLY_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Receiver of every non-empty [`ScaleDecision`]. Implementations start and
+/// stop machines; the scheduler only drains workers.
+#[async_trait]
+pub trait AutoscaleBackend: Send + Sync + std::fmt::Debug + 'static {
+    async fn apply(&self, decision: &ScaleDecision) -> Result<()>;
+}
+
+/// POSTs the decision as JSON, with an optional bearer token.
+#[derive(Debug)]
+pub struct WebhookBackend {
+    http: reqwest::Client,
+    url: String,
+    token: Option<String>,
+}
+
+impl WebhookBackend {
+    pub fn new(http: reqwest::Client, url: String, token: Option<String>) -> Self {
+        Self { http, url, token }
+    }
+}
+
+#[async_trait]
+impl AutoscaleBackend for WebhookBackend {
+    async fn apply(&self, decision: &ScaleDecision) -> Result<()> {
+        let mut req = self
+            .http
+            .post(&self.url)
+            .timeout(APPLY_TIMEOUT)
+            .json(decision);
+        if let Some(token) = &self.token {
+            req = req.bearer_auth(token);
+        }
+        req.send()
+            .await
+            .context("autoscale webhook request failed")?
+            .error_for_status()
+            .context("autoscale webhook rejected the decision")?;
+        Ok(())
+    }
+}
+
+/// Runs `sh -c <command>` with the decision as JSON on stdin; a non-zero exit
+/// is an error carrying the trimmed stderr.
+#[derive(Debug)]
+pub struct CommandBackend {
+    command: String,
+}
+
+impl CommandBackend {
+    pub fn new(command: String) -> Self {
+        Self { command }
+    }
+}
+
+#[async_trait]
+impl AutoscaleBackend for CommandBackend {
+    async fn apply(&self, decision: &ScaleDecision) -> Result<()> {
+        let input = serde_json::to_vec(decision)?;
+        let mut child = tokio::process::Command::new("sh")
+            .arg("-c")
+            .arg(&self.command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .context("failed to spawn autoscale command")?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            // A command that ignores stdin closes the pipe early; its exit
+            // status is what counts.
+            let _ = stdin.write_all(&input).await;
+        }
+
+        let output = tokio::time::timeout(APPLY_TIMEOUT, child.wait_with_output())
+            .await
+            .context("autoscale command timed out")?
+            .context("failed to run autoscale command")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            bail!("autoscale command failed: {}", stderr.trim());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decision() -> ScaleDecision {
+        ScaleDecision {
+            drain: vec!["w1".into()],
+            ..ScaleDecision::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn webhook_posts_decision_with_bearer_token() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/scale"))
+            .and(wiremock::matchers::header("authorization", "Bearer s3cret"))
+            .and(wiremock::matchers::body_json(serde_json::json!({
+                "pools": [],
+                "drain": ["w1"],
+                "undrain": [],
+                "release": [],
+            })))
+            .respond_with(wiremock::ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let backend = WebhookBackend::new(
+            reqwest::Client::new(),
+            format!("{}/scale", server.uri()),
+            Some("s3cret".into()),
+        );
+        backend.apply(&decision()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn webhook_error_status_is_an_error() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .respond_with(wiremock::ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        let backend = WebhookBackend::new(reqwest::Client::new(), server.uri(), None);
+        assert!(backend.apply(&decision()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn command_gets_decision_on_stdin() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("decision.json");
+        let backend = CommandBackend::new(format!("cat > '{}'", out.display()));
+        backend.apply(&decision()).await.unwrap();
+
+        let written: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&out).unwrap()).unwrap();
+        assert_eq!(written["drain"], serde_json::json!(["w1"]));
+    }
+
+    #[tokio::test]
+    async fn command_failure_carries_stderr() {
+        let backend = CommandBackend::new("echo no capacity >&2; exit 3".into());
+        let err = backend.apply(&decision()).await.unwrap_err();
+        assert!(err.to_string().contains("no capacity"), "{err}");
+    }
+}
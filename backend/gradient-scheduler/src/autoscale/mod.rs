@@ -0,0 +1,277 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Queue-pressure worker autoscaling.
+//!
+//! Every tick the pending build queue is grouped into `(architecture,
+//! system_features)` pools and compared against the connected workers able to
+//! serve each one; the resulting [`ScaleDecision`] goes to an
+//! [`AutoscaleBackend`], which starts or stops machines. Scale-down goes
+//! through the worker drain state: an idle scalable worker is drained first
+//! and only listed in `release` once it holds no job, so in-flight builds are
+//! never cut off. A drained worker that pending work wants again before it
+//! disconnects is put back in service rather than replaced.
+//!
+//! - [`plan`] - the pure decision over a queue/pool snapshot
+//! - [`backend`] - webhook and local-command backends
+
+pub mod backend;
+pub mod plan;
+
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use chrono::NaiveDateTime;
+use gradient_types::{AutoscaleArgs, Labels};
+use tracing::info;
+
+use crate::Scheduler;
+use crate::worker_pool::WorkerInfo;
+
+pub use backend::{AutoscaleBackend, CommandBackend, WebhookBackend};
+pub use plan::{AutoscalePolicy, AutoscaleState, PoolDecision, PoolWorker, ScaleDecision};
+
+/// Stateful driver of [`plan::plan`]: owns the backend and the idle/drain
+/// memory carried between ticks.
+#[derive(Debug)]
+pub struct Autoscaler {
+    backend: Arc<dyn AutoscaleBackend>,
+    policy: AutoscalePolicy,
+    /// Advertised labels a worker needs before it may be drained.
+    selector: Labels,
+    state: AutoscaleState,
+}
+
+impl Autoscaler {
+    pub fn new(
+        backend: Arc<dyn AutoscaleBackend>,
+        policy: AutoscalePolicy,
+        selector: Labels,
+    ) -> Self {
+        Self {
+            backend,
+            policy,
+            selector,
+            state: AutoscaleState::default(),
+        }
+    }
+
+    /// Build the configured autoscaler, or `None` when neither a webhook nor a
+    /// command is set.
+    pub fn from_config(args: &AutoscaleArgs, http: reqwest::Client) -> Result<Option<Self>> {
+        let backend: Arc<dyn AutoscaleBackend> = if let Some(url) = &args.autoscale_webhook_url {
+            let token = args
+                .autoscale_webhook_token_file
+                .as_deref()
+                .map(|path| {
+                    std::fs::read_to_string(path)
+                        .map(|t| t.trim().to_string())
+                        .with_context(|| format!("failed to read autoscale token file {path}"))
+                })
+                .transpose()?;
+            Arc::new(WebhookBackend::new(http, url.clone(), token))
+        } else if let Some(command) = &args.autoscale_command {
+            Arc::new(CommandBackend::new(command.clone()))
+        } else {
+            return Ok(None);
+        };
+        let policy = AutoscalePolicy {
+            idle_secs: args.autoscale_idle_secs,
+            new_worker_slots: args.autoscale_new_worker_slots,
+            max_workers: args.autoscale_max_workers,
+        };
+        let selector = args.autoscale_worker_selector.iter().cloned().collect();
+        Ok(Some(Self::new(backend, policy, selector)))
+    }
+
+    fn scalable(&self, info: &WorkerInfo) -> bool {
+        self.selector
+            .iter()
+            .all(|(k, v)| info.labels.advertised.get(k) == Some(v))
+    }
+
+    /// One decision round: snapshot the queue and the pool, drain the workers
+    /// that idled out and reactivate the drained ones wanted again, then hand
+    /// the decision to the backend. Drains are applied before the backend call
+    /// so a failing backend cannot leave an idle worker taking new jobs after
+    /// it was told to go.
+    pub async fn tick(
+        &mut self,
+        scheduler: &Scheduler,
+        now: NaiveDateTime,
+    ) -> Result<ScaleDecision> {
+        let demand = scheduler.job_tracker.read().await.pending_build_demand();
+        let workers: Vec<PoolWorker> = scheduler
+            .worker_pool
+            .read()
+            .await
+            .all_workers()
+            .iter()
+            .filter(|w| w.capabilities.build)
+            .map(|w| PoolWorker::from_info(w, self.scalable(w)))
+            .collect();
+
+        let decision = plan::plan(&demand, &workers, &mut self.state, &self.policy, now);
+        for worker_id in &decision.drain {
+            info!(%worker_id, "autoscaler draining idle worker");
+            scheduler.mark_worker_draining(worker_id).await;
+        }
+        for worker_id in &decision.undrain {
+            info!(%worker_id, "autoscaler reactivating drained worker");
+            scheduler.mark_worker_active(worker_id).await;
+        }
+        if !decision.is_empty() {
+            self.backend.apply(&decision).await?;
+        }
+        Ok(decision)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+    use gradient_types::ids::*;
+    use gradient_types::proto::{BuildJob, GradientCapabilities};
+
+    use super::*;
+    use crate::jobs::{PendingBuildJob, PendingJob};
+
+    #[derive(Debug, Default)]
+    struct FakeBackend {
+        applied: Mutex<Vec<ScaleDecision>>,
+    }
+
+    #[async_trait]
+    impl AutoscaleBackend for FakeBackend {
+        async fn apply(&self, decision: &ScaleDecision) -> Result<()> {
+            self.applied.lock().unwrap().push(decision.clone());
+            Ok(())
+        }
+    }
+
+    fn test_scheduler() -> Arc<Scheduler> {
+        use gradient_test_support::prelude::*;
+        use sea_orm::{DatabaseBackend, MockDatabase};
+
+        let db = MockDatabase::new(DatabaseBackend::Postgres).into_connection();
+        Arc::new(Scheduler::new(test_state(db)))
+    }
+
+    async fn connect_builder(scheduler: &Scheduler, id: &str, arch: &str) {
+        let caps = GradientCapabilities {
+            build: true,
+            ..GradientCapabilities::default()
+        };
+        scheduler.register_worker(id, caps, HashSet::new()).await;
+        scheduler
+            .update_worker_capabilities(
+                id,
+                vec![arch.into()],
+                vec![],
+                2,
+                8,
+                16_000,
+                100,
+                Default::default(),
+            )
+            .await;
+    }
+
+    fn build_job(arch: &str) -> PendingJob {
+        PendingJob::Build(PendingBuildJob {
+            derivation_build: DerivationBuildId::now_v7(),
+            evaluation_id: EvaluationId::now_v7(),
+            org_id: OrganizationId::now_v7(),
+            job: BuildJob { builds: vec![] },
+            required_paths: vec![],
+            architecture: arch.into(),
+            required_features: vec![],
+            dependency_count: 0,
+            closure_size: None,
+            prefer_local_build: false,
+            is_fixed_output: false,
+            history: Default::default(),
+            queued_at: gradient_types::now(),
+            ready_at: gradient_types::now(),
+            rescore_count: 0,
+            critical_path: None,
+            worker_selector: Default::default(),
+            pname: None,
+            substitute: false,
+            avoid_worker: None,
+        })
+    }
+
+    fn at(secs: i64) -> NaiveDateTime {
+        chrono::DateTime::from_timestamp(secs, 0)
+            .unwrap()
+            .naive_utc()
+    }
+
+    fn autoscaler(backend: Arc<FakeBackend>) -> Autoscaler {
+        let policy = AutoscalePolicy {
+            idle_secs: 60,
+            new_worker_slots: 2,
+            max_workers: 4,
+        };
+        Autoscaler::new(backend, policy, Labels::new())
+    }
+
+    #[tokio::test]
+    async fn tick_reports_demand_and_drains_idle_workers() {
+        let scheduler = test_scheduler();
+        let backend = Arc::new(FakeBackend::default());
+        let mut scaler = autoscaler(Arc::clone(&backend));
+
+        connect_builder(&scheduler, "idle", "aarch64-linux").await;
+        for i in 0..3 {
+            scheduler
+                .job_tracker
+                .write()
+                .await
+                .add_pending(format!("b{i}"), build_job("x86_64-linux"));
+        }
+
+        let d = scaler.tick(&scheduler, at(0)).await.unwrap();
+        assert_eq!(d.pools.len(), 1);
+        assert_eq!(d.pools[0].desired_workers, 2);
+        assert!(d.drain.is_empty());
+
+        let d = scaler.tick(&scheduler, at(60)).await.unwrap();
+        assert_eq!(d.drain, ["idle"]);
+        let draining = scheduler.worker_pool.read().await.all_workers()[0].draining;
+        assert!(draining, "idle worker must be drained before release");
+
+        let d = scaler.tick(&scheduler, at(90)).await.unwrap();
+        assert_eq!(d.release, ["idle"]);
+        assert_eq!(backend.applied.lock().unwrap().len(), 3);
+
+        scheduler
+            .job_tracker
+            .write()
+            .await
+            .add_pending("arm".into(), build_job("aarch64-linux"));
+        let d = scaler.tick(&scheduler, at(120)).await.unwrap();
+        assert_eq!(d.undrain, ["idle"]);
+        assert!(d.release.is_empty());
+        let draining = scheduler.worker_pool.read().await.all_workers()[0].draining;
+        assert!(!draining, "wanted worker must be back in service");
+    }
+
+    #[tokio::test]
+    async fn quiet_tick_does_not_call_backend() {
+        let scheduler = test_scheduler();
+        let backend = Arc::new(FakeBackend::default());
+        let mut scaler = autoscaler(Arc::clone(&backend));
+
+        connect_builder(&scheduler, "w1", "x86_64-linux").await;
+        scaler.tick(&scheduler, at(0)).await.unwrap();
+        assert!(backend.applied.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,371 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Pure scaling decision: pending build demand and the connected pool in,
+//! desired worker counts plus drain/release lists out. No I/O, so every rule
+//! is unit-tested against plain snapshots.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use chrono::NaiveDateTime;
+use serde::Serialize;
+
+use crate::worker_pool::WorkerInfo;
+
+/// Tunables of [`plan`], taken from `AutoscaleArgs`.
+#[derive(Debug, Clone)]
+pub struct AutoscalePolicy {
+    pub idle_secs: u64,
+    pub new_worker_slots: u32,
+    pub max_workers: u32,
+}
+
+/// Demand and sizing for one `(architecture, system_features)` pool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PoolDecision {
+    pub architecture: String,
+    /// Sorted, de-duplicated features every build of the pool requires.
+    pub system_features: Vec<String>,
+    pub pending_builds: u32,
+    /// Unused build slots of the non-draining workers able to serve the pool.
+    pub free_slots: u32,
+    /// Non-draining connected workers able to serve the pool.
+    pub current_workers: u32,
+    pub desired_workers: u32,
+}
+
+/// One autoscaler tick, as sent to the backend.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ScaleDecision {
+    pub pools: Vec<PoolDecision>,
+    /// Scalable workers idle past the threshold: drained this tick, so the
+    /// scheduler offers them no new jobs.
+    pub drain: Vec<String>,
+    /// Workers this autoscaler drained that pending work wants again before
+    /// they disconnected: back in service this tick and no longer released.
+    pub undrain: Vec<String>,
+    /// Workers the autoscaler drained that have no job left - safe to
+    /// terminate. Repeated every tick until the worker disconnects.
+    pub release: Vec<String>,
+}
+
+impl ScaleDecision {
+    pub fn is_empty(&self) -> bool {
+        self.pools.is_empty()
+            && self.drain.is_empty()
+            && self.undrain.is_empty()
+            && self.release.is_empty()
+    }
+}
+
+/// Per-worker memory carried between ticks.
+#[derive(Debug, Default)]
+pub struct AutoscaleState {
+    /// When each scalable worker was first seen with nothing to do.
+    idle_since: HashMap<String, NaiveDateTime>,
+    /// Workers this autoscaler drained and has not yet seen disconnect.
+    drained: HashSet<String>,
+}
+
+/// A build-capable connected worker as the planner sees it.
+#[derive(Debug, Clone)]
+pub struct PoolWorker {
+    pub id: String,
+    pub architectures: Vec<String>,
+    pub system_features: Vec<String>,
+    pub max_concurrent_builds: u32,
+    pub assigned: u32,
+    pub draining: bool,
+    /// Matches the autoscale worker selector, so it may be drained.
+    pub scalable: bool,
+}
+
+impl PoolWorker {
+    pub fn from_info(info: &WorkerInfo, scalable: bool) -> Self {
+        Self {
+            id: info.id.clone(),
+            architectures: info.architectures.clone(),
+            system_features: info.system_features.clone(),
+            max_concurrent_builds: info.max_concurrent_builds,
+            assigned: info.assigned_job_count as u32,
+            draining: info.draining,
+            scalable,
+        }
+    }
+
+    fn serves(&self, (architecture, features): &(String, Vec<String>)) -> bool {
+        self.architectures.iter().any(|a| a == architecture)
+            && features
+                .iter()
+                .all(|f| self.system_features.iter().any(|sf| sf == f))
+    }
+}
+
+/// Decide desired counts per pool, which idle workers to drain, which drained
+/// workers to put back in service, and which to release. `demand` holds one `(architecture,
+/// required_features)` entry per pending build.
+///
+/// A worker able to serve several pools counts toward each of them, so
+/// multi-architecture workers make the per-pool counts optimistic.
+pub fn plan(
+    demand: &[(String, Vec<String>)],
+    workers: &[PoolWorker],
+    state: &mut AutoscaleState,
+    policy: &AutoscalePolicy,
+    now: NaiveDateTime,
+) -> ScaleDecision {
+    let mut pending: BTreeMap<(String, Vec<String>), u32> = BTreeMap::new();
+    for (architecture, features) in demand {
+        let mut features = features.clone();
+        features.sort();
+        features.dedup();
+        *pending.entry((architecture.clone(), features)).or_default() += 1;
+    }
+
+    let connected: HashSet<&str> = workers.iter().map(|w| w.id.as_str()).collect();
+    state.drained.retain(|id| connected.contains(id.as_str()));
+    state
+        .idle_since
+        .retain(|id, _| connected.contains(id.as_str()));
+
+    // A worker the autoscaler drained is reused before anything new is
+    // started; workers drained by anyone else stay drained.
+    let mut undrain: Vec<String> = workers
+        .iter()
+        .filter(|w| w.draining && state.drained.contains(&w.id))
+        .filter(|w| pending.keys().any(|key| w.serves(key)))
+        .map(|w| w.id.clone())
+        .collect();
+    for id in &undrain {
+        state.drained.remove(id);
+    }
+    let in_service = |w: &PoolWorker| !w.draining || undrain.contains(&w.id);
+
+    let slots = policy.new_worker_slots.max(1);
+    let pools: Vec<PoolDecision> = pending
+        .iter()
+        .map(|(key, &pending_builds)| {
+            let serving: Vec<&PoolWorker> = workers
+                .iter()
+                .filter(|w| in_service(w) && w.serves(key))
+                .collect();
+            let free_slots: u32 = serving
+                .iter()
+                .map(|w| w.max_concurrent_builds.saturating_sub(w.assigned))
+                .sum();
+            let current_workers = serving.len() as u32;
+            let extra = pending_builds.saturating_sub(free_slots).div_ceil(slots);
+            let desired_workers = if extra == 0 {
+                current_workers
+            } else {
+                (current_workers + extra).min(policy.max_workers.max(current_workers))
+            };
+            PoolDecision {
+                architecture: key.0.clone(),
+                system_features: key.1.clone(),
+                pending_builds,
+                free_slots,
+                current_workers,
+                desired_workers,
+            }
+        })
+        .collect();
+
+    let mut drain = Vec::new();
+    for w in workers.iter().filter(|w| w.scalable && !w.draining) {
+        let wanted = pending.keys().any(|key| w.serves(key));
+        if w.assigned > 0 || wanted {
+            state.idle_since.remove(&w.id);
+            continue;
+        }
+        let since = *state.idle_since.entry(w.id.clone()).or_insert(now);
+        if (now - since).num_seconds() >= policy.idle_secs as i64 {
+            state.idle_since.remove(&w.id);
+            state.drained.insert(w.id.clone());
+            drain.push(w.id.clone());
+        }
+    }
+
+    let mut release: Vec<String> = workers
+        .iter()
+        .filter(|w| state.drained.contains(&w.id) && w.assigned == 0 && !drain.contains(&w.id))
+        .map(|w| w.id.clone())
+        .collect();
+    release.sort();
+    drain.sort();
+    undrain.sort();
+
+    ScaleDecision {
+        pools,
+        drain,
+        undrain,
+        release,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> AutoscalePolicy {
+        AutoscalePolicy {
+            idle_secs: 600,
+            new_worker_slots: 2,
+            max_workers: 5,
+        }
+    }
+
+    fn worker(id: &str, arch: &str, slots: u32, assigned: u32) -> PoolWorker {
+        PoolWorker {
+            id: id.into(),
+            architectures: vec![arch.into()],
+            system_features: vec![],
+            max_concurrent_builds: slots,
+            assigned,
+            draining: false,
+            scalable: true,
+        }
+    }
+
+    fn builds(arch: &str, features: &[&str], n: usize) -> Vec<(String, Vec<String>)> {
+        let features: Vec<String> = features.iter().map(|f| f.to_string()).collect();
+        vec![(arch.to_string(), features); n]
+    }
+
+    fn at(secs: i64) -> NaiveDateTime {
+        chrono::DateTime::from_timestamp(secs, 0)
+            .unwrap()
+            .naive_utc()
+    }
+
+    #[test]
+    fn shortfall_beyond_free_slots_scales_up_capped() {
+        let mut state = AutoscaleState::default();
+        let workers = [worker("w1", "x86_64-linux", 2, 1)];
+
+        let d = plan(
+            &builds("x86_64-linux", &[], 6),
+            &workers,
+            &mut state,
+            &policy(),
+            at(0),
+        );
+        // 1 free slot, 5 left over at 2 slots per new worker -> 3 more.
+        assert_eq!(d.pools.len(), 1);
+        assert_eq!(d.pools[0].free_slots, 1);
+        assert_eq!(d.pools[0].desired_workers, 4);
+
+        let d = plan(
+            &builds("x86_64-linux", &[], 40),
+            &workers,
+            &mut state,
+            &policy(),
+            at(0),
+        );
+        assert_eq!(d.pools[0].desired_workers, 5);
+    }
+
+    #[test]
+    fn pools_split_by_architecture_and_feature_set() {
+        let mut state = AutoscaleState::default();
+        let mut demand = builds("x86_64-linux", &["kvm", "big-parallel"], 1);
+        demand.extend(builds("x86_64-linux", &["big-parallel", "kvm"], 1));
+        demand.extend(builds("aarch64-linux", &[], 1));
+
+        let d = plan(&demand, &[], &mut state, &policy(), at(0));
+        assert_eq!(d.pools.len(), 2);
+        assert_eq!(d.pools[1].architecture, "x86_64-linux");
+        assert_eq!(d.pools[1].system_features, ["big-parallel", "kvm"]);
+        assert_eq!(d.pools[1].pending_builds, 2);
+        assert_eq!(d.pools[1].desired_workers, 1);
+        assert_eq!(d.pools[0].current_workers, 0);
+    }
+
+    #[test]
+    fn idle_worker_is_drained_then_released() {
+        let mut state = AutoscaleState::default();
+        let mut workers = vec![worker("w1", "x86_64-linux", 2, 0)];
+
+        assert!(plan(&[], &workers, &mut state, &policy(), at(0)).is_empty());
+        assert!(plan(&[], &workers, &mut state, &policy(), at(599)).is_empty());
+        let d = plan(&[], &workers, &mut state, &policy(), at(600));
+        assert_eq!(d.drain, ["w1"]);
+        assert!(d.release.is_empty());
+
+        workers[0].draining = true;
+        let d = plan(&[], &workers, &mut state, &policy(), at(630));
+        assert_eq!(d.release, ["w1"]);
+
+        // Gone: forgotten, no further release.
+        assert!(plan(&[], &[], &mut state, &policy(), at(660)).is_empty());
+    }
+
+    #[test]
+    fn drained_worker_is_reused_when_work_returns() {
+        let mut state = AutoscaleState::default();
+        let mut workers = vec![worker("w1", "x86_64-linux", 2, 0)];
+        plan(&[], &workers, &mut state, &policy(), at(0));
+        plan(&[], &workers, &mut state, &policy(), at(600));
+        workers[0].draining = true;
+
+        let d = plan(
+            &builds("x86_64-linux", &[], 2),
+            &workers,
+            &mut state,
+            &policy(),
+            at(630),
+        );
+        assert_eq!(d.undrain, ["w1"]);
+        assert!(d.release.is_empty());
+        assert_eq!(d.pools[0].free_slots, 2);
+        assert_eq!(d.pools[0].desired_workers, 1);
+
+        // Drained by someone else (e.g. an operator): left alone.
+        let mut state = AutoscaleState::default();
+        let d = plan(
+            &builds("x86_64-linux", &[], 2),
+            &workers,
+            &mut state,
+            &policy(),
+            at(0),
+        );
+        assert!(d.undrain.is_empty());
+        assert_eq!(d.pools[0].current_workers, 0);
+    }
+
+    #[test]
+    fn drained_worker_with_jobs_is_not_released() {
+        let mut state = AutoscaleState::default();
+        let mut workers = vec![worker("w1", "x86_64-linux", 2, 0)];
+        plan(&[], &workers, &mut state, &policy(), at(0));
+        plan(&[], &workers, &mut state, &policy(), at(600));
+
+        workers[0].draining = true;
+        workers[0].assigned = 1;
+        assert!(
+            plan(&[], &workers, &mut state, &policy(), at(630))
+                .release
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn busy_wanted_or_unscalable_workers_are_never_drained() {
+        let mut state = AutoscaleState::default();
+        let mut unscalable = worker("fixed", "aarch64-linux", 2, 0);
+        unscalable.scalable = false;
+        let workers = [
+            worker("busy", "aarch64-linux", 2, 1),
+            worker("wanted", "x86_64-linux", 2, 0),
+            unscalable,
+        ];
+        let demand = builds("x86_64-linux", &[], 1);
+
+        plan(&demand, &workers, &mut state, &policy(), at(0));
+        let d = plan(&demand, &workers, &mut state, &policy(), at(10_000));
+        assert!(d.drain.is_empty());
+    }
+}
@@ -0,0 +1,182 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Flaky-build detection. A failure that would be final is re-run on another
+//! worker when its derivation name has a flaky history in the project or the
+//! tail of the failed attempt's log matches a configured pattern, up to
+//! `flaky_max_reruns` times per build and evaluation.
+
+use std::sync::Arc;
+
+use gradient_core::ServerState;
+use gradient_db::FlakySubject;
+use gradient_entity::build_flakiness;
+use gradient_types::constants::BUILD_LOG_TAIL_BYTES;
+use gradient_types::ids::{BuildAttemptId, DerivationBuildId};
+use tracing::warn;
+
+/// Final outcomes recorded before the flip rate alone may mark a name flaky.
+pub(crate) const FLAKY_MIN_RUNS: i64 = 5;
+
+/// Whether `history` shows the name to be flaky: an earlier re-run of it
+/// passed, or enough consecutive outcomes flip between pass and fail.
+pub(crate) fn known_flaky(history: &build_flakiness::Model, flip_rate_percent: u32) -> bool {
+    if history.rerun_passes > 0 {
+        return true;
+    }
+
+    history.runs >= FLAKY_MIN_RUNS
+        && history.flips * 100 >= (history.runs - 1) * i64::from(flip_rate_percent)
+}
+
+/// Whether `log` contains any of `patterns`, ignoring case. Blank patterns
+/// never match.
+pub(crate) fn matches_flaky_pattern(log: &str, patterns: &[String]) -> bool {
+    let log = log.to_lowercase();
+    patterns
+        .iter()
+        .map(|p| p.trim())
+        .filter(|p| !p.is_empty())
+        .any(|p| log.contains(&p.to_lowercase()))
+}
+
+/// Decide whether a failure that would be final is re-run instead, given the
+/// re-runs this build already had.
+pub(crate) fn should_rerun(
+    reruns: i64,
+    max_reruns: u32,
+    known_flaky: bool,
+    pattern_match: bool,
+) -> bool {
+    reruns < i64::from(max_reruns) && (known_flaky || pattern_match)
+}
+
+/// Decide whether `derivation_build`'s final failure gets a flaky re-run.
+/// Patterns are matched against the tail of `attempt_id`'s log, which ends
+/// with the worker's error. Lookup errors are logged and count as "no
+/// re-run".
+pub(crate) async fn rerun_flaky_failure(
+    state: &Arc<ServerState>,
+    derivation_build: DerivationBuildId,
+    attempt_id: Option<BuildAttemptId>,
+    subject: Option<&FlakySubject>,
+) -> bool {
+    let eval = &state.config.eval;
+    if eval.flaky_max_reruns == 0 {
+        return false;
+    }
+
+    let reruns = match gradient_db::flaky_attempt_count(&state.worker_db, derivation_build).await {
+        Ok(n) => n,
+        Err(e) => {
+            warn!(%derivation_build, error = %e, "failed to count flaky re-runs");
+            return false;
+        }
+    };
+    let history = match subject {
+        Some(subject) => gradient_db::flakiness_of(&state.worker_db, subject)
+            .await
+            .unwrap_or_else(|e| {
+                warn!(%derivation_build, error = %e, "failed to load flakiness history");
+                None
+            }),
+        None => None,
+    };
+    let known = history
+        .as_ref()
+        .is_some_and(|h| known_flaky(h, eval.flaky_flip_rate_percent));
+    // The log only matters when the history alone does not decide.
+    if reruns >= i64::from(eval.flaky_max_reruns) || known || eval.flaky_log_patterns.is_empty() {
+        return should_rerun(reruns, eval.flaky_max_reruns, known, false);
+    }
+
+    let log = match attempt_id {
+        Some(attempt_id) => state
+            .log_storage
+            .read_tail(attempt_id, BUILD_LOG_TAIL_BYTES)
+            .await
+            .unwrap_or_else(|e| {
+                warn!(%derivation_build, error = %e, "failed to read build log for flaky patterns");
+                String::new()
+            }),
+        None => String::new(),
+    };
+    should_rerun(
+        reruns,
+        eval.flaky_max_reruns,
+        known,
+        matches_flaky_pattern(&log, &eval.flaky_log_patterns),
+    )
+}
+
+/// Record a final outcome of `subject`, best-effort.
+pub(crate) async fn record_outcome(
+    state: &Arc<ServerState>,
+    subject: Option<&FlakySubject>,
+    passed: bool,
+    rerun_passed: bool,
+) {
+    let Some(subject) = subject else {
+        return;
+    };
+    if let Err(e) =
+        gradient_db::record_flaky_outcome(&state.worker_db, subject, passed, rerun_passed).await
+    {
+        warn!(project = %subject.project, name = %subject.name, error = %e, "failed to record build outcome history");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(runs: i64, flips: i64, rerun_passes: i64) -> build_flakiness::Model {
+        build_flakiness::Model {
+            runs,
+            flips,
+            rerun_passes,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn flip_rate_marks_flaky_only_past_min_runs() {
+        // 2 flips over 4 transitions is 50%, but 5 runs are needed first.
+        assert!(!known_flaky(&history(4, 2, 0), 20));
+        assert!(known_flaky(&history(5, 1, 0), 25));
+        assert!(!known_flaky(&history(5, 0, 0), 20));
+        assert!(!known_flaky(&history(11, 1, 0), 20));
+        assert!(known_flaky(&history(11, 2, 0), 20));
+    }
+
+    #[test]
+    fn a_passing_rerun_marks_flaky_immediately() {
+        assert!(known_flaky(&history(1, 0, 1), 20));
+    }
+
+    #[test]
+    fn pattern_match_is_case_insensitive_and_skips_blanks() {
+        let patterns = vec![" ".to_string(), "connection reset".to_string()];
+        assert!(matches_flaky_pattern(
+            "error: Connection Reset by peer",
+            &patterns
+        ));
+        assert!(!matches_flaky_pattern(
+            "builder failed with exit code 1",
+            &patterns
+        ));
+        assert!(!matches_flaky_pattern("anything", &[String::new()]));
+    }
+
+    #[test]
+    fn rerun_needs_a_signal_and_remaining_budget() {
+        assert!(should_rerun(0, 1, true, false));
+        assert!(should_rerun(0, 1, false, true));
+        assert!(!should_rerun(0, 1, false, false));
+        assert!(!should_rerun(1, 1, true, true));
+        assert!(!should_rerun(0, 0, true, true));
+    }
+}
@@ -24,6 +24,7 @@ use sea_orm::{
 };
 use tracing::{error, info, warn};
 
+use super::flaky;
 use super::self_heal::reconcile_missing_inputs;
 use crate::jobs::{PendingBuildJob, PendingJob};
 use crate::waiting_state::persist_waiting_reason;
@@ -126,6 +127,44 @@ fn attempt_reason(kind: BuildFailureKind) -> Option<AttemptFailureReason> {
     }
 }
 
+/// Run the log matchers over the tail of a failed attempt's stored log and
+/// record the recognised cause on it, best-effort. Substitute misses and missing inputs
+/// carry their own reason and are not analysed.
+async fn record_failure_cause(
+    state: &Arc<ServerState>,
+    attempt_id: BuildAttemptId,
+    kind: BuildFailureKind,
+) {
+    if !matches!(
+        kind,
+        BuildFailureKind::Transient | BuildFailureKind::Permanent | BuildFailureKind::Timeout
+    ) {
+        return;
+    }
+
+    let log = match state
+        .log_storage
+        .read_tail(attempt_id, gradient_types::constants::BUILD_LOG_TAIL_BYTES)
+        .await
+    {
+        Ok(log) => log,
+        Err(e) => {
+            warn!(%attempt_id, error = %e, "failed to read build log for failure analysis");
+            return;
+        }
+    };
+    let Some(cause) =
+        gradient_types::analyze_build_log(&log, matches!(kind, BuildFailureKind::Timeout))
+    else {
+        return;
+    };
+    if let Err(e) =
+        gradient_db::set_attempt_failure_cause(&state.worker_db, attempt_id, &cause).await
+    {
+        warn!(%attempt_id, error = %e, "failed to record build failure cause");
+    }
+}
+
 /// Circuit breaker for the `InputsUnavailable` self-heal. Each failed eval
 /// reconciles the cache (purges the stale input) so the next eval rebuilds it; a
 /// genuinely unrecoverable input turns that into a hot loop that churns the cache
@@ -150,6 +189,36 @@ pub(crate) fn retry_backoff_elapsed(
     (now - failed_at).num_seconds() >= window as i64
 }
 
+/// Record the realisation of a floating CA output `organization`'s build just
+/// produced. `None` when `output` is not one, or recording it failed.
+async fn record_realisation(
+    state: &Arc<ServerState>,
+    organization: OrganizationId,
+    derivation_id: DerivationId,
+    output: &BuildOutput,
+) -> Option<MDerivationOutput> {
+    let store_path = match StorePath::parse(&output.store_path) {
+        Ok(path) => path,
+        Err(e) => {
+            warn!(error = %e, output_name = %output.name, "unparseable output path");
+            return None;
+        }
+    };
+    gradient_db::record_realised_output(
+        &state.worker_db,
+        organization,
+        derivation_id,
+        &output.name,
+        &store_path,
+    )
+    .await
+        .inspect_err(|e| {
+            error!(error = %e, %derivation_id, output_name = %output.name, "failed to record realisation");
+        })
+        .ok()
+        .flatten()
+}
+
 /// Cap a worker failure string before persisting it on `build_attempt`. The full
 /// text already lands in the build log; the stored message is for quick surfacing,
 /// so bound it on a char boundary to keep the row lean.
@@ -168,7 +237,7 @@ fn truncate_failure_message(error: &str) -> String {
 
 pub async fn handle_build_output(
     state: &Arc<ServerState>,
-    _job: &PendingBuildJob,
+    job: &PendingBuildJob,
     derivation_build: DerivationBuildId,
     outputs: Vec<BuildOutput>,
     metrics: Option<BuildMetrics>,
@@ -196,6 +265,11 @@ pub async fn handle_build_output(
             .one(&state.worker_db)
             .await
             .context("fetch derivation_output")?;
+        // A floating CA output gets its row once a build realises it.
+        let existing = match existing {
+            Some(row) => Some(row),
+            None => record_realisation(state, job.org_id, derivation_id, output).await,
+        };
 
         if let Some(row) = existing {
             let row_id = row.id;
@@ -283,6 +357,21 @@ pub async fn handle_build_job_completed(
     let derivation_id = anchor.derivation;
     let was_external_cached = anchor.substitutable;
 
+    // Only a real build is a run of the derivation; a substitution says
+    // nothing about flakiness.
+    if !anchor.substituted && !was_external_cached {
+        let rerun_passed = gradient_db::latest_attempt(&state.worker_db, derivation_build)
+            .await
+            .ok()
+            .flatten()
+            .is_some_and(|a| a.flaky_rerun);
+        let subject = flaky_subject(state, derivation_build).await;
+        flaky::record_outcome(state, subject.as_ref(), true, rerun_passed).await;
+        if rerun_passed {
+            info!(%derivation_build, "flaky re-run passed");
+        }
+    }
+
     // The worker has finished pushing this job's output NARs by the time
     // `JobCompleted` arrives, so it is now safe to make the anchor
     // dispatch-ready. `Substituted` when the daemon found the outputs
@@ -402,10 +491,11 @@ pub async fn handle_build_job_failed(
     // Strip nix's repeated log tail here too: a worker older than this change
     // still sends it, and those lines are already in the log above.
     let banner = gradient_sources::strip_nix_log_tail(error);
-    if let Some(attempt_id) = gradient_db::latest_attempt_id(&state.worker_db, anchor.id)
+    let attempt_id = gradient_db::latest_attempt_id(&state.worker_db, anchor.id)
         .await
         .ok()
-        .flatten()
+        .flatten();
+    if let Some(attempt_id) = attempt_id
         && let Err(e) = state
             .log_storage
             .append(attempt_id, &format!("\n=== build failed: {banner} ===\n"))
@@ -439,6 +529,11 @@ pub async fn handle_build_job_failed(
     {
         warn!(%derivation_build, error = %e, "failed to record attempt failure reason");
     }
+    // Before any status change: the `build.failed` action payload reads the
+    // cause back from the attempt.
+    if let Some(attempt_id) = attempt_id {
+        record_failure_cause(state, attempt_id, kind).await;
+    }
 
     // Self-heal: a required input was reported absent from the cache while
     // its producer was marked done/substituted. Purge those stale outputs so
@@ -469,17 +564,7 @@ pub async fn handle_build_job_failed(
     };
     match outcome {
         FailureOutcome::Retry => {
-            let mut active: ADerivationBuild = anchor.clone().into_active_model();
-            active.attempt = Set(attempt + 1);
-            if let Err(e) = active.update(&state.worker_db).await {
-                error!(%derivation_build, error = %e, "failed to bump anchor attempt");
-            }
-            let reloaded = EDerivationBuild::find_by_id(derivation_build)
-                .one(&state.worker_db)
-                .await?
-                .unwrap_or(anchor);
-            update_derivation_build_status(&state.db(), reloaded, BuildStatus::FailedTransient)
-                .await;
+            schedule_retry(state, anchor).await?;
             info!(%derivation_build, attempt = attempt + 1, "transient build failure; scheduled for retry");
             return Ok(());
         }
@@ -493,10 +578,35 @@ pub async fn handle_build_job_failed(
             return Ok(());
         }
         FailureOutcome::Permanent => {
+            let subject = flaky_subject(state, derivation_build).await;
+            // A flaky failure gets another run on a different worker before
+            // it is final; an unrecoverable input is never flaky.
+            if !inputs_circuit_open
+                && flaky::rerun_flaky_failure(state, derivation_build, attempt_id, subject.as_ref())
+                    .await
+            {
+                if let Err(e) =
+                    gradient_db::mark_latest_attempt_flaky(&state.worker_db, derivation_build).await
+                {
+                    warn!(%derivation_build, error = %e, "failed to mark attempt flaky");
+                }
+                if let Some(subject) = &subject
+                    && let Err(e) = gradient_db::record_flaky_rerun(&state.worker_db, subject).await
+                {
+                    warn!(%derivation_build, error = %e, "failed to record flaky re-run");
+                }
+                schedule_retry(state, anchor).await?;
+                info!(%derivation_build, "flaky build failure; re-running on another worker");
+                return Ok(());
+            }
+
             update_derivation_build_status(&state.db(), anchor, BuildStatus::FailedPermanent).await;
+            flaky::record_outcome(state, subject.as_ref(), false, false).await;
         }
         FailureOutcome::Timeout => {
+            let subject = flaky_subject(state, derivation_build).await;
             update_derivation_build_status(&state.db(), anchor, BuildStatus::FailedTimeout).await;
+            flaky::record_outcome(state, subject.as_ref(), false, false).await;
         }
     }
 
@@ -507,6 +617,36 @@ pub async fn handle_build_job_failed(
     check_referencing_evals_done(state, derivation_id).await
 }
 
+/// Bump the anchor's `attempt` and park it `FailedTransient`, so the requeue
+/// pass dispatches it again once its backoff elapses.
+async fn schedule_retry(state: &Arc<ServerState>, anchor: MDerivationBuild) -> Result<()> {
+    let derivation_build = anchor.id;
+    let mut active: ADerivationBuild = anchor.clone().into_active_model();
+    active.attempt = Set(anchor.attempt + 1);
+    if let Err(e) = active.update(&state.worker_db).await {
+        error!(%derivation_build, error = %e, "failed to bump anchor attempt");
+    }
+    let reloaded = EDerivationBuild::find_by_id(derivation_build)
+        .one(&state.worker_db)
+        .await?
+        .unwrap_or(anchor);
+    update_derivation_build_status(&state.db(), reloaded, BuildStatus::FailedTransient).await;
+    Ok(())
+}
+
+/// The flakiness key of an anchor, best-effort.
+async fn flaky_subject(
+    state: &Arc<ServerState>,
+    derivation_build: DerivationBuildId,
+) -> Option<gradient_db::FlakySubject> {
+    gradient_db::flaky_subject(&state.worker_db, derivation_build)
+        .await
+        .unwrap_or_else(|e| {
+            warn!(%derivation_build, error = %e, "failed to resolve flakiness key");
+            None
+        })
+}
+
 /// After an anchor reaches a terminal status, sweep every evaluation that
 /// references the derivation and finalize the settled ones. Idempotent
 /// belt-and-braces around the emitter's own finalize (which is skipped when
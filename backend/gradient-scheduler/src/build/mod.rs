@@ -8,12 +8,14 @@
 //!
 //! Split by concern:
 //! - [`lifecycle`] - build output/completion/failure handling and retry policy
+//! - [`flaky`] - flaky-failure detection and targeted re-runs
 //! - [`self_heal`] - missing-input purge-and-requeue self-heal
 //!
 //! Waiting-state reconciliation ([`crate::waiting_state`]) and buildability
 //! checks ([`crate::buildability`]) live in their own top-level modules but are
 //! re-exported here so the public surface of `crate::build` is unchanged.
 
+mod flaky;
 mod lifecycle;
 mod self_heal;
 
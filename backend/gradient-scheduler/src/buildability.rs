@@ -257,11 +257,7 @@ impl BuildabilityChecker {
         available_architectures.sort_unstable();
         available_architectures.dedup();
 
-        WaitingReason::Workers {
-            unmet,
-            connected_workers: worker_caps.len() as u32,
-            available_architectures,
-        }
+        WaitingReason::workers(unmet, worker_caps.len() as u32, available_architectures)
     }
 }
 
@@ -275,6 +271,7 @@ mod tests {
                 unmet,
                 connected_workers,
                 available_architectures,
+                ..
             } => (unmet, *connected_workers, available_architectures),
             other => panic!("expected Workers variant, got {other:?}"),
         }
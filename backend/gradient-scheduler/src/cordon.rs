@@ -0,0 +1,220 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Per-registration cordon of connected workers.
+//!
+//! An organization cordons a worker through its registration - explicitly via
+//! `cordoned`, or on a timer via `maintenance_windows`. A cordoned worker is
+//! offered and assigned no new jobs of that organization; jobs it already runs
+//! finish. Once none are left the worker is drained for that organization.
+//! A worker registered by a single organization is thereby fully drained.
+//!
+//! A superuser cordons a worker for every organization at once - the only
+//! cordon a base worker has, since no organization owns its registration.
+//! That cordon lives in memory, like instance draining, and clears on the
+//! next server startup.
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use gradient_entity::worker_registration;
+use gradient_types::ids::OrganizationId;
+use gradient_types::worker_maintenance::{any_window_active, windows_from_json};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use tracing::info;
+
+use crate::Scheduler;
+
+/// Whether `reg` cordons its worker at `now`.
+pub fn registration_cordoned(reg: &worker_registration::Model, now: NaiveDateTime) -> bool {
+    reg.cordoned || any_window_active(&windows_from_json(reg.maintenance_windows.as_ref()), now)
+}
+
+/// Organizations among `regs` that cordon their worker at `now`.
+pub fn cordoned_orgs(
+    regs: &[worker_registration::Model],
+    now: NaiveDateTime,
+) -> HashSet<OrganizationId> {
+    regs.iter()
+        .filter(|reg| reg.active && registration_cordoned(reg, now))
+        .map(|reg| reg.peer_id)
+        .collect()
+}
+
+impl Scheduler {
+    /// Recompute the cordoning organizations of every connected worker from
+    /// their registrations at `now`. Returns the workers whose cordon changed.
+    /// Called on a timer so maintenance windows open and close on schedule;
+    /// manual cordon edits arrive sooner through the re-auth path.
+    pub async fn refresh_cordons(&self, now: NaiveDateTime) -> Result<Vec<String>> {
+        let connected: Vec<String> = self
+            .worker_pool
+            .read()
+            .await
+            .all_workers()
+            .into_iter()
+            .map(|w| w.id)
+            .collect();
+        if connected.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let regs = worker_registration::Entity::find()
+            .filter(worker_registration::Column::WorkerId.is_in(connected.clone()))
+            .filter(worker_registration::Column::Active.eq(true))
+            .all(&self.state.worker_db)
+            .await?;
+        let mut by_worker: HashMap<String, Vec<worker_registration::Model>> = HashMap::new();
+        for reg in regs {
+            by_worker
+                .entry(reg.worker_id.clone())
+                .or_default()
+                .push(reg);
+        }
+
+        let mut changed = Vec::new();
+        {
+            let mut pool = self.worker_pool.write().await;
+            for worker_id in connected {
+                let cordoned = by_worker
+                    .get(&worker_id)
+                    .map(|regs| cordoned_orgs(regs, now))
+                    .unwrap_or_default();
+                if pool.cordoned_by(&worker_id) == Some(&cordoned) {
+                    continue;
+                }
+                if pool.set_cordoned(&worker_id, cordoned) {
+                    changed.push(worker_id);
+                }
+            }
+        }
+
+        if !changed.is_empty() {
+            info!(workers = ?changed, "worker cordons changed");
+            // Uncordoned workers can take jobs again right away.
+            self.job_notify.send_modify(|g| *g = g.wrapping_add(1));
+            self.kick_dispatch();
+        }
+        Ok(changed)
+    }
+
+    /// Cordon (`true`) or uncordon (`false`) `worker_id` for every
+    /// organization. In-flight jobs finish; an uncordoned worker is offered
+    /// jobs again right away.
+    pub async fn set_worker_cordon(&self, worker_id: &str, cordoned: bool) {
+        let changed = self
+            .worker_pool
+            .write()
+            .await
+            .set_worker_cordoned(worker_id, cordoned);
+        if !changed {
+            return;
+        }
+        info!(%worker_id, cordoned, "worker cordon changed");
+        if !cordoned {
+            self.job_notify.send_modify(|g| *g = g.wrapping_add(1));
+            self.kick_dispatch();
+        }
+    }
+
+    /// Whether `worker_id` is cordoned for every organization.
+    pub async fn is_worker_cordoned(&self, worker_id: &str) -> bool {
+        self.worker_pool.read().await.is_worker_cordoned(worker_id)
+    }
+
+    /// Number of jobs of any organization currently running on `worker_id`.
+    pub async fn jobs_on_worker(&self, worker_id: &str) -> u32 {
+        self.job_tracker
+            .read()
+            .await
+            .active_jobs()
+            .filter(|(_, w, _)| *w == worker_id)
+            .count() as u32
+    }
+
+    /// Number of `org`'s jobs currently running on `worker_id`.
+    pub async fn org_jobs_on_worker(&self, worker_id: &str, org: OrganizationId) -> u32 {
+        self.job_tracker
+            .read()
+            .await
+            .active_jobs()
+            .filter(|(_, w, job)| *w == worker_id && job.org_id() == org)
+            .count() as u32
+    }
+
+    /// Organizations currently cordoning a connected worker; empty when the
+    /// worker is not connected.
+    pub async fn worker_cordoned_by(&self, worker_id: &str) -> HashSet<OrganizationId> {
+        self.worker_pool
+            .read()
+            .await
+            .cordoned_by(worker_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gradient_types::MaintenanceWindow;
+    use gradient_types::worker_maintenance::windows_to_json;
+
+    fn reg(org: OrganizationId) -> worker_registration::Model {
+        worker_registration::Model {
+            peer_id: org,
+            worker_id: "w1".into(),
+            active: true,
+            ..Default::default()
+        }
+    }
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn manual_cordon_and_open_window_cordon_their_org_only() {
+        let (a, b, c) = (
+            OrganizationId::now_v7(),
+            OrganizationId::now_v7(),
+            OrganizationId::now_v7(),
+        );
+        let manual = worker_registration::Model {
+            cordoned: true,
+            ..reg(a)
+        };
+        let windowed = worker_registration::Model {
+            maintenance_windows: windows_to_json(&[MaintenanceWindow {
+                schedule: "0 0 3 * * *".into(),
+                duration_secs: 3600,
+            }]),
+            ..reg(b)
+        };
+        let regs = [manual, windowed, reg(c)];
+
+        assert_eq!(
+            cordoned_orgs(&regs, at("2026-07-16 03:30:00")),
+            HashSet::from([a, b])
+        );
+        assert_eq!(
+            cordoned_orgs(&regs, at("2026-07-16 12:00:00")),
+            HashSet::from([a])
+        );
+    }
+
+    #[test]
+    fn inactive_registration_never_cordons() {
+        let org = OrganizationId::now_v7();
+        let inactive = worker_registration::Model {
+            cordoned: true,
+            active: false,
+            ..reg(org)
+        };
+        assert!(cordoned_orgs(&[inactive], gradient_types::now()).is_empty());
+    }
+}
@@ -0,0 +1,386 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Remaining critical-path precomputation over the `derivation_dependency`
+//! graph, consumed by `CriticalPathRule`.
+//!
+//! For every pending build of an evaluation, the remaining critical path is its
+//! own predicted duration plus the longest predicted chain of pending
+//! dependents above it, up to the evaluation's entry points. The longest such
+//! chain bounds how soon the evaluation can finish however many workers it
+//! gets, so starting the builds at its bottom first shortens wall time.
+//! Durations are the mean `build_time_ms` of past builds with the same `pname`;
+//! a derivation without history counts as the fleet's mean build.
+//!
+//! The graph side of that input (jobs, edges, predictions) is cached per
+//! evaluation in [`CriticalPathCache`] and reloaded only when the eval stream
+//! records new jobs or edges; each dispatch tick re-reads just which anchors
+//! are still pending.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use gradient_entity::build::BuildStatus;
+use gradient_score::CriticalPath;
+use gradient_types::*;
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter};
+use tokio::sync::RwLock;
+
+/// Duration assumed for a derivation without history when the fleet has no
+/// mean build time yet either.
+pub const FALLBACK_BUILD_MS: u64 = 60_000;
+
+/// Remaining critical-path time of every node: `durations[n]` plus the
+/// longest remaining path among the nodes depending on `n`. `edges` are
+/// `(dependent, dependency)` pairs; edges touching a node outside `durations`
+/// are ignored, and nodes on a (malformed) cycle keep their own duration.
+pub fn remaining_paths(
+    durations: &HashMap<DerivationId, u64>,
+    edges: &[(DerivationId, DerivationId)],
+) -> HashMap<DerivationId, u64> {
+    let mut dependencies: HashMap<DerivationId, Vec<DerivationId>> = HashMap::new();
+    let mut open_dependents: HashMap<DerivationId, usize> = HashMap::new();
+    for &(dependent, dependency) in edges {
+        if dependent == dependency
+            || !durations.contains_key(&dependent)
+            || !durations.contains_key(&dependency)
+        {
+            continue;
+        }
+        dependencies.entry(dependent).or_default().push(dependency);
+        *open_dependents.entry(dependency).or_default() += 1;
+    }
+
+    // Walk from the entry points (nothing pending depends on them) down to the
+    // leaves, settling a node once every dependent above it is settled.
+    let mut longest_above: HashMap<DerivationId, u64> = HashMap::new();
+    let mut remaining: HashMap<DerivationId, u64> = HashMap::with_capacity(durations.len());
+    let mut ready: VecDeque<DerivationId> = durations
+        .keys()
+        .filter(|n| !open_dependents.contains_key(n))
+        .copied()
+        .collect();
+    while let Some(node) = ready.pop_front() {
+        let path = durations[&node] + longest_above.get(&node).copied().unwrap_or(0);
+        remaining.insert(node, path);
+        for dependency in dependencies.get(&node).into_iter().flatten() {
+            let above = longest_above.entry(*dependency).or_default();
+            *above = (*above).max(path);
+            let open = open_dependents
+                .get_mut(dependency)
+                .expect("counted when the edge was recorded");
+            *open -= 1;
+            if *open == 0 {
+                ready.push_back(*dependency);
+            }
+        }
+    }
+
+    for (node, duration) in durations {
+        remaining.entry(*node).or_insert(*duration);
+    }
+    remaining
+}
+
+/// How long a cached [`EvalGraph`] is trusted without a graph change, so its
+/// duration predictions follow the build history.
+const GRAPH_TTL: Duration = Duration::from_secs(300);
+
+/// The part of an evaluation's critical-path input that only changes with its
+/// build graph: every anchor with its derivation, the edges among them, and
+/// the history-predicted duration of each derivation that has one.
+#[derive(Debug)]
+pub struct EvalGraph {
+    anchors: Vec<(DerivationBuildId, DerivationId)>,
+    edges: Vec<(DerivationId, DerivationId)>,
+    predicted_ms: HashMap<DerivationId, u64>,
+    loaded_at: Instant,
+}
+
+/// [`EvalGraph`]s kept across dispatch ticks. An entry is dropped when its
+/// evaluation's graph changes ([`Self::invalidate`]) or after [`GRAPH_TTL`];
+/// a pass only asks for the evaluations it enqueues builds for, so absence
+/// from one pass says nothing about an evaluation.
+#[derive(Debug, Default)]
+pub struct CriticalPathCache {
+    graphs: HashMap<EvaluationId, Arc<EvalGraph>>,
+    /// Bumped by every invalidation, so a graph loaded concurrently with one
+    /// is not stored.
+    generation: u64,
+}
+
+impl CriticalPathCache {
+    /// Forget `evaluation`'s graph after new build jobs or edges were recorded
+    /// for it.
+    pub fn invalidate(&mut self, evaluation: EvaluationId) {
+        self.graphs.remove(&evaluation);
+        self.generation += 1;
+    }
+
+    /// Cached graphs of `evaluations`, dropping every expired entry. Returns the hits, the evaluations to load, and the generation to pass
+    /// to [`Self::store`].
+    fn checkout(
+        &mut self,
+        evaluations: &[EvaluationId],
+    ) -> (Vec<Arc<EvalGraph>>, Vec<EvaluationId>, u64) {
+        let wanted: HashSet<EvaluationId> = evaluations.iter().copied().collect();
+        self.graphs.retain(|_, g| g.loaded_at.elapsed() < GRAPH_TTL);
+        let mut hits = Vec::new();
+        let mut misses = Vec::new();
+        for id in wanted {
+            match self.graphs.get(&id) {
+                Some(g) => hits.push(g.clone()),
+                None => misses.push(id),
+            }
+        }
+        (hits, misses, self.generation)
+    }
+
+    /// Keep freshly loaded graphs unless an invalidation ran since `generation`.
+    fn store(&mut self, generation: u64, graphs: &HashMap<EvaluationId, Arc<EvalGraph>>) {
+        if generation == self.generation {
+            self.graphs
+                .extend(graphs.iter().map(|(id, g)| (*id, g.clone())));
+        }
+    }
+}
+
+/// Load the [`EvalGraph`] of each of `evaluations`.
+async fn load_eval_graphs(
+    db: &impl ConnectionTrait,
+    evaluations: &[EvaluationId],
+) -> Result<HashMap<EvaluationId, Arc<EvalGraph>>, sea_orm::DbErr> {
+    let jobs = gradient_db::fetch_in_chunks(evaluations, |chunk| async move {
+        EBuildJob::find()
+            .filter(CBuildJob::Evaluation.is_in(chunk))
+            .all(db)
+            .await
+    })
+    .await?;
+    let mut by_eval: HashMap<EvaluationId, Vec<(DerivationBuildId, DerivationId)>> = HashMap::new();
+    for job in &jobs {
+        by_eval
+            .entry(job.evaluation)
+            .or_default()
+            .push((job.derivation_build, job.derivation));
+    }
+
+    let drv_ids: Vec<DerivationId> = jobs
+        .iter()
+        .map(|j| j.derivation)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let pnames: HashMap<DerivationId, String> =
+        gradient_db::fetch_in_chunks(&drv_ids, |chunk| async move {
+            EDerivation::find()
+                .filter(CDerivation::Id.is_in(chunk))
+                .all(db)
+                .await
+        })
+        .await?
+        .into_iter()
+        .filter_map(|d| Some((d.id, d.pname?)))
+        .collect();
+    let edges: Vec<(DerivationId, DerivationId)> =
+        gradient_db::fetch_in_chunks(&drv_ids, |chunk| async move {
+            EDerivationDependency::find()
+                .filter(CDerivationDependency::Derivation.is_in(chunk))
+                .all(db)
+                .await
+        })
+        .await?
+        .into_iter()
+        .map(|e| (e.derivation, e.dependency))
+        .collect();
+
+    let distinct: Vec<String> = pnames
+        .values()
+        .cloned()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let means = crate::history::mean_build_times(db, &distinct).await?;
+
+    let loaded_at = Instant::now();
+    Ok(evaluations
+        .iter()
+        .map(|id| {
+            let anchors = by_eval.remove(id).unwrap_or_default();
+            let drvs: HashSet<DerivationId> = anchors.iter().map(|(_, d)| *d).collect();
+            let predicted_ms = drvs
+                .iter()
+                .filter_map(|d| Some((*d, *means.get(pnames.get(d)?)?)))
+                .collect();
+            let edges = edges
+                .iter()
+                .filter(|(dependent, _)| drvs.contains(dependent))
+                .copied()
+                .collect();
+            let graph = EvalGraph {
+                anchors,
+                edges,
+                predicted_ms,
+                loaded_at,
+            };
+            (*id, Arc::new(graph))
+        })
+        .collect())
+}
+
+/// Critical-path position of every pending anchor of `evaluations`. An anchor
+/// shared by several evaluations keeps the position where it is most critical.
+/// Graphs come from `cache` where possible; only the pending set is read on
+/// every call.
+pub async fn load_critical_paths(
+    db: &impl ConnectionTrait,
+    cache: &RwLock<CriticalPathCache>,
+    evaluations: &[EvaluationId],
+    fallback_ms: u64,
+) -> Result<HashMap<DerivationBuildId, CriticalPath>, sea_orm::DbErr> {
+    let (mut graphs, misses, generation) = cache.write().await.checkout(evaluations);
+    if !misses.is_empty() {
+        let loaded = load_eval_graphs(db, &misses).await?;
+        cache.write().await.store(generation, &loaded);
+        graphs.extend(loaded.into_values());
+    }
+
+    let anchor_ids: Vec<DerivationBuildId> = graphs
+        .iter()
+        .flat_map(|g| g.anchors.iter().map(|(a, _)| *a))
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let pending: HashSet<DerivationBuildId> =
+        gradient_db::fetch_in_chunks(&anchor_ids, |chunk| async move {
+            EDerivationBuild::find()
+                .filter(CDerivationBuild::Id.is_in(chunk))
+                .filter(CDerivationBuild::Status.is_in(vec![
+                    BuildStatus::Created,
+                    BuildStatus::Queued,
+                    BuildStatus::Building,
+                    BuildStatus::FailedTransient,
+                ]))
+                .all(db)
+                .await
+        })
+        .await?
+        .into_iter()
+        .map(|a| a.id)
+        .collect();
+
+    let mut out: HashMap<DerivationBuildId, CriticalPath> = HashMap::new();
+    for graph in &graphs {
+        let anchors: Vec<&(DerivationBuildId, DerivationId)> = graph
+            .anchors
+            .iter()
+            .filter(|(a, _)| pending.contains(a))
+            .collect();
+        let durations: HashMap<DerivationId, u64> = anchors
+            .iter()
+            .map(|(_, d)| {
+                (
+                    *d,
+                    graph.predicted_ms.get(d).copied().unwrap_or(fallback_ms),
+                )
+            })
+            .collect();
+        let paths = remaining_paths(&durations, &graph.edges);
+        let eval_longest_ms = paths.values().copied().max().unwrap_or(0);
+        for (anchor, drv) in anchors {
+            let candidate = CriticalPath {
+                remaining_ms: paths.get(drv).copied().unwrap_or(0),
+                eval_longest_ms,
+            };
+            let entry = out.entry(*anchor).or_insert(candidate);
+            if candidate.share() > entry.share() {
+                *entry = candidate;
+            }
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(n: usize) -> Vec<DerivationId> {
+        (0..n).map(|_| DerivationId::now_v7()).collect()
+    }
+
+    /// `app` depends on a slow chain `lib <- compiler` and a quick `docs`:
+    /// the compiler heads the bottleneck chain even though `docs` has the
+    /// same direct-dependent count.
+    #[test]
+    fn longest_chain_of_dependents_is_summed() {
+        let n = ids(4);
+        let (app, lib, compiler, docs) = (n[0], n[1], n[2], n[3]);
+        let durations = HashMap::from([(app, 10), (lib, 100), (compiler, 1_000), (docs, 5)]);
+        let edges = [(app, lib), (lib, compiler), (app, docs)];
+        let paths = remaining_paths(&durations, &edges);
+        assert_eq!(paths[&app], 10);
+        assert_eq!(paths[&lib], 110);
+        assert_eq!(paths[&compiler], 1_110);
+        assert_eq!(paths[&docs], 15);
+    }
+
+    /// A shared dependency takes the longer of its dependents' paths.
+    #[test]
+    fn diamond_takes_the_longer_branch() {
+        let n = ids(4);
+        let (top, slow, fast, base) = (n[0], n[1], n[2], n[3]);
+        let durations = HashMap::from([(top, 1), (slow, 50), (fast, 5), (base, 10)]);
+        let edges = [(top, slow), (top, fast), (slow, base), (fast, base)];
+        let paths = remaining_paths(&durations, &edges);
+        assert_eq!(paths[&base], 61);
+        assert_eq!(paths[&fast], 6);
+    }
+
+    /// Edges to nodes outside the pending set (already built) are ignored, and
+    /// a malformed cycle does not drop its nodes.
+    #[test]
+    fn foreign_edges_and_cycles_are_tolerated() {
+        let n = ids(3);
+        let (a, b, built) = (n[0], n[1], n[2]);
+        let durations = HashMap::from([(a, 7), (b, 3)]);
+        let paths = remaining_paths(&durations, &[(a, b), (b, a), (a, built)]);
+        assert_eq!(paths[&a], 7);
+        assert_eq!(paths[&b], 3);
+    }
+
+    /// A graph loaded across an invalidation is not kept, and a pass that
+    /// does not ask for an evaluation leaves its graph cached.
+    #[test]
+    fn cache_drops_only_stale_graphs() {
+        let eval = EvaluationId::now_v7();
+        let graph = || {
+            let g = EvalGraph {
+                anchors: Vec::new(),
+                edges: Vec::new(),
+                predicted_ms: HashMap::new(),
+                loaded_at: Instant::now(),
+            };
+            HashMap::from([(eval, Arc::new(g))])
+        };
+        let mut cache = CriticalPathCache::default();
+
+        let (_, misses, generation) = cache.checkout(&[eval]);
+        assert_eq!(misses, vec![eval]);
+        cache.invalidate(eval);
+        cache.store(generation, &graph());
+        assert_eq!(cache.checkout(&[eval]).1, vec![eval]);
+
+        let (_, _, generation) = cache.checkout(&[eval]);
+        cache.store(generation, &graph());
+        let (hits, misses, _) = cache.checkout(&[eval]);
+        assert_eq!((hits.len(), misses.len()), (1, 0));
+
+        cache.checkout(&[EvaluationId::now_v7()]);
+        assert!(cache.graphs.contains_key(&eval));
+    }
+}
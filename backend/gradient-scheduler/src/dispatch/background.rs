@@ -4,9 +4,11 @@
  * SPDX-License-Identifier: AGPL-3.0-only
  */
 
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Duration;
 
+use gradient_types::ids::OrganizationId;
 use tracing::{debug, error, info, warn};
 
 use crate::Scheduler;
@@ -14,6 +16,149 @@ use crate::Scheduler;
 /// Poll ~3x per heartbeat deadline so worst-case detection latency is timeout + tick.
 const LIVENESS_POLLS_PER_DEADLINE: u64 = 3;
 
+/// How often maintenance windows are re-evaluated against the clock.
+const CORDON_REFRESH_SECS: u64 = 30;
+
+/// How often the scoring policy file's mtime is checked for hot-reload.
+const POLICY_RELOAD_POLL_SECS: u64 = 10;
+
+/// Hot-reload the declarative scoring policy file. Polls the mtime rather than
+/// watching the file so editor rename-over-write and config-management symlink
+/// swaps are both picked up. A reload that fails validation keeps the previous
+/// policy and only surfaces the errors on the board.
+pub(super) async fn scoring_policy_reload_loop(scheduler: Arc<Scheduler>) {
+    let eval = &scheduler.state.config.eval;
+    let Some(path) = eval.scheduler_scoring_policy_file.clone() else {
+        return;
+    };
+    let name = eval.scheduler_scoring_policy.clone();
+    let modified = |path: &str| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+
+    let mut last = modified(&path);
+    let mut interval = tokio::time::interval(Duration::from_secs(POLICY_RELOAD_POLL_SECS));
+    let cancel = scheduler.state.shutdown.token();
+    loop {
+        tokio::select! {
+            _ = cancel.cancelled() => return,
+            _ = interval.tick() => {}
+        }
+        let current = modified(&path);
+        if current == last {
+            continue;
+        }
+        last = current;
+        let previous = scheduler.policy.load_full();
+        let loaded = gradient_score::reload_policy(&previous, &name, Some(&path));
+        if loaded.status.errors.is_empty() {
+            info!(policy = %loaded.status.active, path, "reloaded scoring policy");
+        }
+        scheduler.policy.store(Arc::new(loaded));
+    }
+}
+
+/// Reload org quota limits and rolling build-minute usage into the tracker
+/// every dispatch tick. A failed load keeps the previous snapshot, so a DB
+/// blip neither lifts nor tightens limits.
+pub(super) async fn quota_refresh_loop(scheduler: Arc<Scheduler>) {
+    let mut interval = tokio::time::interval(Duration::from_secs(super::DISPATCH_TICK_SECS));
+    let cancel = scheduler.state.shutdown.token();
+    loop {
+        tokio::select! {
+            _ = cancel.cancelled() => return,
+            _ = interval.tick() => {}
+        }
+        match crate::quota::load_quota_snapshot(&scheduler.state.worker_db, gradient_types::now())
+            .await
+        {
+            Ok(snapshot) => scheduler.job_tracker.write().await.set_quotas(snapshot),
+            Err(e) => error!(error = %e, "failed to refresh organization quotas"),
+        }
+    }
+}
+
+/// Emit a scaling decision every `autoscale_interval_secs`; only spawned when
+/// a webhook or command is configured. A backend error is logged and retried next tick;
+/// the autoscaler's own drains have already been applied by then.
+pub(super) async fn autoscale_loop(scheduler: Arc<Scheduler>) {
+    let args = &scheduler.state.config.autoscale;
+    let mut autoscaler =
+        match crate::autoscale::Autoscaler::from_config(args, scheduler.state.http.clone()) {
+            Ok(Some(a)) => a,
+            Ok(None) => return,
+            Err(e) => {
+                error!(error = %e, "autoscaler disabled: invalid configuration");
+                return;
+            }
+        };
+    info!(
+        interval_secs = args.autoscale_interval_secs,
+        "autoscaler started"
+    );
+
+    let mut interval = tokio::time::interval(Duration::from_secs(args.autoscale_interval_secs));
+    let cancel = scheduler.state.shutdown.token();
+    loop {
+        tokio::select! {
+            _ = cancel.cancelled() => return,
+            _ = interval.tick() => {}
+        }
+        if let Err(e) = autoscaler.tick(&scheduler, gradient_types::now()).await {
+            warn!(error = %e, "autoscale backend failed");
+        }
+    }
+}
+
+/// Open and close maintenance windows on schedule and log once per cordon
+/// when a worker has finished the cordoning organization's in-flight jobs, or
+/// all of them for a worker-level cordon.
+pub(super) async fn cordon_refresh_loop(scheduler: Arc<Scheduler>) {
+    let mut drained: HashSet<(String, OrganizationId)> = HashSet::new();
+    let mut drained_workers: HashSet<String> = HashSet::new();
+    let mut interval = tokio::time::interval(Duration::from_secs(CORDON_REFRESH_SECS));
+    let cancel = scheduler.state.shutdown.token();
+    loop {
+        tokio::select! {
+            _ = cancel.cancelled() => return,
+            _ = interval.tick() => {}
+        }
+        if let Err(e) = scheduler.refresh_cordons(gradient_types::now()).await {
+            error!(error = %e, "failed to refresh worker cordons");
+            continue;
+        }
+
+        let workers = scheduler.worker_pool.read().await.all_workers();
+        let cordoned_workers: Vec<String> = workers
+            .iter()
+            .filter(|w| w.worker_cordoned)
+            .map(|w| w.id.clone())
+            .collect();
+        drained_workers.retain(|id| cordoned_workers.contains(id));
+        for worker_id in cordoned_workers {
+            if !drained_workers.contains(&worker_id)
+                && scheduler.jobs_on_worker(&worker_id).await == 0
+            {
+                info!(%worker_id, "cordoned worker drained");
+                drained_workers.insert(worker_id);
+            }
+        }
+
+        let cordons: Vec<(String, OrganizationId)> = workers
+            .into_iter()
+            .flat_map(|w| w.cordoned.into_iter().map(move |org| (w.id.clone(), org)))
+            .collect();
+        drained.retain(|key| cordons.contains(key));
+        for (worker_id, org) in cordons {
+            if drained.contains(&(worker_id.clone(), org)) {
+                continue;
+            }
+            if scheduler.org_jobs_on_worker(&worker_id, org).await == 0 {
+                info!(%worker_id, %org, "cordoned worker drained");
+                drained.insert((worker_id, org));
+            }
+        }
+    }
+}
+
 /// Periodic read-only invariant check: counts stale gate flags, unpromoted-ready
 /// anchors, unbacked trusted outputs, and wedged Building evals so a dead zone
 /// becomes a warning long before a user reports a stuck evaluation. Transient
@@ -132,6 +132,8 @@ struct BuildDispatchMaps {
     evaluations: HashMap<EvaluationId, MEvaluation>,
     /// project_id → organization_id
     projects: HashMap<ProjectId, OrganizationId>,
+    /// project_id → the project's build-limit settings.
+    project_limits: HashMap<ProjectId, ProjectLimits>,
     features_by_drv: HashMap<DerivationId, Vec<FeatureId>>,
     feature_names: HashMap<FeatureId, String>,
     /// derivation_id → number of direct dependencies
@@ -158,10 +160,27 @@ struct BuildDispatchMaps {
     /// derivation_build → the evaluation driving this anchor's dispatch (used for
     /// peer routing and `build_job` attribution on win). Prefers a non-terminal eval.
     driving_eval: HashMap<DerivationBuildId, EvaluationId>,
+    /// derivation_build → remaining critical path within its driving
+    /// evaluation. Empty unless the scoring policy consumes it.
+    critical_paths: HashMap<DerivationBuildId, gradient_score::CriticalPath>,
+    /// evaluation → merged project/trigger worker selector. Absent ⇒ none.
+    worker_selectors: HashMap<EvaluationId, Labels>,
+    /// derivation_build → worker whose flaky failure this dispatch re-runs.
+    /// Absent ⇒ not a flaky re-run.
+    flaky_reruns: HashMap<DerivationBuildId, String>,
     connected_architectures: HashSet<String>,
     config: DispatchConfig,
 }
 
+/// A project's stored build limits. `None` falls back to the server default
+/// (no cap for `max_output_size`); `Some(0)` means no limit.
+#[derive(Debug, Clone, Copy, Default)]
+struct ProjectLimits {
+    timeout_secs: Option<i64>,
+    max_silent_secs: Option<i64>,
+    max_output_size: Option<i64>,
+}
+
 /// The scalar dispatch knobs, split from the per-pass lookup maps.
 struct DispatchConfig {
     substitute_miss_escalation_threshold: i64,
@@ -201,6 +220,7 @@ impl BuildDispatchMaps {
 
         let db = &state.worker_db;
         let substitute_misses = gradient_db::substitute_miss_counts(db, &anchor_ids).await?;
+        let flaky_reruns = gradient_db::flaky_rerun_avoid_workers(db, &anchor_ids).await?;
 
         // Resolve the eval driving each anchor's dispatch: any referencing
         // build_job, preferring one whose evaluation is not terminal. The driving
@@ -271,17 +291,36 @@ impl BuildDispatchMaps {
             .collect::<std::collections::HashSet<_>>()
             .into_iter()
             .collect();
-        let projects: HashMap<ProjectId, OrganizationId> =
-            gradient_db::fetch_in_chunks(&project_ids, |chunk| async move {
-                EProject::find()
-                    .filter(CProject::Id.is_in(chunk))
-                    .all(db)
-                    .await
-            })
-            .await?
-            .into_iter()
+        let project_rows = gradient_db::fetch_in_chunks(&project_ids, |chunk| async move {
+            EProject::find()
+                .filter(CProject::Id.is_in(chunk))
+                .all(db)
+                .await
+        })
+        .await?;
+        let projects: HashMap<ProjectId, OrganizationId> = project_rows
+            .iter()
             .map(|p| (p.id, p.organization))
             .collect();
+        let project_limits: HashMap<ProjectId, ProjectLimits> = project_rows
+            .iter()
+            .map(|p| {
+                (
+                    p.id,
+                    ProjectLimits {
+                        timeout_secs: p.build_timeout_secs,
+                        max_silent_secs: p.build_max_silent_secs,
+                        max_output_size: p.max_output_size,
+                    },
+                )
+            })
+            .collect();
+        let worker_selectors: HashMap<EvaluationId, Labels> =
+            crate::worker_selector::load_eval_selectors(db, evaluations.values())
+                .await?
+                .into_iter()
+                .map(|(eval, (_, selector))| (eval, selector))
+                .collect();
 
         // Required features: per-derivation list of feature names.
         let feature_edges = gradient_db::fetch_in_chunks(&drv_ids, |chunk| async move {
@@ -438,6 +477,7 @@ impl BuildDispatchMaps {
             derivations,
             evaluations,
             projects,
+            project_limits,
             features_by_drv,
             feature_names,
             dep_counts,
@@ -447,6 +487,9 @@ impl BuildDispatchMaps {
             histories,
             substitute_misses,
             driving_eval,
+            critical_paths: HashMap::new(),
+            worker_selectors,
+            flaky_reruns,
             connected_architectures,
             config: DispatchConfig::from_state(state),
         })
@@ -459,6 +502,15 @@ impl BuildDispatchMaps {
             .and_then(|pid| self.projects.get(&pid).copied())
     }
 
+    /// Build limits of the project owning `eval_id`; defaults when unknown.
+    fn project_limits(&self, eval_id: EvaluationId) -> ProjectLimits {
+        self.evaluations
+            .get(&eval_id)
+            .and_then(|e| e.project)
+            .and_then(|pid| self.project_limits.get(&pid).copied())
+            .unwrap_or_default()
+    }
+
     /// Return the required Nix system features for `derivation_id`.
     fn required_features(&self, derivation_id: DerivationId) -> Vec<String> {
         self.features_by_drv
@@ -542,6 +594,7 @@ impl BuildDispatchMaps {
         } else {
             Vec::new()
         };
+        let limits = self.project_limits(eval_id);
         let build_job = BuildJob {
             builds: vec![BuildTask {
                 build_id: anchor.id.to_string(),
@@ -549,11 +602,15 @@ impl BuildDispatchMaps {
                 external_cached: substitute,
                 is_fixed_output: derivation.is_fixed_output,
                 outputs,
-                timeout_secs: resolve_limit(anchor.timeout_secs, self.config.default_timeout_secs),
+                timeout_secs: resolve_limit(
+                    anchor.timeout_secs,
+                    resolve_limit(limits.timeout_secs, self.config.default_timeout_secs),
+                ),
                 max_silent_secs: resolve_limit(
                     anchor.max_silent_secs,
-                    self.config.default_max_silent_secs,
+                    resolve_limit(limits.max_silent_secs, self.config.default_max_silent_secs),
                 ),
+                max_output_size: resolve_limit(limits.max_output_size, None),
             }],
         };
         let (architecture, required_features) = if substitute {
@@ -606,8 +663,15 @@ impl BuildDispatchMaps {
             queued_at: anchor.updated_at,
             ready_at: now(),
             rescore_count: 0,
+            critical_path: self.critical_paths.get(&anchor.id).copied(),
+            worker_selector: self
+                .worker_selectors
+                .get(&eval_id)
+                .cloned()
+                .unwrap_or_default(),
             pname: derivation.pname.clone(),
             substitute,
+            avoid_worker: self.flaky_reruns.get(&anchor.id).cloned(),
         };
 
         (job_id, pending)
@@ -760,14 +824,49 @@ pub(crate) async fn dispatch_ready_builds(scheduler: &Scheduler) -> anyhow::Resu
         .flat_map(|w| w.architectures)
         .collect();
 
-    let maps = BuildDispatchMaps::load(
+    let mut maps = BuildDispatchMaps::load(
         state,
         &new_anchors,
-        scheduler.policy.uses_history(),
+        scheduler.scoring_policy().uses_history(),
         connected_architectures,
     )
     .await?;
 
+    if scheduler.scoring_policy().uses_critical_path() {
+        let evaluations: Vec<EvaluationId> = maps
+            .driving_eval
+            .values()
+            .copied()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let fallback_ms = scheduler
+            .instance
+            .load()
+            .build_time_ms
+            .w24h_or(crate::critical_path::FALLBACK_BUILD_MS as f64)
+            as u64;
+        match crate::critical_path::load_critical_paths(
+            db,
+            &scheduler.critical_paths,
+            &evaluations,
+            fallback_ms,
+        )
+        .await
+        {
+            Ok(paths) => {
+                // Builds enqueued on earlier ticks shift as their siblings finish.
+                scheduler
+                    .job_tracker
+                    .write()
+                    .await
+                    .set_critical_paths(&paths);
+                maps.critical_paths = paths;
+            }
+            Err(e) => error!(error = %e, "failed to load critical paths"),
+        }
+    }
+
     let mut enqueued = 0usize;
     for anchor in new_anchors {
         match maps.classify_dispatch(&anchor) {
@@ -796,7 +895,9 @@ fn nonzero(v: u64) -> Option<u64> {
     (v != 0).then_some(v)
 }
 
-/// Per-derivation limit takes precedence over the server default. A stored `0` means "no limit".
+/// A stored limit takes precedence over `default`. A stored `0` means "no limit".
+/// Applied twice for timeouts: the project setting over the server default,
+/// then the derivation's own (`timeout`/`meta.timeout`) over that.
 fn resolve_limit(stored: Option<i64>, default: Option<u64>) -> Option<u64> {
     match stored {
         Some(0) => None,
@@ -842,4 +943,21 @@ mod limit_tests {
         assert_eq!(resolve_limit(None, Some(3600)), Some(3600));
         assert_eq!(resolve_limit(None, None), None);
     }
+
+    #[test]
+    fn project_limit_sits_between_derivation_and_server_default() {
+        let server = Some(14400);
+        // Project setting replaces the server default...
+        assert_eq!(
+            resolve_limit(None, resolve_limit(Some(600), server)),
+            Some(600)
+        );
+        // ...a derivation's own limit still wins...
+        assert_eq!(
+            resolve_limit(Some(60), resolve_limit(Some(600), server)),
+            Some(60)
+        );
+        // ...and a project `0` lifts the server default.
+        assert_eq!(resolve_limit(None, resolve_limit(Some(0), server)), None);
+    }
 }
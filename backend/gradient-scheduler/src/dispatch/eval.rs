@@ -10,6 +10,7 @@ use std::sync::atomic::Ordering;
 use std::time::Duration;
 
 use gradient_core::ServerState;
+use gradient_db::update_evaluation_status;
 use gradient_entity::evaluation::EvaluationStatus;
 use gradient_types::input::vec_to_hex;
 use gradient_types::wildcard::Wildcard;
@@ -68,6 +69,10 @@ pub(crate) async fn dispatch_queued_evals(scheduler: &Scheduler) -> anyhow::Resu
     }
 
     let maps = EvalDispatchMaps::load(state, &evals).await?;
+    let mut selectors =
+        crate::worker_selector::load_eval_selectors(&state.worker_db, &evals).await?;
+    let quota_gate = scheduler.job_tracker.read().await.quota_gate();
+    let mut enqueued_per_org: HashMap<OrganizationId, u64> = HashMap::new();
     let split_fetch = scheduler
         .worker_pool
         .read()
@@ -130,6 +135,16 @@ pub(crate) async fn dispatch_queued_evals(scheduler: &Scheduler) -> anyhow::Resu
             continue;
         };
 
+        // An org at `max_concurrent_evaluations` parks its surplus evals
+        // instead of queueing them; the waiting-state reconciler re-queues
+        // them oldest-first as slots free up.
+        let enqueued = enqueued_per_org.entry(org_id).or_default();
+        if let Some(reason) = quota_gate.holds_evaluation(org_id, *enqueued) {
+            park_on_evaluation_quota(scheduler, eval, reason).await;
+            continue;
+        }
+        *enqueued += 1;
+
         let history = eval_history.get(&project_id).copied().unwrap_or_default();
 
         let pending = PendingEvalJob {
@@ -144,6 +159,10 @@ pub(crate) async fn dispatch_queued_evals(scheduler: &Scheduler) -> anyhow::Resu
             ready_at: eval.updated_at,
             rescore_count: 0,
             history,
+            worker_selector: selectors
+                .remove(&eval.id)
+                .map(|(_, selector)| selector)
+                .unwrap_or_default(),
         };
 
         scheduler.enqueue_eval_job(job_id.clone(), pending).await;
@@ -153,6 +172,18 @@ pub(crate) async fn dispatch_queued_evals(scheduler: &Scheduler) -> anyhow::Resu
     Ok(())
 }
 
+async fn park_on_evaluation_quota(scheduler: &Scheduler, eval: MEvaluation, reason: WaitingReason) {
+    info!(evaluation_id = %eval.id, "parking evaluation: organization at evaluation quota");
+    crate::waiting_state::persist_waiting_reason(
+        &scheduler.state,
+        eval.id,
+        &eval.waiting_reason,
+        Some(&reason),
+    )
+    .await;
+    update_evaluation_status(&scheduler.state.db(), eval, EvaluationStatus::Waiting).await;
+}
+
 /// Every per-eval row a dispatch pass needs, loaded in one IN-list query per
 /// table instead of a round-trip per queued evaluation.
 struct EvalDispatchMaps {
@@ -49,4 +49,14 @@ pub fn start_dispatch_loops(scheduler: Arc<Scheduler>) {
     shutdown.spawn(async move { background::worker_liveness_loop(s6).await });
     let s7 = Arc::clone(&scheduler);
     shutdown.spawn(async move { background::consistency_sweep_loop(s7).await });
+    let s8 = Arc::clone(&scheduler);
+    shutdown.spawn(async move { background::scoring_policy_reload_loop(s8).await });
+    let s9 = Arc::clone(&scheduler);
+    shutdown.spawn(async move { background::quota_refresh_loop(s9).await });
+    if scheduler.state.config.autoscale.enabled() {
+        let s10 = Arc::clone(&scheduler);
+        shutdown.spawn(async move { background::autoscale_loop(s10).await });
+    }
+    let s11 = Arc::clone(&scheduler);
+    shutdown.spawn(async move { background::cordon_refresh_loop(s11).await });
 }
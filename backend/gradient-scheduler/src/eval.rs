@@ -68,6 +68,12 @@ impl DerivationInsertBatch {
                     prefer_local_build: d.prefer_local_build,
                     is_fixed_output: d.is_fixed_output,
                     allow_substitutes: d.allow_substitutes,
+                    realisation_hash: d.floating_outputs.as_ref().map(|f| f.drv_hash.clone()),
+                    floating_outputs: d
+                        .floating_outputs
+                        .as_ref()
+                        .map(|f| f.outputs.clone())
+                        .unwrap_or_default(),
                     created_at: now,
                     ..Default::default()
                 }
@@ -150,6 +156,8 @@ struct EvalResultProcessor<'a> {
     state: &'a Arc<ServerState>,
     evaluation_id: EvaluationId,
     evaluation: MEvaluation,
+    /// Organization the evaluation runs for; only its realisations apply.
+    organization: OrganizationId,
 }
 
 impl<'a> EvalResultProcessor<'a> {
@@ -157,11 +165,13 @@ impl<'a> EvalResultProcessor<'a> {
         state: &'a Arc<ServerState>,
         evaluation_id: EvaluationId,
         evaluation: MEvaluation,
+        organization: OrganizationId,
     ) -> Self {
         Self {
             state,
             evaluation_id,
             evaluation,
+            organization,
         }
     }
 
@@ -279,11 +289,30 @@ impl<'a> EvalResultProcessor<'a> {
             .into_iter()
             .collect();
 
-        let truly_substituted = self.compute_truly_substituted(&all_drv_ids).await?;
+        // Floating CA outputs only get `derivation_output` rows from the
+        // organization's recorded realisations; a derivation still missing one
+        // cannot be substituted.
+        let unrealised = gradient_db::realise_floating_outputs(
+            &self.state.worker_db,
+            self.organization,
+            &all_drv_ids,
+        )
+        .await
+        .unwrap_or_else(|e| {
+            error!(error = %e, "failed to apply realisations");
+            derivations
+                .iter()
+                .filter(|d| d.floating_outputs.is_some())
+                .filter_map(|d| drv_path_to_id.get(&d.drv_path).copied())
+                .collect()
+        });
+
+        let mut truly_substituted = self.compute_truly_substituted(&all_drv_ids).await?;
+        truly_substituted.retain(|id| !unrealised.contains(id));
         let not_substituted: Vec<DerivationId> = all_drv_ids
             .iter()
             .copied()
-            .filter(|id| !truly_substituted.contains(id))
+            .filter(|id| !truly_substituted.contains(id) && !unrealised.contains(id))
             .collect();
         let upstream_substitutable = self
             .compute_upstream_substitutable(&not_substituted)
@@ -309,7 +338,10 @@ impl<'a> EvalResultProcessor<'a> {
             } else if upstream_substitutable.contains(&drv_id) {
                 (BuildStatus::Created, true)
             } else {
-                (BuildStatus::Created, d.substituted)
+                (
+                    BuildStatus::Created,
+                    d.substituted && !unrealised.contains(&drv_id),
+                )
             };
 
             anchors.push(
@@ -333,11 +365,16 @@ impl<'a> EvalResultProcessor<'a> {
             );
         }
 
+        // An existing anchor keeps its state but takes this evaluation's build
+        // limits: `meta.timeout` may have changed without touching the `.drv`.
         for chunk in anchors.chunks(BATCH_SIZE) {
             let res = EDerivationBuild::insert_many(chunk.to_vec())
                 .on_conflict(
                     sea_orm::sea_query::OnConflict::column(CDerivationBuild::Derivation)
-                        .do_nothing()
+                        .update_columns([
+                            CDerivationBuild::TimeoutSecs,
+                            CDerivationBuild::MaxSilentSecs,
+                        ])
                         .to_owned(),
                 )
                 .exec(&self.state.worker_db)
@@ -762,6 +799,8 @@ impl<'a> EvalResultProcessor<'a> {
                         derivation: drv_id,
                         eval: d.attr.clone(),
                         created_at: now,
+                        aggregate: d.constituents.is_some(),
+                        pending_constituents: d.constituents.clone().unwrap_or_default(),
                         ..Default::default()
                     }
                     .into_active_model(),
@@ -818,6 +857,9 @@ pub async fn handle_eval_result(
         for dep in &mut d.dependencies {
             *dep = strip_nix_store_prefix(dep);
         }
+        for constituent in d.constituents.iter_mut().flatten() {
+            *constituent = strip_nix_store_prefix(constituent);
+        }
     }
 
     let evaluation_id = job.evaluation_id;
@@ -844,7 +886,7 @@ pub async fn handle_eval_result(
         "processing eval result from worker",
     );
 
-    let proc = EvalResultProcessor::new(state, evaluation_id, evaluation);
+    let proc = EvalResultProcessor::new(state, evaluation_id, evaluation, job.org_id);
 
     let existing = proc.load_existing_derivations(&derivations).await?;
     let batch = DerivationInsertBatch::prepare(&derivations, &existing);
@@ -1227,6 +1269,121 @@ pub async fn flush_deferred_deps(
     Ok(())
 }
 
+/// Link each aggregate entry point of the evaluation to its constituents'
+/// derivations, then re-announce the aggregates so their forge checks show the
+/// combined status. A constituent may stream in a later batch than its
+/// aggregate, so the drv paths wait in `entry_point.pending_constituents` and
+/// are linked here, once at stream completion when every constituent has a
+/// row; a constituent without one - unresolved by the worker, or never
+/// recorded - is counted in `entry_point.unresolved_constituents`, which fails
+/// the aggregate.
+pub async fn record_aggregate_constituents(
+    state: &Arc<ServerState>,
+    evaluation_id: EvaluationId,
+) -> Result<()> {
+    let db = &state.worker_db;
+    let pending: Vec<MEntryPoint> = EEntryPoint::find()
+        .filter(CEntryPoint::Evaluation.eq(evaluation_id))
+        .filter(CEntryPoint::Aggregate.eq(true))
+        .filter(sea_orm::sea_query::Expr::cust(
+            "cardinality(pending_constituents) > 0",
+        ))
+        .all(db)
+        .await
+        .context("record_aggregate_constituents: query entry points")?;
+    if pending.is_empty() {
+        return Ok(());
+    }
+
+    let hashes: Vec<String> = pending
+        .iter()
+        .flat_map(|ep| &ep.pending_constituents)
+        .filter_map(|p| parse_drv_hash_name(p).ok().map(|(h, _)| h))
+        .collect::<std::collections::HashSet<_>>()
+        .into_iter()
+        .collect();
+    let drv_path_to_id: HashMap<String, DerivationId> =
+        gradient_db::fetch_in_chunks(&hashes, |chunk| async move {
+            EDerivation::find()
+                .filter(CDerivation::Hash.is_in(chunk))
+                .all(db)
+                .await
+        })
+        .await
+        .context("record_aggregate_constituents: query derivations")?
+        .into_iter()
+        .map(|d| (d.drv_path(), d.id))
+        .collect();
+
+    let mut rows: Vec<AEntryPointConstituent> = Vec::new();
+    let mut unresolved: HashMap<EntryPointId, i32> = HashMap::new();
+    for ep in &pending {
+        let entry_point = ep.id;
+        let aggregate = &ep.eval;
+        for constituent in &ep.pending_constituents {
+            match drv_path_to_id.get(constituent) {
+                Some(&derivation) => rows.push(
+                    MEntryPointConstituent {
+                        id: EntryPointConstituentId::now_v7(),
+                        entry_point,
+                        derivation,
+                    }
+                    .into_active_model(),
+                ),
+                None => {
+                    warn!(%evaluation_id, %aggregate, %constituent, "aggregate constituent has no derivation row; failing the aggregate");
+                    *unresolved.entry(entry_point).or_default() += 1;
+                }
+            }
+        }
+    }
+
+    for chunk in rows.chunks(BATCH_SIZE) {
+        EEntryPointConstituent::insert_many(chunk.to_vec())
+            .on_conflict(
+                sea_orm::sea_query::OnConflict::columns([
+                    CEntryPointConstituent::EntryPoint,
+                    CEntryPointConstituent::Derivation,
+                ])
+                .do_nothing()
+                .to_owned(),
+            )
+            .do_nothing()
+            .exec(db)
+            .await
+            .context("record_aggregate_constituents: insert")?;
+    }
+
+    for ep in &pending {
+        let count = unresolved.get(&ep.id).copied().unwrap_or_default();
+        EEntryPoint::update_many()
+            .col_expr(
+                CEntryPoint::UnresolvedConstituents,
+                sea_orm::sea_query::Expr::value(count),
+            )
+            .col_expr(
+                CEntryPoint::PendingConstituents,
+                sea_orm::sea_query::Expr::value(Vec::<String>::new()),
+            )
+            .filter(CEntryPoint::Id.eq(ep.id))
+            .exec(db)
+            .await
+            .context("record_aggregate_constituents: record unresolved")?;
+    }
+
+    info!(
+        %evaluation_id,
+        aggregates = pending.len(),
+        linked = rows.len(),
+        unresolved = unresolved.values().sum::<i32>(),
+        "recorded aggregate constituents"
+    );
+
+    let aggregate_drvs: Vec<DerivationId> = pending.iter().map(|ep| ep.derivation).collect();
+    gradient_db::announce_entry_point_statuses(&state.db(), evaluation_id, &aggregate_drvs).await;
+    Ok(())
+}
+
 /// Resolve deferred `(src, [dep])` drv-path pairs against the recorded
 /// derivations. Returns the resolvable edges, the sources whose every dep
 /// resolved, and the sources with at least one unresolved dep (a dependency the
@@ -1467,6 +1624,8 @@ mod incremental_edge_flush_tests {
             allow_substitutes: true,
             pname: None,
             substituted: false,
+            constituents: None,
+            floating_outputs: None,
         }
     }
 
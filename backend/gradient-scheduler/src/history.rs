@@ -6,8 +6,13 @@
 
 //! Resource-usage predictions derived from historical `derivation_metric` rows.
 
+use std::collections::HashMap;
+
 use gradient_types::{CDerivationMetric, EDerivationMetric, MDerivationMetric};
-use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DbBackend, EntityTrait, FromQueryResult, QueryFilter, QueryOrder,
+    QuerySelect, Statement,
+};
 
 /// Most recent rows considered when predicting; bounds query cost.
 const HISTORY_WINDOW: u64 = 200;
@@ -62,6 +67,50 @@ pub async fn predict(
     summarize(&rows)
 }
 
+#[derive(FromQueryResult)]
+struct MeanBuildTime {
+    pname: String,
+    build_time_ms: i64,
+}
+
+/// Mean recorded `build_time_ms` per `pname`, over each name's most recent
+/// [`HISTORY_WINDOW`] timed builds, in one query for a whole batch of names.
+/// Names without a timed build are absent.
+pub async fn mean_build_times(
+    db: &impl ConnectionTrait,
+    pnames: &[String],
+) -> Result<HashMap<String, u64>, sea_orm::DbErr> {
+    if pnames.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let sql = format!(
+        r#"
+        SELECT pname, AVG(build_time_ms)::int8 AS build_time_ms
+        FROM (
+            SELECT pname, build_time_ms,
+                   ROW_NUMBER() OVER (PARTITION BY pname ORDER BY created_at DESC) AS rn
+            FROM derivation_metric
+            WHERE pname = ANY($1) AND build_time_ms IS NOT NULL
+        ) recent
+        WHERE rn <= {HISTORY_WINDOW}
+        GROUP BY pname
+        "#
+    );
+    let rows = MeanBuildTime::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        &sql,
+        [pnames.to_vec().into()],
+    ))
+    .all(db)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| (r.pname, r.build_time_ms.max(0) as u64))
+        .collect())
+}
+
 fn summarize(rows: &[MDerivationMetric]) -> gradient_score::HistoryPrediction {
     if rows.is_empty() {
         return gradient_score::HistoryPrediction::default();
@@ -106,17 +106,14 @@ impl Scheduler {
             .map(|j| j.org_id())
     }
 
-    /// Fetch the peer auth filter and capabilities for a worker from the pool.
+    /// Fetch the peer auth filter (minus cordoning orgs) and capabilities for
+    /// a worker from the pool.
     pub(super) async fn worker_auth_and_caps(
         &self,
         worker_id: &str,
     ) -> (Option<HashSet<OrganizationId>>, Option<WorkerCaps>) {
         let pool = self.worker_pool.read().await;
-        let authorized = pool
-            .peer_auth_for(worker_id)
-            .and_then(|a| a.as_filter())
-            .cloned();
-        (authorized, pool.worker_caps(worker_id))
+        (pool.offer_filter(worker_id), pool.worker_caps(worker_id))
     }
 
     /// Atomically take the best matching job from the tracker and record the
@@ -128,7 +125,7 @@ impl Scheduler {
         caps: Option<&WorkerCaps>,
         kind: &JobKind,
     ) -> Option<Assignment> {
-        let policy = Arc::clone(&self.policy);
+        let policy = self.scoring_policy();
         let instance = self.instance.load_full();
         let mut assignment = self
             .job_tracker
@@ -210,6 +207,7 @@ async fn persist_dispatched_job(state: &Arc<ServerState>, worker_id: &str, rec:
             derivation_build,
             dispatched_job_id,
             rec.substitute,
+            rec.flaky_rerun,
             rec.build_context.clone(),
         )
         .await
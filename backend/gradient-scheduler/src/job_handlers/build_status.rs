@@ -142,17 +142,26 @@ impl Scheduler {
                 // row: flush the dependency edges still pending after the
                 // incremental per-batch flushes so the graph is complete for
                 // promotion + dispatch.
-                let edges = self
+                let acc = self
                     .eval_edges
                     .write()
                     .await
                     .remove(&j.evaluation_id)
-                    .unwrap_or_default()
-                    .into_pending();
+                    .unwrap_or_default();
+                let edges = acc.into_pending();
                 if let Err(e) = eval::flush_deferred_deps(&self.state, j.evaluation_id, edges).await
                 {
                     error!(error = %e, evaluation_id = %j.evaluation_id, "flush_deferred_deps failed");
                 }
+                self.critical_paths
+                    .write()
+                    .await
+                    .invalidate(j.evaluation_id);
+                if let Err(e) =
+                    eval::record_aggregate_constituents(&self.state, j.evaluation_id).await
+                {
+                    error!(error = %e, evaluation_id = %j.evaluation_id, "record_aggregate_constituents failed");
+                }
                 let r = eval::handle_eval_job_completed(&self.state, j.evaluation_id).await;
                 if worker_idle {
                     self.kick_dispatch();
@@ -191,6 +200,10 @@ impl Scheduler {
         match job {
             Some(PendingJob::Eval(j)) => {
                 self.eval_edges.write().await.remove(&j.evaluation_id);
+                self.critical_paths
+                    .write()
+                    .await
+                    .invalidate(j.evaluation_id);
                 let r = eval::handle_eval_job_failed(
                     &self.state,
                     j.evaluation_id,
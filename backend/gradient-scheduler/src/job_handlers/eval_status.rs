@@ -219,6 +219,9 @@ impl Scheduler {
             for dep in &mut d.dependencies {
                 *dep = strip_nix_store_prefix(dep);
             }
+            for constituent in d.constituents.iter_mut().flatten() {
+                *constituent = strip_nix_store_prefix(constituent);
+            }
         }
 
         // Accumulate this batch's dependency edges. Whatever is fully
@@ -242,6 +245,11 @@ impl Scheduler {
                 warn!(error = %e, evaluation_id = %job.evaluation_id, "incremental edge flush failed; deferring to completion flush");
             }
         }
+        // The batch added build jobs and edges to the evaluation's graph.
+        self.critical_paths
+            .write()
+            .await
+            .invalidate(job.evaluation_id);
 
         Ok(())
     }
@@ -16,9 +16,12 @@ use gradient_types::proto::{
     BuildJob, CandidateScore, FlakeJob, FlakeSource, FlakeTask, Job, JobCandidate, JobKind,
     RequiredPath,
 };
+use gradient_types::{Labels, WaitingReason, WorkerLabels};
 
 use gradient_score::{JobContext, ScoredJob, ScoringPolicy, WorkerContext};
 
+use crate::quota::{OrgActivity, QuotaGate, QuotaSnapshot};
+
 #[derive(Debug, Clone)]
 pub struct PendingEvalJob {
     pub evaluation_id: EvaluationId,
@@ -41,6 +44,9 @@ pub struct PendingEvalJob {
     pub rescore_count: u32,
     /// Per-project predicted peak eval RSS, fed into `ResourceFitRule`.
     pub history: gradient_score::HistoryPrediction,
+    /// Merged project and trigger worker selector; workers whose labels don't
+    /// satisfy it are vetoed for this evaluation.
+    pub worker_selector: Labels,
 }
 
 impl PendingEvalJob {
@@ -88,6 +94,12 @@ pub struct PendingBuildJob {
     /// Historical resource-usage prediction for this build's derivation,
     /// preloaded once per dispatch round and consumed by scoring rules.
     pub history: gradient_score::HistoryPrediction,
+    /// Position on the driving evaluation's remaining critical path. Only
+    /// precomputed when the active scoring policy consumes it.
+    pub critical_path: Option<gradient_score::CriticalPath>,
+    /// Labels a worker must carry to take this build, inherited from the
+    /// driving evaluation's project and trigger. Empty matches any worker.
+    pub worker_selector: Labels,
     /// `build.updated_at` at the time this job was dispatched to the tracker.
     /// Used by the scoring policy to prefer builds that have waited longer.
     pub queued_at: chrono::NaiveDateTime,
@@ -102,6 +114,21 @@ pub struct PendingBuildJob {
     /// True when the build's output is already available from cache; the job can
     /// run on any worker regardless of architecture.
     pub substitute: bool,
+    /// Set on the re-run of a flaky failure: the worker that failed it, which
+    /// the re-run avoids for [`FLAKY_AVOID_MAX_TICKS`] dispatch ticks.
+    pub avoid_worker: Option<String>,
+}
+
+/// Dispatch ticks a flaky re-run waits for a different worker before it may
+/// run on the worker that failed it, so a single-worker fleet still re-runs.
+pub const FLAKY_AVOID_MAX_TICKS: u32 = 12;
+
+impl PendingBuildJob {
+    /// Whether this job should not be assigned to `worker_id` yet.
+    pub fn avoids(&self, worker_id: &str) -> bool {
+        self.avoid_worker.as_deref() == Some(worker_id)
+            && self.rescore_count < FLAKY_AVOID_MAX_TICKS
+    }
 }
 
 /// A connected worker's capabilities, used to gate which jobs are eligible
@@ -119,6 +146,10 @@ pub struct WorkerCaps {
     pub capabilities: gradient_types::proto::GradientCapabilities,
     /// Live resource view of the worker, fed into resource-aware scoring rules.
     pub metrics: Option<gradient_score::WorkerMetricsView>,
+    /// Advertised and registration labels, matched against build selectors.
+    pub labels: WorkerLabels,
+    /// The worker's id, matched against a flaky re-run's avoided worker.
+    pub worker_id: String,
 }
 
 impl WorkerCaps {
@@ -277,6 +308,7 @@ pub struct DispatchRecord {
     pub job_context: serde_json::Value,
     pub instance_context: serde_json::Value,
     pub substitute: bool,
+    pub flaky_rerun: bool,
     pub build_context: serde_json::Value,
 }
 
@@ -286,6 +318,9 @@ struct ScoredCandidate {
     total: f64,
     /// Some rule vetoed dispatch this round; the job never wins regardless of total.
     vetoed: bool,
+    /// The veto came from the job's worker selector: this worker may never
+    /// take it, as opposed to "not yet" holds such as the rescore wait.
+    selector_vetoed: bool,
     score_breakdown: serde_json::Value,
     job_context: serde_json::Value,
 }
@@ -300,10 +335,31 @@ fn job_eligible_for_caps(job: &PendingJob, caps: Option<&WorkerCaps>) -> bool {
         // that don't supply caps, e.g. unit tests for unrelated logic).
         (_, None) => true,
         (PendingJob::Eval(j), Some(c)) => c.can_eval(&j.job),
-        (PendingJob::Build(j), Some(c)) => c.can_build(&j.architecture, &j.required_features),
+        (PendingJob::Build(j), Some(c)) => {
+            c.can_build(&j.architecture, &j.required_features) && !j.avoids(&c.worker_id)
+        }
     }
 }
 
+/// Candidate order: jobs the worker's labels rule out go last so they cannot
+/// shadow one it may take; then highest score first, tie-breaking on the
+/// smaller job_id for determinism. Other vetoes keep their place, so a
+/// top-ranked job held back (e.g. awaiting a rescore) still idles the worker
+/// this round rather than letting a lower-ranked job jump ahead.
+fn candidate_order(
+    (id_a, a): &(String, ScoredCandidate),
+    (id_b, b): &(String, ScoredCandidate),
+) -> std::cmp::Ordering {
+    a.selector_vetoed
+        .cmp(&b.selector_vetoed)
+        .then_with(|| {
+            b.total
+                .partial_cmp(&a.total)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .then_with(|| id_a.cmp(id_b))
+}
+
 /// A vetoed candidate never wins (a rule said "not yet"); below the floor,
 /// dispatching now is worse than idling this round.
 fn wins(sc: &ScoredCandidate) -> bool {
@@ -319,12 +375,14 @@ fn worker_context_of(caps: Option<&WorkerCaps>) -> WorkerContext<'_> {
             system_features: &c.system_features,
             fetch: c.fetch,
             metrics: c.metrics,
+            labels: &c.labels,
         },
         None => WorkerContext {
             architectures: &[],
             system_features: &[],
             fetch: false,
             metrics: None,
+            labels: WorkerLabels::empty(),
         },
     }
 }
@@ -460,6 +518,8 @@ pub struct JobTracker {
     active: HashMap<String, (String, PendingJob)>,
     /// Bounded ring of recent dispatch decisions for the Live Jobs view.
     decisions: VecDeque<DispatchDecision>,
+    /// Per-org hard quotas; jobs of an org at its limit are never assigned.
+    quotas: QuotaSnapshot,
 }
 
 impl JobTracker {
@@ -649,6 +709,7 @@ impl JobTracker {
     ) -> Vec<(String, ScoredCandidate)> {
         let worker_scores = self.scores.get(worker_id);
         let shares = self.org_work_shares(policy, instance);
+        let activity = self.org_activity();
         let now = gradient_types::now();
 
         let mut scored: Vec<(String, ScoredCandidate)> = self
@@ -659,6 +720,7 @@ impl JobTracker {
                     (JobKind::Flake, PendingJob::Eval(_)) | (JobKind::Build, PendingJob::Build(_))
                 )
             })
+            .filter(|(_, j)| self.quota_block(j, &activity).is_none())
             .map(|(id, job)| {
                 let s = worker_scores.and_then(|ws| ws.get(id));
                 let scored_job = match job {
@@ -667,7 +729,8 @@ impl JobTracker {
                         job.org_id(),
                         e.job.tasks.contains(&FlakeTask::FetchFlake),
                         e.history,
-                    ),
+                    )
+                    .with_worker_selector(&e.worker_selector),
                     PendingJob::Build(b) => ScoredJob::new_build(
                         id,
                         job.org_id(),
@@ -677,7 +740,9 @@ impl JobTracker {
                         b.pname.as_deref(),
                         b.closure_size,
                         b.history,
-                    ),
+                    )
+                    .with_critical_path(b.critical_path)
+                    .with_worker_selector(&b.worker_selector),
                 };
                 let ctx = JobContext {
                     job: &scored_job,
@@ -694,6 +759,10 @@ impl JobTracker {
                 let candidate = ScoredCandidate {
                     total: breakdown.total,
                     vetoed: !breakdown.vetoes.is_empty(),
+                    selector_vetoed: breakdown
+                        .vetoes
+                        .iter()
+                        .any(|v| v == gradient_score::rules::WORKER_SELECTOR_RULE),
                     score_breakdown: serde_json::to_value(&breakdown)
                         .unwrap_or(serde_json::Value::Null),
                     job_context: serde_json::to_value(crate::views::JobContextView::new(&ctx, job))
@@ -702,16 +771,110 @@ impl JobTracker {
                 (id.clone(), candidate)
             })
             .collect();
-        // Highest score first; tie-break on the smaller job_id for determinism.
-        scored.sort_by(|(id_a, a), (id_b, b)| {
-            b.total
-                .partial_cmp(&a.total)
-                .unwrap_or(std::cmp::Ordering::Equal)
-                .then_with(|| id_a.cmp(id_b))
-        });
+        scored.sort_by(candidate_order);
         scored
     }
 
+    /// Refresh the critical-path estimate of every pending build the map
+    /// covers; paths shift as sibling builds finish.
+    pub fn set_critical_paths(
+        &mut self,
+        paths: &HashMap<DerivationBuildId, gradient_score::CriticalPath>,
+    ) {
+        for job in self.pending.values_mut() {
+            if let PendingJob::Build(b) = job
+                && let Some(path) = paths.get(&b.derivation_build)
+            {
+                b.critical_path = Some(*path);
+            }
+        }
+    }
+
+    /// Replace the quota snapshot, reloaded every dispatch tick.
+    pub fn set_quotas(&mut self, quotas: QuotaSnapshot) {
+        self.quotas = quotas;
+    }
+
+    pub fn quotas(&self) -> &QuotaSnapshot {
+        &self.quotas
+    }
+
+    /// Builds assigned to workers and evaluations started, per org: the
+    /// snapshot's running evaluations plus any with an eval job assigned since
+    /// it was loaded. Empty when no org has a quota, so the unlimited common
+    /// case skips the O(active) walk.
+    pub fn org_activity(&self) -> HashMap<OrganizationId, OrgActivity> {
+        let mut by_org: HashMap<OrganizationId, OrgActivity> = HashMap::new();
+        if self.quotas.limits.is_empty() {
+            return by_org;
+        }
+        let mut evaluations = self.quotas.running_evaluations.clone();
+        for (_, job) in self.active.values() {
+            match job {
+                PendingJob::Eval(j) => {
+                    evaluations
+                        .entry(j.org_id)
+                        .or_default()
+                        .insert(j.evaluation_id);
+                }
+                PendingJob::Build(j) => by_org.entry(j.org_id).or_default().builds += 1,
+            }
+        }
+        for (org, evals) in evaluations {
+            by_org.entry(org).or_default().evaluations = evals.len() as u64;
+        }
+        by_org
+    }
+
+    /// Snapshot for the waiting-state reconciler's quota parks.
+    pub fn quota_gate(&self) -> QuotaGate {
+        if self.quotas.limits.is_empty() {
+            return QuotaGate::default();
+        }
+        let mut evaluations = self.quotas.running_evaluations.clone();
+        let evals = self
+            .pending
+            .values()
+            .chain(self.active.values().map(|(_, j)| j))
+            .filter(|j| matches!(j, PendingJob::Eval(_)));
+        for job in evals {
+            evaluations
+                .entry(job.org_id())
+                .or_default()
+                .insert(job.evaluation_id());
+        }
+        QuotaGate {
+            snapshot: self.quotas.clone(),
+            activity: self.org_activity(),
+            tracked_evaluations: evaluations
+                .into_iter()
+                .map(|(org, evals)| (org, evals.len() as u64))
+                .collect(),
+            busy_evaluations: self
+                .active
+                .values()
+                .map(|(_, job)| job.evaluation_id())
+                .collect(),
+        }
+    }
+
+    /// The quota holding `job` back, if its org is at a limit for its kind.
+    fn quota_block(
+        &self,
+        job: &PendingJob,
+        activity: &HashMap<OrganizationId, OrgActivity>,
+    ) -> Option<WaitingReason> {
+        let org = job.org_id();
+        let act = activity.get(&org).copied().unwrap_or_default();
+        match job {
+            // An evaluation that already started keeps its slot for every
+            // later eval job, e.g. one resuming after IFD builds.
+            PendingJob::Eval(j) if self.quotas.holds_slot(org, j.evaluation_id) => None,
+            PendingJob::Eval(_) => self.quotas.blocks_evaluations(org, act),
+            PendingJob::Build(_) => self.quotas.blocks_builds(org, act),
+        }
+    }
+
     /// Per-org share of in-flight build work, weighted by predicted build time.
     /// O(active builds) per request, so computed only when an enabled rule
     /// actually consumes the share (none do while FairShareRule is disabled).
@@ -818,6 +981,7 @@ impl JobTracker {
             job_context: sc.job_context.clone(),
             instance_context,
             substitute: matches!(job, PendingJob::Build(b) if b.substitute),
+            flaky_rerun: matches!(job, PendingJob::Build(b) if b.avoid_worker.is_some()),
             build_context: match job {
                 PendingJob::Build(b) => serde_json::json!({
                     "architecture": b.architecture,
@@ -1050,6 +1214,23 @@ impl JobTracker {
         (active, pending)
     }
 
+    /// `(architecture, required_features)` of every pending build that needs
+    /// a specific platform - the autoscaler's demand. Substitutions and
+    /// `builtin` builds run anywhere and are left out.
+    pub fn pending_build_demand(&self) -> Vec<(String, Vec<String>)> {
+        self.pending
+            .values()
+            .filter_map(|j| match j {
+                PendingJob::Build(b)
+                    if !b.substitute && b.architecture != gradient_types::BUILTIN_ARCH =>
+                {
+                    Some((b.architecture.clone(), b.required_features.clone()))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Whether any job is waiting to be dispatched.
     pub fn has_pending(&self) -> bool {
         !self.pending.is_empty()
@@ -1102,6 +1283,7 @@ mod tests {
             ready_at: gradient_types::now(),
             rescore_count: 0,
             history: Default::default(),
+            worker_selector: Default::default(),
         })
     }
 
@@ -1132,6 +1314,7 @@ mod tests {
             ready_at: gradient_types::now(),
             rescore_count: 0,
             history: Default::default(),
+            worker_selector: Default::default(),
         })
     }
 
@@ -1159,6 +1342,7 @@ mod tests {
                     outputs: vec![],
                     timeout_secs: None,
                     max_silent_secs: None,
+                    max_output_size: None,
                 }],
             },
             required_paths: required,
@@ -1172,8 +1356,11 @@ mod tests {
             queued_at: gradient_types::now(),
             ready_at: gradient_types::now(),
             rescore_count: 0,
+            critical_path: None,
+            worker_selector: Default::default(),
             pname: None,
             substitute: false,
+            avoid_worker: None,
         })
     }
 
@@ -1305,6 +1492,32 @@ mod tests {
         assert_eq!(tracker.pending_count(), 3);
     }
 
+    #[test]
+    fn set_critical_paths_updates_only_covered_builds() {
+        let mut tracker = JobTracker::new();
+        let peer = OrganizationId::now_v7();
+        let covered = build_job(peer, vec![]);
+        let PendingJob::Build(ref b) = covered else {
+            unreachable!()
+        };
+        let path = gradient_score::CriticalPath {
+            remaining_ms: 30_000,
+            eval_longest_ms: 60_000,
+        };
+        let paths = HashMap::from([(b.derivation_build, path)]);
+        tracker.add_pending("covered".into(), covered);
+        tracker.add_pending("other".into(), build_job(peer, vec![]));
+
+        tracker.set_critical_paths(&paths);
+
+        let critical_path = |id: &str| match tracker.pending_job(id) {
+            Some(PendingJob::Build(b)) => b.critical_path,
+            _ => unreachable!(),
+        };
+        assert_eq!(critical_path("covered"), Some(path));
+        assert_eq!(critical_path("other"), None);
+    }
+
     #[test]
     fn test_candidates_filtered_by_peer() {
         let mut tracker = JobTracker::new();
@@ -1321,6 +1534,39 @@ mod tests {
         assert_eq!(candidates[0].job_id, "ja");
     }
 
+    #[test]
+    fn flaky_rerun_avoids_the_failed_worker_until_patience_runs_out() {
+        let mut tracker = JobTracker::new();
+        let mut job = build_job(OrganizationId::now_v7(), vec![]);
+        if let PendingJob::Build(b) = &mut job {
+            b.avoid_worker = Some("w1".into());
+        }
+        tracker.add_pending("build:1".into(), job);
+        let caps = |worker_id: &str| WorkerCaps {
+            architectures: vec!["x86_64-linux".into()],
+            worker_id: worker_id.into(),
+            ..Default::default()
+        };
+
+        assert!(
+            tracker
+                .candidates_for_worker(None, Some(&caps("w1")))
+                .is_empty()
+        );
+        assert_eq!(
+            tracker.candidates_for_worker(None, Some(&caps("w2"))).len(),
+            1
+        );
+
+        for _ in 0..FLAKY_AVOID_MAX_TICKS {
+            tracker.bump_rescore_counts();
+        }
+        assert_eq!(
+            tracker.candidates_for_worker(None, Some(&caps("w1"))).len(),
+            1
+        );
+    }
+
     #[test]
     fn test_candidates_filtered_by_architecture() {
         let mut tracker = JobTracker::new();
@@ -1618,6 +1864,76 @@ mod tests {
         assert_eq!(decisions[0].winner.as_deref(), Some("j1"));
     }
 
+    #[test]
+    fn selector_mismatch_vetoes_and_falls_through_to_a_matching_build() {
+        let mut tracker = JobTracker::new();
+        let peer = OrganizationId::now_v7();
+        let mut secure = build_job(peer, vec![]);
+        if let PendingJob::Build(b) = &mut secure {
+            b.worker_selector = Labels::from([("pool".to_string(), "secure".to_string())]);
+            // Outranks the plain build on score alone.
+            b.dependency_count = 50;
+        }
+        tracker.add_pending("secure".into(), secure);
+        tracker.add_pending("plain".into(), build_job(peer, vec![]));
+        let score = |job_id: &str| CandidateScore {
+            job_id: job_id.into(),
+            missing_count: 0,
+            missing_nar_size: 0,
+        };
+        tracker.record_scores("w1", vec![score("secure"), score("plain")]);
+        let p = gradient_score::policy_by_name("simple");
+        let inst = gradient_score::InstanceContext::default();
+        let caps = WorkerCaps {
+            architectures: vec!["x86_64-linux".into()],
+            labels: WorkerLabels {
+                advertised: Labels::from([("pool".to_string(), "untrusted".to_string())]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let assignment = tracker
+            .take_best_of_kind("w1", None, Some(&caps), &JobKind::Build, &*p, &inst)
+            .expect("the unconstrained build dispatches");
+        assert_eq!(assignment.job_id, "plain");
+        let secure = tracker.recent_decisions()[0]
+            .candidates
+            .iter()
+            .find(|c| c.job_id == "secure")
+            .expect("the vetoed build is still recorded")
+            .score_breakdown["vetoes"]
+            .clone();
+        assert_eq!(secure, serde_json::json!(["WorkerSelectorRule"]));
+    }
+
+    #[test]
+    fn only_selector_vetoes_reorder_candidates() {
+        let candidate = |id: &str, total: f64, vetoes: &[&str]| {
+            (
+                id.to_string(),
+                ScoredCandidate {
+                    total,
+                    vetoed: !vetoes.is_empty(),
+                    selector_vetoed: vetoes.contains(&"WorkerSelectorRule"),
+                    score_breakdown: serde_json::Value::Null,
+                    job_context: serde_json::Value::Null,
+                },
+            )
+        };
+        let mut scored = vec![
+            candidate("plain", 10.0, &[]),
+            candidate("secure", 30.0, &["WorkerSelectorRule"]),
+            candidate("held", 20.0, &["RescoreWaitRule"]),
+        ];
+        scored.sort_by(candidate_order);
+        let order: Vec<&str> = scored.iter().map(|(id, _)| id.as_str()).collect();
+        // The rescore hold keeps its rank and so idles the worker this round;
+        // only the selector mismatch drops below the job the worker can take.
+        assert_eq!(order, ["held", "plain", "secure"]);
+        assert!(!wins(&scored[0].1));
+    }
+
     #[test]
     fn candidates_carry_ephemeral_id_and_breakdown_for_detail_lookup() {
         let mut tracker = JobTracker::new();
@@ -1845,6 +2161,88 @@ mod tests {
         assert_eq!(tracker.active_count(), 0);
     }
 
+    #[test]
+    fn org_at_build_quota_is_skipped_until_a_build_finishes() {
+        let mut tracker = JobTracker::new();
+        let capped = OrganizationId::now_v7();
+        let other = OrganizationId::now_v7();
+        tracker.set_quotas(QuotaSnapshot {
+            limits: HashMap::from([(
+                capped,
+                crate::quota::OrgQuota {
+                    max_concurrent_builds: Some(1),
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        });
+        for (id, org) in [("a1", capped), ("a2", capped), ("b1", other)] {
+            tracker.add_pending(id.into(), build_job(org, vec![]));
+        }
+        let cached = |id: &str| CandidateScore {
+            job_id: id.into(),
+            missing_count: 0,
+            missing_nar_size: 0,
+        };
+        tracker.record_scores("w1", vec![cached("a1"), cached("a2"), cached("b1")]);
+
+        let p = gradient_score::policy_by_name("simple");
+        let inst = gradient_score::InstanceContext::default();
+        let mut take = |tracker: &mut JobTracker| {
+            tracker
+                .take_best_of_kind("w1", None, None, &JobKind::Build, &*p, &inst)
+                .map(|a| a.job_id)
+        };
+
+        assert_eq!(take(&mut tracker).as_deref(), Some("a1"));
+        // `capped` is at its one-build cap: only the other org's build goes.
+        assert_eq!(take(&mut tracker).as_deref(), Some("b1"));
+        assert_eq!(take(&mut tracker), None);
+        assert_eq!(tracker.pending_count(), 1);
+
+        tracker.remove_active("a1");
+        assert_eq!(take(&mut tracker).as_deref(), Some("a2"));
+    }
+
+    #[test]
+    fn started_evaluations_hold_their_slot_without_an_eval_job() {
+        let mut tracker = JobTracker::new();
+        let org = OrganizationId::now_v7();
+        let resuming = eval_job(org);
+        let started = resuming.evaluation_id();
+        tracker.set_quotas(QuotaSnapshot {
+            limits: HashMap::from([(
+                org,
+                crate::quota::OrgQuota {
+                    max_concurrent_evaluations: Some(1),
+                    ..Default::default()
+                },
+            )]),
+            // Building or blocked on IFD builds: no eval job in the tracker.
+            running_evaluations: HashMap::from([(org, HashSet::from([started]))]),
+            ..Default::default()
+        });
+        tracker.add_pending("new".into(), eval_job(org));
+
+        let p = gradient_score::policy_by_name("simple");
+        let inst = gradient_score::InstanceContext::default();
+        let mut take = |tracker: &mut JobTracker| {
+            tracker
+                .take_best_of_kind("w1", None, None, &JobKind::Flake, &*p, &inst)
+                .map(|a| a.job_id)
+        };
+
+        assert_eq!(tracker.org_activity()[&org].evaluations, 1);
+        assert_eq!(take(&mut tracker), None);
+        assert_eq!(tracker.quota_gate().tracked_evaluations[&org], 2);
+
+        // The started evaluation's own next eval job takes no new slot.
+        tracker.add_pending("resume".into(), resuming);
+        assert_eq!(take(&mut tracker).as_deref(), Some("resume"));
+        assert_eq!(tracker.org_activity()[&org].evaluations, 1);
+        assert_eq!(take(&mut tracker), None);
+    }
+
     #[test]
     fn dispatch_picks_non_negative() {
         // A fully-cached build (missing_nar_size Some(0)) earns the MissingNarSize
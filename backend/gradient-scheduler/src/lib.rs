@@ -15,9 +15,17 @@
 //! - [`build`] - `BuildOutput`/completion/failure handling and self-heal
 //! - [`waiting_state`] - reconciles evaluation status against the worker pool
 //! - [`buildability`] - whether the connected pool can build a pending anchor
+//! - [`quota`] - hard per-organization build and evaluation quotas
+//! - [`critical_path`] - remaining critical-path estimates for build prioritisation
+//! - [`worker_selector`] - project/trigger worker selectors of in-flight evaluations
+//! - [`autoscale`] - queue-pressure worker scaling decisions and their backends
+//! - [`cordon`] - per-organization worker cordons and maintenance windows
 
+pub mod autoscale;
 pub mod build;
 pub mod buildability;
+pub mod cordon;
+pub mod critical_path;
 pub mod dispatch;
 pub mod eval;
 pub mod history;
@@ -25,6 +33,7 @@ pub mod instance;
 pub mod jobs;
 pub mod log_substitution;
 pub mod peer_auth;
+pub mod quota;
 pub mod views;
 pub mod waiting_state;
 pub mod worker_pool;
@@ -35,6 +44,7 @@ mod eval_metrics;
 mod job_handlers;
 pub(crate) mod trigger_dispatch;
 mod worker_lifecycle;
+pub(crate) mod worker_selector;
 
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -84,9 +94,15 @@ pub struct Scheduler {
     /// the eval stream completes. Promotion itself is graph-driven (see
     /// `gradient_db::promotion`), not tied to this map.
     pub(crate) eval_edges: EvalEdgesMap,
+    /// Per-evaluation build graphs behind the critical-path rule, kept across
+    /// dispatch ticks and invalidated whenever the eval stream records new
+    /// jobs or edges for an evaluation.
+    pub(crate) critical_paths: Arc<RwLock<critical_path::CriticalPathCache>>,
     /// Scoring policy used when selecting which pending job to assign to a
-    /// requesting worker.  Shared via `Arc` so it can be read lock-free.
-    pub(crate) policy: Arc<dyn gradient_score::ScoringPolicy>,
+    /// requesting worker, plus its load status. Swapped by
+    /// `scoring_policy_reload_loop` when the policy file changes and read
+    /// lock-free during scoring.
+    pub(crate) policy: Arc<arc_swap::ArcSwap<gradient_score::LoadedPolicy>>,
     /// Windowed instance metrics snapshot, recomputed periodically by
     /// `instance_metrics_loop` and read lock-free during scoring.
     pub(crate) instance: Arc<arc_swap::ArcSwap<gradient_score::InstanceContext>>,
@@ -114,7 +130,10 @@ impl std::fmt::Debug for Scheduler {
 
 impl Scheduler {
     pub fn new(state: Arc<ServerState>) -> Self {
-        let policy = gradient_score::policy_by_name(&state.config.eval.scheduler_scoring_policy);
+        let policy = gradient_score::load_policy(
+            &state.config.eval.scheduler_scoring_policy,
+            state.config.eval.scheduler_scoring_policy_file.as_deref(),
+        );
         Self {
             state,
             worker_pool: Arc::new(RwLock::new(WorkerPool::new())),
@@ -122,7 +141,8 @@ impl Scheduler {
             job_notify: Arc::new(tokio::sync::watch::channel(0u64).0),
             dispatch_kick: Arc::new(tokio::sync::Notify::new()),
             eval_edges: Arc::new(RwLock::new(HashMap::new())),
-            policy,
+            critical_paths: Arc::new(RwLock::new(Default::default())),
+            policy: Arc::new(arc_swap::ArcSwap::from_pointee(policy)),
             instance: Arc::new(arc_swap::ArcSwap::from_pointee(
                 gradient_score::InstanceContext::default(),
             )),
@@ -133,6 +153,17 @@ impl Scheduler {
         }
     }
 
+    /// The scoring policy currently in effect.
+    pub(crate) fn scoring_policy(&self) -> Arc<dyn gradient_score::ScoringPolicy> {
+        Arc::clone(&self.policy.load().policy)
+    }
+
+    /// Active policy, its rules, and the validation errors of the last load,
+    /// for the board.
+    pub fn scoring_policy_status(&self) -> gradient_score::PolicyStatus {
+        self.policy.load().status.clone()
+    }
+
     /// Drop the eval job and any associated build jobs from the in-memory
     /// tracker. Workers that have already been assigned will finish or time out
     /// normally; the DB-side abort (via `gradient_ci::abort_evaluation`) is the
@@ -175,6 +206,12 @@ impl Scheduler {
         self.job_tracker.read().await.board_active_jobs()
     }
 
+    /// Limits, in-flight counts and budget use of every org with a quota.
+    pub async fn org_quota_usage(&self) -> Vec<quota::OrgQuotaUsage> {
+        let tracker = self.job_tracker.read().await;
+        tracker.quotas().usage(&tracker.org_activity())
+    }
+
     pub async fn recent_decisions(&self) -> Vec<jobs::DispatchDecision> {
         self.job_tracker.read().await.recent_decisions()
     }
@@ -0,0 +1,437 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Hard per-organization quotas: concurrent builds, concurrent evaluations and
+//! a rolling build-minutes budget, configured on `organization`.
+//!
+//! Unlike `FairShareRule`, which only nudges scores, a quota is a gate: the
+//! [`crate::jobs::JobTracker`] never assigns a job whose org is at its limit,
+//! and the waiting-state reconciler parks the org's idle evaluations under
+//! [`WaitingReason::Quota`] until it is back under. Limits and minutes used are
+//! reloaded into a [`QuotaSnapshot`] every dispatch tick; in-flight counts come
+//! live from the tracker, so a concurrency cap is exact at assignment time.
+//! An evaluation holds its slot from its first eval job until it finishes, so
+//! the snapshot also carries the evaluations already started, including those
+//! building or blocked on IFD builds with no eval job in the tracker.
+
+use std::collections::{HashMap, HashSet};
+
+use gradient_entity::evaluation::EvaluationStatus;
+use gradient_types::*;
+use sea_orm::{
+    ColumnTrait, Condition, ConnectionTrait, DbBackend, EntityTrait, FromQueryResult, QueryFilter,
+    Statement,
+};
+use serde::Serialize;
+
+/// Length of the rolling window `build_minutes_budget` is measured over.
+pub const BUILD_MINUTES_WINDOW_HOURS: i64 = 24;
+
+/// One org's configured limits; `None` is unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct OrgQuota {
+    pub max_concurrent_builds: Option<u64>,
+    pub max_concurrent_evaluations: Option<u64>,
+    pub build_minutes_budget: Option<u64>,
+}
+
+impl OrgQuota {
+    pub fn from_org(org: &MOrganization) -> Self {
+        let limit = |v: Option<i32>| v.filter(|v| *v > 0).map(|v| v as u64);
+        Self {
+            max_concurrent_builds: limit(org.max_concurrent_builds),
+            max_concurrent_evaluations: limit(org.max_concurrent_evaluations),
+            build_minutes_budget: limit(org.build_minutes_budget),
+        }
+    }
+
+    fn is_unlimited(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// An org's builds assigned to workers and evaluations started but not
+/// finished.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OrgActivity {
+    pub builds: u64,
+    pub evaluations: u64,
+}
+
+/// Limits of every org that has one, plus the build minutes each spent in the
+/// rolling window. Orgs absent from `limits` are unlimited.
+#[derive(Debug, Clone, Default)]
+pub struct QuotaSnapshot {
+    pub limits: HashMap<OrganizationId, OrgQuota>,
+    pub build_minutes: HashMap<OrganizationId, u64>,
+    /// Evaluations of orgs with an evaluation cap that are past `Queued` and
+    /// not finished: evaluating, fetching or building, IFD builds included.
+    pub running_evaluations: HashMap<OrganizationId, HashSet<EvaluationId>>,
+}
+
+/// Quota state of one org for the board.
+#[derive(Debug, Clone, Serialize)]
+pub struct OrgQuotaUsage {
+    pub organization: OrganizationId,
+    #[serde(flatten)]
+    pub limits: OrgQuota,
+    pub active_builds: u64,
+    pub active_evaluations: u64,
+    pub build_minutes_used: u64,
+    /// Quotas the org is currently held on.
+    pub blocked: Vec<QuotaKind>,
+}
+
+impl QuotaSnapshot {
+    /// Whether `evaluation` already holds one of `org`'s evaluation slots, so
+    /// its next eval job does not need another.
+    pub fn holds_slot(&self, org: OrganizationId, evaluation: EvaluationId) -> bool {
+        self.running_evaluations
+            .get(&org)
+            .is_some_and(|evals| evals.contains(&evaluation))
+    }
+
+    /// Why `org` may not start another build, if it is at a build quota.
+    pub fn blocks_builds(
+        &self,
+        org: OrganizationId,
+        activity: OrgActivity,
+    ) -> Option<WaitingReason> {
+        let limits = self.limits.get(&org)?;
+        if let Some(limit) = limits.max_concurrent_builds
+            && activity.builds >= limit
+        {
+            return Some(WaitingReason::quota(
+                QuotaKind::ConcurrentBuilds,
+                limit,
+                activity.builds,
+            ));
+        }
+        if let Some(limit) = limits.build_minutes_budget {
+            let used = self.build_minutes.get(&org).copied().unwrap_or(0);
+            if used >= limit {
+                return Some(WaitingReason::quota(QuotaKind::BuildMinutes, limit, used));
+            }
+        }
+        None
+    }
+
+    /// Why `org` may not start another evaluation, if it is at its cap.
+    pub fn blocks_evaluations(
+        &self,
+        org: OrganizationId,
+        activity: OrgActivity,
+    ) -> Option<WaitingReason> {
+        let limit = self.limits.get(&org)?.max_concurrent_evaluations?;
+        (activity.evaluations >= limit).then(|| {
+            WaitingReason::quota(
+                QuotaKind::ConcurrentEvaluations,
+                limit,
+                activity.evaluations,
+            )
+        })
+    }
+
+    /// Usage of every org with a quota, ordered by org id for a stable view.
+    pub fn usage(&self, activity: &HashMap<OrganizationId, OrgActivity>) -> Vec<OrgQuotaUsage> {
+        let mut out: Vec<OrgQuotaUsage> = self
+            .limits
+            .iter()
+            .map(|(&org, &limits)| {
+                let act = activity.get(&org).copied().unwrap_or_default();
+                let blocked = [
+                    self.blocks_builds(org, act),
+                    self.blocks_evaluations(org, act),
+                ]
+                .into_iter()
+                .flatten()
+                .filter_map(|r| match r {
+                    WaitingReason::Quota { quota, .. } => Some(quota),
+                    _ => None,
+                })
+                .collect();
+                OrgQuotaUsage {
+                    organization: org,
+                    limits,
+                    active_builds: act.builds,
+                    active_evaluations: act.evaluations,
+                    build_minutes_used: self.build_minutes.get(&org).copied().unwrap_or(0),
+                    blocked,
+                }
+            })
+            .collect();
+        out.sort_by_key(|u| u.organization);
+        out
+    }
+}
+
+/// Everything the waiting-state reconciler needs to park and recover
+/// quota-held evaluations, snapshotted from the tracker once per pass.
+#[derive(Debug, Clone, Default)]
+pub struct QuotaGate {
+    pub snapshot: QuotaSnapshot,
+    pub activity: HashMap<OrganizationId, OrgActivity>,
+    /// Evaluations per org already started or in the tracker (assigned or
+    /// pending): the slots counted against `max_concurrent_evaluations` when
+    /// deciding whether a parked evaluation may be queued again.
+    pub tracked_evaluations: HashMap<OrganizationId, u64>,
+    /// Evaluations with a job assigned to a worker. A build-quota park only
+    /// applies to evaluations with nothing in flight: one that is building is
+    /// throttled, not waiting.
+    pub busy_evaluations: HashSet<EvaluationId>,
+}
+
+impl QuotaGate {
+    pub fn is_empty(&self) -> bool {
+        self.snapshot.limits.is_empty()
+    }
+
+    /// Why a buildable evaluation should be parked, if its org is at a build
+    /// quota and it has nothing in flight.
+    pub fn holds_builds(
+        &self,
+        org: OrganizationId,
+        evaluation: EvaluationId,
+    ) -> Option<WaitingReason> {
+        if self.busy_evaluations.contains(&evaluation) {
+            return None;
+        }
+        self.snapshot
+            .blocks_builds(org, self.activity.get(&org).copied().unwrap_or_default())
+    }
+
+    /// Why another evaluation of `org` may not be queued, given `admitted`
+    /// more already let through in this pass; `None` while it has a free slot.
+    pub fn holds_evaluation(&self, org: OrganizationId, admitted: u64) -> Option<WaitingReason> {
+        let limit = self.snapshot.limits.get(&org)?.max_concurrent_evaluations?;
+        let used = self.tracked_evaluations.get(&org).copied().unwrap_or(0) + admitted;
+        (used >= limit).then(|| WaitingReason::quota(QuotaKind::ConcurrentEvaluations, limit, used))
+    }
+}
+
+#[derive(Debug, FromQueryResult)]
+struct MinutesRow {
+    organization: OrganizationId,
+    minutes: i64,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct RunningEvaluationRow {
+    organization: OrganizationId,
+    evaluation: EvaluationId,
+}
+
+/// Load every org's limits; for orgs with a budget, the minutes their
+/// finished non-substitute build attempts spent inside the rolling window;
+/// and for orgs with an evaluation cap, their started evaluations.
+/// In-flight attempts are not counted; the concurrency cap bounds them.
+pub async fn load_quota_snapshot(
+    db: &impl ConnectionTrait,
+    now: chrono::NaiveDateTime,
+) -> Result<QuotaSnapshot, sea_orm::DbErr> {
+    let limits: HashMap<OrganizationId, OrgQuota> = EOrganization::find()
+        .filter(
+            Condition::any()
+                .add(COrganization::MaxConcurrentBuilds.is_not_null())
+                .add(COrganization::MaxConcurrentEvaluations.is_not_null())
+                .add(COrganization::BuildMinutesBudget.is_not_null()),
+        )
+        .all(db)
+        .await?
+        .iter()
+        .map(|o| (o.id, OrgQuota::from_org(o)))
+        .filter(|(_, q)| !q.is_unlimited())
+        .collect();
+
+    let orgs_with = |has: fn(&OrgQuota) -> bool| -> Vec<uuid::Uuid> {
+        limits
+            .iter()
+            .filter(|(_, q)| has(q))
+            .map(|(id, _)| id.into_inner())
+            .collect()
+    };
+    let budgeted = orgs_with(|q| q.build_minutes_budget.is_some());
+    let eval_capped = orgs_with(|q| q.max_concurrent_evaluations.is_some());
+
+    let mut build_minutes = HashMap::new();
+    if !budgeted.is_empty() {
+        let window_start = now - chrono::Duration::hours(BUILD_MINUTES_WINDOW_HOURS);
+        build_minutes = MinutesRow::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            r#"
+            SELECT dj.organization AS organization,
+              (COALESCE(SUM(EXTRACT(EPOCH FROM (ba.build_finished_at - GREATEST(ba.build_started_at, $1)))), 0) / 60)::int8 AS minutes
+            FROM build_attempt ba
+            JOIN dispatched_job dj ON dj.id = ba.dispatched_job
+            WHERE NOT ba.substitute
+              AND ba.build_started_at IS NOT NULL
+              AND ba.build_finished_at >= $1
+              AND dj.organization = ANY($2)
+            GROUP BY dj.organization
+            "#,
+            [window_start.into(), budgeted.into()],
+        ))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|r| (r.organization, r.minutes.max(0) as u64))
+        .collect();
+    }
+
+    let mut running_evaluations: HashMap<OrganizationId, HashSet<EvaluationId>> = HashMap::new();
+    if !eval_capped.is_empty() {
+        let started: Vec<i32> = [
+            EvaluationStatus::Fetching,
+            EvaluationStatus::EvaluatingFlake,
+            EvaluationStatus::EvaluatingDerivation,
+            EvaluationStatus::Building,
+        ]
+        .into_iter()
+        .map(|s| s as i32)
+        .collect();
+        let rows = RunningEvaluationRow::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            r#"
+            SELECT p.organization AS organization, e.id AS evaluation
+            FROM evaluation e
+            JOIN project p ON p.id = e.project
+            WHERE e.status = ANY($1)
+              AND p.organization = ANY($2)
+            "#,
+            [started.into(), eval_capped.into()],
+        ))
+        .all(db)
+        .await?;
+        for r in rows {
+            running_evaluations
+                .entry(r.organization)
+                .or_default()
+                .insert(r.evaluation);
+        }
+    }
+
+    Ok(QuotaSnapshot {
+        limits,
+        build_minutes,
+        running_evaluations,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(org: OrganizationId, quota: OrgQuota, minutes: u64) -> QuotaSnapshot {
+        QuotaSnapshot {
+            limits: HashMap::from([(org, quota)]),
+            build_minutes: HashMap::from([(org, minutes)]),
+            running_evaluations: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn concurrency_caps_block_at_the_limit() {
+        let org = OrganizationId::now_v7();
+        let snap = snapshot(
+            org,
+            OrgQuota {
+                max_concurrent_builds: Some(2),
+                max_concurrent_evaluations: Some(1),
+                build_minutes_budget: None,
+            },
+            0,
+        );
+        let below = OrgActivity {
+            builds: 1,
+            evaluations: 0,
+        };
+        let at = OrgActivity {
+            builds: 2,
+            evaluations: 1,
+        };
+        assert_eq!(snap.blocks_builds(org, below), None);
+        assert_eq!(snap.blocks_evaluations(org, below), None);
+        assert_eq!(
+            snap.blocks_builds(org, at),
+            Some(WaitingReason::quota(QuotaKind::ConcurrentBuilds, 2, 2))
+        );
+        assert_eq!(
+            snap.blocks_evaluations(org, at),
+            Some(WaitingReason::quota(QuotaKind::ConcurrentEvaluations, 1, 1))
+        );
+    }
+
+    #[test]
+    fn spent_budget_blocks_builds_but_not_evaluations() {
+        let org = OrganizationId::now_v7();
+        let snap = snapshot(
+            org,
+            OrgQuota {
+                build_minutes_budget: Some(60),
+                ..Default::default()
+            },
+            61,
+        );
+        let idle = OrgActivity::default();
+        assert_eq!(
+            snap.blocks_builds(org, idle),
+            Some(WaitingReason::quota(QuotaKind::BuildMinutes, 60, 61))
+        );
+        assert_eq!(snap.blocks_evaluations(org, idle), None);
+        assert_eq!(
+            snap.usage(&HashMap::new())[0].blocked,
+            vec![QuotaKind::BuildMinutes]
+        );
+    }
+
+    #[test]
+    fn gate_holds_only_idle_evaluations_and_counts_tracked_slots() {
+        let org = OrganizationId::now_v7();
+        let building = EvaluationId::now_v7();
+        let idle = EvaluationId::now_v7();
+        let gate = QuotaGate {
+            snapshot: snapshot(
+                org,
+                OrgQuota {
+                    max_concurrent_builds: Some(1),
+                    max_concurrent_evaluations: Some(3),
+                    build_minutes_budget: None,
+                },
+                0,
+            ),
+            activity: HashMap::from([(
+                org,
+                OrgActivity {
+                    builds: 1,
+                    evaluations: 0,
+                },
+            )]),
+            tracked_evaluations: HashMap::from([(org, 2)]),
+            busy_evaluations: HashSet::from([building]),
+        };
+        assert_eq!(gate.holds_builds(org, building), None);
+        assert!(gate.holds_builds(org, idle).is_some());
+        assert_eq!(gate.holds_evaluation(org, 0), None);
+        assert_eq!(
+            gate.holds_evaluation(org, 1),
+            Some(WaitingReason::quota(QuotaKind::ConcurrentEvaluations, 3, 3))
+        );
+        assert_eq!(gate.holds_evaluation(OrganizationId::now_v7(), 9), None);
+    }
+
+    #[test]
+    fn orgs_without_limits_are_never_blocked() {
+        let snap = QuotaSnapshot::default();
+        let busy = OrgActivity {
+            builds: 1000,
+            evaluations: 1000,
+        };
+        let org = OrganizationId::now_v7();
+        assert_eq!(snap.blocks_builds(org, busy), None);
+        assert_eq!(snap.blocks_evaluations(org, busy), None);
+        assert!(snap.usage(&HashMap::new()).is_empty());
+    }
+}
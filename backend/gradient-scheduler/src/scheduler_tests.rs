@@ -50,6 +50,7 @@ fn eval_job(peer: OrganizationId) -> PendingEvalJob {
         ready_at: gradient_types::now(),
         rescore_count: 0,
         history: Default::default(),
+        worker_selector: Default::default(),
     }
 }
 
@@ -152,7 +153,16 @@ async fn dispatch_kick_is_retained_when_not_awaiting() {
 async fn capability_update_kicks_dispatch_instead_of_reconciling_inline() {
     let scheduler = test_scheduler();
     scheduler
-        .update_worker_capabilities("peer-x", vec![], vec![], 4, 8, 16_000, 100)
+        .update_worker_capabilities(
+            "peer-x",
+            vec![],
+            vec![],
+            4,
+            8,
+            16_000,
+            100,
+            Default::default(),
+        )
         .await;
     let woke = tokio::time::timeout(
         std::time::Duration::from_secs(1),
@@ -402,6 +412,7 @@ async fn record_eval_message_inserts_for_active_build_job() {
                         outputs: vec![],
                         timeout_secs: None,
                         max_silent_secs: None,
+                        max_output_size: None,
                     }],
                 },
                 required_paths: vec![],
@@ -415,8 +426,11 @@ async fn record_eval_message_inserts_for_active_build_job() {
                 queued_at: gradient_types::now(),
                 ready_at: gradient_types::now(),
                 rescore_count: 0,
+                critical_path: None,
+                worker_selector: Default::default(),
                 pname: None,
                 substitute: false,
+                avoid_worker: None,
             },
         )
         .await;
@@ -559,6 +573,7 @@ async fn cancel_evaluation_jobs_drops_eval_and_build_jobs() {
                 ready_at: gradient_types::now(),
                 rescore_count: 0,
                 history: Default::default(),
+                worker_selector: Default::default(),
             },
         )
         .await;
@@ -583,6 +598,7 @@ async fn cancel_evaluation_jobs_drops_eval_and_build_jobs() {
                             outputs: vec![],
                             timeout_secs: None,
                             max_silent_secs: None,
+                            max_output_size: None,
                         }],
                     },
                     required_paths: vec![],
@@ -596,8 +612,11 @@ async fn cancel_evaluation_jobs_drops_eval_and_build_jobs() {
                     queued_at: gradient_types::now(),
                     ready_at: gradient_types::now(),
                     rescore_count: 0,
+                    critical_path: None,
+                    worker_selector: Default::default(),
                     pname: None,
                     substitute: false,
+                    avoid_worker: None,
                 },
             )
             .await;
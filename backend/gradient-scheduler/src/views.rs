@@ -7,7 +7,8 @@
 //! Full structured views of the worker and job scoring context, serialized
 //! onto the dispatched-job record so the frontend can show every collected value.
 
-use gradient_score::{DerivationRef, HistoryPrediction, JobContext, WorkerContext};
+use gradient_score::{CriticalPath, DerivationRef, HistoryPrediction, JobContext, WorkerContext};
+use gradient_types::Labels;
 use gradient_types::proto::{FlakeTask, GradientCapabilities};
 use serde::Serialize;
 
@@ -92,6 +93,10 @@ pub struct JobContextView {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub history: Option<HistoryView>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub critical_path: Option<CriticalPath>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub worker_selector: Option<Labels>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub derivations: Option<Vec<DerivationRef>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fetch_flake: Option<bool>,
@@ -114,6 +119,8 @@ impl JobContextView {
             prefer_local_build: None,
             is_fixed_output: None,
             history: None,
+            critical_path: None,
+            worker_selector: None,
             derivations: None,
             fetch_flake: None,
         };
@@ -126,6 +133,8 @@ impl JobContextView {
                 prefer_local_build: Some(b.prefer_local_build),
                 is_fixed_output: Some(b.is_fixed_output),
                 history: Some((&b.history).into()),
+                critical_path: b.critical_path,
+                worker_selector: (!b.worker_selector.is_empty()).then(|| b.worker_selector.clone()),
                 derivations: Some(
                     b.job
                         .builds
@@ -143,6 +152,7 @@ impl JobContextView {
             },
             PendingJob::Eval(e) => Self {
                 fetch_flake: Some(e.job.tasks.contains(&FlakeTask::FetchFlake)),
+                worker_selector: (!e.worker_selector.is_empty()).then(|| e.worker_selector.clone()),
                 ..common("Eval", String::new())
             },
         }
@@ -171,6 +181,7 @@ mod tests {
                     outputs: vec![],
                     timeout_secs: None,
                     max_silent_secs: None,
+                    max_output_size: None,
                 }],
             },
             required_paths: vec![],
@@ -187,8 +198,11 @@ mod tests {
             queued_at: now,
             ready_at: now,
             rescore_count: 0,
+            critical_path: None,
+            worker_selector: Default::default(),
             pname: Some("curl".into()),
             substitute: false,
+            avoid_worker: None,
         })
     }
 
@@ -8,6 +8,7 @@
 //! worker pool (`Queued`/`Building` <-> `Waiting`), and self-heals a
 //! graph-stuck evaluation.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
@@ -29,6 +30,7 @@ use tracing::{info, warn};
 const DRV_RECOVERY_GRACE_SECS: i64 = 120;
 
 use crate::buildability::BuildabilityChecker;
+use crate::quota::QuotaGate;
 
 /// Sweep every in-flight evaluation and reconcile its status against the
 /// current set of connected workers, keyed on the eval's current state:
@@ -42,16 +44,25 @@ use crate::buildability::BuildabilityChecker;
 ///   can satisfy any pending build's `(architecture, required_features)`.
 /// - **Waiting**: recover via the reason it parked under - `EvalWorkers`
 ///   back to `Queued` once the capability returns, `Workers` back to
-///   `Building` once buildable. `Approval`/`NoCache`/`CacheStorageFull` parks
-///   are owned by other hooks and left untouched.
+///   `Building` once buildable, `Quota` once the org is back under its
+///   limit. `Approval`/`NoCache`/`CacheStorageFull` parks are owned by other
+///   hooks and left untouched.
+///
+/// A buildable `Building` eval whose org is at a build quota and which has
+/// nothing in flight is parked under `Quota` so the hold is visible; the
+/// [`crate::jobs::JobTracker`] enforces the limit itself either way.
+///
+/// Build-phase decisions for an eval with a worker selector consider only
+/// the workers whose labels match it (see [`crate::worker_selector`]).
 pub async fn reconcile_waiting_state(
     state: &Arc<ServerState>,
-    worker_caps: &[(Vec<String>, Vec<String>)],
+    workers: &[(Vec<String>, Vec<String>, WorkerLabels)],
     eval_capable_workers: usize,
     fetch_capable_workers: usize,
     draining: bool,
+    quotas: &QuotaGate,
 ) -> Result<()> {
-    let evals = EEvaluation::find()
+    let mut evals = EEvaluation::find()
         .filter(CEvaluation::Status.is_in(vec![
             EvaluationStatus::Queued,
             EvaluationStatus::Fetching,
@@ -103,7 +114,22 @@ pub async fn reconcile_waiting_state(
         return Ok(());
     }
 
-    let connected_workers = worker_caps.len() as u32;
+    let connected_workers = workers.len() as u32;
+    let all_caps: Vec<(Vec<String>, Vec<String>)> = workers
+        .iter()
+        .map(|(arch, feats, _)| (arch.clone(), feats.clone()))
+        .collect();
+    let selectors = crate::worker_selector::load_eval_selectors(&state.worker_db, &evals).await?;
+
+    // Quota parks recover oldest-first, counting requeues against the org's
+    // free evaluation slots as they are handed out.
+    evals.sort_by_key(|e| e.created_at);
+    let eval_orgs = if quotas.is_empty() {
+        HashMap::new()
+    } else {
+        eval_organizations(state, &evals).await?
+    };
+    let mut requeued: HashMap<OrganizationId, u64> = HashMap::new();
 
     for eval in evals {
         let reason = eval
@@ -128,8 +154,28 @@ pub async fn reconcile_waiting_state(
             continue;
         }
 
+        let org = eval.project.and_then(|p| eval_orgs.get(&p).copied());
+        let placement = selectors.get(&eval.id);
+        let selected_caps;
+        let worker_caps = match placement {
+            Some((org, selector)) => {
+                selected_caps = matching_caps(workers, *org, selector);
+                &selected_caps
+            }
+            None => &all_caps,
+        };
         let outcome = match eval.status {
             EvaluationStatus::Waiting => match reason {
+                Some(WaitingReason::Quota {
+                    quota: QuotaKind::ConcurrentEvaluations,
+                    ..
+                }) => Some(decide_evaluation_quota_recovery(quotas, org, &mut requeued)),
+                Some(WaitingReason::Quota { .. }) => {
+                    Some(match org.and_then(|o| quotas.holds_builds(o, eval.id)) {
+                        Some(reason) => (EvaluationStatus::Waiting, Some(reason)),
+                        None => (EvaluationStatus::Building, None),
+                    })
+                }
                 Some(WaitingReason::EvalWorkers { capability, .. }) => Some(decide_eval_recovery(
                     capability,
                     eval_capable_workers,
@@ -161,13 +207,26 @@ pub async fn reconcile_waiting_state(
                 fetch_capable_workers,
                 connected_workers,
             ),
-            EvaluationStatus::Building => build_phase_decision(state, eval.id, worker_caps).await?,
+            EvaluationStatus::Building => {
+                match build_phase_decision(state, eval.id, worker_caps).await? {
+                    Some((EvaluationStatus::Building, _)) => org
+                        .and_then(|o| quotas.holds_builds(o, eval.id))
+                        .map(|reason| (EvaluationStatus::Waiting, Some(reason))),
+                    outcome => outcome,
+                }
+            }
             _ => None,
         };
 
         let Some((target, new_reason)) = outcome else {
             continue;
         };
+        let new_reason = match placement {
+            Some((_, selector)) => {
+                new_reason.map(|r| r.with_worker_selector(selector, connected_workers))
+            }
+            None => new_reason,
+        };
 
         if eval.status != target {
             info!(
@@ -191,6 +250,67 @@ pub async fn reconcile_waiting_state(
     Ok(())
 }
 
+/// `(architectures, system_features)` of the workers whose labels, as seen by
+/// `org`'s jobs, match `selector`.
+fn matching_caps(
+    workers: &[(Vec<String>, Vec<String>, WorkerLabels)],
+    org: OrganizationId,
+    selector: &Labels,
+) -> Vec<(Vec<String>, Vec<String>)> {
+    workers
+        .iter()
+        .filter(|(_, _, labels)| labels.matches(org, selector))
+        .map(|(arch, feats, _)| (arch.clone(), feats.clone()))
+        .collect()
+}
+
+/// Organization of every evaluation's project, for quota checks.
+async fn eval_organizations(
+    state: &Arc<ServerState>,
+    evals: &[MEvaluation],
+) -> Result<HashMap<ProjectId, OrganizationId>> {
+    let project_ids: Vec<ProjectId> = evals
+        .iter()
+        .filter_map(|e| e.project)
+        .collect::<std::collections::HashSet<_>>()
+        .into_iter()
+        .collect();
+    let projects = gradient_db::fetch_in_chunks(&project_ids, |chunk| async move {
+        EProject::find()
+            .filter(CProject::Id.is_in(chunk))
+            .all(&state.worker_db)
+            .await
+    })
+    .await
+    .context("fetch evaluation projects")?;
+
+    Ok(projects
+        .into_iter()
+        .map(|p| (p.id, p.organization))
+        .collect())
+}
+
+/// Recovery for an eval parked on its org's evaluation cap: back to `Queued`
+/// while the org has a free slot (counting those already handed out this
+/// pass in `requeued`), otherwise refresh the reason with the live count.
+fn decide_evaluation_quota_recovery(
+    quotas: &QuotaGate,
+    org: Option<OrganizationId>,
+    requeued: &mut HashMap<OrganizationId, u64>,
+) -> (EvaluationStatus, Option<WaitingReason>) {
+    let Some(org) = org else {
+        return (EvaluationStatus::Queued, None);
+    };
+    let admitted = requeued.entry(org).or_default();
+    match quotas.holds_evaluation(org, *admitted) {
+        Some(reason) => (EvaluationStatus::Waiting, Some(reason)),
+        None => {
+            *admitted += 1;
+            (EvaluationStatus::Queued, None)
+        }
+    }
+}
+
 /// Build-phase reconciliation for one evaluation: decide `Building` vs
 /// `Waiting` from whether the connected pool can satisfy any of the eval's
 /// pending anchors. Returns `None` when the eval has no pending anchor
@@ -669,6 +789,43 @@ mod tests {
         assert!(reason.is_none());
     }
 
+    #[test]
+    fn evaluation_quota_recovery_requeues_only_into_free_slots() {
+        use crate::quota::{OrgQuota, QuotaSnapshot};
+
+        let org = OrganizationId::now_v7();
+        let gate = QuotaGate {
+            snapshot: QuotaSnapshot {
+                limits: HashMap::from([(
+                    org,
+                    OrgQuota {
+                        max_concurrent_evaluations: Some(2),
+                        ..Default::default()
+                    },
+                )]),
+                ..Default::default()
+            },
+            tracked_evaluations: HashMap::from([(org, 1)]),
+            ..Default::default()
+        };
+        let mut requeued = HashMap::new();
+
+        let (target, reason) = decide_evaluation_quota_recovery(&gate, Some(org), &mut requeued);
+        assert_eq!(target, EvaluationStatus::Queued);
+        assert!(reason.is_none());
+
+        let (target, reason) = decide_evaluation_quota_recovery(&gate, Some(org), &mut requeued);
+        assert_eq!(target, EvaluationStatus::Waiting);
+        assert_eq!(
+            reason,
+            Some(WaitingReason::quota(QuotaKind::ConcurrentEvaluations, 2, 2))
+        );
+
+        // An eval whose org cannot be resolved is never held.
+        let (target, _) = decide_evaluation_quota_recovery(&gate, None, &mut requeued);
+        assert_eq!(target, EvaluationStatus::Queued);
+    }
+
     #[test]
     fn eval_recovery_refreshes_reason_while_capability_absent() {
         let (target, reason) = decide_eval_recovery(EvalCapability::Fetch, 5, 0, 5);
@@ -677,4 +834,38 @@ mod tests {
         assert_eq!(cap, EvalCapability::Fetch);
         assert_eq!(connected, 5);
     }
+
+    /// A selector narrows the pool the build phase is assessed against to the
+    /// workers whose labels match for the evaluation's organization.
+    #[test]
+    fn matching_caps_keeps_only_selected_workers() {
+        let org = OrganizationId::now_v7();
+        let pool = |v: &str| Labels::from([("pool".to_string(), v.to_string())]);
+        let workers = vec![
+            (
+                vec!["x86_64-linux".to_string()],
+                vec![],
+                WorkerLabels {
+                    advertised: pool("untrusted"),
+                    ..Default::default()
+                },
+            ),
+            (
+                vec!["aarch64-linux".to_string()],
+                vec!["kvm".to_string()],
+                WorkerLabels {
+                    advertised: Labels::new(),
+                    registered: std::collections::BTreeMap::from([(org, pool("secure"))]),
+                },
+            ),
+        ];
+
+        let caps = matching_caps(&workers, org, &pool("secure"));
+        assert_eq!(
+            caps,
+            vec![(vec!["aarch64-linux".to_string()], vec!["kvm".to_string()])]
+        );
+        assert!(matching_caps(&workers, OrganizationId::now_v7(), &pool("secure")).is_empty());
+        assert_eq!(matching_caps(&workers, org, &Labels::new()).len(), 2);
+    }
 }
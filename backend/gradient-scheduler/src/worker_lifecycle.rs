@@ -6,7 +6,7 @@
 
 //! `Scheduler` methods for worker connect / disconnect / capability management.
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::sync::Arc;
 
 use anyhow::Result;
@@ -16,8 +16,10 @@ use sea_orm::{
 };
 use tracing::{debug, info, warn};
 
+use gradient_types::Labels;
 use gradient_types::ids::OrganizationId;
 use gradient_types::proto::GradientCapabilities;
+use gradient_types::worker_labels::labels_from_json;
 
 use crate::Scheduler;
 use crate::build;
@@ -103,10 +105,38 @@ impl Scheduler {
             authorized_peers,
         );
         info!(%worker_id, "worker registered");
+        self.load_registration_state(worker_id).await;
         self.record_worker_connection(worker_id, caps_json).await;
         (notify, abort_rx)
     }
 
+    /// Cache the labels each active registration assigns to `worker_id` on the
+    /// pool, keyed by the registering organization, together with the
+    /// organizations that currently cordon it.
+    async fn load_registration_state(&self, worker_id: &str) {
+        let regs = gradient_entity::worker_registration::Entity::find()
+            .filter(gradient_entity::worker_registration::Column::WorkerId.eq(worker_id))
+            .filter(gradient_entity::worker_registration::Column::Active.eq(true))
+            .all(&self.state.worker_db)
+            .await;
+        let regs = match regs {
+            Ok(regs) => regs,
+            Err(e) => {
+                warn!(error = %e, %worker_id, "failed to load worker registrations");
+                return;
+            }
+        };
+        let cordoned = crate::cordon::cordoned_orgs(&regs, gradient_types::now());
+        let registered: BTreeMap<OrganizationId, Labels> = regs
+            .into_iter()
+            .map(|reg| (reg.peer_id, labels_from_json(reg.labels.as_ref())))
+            .filter(|(_, labels)| !labels.is_empty())
+            .collect();
+        let mut pool = self.worker_pool.write().await;
+        pool.set_registration_labels(worker_id, registered);
+        pool.set_cordoned(worker_id, cordoned);
+    }
+
     /// Resolve the worker's owning org from `worker_registration`, cache it on
     /// the pool for sample attribution, and open a `worker_connection` row.
     async fn record_worker_connection(&self, worker_id: &str, capabilities: serde_json::Value) {
@@ -174,6 +204,9 @@ impl Scheduler {
             .write()
             .await
             .update_authorized_peers(worker_id, authorized_peers);
+        // Re-auth follows a registration change, which may have edited labels
+        // or the cordon.
+        self.load_registration_state(worker_id).await;
         debug!(%worker_id, "authorized peers updated");
     }
 
@@ -231,6 +264,7 @@ impl Scheduler {
         cpu_count: u32,
         ram_total_mb: u64,
         cpu_core_score: u32,
+        labels: Labels,
     ) {
         self.worker_pool.write().await.update_capabilities(
             worker_id,
@@ -240,6 +274,7 @@ impl Scheduler {
             cpu_count,
             ram_total_mb,
             cpu_core_score,
+            labels,
         );
         debug!(%worker_id, "worker capabilities updated");
         // Capabilities just changed - a build that was previously "no worker can
@@ -298,21 +333,29 @@ impl Scheduler {
         self.kick_dispatch();
     }
 
-    /// Snapshot every connected worker's `(architectures, system_features)`
-    /// plus the counts of those advertising the `eval` and `fetch`
+    /// Snapshot every connected worker's `(architectures, system_features,
+    /// labels)` plus the counts of those advertising the `eval` and `fetch`
     /// capabilities, then reconcile each in-flight evaluation's status. See
     /// [`build::reconcile_waiting_state`].
     pub async fn reconcile_waiting_state(&self) -> Result<()> {
         let workers = self.worker_pool.read().await.all_workers();
         let eval_capable = workers.iter().filter(|w| w.capabilities.eval).count();
         let fetch_capable = workers.iter().filter(|w| w.capabilities.fetch).count();
-        let caps: Vec<(Vec<String>, Vec<String>)> = workers
+        let caps: Vec<(Vec<String>, Vec<String>, gradient_types::WorkerLabels)> = workers
             .into_iter()
-            .map(|w| (w.architectures, w.system_features))
+            .map(|w| (w.architectures, w.system_features, w.labels))
             .collect();
         let draining = self.draining.load(std::sync::atomic::Ordering::Relaxed);
-        build::reconcile_waiting_state(&self.state, &caps, eval_capable, fetch_capable, draining)
-            .await
+        let quotas = self.job_tracker.read().await.quota_gate();
+        build::reconcile_waiting_state(
+            &self.state,
+            &caps,
+            eval_capable,
+            fetch_capable,
+            draining,
+            &quotas,
+        )
+        .await
     }
 
     /// Snapshot of every connected worker for the Job Board (includes the
@@ -325,4 +368,9 @@ impl Scheduler {
         self.worker_pool.write().await.mark_draining(worker_id);
         info!(%worker_id, "worker marked draining");
     }
+
+    pub async fn mark_worker_active(&self, worker_id: &str) {
+        self.worker_pool.write().await.mark_active(worker_id);
+        info!(%worker_id, "worker returned to service");
+    }
 }
@@ -11,7 +11,7 @@
 //! only ever performed on `Active` workers, so the compiler prevents the class
 //! of bug where a draining worker accidentally receives a new job assignment.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicI64, Ordering};
 
@@ -19,6 +19,7 @@ use tokio::sync::{Notify, mpsc};
 
 use gradient_types::ids::OrganizationId;
 use gradient_types::proto::{GradientCapabilities, JobKind};
+use gradient_types::{Labels, WorkerLabels};
 
 use crate::peer_auth::PeerAuth;
 use crate::worker_state::{Active, Draining, TypedWorker};
@@ -77,6 +78,9 @@ pub struct WorkerPool {
     /// Owning organization per worker, resolved from `worker_registration` at
     /// connect time. Used to attribute worker_sample / worker_connection rows.
     worker_orgs: HashMap<String, OrganizationId>,
+    /// Workers a superuser cordoned for every organization. Kept by worker id
+    /// rather than on the slot so the cordon survives a reconnect.
+    worker_cordons: HashSet<String>,
 }
 
 impl WorkerPool {
@@ -109,6 +113,7 @@ impl WorkerPool {
                 s.cpu_count,
                 s.ram_total_mb,
                 s.cpu_core_score,
+                s.labels.clone(),
             )
         });
         let notify = Arc::new(Notify::new());
@@ -127,6 +132,7 @@ impl WorkerPool {
             cpu_count,
             ram_total_mb,
             cpu_core_score,
+            labels,
         )) = prior
             && let Some(slot) = self.workers.get_mut(&id)
         {
@@ -137,6 +143,7 @@ impl WorkerPool {
             s.cpu_count = cpu_count;
             s.ram_total_mb = ram_total_mb;
             s.cpu_core_score = cpu_core_score;
+            s.labels = labels;
         }
         (notify, abort_rx)
     }
@@ -170,6 +177,63 @@ impl WorkerPool {
         }
     }
 
+    /// Replace the per-organization registration labels of a connected worker.
+    pub fn set_registration_labels(
+        &mut self,
+        id: &str,
+        registered: BTreeMap<OrganizationId, Labels>,
+    ) {
+        if let Some(slot) = self.workers.get_mut(id) {
+            slot.shared_mut().labels.registered = registered;
+        }
+    }
+
+    /// Replace the organizations whose registration currently cordons a
+    /// connected worker. Returns `false` when the worker is not connected.
+    pub fn set_cordoned(&mut self, id: &str, cordoned: HashSet<OrganizationId>) -> bool {
+        match self.workers.get_mut(id) {
+            Some(slot) => {
+                slot.shared_mut().cordoned = cordoned;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Organizations currently cordoning a worker, or `None` if not connected.
+    pub fn cordoned_by(&self, id: &str) -> Option<&HashSet<OrganizationId>> {
+        self.workers.get(id).map(|slot| &slot.shared().cordoned)
+    }
+
+    /// Cordon (`true`) or uncordon (`false`) a worker for every organization,
+    /// connected or not. Returns whether the cordon changed.
+    pub fn set_worker_cordoned(&mut self, id: &str, cordoned: bool) -> bool {
+        if cordoned {
+            self.worker_cordons.insert(id.to_owned())
+        } else {
+            self.worker_cordons.remove(id)
+        }
+    }
+
+    /// Whether a superuser cordoned the worker for every organization.
+    pub fn is_worker_cordoned(&self, id: &str) -> bool {
+        self.worker_cordons.contains(id)
+    }
+
+    /// Organizations whose jobs may be offered to or assigned on a worker: its
+    /// authorized peers minus the ones that cordoned it, or none at all while
+    /// the worker itself is cordoned. `None` in open mode (no filtering) or
+    /// when the worker is not connected.
+    pub fn offer_filter(&self, id: &str) -> Option<HashSet<OrganizationId>> {
+        let s = self.workers.get(id)?.shared();
+        if self.worker_cordons.contains(id) {
+            return Some(HashSet::new());
+        }
+        s.peer_auth
+            .as_filter()
+            .map(|peers| peers.difference(&s.cordoned).copied().collect())
+    }
+
     /// Returns the peer-auth mode for a worker, or `None` if not connected.
     pub fn peer_auth_for(&self, id: &str) -> Option<&PeerAuth> {
         self.workers.get(id).map(|slot| &slot.shared().peer_auth)
@@ -194,6 +258,8 @@ impl WorkerPool {
                 system_features: s.system_features.clone(),
                 capabilities: s.capabilities.clone(),
                 metrics: self.metrics_for(id),
+                labels: s.labels.clone(),
+                worker_id: id.to_owned(),
             }
         })
     }
@@ -211,6 +277,7 @@ impl WorkerPool {
         cpu_count: u32,
         ram_total_mb: u64,
         cpu_core_score: u32,
+        labels: Labels,
     ) {
         if let Some(slot) = self.workers.get_mut(id) {
             let s = slot.shared_mut();
@@ -220,6 +287,7 @@ impl WorkerPool {
             s.cpu_count = cpu_count;
             s.ram_total_mb = ram_total_mb;
             s.cpu_core_score = cpu_core_score;
+            s.labels.advertised = labels;
         }
     }
 
@@ -281,6 +349,18 @@ impl WorkerPool {
         }
     }
 
+    /// Return a draining worker to the active state so it is offered new
+    /// jobs again.
+    pub fn mark_active(&mut self, id: &str) {
+        if let Some(slot) = self.workers.remove(id) {
+            let new_slot = match slot {
+                WorkerSlot::Draining(w) => WorkerSlot::Active(w.into_active()),
+                already_active => already_active,
+            };
+            self.workers.insert(id.to_owned(), new_slot);
+        }
+    }
+
     /// Mark a batch of job IDs as sent to `worker_id` so they are not
     /// re-included in the next delta `JobOffer`.
     pub fn mark_candidates_sent(&mut self, worker_id: &str, job_ids: &[String]) {
@@ -386,6 +466,9 @@ impl WorkerPool {
             ram_total_mb: s.ram_total_mb,
             disk_speed_mbps: s.disk_speed_mbps,
             network_speed_mbps: s.network_speed_mbps,
+            labels: s.labels.clone(),
+            cordoned: s.cordoned.clone(),
+            worker_cordoned: self.worker_cordons.contains(id),
         }
     }
 
@@ -448,6 +531,13 @@ pub struct WorkerInfo {
     pub disk_speed_mbps: Option<f32>,
     #[serde(skip)]
     pub network_speed_mbps: Option<f32>,
+    #[serde(skip)]
+    pub labels: WorkerLabels,
+    /// Organizations that currently cordon this worker.
+    #[serde(skip)]
+    pub cordoned: HashSet<OrganizationId>,
+    /// Cordoned for every organization by a superuser.
+    pub worker_cordoned: bool,
 }
 
 // ── Tests ─────────────────────────────────────────────────────────────────────
@@ -543,6 +633,7 @@ mod tests {
             8,
             16384,
             1200,
+            Labels::new(),
         );
 
         let workers = pool.all_workers();
@@ -569,6 +660,7 @@ mod tests {
             8,
             16384,
             1200,
+            Labels::from([("pool".to_string(), "secure".to_string())]),
         );
 
         // A reconnect/re-auth re-registers without the worker re-sending caps;
@@ -579,16 +671,46 @@ mod tests {
         assert_eq!(workers[0].architectures, vec!["x86_64-linux"]);
         assert_eq!(workers[0].system_features, vec!["kvm"]);
         assert_eq!(workers[0].max_concurrent_builds, 4);
+        assert_eq!(workers[0].labels.advertised["pool"], "secure");
         let view = pool.metrics_for("w1").unwrap();
         assert_eq!(view.cpu_count, 8);
         assert_eq!(view.ram_total_mb, 16384);
     }
 
+    #[test]
+    fn only_registration_labels_are_matched_per_org() {
+        let org = OrganizationId::now_v7();
+        let mut pool = WorkerPool::new();
+        pool.register("w1".into(), caps(), HashSet::new());
+        pool.update_capabilities(
+            "w1",
+            vec![],
+            vec![],
+            1,
+            0,
+            0,
+            0,
+            Labels::from([("pool".to_string(), "untrusted".to_string())]),
+        );
+        pool.set_registration_labels(
+            "w1",
+            BTreeMap::from([(
+                org,
+                Labels::from([("pool".to_string(), "secure".to_string())]),
+            )]),
+        );
+
+        let labels = pool.worker_caps("w1").unwrap().labels;
+        assert_eq!(labels.get(org, "pool"), Some("secure"));
+        assert_eq!(labels.get(OrganizationId::now_v7(), "pool"), None);
+        assert_eq!(labels.advertised["pool"], "untrusted");
+    }
+
     #[test]
     fn test_update_metrics_updates_view() {
         let mut pool = WorkerPool::new();
         pool.register("w1".into(), caps(), HashSet::new());
-        pool.update_capabilities("w1", vec![], vec![], 1, 4, 8192, 1000);
+        pool.update_capabilities("w1", vec![], vec![], 1, 4, 8192, 1000, Labels::new());
 
         // Before any heartbeat the dynamic fields are absent, not zero.
         let view = pool.metrics_for("w1").unwrap();
@@ -630,6 +752,7 @@ mod tests {
             8,
             16384,
             1200,
+            Labels::new(),
         );
         pool.update_metrics("w1", 12.5, 9000, None, None);
 
@@ -654,13 +777,16 @@ mod tests {
         pool.mark_draining("w1");
         let info = &pool.all_workers()[0];
         assert!(info.draining);
+
+        pool.mark_active("w1");
+        assert!(!pool.all_workers()[0].draining);
     }
 
     #[test]
     fn test_draining_worker_has_no_capacity() {
         let mut pool = WorkerPool::new();
         pool.register("w1".into(), caps(), HashSet::new());
-        pool.update_capabilities("w1", vec![], vec![], 10, 0, 0, 0);
+        pool.update_capabilities("w1", vec![], vec![], 10, 0, 0, 0, Labels::new());
 
         // Active worker has capacity.
         assert!(pool.has_capacity("w1", &JobKind::Build));
@@ -714,6 +840,46 @@ mod tests {
         assert!(matches!(pool.peer_auth_for("w1").unwrap(), PeerAuth::Open));
     }
 
+    #[test]
+    fn offer_filter_excludes_cordoning_orgs() {
+        let mut pool = WorkerPool::new();
+        let org_a = OrganizationId::now_v7();
+        let org_b = OrganizationId::now_v7();
+        pool.register("w1".into(), caps(), HashSet::from([org_a, org_b]));
+        assert_eq!(pool.offer_filter("w1"), Some(HashSet::from([org_a, org_b])));
+
+        assert!(pool.set_cordoned("w1", HashSet::from([org_a])));
+        assert_eq!(pool.offer_filter("w1"), Some(HashSet::from([org_b])));
+        assert_eq!(pool.cordoned_by("w1"), Some(&HashSet::from([org_a])));
+
+        pool.set_cordoned("w1", HashSet::new());
+        assert_eq!(pool.offer_filter("w1"), Some(HashSet::from([org_a, org_b])));
+        assert!(!pool.set_cordoned("ghost", HashSet::new()));
+    }
+
+    #[test]
+    fn worker_cordon_offers_nothing_and_survives_reconnect() {
+        let mut pool = WorkerPool::new();
+        let org = OrganizationId::now_v7();
+        pool.register("w1".into(), caps(), HashSet::from([org]));
+        pool.register("w2".into(), caps(), HashSet::new());
+
+        assert!(pool.set_worker_cordoned("w1", true));
+        assert!(pool.set_worker_cordoned("w2", true));
+        assert!(!pool.set_worker_cordoned("w1", true));
+        assert_eq!(pool.offer_filter("w1"), Some(HashSet::new()));
+        // Open mode filters nothing, but a cordon still applies.
+        assert_eq!(pool.offer_filter("w2"), Some(HashSet::new()));
+
+        pool.unregister("w1");
+        pool.register("w1".into(), caps(), HashSet::from([org]));
+        assert!(pool.is_worker_cordoned("w1"));
+        assert_eq!(pool.offer_filter("w1"), Some(HashSet::new()));
+
+        assert!(pool.set_worker_cordoned("w1", false));
+        assert_eq!(pool.offer_filter("w1"), Some(HashSet::from([org])));
+    }
+
     #[test]
     fn remove_sent_candidate_allows_reoffer() {
         // A build re-queued after a failed/rejected dispatch must lose its
@@ -735,7 +901,16 @@ mod tests {
         // Guards against `<` → `<=` off-by-one in `has_build_capacity`.
         let mut pool = WorkerPool::new();
         pool.register("w1".into(), caps(), HashSet::new());
-        pool.update_capabilities("w1", vec!["x86_64-linux".into()], vec![], 2, 0, 0, 0);
+        pool.update_capabilities(
+            "w1",
+            vec!["x86_64-linux".into()],
+            vec![],
+            2,
+            0,
+            0,
+            0,
+            Labels::new(),
+        );
 
         assert!(pool.has_capacity("w1", &JobKind::Build), "0/2 has capacity");
         pool.assign_job("w1", "j1");
@@ -774,7 +949,16 @@ mod tests {
         let mut pool = WorkerPool::new();
         pool.register("w1".into(), caps(), HashSet::new());
         pool.register("w2".into(), caps(), HashSet::new());
-        pool.update_capabilities("w1", vec!["x86_64-linux".into()], vec![], 2, 0, 0, 0);
+        pool.update_capabilities(
+            "w1",
+            vec!["x86_64-linux".into()],
+            vec![],
+            2,
+            0,
+            0,
+            0,
+            Labels::new(),
+        );
         pool.assign_job("w1", "j1");
         pool.mark_draining("w2");
 
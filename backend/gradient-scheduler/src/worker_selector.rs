@@ -0,0 +1,78 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Worker selectors of in-flight evaluations: the project's selector merged
+//! with that of the trigger which fired the evaluation. The selector
+//! constrains the evaluation's own flake job, and builds inherit the selector
+//! of their driving evaluation.
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{Context, Result};
+use gradient_types::worker_labels::{labels_from_json, merge_selector};
+use gradient_types::*;
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter};
+
+/// Owning organization and non-empty worker selector of each evaluation in
+/// `evals`. Evaluations without a selector are absent.
+pub(crate) async fn load_eval_selectors<'a>(
+    db: &impl ConnectionTrait,
+    evals: impl IntoIterator<Item = &'a MEvaluation>,
+) -> Result<HashMap<EvaluationId, (OrganizationId, Labels)>> {
+    let evals: Vec<&MEvaluation> = evals.into_iter().collect();
+    let project_ids: Vec<ProjectId> = evals
+        .iter()
+        .filter_map(|e| e.project)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let trigger_ids: Vec<ProjectTriggerId> = evals
+        .iter()
+        .filter_map(|e| e.trigger)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+
+    let projects: HashMap<ProjectId, (OrganizationId, Labels)> =
+        gradient_db::fetch_in_chunks(&project_ids, |chunk| async move {
+            EProject::find()
+                .filter(CProject::Id.is_in(chunk))
+                .all(db)
+                .await
+        })
+        .await
+        .context("fetch evaluation projects")?
+        .into_iter()
+        .map(|p| {
+            let selector = labels_from_json(p.worker_selector.as_ref());
+            (p.id, (p.organization, selector))
+        })
+        .collect();
+    let triggers: HashMap<ProjectTriggerId, Labels> =
+        gradient_db::fetch_in_chunks(&trigger_ids, |chunk| async move {
+            EProjectTrigger::find()
+                .filter(CProjectTrigger::Id.is_in(chunk))
+                .all(db)
+                .await
+        })
+        .await
+        .context("fetch evaluation triggers")?
+        .into_iter()
+        .map(|t| (t.id, labels_from_json(t.worker_selector.as_ref())))
+        .collect();
+
+    Ok(evals
+        .into_iter()
+        .filter_map(|e| {
+            let (org, project_selector) = projects.get(&e.project?)?;
+            let selector = match e.trigger.and_then(|t| triggers.get(&t)) {
+                Some(trigger_selector) => merge_selector(project_selector, trigger_selector),
+                None => project_selector.clone(),
+            };
+            (!selector.is_empty()).then_some((e.id, (*org, selector)))
+        })
+        .collect())
+}
@@ -25,6 +25,7 @@ use std::sync::atomic::AtomicI64;
 use gradient_types::ids::OrganizationId;
 use tokio::sync::{Notify, mpsc};
 
+use gradient_types::WorkerLabels;
 use gradient_types::proto::GradientCapabilities;
 
 use crate::peer_auth::PeerAuth;
@@ -70,6 +71,12 @@ pub struct WorkerShared {
     pub cpu_count: u32,
     pub ram_total_mb: u64,
     pub cpu_core_score: u32,
+    /// Advertised labels plus the per-organization registration labels,
+    /// matched against project worker selectors.
+    pub labels: WorkerLabels,
+    /// Organizations whose registration cordons this worker (manually or in a
+    /// maintenance window): their jobs are no longer offered or assigned here.
+    pub cordoned: HashSet<OrganizationId>,
     /// Latest live-metrics heartbeat; `None` until the first report so scoring
     /// can tell "no sample yet" apart from a measured zero.
     pub cpu_usage_pct: Option<f32>,
@@ -135,6 +142,8 @@ impl TypedWorker<Active> {
                 cpu_count: 0,
                 ram_total_mb: 0,
                 cpu_core_score: 0,
+                labels: WorkerLabels::default(),
+                cordoned: HashSet::new(),
                 cpu_usage_pct: None,
                 ram_free_mb: None,
                 disk_speed_mbps: None,
@@ -171,3 +180,14 @@ impl TypedWorker<Active> {
         }
     }
 }
+
+impl TypedWorker<Draining> {
+    /// Consume this draining worker and return it to service, keeping its
+    /// in-flight assigned jobs.
+    pub fn into_active(self) -> TypedWorker<Active> {
+        TypedWorker {
+            shared: self.shared,
+            _state: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,420 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Declarative scoring policies, loaded from a JSON file instead of compiled
+//! in. A policy picks rules by their persisted name (see
+//! [`crate::rule_catalog`]), optionally starting from a built-in table, and
+//! tunes each one with a weight multiplier and a veto toggle:
+//!
+//! ```json
+//! { "policies": [ {
+//!     "name": "mixed-fleet",
+//!     "extends": "resource-aware",
+//!     "rules": {
+//!       "ResourceFitRule": { "weight": 2.0 },
+//!       "RescoreWaitRule": { "veto": false },
+//!       "DiskAffinityRule": { "enabled": false }
+//!     }
+//! } ] }
+//! ```
+//!
+//! Loading never fails hard: a broken file or policy yields a [`LoadedPolicy`]
+//! that keeps scoring with a working policy and lists every validation error
+//! in its [`PolicyStatus`], which the board serves next to the rule catalog.
+
+use crate::policy::{
+    BUILTIN_POLICIES, RulePolicy, ScoringPolicy, WeightedRule, builtin_rules, policy_by_name,
+    rule_by_name,
+};
+use crate::rules::{WORKER_SELECTOR_RULE, WorkerSelectorRule};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+
+/// Top-level shape of the policy file.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyFile {
+    #[serde(default)]
+    pub policies: Vec<PolicyDefinition>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyDefinition {
+    pub name: String,
+    /// Built-in policy whose enabled rules this one starts from. Without it,
+    /// only the rules listed in `rules` are enabled.
+    #[serde(default)]
+    pub extends: Option<String>,
+    /// Whether the scheduler loads build history for this policy. Defaults to
+    /// the extended policy's setting, or `true` without one.
+    #[serde(default)]
+    pub uses_history: Option<bool>,
+    /// Per-rule settings keyed by rule name. Listing a rule enables it unless
+    /// `enabled` is `false`.
+    #[serde(default)]
+    pub rules: BTreeMap<String, RuleSetting>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuleSetting {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Multiplier on the rule's contribution; must be finite and >= 0.
+    #[serde(default = "default_weight")]
+    pub weight: f64,
+    /// Honor the rule's hold sentinel. `false` keeps its score but lets the
+    /// job dispatch even when the rule would veto it.
+    #[serde(default = "default_true")]
+    pub veto: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_weight() -> f64 {
+    1.0
+}
+
+/// One enabled rule of the active policy.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActiveRule {
+    pub rule: String,
+    pub weight: f64,
+    pub veto: bool,
+}
+
+/// What the scheduler is scoring with, and why.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PolicyStatus {
+    /// Policy name from the server configuration.
+    pub requested: String,
+    /// Policy actually in use; differs from `requested` when it failed to load.
+    pub active: String,
+    /// Policy file, when one is configured.
+    pub source: Option<String>,
+    pub rules: Vec<ActiveRule>,
+    /// Validation errors from the last load. Non-empty errors leave the
+    /// previous (or built-in) policy in place.
+    pub errors: Vec<String>,
+    pub loaded_at: Option<chrono::NaiveDateTime>,
+}
+
+/// A policy ready to score with, plus its status.
+#[derive(Debug, Clone)]
+pub struct LoadedPolicy {
+    pub policy: Arc<dyn ScoringPolicy>,
+    pub status: PolicyStatus,
+}
+
+impl PolicyDefinition {
+    /// Build the policy, collecting every validation error instead of stopping
+    /// at the first.
+    pub fn build(&self) -> Result<RulePolicy, Vec<String>> {
+        let mut errors = Vec::new();
+        let mut rules: Vec<WeightedRule> = Vec::new();
+        let mut uses_history = true;
+
+        if let Some(base) = &self.extends {
+            match builtin_rules(base) {
+                Some((base_rules, base_history)) => {
+                    rules = base_rules.into_iter().map(WeightedRule::new).collect();
+                    uses_history = base_history;
+                }
+                None => errors.push(format!(
+                    "policy {:?}: unknown base policy {base:?} (expected one of {})",
+                    self.name,
+                    BUILTIN_POLICIES.join(", ")
+                )),
+            }
+        }
+
+        for (name, setting) in &self.rules {
+            let Some(rule) = rule_by_name(name) else {
+                errors.push(format!("policy {:?}: unknown rule {name:?}", self.name));
+                continue;
+            };
+            if !setting.weight.is_finite() || setting.weight < 0.0 {
+                errors.push(format!(
+                    "policy {:?}: rule {name:?} weight must be a finite number >= 0",
+                    self.name
+                ));
+                continue;
+            }
+            if rule.name() == WORKER_SELECTOR_RULE && (!setting.enabled || !setting.veto) {
+                errors.push(format!(
+                    "policy {:?}: rule {name:?} cannot be disabled or lose its veto",
+                    self.name
+                ));
+                continue;
+            }
+            let existing = rules.iter().position(|r| r.rule.name() == rule.name());
+            match (setting.enabled, existing) {
+                (false, Some(i)) => {
+                    rules.remove(i);
+                }
+                (false, None) => {}
+                (true, Some(i)) => {
+                    rules[i].weight = setting.weight;
+                    rules[i].veto = setting.veto;
+                }
+                (true, None) => rules.push(WeightedRule {
+                    rule,
+                    weight: setting.weight,
+                    veto: setting.veto,
+                }),
+            }
+        }
+
+        if errors.is_empty() && rules.is_empty() {
+            errors.push(format!("policy {:?}: enables no rules", self.name));
+        }
+        // Worker selectors are a placement constraint, not a tunable
+        // preference, so every policy enforces them.
+        if !rules.iter().any(|r| r.rule.name() == WORKER_SELECTOR_RULE) {
+            rules.push(WeightedRule::new(Box::new(WorkerSelectorRule)));
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(RulePolicy::weighted(
+            self.name.clone(),
+            rules,
+            self.uses_history.unwrap_or(uses_history),
+        ))
+    }
+}
+
+impl PolicyFile {
+    pub fn parse(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| format!("invalid policy file: {e}"))
+    }
+
+    /// Every error in the file: duplicate or reserved names plus each
+    /// policy's own validation errors.
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        let mut seen = HashSet::new();
+        for def in &self.policies {
+            if BUILTIN_POLICIES.contains(&def.name.as_str()) {
+                errors.push(format!(
+                    "policy {:?}: name is reserved for a built-in policy",
+                    def.name
+                ));
+            } else if !seen.insert(def.name.as_str()) {
+                errors.push(format!("policy {:?}: defined more than once", def.name));
+            }
+            if let Err(e) = def.build() {
+                errors.extend(e);
+            }
+        }
+        errors
+    }
+}
+
+/// Resolve the policy named `name` from the built-ins and the (optional)
+/// policy file. On any error the built-in `resource-aware` policy is used and
+/// the errors are reported in the status.
+pub fn load_policy(name: &str, source: Option<&str>) -> LoadedPolicy {
+    let mut errors = Vec::new();
+    let file = match source {
+        Some(path) => match std::fs::read_to_string(path) {
+            Ok(raw) => match PolicyFile::parse(&raw) {
+                Ok(file) => Some(file),
+                Err(e) => {
+                    errors.push(e);
+                    None
+                }
+            },
+            Err(e) => {
+                errors.push(format!("reading policy file {path}: {e}"));
+                None
+            }
+        },
+        None => None,
+    };
+    if let Some(file) = &file {
+        errors.extend(file.validate());
+    }
+
+    let defined = file
+        .as_ref()
+        .and_then(|f| f.policies.iter().find(|p| p.name == name));
+    let policy: Arc<dyn ScoringPolicy> = match defined {
+        Some(def) if errors.is_empty() => match def.build() {
+            Ok(policy) => Arc::new(policy),
+            Err(e) => {
+                errors.extend(e);
+                policy_by_name("resource-aware")
+            }
+        },
+        Some(_) => policy_by_name("resource-aware"),
+        None if BUILTIN_POLICIES.contains(&name) => policy_by_name(name),
+        None => {
+            // A missing file already explains why the name is unknown.
+            if file.is_some() || source.is_none() {
+                errors.push(format!("unknown scoring policy {name:?}"));
+            }
+            policy_by_name("resource-aware")
+        }
+    };
+
+    if !errors.is_empty() {
+        tracing::warn!(policy = name, ?errors, "scoring policy has errors");
+    }
+    LoadedPolicy {
+        status: PolicyStatus {
+            requested: name.to_string(),
+            active: policy.name().to_string(),
+            source: source.map(str::to_owned),
+            rules: policy.active_rules(),
+            errors,
+            loaded_at: Some(gradient_types::now()),
+        },
+        policy,
+    }
+}
+
+/// Hot-reload step: load again, but when the requested policy no longer
+/// builds keep scoring with `previous` and only surface the new errors.
+pub fn reload_policy(previous: &LoadedPolicy, name: &str, source: Option<&str>) -> LoadedPolicy {
+    let fresh = load_policy(name, source);
+    if fresh.status.errors.is_empty() {
+        return fresh;
+    }
+    LoadedPolicy {
+        policy: Arc::clone(&previous.policy),
+        status: PolicyStatus {
+            active: previous.status.active.clone(),
+            rules: previous.status.rules.clone(),
+            ..fresh.status
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn def(json: &str) -> PolicyDefinition {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn extends_builtin_and_tunes_rules() {
+        let policy = def(r#"{
+            "name": "fleet",
+            "extends": "simple",
+            "rules": {
+                "WaitTimeRule": { "weight": 2.5 },
+                "RescoreWaitRule": { "veto": false },
+                "BuiltinDeprioritizeRule": { "enabled": false },
+                "DiskAffinityRule": {}
+            }
+        }"#)
+        .build()
+        .unwrap();
+        let rules = policy.active_rules();
+        assert_eq!(policy.name(), "fleet");
+        assert!(!policy.uses_history(), "inherits simple's history setting");
+        assert!(!rules.iter().any(|r| r.rule == "BuiltinDeprioritizeRule"));
+        let wait = rules.iter().find(|r| r.rule == "WaitTimeRule").unwrap();
+        assert_eq!(wait.weight, 2.5);
+        let rescore = rules.iter().find(|r| r.rule == "RescoreWaitRule").unwrap();
+        assert!(!rescore.veto);
+        assert_eq!(rules.last().unwrap().rule, "DiskAffinityRule");
+    }
+
+    #[test]
+    fn without_base_only_listed_rules_are_enabled() {
+        let policy = def(r#"{ "name": "p", "rules": { "FairShareRule": { "weight": 0.5 } } }"#)
+            .build()
+            .unwrap();
+        assert_eq!(
+            policy.active_rules(),
+            vec![
+                ActiveRule {
+                    rule: "FairShareRule".into(),
+                    weight: 0.5,
+                    veto: true,
+                },
+                ActiveRule {
+                    rule: "WorkerSelectorRule".into(),
+                    weight: 1.0,
+                    veto: true,
+                },
+            ]
+        );
+        assert!(policy.uses_org_work_share());
+    }
+
+    #[test]
+    fn build_collects_every_error() {
+        let errors = def(r#"{
+            "name": "bad",
+            "extends": "fancy",
+            "rules": { "NoSuchRule": {}, "WaitTimeRule": { "weight": -1 } }
+        }"#)
+        .build()
+        .unwrap_err();
+        assert_eq!(errors.len(), 3, "{errors:?}");
+    }
+
+    #[test]
+    fn worker_selector_rule_cannot_be_disabled() {
+        for setting in [r#"{ "enabled": false }"#, r#"{ "veto": false }"#] {
+            let errors = def(&format!(
+                r#"{{ "name": "p", "extends": "simple", "rules": {{ "WorkerSelectorRule": {setting} }} }}"#
+            ))
+            .build()
+            .unwrap_err();
+            assert_eq!(errors.len(), 1, "{errors:?}");
+        }
+    }
+
+    #[test]
+    fn file_rejects_reserved_and_duplicate_names() {
+        let file = PolicyFile::parse(
+            r#"{ "policies": [
+                { "name": "simple", "extends": "simple" },
+                { "name": "a", "extends": "simple" },
+                { "name": "a", "extends": "simple" }
+            ] }"#,
+        )
+        .unwrap();
+        let errors = file.validate();
+        assert_eq!(errors.len(), 2, "{errors:?}");
+    }
+
+    #[test]
+    fn unknown_fields_are_rejected() {
+        assert!(PolicyFile::parse(r#"{ "policies": [ { "name": "a", "wieght": 1 } ] }"#).is_err());
+    }
+
+    #[test]
+    fn load_without_file_resolves_builtins_and_reports_unknown() {
+        let loaded = load_policy("simple", None);
+        assert_eq!(loaded.status.active, "simple");
+        assert!(loaded.status.errors.is_empty());
+        assert_eq!(loaded.status.rules.len(), 8);
+
+        let loaded = load_policy("nonsense", None);
+        assert_eq!(loaded.status.active, "resource-aware");
+        assert_eq!(loaded.status.errors.len(), 1);
+    }
+
+    #[test]
+    fn reload_keeps_previous_policy_on_errors() {
+        let previous = load_policy("simple", None);
+        let reloaded = reload_policy(&previous, "simple", Some("/nonexistent/policies.json"));
+        assert_eq!(reloaded.status.active, "simple");
+        assert_eq!(reloaded.status.rules, previous.status.rules);
+        assert_eq!(reloaded.status.errors.len(), 1);
+    }
+}
@@ -4,6 +4,11 @@
  * SPDX-License-Identifier: AGPL-3.0-only
  */
 
+use gradient_types::Labels;
+
+/// Selector of a job that carries none.
+static NO_SELECTOR: Labels = Labels::new();
+
 /// One fleet metric aggregated over three trailing windows. `None` means the
 /// window had no samples - distinct from a measured zero, which is honored
 /// instead of silently swapping in a rule's fallback constant.
@@ -69,6 +74,26 @@ pub struct WorkerMetricsView {
     pub network_speed_mbps: Option<f32>,
 }
 
+/// Where a build sits on its evaluation's remaining critical path:
+/// `remaining_ms` is its own predicted duration plus the longest predicted
+/// chain of pending dependents above it up to the entry points, and
+/// `eval_longest_ms` the longest such chain in the whole evaluation, so
+/// `remaining_ms == eval_longest_ms` marks the bottleneck chain.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CriticalPath {
+    pub remaining_ms: u64,
+    pub eval_longest_ms: u64,
+}
+
+impl CriticalPath {
+    /// This build's share of the evaluation's longest remaining path,
+    /// `0.0..=1.0`; `None` when the evaluation has no predicted duration.
+    pub fn share(self) -> Option<f64> {
+        (self.eval_longest_ms > 0)
+            .then(|| (self.remaining_ms as f64 / self.eval_longest_ms as f64).clamp(0.0, 1.0))
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct DerivationRef {
     pub build_id: String,
@@ -139,6 +164,7 @@ pub struct ScoredBuild<'a> {
     pub pname: Option<&'a str>,
     closure_size: Option<i64>,
     history: HistoryPrediction,
+    critical_path: Option<CriticalPath>,
 }
 
 impl ScoredBuild<'_> {
@@ -149,6 +175,12 @@ impl ScoredBuild<'_> {
     pub fn history(&self) -> HistoryPrediction {
         self.history
     }
+
+    /// `None` unless the scheduler precomputed it, which it does only for
+    /// policies that consume it.
+    pub fn critical_path(&self) -> Option<CriticalPath> {
+        self.critical_path
+    }
 }
 
 pub enum JobKindContext<'a> {
@@ -160,6 +192,7 @@ pub struct ScoredJob<'a> {
     pub job_id: &'a str,
     pub org_id: gradient_types::ids::OrganizationId,
     kind: JobKindContext<'a>,
+    worker_selector: &'a Labels,
 }
 
 impl<'a> ScoredJob<'a> {
@@ -176,6 +209,7 @@ impl<'a> ScoredJob<'a> {
                 fetch_flake,
                 history,
             }),
+            worker_selector: &NO_SELECTOR,
         }
     }
 
@@ -203,8 +237,30 @@ impl<'a> ScoredJob<'a> {
                 pname,
                 closure_size,
                 history,
+                critical_path: None,
             }),
+            worker_selector: &NO_SELECTOR,
+        }
+    }
+
+    /// Attach the build's precomputed critical-path position; a no-op for
+    /// evaluations.
+    pub fn with_critical_path(mut self, critical_path: Option<CriticalPath>) -> Self {
+        if let JobKindContext::Build(b) = &mut self.kind {
+            b.critical_path = critical_path;
         }
+        self
+    }
+
+    /// Attach the job's merged project and trigger worker selector.
+    pub fn with_worker_selector(mut self, selector: &'a Labels) -> Self {
+        self.worker_selector = selector;
+        self
+    }
+
+    /// Labels a worker must carry to take this job; empty matches any.
+    pub fn worker_selector(&self) -> &Labels {
+        self.worker_selector
     }
 
     pub fn kind(&self) -> &JobKindContext<'a> {
@@ -252,6 +308,18 @@ mod tests {
         assert_eq!(b.history(), HistoryPrediction::default());
     }
 
+    #[test]
+    fn critical_path_share_is_relative_to_the_longest_chain() {
+        let cp = CriticalPath {
+            remaining_ms: 30_000,
+            eval_longest_ms: 120_000,
+        };
+        let job = make_job().with_critical_path(Some(cp));
+        assert_eq!(job.build().unwrap().critical_path(), Some(cp));
+        assert_eq!(cp.share(), Some(0.25));
+        assert_eq!(CriticalPath::default().share(), None);
+    }
+
     #[test]
     fn scored_job_exposes_build_kind_context() {
         let job = make_job();
@@ -5,6 +5,7 @@
  */
 
 pub mod breakdown;
+pub mod config;
 pub mod context;
 pub mod policy;
 pub mod rule;
@@ -12,9 +13,16 @@ pub mod rules;
 pub mod weights;
 
 pub use breakdown::ScoreBreakdown;
+pub use config::{
+    ActiveRule, LoadedPolicy, PolicyDefinition, PolicyFile, PolicyStatus, RuleSetting, load_policy,
+    reload_policy,
+};
 pub use context::{
-    BuildContext, DerivationRef, EvalContext, HistoryPrediction, InstanceContext, JobKindContext,
-    ScoredBuild, ScoredJob, Windowed, WorkerMetricsView,
+    BuildContext, CriticalPath, DerivationRef, EvalContext, HistoryPrediction, InstanceContext,
+    JobKindContext, ScoredBuild, ScoredJob, Windowed, WorkerMetricsView,
+};
+pub use policy::{
+    BUILTIN_POLICIES, RulePolicy, ScoringPolicy, WeightedRule, policy_by_name, rule_by_name,
+    rule_catalog,
 };
-pub use policy::{RulePolicy, ScoringPolicy, policy_by_name, rule_catalog};
 pub use rule::{JobContext, ScoreRule, WorkerContext};
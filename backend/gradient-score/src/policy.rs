@@ -4,6 +4,7 @@
  * SPDX-License-Identifier: AGPL-3.0-only
  */
 
+use crate::config::ActiveRule;
 use crate::context::InstanceContext;
 use crate::rule::{JobContext, ScoreRule, WorkerContext};
 use crate::rules::builtin::{
@@ -11,8 +12,8 @@ use crate::rules::builtin::{
     RescoreWaitRule, ReserveFetchWorkersRule, WaitTimeRule,
 };
 use crate::rules::{
-    DiskAffinityRule, FairShareRule, NetworkAffinityRule, PreferLocalBuildRule, ResourceFitRule,
-    ResourceSaturationRule,
+    CriticalPathRule, DiskAffinityRule, FairShareRule, NetworkAffinityRule, PreferLocalBuildRule,
+    ResourceFitRule, ResourceSaturationRule, WorkerSelectorRule,
 };
 
 pub trait ScoringPolicy: Send + Sync + std::fmt::Debug {
@@ -43,31 +44,75 @@ pub trait ScoringPolicy: Send + Sync + std::fmt::Debug {
     fn uses_org_work_share(&self) -> bool {
         false
     }
+    /// Whether any enabled rule consumes a build's critical path, so the
+    /// scheduler skips the dependency-graph walk otherwise.
+    fn uses_critical_path(&self) -> bool {
+        false
+    }
+    /// Enabled rules with their tuning, for the board's active-policy view.
+    fn active_rules(&self) -> Vec<ActiveRule> {
+        Vec::new()
+    }
+}
+
+/// A rule plus its policy-level tuning: `weight` multiplies the rule's score
+/// and `veto = false` ignores its hold sentinel. The built-in tables use
+/// `1.0` / `true`, i.e. the rule exactly as written.
+#[derive(Debug)]
+pub struct WeightedRule {
+    pub rule: Box<dyn ScoreRule>,
+    pub weight: f64,
+    pub veto: bool,
+}
+
+impl WeightedRule {
+    pub fn new(rule: Box<dyn ScoreRule>) -> Self {
+        Self {
+            rule,
+            weight: 1.0,
+            veto: true,
+        }
+    }
 }
 
 #[derive(Debug)]
 pub struct RulePolicy {
-    name: &'static str,
-    rules: Vec<Box<dyn ScoreRule>>,
+    name: String,
+    rules: Vec<WeightedRule>,
     uses_history: bool,
     uses_org_work_share: bool,
+    uses_critical_path: bool,
 }
 
 impl RulePolicy {
-    pub fn new(name: &'static str, rules: Vec<Box<dyn ScoreRule>>, uses_history: bool) -> Self {
-        let uses_org_work_share = rules.iter().any(|r| r.uses_org_work_share());
-        Self {
+    pub fn new(
+        name: impl Into<String>,
+        rules: Vec<Box<dyn ScoreRule>>,
+        uses_history: bool,
+    ) -> Self {
+        Self::weighted(
             name,
+            rules.into_iter().map(WeightedRule::new).collect(),
+            uses_history,
+        )
+    }
+
+    pub fn weighted(name: impl Into<String>, rules: Vec<WeightedRule>, uses_history: bool) -> Self {
+        let uses_org_work_share = rules.iter().any(|r| r.rule.uses_org_work_share());
+        let uses_critical_path = rules.iter().any(|r| r.rule.uses_critical_path());
+        Self {
+            name: name.into(),
             rules,
             uses_history,
             uses_org_work_share,
+            uses_critical_path,
         }
     }
 }
 
 impl ScoringPolicy for RulePolicy {
     fn name(&self) -> &str {
-        self.name
+        &self.name
     }
 
     fn score(
@@ -78,7 +123,7 @@ impl ScoringPolicy for RulePolicy {
     ) -> f64 {
         self.rules
             .iter()
-            .map(|r| r.score(job, worker, instance))
+            .map(|r| r.rule.score(job, worker, instance) * r.weight)
             .sum()
     }
 
@@ -92,11 +137,11 @@ impl ScoringPolicy for RulePolicy {
         let mut vetoes = Vec::new();
         let mut total = 0.0;
         for r in &self.rules {
-            let s = r.score(job, worker, instance);
+            let s = r.rule.score(job, worker, instance) * r.weight;
             total += s;
-            rules.insert(r.name().to_string(), s);
-            if r.veto(job, worker, instance) {
-                vetoes.push(r.name().to_string());
+            rules.insert(r.rule.name().to_string(), s);
+            if r.veto && r.rule.veto(job, worker, instance) {
+                vetoes.push(r.rule.name().to_string());
             }
         }
         crate::ScoreBreakdown {
@@ -113,6 +158,21 @@ impl ScoringPolicy for RulePolicy {
     fn uses_org_work_share(&self) -> bool {
         self.uses_org_work_share
     }
+
+    fn uses_critical_path(&self) -> bool {
+        self.uses_critical_path
+    }
+
+    fn active_rules(&self) -> Vec<ActiveRule> {
+        self.rules
+            .iter()
+            .map(|r| ActiveRule {
+                rule: r.rule.name().to_string(),
+                weight: r.weight,
+                veto: r.veto,
+            })
+            .collect()
+    }
 }
 
 /// One row of the declarative policy table: the rule and whether the policy
@@ -136,6 +196,7 @@ fn simple_table() -> Vec<RuleSpec> {
         spec(true, Box::new(WaitTimeRule::default())),
         spec(true, Box::new(BuiltinDeprioritizeRule::default())),
         spec(true, Box::new(ReserveFetchWorkersRule::default())),
+        spec(true, Box::new(WorkerSelectorRule)),
     ]
 }
 
@@ -144,6 +205,7 @@ fn resource_aware_table() -> Vec<RuleSpec> {
     rules.push(spec(true, Box::new(ResourceFitRule::default())));
     rules.push(spec(true, Box::new(ResourceSaturationRule::default())));
     rules.push(spec(true, Box::new(PreferLocalBuildRule::default())));
+    rules.push(spec(true, Box::new(CriticalPathRule::default())));
     // Disabled: its idle gate counts zero-occupancy rather than spare capacity,
     // over-penalizing busy-but-fair orgs. Re-enabling is a scheduling-policy
     // decision (#476), made here by flipping the flag.
@@ -170,9 +232,10 @@ pub fn resource_aware_rules() -> Vec<Box<dyn ScoreRule>> {
 }
 
 /// `(name, description)` for every known scoring rule, so the board UI can show
-/// what each rule does. Built from the superset policy and deduplicated by name.
+/// what each rule does and policy files know what they may pick. Built from the
+/// superset table, disabled rules included, and deduplicated by name.
 pub fn rule_catalog() -> Vec<(&'static str, &'static str)> {
-    let mut catalog: Vec<(&'static str, &'static str)> = resource_aware_rules()
+    let mut catalog: Vec<(&'static str, &'static str)> = all_rules()
         .iter()
         .map(|r| (r.name(), r.description()))
         .collect();
@@ -181,17 +244,37 @@ pub fn rule_catalog() -> Vec<(&'static str, &'static str)> {
     catalog
 }
 
-pub fn policy_by_name(name: &str) -> std::sync::Arc<dyn ScoringPolicy> {
+/// Names of the policies compiled into the binary. Declarative policies may
+/// `extend` these but not reuse their names.
+pub const BUILTIN_POLICIES: [&str; 2] = ["simple", "resource-aware"];
+
+/// Every compiled rule, including ones no built-in policy ships enabled.
+fn all_rules() -> Vec<Box<dyn ScoreRule>> {
+    resource_aware_table().into_iter().map(|s| s.rule).collect()
+}
+
+/// Construct a rule by its persisted name, as listed in [`rule_catalog`].
+pub fn rule_by_name(name: &str) -> Option<Box<dyn ScoreRule>> {
+    all_rules().into_iter().find(|r| r.name() == name)
+}
+
+/// Enabled rules of a built-in policy and whether it consumes build history.
+pub(crate) fn builtin_rules(name: &str) -> Option<(Vec<Box<dyn ScoreRule>>, bool)> {
     match name {
-        "simple" => std::sync::Arc::new(RulePolicy::new("simple", simple_rules(), false)),
-        "resource-aware" => std::sync::Arc::new(RulePolicy::new(
-            "resource-aware",
-            resource_aware_rules(),
-            true,
-        )),
-        other => {
+        "simple" => Some((simple_rules(), false)),
+        "resource-aware" => Some((resource_aware_rules(), true)),
+        _ => None,
+    }
+}
+
+pub fn policy_by_name(name: &str) -> std::sync::Arc<dyn ScoringPolicy> {
+    match builtin_rules(name) {
+        Some((rules, uses_history)) => {
+            std::sync::Arc::new(RulePolicy::new(name, rules, uses_history))
+        }
+        None => {
             tracing::warn!(
-                policy = other,
+                policy = name,
                 "unknown scoring policy, using \"resource-aware\""
             );
             std::sync::Arc::new(RulePolicy::new(
@@ -207,6 +290,7 @@ pub fn policy_by_name(name: &str) -> std::sync::Arc<dyn ScoringPolicy> {
 mod tests {
     use super::*;
     use crate::context::{HistoryPrediction, ScoredJob};
+    use gradient_types::WorkerLabels;
     use gradient_types::ids::OrganizationId;
     use gradient_types::now;
 
@@ -229,13 +313,14 @@ mod tests {
             system_features: feats,
             fetch: false,
             metrics: None,
+            labels: WorkerLabels::empty(),
         }
     }
 
     #[test]
     fn rule_catalog_covers_every_rule_with_a_description() {
         let catalog = rule_catalog();
-        let rules = resource_aware_rules();
+        let rules = all_rules();
 
         assert_eq!(
             catalog.len(),
@@ -343,6 +428,7 @@ mod tests {
                 network_speed_mbps: Some(100.0),
                 ..Default::default()
             }),
+            labels: WorkerLabels::empty(),
         };
         let slow = WorkerContext {
             architectures: &archs,
@@ -352,6 +438,7 @@ mod tests {
                 network_speed_mbps: Some(5.0),
                 ..Default::default()
             }),
+            labels: WorkerLabels::empty(),
         };
         assert!(
             policy.score(&c, &fast, &InstanceContext::default())
@@ -442,6 +529,7 @@ mod tests {
     fn rule_names_are_pinned() {
         let expected = [
             "BuiltinDeprioritizeRule",
+            "CriticalPathRule",
             "DependencyCountRule",
             "DiskAffinityRule",
             "MissingNarSizeRule",
@@ -453,6 +541,7 @@ mod tests {
             "ResourceFitRule",
             "ResourceSaturationRule",
             "WaitTimeRule",
+            "WorkerSelectorRule",
         ];
         let mut got: Vec<&str> = resource_aware_rules().iter().map(|r| r.name()).collect();
         got.sort_unstable();
@@ -494,4 +583,11 @@ mod tests {
         assert!(!policy_by_name("resource-aware").uses_org_work_share());
         assert!(FairShareRule::default().uses_org_work_share());
     }
+
+    /// The dependency-graph walk is only paid for by policies that score it.
+    #[test]
+    fn critical_path_is_consumed_only_by_resource_aware() {
+        assert!(!policy_by_name("simple").uses_critical_path());
+        assert!(policy_by_name("resource-aware").uses_critical_path());
+    }
 }
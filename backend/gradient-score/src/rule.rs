@@ -4,6 +4,8 @@
  * SPDX-License-Identifier: AGPL-3.0-only
  */
 
+use gradient_types::WorkerLabels;
+
 use crate::context::{InstanceContext, ScoredJob, WorkerMetricsView};
 
 /// Everything the policy knows about the candidate job at scoring time.
@@ -31,6 +33,8 @@ pub struct WorkerContext<'a> {
     pub system_features: &'a [String],
     pub fetch: bool,
     pub metrics: Option<WorkerMetricsView>,
+    /// Advertised and per-organization registration labels.
+    pub labels: &'a WorkerLabels,
 }
 
 pub trait ScoreRule: Send + Sync + std::fmt::Debug {
@@ -65,6 +69,11 @@ pub trait ScoreRule: Send + Sync + std::fmt::Debug {
     fn uses_org_work_share(&self) -> bool {
         false
     }
+    /// Whether this rule reads a build's [`crate::CriticalPath`]; the
+    /// scheduler only walks the dependency graph when some enabled rule does.
+    fn uses_critical_path(&self) -> bool {
+        false
+    }
     /// Human-readable explanation of what the rule rewards or penalizes, surfaced
     /// in the board UI next to the rule name.
     fn description(&self) -> &'static str;
@@ -107,6 +107,7 @@ impl ScoreRule for DiskAffinityRule {
 mod tests {
     use super::*;
     use crate::context::{HistoryPrediction, ScoredJob, WorkerMetricsView};
+    use gradient_types::WorkerLabels;
     use gradient_types::ids::OrganizationId;
 
     fn job(is_fixed_output: bool, h: HistoryPrediction) -> ScoredJob<'static> {
@@ -142,6 +143,7 @@ mod tests {
             system_features: &[],
             fetch: false,
             metrics: Some(metrics),
+            labels: WorkerLabels::empty(),
         }
     }
 
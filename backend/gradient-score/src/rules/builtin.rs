@@ -334,6 +334,7 @@ impl ScoreRule for RescoreWaitRule {
 mod tests {
     use super::*;
     use crate::context::{HistoryPrediction, ScoredJob};
+    use gradient_types::WorkerLabels;
     use gradient_types::ids::OrganizationId;
 
     fn build_job(arch: &'static str) -> ScoredJob<'static> {
@@ -364,6 +365,7 @@ mod tests {
             system_features: &[],
             fetch,
             metrics: None,
+            labels: WorkerLabels::empty(),
         }
     }
 
@@ -0,0 +1,117 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+use crate::context::InstanceContext;
+use crate::rule::{JobContext, ScoreRule, WorkerContext};
+
+/// Favours builds on their evaluation's longest remaining chain of predicted
+/// build time. `DependencyCountRule` only sees direct dependents, so a short
+/// build heading a long serial chain ranks like any leaf; this rule ranks it by
+/// how much of the evaluation's remaining wall time sits above it.
+#[derive(Debug)]
+pub struct CriticalPathRule {
+    pub cap: f64,
+}
+
+impl Default for CriticalPathRule {
+    fn default() -> Self {
+        Self {
+            cap: crate::weights::CRITICAL_PATH_CAP,
+        }
+    }
+}
+
+impl ScoreRule for CriticalPathRule {
+    fn name(&self) -> &'static str {
+        "CriticalPathRule"
+    }
+
+    fn score(
+        &self,
+        job: &JobContext<'_>,
+        _worker: &WorkerContext<'_>,
+        _instance: &InstanceContext,
+    ) -> f64 {
+        job.job
+            .build()
+            .and_then(|b| b.critical_path())
+            .and_then(|cp| cp.share())
+            .map_or(0.0, |share| self.cap * share)
+    }
+
+    fn uses_critical_path(&self) -> bool {
+        true
+    }
+
+    fn description(&self) -> &'static str {
+        "Rewards builds on their evaluation's longest remaining chain of predicted build time, so the bottleneck chain starts first and the evaluation finishes sooner."
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::{CriticalPath, HistoryPrediction, ScoredJob};
+    use gradient_types::WorkerLabels;
+    use gradient_types::ids::OrganizationId;
+
+    fn job(critical_path: Option<CriticalPath>) -> ScoredJob<'static> {
+        ScoredJob::new_build(
+            "test",
+            OrganizationId::now_v7(),
+            "x86_64-linux",
+            false,
+            false,
+            None,
+            None,
+            HistoryPrediction::default(),
+        )
+        .with_critical_path(critical_path)
+    }
+
+    fn score(job: &ScoredJob<'_>) -> f64 {
+        let ctx = JobContext {
+            job,
+            missing_count: None,
+            missing_nar_size: None,
+            dependency_count: 0,
+            queued_at: gradient_types::now(),
+            ready_at: gradient_types::now(),
+            org_work_share: None,
+            rescore_count: 0,
+            now: gradient_types::now(),
+        };
+        let worker = WorkerContext {
+            architectures: &[],
+            system_features: &[],
+            fetch: false,
+            metrics: None,
+            labels: WorkerLabels::empty(),
+        };
+        CriticalPathRule::default().score(&ctx, &worker, &InstanceContext::default())
+    }
+
+    #[test]
+    fn bottleneck_chain_outscores_a_side_branch() {
+        let bottleneck = job(Some(CriticalPath {
+            remaining_ms: 600_000,
+            eval_longest_ms: 600_000,
+        }));
+        let side = job(Some(CriticalPath {
+            remaining_ms: 60_000,
+            eval_longest_ms: 600_000,
+        }));
+        assert_eq!(score(&bottleneck), crate::weights::CRITICAL_PATH_CAP);
+        assert!(score(&side) < score(&bottleneck));
+        assert!(score(&side) > 0.0);
+    }
+
+    #[test]
+    fn unknown_path_scores_zero() {
+        assert_eq!(score(&job(None)), 0.0);
+        assert_eq!(score(&job(Some(CriticalPath::default()))), 0.0);
+    }
+}
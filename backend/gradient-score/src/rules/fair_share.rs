@@ -62,6 +62,7 @@ mod tests {
     use super::*;
     use crate::context::{HistoryPrediction, ScoredJob};
     use crate::rules::builtin::WaitTimeRule;
+    use gradient_types::WorkerLabels;
     use gradient_types::ids::OrganizationId;
     use gradient_types::now;
 
@@ -98,6 +99,7 @@ mod tests {
             system_features: &[],
             fetch: false,
             metrics: None,
+            labels: WorkerLabels::empty(),
         }
     }
 
@@ -6,15 +6,19 @@
 
 pub mod affinity;
 pub mod builtin;
+pub mod critical_path;
 pub mod fair_share;
 pub mod prefer_local;
 pub mod resource;
+pub mod worker_selector;
 
 pub use affinity::{DiskAffinityRule, NetworkAffinityRule};
 pub use builtin::{
     BuiltinDeprioritizeRule, DependencyCountRule, MissingNarSizeRule, MissingPathsRule,
     RescoreWaitRule, ReserveFetchWorkersRule, WaitTimeRule,
 };
+pub use critical_path::CriticalPathRule;
 pub use fair_share::FairShareRule;
 pub use prefer_local::PreferLocalBuildRule;
 pub use resource::{ResourceFitRule, ResourceSaturationRule};
+pub use worker_selector::{WORKER_SELECTOR_RULE, WorkerSelectorRule};
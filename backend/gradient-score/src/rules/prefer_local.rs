@@ -60,6 +60,7 @@ impl ScoreRule for PreferLocalBuildRule {
 mod tests {
     use super::*;
     use crate::context::{HistoryPrediction, ScoredJob};
+    use gradient_types::WorkerLabels;
     use gradient_types::ids::OrganizationId;
 
     fn job(prefer_local_build: bool) -> ScoredJob<'static> {
@@ -95,6 +96,7 @@ mod tests {
             system_features: &[],
             fetch: false,
             metrics: None,
+            labels: WorkerLabels::empty(),
         }
     }
 
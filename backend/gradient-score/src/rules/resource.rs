@@ -156,6 +156,7 @@ impl ScoreRule for ResourceSaturationRule {
 mod tests {
     use super::*;
     use crate::context::{HistoryPrediction, ScoredJob, Windowed, WorkerMetricsView};
+    use gradient_types::WorkerLabels;
     use gradient_types::ids::OrganizationId;
 
     fn job_with_history(h: HistoryPrediction) -> ScoredJob<'static> {
@@ -195,6 +196,7 @@ mod tests {
             system_features: &[],
             fetch: false,
             metrics: Some(metrics),
+            labels: WorkerLabels::empty(),
         }
     }
 
@@ -363,6 +365,7 @@ mod tests {
             system_features: &[],
             fetch: false,
             metrics: None,
+            labels: WorkerLabels::empty(),
         };
         let job = job_with_history(HistoryPrediction {
             predicted_peak_ram_mb: 9000,
@@ -490,6 +493,7 @@ mod tests {
             system_features: &[],
             fetch: false,
             metrics: None,
+            labels: WorkerLabels::empty(),
         };
         assert_eq!(
             rule.score(&ctx(&real), &no_metrics, &InstanceContext::default()),
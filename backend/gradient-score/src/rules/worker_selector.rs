@@ -0,0 +1,143 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+use crate::context::InstanceContext;
+use crate::rule::{JobContext, ScoreRule, WorkerContext};
+
+/// Persisted name of [`WorkerSelectorRule`]; policies may not disable it.
+pub const WORKER_SELECTOR_RULE: &str = "WorkerSelectorRule";
+
+/// Holds a build or evaluation off every worker whose labels do not satisfy
+/// the job's project/trigger selector. Scores nothing: it is a placement constraint, not
+/// a preference, so no bonus elsewhere may out-vote it.
+#[derive(Debug, Default)]
+pub struct WorkerSelectorRule;
+
+impl ScoreRule for WorkerSelectorRule {
+    fn name(&self) -> &'static str {
+        WORKER_SELECTOR_RULE
+    }
+
+    fn score(
+        &self,
+        _job: &JobContext<'_>,
+        _worker: &WorkerContext<'_>,
+        _instance: &InstanceContext,
+    ) -> f64 {
+        0.0
+    }
+
+    fn veto(
+        &self,
+        job: &JobContext<'_>,
+        worker: &WorkerContext<'_>,
+        _instance: &InstanceContext,
+    ) -> bool {
+        !worker
+            .labels
+            .matches(job.job.org_id, job.job.worker_selector())
+    }
+
+    fn description(&self) -> &'static str {
+        "Keeps a build or evaluation off workers whose labels do not match its project's worker selector. Always enabled."
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::{HistoryPrediction, ScoredJob};
+    use gradient_types::ids::OrganizationId;
+    use gradient_types::{Labels, WorkerLabels};
+    use std::collections::BTreeMap;
+
+    fn job<'a>(org: OrganizationId, selector: &'a Labels) -> ScoredJob<'a> {
+        ScoredJob::new_build(
+            "test",
+            org,
+            "x86_64-linux",
+            false,
+            false,
+            None,
+            None,
+            HistoryPrediction::default(),
+        )
+        .with_worker_selector(selector)
+    }
+
+    fn eval_job<'a>(org: OrganizationId, selector: &'a Labels) -> ScoredJob<'a> {
+        ScoredJob::new_eval("test", org, false, HistoryPrediction::default())
+            .with_worker_selector(selector)
+    }
+
+    fn vetoed(job: &ScoredJob<'_>, labels: &WorkerLabels) -> bool {
+        let ctx = JobContext {
+            job,
+            missing_count: None,
+            missing_nar_size: None,
+            dependency_count: 0,
+            queued_at: gradient_types::now(),
+            ready_at: gradient_types::now(),
+            org_work_share: None,
+            rescore_count: 0,
+            now: gradient_types::now(),
+        };
+        let worker = WorkerContext {
+            architectures: &[],
+            system_features: &[],
+            fetch: false,
+            metrics: None,
+            labels,
+        };
+        WorkerSelectorRule.veto(&ctx, &worker, &InstanceContext::default())
+    }
+
+    fn pool(value: &str) -> Labels {
+        Labels::from([("pool".to_string(), value.to_string())])
+    }
+
+    #[test]
+    fn unmatched_selector_vetoes_the_worker() {
+        let org = OrganizationId::now_v7();
+        let secure = pool("secure");
+        let worker = WorkerLabels {
+            advertised: pool("untrusted"),
+            ..Default::default()
+        };
+        assert!(vetoed(&job(org, &secure), &worker));
+        assert!(!vetoed(&job(org, &Labels::new()), &worker));
+
+        let self_claimed = WorkerLabels {
+            advertised: pool("secure"),
+            ..Default::default()
+        };
+        assert!(vetoed(&job(org, &secure), &self_claimed));
+    }
+
+    #[test]
+    fn registration_labels_satisfy_only_their_org() {
+        let org = OrganizationId::now_v7();
+        let secure = pool("secure");
+        let worker = WorkerLabels {
+            advertised: Labels::new(),
+            registered: BTreeMap::from([(org, pool("secure"))]),
+        };
+        assert!(!vetoed(&job(org, &secure), &worker));
+        assert!(vetoed(&job(OrganizationId::now_v7(), &secure), &worker));
+    }
+
+    #[test]
+    fn selector_vetoes_evaluations_too() {
+        let org = OrganizationId::now_v7();
+        let secure = pool("secure");
+        let worker = WorkerLabels {
+            registered: BTreeMap::from([(org, pool("untrusted"))]),
+            ..Default::default()
+        };
+        assert!(vetoed(&eval_job(org, &secure), &worker));
+        assert!(!vetoed(&eval_job(org, &Labels::new()), &worker));
+    }
+}
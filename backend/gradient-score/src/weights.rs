@@ -43,6 +43,10 @@ pub const DEPENDENCY_COUNT_CAP: f64 = 50.0;
 pub const DEPENDENCY_COUNT_BASELINE_K: f64 = 2.0;
 pub const DEPENDENCY_COUNT_FALLBACK_AVG: f64 = 10.0;
 
+/// CriticalPathRule: bonus for a build on its evaluation's longest remaining
+/// chain of predicted build time, scaled by its share of that chain.
+pub const CRITICAL_PATH_CAP: f64 = 150.0;
+
 /// WaitTimeRule: gain per multiple of the average wait, the fallback average,
 /// and the anti-starvation cap that out-budgets every other rule.
 pub const WAIT_TIME_GAIN: f64 = 60.0;
@@ -55,6 +55,17 @@ impl CacheSigner {
         references: &[String],
     ) -> String {
         let raw = self.sign_narinfo_raw(store_path, nar_hash, nar_size, references);
+        self.token(&raw)
+    }
+
+    /// Sign any other fingerprint, such as a realisation's, and return the
+    /// signature token (`{key_name}:{base64_sig}`).
+    pub fn sign_fingerprint(&self, fingerprint: &str) -> String {
+        let sig = self.secret_key.sign(fingerprint.as_bytes(), None);
+        self.token(&sig[..])
+    }
+
+    fn token(&self, raw: &[u8]) -> String {
         let sig_b64 = general_purpose::STANDARD.encode(raw);
         format!("{}-{}:{}", self.base_url, self.cache_name, sig_b64)
     }
@@ -33,6 +33,8 @@ pub fn make_cache(name: &str, public_key: &str, private_key: &str) -> MCache {
         created_at: NaiveDateTime::default(),
         managed: false,
         max_storage_gb: 0,
+        eviction_policy: Default::default(),
+        nar_compression: Default::default(),
     }
 }
 
@@ -134,6 +134,9 @@ mod tests {
             created_by: gradient_types::ids::UserId::nil(),
             created_at: NaiveDateTime::default(),
             managed: false,
+            max_concurrent_builds: None,
+            max_concurrent_evaluations: None,
+            build_minutes_budget: None,
         }
     }
 
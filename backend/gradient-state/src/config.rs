@@ -8,8 +8,12 @@
 //! `State*` types it deserializes from the state JSON file. Validation lives in
 //! [`super::validation`]; provisioning in [`super::provisioning`].
 
+use chrono::NaiveDateTime;
+use gradient_entity::cache::{CacheEvictionPolicy, NarCompression};
+use gradient_entity::ids::EvaluationId;
 use gradient_entity::organization_cache::CacheSubscriptionMode;
 use gradient_types::triggers::{ConcurrencyPolicy, TriggerType};
+use gradient_types::{Labels, MaintenanceWindow};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
@@ -47,6 +51,15 @@ pub struct StateOrganization {
     pub public: bool,
     #[serde(default)]
     pub hide_build_requests: bool,
+    /// Hard cap on builds in flight at once; unset is unlimited.
+    #[serde(default)]
+    pub max_concurrent_builds: Option<i32>,
+    /// Hard cap on evaluations running at once; unset is unlimited.
+    #[serde(default)]
+    pub max_concurrent_evaluations: Option<i32>,
+    /// Build minutes per rolling 24 hours; unset is unlimited.
+    #[serde(default)]
+    pub build_minutes_budget: Option<i32>,
     pub created_by: String,
     /// Declarative org membership. Empty preserves the legacy behavior of
     /// auto-adding `created_by` as Admin. Non-empty makes the list
@@ -101,12 +114,29 @@ pub struct StateProject {
     /// the missing ones (matched by `name` within the project).
     #[serde(default)]
     pub actions: Vec<StateAction>,
+    /// Worker label selector every build of this project must match, e.g.
+    /// `{ "pool": "secure" }`. Empty means any worker.
+    #[serde(default)]
+    pub worker_selector: Labels,
+    /// Wall-clock build limit in seconds. Omitted uses the server default,
+    /// `0` means no limit; a derivation's own `timeout`/`meta.timeout` wins.
+    #[serde(default)]
+    pub build_timeout_secs: Option<i64>,
+    /// Silent-output build limit in seconds, resolved like `build_timeout_secs`.
+    #[serde(default)]
+    pub build_max_silent_secs: Option<i64>,
+    /// Largest NAR, in bytes, a single build output may have. Omitted or `0`
+    /// means no limit.
+    #[serde(default)]
+    pub max_output_size: Option<i64>,
 }
 
 /// Declarative project action. `config` is type-specific and validated
 /// against `action_type` at apply time:
-///   - `send_mail`           `{ recipients: [..], subject_template?: str }`
-///   - `send_web_request`    `{ url: str, token_file?: str }`
+///   - `send_mail`           `{ recipients: [..], subject_template?: str, digest_window_secs?: int }`
+///   - `send_web_request`    `{ url: str, token_file?: str, signing_secret_file?: str, method?, headers?, body_template?: str }`
+///   - `send_matrix`         `{ homeserver: str, room_id: str, access_token_file: str, message_template?: str }`
+///   - `send_slack`          `{ url: str, message_template?: str }`
 ///   - `forge_status_report` `{ integration: <outbound integration name> }`
 ///   - `open_pr`             `{ integration: <name>, generator?, granularity?, verify_gate?, branch_pattern?, ... }`
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -139,18 +169,24 @@ pub struct StateTrigger {
     #[serde(rename = "type")]
     pub trigger_type: TriggerType,
     /// Name of an inbound integration in the same org. Required for
-    /// `reporter_push` and `reporter_pull_request` triggers.
+    /// `reporter_push`, `reporter_pull_request` and `reporter_merge_queue`
+    /// triggers.
     #[serde(default)]
     pub integration: Option<String>,
     /// Type-specific config shape:
     /// - polling: `{ interval_secs }`
     /// - reporter_push: `{ branches, tags, releases_only }`
     /// - reporter_pull_request: `{ branches, actions }`
+    /// - reporter_merge_queue: `{ branches }`
     /// - time: `{ cron }`
     #[serde(default)]
     pub config: serde_json::Value,
     #[serde(default = "default_active")]
     pub active: bool,
+    /// Worker label selector layered over the project's for evaluations this
+    /// trigger fires; its keys override the project's.
+    #[serde(default)]
+    pub worker_selector: Labels,
 }
 
 fn default_active() -> bool {
@@ -166,7 +202,8 @@ pub struct StateIntegration {
     pub organization: String,
     /// `"inbound"` or `"outbound"`.
     pub kind: String,
-    /// `"gitea"`, `"forgejo"`, `"gitlab"`, or `"github"`.
+    /// `"gitea"`, `"forgejo"`, `"gitlab"`, `"github"`, `"bitbucket"`, or
+    /// `"bitbucket-server"`.
     pub forge_type: String,
     #[serde(default)]
     pub secret_file: Option<String>,
@@ -199,6 +236,10 @@ pub struct StateCache {
     pub local_priority: Option<i32>,
     #[serde(default)]
     pub max_storage_gb: i32,
+    #[serde(default)]
+    pub eviction_policy: CacheEvictionPolicy,
+    #[serde(default)]
+    pub nar_compression: NarCompression,
     pub signing_key_file: String,
     #[serde(default)]
     pub organizations: Vec<String>,
@@ -210,6 +251,8 @@ pub struct StateCache {
     pub roles: Vec<StateCacheRoleEntry>,
     #[serde(default)]
     pub members: Vec<StateCacheMemberEntry>,
+    #[serde(default)]
+    pub pins: Vec<StatePin>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -224,6 +267,18 @@ pub struct StateCacheMemberEntry {
     pub role: String,
 }
 
+/// A managed cache pin: exactly one of `store_path` or `evaluation`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatePin {
+    pub name: String,
+    #[serde(default)]
+    pub store_path: Option<String>,
+    #[serde(default)]
+    pub evaluation: Option<EvaluationId>,
+    #[serde(default)]
+    pub expires_at: Option<NaiveDateTime>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum StateUpstream {
@@ -312,6 +367,16 @@ pub struct StateWorker {
     /// Global enable for a base worker. Ignored for non-base workers.
     #[serde(default = "default_true")]
     pub enabled: bool,
+    /// Labels set on each of the worker's registrations, matched against these
+    /// organizations' worker selectors. The worker's self-advertised labels are
+    /// never matched. Not supported for base workers.
+    #[serde(default)]
+    pub labels: Labels,
+    /// Scheduled windows during which each registration cordons the worker.
+    /// Not supported for base workers. A manual cordon is runtime state and
+    /// is left untouched by provisioning.
+    #[serde(default)]
+    pub maintenance_windows: Vec<MaintenanceWindow>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
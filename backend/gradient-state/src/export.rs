@@ -16,7 +16,7 @@
 
 use super::{
     StateApiKey, StateCache, StateCacheMemberEntry, StateCacheRoleEntry, StateConfiguration,
-    StateFlakeInputOverride, StateIntegration, StateOrgMemberEntry, StateOrganization,
+    StateFlakeInputOverride, StateIntegration, StateOrgMemberEntry, StateOrganization, StatePin,
     StateProject, StateRole, StateTrigger, StateUpstream, StateUser, StateWorker,
 };
 use gradient_ci::IntegrationKind;
@@ -25,8 +25,9 @@ use gradient_db::permissions::{
 };
 use gradient_entity::cache_upstream::CacheUpstreamKind;
 use gradient_entity::ids::*;
-use gradient_types::actions::{ActionConfig, ActionType};
+use gradient_types::actions::{ActionConfig, ActionType, WebRequestMethod};
 use gradient_types::triggers::{TriggerConfig, TriggerType};
+use gradient_types::{worker_labels, worker_maintenance};
 use sea_orm::{ConnectionTrait, DbErr, EntityTrait};
 use std::collections::HashMap;
 
@@ -40,6 +41,7 @@ const SECRET_KEYS: &[&str] = &[
     "key_file",
     "secret_file",
     "access_token_file",
+    "signing_secret_file",
 ];
 
 /// Build the full declarative state from every relevant table.
@@ -78,6 +80,7 @@ pub async fn export_state<C: ConnectionTrait>(db: &C) -> Result<StateConfigurati
     let upstreams = gradient_entity::cache_upstream::Entity::find()
         .all(db)
         .await?;
+    let pins = gradient_entity::cache_pin::Entity::find().all(db).await?;
     let triggers = gradient_entity::project_trigger::Entity::find()
         .all(db)
         .await?;
@@ -145,6 +148,9 @@ pub async fn export_state<C: ConnectionTrait>(db: &C) -> Result<StateConfigurati
                 private_key_file: String::new(),
                 public: o.public,
                 hide_build_requests: o.hide_build_requests,
+                max_concurrent_builds: o.max_concurrent_builds,
+                max_concurrent_evaluations: o.max_concurrent_evaluations,
+                build_minutes_budget: o.build_minutes_budget,
                 created_by: name_or_blank(&username, o.created_by),
                 members,
             },
@@ -195,6 +201,10 @@ pub async fn export_state<C: ConnectionTrait>(db: &C) -> Result<StateConfigurati
                 sign_cache: p.sign_cache,
                 flake_input_overrides,
                 actions: project_actions,
+                worker_selector: worker_labels::labels_from_json(p.worker_selector.as_ref()),
+                build_timeout_secs: p.build_timeout_secs,
+                build_max_silent_secs: p.build_max_silent_secs,
+                max_output_size: p.max_output_size,
             },
         );
     }
@@ -231,6 +241,16 @@ pub async fn export_state<C: ConnectionTrait>(db: &C) -> Result<StateConfigurati
                 })
             })
             .collect();
+        let cache_pins = pins
+            .iter()
+            .filter(|p| p.cache == c.id)
+            .map(|p| StatePin {
+                name: p.name.clone(),
+                store_path: p.store_path().map(|sp| sp.full()),
+                evaluation: p.evaluation,
+                expires_at: p.expires_at,
+            })
+            .collect();
         config.caches.insert(
             c.name.clone(),
             StateCache {
@@ -241,6 +261,8 @@ pub async fn export_state<C: ConnectionTrait>(db: &C) -> Result<StateConfigurati
                 priority: c.priority,
                 local_priority: c.local_priority,
                 max_storage_gb: c.max_storage_gb,
+                eviction_policy: c.eviction_policy,
+                nar_compression: c.nar_compression,
                 signing_key_file: String::new(),
                 organizations,
                 upstreams: cache_upstreams,
@@ -248,6 +270,7 @@ pub async fn export_state<C: ConnectionTrait>(db: &C) -> Result<StateConfigurati
                 created_by: name_or_blank(&username, c.created_by),
                 roles,
                 members,
+                pins: cache_pins,
             },
         );
     }
@@ -327,6 +350,10 @@ pub async fn export_state<C: ConnectionTrait>(db: &C) -> Result<StateConfigurati
                 base_worker: false,
                 authorize_against: None,
                 enabled: true,
+                labels: worker_labels::labels_from_json(reg.labels.as_ref()),
+                maintenance_windows: worker_maintenance::windows_from_json(
+                    reg.maintenance_windows.as_ref(),
+                ),
             },
         );
     }
@@ -401,6 +428,8 @@ fn export_base_worker(
         base_worker: true,
         authorize_against: bw.authorize_against.map(|u| u.to_string()),
         enabled: bw.enabled,
+        labels: Default::default(),
+        maintenance_windows: Vec::new(),
     }
 }
 
@@ -458,12 +487,25 @@ fn export_trigger(
             c.insert("cron".into(), cron.into());
             (TriggerType::Time, None, c)
         }
+        TriggerConfig::ReporterMergeQueue {
+            integration_id,
+            branches,
+        } => {
+            let mut c = serde_json::Map::new();
+            c.insert("branches".into(), branches.into());
+            (
+                TriggerType::ReporterMergeQueue,
+                integration_name.get(&integration_id).cloned(),
+                c,
+            )
+        }
     };
     Some(StateTrigger {
         trigger_type,
         integration,
         config: serde_json::Value::Object(config),
         active: t.active,
+        worker_selector: worker_labels::labels_from_json(t.worker_selector.as_ref()),
     })
 }
 
@@ -482,24 +524,73 @@ fn export_action(
     let cfg: ActionConfig = serde_json::from_value(a.config.clone()).ok()?;
     let events: Vec<String> = serde_json::from_value(a.events.clone()).unwrap_or_default();
     let (action_type, config) = match cfg {
-        // The stored web-request token is encrypted and unrecoverable, so the
-        // `token_file` path is dropped - re-add it in nix if the hook needs auth.
+        // The stored web-request token and signing secret are encrypted and
+        // unrecoverable, so `token_file` / `signing_secret_file` are dropped -
+        // re-add them in nix if the hook needs auth or signatures.
         ActionConfig::SendMail {
             recipients,
             subject_template,
+            digest_window_secs,
         } => {
             let mut c = serde_json::Map::new();
             c.insert("recipients".into(), recipients.into());
             if let Some(s) = subject_template {
                 c.insert("subject_template".into(), s.into());
             }
+            if let Some(w) = digest_window_secs {
+                c.insert("digest_window_secs".into(), w.into());
+            }
             (ActionType::SendMail, c)
         }
-        ActionConfig::SendWebRequest { url, .. } => {
+        ActionConfig::SendWebRequest {
+            url,
+            method,
+            headers,
+            body_template,
+            ..
+        } => {
             let mut c = serde_json::Map::new();
             c.insert("url".into(), url.into());
+            if method != WebRequestMethod::Post {
+                c.insert("method".into(), enum_str(&method).into());
+            }
+            if !headers.is_empty() {
+                c.insert(
+                    "headers".into(),
+                    serde_json::to_value(headers).unwrap_or_default(),
+                );
+            }
+            if let Some(t) = body_template {
+                c.insert("body_template".into(), t.into());
+            }
             (ActionType::SendWebRequest, c)
         }
+        // The Matrix access token is dropped for the same reason as above.
+        ActionConfig::SendMatrix {
+            homeserver,
+            room_id,
+            message_template,
+            ..
+        } => {
+            let mut c = serde_json::Map::new();
+            c.insert("homeserver".into(), homeserver.into());
+            c.insert("room_id".into(), room_id.into());
+            if let Some(t) = message_template {
+                c.insert("message_template".into(), t.into());
+            }
+            (ActionType::SendMatrix, c)
+        }
+        ActionConfig::SendSlack {
+            url,
+            message_template,
+        } => {
+            let mut c = serde_json::Map::new();
+            c.insert("url".into(), url.into());
+            if let Some(t) = message_template {
+                c.insert("message_template".into(), t.into());
+            }
+            (ActionType::SendSlack, c)
+        }
         ActionConfig::ForgeStatusReport { integration_id } => {
             let mut c = serde_json::Map::new();
             c.insert(
@@ -550,6 +641,8 @@ fn export_action(
             ActionType::SendWebRequest => "send_web_request",
             ActionType::ForgeStatusReport => "forge_status_report",
             ActionType::OpenPr => "open_pr",
+            ActionType::SendMatrix => "send_matrix",
+            ActionType::SendSlack => "send_slack",
         }
         .to_string(),
         active: a.active,
@@ -16,7 +16,9 @@ use gradient_types::consts::{
     BASE_CACHE_ROLE_ADMIN_ID, BASE_CACHE_ROLE_VIEW_ID, BASE_CACHE_ROLE_WRITE_ID,
 };
 use gradient_types::*;
-use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter, Set};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, EntityTrait, IntoActiveModel, QueryFilter, Set,
+};
 use std::collections::{HashMap, HashSet};
 
 impl<'a> StateApplicator<'a> {
@@ -77,6 +79,8 @@ impl<'a> StateApplicator<'a> {
                 cache_model.priority = Set(state_cache.priority);
                 cache_model.local_priority = Set(state_cache.local_priority);
                 cache_model.max_storage_gb = Set(state_cache.max_storage_gb);
+                cache_model.eviction_policy = Set(state_cache.eviction_policy);
+                cache_model.nar_compression = Set(state_cache.nar_compression);
                 cache_model.public_key = Set(public_key.clone());
                 cache_model.private_key = Set(encrypted_signing_key.clone());
                 cache_model.created_by = Set(created_by_id);
@@ -102,6 +106,8 @@ impl<'a> StateApplicator<'a> {
                     created_at: now,
                     managed: true,
                     max_storage_gb: state_cache.max_storage_gb,
+                    eviction_policy: state_cache.eviction_policy,
+                    nar_compression: state_cache.nar_compression,
                 }
                 .into_active_model();
 
@@ -112,6 +118,8 @@ impl<'a> StateApplicator<'a> {
 
             self.apply_cache_upstreams(cache_id, &state_cache.name, &state_cache.upstreams)
                 .await?;
+            self.apply_cache_pins(cache_id, &state_cache.name, &state_cache.pins)
+                .await?;
 
             for org_name in &state_cache.organizations {
                 let org_id = org_map.get(org_name).copied().ok_or_else(|| {
@@ -288,6 +296,65 @@ impl<'a> StateApplicator<'a> {
         Ok(())
     }
 
+    /// Replace the cache's managed pins with the declared ones. A pin created
+    /// through the API under a declared name gives way to the state one.
+    pub(crate) async fn apply_cache_pins(
+        &self,
+        cache_id: CacheId,
+        cache_name: &str,
+        pins: &[StatePin],
+    ) -> Result<(), DynError> {
+        ECachePin::delete_many()
+            .filter(CCachePin::Cache.eq(cache_id))
+            .filter(
+                Condition::any()
+                    .add(CCachePin::Managed.eq(true))
+                    .add(CCachePin::Name.is_in(pins.iter().map(|p| p.name.clone()))),
+            )
+            .exec(self.db)
+            .await?;
+
+        let now = now();
+        for pin in pins {
+            let mut record = MCachePin {
+                id: CachePinId::now_v7(),
+                cache: cache_id,
+                name: pin.name.clone(),
+                managed: true,
+                created_at: now,
+                expires_at: pin.expires_at,
+                ..Default::default()
+            };
+            if let Some(path) = &pin.store_path {
+                let path = StorePath::parse(path)
+                    .map_err(|e| format!("Pin '{}' of cache '{}': {}", pin.name, cache_name, e))?;
+                record.hash = Some(path.hash().to_string());
+                record.package = Some(path.name().to_string());
+            } else if let Some(evaluation) = pin.evaluation {
+                if EEvaluation::find_by_id(evaluation)
+                    .one(self.db)
+                    .await?
+                    .is_none()
+                {
+                    return Err(format!(
+                        "Evaluation '{}' not found for pin '{}' of cache '{}'",
+                        evaluation, pin.name, cache_name
+                    )
+                    .into());
+                }
+                record.evaluation = Some(evaluation);
+            }
+            record.into_active_model().insert(self.db).await?;
+        }
+
+        tracing::debug!(
+            count = pins.len(),
+            cache = %cache_name,
+            "Applied pins to cache"
+        );
+        Ok(())
+    }
+
     // ── apply_cache_roles_and_members ─────────────────────────────────────────
 
     pub(crate) async fn apply_cache_roles_and_members(
@@ -74,6 +74,9 @@ impl<'a> StateApplicator<'a> {
                 org.created_by = Set(created_by_id);
                 org.public = Set(state_org.public);
                 org.hide_build_requests = Set(state_org.hide_build_requests);
+                org.max_concurrent_builds = Set(state_org.max_concurrent_builds);
+                org.max_concurrent_evaluations = Set(state_org.max_concurrent_evaluations);
+                org.build_minutes_budget = Set(state_org.build_minutes_budget);
                 org.managed = Set(true);
                 org.update(self.db).await?;
                 tracing::info!(name = %state_org.name, "Updated managed organization");
@@ -92,6 +95,9 @@ impl<'a> StateApplicator<'a> {
                     created_by: created_by_id,
                     created_at: now,
                     managed: true,
+                    max_concurrent_builds: state_org.max_concurrent_builds,
+                    max_concurrent_evaluations: state_org.max_concurrent_evaluations,
+                    build_minutes_budget: state_org.build_minutes_budget,
                 }
                 .into_active_model();
 
@@ -17,7 +17,7 @@ use gradient_types::*;
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, IntoActiveModel, QueryFilter, Set,
 };
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 impl<'a> StateApplicator<'a> {
     // ── apply_projects ────────────────────────────────────────────────────────
@@ -54,6 +54,12 @@ impl<'a> StateApplicator<'a> {
                 proj.created_by = Set(created_by_id);
                 proj.concurrency = Set(state_project.concurrency);
                 proj.sign_cache = Set(state_project.sign_cache);
+                proj.worker_selector = Set(worker_labels::labels_to_json(
+                    &state_project.worker_selector,
+                ));
+                proj.build_timeout_secs = Set(state_project.build_timeout_secs);
+                proj.build_max_silent_secs = Set(state_project.build_max_silent_secs);
+                proj.max_output_size = Set(state_project.max_output_size);
                 proj.managed = Set(true);
                 proj.update(self.db).await?;
                 tracing::info!(name = %state_project.name, "Updated managed project");
@@ -80,6 +86,10 @@ impl<'a> StateApplicator<'a> {
                     keep_evaluations: state_project.keep_evaluations,
                     concurrency: state_project.concurrency,
                     sign_cache: state_project.sign_cache,
+                    worker_selector: worker_labels::labels_to_json(&state_project.worker_selector),
+                    build_timeout_secs: state_project.build_timeout_secs,
+                    build_max_silent_secs: state_project.build_max_silent_secs,
+                    max_output_size: state_project.max_output_size,
                     ..Default::default()
                 }
                 .into_active_model();
@@ -319,11 +329,13 @@ pub(crate) async fn apply_project_triggers<C: ConnectionTrait>(
         anyhow::bail!("project '{}' must have at least one trigger", project.name);
     }
 
-    let mut desired_by_key: HashMap<String, (TriggerConfig, bool)> = HashMap::new();
+    let mut desired_by_key: HashMap<String, (TriggerConfig, bool, Option<serde_json::Value>)> =
+        HashMap::new();
     for t in desired {
         let cfg = build_trigger_config(t, inbound_by_name, outbound_by_name)?;
         let key = trigger_key(&cfg);
-        desired_by_key.insert(key, (cfg, t.active));
+        let selector = worker_labels::labels_to_json(&t.worker_selector);
+        desired_by_key.insert(key, (cfg, t.active, selector));
     }
 
     let existing: Vec<MProjectTrigger> = EProjectTrigger::find()
@@ -341,7 +353,7 @@ pub(crate) async fn apply_project_triggers<C: ConnectionTrait>(
 
     let now = gradient_types::now();
 
-    for (key, (cfg, active)) in &desired_by_key {
+    for (key, (cfg, active, selector)) in &desired_by_key {
         if existing_by_key.contains_key(key) {
             continue;
         }
@@ -351,6 +363,7 @@ pub(crate) async fn apply_project_triggers<C: ConnectionTrait>(
             trigger_type: cfg.trigger_type(),
             config: cfg.to_db_json(),
             active: *active,
+            worker_selector: selector.clone(),
             created_at: now,
             updated_at: now,
             ..Default::default()
@@ -361,10 +374,11 @@ pub(crate) async fn apply_project_triggers<C: ConnectionTrait>(
     }
 
     for (key, row) in existing_by_key {
-        if let Some((_, active)) = desired_by_key.get(&key) {
-            if row.active != *active {
+        if let Some((_, active, selector)) = desired_by_key.get(&key) {
+            if row.active != *active || row.worker_selector != *selector {
                 let mut a: AProjectTrigger = row.into();
                 a.active = Set(*active);
+                a.worker_selector = Set(selector.clone());
                 a.updated_at = Set(now);
                 a.update(db).await?;
             }
@@ -398,7 +412,7 @@ pub(crate) fn build_trigger_config(
                     .map(|s| s.to_owned()),
             }
         }
-        TT::ReporterPush | TT::ReporterPullRequest => {
+        TT::ReporterPush | TT::ReporterPullRequest | TT::ReporterMergeQueue => {
             let name = t
                 .integration
                 .as_ref()
@@ -413,7 +427,16 @@ pub(crate) fn build_trigger_config(
                 ),
                 None => anyhow::bail!("unknown integration: {name}"),
             };
-            if t.trigger_type == TT::ReporterPush {
+            if t.trigger_type == TT::ReporterMergeQueue {
+                TriggerConfig::ReporterMergeQueue {
+                    integration_id: id,
+                    branches: t
+                        .config
+                        .get("branches")
+                        .and_then(|v| serde_json::from_value(v.clone()).ok())
+                        .unwrap_or_default(),
+                }
+            } else if t.trigger_type == TT::ReporterPush {
                 TriggerConfig::ReporterPush {
                     integration_id: id,
                     branches: t
@@ -477,8 +500,9 @@ pub(crate) fn trigger_key(cfg: &TriggerConfig) -> String {
 
 /// Build a stored `ActionConfig` from a declared `StateAction`. Tokens for
 /// `send_web_request` are loaded from the systemd credential file
-/// `gradient_action_${name}_token` and encrypted with the server's crypt key
-/// before storage, matching the REST `create_action` path.
+/// `gradient_action_${name}_token` (`..._access_token` for `send_matrix`) and
+/// encrypted with the server's crypt key before storage, matching the REST
+/// `create_action` path.
 pub(crate) fn build_action_config(
     a: &StateAction,
     project_name: &str,
@@ -517,10 +541,27 @@ pub(crate) fn build_action_config(
                 .get("subject_template")
                 .and_then(|v| v.as_str())
                 .map(str::to_owned);
-            Ok(ActionConfig::SendMail {
+            let digest_window_secs = match a.config.get("digest_window_secs") {
+                None | Some(serde_json::Value::Null) => None,
+                Some(v) => Some(
+                    v.as_u64()
+                        .and_then(|n| u32::try_from(n).ok())
+                        .ok_or_else(|| {
+                            format!(
+                                "action '{}': digest_window_secs must be a positive integer",
+                                a.name
+                            )
+                        })?,
+                ),
+            };
+            let cfg = ActionConfig::SendMail {
                 recipients,
                 subject_template,
-            })
+                digest_window_secs,
+            };
+            cfg.validate()
+                .map_err(|e| format!("action '{}': {}", a.name, e))?;
+            Ok(cfg)
         }
         "send_web_request" => {
             let url = want("url")?
@@ -539,7 +580,94 @@ pub(crate) fn build_action_config(
             } else {
                 None
             };
-            Ok(ActionConfig::SendWebRequest { url, token })
+            let signing_secret = if a.config.get("signing_secret_file").is_some() {
+                let (plain, _) = read_credential(
+                    "action",
+                    &a.name,
+                    "signing_secret",
+                    "action signing secret file",
+                )?;
+                let plain = plain.trim();
+                let enc = gradient_ci::actions::encrypt_action_secret(plain, crypt_key)
+                    .map_err(|e| format!("encrypt action signing secret: {e}"))?;
+                Some(enc)
+            } else {
+                None
+            };
+            let method: WebRequestMethod = parse_action_enum(a, "method")?;
+            let headers: BTreeMap<String, String> = parse_action_enum(a, "headers")?;
+            let body_template = a
+                .config
+                .get("body_template")
+                .and_then(|v| v.as_str())
+                .map(str::to_owned);
+            let cfg = ActionConfig::SendWebRequest {
+                url,
+                token,
+                signing_secret,
+                method,
+                headers,
+                body_template,
+            };
+            cfg.validate()
+                .map_err(|e| format!("action '{}': {}", a.name, e))?;
+            Ok(cfg)
+        }
+        "send_matrix" => {
+            let homeserver = want("homeserver")?
+                .as_str()
+                .ok_or_else(|| format!("action '{}': homeserver must be a string", a.name))?
+                .to_owned();
+            gradient_util::http_validation::validate_webhook_url(&homeserver)
+                .map_err(|e| format!("action '{}': {}", a.name, e))?;
+            let room_id = want("room_id")?
+                .as_str()
+                .ok_or_else(|| format!("action '{}': room_id must be a string", a.name))?
+                .to_owned();
+            if !room_id.starts_with('!') || !room_id.contains(':') {
+                return Err(format!(
+                    "action '{}': room_id must be a Matrix room id of the form '!id:server'",
+                    a.name
+                )
+                .into());
+            }
+            want("access_token_file")?;
+            let (plain, _) = read_credential(
+                "action",
+                &a.name,
+                "access_token",
+                "action access token file",
+            )?;
+            let access_token = gradient_ci::actions::encrypt_action_secret(plain.trim(), crypt_key)
+                .map_err(|e| format!("encrypt action access token: {e}"))?;
+            let message_template = a
+                .config
+                .get("message_template")
+                .and_then(|v| v.as_str())
+                .map(str::to_owned);
+            Ok(ActionConfig::SendMatrix {
+                homeserver,
+                room_id,
+                access_token: Some(access_token),
+                message_template,
+            })
+        }
+        "send_slack" => {
+            let url = want("url")?
+                .as_str()
+                .ok_or_else(|| format!("action '{}': url must be a string", a.name))?
+                .to_owned();
+            gradient_util::http_validation::validate_webhook_url(&url)
+                .map_err(|e| format!("action '{}': {}", a.name, e))?;
+            let message_template = a
+                .config
+                .get("message_template")
+                .and_then(|v| v.as_str())
+                .map(str::to_owned);
+            Ok(ActionConfig::SendSlack {
+                url,
+                message_template,
+            })
         }
         "forge_status_report" => {
             if !a.events.is_empty() {
@@ -613,8 +741,8 @@ pub(crate) fn build_action_config(
     }
 }
 
-/// Decode a snake_case enum field from an action config, falling back to the
-/// enum's serde `Default` when the key is absent.
+/// Decode a snake_case enum (or other structured) field from an action
+/// config, falling back to the type's `Default` when the key is absent.
 fn parse_action_enum<T>(a: &StateAction, key: &str) -> Result<T, DynError>
 where
     T: Default + serde::de::DeserializeOwned,
@@ -640,6 +768,7 @@ mod trigger_helper_tests {
             integration: None,
             config: serde_json::json!({ "interval_secs": interval_secs }),
             active: true,
+            worker_selector: Default::default(),
         }
     }
 
@@ -667,6 +796,7 @@ mod trigger_helper_tests {
             integration: None,
             config: serde_json::Value::Null,
             active: true,
+            worker_selector: Default::default(),
         };
         let cfg = build_trigger_config(&t, &empty_integrations(), &empty_integrations()).unwrap();
         assert_eq!(
@@ -697,6 +827,7 @@ mod trigger_helper_tests {
             integration: None,
             config: serde_json::json!({ "cron": "0 0 2 * * *" }),
             active: true,
+            worker_selector: Default::default(),
         };
         let cfg = build_trigger_config(&t, &empty_integrations(), &empty_integrations()).unwrap();
         assert_eq!(
@@ -714,6 +845,7 @@ mod trigger_helper_tests {
             integration: None,
             config: serde_json::json!({}),
             active: true,
+            worker_selector: Default::default(),
         };
         let err =
             build_trigger_config(&t, &empty_integrations(), &empty_integrations()).unwrap_err();
@@ -727,6 +859,7 @@ mod trigger_helper_tests {
             integration: None,
             config: serde_json::json!({}),
             active: true,
+            worker_selector: Default::default(),
         };
         let err =
             build_trigger_config(&t, &empty_integrations(), &empty_integrations()).unwrap_err();
@@ -740,6 +873,7 @@ mod trigger_helper_tests {
             integration: Some("github-app".into()),
             config: serde_json::json!({}),
             active: true,
+            worker_selector: Default::default(),
         };
         let err =
             build_trigger_config(&t, &empty_integrations(), &empty_integrations()).unwrap_err();
@@ -761,6 +895,7 @@ mod trigger_helper_tests {
             integration: Some("gh".into()),
             config: serde_json::json!({ "branches": ["main"], "tags": [], "releases_only": false }),
             active: true,
+            worker_selector: Default::default(),
         };
         let cfg = build_trigger_config(&t, &integrations, &empty_integrations()).unwrap();
         assert_eq!(
@@ -774,6 +909,29 @@ mod trigger_helper_tests {
         );
     }
 
+    #[test]
+    fn build_reporter_merge_queue_with_known_integration() {
+        let int_id = IntegrationId::nil();
+        let mut integrations = HashMap::new();
+        integrations.insert("gh".into(), int_id);
+
+        let t = StateTrigger {
+            trigger_type: TriggerType::ReporterMergeQueue,
+            integration: Some("gh".into()),
+            config: serde_json::json!({ "branches": ["main"] }),
+            active: true,
+            worker_selector: Default::default(),
+        };
+        let cfg = build_trigger_config(&t, &integrations, &empty_integrations()).unwrap();
+        assert_eq!(
+            cfg,
+            TriggerConfig::ReporterMergeQueue {
+                integration_id: int_id,
+                branches: vec!["main".into()],
+            }
+        );
+    }
+
     #[test]
     fn trigger_key_differs_by_type() {
         let polling = TriggerConfig::Polling {
@@ -827,6 +985,7 @@ mod trigger_helper_tests {
             integration: Some("forgejo-status-reports".into()),
             config: serde_json::json!({}),
             active: true,
+            worker_selector: Default::default(),
         };
 
         let err = build_trigger_config(&t, &empty_integrations(), &outbound).unwrap_err();
@@ -870,8 +1029,10 @@ mod action_helper_tests {
             ActionConfig::SendMail {
                 recipients,
                 subject_template,
+                digest_window_secs,
             } => {
                 assert_eq!(recipients, vec!["ops@example.com".to_string()]);
+                assert_eq!(digest_window_secs, None);
                 assert_eq!(subject_template.as_deref(), Some("[Gradient] {event}"));
             }
             other => panic!("expected SendMail, got {other:?}"),
@@ -915,12 +1076,99 @@ mod action_helper_tests {
         };
         let cfg = build_action_config(&a, "web", &HashMap::new(), true, &key()).unwrap();
         match cfg {
-            ActionConfig::SendWebRequest { url, token } => {
+            ActionConfig::SendWebRequest {
+                url,
+                token,
+                signing_secret,
+                method,
+                headers,
+                body_template,
+            } => {
                 assert_eq!(url, "https://hooks.example.com/x");
                 assert!(token.is_none());
+                assert!(signing_secret.is_none());
+                assert_eq!(method, WebRequestMethod::Post);
+                assert!(headers.is_empty());
+                assert!(body_template.is_none());
+            }
+            other => panic!("expected SendWebRequest, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn build_send_web_request_with_template_and_headers() {
+        let a = StateAction {
+            name: "pagerduty".into(),
+            action_type: "send_web_request".into(),
+            active: true,
+            events: vec!["evaluation.failed".into()],
+            config: serde_json::json!({
+                "url": "https://events.example.com/v2/enqueue",
+                "method": "put",
+                "headers": { "X-Routing-Key": "ops" },
+                "body_template": "{\"summary\": \"{{repository}} {{event}}\"}",
+            }),
+        };
+        let cfg = build_action_config(&a, "web", &HashMap::new(), true, &key()).unwrap();
+        match cfg {
+            ActionConfig::SendWebRequest {
+                method, headers, ..
+            } => {
+                assert_eq!(method, WebRequestMethod::Put);
+                assert_eq!(
+                    headers.get("X-Routing-Key").map(String::as_str),
+                    Some("ops")
+                );
             }
             other => panic!("expected SendWebRequest, got {other:?}"),
         }
+
+        let mut bad = a.clone();
+        bad.config["headers"] = serde_json::json!({ "X-Gradient-Signature": "x" });
+        let err = build_action_config(&bad, "web", &HashMap::new(), true, &key()).unwrap_err();
+        assert!(
+            err.to_string().contains("X-Gradient-Signature"),
+            "got: {err}"
+        );
+    }
+
+    #[test]
+    fn build_send_slack_with_template() {
+        let a = StateAction {
+            name: "chat".into(),
+            action_type: "send_slack".into(),
+            active: true,
+            events: vec!["evaluation.failed".into()],
+            config: serde_json::json!({
+                "url": "https://hooks.slack.com/services/T/B/X",
+                "message_template": "{project}: {status}",
+            }),
+        };
+        let cfg = build_action_config(&a, "web", &HashMap::new(), true, &key()).unwrap();
+        assert_eq!(
+            cfg,
+            ActionConfig::SendSlack {
+                url: "https://hooks.slack.com/services/T/B/X".into(),
+                message_template: Some("{project}: {status}".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn build_send_matrix_rejects_room_alias() {
+        let a = StateAction {
+            name: "matrix".into(),
+            action_type: "send_matrix".into(),
+            active: true,
+            events: vec!["evaluation.failed".into()],
+            config: serde_json::json!({
+                "homeserver": "https://matrix.example.org",
+                "room_id": "#ops:example.org",
+                "access_token_file": "/dev/null",
+            }),
+        };
+        let err = build_action_config(&a, "web", &HashMap::new(), true, &key()).unwrap_err();
+        assert!(err.to_string().contains("room_id"), "got: {err}");
     }
 
     #[test]
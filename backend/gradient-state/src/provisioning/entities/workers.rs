@@ -68,6 +68,10 @@ impl<'a> StateApplicator<'a> {
                     reg.enable_fetch = Set(state_worker.enable_fetch);
                     reg.enable_eval = Set(state_worker.enable_eval);
                     reg.enable_build = Set(state_worker.enable_build);
+                    reg.labels = Set(worker_labels::labels_to_json(&state_worker.labels));
+                    reg.maintenance_windows = Set(worker_maintenance::windows_to_json(
+                        &state_worker.maintenance_windows,
+                    ));
                     reg.created_by = Set(Some(created_by_id));
                     reg.update(self.db).await?;
                     tracing::info!(
@@ -88,6 +92,11 @@ impl<'a> StateApplicator<'a> {
                         enable_fetch: state_worker.enable_fetch,
                         enable_eval: state_worker.enable_eval,
                         enable_build: state_worker.enable_build,
+                        labels: worker_labels::labels_to_json(&state_worker.labels),
+                        cordoned: false,
+                        maintenance_windows: worker_maintenance::windows_to_json(
+                            &state_worker.maintenance_windows,
+                        ),
                         created_by: Some(created_by_id),
                         created_at: now(),
                     }
@@ -227,6 +236,8 @@ mod base_worker_tests {
             base_worker: true,
             authorize_against: None,
             enabled: true,
+            labels: Default::default(),
+            maintenance_windows: Vec::new(),
         }
     }
 
@@ -199,6 +199,41 @@ fn state_project_keep_evaluations_zero_rejected_by_validator() {
     );
 }
 
+#[test]
+fn state_project_build_limits_reject_negative_values() {
+    let json = r#"{
+        "projects": {
+            "web": {
+                "name": "web",
+                "organization": "acme",
+                "display_name": "Web",
+                "repository": "https://example.com/acme/web.git",
+                "created_by": "alice",
+                "build_timeout_secs": 0,
+                "max_output_size": -1
+            }
+        }
+    }"#;
+    let cfg: StateConfiguration = serde_json::from_str(json).unwrap();
+    assert_eq!(cfg.projects["web"].build_timeout_secs, Some(0));
+    assert_eq!(cfg.projects["web"].build_max_silent_secs, None);
+
+    let v = cfg.validate();
+    assert!(
+        v.errors
+            .iter()
+            .any(|e| e.field == "projects.web.max_output_size"),
+        "expected max_output_size validation error, got: {:?}",
+        v.errors
+    );
+    assert!(
+        !v.errors
+            .iter()
+            .any(|e| e.field == "projects.web.build_timeout_secs"),
+        "0 means no limit and is valid"
+    );
+}
+
 #[test]
 fn state_project_actions_round_trip_all_types() {
     let json = r#"{
@@ -628,6 +663,62 @@ fn base_worker_accepts_valid_authorize_against_and_empty_orgs() {
     assert!(cfg.validate().is_valid, "{:?}", cfg.validate().errors);
 }
 
+#[test]
+fn workers_reject_invalid_and_base_worker_labels() {
+    let mut cfg = base_worker_cfg(r#""018f6f3a-0000-7000-8000-000000000001""#);
+    let worker = cfg.workers.get_mut("base-1").unwrap();
+    worker.labels.insert("pool".into(), "secure".into());
+    let v = cfg.validate();
+    assert!(
+        v.errors
+            .iter()
+            .any(|e| e.field.ends_with(".labels") && e.message.contains("Base workers")),
+        "expected base worker labels error, got: {:?}",
+        v.errors
+    );
+
+    let worker = cfg.workers.get_mut("base-1").unwrap();
+    worker.base_worker = false;
+    worker.organizations = vec![];
+    worker.labels = [("bad key".to_string(), "x".to_string())].into();
+    let v = cfg.validate();
+    assert!(
+        v.errors
+            .iter()
+            .any(|e| e.field.ends_with(".labels") && e.message.contains("label key")),
+        "expected invalid label key error, got: {:?}",
+        v.errors
+    );
+}
+
+#[test]
+fn workers_reject_invalid_and_base_worker_maintenance_windows() {
+    let mut cfg = base_worker_cfg(r#""018f6f3a-0000-7000-8000-000000000001""#);
+    let worker = cfg.workers.get_mut("base-1").unwrap();
+    worker.maintenance_windows = vec![gradient_types::MaintenanceWindow {
+        schedule: "0 0 3 * * Sun".into(),
+        duration_secs: 0,
+    }];
+    let v = cfg.validate();
+    let window_errors: Vec<_> = v
+        .errors
+        .iter()
+        .filter(|e| e.field.ends_with(".maintenance_windows"))
+        .collect();
+    assert!(
+        window_errors
+            .iter()
+            .any(|e| e.message.contains("Base workers")),
+        "expected base worker window error, got: {:?}",
+        v.errors
+    );
+    assert!(
+        window_errors.iter().any(|e| e.message.contains("duration")),
+        "expected window duration error, got: {:?}",
+        v.errors
+    );
+}
+
 #[test]
 fn state_org_accepts_explicit_id() {
     let json = r#"{
@@ -968,3 +1059,85 @@ fn resolves_scim_group_to_org_role_grants() {
     assert_eq!(resolved.get("ops"), Some(&vec![(org, role)]));
     assert!(!resolved.contains_key("unmapped"));
 }
+
+#[test]
+fn state_org_validator_rejects_non_positive_quotas() {
+    let json = r#"{
+        "users": {
+            "alice": { "username": "alice", "name": "Alice", "email": "a@x.io", "password_file": "/dev/null" }
+        },
+        "organizations": {
+            "acme": {
+                "name": "acme", "display_name": "ACME",
+                "private_key_file": "/dev/null", "public": false, "created_by": "alice",
+                "max_concurrent_builds": 0, "max_concurrent_evaluations": 4, "build_minutes_budget": -5
+            }
+        }
+    }"#;
+    let cfg: StateConfiguration = serde_json::from_str(json).unwrap();
+    assert_eq!(
+        cfg.organizations["acme"].max_concurrent_evaluations,
+        Some(4)
+    );
+    let v = cfg.validate();
+    assert!(!v.is_valid);
+    let fields: Vec<&str> = v.errors.iter().map(|e| e.field.as_str()).collect();
+    assert!(
+        fields.contains(&"organizations.acme.max_concurrent_builds"),
+        "{fields:?}"
+    );
+    assert!(
+        fields.contains(&"organizations.acme.build_minutes_budget"),
+        "{fields:?}"
+    );
+    assert!(
+        !fields.contains(&"organizations.acme.max_concurrent_evaluations"),
+        "{fields:?}"
+    );
+}
+
+#[test]
+fn state_cache_pins_validated() {
+    let json = r#"{
+        "users": {
+            "alice": {
+                "username": "alice",
+                "name": "Alice",
+                "email": "alice@example.com",
+                "password_file": "/dev/null"
+            }
+        },
+        "caches": {
+            "main": {
+                "name": "main",
+                "display_name": "Main",
+                "signing_key_file": "/dev/null",
+                "public": false,
+                "created_by": "alice",
+                "pins": [
+                    { "name": "release", "store_path": "/nix/store/aaaa-hello" },
+                    { "name": "release", "evaluation": "0190c6f0-0000-7000-8000-000000000000" },
+                    { "name": "both", "store_path": "/nix/store/bbbb-x",
+                      "evaluation": "0190c6f0-0000-7000-8000-000000000000" },
+                    { "name": "bad-path", "store_path": "nodash",
+                      "expires_at": "2030-01-01T00:00:00" }
+                ]
+            }
+        }
+    }"#;
+    let cfg: StateConfiguration = serde_json::from_str(json).unwrap();
+    assert_eq!(cfg.caches["main"].pins.len(), 4);
+    let v = cfg.validate();
+    assert!(!v.is_valid);
+    let fields: Vec<&str> = v.errors.iter().map(|e| e.field.as_str()).collect();
+    assert!(
+        fields.contains(&"caches.main.pins.release.name"),
+        "{fields:?}"
+    );
+    assert!(fields.contains(&"caches.main.pins.both"), "{fields:?}");
+    assert!(
+        fields.contains(&"caches.main.pins.bad-path.store_path"),
+        "{fields:?}"
+    );
+    assert_eq!(v.errors.len(), 3, "{:?}", v.errors);
+}
@@ -6,6 +6,8 @@
 
 use super::helpers::{EntityLookup, ErrorCollector};
 use gradient_db::permissions::CachePermission;
+use gradient_entity::StorePath;
+use gradient_types::input::check_index_name;
 use std::collections::HashSet;
 
 pub(super) fn validate(lookup: &EntityLookup, errors: &mut ErrorCollector) {
@@ -79,5 +81,31 @@ pub(super) fn validate(lookup: &EntityLookup, errors: &mut ErrorCollector) {
                 );
             }
         }
+
+        let mut pin_names_seen: HashSet<&str> = HashSet::new();
+        for pin in &cache.pins {
+            let field = format!("caches.{}.pins.{}", cache.name, pin.name);
+            if check_index_name(&pin.name).is_err() {
+                errors.push(format!("{field}.name"), "Invalid pin name");
+            }
+            if !pin_names_seen.insert(pin.name.as_str()) {
+                errors.push(
+                    format!("{field}.name"),
+                    format!(
+                        "Duplicate pin name '{}' in cache '{}'",
+                        pin.name, cache.name
+                    ),
+                );
+            }
+            match (&pin.store_path, pin.evaluation) {
+                (Some(path), None) => {
+                    if let Err(e) = StorePath::parse(path) {
+                        errors.push(format!("{field}.store_path"), e.to_string());
+                    }
+                }
+                (None, Some(_)) => {}
+                _ => errors.push(field, "Exactly one of store_path or evaluation must be set"),
+            }
+        }
     }
 }
@@ -35,6 +35,19 @@ pub(super) fn validate(lookup: &EntityLookup, errors: &mut ErrorCollector) {
             }
         }
 
+        for (field, value) in [
+            ("max_concurrent_builds", org.max_concurrent_builds),
+            ("max_concurrent_evaluations", org.max_concurrent_evaluations),
+            ("build_minutes_budget", org.build_minutes_budget),
+        ] {
+            if value.is_some_and(|v| v <= 0) {
+                errors.push(
+                    format!("organizations.{}.{}", org.name, field),
+                    "Quota must be positive (omit it for unlimited)".to_string(),
+                );
+            }
+        }
+
         let declared_org_role_names: HashSet<&str> = config
             .roles
             .values()
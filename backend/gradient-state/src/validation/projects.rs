@@ -6,6 +6,7 @@
 
 use super::helpers::{EntityLookup, ErrorCollector};
 use gradient_types::triggers::TriggerType;
+use gradient_types::worker_labels::validate_labels;
 use std::collections::HashSet;
 
 pub(super) fn validate(lookup: &EntityLookup, errors: &mut ErrorCollector) {
@@ -39,6 +40,33 @@ pub(super) fn validate(lookup: &EntityLookup, errors: &mut ErrorCollector) {
             );
         }
 
+        if let Err(e) = validate_labels(&project.worker_selector) {
+            errors.push(
+                format!("projects.{}.worker_selector", project.name),
+                e.to_string(),
+            );
+        }
+        for (field, value) in [
+            ("build_timeout_secs", project.build_timeout_secs),
+            ("build_max_silent_secs", project.build_max_silent_secs),
+            ("max_output_size", project.max_output_size),
+        ] {
+            if value.is_some_and(|v| v < 0) {
+                errors.push(
+                    format!("projects.{}.{field}", project.name),
+                    format!("{field} must not be negative"),
+                );
+            }
+        }
+        for trigger in project.triggers.iter().flatten() {
+            if let Err(e) = validate_labels(&trigger.worker_selector) {
+                errors.push(
+                    format!("projects.{}.triggers.worker_selector", project.name),
+                    e.to_string(),
+                );
+            }
+        }
+
         let mut action_names: HashSet<&str> = HashSet::new();
         for action in &project.actions {
             if !matches!(
@@ -80,14 +108,16 @@ pub(super) fn validate(lookup: &EntityLookup, errors: &mut ErrorCollector) {
         for trigger in project.triggers.iter().flatten() {
             if !matches!(
                 trigger.trigger_type,
-                TriggerType::ReporterPush | TriggerType::ReporterPullRequest
+                TriggerType::ReporterPush
+                    | TriggerType::ReporterPullRequest
+                    | TriggerType::ReporterMergeQueue
             ) {
                 continue;
             }
             let Some(name) = &trigger.integration else {
                 errors.push(
                     format!("projects.{}.triggers", project.name),
-                    "reporter_push/reporter_pull_request triggers require an `integration`",
+                    "reporter_push/reporter_pull_request/reporter_merge_queue triggers require an `integration`",
                 );
                 continue;
             };
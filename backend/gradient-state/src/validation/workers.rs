@@ -5,6 +5,8 @@
  */
 
 use super::helpers::{EntityLookup, ErrorCollector};
+use gradient_types::worker_labels::validate_labels;
+use gradient_types::worker_maintenance::validate_windows;
 
 pub(super) fn validate(lookup: &EntityLookup, errors: &mut ErrorCollector) {
     for worker in lookup.config.workers.values() {
@@ -25,6 +27,32 @@ pub(super) fn validate(lookup: &EntityLookup, errors: &mut ErrorCollector) {
             );
         }
 
+        if worker.base_worker && !worker.labels.is_empty() {
+            errors.push(
+                format!("workers.{}.labels", worker.worker_id),
+                "Base workers cannot carry registration labels; set GRADIENT_WORKER_LABELS on the worker instead",
+            );
+        }
+        if let Err(e) = validate_labels(&worker.labels) {
+            errors.push(
+                format!("workers.{}.labels", worker.worker_id),
+                e.to_string(),
+            );
+        }
+
+        if worker.base_worker && !worker.maintenance_windows.is_empty() {
+            errors.push(
+                format!("workers.{}.maintenance_windows", worker.worker_id),
+                "Base workers cannot carry maintenance windows",
+            );
+        }
+        if let Err(e) = validate_windows(&worker.maintenance_windows) {
+            errors.push(
+                format!("workers.{}.maintenance_windows", worker.worker_id),
+                e.to_string(),
+            );
+        }
+
         for org in &worker.organizations {
             if !lookup.org_exists(org) {
                 errors.push(
@@ -10,7 +10,9 @@ mod layout;
 pub mod log;
 pub mod log_chunk;
 pub mod nar;
+pub mod nar_chunk;
 pub mod nar_extract;
+pub mod nar_transcode;
 pub mod partial;
 pub mod sgr;
 pub mod source_nar;
@@ -19,4 +21,5 @@ pub use self::context::StorageCtx;
 pub use self::digest::{VerifyError, file_hash_sri, verify_nar_bytes, verify_nar_reader};
 pub use self::log::*;
 pub use self::nar::*;
+pub use self::nar_chunk::ChunkManifest;
 pub use self::partial::PartialStore;
@@ -7,11 +7,14 @@
 use anyhow::Result;
 use futures::future::BoxFuture;
 use gradient_types::ids::BuildAttemptId;
+use object_store::{GetOptions, GetRange};
 use object_store::{ObjectStore, ObjectStoreExt as _, PutPayload, path::Path as ObjectPath};
+use std::collections::VecDeque;
+use std::io::SeekFrom;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::fs::{self, OpenOptions};
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
 use tracing::warn;
 
 /// Abstraction for build log storage.
@@ -27,6 +30,20 @@ pub trait LogStorage: Send + Sync + std::fmt::Debug {
     /// Read the full log for `attempt_id`. Returns an empty string when no log exists yet.
     fn read<'a>(&'a self, attempt_id: BuildAttemptId) -> BoxFuture<'a, Result<String>>;
 
+    /// Read at most the last `max_bytes` of the log for `attempt_id`, starting
+    /// at a line boundary when the log is longer. The default reads the whole
+    /// log; the real backends override it to read only the tail.
+    fn read_tail<'a>(
+        &'a self,
+        attempt_id: BuildAttemptId,
+        max_bytes: usize,
+    ) -> BoxFuture<'a, Result<String>> {
+        Box::pin(async move {
+            let log = self.read(attempt_id).await?;
+            Ok(log_tail(&log, max_bytes).to_string())
+        })
+    }
+
     /// Called once after the build reaches a terminal state. Default impl is a no-op;
     /// remote backends use this hook to upload the local file to object storage.
     fn finalize<'a>(&'a self, _attempt_id: BuildAttemptId) -> BoxFuture<'a, Result<()>> {
@@ -83,6 +100,55 @@ pub trait LogStorage: Send + Sync + std::fmt::Debug {
             Ok(out)
         })
     }
+
+    /// Like [`Self::reassemble_chunks`], but keeps only the chunks that make up
+    /// the last `max_bytes`.
+    fn reassemble_chunks_tail<'a>(
+        &'a self,
+        attempt_id: BuildAttemptId,
+        max_bytes: usize,
+    ) -> BoxFuture<'a, Result<String>> {
+        Box::pin(async move {
+            let mut kept: VecDeque<String> = VecDeque::new();
+            let mut kept_bytes = 0;
+            let mut index = 0u32;
+            while let Ok(raw) = self.read_chunk(attempt_id, index).await {
+                let bytes = zstd::stream::decode_all(&raw[..])?;
+                let text = String::from_utf8_lossy(&bytes).into_owned();
+                kept_bytes += text.len();
+                kept.push_back(text);
+                while kept
+                    .front()
+                    .is_some_and(|front| kept_bytes - front.len() >= max_bytes)
+                {
+                    kept_bytes -= kept.pop_front().map_or(0, |front| front.len());
+                }
+                index += 1;
+            }
+            let out: String = kept.into_iter().collect();
+            Ok(log_tail(&out, max_bytes).to_string())
+        })
+    }
+}
+
+/// The last `max_bytes` of `log`, starting at a line so no partial line is
+/// returned. The whole log when it fits. Callers reading a suffix from storage
+/// fetch one byte more, so a window that begins on a line is recognised.
+fn log_tail(log: &str, max_bytes: usize) -> &str {
+    if log.len() <= max_bytes {
+        return log;
+    }
+    let mut start = log.len() - max_bytes;
+    while !log.is_char_boundary(start) {
+        start += 1;
+    }
+    if log.as_bytes()[start - 1] == b'\n' {
+        return &log[start..];
+    }
+    match log[start..].find('\n') {
+        Some(i) => &log[start + i + 1..],
+        None => &log[start..],
+    }
 }
 
 #[derive(Debug)]
@@ -191,6 +257,32 @@ impl LogStorage for FileLogStorage {
         })
     }
 
+    fn read_tail<'a>(
+        &'a self,
+        attempt_id: BuildAttemptId,
+        max_bytes: usize,
+    ) -> BoxFuture<'a, Result<String>> {
+        Box::pin(async move {
+            let mut file = match fs::File::open(self.log_path(attempt_id)).await {
+                Ok(file) => file,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    return self.reassemble_chunks_tail(attempt_id, max_bytes).await;
+                }
+                Err(e) => return Err(e.into()),
+            };
+            let len = file.metadata().await?.len();
+            if len == 0 {
+                return self.reassemble_chunks_tail(attempt_id, max_bytes).await;
+            }
+            file.seek(SeekFrom::Start(len.saturating_sub(max_bytes as u64 + 1)))
+                .await?;
+            let mut bytes = Vec::with_capacity(max_bytes.min(len as usize));
+            file.read_to_end(&mut bytes).await?;
+            let text = String::from_utf8_lossy(&bytes);
+            Ok(log_tail(&text, max_bytes).to_string())
+        })
+    }
+
     fn delete<'a>(&'a self, attempt_id: BuildAttemptId) -> BoxFuture<'a, Result<()>> {
         Box::pin(async move {
             self.delete_chunks(attempt_id).await.ok();
@@ -343,6 +435,38 @@ impl LogStorage for S3LogStorage {
         })
     }
 
+    fn read_tail<'a>(
+        &'a self,
+        attempt_id: BuildAttemptId,
+        max_bytes: usize,
+    ) -> BoxFuture<'a, Result<String>> {
+        Box::pin(async move {
+            let local = self.local.read_tail(attempt_id, max_bytes).await?;
+            if !local.is_empty() {
+                return Ok(local);
+            }
+            let opts = GetOptions {
+                range: Some(GetRange::Suffix(max_bytes as u64 + 1)),
+                ..Default::default()
+            };
+            match self
+                .object_store
+                .get_opts(&self.object_path(attempt_id), opts)
+                .await
+            {
+                Ok(result) => {
+                    let bytes = result.bytes().await?;
+                    let text = String::from_utf8_lossy(&bytes);
+                    Ok(log_tail(&text, max_bytes).to_string())
+                }
+                Err(object_store::Error::NotFound { .. }) => {
+                    self.reassemble_chunks_tail(attempt_id, max_bytes).await
+                }
+                Err(e) => Err(e.into()),
+            }
+        })
+    }
+
     fn finalize<'a>(&'a self, attempt_id: BuildAttemptId) -> BoxFuture<'a, Result<()>> {
         Box::pin(async move {
             let path = self.local.log_path(attempt_id);
@@ -478,6 +602,41 @@ mod chunk_tests {
     use super::*;
     use gradient_types::ids::BuildAttemptId;
 
+    #[test]
+    fn log_tail_starts_at_a_line() {
+        assert_eq!(log_tail("short", 64), "short");
+        assert_eq!(log_tail("first line\nsecond\nlast\n", 9), "last\n");
+        assert_eq!(log_tail("ääää", 3), "ä");
+    }
+
+    #[tokio::test]
+    async fn read_tail_reads_inline_and_chunked_logs() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FileLogStorage::new(dir.path()).await.unwrap();
+        let inline = BuildAttemptId::new(uuid::Uuid::new_v4());
+        storage
+            .append(inline, "noise\nnoise\nerror: boom\n")
+            .await
+            .unwrap();
+        assert_eq!(
+            storage.read_tail(inline, 14).await.unwrap(),
+            "error: boom\n"
+        );
+
+        let chunked = BuildAttemptId::new(uuid::Uuid::new_v4());
+        for (index, text) in ["a\n".repeat(100), "b\n".repeat(100), "end\n".into()]
+            .iter()
+            .enumerate()
+        {
+            let raw = zstd::stream::encode_all(text.as_bytes(), 0).unwrap();
+            storage
+                .write_chunk(chunked, index as u32, &raw)
+                .await
+                .unwrap();
+        }
+        assert_eq!(storage.read_tail(chunked, 8).await.unwrap(), "b\nb\nend\n");
+    }
+
     #[tokio::test]
     async fn write_read_delete_chunk_roundtrip() {
         let dir = tempfile::tempdir().unwrap();
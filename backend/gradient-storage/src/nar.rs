@@ -4,19 +4,30 @@
  * SPDX-License-Identifier: AGPL-3.0-only
  */
 
+use crate::nar_chunk::{ChunkManifest, ChunkRef, Chunker, chunk_hash};
 use anyhow::{Context, Result};
 use bytes::Bytes;
-use futures::StreamExt as _;
 use futures::stream::BoxStream;
+use futures::{StreamExt as _, TryStreamExt as _};
+use harmonia_utils_hash::{Algorithm, HashFormat as _, Sha256};
 use object_store::{ClientOptions, ObjectStore, ObjectStoreExt as _, PutPayload, path::Path};
 pub use object_store::{MultipartUpload, WriteMultipart};
+use std::collections::{BTreeMap, HashSet};
 use std::sync::Arc;
 use tokio::io::{AsyncRead, AsyncReadExt as _};
 
+/// Chunk objects opened ahead of the one being streamed when serving a
+/// chunked NAR.
+const CHUNK_PREFETCH: usize = 4;
+
 /// Unified NAR file storage abstraction over local disk or an S3-compatible backend.
 ///
 /// All NARs are stored pre-compressed (`.nar.zst`). The key path within the store is
 /// `nars/{hash[..2]}/{hash[2..]}.nar.zst` (same two-level sharding used locally).
+/// A NAR converted to the chunked layout (see [`crate::nar_chunk`]) has a
+/// `nars/{hash[..2]}/{hash[2..]}.nar.chunks` manifest instead, pointing at
+/// shared `nar-chunks/{chunk[..2]}/{chunk}.zst` frames; every read falls back
+/// to the manifest when the flat object is absent.
 #[derive(Clone)]
 pub struct NarStore {
     inner: Arc<dyn ObjectStore>,
@@ -110,6 +121,14 @@ impl NarStore {
     }
 
     fn object_path(&self, hash: &str) -> Path {
+        self.nar_path(hash, "nar.zst")
+    }
+
+    fn manifest_path(&self, hash: &str) -> Path {
+        self.nar_path(hash, "nar.chunks")
+    }
+
+    fn nar_path(&self, hash: &str, ext: &str) -> Path {
         // Hash is validated at every callable entry point, but defend the
         // formatter anyway: a too-short hash would otherwise panic on
         // `&hash[..2]` / `&hash[2..]`.
@@ -118,7 +137,13 @@ impl NarStore {
         } else {
             ("__", hash)
         };
-        Path::from(format!("{}nars/{}/{}.nar.zst", self.prefix, shard, stem,))
+        Path::from(format!("{}nars/{}/{}.{}", self.prefix, shard, stem, ext))
+    }
+
+    /// Object-store path of a shared NAR chunk frame, keyed by its hex hash.
+    fn chunk_path(&self, chunk: &str) -> Path {
+        let shard = chunk.get(..2).unwrap_or("__");
+        Path::from(format!("{}nar-chunks/{}/{}.zst", self.prefix, shard, chunk))
     }
 
     /// One canonical `HEAD`, mapping `NotFound` to `None`. Backs `exists`,
@@ -254,7 +279,10 @@ impl NarStore {
     /// not rewrite the object - on a versioning-enabled bucket every rewrite is
     /// a retained version that no S3-API GC can reclaim.
     pub async fn exists(&self, hash: &str) -> Result<bool> {
-        Ok(self.head_object(&self.object_path(hash)).await?.is_some())
+        if self.head_object(&self.object_path(hash)).await?.is_some() {
+            return Ok(true);
+        }
+        Ok(self.head_object(&self.manifest_path(hash)).await?.is_some())
     }
 
     /// Size in bytes of the stored NAR object for `hash`, or `None` when
@@ -262,10 +290,10 @@ impl NarStore {
     /// bytes directly to object storage, so this HEAD is the only server-side
     /// evidence the object actually landed with the reported size.
     pub async fn head_size(&self, hash: &str) -> Result<Option<u64>> {
-        Ok(self
-            .head_object(&self.object_path(hash))
-            .await?
-            .map(|meta| meta.size))
+        if let Some(meta) = self.head_object(&self.object_path(hash)).await? {
+            return Ok(Some(meta.size));
+        }
+        Ok(self.manifest(hash).await?.map(|m| m.size()))
     }
 
     /// Verify a stored NAR object against its reported file_hash and size.
@@ -349,7 +377,18 @@ impl NarStore {
     }
 
     pub async fn get(&self, hash: &str) -> Result<Option<Vec<u8>>> {
-        self.get_object(&self.object_path(hash)).await
+        if let Some(bytes) = self.get_object(&self.object_path(hash)).await? {
+            return Ok(Some(bytes));
+        }
+        let Some(manifest) = self.manifest(hash).await? else {
+            return Ok(None);
+        };
+        let mut out = Vec::with_capacity(manifest.size() as usize);
+        let mut stream = self.chunked_stream(&manifest, 0);
+        while let Some(bytes) = stream.next().await {
+            out.extend_from_slice(&bytes?);
+        }
+        Ok(Some(out))
     }
 
     /// Streaming counterpart to [`Self::get`].
@@ -362,7 +401,13 @@ impl NarStore {
         &self,
         hash: &str,
     ) -> Result<Option<(u64, BoxStream<'static, Result<Bytes>>)>> {
-        self.get_stream_object(&self.object_path(hash), None).await
+        if let Some(found) = self
+            .get_stream_object(&self.object_path(hash), None)
+            .await?
+        {
+            return Ok(Some(found));
+        }
+        self.get_chunked_stream(hash, 0).await
     }
 
     /// Range variant of [`Self::get_stream`]: streams the stored object from
@@ -375,14 +420,152 @@ impl NarStore {
         hash: &str,
         offset: u64,
     ) -> Result<Option<(u64, BoxStream<'static, Result<Bytes>>)>> {
-        self.get_stream_object(&self.object_path(hash), Some(offset))
-            .await
+        if let Some(found) = self
+            .get_stream_object(&self.object_path(hash), Some(offset))
+            .await?
+        {
+            return Ok(Some(found));
+        }
+        self.get_chunked_stream(hash, offset).await
     }
 
+    /// Removes the NAR in either layout. The chunks a manifest pointed at are
+    /// shared and refcounted in the database, so they stay.
     pub async fn delete(&self, hash: &str) -> Result<()> {
+        self.delete_object(&self.object_path(hash)).await?;
+        self.delete_object(&self.manifest_path(hash)).await
+    }
+
+    /// Removes only the flat `.nar.zst` object, once its chunked copy is recorded.
+    pub async fn delete_flat(&self, hash: &str) -> Result<()> {
         self.delete_object(&self.object_path(hash)).await
     }
 
+    /// Removes only the chunk manifest, leaving a flat object in place.
+    pub async fn delete_manifest(&self, hash: &str) -> Result<()> {
+        self.delete_object(&self.manifest_path(hash)).await
+    }
+
+    pub async fn delete_chunk(&self, chunk: &str) -> Result<()> {
+        self.delete_object(&self.chunk_path(chunk)).await
+    }
+
+    /// The chunk manifest for `hash`, or `None` when the NAR is not chunked.
+    pub async fn manifest(&self, hash: &str) -> Result<Option<ChunkManifest>> {
+        match self.get_object(&self.manifest_path(hash)).await? {
+            Some(bytes) => ChunkManifest::parse(&bytes).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_chunked_stream(
+        &self,
+        hash: &str,
+        offset: u64,
+    ) -> Result<Option<(u64, BoxStream<'static, Result<Bytes>>)>> {
+        let Some(manifest) = self.manifest(hash).await? else {
+            return Ok(None);
+        };
+        Ok(Some((
+            manifest.size(),
+            self.chunked_stream(&manifest, offset),
+        )))
+    }
+
+    /// The manifest's frames back to back from byte `offset` of the
+    /// concatenation, opening up to [`CHUNK_PREFETCH`] chunks ahead.
+    fn chunked_stream(
+        &self,
+        manifest: &ChunkManifest,
+        offset: u64,
+    ) -> BoxStream<'static, Result<Bytes>> {
+        let mut parts = Vec::new();
+        let mut start = 0u64;
+        for chunk in &manifest.chunks {
+            let end = start + chunk.size;
+            if end > offset {
+                let skip = offset.saturating_sub(start);
+                parts.push((self.chunk_path(&chunk.hash), (skip > 0).then_some(skip)));
+            }
+            start = end;
+        }
+
+        let store = self.clone();
+        futures::stream::iter(parts)
+            .map(move |(path, skip)| {
+                let store = store.clone();
+                async move {
+                    match store.get_stream_object(&path, skip).await? {
+                        Some((_, stream)) => Ok(stream),
+                        None => Err(anyhow::anyhow!("NAR chunk {path} missing from storage")),
+                    }
+                }
+            })
+            .buffered(CHUNK_PREFETCH)
+            .try_flatten()
+            .boxed()
+    }
+
+    /// Converts the flat NAR `hash` to the chunked layout: decompresses it,
+    /// splits it with a [`Chunker`], stores every chunk not already present as
+    /// its own zstd frame, then writes the manifest. The flat object is left
+    /// alone - the caller records the result and then calls
+    /// [`Self::delete_flat`]. Returns `None` when there is no flat object.
+    pub async fn chunk_nar(&self, hash: &str, zstd_level: i32) -> Result<Option<ChunkedNar>> {
+        let Some((_, stream)) = self
+            .get_stream_object(&self.object_path(hash), None)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let mut reader = crate::nar_extract::nar_reader_from_stream(stream);
+        let mut chunker = Chunker::default();
+        let mut file_hash = harmonia_utils_hash::Context::new(Algorithm::SHA256);
+        let mut result = ChunkedNar::default();
+        let mut seen = HashSet::new();
+        let mut buf = vec![0u8; 256 * 1024];
+        loop {
+            let n = reader
+                .read(&mut buf)
+                .await
+                .with_context(|| format!("decompress NAR {hash} for chunking"))?;
+            let raws = if n == 0 {
+                std::mem::take(&mut chunker).finish().into_iter().collect()
+            } else {
+                chunker.push(&buf[..n])
+            };
+            for raw in raws {
+                let frame = zstd::encode_all(&raw[..], zstd_level).context("compress NAR chunk")?;
+                file_hash.update(&frame);
+                let chunk = chunk_hash(&raw);
+                if seen.insert(chunk.clone())
+                    && self.head_object(&self.chunk_path(&chunk)).await?.is_none()
+                {
+                    self.put_object(self.chunk_path(&chunk), frame.clone())
+                        .await?;
+                    result.new_chunks.push(chunk.clone());
+                }
+                result.manifest.chunks.push(ChunkRef {
+                    hash: chunk,
+                    size: frame.len() as u64,
+                    raw_size: raw.len() as u64,
+                });
+            }
+            if n == 0 {
+                break;
+            }
+        }
+
+        let digest = Sha256::try_from(file_hash.finish())
+            .map_err(|_| anyhow::anyhow!("sha256 finalize produced non-sha256"))?;
+        result.file_hash =
+            gradient_util::nix_hash::normalize_nar_hash(&digest.as_sri().to_string());
+        self.put_object(self.manifest_path(hash), result.manifest.encode())
+            .await?;
+        Ok(Some(result))
+    }
+
     /// Returns the local base path when using local-disk storage; `None` for S3.
     pub fn local_base(&self) -> Option<&str> {
         self.local_base.as_deref()
@@ -399,6 +582,11 @@ impl NarStore {
         hash: &str,
         expires_in: std::time::Duration,
     ) -> Result<Option<String>> {
+        // A chunked NAR has no single object to sign; the caller falls back
+        // to streaming it through the server.
+        if self.s3_signer.is_some() && self.head_object(&self.object_path(hash)).await?.is_none() {
+            return Ok(None);
+        }
         self.presign_object(reqwest::Method::GET, &self.object_path(hash), expires_in)
             .await
     }
@@ -500,7 +688,8 @@ impl NarStore {
         self.delete_object(&self.blob_path(org, hash)).await
     }
 
-    /// Lists all NAR hashes currently present in the store (both local and S3).
+    /// Lists all NAR hashes currently present in the store (both local and S3),
+    /// flat or chunked.
     /// Returns the full hash strings as stored (e.g. `"ab12cd34..."`).
     pub async fn list_hashes(&self) -> Result<Vec<String>> {
         Ok(self
@@ -520,23 +709,53 @@ impl NarStore {
     pub async fn list_hashes_with_modified(&self) -> Result<Vec<(String, i64)>> {
         let prefix = Path::from(format!("{}nars", self.prefix));
         let mut stream = self.inner.list(Some(&prefix));
-        let mut hashes = Vec::new();
+        // A NAR mid-conversion briefly has both objects; report it once, with
+        // the newer timestamp.
+        let mut hashes = BTreeMap::new();
         while let Some(item) = stream.next().await {
             let meta = item.context("Failed to list NAR store")?;
-            // Path format: `{prefix}nars/{first2}/{rest}.nar.zst`
+            // Path format: `{prefix}nars/{first2}/{rest}.nar.zst` (or `.nar.chunks`)
             let p = meta.location.to_string();
             if let Some(name) = p.split('/').next_back()
-                && let Some(stem) = name.strip_suffix(".nar.zst")
+                && let Some(stem) = name
+                    .strip_suffix(".nar.zst")
+                    .or_else(|| name.strip_suffix(".nar.chunks"))
             {
                 // Reconstruct full hash from parent dir + stem.
                 let parts: Vec<&str> = p.split('/').collect();
                 if parts.len() >= 2 {
                     let dir = parts[parts.len() - 2];
-                    hashes.push((format!("{}{}", dir, stem), meta.last_modified.timestamp()));
+                    let modified = meta.last_modified.timestamp();
+                    hashes
+                        .entry(format!("{}{}", dir, stem))
+                        .and_modify(|m: &mut i64| *m = (*m).max(modified))
+                        .or_insert(modified);
                 }
             }
         }
-        Ok(hashes)
+        Ok(hashes.into_iter().collect())
+    }
+
+    /// Lists every shared chunk frame under `nar-chunks/` with its object's
+    /// last-modified time as a unix timestamp (seconds), for the deep GC's
+    /// orphan-chunk pass.
+    pub async fn list_chunks_with_modified(&self) -> Result<Vec<(String, i64)>> {
+        let prefix = Path::from(format!("{}nar-chunks", self.prefix));
+        let mut stream = self.inner.list(Some(&prefix));
+        let mut out = Vec::new();
+        while let Some(item) = stream.next().await {
+            let meta = item.context("Failed to list NAR chunks")?;
+            // Path format: `{prefix}nar-chunks/{chunk[..2]}/{chunk}.zst`
+            let p = meta.location.to_string();
+            if let Some(chunk) = p
+                .split('/')
+                .next_back()
+                .and_then(|name| name.strip_suffix(".zst"))
+            {
+                out.push((chunk.to_string(), meta.last_modified.timestamp()));
+            }
+        }
+        Ok(out)
     }
 
     /// Lists every build-request blob currently in storage. Returns
@@ -576,6 +795,16 @@ impl NarStore {
     }
 }
 
+/// Outcome of [`NarStore::chunk_nar`].
+#[derive(Debug, Clone, Default)]
+pub struct ChunkedNar {
+    pub manifest: ChunkManifest,
+    /// `file_hash` of the served concatenation (`sha256:<nix32>`).
+    pub file_hash: String,
+    /// Chunks this conversion uploaded; everything else was already stored.
+    pub new_chunks: Vec<String>,
+}
+
 impl std::fmt::Debug for NarStore {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let backend = if self.local_base.is_some() {
@@ -765,6 +994,118 @@ mod tests {
         assert_eq!(buf, b"abcdef");
     }
 
+    fn noise(len: usize, seed: u64) -> Vec<u8> {
+        let mut x = seed | 1;
+        (0..len)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 7;
+                x ^= x << 17;
+                x as u8
+            })
+            .collect()
+    }
+
+    async fn collect(mut stream: BoxStream<'static, Result<Bytes>>) -> Vec<u8> {
+        let mut out = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            out.extend_from_slice(&chunk.expect("chunk"));
+        }
+        out
+    }
+
+    #[tokio::test]
+    async fn chunked_nar_serves_same_content() {
+        let (_d, store) = local_store();
+        let raw = noise(1024 * 1024, 5);
+        store
+            .put("ab12cd", zstd::encode_all(&raw[..], 3).unwrap())
+            .await
+            .unwrap();
+
+        let chunked = store.chunk_nar("ab12cd", 3).await.unwrap().expect("flat");
+        assert!(chunked.manifest.chunks.len() > 1);
+        assert_eq!(chunked.manifest.raw_size(), raw.len() as u64);
+        store.delete_flat("ab12cd").await.unwrap();
+
+        assert!(store.exists("ab12cd").await.unwrap());
+        let size = chunked.manifest.size();
+        assert_eq!(store.head_size("ab12cd").await.unwrap(), Some(size));
+        let served = store.get("ab12cd").await.unwrap().expect("chunked");
+        assert_eq!(served.len() as u64, size);
+        assert_eq!(zstd::decode_all(&served[..]).unwrap(), raw);
+        assert_eq!(
+            chunked.file_hash,
+            gradient_util::nix_hash::normalize_nar_hash(&crate::digest::file_hash_sri(&served))
+        );
+
+        let (full, stream) = store
+            .get_stream_from("ab12cd", 1000)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(full, size);
+        assert_eq!(collect(stream).await, served[1000..]);
+        let first = chunked.manifest.chunks[0].size;
+        let (_, stream) = store
+            .get_stream_from("ab12cd", first)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(collect(stream).await, served[first as usize..]);
+
+        assert_eq!(
+            store.list_hashes().await.unwrap(),
+            vec!["ab12cd".to_string()]
+        );
+        store.delete("ab12cd").await.unwrap();
+        assert!(!store.exists("ab12cd").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn chunking_reuses_stored_chunks() {
+        let (_d, store) = local_store();
+        let raw = noise(1024 * 1024, 9);
+        let mut edited = raw.clone();
+        edited.splice(500_000..500_000, b"patched".iter().copied());
+        store
+            .put("aa0001", zstd::encode_all(&raw[..], 3).unwrap())
+            .await
+            .unwrap();
+        store
+            .put("aa0002", zstd::encode_all(&edited[..], 3).unwrap())
+            .await
+            .unwrap();
+
+        let first = store.chunk_nar("aa0001", 3).await.unwrap().unwrap();
+        assert_eq!(first.new_chunks.len(), first.manifest.chunks.len());
+        let second = store.chunk_nar("aa0002", 3).await.unwrap().unwrap();
+        assert!(second.new_chunks.len() <= 2, "{:?}", second.new_chunks);
+        assert_eq!(store.list_hashes().await.unwrap().len(), 2);
+
+        let mut listed: Vec<String> = store
+            .list_chunks_with_modified()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(chunk, _)| chunk)
+            .collect();
+        listed.sort();
+        let mut stored: Vec<String> = first
+            .new_chunks
+            .into_iter()
+            .chain(second.new_chunks)
+            .collect();
+        stored.sort();
+        assert_eq!(listed, stored);
+    }
+
+    #[tokio::test]
+    async fn chunk_nar_none_without_flat_object() {
+        let (_d, store) = local_store();
+        assert!(store.chunk_nar("ab12cd", 3).await.unwrap().is_none());
+    }
+
     #[tokio::test]
     async fn get_stream_from_past_end_is_empty() {
         let (_d, store) = local_store();
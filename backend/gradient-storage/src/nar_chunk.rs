@@ -0,0 +1,298 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Content-defined chunking for the deduplicated NAR layout.
+//!
+//! A chunked NAR is split on content-defined boundaries (a FastCDC-style gear
+//! hash with normalized chunking) of the *uncompressed* NAR, so an edit early
+//! in a file only moves the boundaries around it. Every chunk is stored once
+//! as an independent zstd frame keyed by the SHA-256 of its raw bytes; a
+//! per-NAR [`ChunkManifest`] lists the frames in order. Concatenated zstd
+//! frames are themselves a valid zstd stream, so the served `.nar.zst` is just
+//! the frames back to back.
+
+use anyhow::{Context as _, Result, bail};
+use gradient_types::constants::{NAR_CHUNK_AVG_BYTES, NAR_CHUNK_MAX_BYTES, NAR_CHUNK_MIN_BYTES};
+use harmonia_utils_hash::Sha256;
+
+/// First line of every manifest object; bumped if the line format changes.
+const MANIFEST_HEADER: &str = "gradient-nar-chunks 1";
+
+/// Gear table for the rolling hash: 256 fixed pseudo-random words (SplitMix64),
+/// so chunk boundaries are stable across releases and hosts.
+const GEAR: [u64; 256] = gear_table();
+
+const fn gear_table() -> [u64; 256] {
+    let mut table = [0u64; 256];
+    let mut seed: u64 = 0;
+    let mut i = 0;
+    while i < 256 {
+        seed = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = seed;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        table[i] = z ^ (z >> 31);
+        i += 1;
+    }
+    table
+}
+
+/// Mask over the top `bits` bits of the gear hash. The top bits depend on the
+/// last 64 input bytes, the low bits only on the last few.
+const fn top_bits_mask(bits: u32) -> u64 {
+    !0u64 << (64 - bits)
+}
+
+/// Streaming content-defined chunker. Feed bytes with [`Chunker::push`], which
+/// returns every chunk completed so far; [`Chunker::finish`] yields the tail.
+/// Chunk boundaries depend only on content, never on how the input is split
+/// across `push` calls.
+#[derive(Debug)]
+pub struct Chunker {
+    min: usize,
+    avg: usize,
+    max: usize,
+    /// Stricter mask below `avg`, looser above: normalized chunking keeps
+    /// sizes clustered around `avg`.
+    mask_small: u64,
+    mask_large: u64,
+    buf: Vec<u8>,
+    /// Next byte of `buf` to feed into `hash`.
+    pos: usize,
+    hash: u64,
+}
+
+impl Default for Chunker {
+    fn default() -> Self {
+        Self::new(
+            NAR_CHUNK_MIN_BYTES,
+            NAR_CHUNK_AVG_BYTES,
+            NAR_CHUNK_MAX_BYTES,
+        )
+    }
+}
+
+impl Chunker {
+    /// `min <= avg <= max`; `avg` is rounded to a power of two for the mask.
+    pub fn new(min: usize, avg: usize, max: usize) -> Self {
+        let min = min.max(1);
+        let max = max.max(min);
+        let avg = avg.clamp(min, max);
+        let bits = avg.max(4).ilog2().clamp(2, 62);
+        Self {
+            min,
+            avg,
+            max,
+            mask_small: top_bits_mask(bits + 1),
+            mask_large: top_bits_mask(bits - 1),
+            buf: Vec::with_capacity(max),
+            pos: 0,
+            hash: 0,
+        }
+    }
+
+    pub fn push(&mut self, data: &[u8]) -> Vec<Vec<u8>> {
+        self.buf.extend_from_slice(data);
+        let mut out = Vec::new();
+        while let Some(cut) = self.next_cut() {
+            out.push(self.buf.drain(..cut).collect());
+            self.pos = 0;
+            self.hash = 0;
+        }
+        out
+    }
+
+    /// The final, possibly short chunk; `None` when the input ended on a
+    /// boundary (or was empty).
+    pub fn finish(self) -> Option<Vec<u8>> {
+        (!self.buf.is_empty()).then_some(self.buf)
+    }
+
+    /// Length of the next chunk if its boundary is already buffered. The
+    /// first `min` bytes are never hashed, so no chunk is shorter than `min`.
+    fn next_cut(&mut self) -> Option<usize> {
+        let end = self.buf.len().min(self.max);
+        self.pos = self.pos.max(self.min);
+        while self.pos < end {
+            self.hash = (self.hash << 1).wrapping_add(GEAR[self.buf[self.pos] as usize]);
+            self.pos += 1;
+            let mask = if self.pos <= self.avg {
+                self.mask_small
+            } else {
+                self.mask_large
+            };
+            if self.hash & mask == 0 {
+                return Some(self.pos);
+            }
+        }
+        (self.buf.len() >= self.max).then_some(self.max)
+    }
+}
+
+/// Object key of a chunk: lowercase hex SHA-256 of its uncompressed bytes.
+pub fn chunk_hash(raw: &[u8]) -> String {
+    hex::encode(Sha256::digest(raw).digest_bytes())
+}
+
+/// One chunk of a chunked NAR, in serving order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkRef {
+    pub hash: String,
+    /// Size of the stored zstd frame.
+    pub size: u64,
+    /// Size of the uncompressed chunk.
+    pub raw_size: u64,
+}
+
+/// Ordered chunk list of one NAR, stored next to where its flat object would
+/// be (`nars/{hh}/{rest}.nar.chunks`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChunkManifest {
+    pub chunks: Vec<ChunkRef>,
+}
+
+impl ChunkManifest {
+    /// Size of the served `.nar.zst`: all frames back to back.
+    pub fn size(&self) -> u64 {
+        self.chunks.iter().map(|c| c.size).sum()
+    }
+
+    /// Size of the uncompressed NAR.
+    pub fn raw_size(&self) -> u64 {
+        self.chunks.iter().map(|c| c.raw_size).sum()
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = format!("{MANIFEST_HEADER}\n");
+        for c in &self.chunks {
+            out.push_str(&format!("{} {} {}\n", c.hash, c.size, c.raw_size));
+        }
+        out.into_bytes()
+    }
+
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        let text = std::str::from_utf8(bytes).context("chunk manifest is not UTF-8")?;
+        let mut lines = text.lines();
+        if lines.next() != Some(MANIFEST_HEADER) {
+            bail!("unsupported chunk manifest header");
+        }
+        let mut chunks = Vec::new();
+        for (i, line) in lines.enumerate() {
+            let mut fields = line.split(' ');
+            let (Some(hash), Some(size), Some(raw_size), None) =
+                (fields.next(), fields.next(), fields.next(), fields.next())
+            else {
+                bail!("malformed chunk manifest line {}", i + 2);
+            };
+            if hash.len() != 64 || !hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+                bail!("invalid chunk hash on manifest line {}", i + 2);
+            }
+            chunks.push(ChunkRef {
+                hash: hash.to_string(),
+                size: size.parse().context("invalid chunk size")?,
+                raw_size: raw_size.parse().context("invalid chunk raw size")?,
+            });
+        }
+        Ok(Self { chunks })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-random bytes (xorshift), incompressible enough to
+    /// exercise real boundaries.
+    fn noise(len: usize, seed: u64) -> Vec<u8> {
+        let mut x = seed | 1;
+        (0..len)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 7;
+                x ^= x << 17;
+                x as u8
+            })
+            .collect()
+    }
+
+    fn chunk_all(data: &[u8], step: usize) -> Vec<Vec<u8>> {
+        let mut chunker = Chunker::default();
+        let mut out = Vec::new();
+        for piece in data.chunks(step) {
+            out.extend(chunker.push(piece));
+        }
+        out.extend(chunker.finish());
+        out
+    }
+
+    #[test]
+    fn chunks_reassemble_and_respect_bounds() {
+        let data = noise(3 * 1024 * 1024, 7);
+        let chunks = chunk_all(&data, 100_000);
+        assert_eq!(chunks.concat(), data);
+        let (last, rest) = chunks.split_last().unwrap();
+        assert!(!rest.is_empty());
+        for c in rest {
+            assert!((NAR_CHUNK_MIN_BYTES..=NAR_CHUNK_MAX_BYTES).contains(&c.len()));
+        }
+        assert!(last.len() <= NAR_CHUNK_MAX_BYTES);
+    }
+
+    #[test]
+    fn boundaries_ignore_push_granularity() {
+        let data = noise(1024 * 1024, 11);
+        assert_eq!(chunk_all(&data, 1), chunk_all(&data, 1024 * 1024));
+        assert_eq!(chunk_all(&data, 4093), chunk_all(&data, 65_536));
+    }
+
+    #[test]
+    fn prefix_insert_keeps_most_chunks() {
+        let data = noise(2 * 1024 * 1024, 3);
+        let mut shifted = b"a few inserted bytes".to_vec();
+        shifted.extend_from_slice(&data);
+
+        let before: std::collections::HashSet<String> = chunk_all(&data, 65_536)
+            .iter()
+            .map(|c| chunk_hash(c))
+            .collect();
+        let after = chunk_all(&shifted, 65_536);
+        let shared = after
+            .iter()
+            .filter(|c| before.contains(&chunk_hash(c)))
+            .count();
+        assert!(shared + 2 >= after.len(), "{shared} of {}", after.len());
+    }
+
+    #[test]
+    fn manifest_round_trips() {
+        let manifest = ChunkManifest {
+            chunks: vec![
+                ChunkRef {
+                    hash: chunk_hash(b"one"),
+                    size: 12,
+                    raw_size: 3,
+                },
+                ChunkRef {
+                    hash: chunk_hash(b"two"),
+                    size: 15,
+                    raw_size: 3,
+                },
+            ],
+        };
+        let parsed = ChunkManifest::parse(&manifest.encode()).unwrap();
+        assert_eq!(parsed, manifest);
+        assert_eq!(parsed.size(), 27);
+        assert_eq!(parsed.raw_size(), 6);
+    }
+
+    #[test]
+    fn manifest_rejects_garbage() {
+        assert!(ChunkManifest::parse(b"").is_err());
+        assert!(ChunkManifest::parse(b"gradient-nar-chunks 1\nnothex 1 1\n").is_err());
+        let extra = format!("gradient-nar-chunks 1\n{} 1 1 1\n", chunk_hash(b"x"));
+        assert!(ChunkManifest::parse(extra.as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,181 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! On-the-fly transcoding of stored zstd objects into the compression a
+//! cache client asked for.
+//!
+//! The codecs run as async readers stacked on the stored stream, so bytes are
+//! only pulled and re-encoded as the client reads. A slow client stalls the
+//! pipeline rather than buffering: a stream holds the zstd window, the xz
+//! encoder and one `NAR_TRANSCODE_CHUNK_BYTES` buffer per stage.
+
+use async_compression::Level;
+use async_compression::tokio::bufread::{XzEncoder, ZstdDecoder};
+use bytes::Bytes;
+use futures::StreamExt as _;
+use futures::stream::BoxStream;
+use gradient_types::NarCompression;
+use gradient_types::constants::{NAR_TRANSCODE_CHUNK_BYTES, NAR_XZ_PRESET};
+use std::io;
+use std::pin::Pin;
+use tokio::io::{AsyncRead, BufReader};
+use tokio_util::io::{ReaderStream, StreamReader};
+
+type ByteStream = BoxStream<'static, anyhow::Result<Bytes>>;
+
+/// Re-encode a zstd byte stream (as returned by `NarStore::get_stream`) in
+/// `to`. Zstd passes through untouched.
+pub fn transcode_stream(stream: ByteStream, to: NarCompression) -> ByteStream {
+    if to == NarCompression::Zstd {
+        return stream;
+    }
+
+    let reader = StreamReader::new(stream.map(|chunk| chunk.map_err(io::Error::other)));
+    // A chunked NAR is a run of concatenated zstd frames.
+    let mut decoded = ZstdDecoder::new(reader);
+    decoded.multiple_members(true);
+    let source: Pin<Box<dyn AsyncRead + Send>> = match to {
+        NarCompression::Xz => Box::pin(XzEncoder::with_quality(
+            BufReader::with_capacity(NAR_TRANSCODE_CHUNK_BYTES, decoded),
+            Level::Precise(NAR_XZ_PRESET as i32),
+        )),
+        NarCompression::None | NarCompression::Zstd => Box::pin(decoded),
+    };
+
+    ReaderStream::with_capacity(source, NAR_TRANSCODE_CHUNK_BYTES)
+        .map(|chunk| chunk.map_err(|e| anyhow::Error::new(e).context("transcode NAR")))
+        .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+    use tokio::io::AsyncReadExt as _;
+
+    /// Deterministic pseudo-random bytes (xorshift), so zstd cannot shrink
+    /// them and the stored stream is as large as the NAR.
+    fn noise(len: usize, seed: u64) -> Vec<u8> {
+        let mut x = seed | 1;
+        (0..len)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 7;
+                x ^= x << 17;
+                x as u8
+            })
+            .collect()
+    }
+
+    /// `data` as a stored object: one zstd frame per 1 MiB (like a chunked
+    /// NAR's concatenated frames), streamed in 64 KiB pieces. `pulled`
+    /// counts the stored bytes the transcoder has read.
+    fn stored(data: &[u8], pulled: Arc<AtomicUsize>) -> ByteStream {
+        let compressed: Vec<u8> = data
+            .chunks(1024 * 1024)
+            .flat_map(|frame| zstd::stream::encode_all(frame, 1).unwrap())
+            .collect();
+        let pieces: Vec<Bytes> = compressed
+            .chunks(64 * 1024)
+            .map(Bytes::copy_from_slice)
+            .collect();
+        futures::stream::iter(pieces)
+            .map(move |piece| {
+                pulled.fetch_add(piece.len(), Ordering::SeqCst);
+                Ok(piece)
+            })
+            .boxed()
+    }
+
+    async fn collect(stream: ByteStream) -> Vec<u8> {
+        let chunks: Vec<Bytes> = stream.map(|c| c.unwrap()).collect().await;
+        assert!(chunks.iter().all(|c| c.len() <= NAR_TRANSCODE_CHUNK_BYTES));
+        chunks.concat()
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn transcodes_to_every_compression() {
+        let mut nar = noise(3 * 1024 * 1024, 7);
+        nar.extend(std::iter::repeat_n(0u8, 2 * 1024 * 1024));
+        let pulled = Arc::new(AtomicUsize::new(0));
+
+        let zstd = collect(transcode_stream(
+            stored(&nar, pulled.clone()),
+            NarCompression::Zstd,
+        ))
+        .await;
+        assert_eq!(zstd::stream::decode_all(&zstd[..]).unwrap(), nar);
+
+        let none = collect(transcode_stream(
+            stored(&nar, pulled.clone()),
+            NarCompression::None,
+        ))
+        .await;
+        assert_eq!(none, nar);
+
+        let xz = collect(transcode_stream(stored(&nar, pulled), NarCompression::Xz)).await;
+        let mut decoded = Vec::new();
+        async_compression::tokio::bufread::XzDecoder::new(&xz[..])
+            .read_to_end(&mut decoded)
+            .await
+            .unwrap();
+        assert_eq!(decoded, nar);
+    }
+
+    /// A client that stops reading stalls the transcoder: only the codecs'
+    /// own buffers are read ahead, not the rest of the NAR.
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn slow_client_bounds_read_ahead() {
+        const NAR_BYTES: usize = 64 * 1024 * 1024;
+        const READ_AHEAD_LIMIT: usize = 8 * 1024 * 1024;
+        let nar = noise(NAR_BYTES, 11);
+
+        for to in [NarCompression::None, NarCompression::Xz] {
+            let pulled = Arc::new(AtomicUsize::new(0));
+            let mut stream = transcode_stream(stored(&nar, pulled.clone()), to);
+            let first = stream.next().await.unwrap().unwrap();
+            assert!(!first.is_empty());
+
+            // Give the transcoder time to run ahead if it were unbounded.
+            tokio::task::spawn_blocking(|| std::thread::sleep(Duration::from_millis(300)))
+                .await
+                .unwrap();
+            let read = pulled.load(Ordering::SeqCst);
+            assert!(
+                read < READ_AHEAD_LIMIT,
+                "{to:?}: read {read} bytes ahead of a stalled client"
+            );
+        }
+    }
+
+    /// A NAR that decompresses far beyond its stored size still streams in
+    /// fixed chunks instead of being inflated in one piece.
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn highly_compressed_nar_streams_in_chunks() {
+        let nar = vec![0u8; 64 * 1024 * 1024];
+        let pulled = Arc::new(AtomicUsize::new(0));
+        let mut stream = transcode_stream(stored(&nar, pulled), NarCompression::None);
+
+        let mut total = 0;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.unwrap();
+            assert!(chunk.len() <= NAR_TRANSCODE_CHUNK_BYTES);
+            total += chunk.len();
+        }
+        assert_eq!(total, nar.len());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn corrupt_input_ends_in_an_error() {
+        let garbage = futures::stream::iter([Ok(Bytes::from_static(b"not zstd at all"))]).boxed();
+        let items: Vec<_> = transcode_stream(garbage, NarCompression::None)
+            .collect()
+            .await;
+        assert!(matches!(items.last(), Some(Err(_))));
+    }
+}
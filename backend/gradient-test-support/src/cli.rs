@@ -39,7 +39,11 @@ pub fn test_cli_with_crypt(crypt_secret_file: String) -> Cli {
             build_retry_backoff_secs: 30,
             build_default_timeout_secs: 3600,
             build_default_max_silent_secs: 1800,
+            flaky_max_reruns: 1,
+            flaky_log_patterns: Vec::new(),
+            flaky_flip_rate_percent: 20,
             scheduler_scoring_policy: "resource-aware".into(),
+            scheduler_scoring_policy_file: None,
         },
         storage: StorageArgs {
             base_path: tempfile::Builder::new()
@@ -78,5 +82,6 @@ pub fn test_cli_with_crypt(crypt_secret_file: String) -> Cli {
         github_app: GitHubAppArgs::default(),
         metrics: MetricsArgs::default(),
         network: NetworkArgs::default(),
+        autoscale: AutoscaleArgs::default(),
     }
 }
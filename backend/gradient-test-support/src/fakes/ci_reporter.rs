@@ -63,4 +63,31 @@ impl CiReporter for RecordingCiReporter {
         });
         Ok(())
     }
+
+    async fn upsert_pr_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        marker: &str,
+        body: &str,
+    ) -> Result<()> {
+        let comment = RecordedComment {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            pr_number,
+            body: body.to_string(),
+        };
+        let mut comments = self.comments.lock().unwrap();
+        match comments.iter_mut().find(|c| {
+            c.owner == owner
+                && c.repo == repo
+                && c.pr_number == pr_number
+                && c.body.contains(marker)
+        }) {
+            Some(existing) => *existing = comment,
+            None => comments.push(comment),
+        }
+        Ok(())
+    }
 }
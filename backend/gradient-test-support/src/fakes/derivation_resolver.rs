@@ -7,7 +7,7 @@
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use gradient_db::Derivation;
-use gradient_nix::{DerivationResolver, FlakeDiscovery, ResolvedDerivation};
+use gradient_nix::{DerivationResolver, FlakeDiscovery, MetaLimits, ResolvedDerivation};
 use std::collections::HashMap;
 use std::sync::Mutex;
 
@@ -21,6 +21,7 @@ pub struct FakeDerivationResolver {
     flake_attrs: Mutex<HashMap<String, Vec<String>>>,
     flake_errors: Mutex<HashMap<String, Vec<String>>>,
     drv_paths: Mutex<HashMap<(String, String), String>>,
+    meta_limits: Mutex<HashMap<(String, String), MetaLimits>>,
     derivations: Mutex<HashMap<String, Derivation>>,
     features: Mutex<HashMap<String, (String, Vec<String>)>>,
 }
@@ -56,6 +57,19 @@ impl FakeDerivationResolver {
         self
     }
 
+    pub fn with_meta_limits(
+        self,
+        flake: impl Into<String>,
+        attr: impl Into<String>,
+        limits: MetaLimits,
+    ) -> Self {
+        self.meta_limits
+            .lock()
+            .unwrap()
+            .insert((flake.into(), attr.into()), limits);
+        self
+    }
+
     pub fn with_derivation(self, drv_path: impl Into<String>, drv: Derivation) -> Self {
         self.derivations
             .lock()
@@ -112,14 +126,22 @@ impl DerivationResolver for FakeDerivationResolver {
         _overrides: &[(String, String)],
     ) -> Result<(Vec<ResolvedDerivation>, Vec<String>)> {
         let drv_paths = self.drv_paths.lock().unwrap();
+        let meta_limits = self.meta_limits.lock().unwrap();
         Ok((
             attrs
                 .into_iter()
                 .map(|attr| {
+                    let key = (repository.clone(), attr.clone());
                     let resolved = drv_paths
-                        .get(&(repository.clone(), attr.clone()))
+                        .get(&key)
                         .cloned()
-                        .map(|p| (p, vec![]))
+                        .map(|p| {
+                            (
+                                p,
+                                vec![],
+                                meta_limits.get(&key).copied().unwrap_or_default(),
+                            )
+                        })
                         .ok_or_else(|| anyhow!("no fake drv path for {}#{}", repository, attr));
                     (attr, resolved)
                 })
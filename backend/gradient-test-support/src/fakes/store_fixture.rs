@@ -154,6 +154,8 @@ pub fn load_store(dir: &Path) -> StoreFixture {
                     .unwrap_or(""),
             ),
             substituted: false,
+            constituents: None,
+            floating_outputs: None,
         });
     }
 
@@ -4,8 +4,11 @@
  * SPDX-License-Identifier: AGPL-3.0-only
  */
 
+use crate::body_template::{self, BodyTemplateError};
 use crate::ids::IntegrationId;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use thiserror::Error;
 
 pub use gradient_entity::project_action::ActionType;
 
@@ -36,6 +39,64 @@ pub enum VerifyGate {
     Build,
 }
 
+/// HTTP method a `SendWebRequest` action delivers with.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebRequestMethod {
+    #[default]
+    Post,
+    Put,
+    Patch,
+}
+
+impl WebRequestMethod {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WebRequestMethod::Post => "POST",
+            WebRequestMethod::Put => "PUT",
+            WebRequestMethod::Patch => "PATCH",
+        }
+    }
+}
+
+/// Headers Gradient sets itself on a `SendWebRequest` delivery; `headers`
+/// may not override them.
+pub const RESERVED_WEB_REQUEST_HEADERS: &[&str] = &[
+    "host",
+    "content-length",
+    "transfer-encoding",
+    "connection",
+    "x-gradient-event",
+    "x-gradient-delivery",
+    "x-gradient-timestamp",
+    "x-gradient-signature",
+];
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ActionConfigError {
+    #[error(transparent)]
+    BodyTemplate(#[from] BodyTemplateError),
+    #[error("invalid header name {0:?}")]
+    InvalidHeaderName(String),
+    #[error("header {0:?} has an invalid value")]
+    InvalidHeaderValue(String),
+    #[error("header {0:?} is set by Gradient and cannot be overridden")]
+    ReservedHeader(String),
+    #[error("header \"Authorization\" conflicts with token; set one or the other")]
+    AuthorizationWithToken,
+    #[error(
+        "digest_window_secs must be between {} and {}",
+        MIN_DIGEST_WINDOW_SECS,
+        MAX_DIGEST_WINDOW_SECS
+    )]
+    DigestWindow,
+}
+
+/// Bounds for `SendMail::digest_window_secs`: at least a minute so a digest
+/// is worth sending, at most a day so failures are not reported stale.
+pub const MIN_DIGEST_WINDOW_SECS: u32 = 60;
+pub const MAX_DIGEST_WINDOW_SECS: u32 = 86_400;
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ActionConfig {
@@ -43,15 +104,49 @@ pub enum ActionConfig {
         recipients: Vec<String>,
         #[serde(default, skip_serializing_if = "Option::is_none")]
         subject_template: Option<String>,
+        /// Digest mode: buffer matched events for this many seconds after the
+        /// first one and mail a single summary grouped by evaluation.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        digest_window_secs: Option<u32>,
     },
     SendWebRequest {
         url: String,
         #[serde(default, skip_serializing_if = "Option::is_none")]
         token: Option<String>,
+        /// HMAC-SHA256 key for `X-Gradient-Signature`; stored encrypted like
+        /// `token`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        signing_secret: Option<String>,
+        #[serde(default)]
+        method: WebRequestMethod,
+        /// Extra request headers, sent verbatim after Gradient's own.
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        headers: BTreeMap<String, String>,
+        /// JSON body rendered by [`crate::body_template`]; the raw event
+        /// payload is sent when unset.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        body_template: Option<String>,
     },
     ForgeStatusReport {
         integration_id: IntegrationId,
     },
+    /// Post a formatted message to a Matrix room through the client-server
+    /// API. `access_token` is stored encrypted like `SendWebRequest::token`.
+    SendMatrix {
+        homeserver: String,
+        room_id: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        access_token: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        message_template: Option<String>,
+    },
+    /// Post a formatted message to a Slack-compatible incoming webhook
+    /// (Slack, Mattermost, Rocket.Chat, ...).
+    SendSlack {
+        url: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        message_template: Option<String>,
+    },
     OpenPr {
         integration_id: IntegrationId,
         #[serde(default)]
@@ -85,9 +180,58 @@ impl ActionConfig {
             ActionConfig::SendMail { .. } => ActionType::SendMail,
             ActionConfig::SendWebRequest { .. } => ActionType::SendWebRequest,
             ActionConfig::ForgeStatusReport { .. } => ActionType::ForgeStatusReport,
+            ActionConfig::SendMatrix { .. } => ActionType::SendMatrix,
+            ActionConfig::SendSlack { .. } => ActionType::SendSlack,
             ActionConfig::OpenPr { .. } => ActionType::OpenPr,
         }
     }
+
+    /// Reject configs that would only fail at delivery time.
+    pub fn validate(&self) -> Result<(), ActionConfigError> {
+        let (token, headers, body_template) = match self {
+            ActionConfig::SendWebRequest {
+                token,
+                headers,
+                body_template,
+                ..
+            } => (token, headers, body_template),
+            ActionConfig::SendMail {
+                digest_window_secs: Some(window),
+                ..
+            } => {
+                return if (MIN_DIGEST_WINDOW_SECS..=MAX_DIGEST_WINDOW_SECS).contains(window) {
+                    Ok(())
+                } else {
+                    Err(ActionConfigError::DigestWindow)
+                };
+            }
+            _ => return Ok(()),
+        };
+        for (name, value) in headers {
+            if name.is_empty() || !name.bytes().all(is_header_token_byte) {
+                return Err(ActionConfigError::InvalidHeaderName(name.clone()));
+            }
+            if value.bytes().any(|b| b == b'\r' || b == b'\n' || b == 0) {
+                return Err(ActionConfigError::InvalidHeaderValue(name.clone()));
+            }
+            let lower = name.to_ascii_lowercase();
+            if RESERVED_WEB_REQUEST_HEADERS.contains(&lower.as_str()) {
+                return Err(ActionConfigError::ReservedHeader(name.clone()));
+            }
+            if lower == "authorization" && token.is_some() {
+                return Err(ActionConfigError::AuthorizationWithToken);
+            }
+        }
+        if let Some(template) = body_template {
+            body_template::validate(template)?;
+        }
+        Ok(())
+    }
+}
+
+/// RFC 9110 `tchar`.
+fn is_header_token_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
 }
 
 #[cfg(test)]
@@ -99,6 +243,7 @@ mod tests {
         let cfg = ActionConfig::SendMail {
             recipients: vec!["ops@example.com".into()],
             subject_template: Some("[Gradient] {event}".into()),
+            digest_window_secs: None,
         };
         let json = serde_json::to_string(&cfg).unwrap();
         assert!(json.contains("\"type\":\"send_mail\""));
@@ -112,9 +257,16 @@ mod tests {
         let cfg = ActionConfig::SendWebRequest {
             url: "https://example.com/hook".into(),
             token: None,
+            signing_secret: None,
+            method: WebRequestMethod::Post,
+            headers: BTreeMap::new(),
+            body_template: None,
         };
         let json = serde_json::to_string(&cfg).unwrap();
         assert!(!json.contains("token"));
+        assert!(!json.contains("signing_secret"));
+        assert!(!json.contains("headers"));
+        assert!(!json.contains("body_template"));
         let back: ActionConfig = serde_json::from_str(&json).unwrap();
         assert_eq!(cfg, back);
     }
@@ -135,12 +287,40 @@ mod tests {
             ActionType::SendWebRequest,
             ActionType::ForgeStatusReport,
             ActionType::OpenPr,
+            ActionType::SendMatrix,
+            ActionType::SendSlack,
         ] {
             assert_eq!(ActionType::try_from(i16::from(at)), Ok(at));
         }
         assert!(ActionType::try_from(99i16).is_err());
     }
 
+    #[test]
+    fn chat_configs_round_trip() {
+        let matrix = ActionConfig::SendMatrix {
+            homeserver: "https://matrix.example.org".into(),
+            room_id: "!ops:example.org".into(),
+            access_token: Some("encrypted".into()),
+            message_template: None,
+        };
+        let json = serde_json::to_string(&matrix).unwrap();
+        assert!(json.contains("\"type\":\"send_matrix\""));
+        assert!(!json.contains("message_template"));
+        let back: ActionConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(matrix, back);
+        assert_eq!(matrix.action_type(), ActionType::SendMatrix);
+
+        let slack = ActionConfig::SendSlack {
+            url: "https://hooks.slack.com/services/T/B/X".into(),
+            message_template: Some("{project}: {status}".into()),
+        };
+        let json = serde_json::to_string(&slack).unwrap();
+        assert!(json.contains("\"type\":\"send_slack\""));
+        let back: ActionConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(slack, back);
+        assert_eq!(slack.action_type(), ActionType::SendSlack);
+    }
+
     #[test]
     fn open_pr_defaults_apply() {
         let json = serde_json::json!({
@@ -185,4 +365,89 @@ mod tests {
         let back: ActionConfig = serde_json::from_str(&json).unwrap();
         assert_eq!(cfg, back);
     }
+
+    #[test]
+    fn send_web_request_defaults_to_post() {
+        let cfg: ActionConfig = serde_json::from_value(serde_json::json!({
+            "type": "send_web_request",
+            "url": "https://example.com/hook",
+        }))
+        .unwrap();
+        match cfg {
+            ActionConfig::SendWebRequest {
+                method, headers, ..
+            } => {
+                assert_eq!(method, WebRequestMethod::Post);
+                assert!(headers.is_empty());
+            }
+            _ => panic!("expected SendWebRequest"),
+        }
+    }
+
+    #[test]
+    fn send_web_request_validate() {
+        let with = |token: Option<&str>, headers: &[(&str, &str)], template: Option<&str>| {
+            ActionConfig::SendWebRequest {
+                url: "https://example.com/hook".into(),
+                token: token.map(Into::into),
+                signing_secret: None,
+                method: WebRequestMethod::Put,
+                headers: headers
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+                body_template: template.map(Into::into),
+            }
+            .validate()
+        };
+        assert_eq!(
+            with(
+                None,
+                &[("X-Routing", "ops")],
+                Some(r#"{"s": "{{status}}"}"#)
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            with(None, &[("Bad Header", "x")], None),
+            Err(ActionConfigError::InvalidHeaderName("Bad Header".into()))
+        );
+        assert_eq!(
+            with(None, &[("X-A", "a\r\nX-B: b")], None),
+            Err(ActionConfigError::InvalidHeaderValue("X-A".into()))
+        );
+        assert_eq!(
+            with(None, &[("x-gradient-signature", "forged")], None),
+            Err(ActionConfigError::ReservedHeader(
+                "x-gradient-signature".into()
+            ))
+        );
+        assert_eq!(
+            with(Some("enc"), &[("Authorization", "Basic x")], None),
+            Err(ActionConfigError::AuthorizationWithToken)
+        );
+        assert!(matches!(
+            with(None, &[], Some("{")),
+            Err(ActionConfigError::BodyTemplate(_))
+        ));
+    }
+
+    #[test]
+    fn send_mail_digest_window_validate() {
+        let with = |digest_window_secs| ActionConfig::SendMail {
+            recipients: vec!["ops@example.com".into()],
+            subject_template: None,
+            digest_window_secs,
+        };
+        assert_eq!(with(None).validate(), Ok(()));
+        assert_eq!(with(Some(900)).validate(), Ok(()));
+        assert_eq!(
+            with(Some(5)).validate(),
+            Err(ActionConfigError::DigestWindow)
+        );
+        assert_eq!(
+            with(Some(MAX_DIGEST_WINDOW_SECS + 1)).validate(),
+            Err(ActionConfigError::DigestWindow)
+        );
+    }
 }
@@ -0,0 +1,224 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! JSON body templates for `send_web_request` actions.
+//!
+//! A template is a JSON document whose string values may contain
+//! `{{path}}` placeholders. `path` is `event` (the event name) or a dotted
+//! path into the event payload (`status`, `evaluation_id`, `a.b.c`).
+//!
+//! - A string that is exactly one placeholder is replaced by the referenced
+//!   JSON value as-is (numbers stay numbers, objects stay objects), or
+//!   `null` when the path is missing.
+//! - Placeholders embedded in longer strings are substituted with the value's
+//!   text form (strings unquoted, anything else as compact JSON), or the
+//!   empty string when missing.
+//!
+//! Object keys are never rendered.
+
+use serde_json::Value as JsonValue;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum BodyTemplateError {
+    #[error("body_template is not valid JSON: {0}")]
+    InvalidJson(String),
+    #[error("body_template has an unterminated placeholder in {0:?}")]
+    Unterminated(String),
+    #[error("body_template placeholder {{{{{0}}}}} is not a dotted path")]
+    InvalidPath(String),
+}
+
+/// Check that `template` parses and every placeholder is well-formed.
+pub fn validate(template: &str) -> Result<(), BodyTemplateError> {
+    let doc = parse(template)?;
+    visit_strings(&doc, &mut |s| placeholders(s).map(|_| ()))
+}
+
+/// Render `template` against `payload`; `event` answers the `{{event}}`
+/// placeholder.
+pub fn render(
+    template: &str,
+    event: &str,
+    payload: &JsonValue,
+) -> Result<JsonValue, BodyTemplateError> {
+    let doc = parse(template)?;
+    render_value(doc, event, payload)
+}
+
+fn parse(template: &str) -> Result<JsonValue, BodyTemplateError> {
+    serde_json::from_str(template).map_err(|e| BodyTemplateError::InvalidJson(e.to_string()))
+}
+
+fn visit_strings(
+    value: &JsonValue,
+    f: &mut impl FnMut(&str) -> Result<(), BodyTemplateError>,
+) -> Result<(), BodyTemplateError> {
+    match value {
+        JsonValue::String(s) => f(s),
+        JsonValue::Array(items) => items.iter().try_for_each(|v| visit_strings(v, f)),
+        JsonValue::Object(map) => map.values().try_for_each(|v| visit_strings(v, f)),
+        _ => Ok(()),
+    }
+}
+
+fn render_value(
+    value: JsonValue,
+    event: &str,
+    payload: &JsonValue,
+) -> Result<JsonValue, BodyTemplateError> {
+    Ok(match value {
+        JsonValue::String(s) => render_string(&s, event, payload)?,
+        JsonValue::Array(items) => JsonValue::Array(
+            items
+                .into_iter()
+                .map(|v| render_value(v, event, payload))
+                .collect::<Result<_, _>>()?,
+        ),
+        JsonValue::Object(map) => JsonValue::Object(
+            map.into_iter()
+                .map(|(k, v)| Ok((k, render_value(v, event, payload)?)))
+                .collect::<Result<_, BodyTemplateError>>()?,
+        ),
+        other => other,
+    })
+}
+
+fn render_string(
+    s: &str,
+    event: &str,
+    payload: &JsonValue,
+) -> Result<JsonValue, BodyTemplateError> {
+    let found = placeholders(s)?;
+    if let [(0, end, path)] = found.as_slice()
+        && *end == s.len()
+    {
+        return Ok(lookup(path, event, payload).unwrap_or(JsonValue::Null));
+    }
+
+    let mut out = String::with_capacity(s.len());
+    let mut cursor = 0;
+    for (start, end, path) in found {
+        out.push_str(&s[cursor..start]);
+        match lookup(path, event, payload) {
+            Some(JsonValue::String(v)) => out.push_str(&v),
+            Some(JsonValue::Null) | None => {}
+            Some(other) => out.push_str(&other.to_string()),
+        }
+        cursor = end;
+    }
+    out.push_str(&s[cursor..]);
+    Ok(JsonValue::String(out))
+}
+
+/// `(start, end, path)` byte spans of every `{{path}}` in `s`.
+fn placeholders(s: &str) -> Result<Vec<(usize, usize, &str)>, BodyTemplateError> {
+    let mut out = Vec::new();
+    let mut rest = 0;
+    while let Some(open) = s[rest..].find("{{") {
+        let start = rest + open;
+        let Some(close) = s[start + 2..].find("}}") else {
+            return Err(BodyTemplateError::Unterminated(s.to_owned()));
+        };
+        let end = start + 2 + close + 2;
+        let path = s[start + 2..end - 2].trim();
+        if !is_dotted_path(path) {
+            return Err(BodyTemplateError::InvalidPath(path.to_owned()));
+        }
+        out.push((start, end, path));
+        rest = end;
+    }
+    Ok(out)
+}
+
+fn is_dotted_path(path: &str) -> bool {
+    !path.is_empty()
+        && path.split('.').all(|seg| {
+            !seg.is_empty()
+                && seg
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        })
+}
+
+fn lookup(path: &str, event: &str, payload: &JsonValue) -> Option<JsonValue> {
+    if path == "event" {
+        return Some(JsonValue::String(event.to_owned()));
+    }
+    path.split('.')
+        .try_fold(payload, |v, seg| match v {
+            JsonValue::Array(items) => items.get(seg.parse::<usize>().ok()?),
+            _ => v.get(seg),
+        })
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn payload() -> JsonValue {
+        json!({
+            "evaluation_id": "0190",
+            "status": "evaluation.failed",
+            "failed": 3,
+            "repo": { "owner": "acme", "name": "web" },
+        })
+    }
+
+    #[test]
+    fn whole_string_placeholder_keeps_json_type() {
+        let out = render(
+            r#"{"count": "{{failed}}", "repo": "{{repo}}", "gone": "{{nope}}"}"#,
+            "evaluation.failed",
+            &payload(),
+        )
+        .unwrap();
+        assert_eq!(out["count"], json!(3));
+        assert_eq!(out["repo"], json!({ "owner": "acme", "name": "web" }));
+        assert_eq!(out["gone"], JsonValue::Null);
+    }
+
+    #[test]
+    fn embedded_placeholders_are_substituted_as_text() {
+        let out = render(
+            r#"{"summary": "{{repo.owner}}/{{repo.name}}: {{event}} ({{failed}} failed){{nope}}"}"#,
+            "evaluation.failed",
+            &payload(),
+        )
+        .unwrap();
+        assert_eq!(out["summary"], "acme/web: evaluation.failed (3 failed)");
+    }
+
+    #[test]
+    fn nested_arrays_and_objects_are_rendered() {
+        let out = render(
+            r#"{"payload": {"details": ["{{evaluation_id}}", 1, true]}}"#,
+            "x",
+            &payload(),
+        )
+        .unwrap();
+        assert_eq!(out, json!({ "payload": { "details": ["0190", 1, true] } }));
+    }
+
+    #[test]
+    fn validate_rejects_bad_templates() {
+        assert!(matches!(
+            validate("{not json"),
+            Err(BodyTemplateError::InvalidJson(_))
+        ));
+        assert!(matches!(
+            validate(r#"{"a": "{{status"}"#),
+            Err(BodyTemplateError::Unterminated(_))
+        ));
+        assert_eq!(
+            validate(r#"{"a": "{{ status | upper }}"}"#),
+            Err(BodyTemplateError::InvalidPath("status | upper".into()))
+        );
+        assert_eq!(validate(r#"{"a": "{{ status }}", "b": [1]}"#), Ok(()));
+    }
+}
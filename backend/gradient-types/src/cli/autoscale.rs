@@ -0,0 +1,82 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+use crate::input::greater_than_zero;
+use crate::worker_labels::parse_label;
+use clap::Args;
+
+#[derive(Args, Debug, Clone)]
+pub struct AutoscaleArgs {
+    /// URL the autoscaler POSTs each scaling decision to as JSON. Enables the
+    /// autoscaler; mutually exclusive with `autoscale_command`.
+    #[arg(
+        long,
+        env = "GRADIENT_AUTOSCALE_WEBHOOK_URL",
+        conflicts_with = "autoscale_command"
+    )]
+    pub autoscale_webhook_url: Option<String>,
+
+    /// File holding a bearer token sent with every autoscale webhook request.
+    /// The file is read once at startup.
+    #[arg(long, env = "GRADIENT_AUTOSCALE_WEBHOOK_TOKEN_FILE")]
+    pub autoscale_webhook_token_file: Option<String>,
+
+    /// Shell command run with each scaling decision as JSON on stdin. Enables
+    /// the autoscaler; mutually exclusive with `autoscale_webhook_url`.
+    #[arg(long, env = "GRADIENT_AUTOSCALE_COMMAND")]
+    pub autoscale_command: Option<String>,
+
+    /// Interval in seconds between scaling decisions.
+    #[arg(long, env = "GRADIENT_AUTOSCALE_INTERVAL", value_parser = greater_than_zero::<u64>, default_value_t = 30)]
+    pub autoscale_interval_secs: u64,
+
+    /// Seconds a scalable worker must sit without jobs, and without pending
+    /// work it could take, before it is drained for scale-down.
+    #[arg(long, env = "GRADIENT_AUTOSCALE_IDLE_SECS", default_value_t = 600)]
+    pub autoscale_idle_secs: u64,
+
+    /// Build slots a newly started worker is assumed to offer when sizing a
+    /// scale-up.
+    #[arg(long, env = "GRADIENT_AUTOSCALE_NEW_WORKER_SLOTS", value_parser = greater_than_zero::<u32>, default_value_t = 4)]
+    pub autoscale_new_worker_slots: u32,
+
+    /// Upper bound on the desired worker count of any one
+    /// (architecture, feature set) pool.
+    #[arg(long, env = "GRADIENT_AUTOSCALE_MAX_WORKERS", value_parser = greater_than_zero::<u32>, default_value_t = 10)]
+    pub autoscale_max_workers: u32,
+
+    /// Advertised labels (`key=value`, comma-separated) marking the workers
+    /// the autoscaler may drain and release. Empty treats every worker as
+    /// scalable; other workers still count as capacity.
+    #[arg(
+        long,
+        env = "GRADIENT_AUTOSCALE_WORKER_SELECTOR",
+        value_delimiter = ',',
+        value_parser = parse_label
+    )]
+    pub autoscale_worker_selector: Vec<(String, String)>,
+}
+
+impl AutoscaleArgs {
+    pub fn enabled(&self) -> bool {
+        self.autoscale_webhook_url.is_some() || self.autoscale_command.is_some()
+    }
+}
+
+impl Default for AutoscaleArgs {
+    fn default() -> Self {
+        Self {
+            autoscale_webhook_url: None,
+            autoscale_webhook_token_file: None,
+            autoscale_command: None,
+            autoscale_interval_secs: 30,
+            autoscale_idle_secs: 600,
+            autoscale_new_worker_slots: 4,
+            autoscale_max_workers: 10,
+            autoscale_worker_selector: Vec::new(),
+        }
+    }
+}
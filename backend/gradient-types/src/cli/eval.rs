@@ -50,14 +50,32 @@ pub struct EvalArgs {
         default_value = "3600"
     )]
     pub build_default_max_silent_secs: u64,
-    /// Name of the scheduler scoring policy (`simple`, `resource-aware`).
-    /// Unknown names fall back to `resource-aware`.
+    /// Re-runs on another worker granted to a build whose failure looks flaky
+    /// (known-flaky derivation name or matching log pattern) before the
+    /// failure is final, per evaluation. Set to 0 to disable flaky re-runs.
+    #[arg(long, env = "GRADIENT_FLAKY_MAX_RERUNS", default_value = "1")]
+    pub flaky_max_reruns: u32,
+    /// Case-insensitive substrings (comma-separated) that mark a failure as
+    /// flaky when found in the tail of the build log.
+    #[arg(long, env = "GRADIENT_FLAKY_LOG_PATTERNS", value_delimiter = ',')]
+    pub flaky_log_patterns: Vec<String>,
+    /// Share (percent) of consecutive outcomes that must flip between pass and
+    /// fail before a derivation name is considered flaky.
+    #[arg(long, env = "GRADIENT_FLAKY_FLIP_RATE_PERCENT", value_parser = greater_than_zero::<u32>, default_value = "20")]
+    pub flaky_flip_rate_percent: u32,
+    /// Name of the scheduler scoring policy: a built-in (`simple`,
+    /// `resource-aware`) or one defined in the policy file. Unknown names fall
+    /// back to `resource-aware`.
     #[arg(
         long,
         env = "GRADIENT_SCHEDULER_SCORING_POLICY",
         default_value = "resource-aware"
     )]
     pub scheduler_scoring_policy: String,
+    /// JSON file with declarative scoring policies. Re-read when it changes,
+    /// so weights can be tuned without a restart.
+    #[arg(long, env = "GRADIENT_SCHEDULER_SCORING_POLICY_FILE")]
+    pub scheduler_scoring_policy_file: Option<String>,
 }
 
 impl Default for EvalArgs {
@@ -74,7 +92,11 @@ impl Default for EvalArgs {
             build_retry_backoff_secs: 30,
             build_default_timeout_secs: 14400,
             build_default_max_silent_secs: 3600,
+            flaky_max_reruns: 1,
+            flaky_log_patterns: Vec::new(),
+            flaky_flip_rate_percent: 20,
             scheduler_scoring_policy: "resource-aware".into(),
+            scheduler_scoring_policy_file: None,
         }
     }
 }
@@ -11,6 +11,7 @@
 //! names, env vars, defaults and doc comments are preserved verbatim - only
 //! the Rust access path changes (e.g. `cli.port` → `cli.server.port`).
 
+mod autoscale;
 mod database;
 mod email;
 mod eval;
@@ -28,6 +29,7 @@ mod secrets;
 mod server;
 mod storage;
 
+pub use autoscale::AutoscaleArgs;
 pub use database::DatabaseArgs;
 pub use email::EmailArgs;
 pub use eval::EvalArgs;
@@ -106,6 +106,21 @@ pub struct StorageArgs {
     /// content-verify since they already hold the bytes in memory.
     #[arg(long, env = "GRADIENT_NAR_VERIFY_DIGEST", default_value_t = false)]
     pub nar_verify_digest: bool,
+    /// Convert stored NARs to the deduplicated chunked layout: each
+    /// cache-maintenance pass splits flat NARs into content-defined chunks,
+    /// stores every distinct chunk once and serves the NAR reassembled.
+    /// Uploads still land flat and are converted afterwards. Off by default.
+    #[arg(long, env = "GRADIENT_NAR_CHUNKING", default_value_t = false)]
+    pub nar_chunking: bool,
+    /// Compressed size in bytes below which a NAR stays flat when
+    /// `--nar-chunking` is on; small NARs gain nothing from chunking.
+    /// Defaults to 1048576 (1 MiB).
+    #[arg(
+        long,
+        env = "GRADIENT_NAR_CHUNKING_THRESHOLD_BYTES",
+        default_value_t = 1024 * 1024
+    )]
+    pub nar_chunking_threshold_bytes: u64,
 }
 
 impl Default for StorageArgs {
@@ -129,6 +144,8 @@ impl Default for StorageArgs {
             cache_maintenance_interval_secs: 3600,
             sign_sweep_interval_secs: 60,
             nar_verify_digest: false,
+            nar_chunking: false,
+            nar_chunking_threshold_bytes: 1024 * 1024,
         }
     }
 }
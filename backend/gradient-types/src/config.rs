@@ -14,8 +14,8 @@
 
 use super::Cli;
 use super::cli::{
-    DatabaseArgs, EvalArgs, LimitsArgs, LoggingArgs, MetricsArgs, ProtoArgs, RegistrationArgs,
-    SecretsArgs, ServerArgs, StorageArgs,
+    AutoscaleArgs, DatabaseArgs, EvalArgs, LimitsArgs, LoggingArgs, MetricsArgs, ProtoArgs,
+    RegistrationArgs, SecretsArgs, ServerArgs, StorageArgs,
 };
 use ipnet::IpNet;
 
@@ -234,6 +234,7 @@ pub struct RuntimeConfig {
     /// OTLP, sampling). Distinct from `metrics`, which gates the scrape token.
     pub metrics_args: MetricsArgs,
     pub network: NetworkConfig,
+    pub autoscale: AutoscaleArgs,
 }
 
 impl RuntimeConfig {
@@ -258,6 +259,7 @@ impl RuntimeConfig {
             metrics: cli.metrics_config(),
             metrics_args: cli.metrics.clone(),
             network: cli.network_config()?,
+            autoscale: cli.autoscale.clone(),
         })
     }
 }
@@ -291,7 +293,11 @@ mod tests {
                 build_retry_backoff_secs: 30,
                 build_default_timeout_secs: 14400,
                 build_default_max_silent_secs: 3600,
+                flaky_max_reruns: 1,
+                flaky_log_patterns: Vec::new(),
+                flaky_flip_rate_percent: 20,
                 scheduler_scoring_policy: "resource-aware".into(),
+                scheduler_scoring_policy_file: None,
             },
             storage: StorageArgs {
                 base_path: "/tmp/gradient-test".into(),
@@ -324,6 +330,7 @@ mod tests {
             github_app: GitHubAppArgs::default(),
             metrics: MetricsArgs::default(),
             network: NetworkArgs::default(),
+            autoscale: AutoscaleArgs::default(),
         }
     }
 
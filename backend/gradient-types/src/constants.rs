@@ -16,6 +16,22 @@ pub const NAR_ZSTD_LEVEL: i32 = 6;
 pub const TAR_ZSTD_LEVEL: i32 = 1;
 /// zstd level for finalized build-log chunks (0 = zstd default).
 pub const LOG_CHUNK_ZSTD_LEVEL: i32 = 0;
+/// How much of a failed build's log the scheduler reads to classify the
+/// failure. Causes show up at the end of a log, and a full read of a
+/// multi-GiB log would stall the failure handler.
+pub const BUILD_LOG_TAIL_BYTES: usize = 256 * 1024;
+/// Content-defined NAR chunk bounds (uncompressed bytes) for the deduplicated
+/// NAR layout. Changing them re-chunks nothing already stored, but new NARs
+/// stop sharing chunks with old ones.
+pub const NAR_CHUNK_MIN_BYTES: usize = 16 * 1024;
+pub const NAR_CHUNK_AVG_BYTES: usize = 64 * 1024;
+pub const NAR_CHUNK_MAX_BYTES: usize = 256 * 1024;
+/// xz preset for NARs transcoded on the fly. Low presets keep the encoder
+/// near 10 MiB per stream; preset 6 (what Nix writes) needs ~94 MiB.
+pub const NAR_XZ_PRESET: u32 = 1;
+/// Largest chunk a transcoded NAR streams in, and the buffer size of each
+/// transcoding stage.
+pub const NAR_TRANSCODE_CHUNK_BYTES: usize = 64 * 1024;
 /// Cap on per-file buffer preallocation during NAR extraction (16 MiB).
 pub const NAR_EXTRACT_MAX_PREALLOC: usize = 16 * 1024 * 1024;
 /// Lifetime of presigned GET/PUT URLs handed to workers and cache clients.
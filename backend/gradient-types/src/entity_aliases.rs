@@ -44,6 +44,7 @@ pub type EBuildRequestBlob = build_request_blob::Entity;
 pub type ECache = cache::Entity;
 pub type ECacheDerivation = cache_derivation::Entity;
 pub type ECacheMetric = cache_metric::Entity;
+pub type ECachePin = cache_pin::Entity;
 pub type ECacheRole = cache_role::Entity;
 pub type ECachedPath = cached_path::Entity;
 pub type ECacheUpstream = cache_upstream::Entity;
@@ -60,6 +61,7 @@ pub type EDerivationMetric = derivation_metric::Entity;
 pub type EDerivationOutput = derivation_output::Entity;
 pub type ECachedPathSignature = cached_path_signature::Entity;
 pub type EEntryPoint = entry_point::Entity;
+pub type EEntryPointConstituent = entry_point_constituent::Entity;
 pub type EEntryPointDepCount = entry_point_dep_count::Entity;
 pub type EEntryPointMessage = entry_point_message::Entity;
 pub type EEvalCacheStore = eval_cache_store::Entity;
@@ -78,12 +80,15 @@ pub type EOrganizationUser = organization_user::Entity;
 pub type EProject = project::Entity;
 pub type EProjectAction = project_action::Entity;
 pub type EProjectActionDelivery = project_action_delivery::Entity;
+pub type EProjectActionDigestEntry = project_action_digest_entry::Entity;
 pub type EProjectFlakeInputOverride = project_flake_input_override::Entity;
 pub type EProjectTrigger = project_trigger::Entity;
+pub type ERealisation = realisation::Entity;
 pub type ERole = role::Entity;
 pub type ESession = session::Entity;
 pub type EUploadSession = upload_session::Entity;
 pub type EUser = user::Entity;
+pub type EUserNotificationSubscription = user_notification_subscription::Entity;
 pub type EWorkerRegistration = worker_registration::Entity;
 
 pub type MAdminTask = admin_task::Model;
@@ -96,6 +101,7 @@ pub type MBuildRequestBlob = build_request_blob::Model;
 pub type MCache = cache::Model;
 pub type MCacheDerivation = cache_derivation::Model;
 pub type MCacheMetric = cache_metric::Model;
+pub type MCachePin = cache_pin::Model;
 pub type MCacheRole = cache_role::Model;
 pub type MCachedPath = cached_path::Model;
 pub type MCacheUpstream = cache_upstream::Model;
@@ -112,6 +118,7 @@ pub type MDerivationMetric = derivation_metric::Model;
 pub type MDerivationOutput = derivation_output::Model;
 pub type MCachedPathSignature = cached_path_signature::Model;
 pub type MEntryPoint = entry_point::Model;
+pub type MEntryPointConstituent = entry_point_constituent::Model;
 pub type MEntryPointDepCount = entry_point_dep_count::Model;
 pub type MEntryPointMessage = entry_point_message::Model;
 pub type MEvalCacheStore = eval_cache_store::Model;
@@ -131,12 +138,15 @@ pub type MOrganizationUser = organization_user::Model;
 pub type MProject = project::Model;
 pub type MProjectAction = project_action::Model;
 pub type MProjectActionDelivery = project_action_delivery::Model;
+pub type MProjectActionDigestEntry = project_action_digest_entry::Model;
 pub type MProjectFlakeInputOverride = project_flake_input_override::Model;
 pub type MProjectTrigger = project_trigger::Model;
+pub type MRealisation = realisation::Model;
 pub type MRole = role::Model;
 pub type MSession = session::Model;
 pub type MUploadSession = upload_session::Model;
 pub type MUser = user::Model;
+pub type MUserNotificationSubscription = user_notification_subscription::Model;
 pub type MWorkerRegistration = worker_registration::Model;
 
 pub type AAdminTask = admin_task::ActiveModel;
@@ -148,6 +158,7 @@ pub type ABuildRequestBlob = build_request_blob::ActiveModel;
 pub type ACache = cache::ActiveModel;
 pub type ACacheDerivation = cache_derivation::ActiveModel;
 pub type ACacheMetric = cache_metric::ActiveModel;
+pub type ACachePin = cache_pin::ActiveModel;
 pub type ACacheRole = cache_role::ActiveModel;
 pub type ACachedPath = cached_path::ActiveModel;
 pub type ACacheUpstream = cache_upstream::ActiveModel;
@@ -164,6 +175,7 @@ pub type ADerivationMetric = derivation_metric::ActiveModel;
 pub type ADerivationOutput = derivation_output::ActiveModel;
 pub type ACachedPathSignature = cached_path_signature::ActiveModel;
 pub type AEntryPoint = entry_point::ActiveModel;
+pub type AEntryPointConstituent = entry_point_constituent::ActiveModel;
 pub type AEntryPointDepCount = entry_point_dep_count::ActiveModel;
 pub type AEntryPointMessage = entry_point_message::ActiveModel;
 pub type AEvalCacheStore = eval_cache_store::ActiveModel;
@@ -183,12 +195,15 @@ pub type AOrganizationUser = organization_user::ActiveModel;
 pub type AProject = project::ActiveModel;
 pub type AProjectAction = project_action::ActiveModel;
 pub type AProjectActionDelivery = project_action_delivery::ActiveModel;
+pub type AProjectActionDigestEntry = project_action_digest_entry::ActiveModel;
 pub type AProjectFlakeInputOverride = project_flake_input_override::ActiveModel;
 pub type AProjectTrigger = project_trigger::ActiveModel;
+pub type ARealisation = realisation::ActiveModel;
 pub type ARole = role::ActiveModel;
 pub type ASession = session::ActiveModel;
 pub type AUploadSession = upload_session::ActiveModel;
 pub type AUser = user::ActiveModel;
+pub type AUserNotificationSubscription = user_notification_subscription::ActiveModel;
 pub type AWorkerRegistration = worker_registration::ActiveModel;
 
 pub type CAdminTask = admin_task::Column;
@@ -201,6 +216,7 @@ pub type CBuildRequestBlob = build_request_blob::Column;
 pub type CCache = cache::Column;
 pub type CCacheDerivation = cache_derivation::Column;
 pub type CCacheMetric = cache_metric::Column;
+pub type CCachePin = cache_pin::Column;
 pub type CCacheRole = cache_role::Column;
 pub type CCachedPath = cached_path::Column;
 pub type CCacheUpstream = cache_upstream::Column;
@@ -217,6 +233,7 @@ pub type CDerivationMetric = derivation_metric::Column;
 pub type CDerivationOutput = derivation_output::Column;
 pub type CCachedPathSignature = cached_path_signature::Column;
 pub type CEntryPoint = entry_point::Column;
+pub type CEntryPointConstituent = entry_point_constituent::Column;
 pub type CEntryPointDepCount = entry_point_dep_count::Column;
 pub type CEntryPointMessage = entry_point_message::Column;
 pub type CEvalCacheStore = eval_cache_store::Column;
@@ -233,12 +250,15 @@ pub type COrganizationUser = organization_user::Column;
 pub type CProject = project::Column;
 pub type CProjectAction = project_action::Column;
 pub type CProjectActionDelivery = project_action_delivery::Column;
+pub type CProjectActionDigestEntry = project_action_digest_entry::Column;
 pub type CProjectFlakeInputOverride = project_flake_input_override::Column;
 pub type CProjectTrigger = project_trigger::Column;
+pub type CRealisation = realisation::Column;
 pub type CRole = role::Column;
 pub type CSession = session::Column;
 pub type CUploadSession = upload_session::Column;
 pub type CUser = user::Column;
+pub type CUserNotificationSubscription = user_notification_subscription::Column;
 pub type CWorkerRegistration = worker_registration::Column;
 
 // `R*` (Relation) aliases removed - sea-orm relations are referenced via the
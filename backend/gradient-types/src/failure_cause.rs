@@ -0,0 +1,365 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Structured "why did this build fail?" payload.
+//!
+//! The scheduler runs [`analyze_build_log`] over a failed attempt's stored log
+//! and persists the result on `build_attempt.failure_cause` (JSON). It is
+//! returned by `GET /builds/{build}`, added to `build.failed` action payloads
+//! and printed by `gradient builds show`.
+//!
+//! Causes are checked in a fixed order, most specific first, so a log that
+//! shows several symptoms reports the root one:
+//! - `HashMismatch` - a fixed-output derivation produced a different hash than
+//!   declared; both hashes are extracted when nix prints them.
+//! - `MissingSystemFeature` - no builder offers a required system feature.
+//! - `DiskFull` - the build ran out of disk space.
+//! - `Oom` - the builder was killed by the OOM killer or failed to allocate.
+//! - `Timeout` - the wall-clock or silence timeout fired.
+//! - `TestFailure` - a test suite reported a failing test; the first failing
+//!   test's name is extracted for Rust, Go, pytest and CTest output.
+
+use serde::{Deserialize, Serialize};
+
+/// Lines after a `hash mismatch` header searched for the `specified:`/`got:`
+/// pair nix prints below it.
+const HASH_MISMATCH_WINDOW: usize = 4;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FailureCause {
+    /// Out of memory: the kernel OOM killer or a failed allocation.
+    Oom,
+    /// A fixed-output derivation's output hash differs from the declared one.
+    HashMismatch {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        expected: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        got: Option<String>,
+    },
+    DiskFull,
+    /// A test failed; `test` is the first failing test named in the log.
+    TestFailure {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        test: Option<String>,
+    },
+    /// The derivation needs a system feature (`kvm`, `big-parallel`, ...) that
+    /// the builder does not offer.
+    MissingSystemFeature {
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        features: Vec<String>,
+    },
+    Timeout,
+}
+
+impl FailureCause {
+    /// One-line human summary, as shown by the CLI and the build view.
+    pub fn summary(&self) -> String {
+        match self {
+            FailureCause::Oom => "out of memory".to_string(),
+            FailureCause::HashMismatch { expected, got } => match (expected, got) {
+                (Some(expected), Some(got)) => {
+                    format!("hash mismatch: expected {expected}, got {got}")
+                }
+                _ => "hash mismatch in fixed-output derivation".to_string(),
+            },
+            FailureCause::DiskFull => "disk full".to_string(),
+            FailureCause::TestFailure { test: Some(test) } => format!("test failed: {test}"),
+            FailureCause::TestFailure { test: None } => "test failure".to_string(),
+            FailureCause::MissingSystemFeature { features } if features.is_empty() => {
+                "missing system feature".to_string()
+            }
+            FailureCause::MissingSystemFeature { features } => {
+                format!("missing system feature: {}", features.join(", "))
+            }
+            FailureCause::Timeout => "timed out".to_string(),
+        }
+    }
+}
+
+/// Recognise the cause of a failed build from its log. `timed_out` is the
+/// worker's own timeout classification; it is reported as `Timeout` unless
+/// the log shows a hash mismatch or missing feature. `None` when no matcher
+/// fires.
+pub fn analyze_build_log(log: &str, timed_out: bool) -> Option<FailureCause> {
+    let lower = log.to_lowercase();
+
+    if let Some(cause) = hash_mismatch(log) {
+        return Some(cause);
+    }
+    if let Some(cause) = missing_system_feature(log) {
+        return Some(cause);
+    }
+    if timed_out {
+        return Some(FailureCause::Timeout);
+    }
+    if lower.contains("no space left on device") || lower.contains("disk quota exceeded") {
+        return Some(FailureCause::DiskFull);
+    }
+    if lower.lines().any(reports_out_of_memory)
+        || [
+            "oom-kill",
+            "oom_kill",
+            "cannot allocate memory",
+            "std::bad_alloc",
+            "signal 9 (killed)",
+        ]
+        .iter()
+        .any(|p| lower.contains(p))
+    {
+        return Some(FailureCause::Oom);
+    }
+    if lower.contains("timed out after") {
+        return Some(FailureCause::Timeout);
+    }
+
+    test_failure(log)
+}
+
+/// A line whose message is "out of memory" (`error: out of memory`, the
+/// kernel's `Out of memory: Killed process ...`), not one that merely
+/// mentions it, like a configure check or a test name. `line` is lowercase.
+fn reports_out_of_memory(line: &str) -> bool {
+    const OOM: &str = "out of memory";
+    let line = line.trim().trim_end_matches(['.', '!']);
+    let message_ends = |rest: &str| rest.is_empty() || rest.starts_with(':');
+    if let Some(rest) = line.strip_prefix(OOM) {
+        return message_ends(rest);
+    }
+    line.match_indices(": out of memory").any(|(i, m)| {
+        let rest = &line[i + m.len()..];
+        message_ends(rest) || rest.starts_with(" allocating")
+    })
+}
+
+fn hash_mismatch(log: &str) -> Option<FailureCause> {
+    let lines: Vec<&str> = log.lines().collect();
+    let header = lines
+        .iter()
+        .position(|l| l.contains("hash mismatch in fixed-output derivation"))?;
+
+    let mut expected = None;
+    let mut got = None;
+    for line in lines.iter().skip(header + 1).take(HASH_MISMATCH_WINDOW) {
+        let line = line.trim();
+        if let Some(v) = line
+            .strip_prefix("specified:")
+            .or_else(|| line.strip_prefix("wanted:"))
+        {
+            expected = Some(v.trim().to_string());
+        } else if let Some(v) = line.strip_prefix("got:") {
+            got = Some(v.trim().to_string());
+        }
+    }
+
+    Some(FailureCause::HashMismatch { expected, got })
+}
+
+/// nix: `a 'x86_64-linux' with features {kvm, nixos-test} is required to
+/// build '...', but I am a 'x86_64-linux' with features {...}`.
+fn missing_system_feature(log: &str) -> Option<FailureCause> {
+    let line = log
+        .lines()
+        .find(|l| l.contains("with features {") && l.contains("is required to build"))?;
+    let (_, rest) = line.split_once("with features {")?;
+    let (list, _) = rest.split_once('}')?;
+    let features = list
+        .split(',')
+        .map(str::trim)
+        .filter(|f| !f.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    Some(FailureCause::MissingSystemFeature { features })
+}
+
+fn test_failure(log: &str) -> Option<FailureCause> {
+    let mut generic = false;
+    for line in log.lines() {
+        let line = line.trim();
+        // Rust: `test foo::bar ... FAILED`
+        if let Some(rest) = line.strip_prefix("test ")
+            && let Some(name) = rest.strip_suffix(" ... FAILED")
+        {
+            return Some(named_test(name));
+        }
+        // Go: `--- FAIL: TestFoo (0.00s)`
+        if let Some(rest) = line.strip_prefix("--- FAIL: ") {
+            return Some(named_test(rest.split_whitespace().next().unwrap_or("")));
+        }
+        // pytest: `FAILED tests/test_x.py::test_y - AssertionError`
+        if let Some(rest) = line.strip_prefix("FAILED ")
+            && rest.contains("::")
+        {
+            return Some(named_test(rest.split(" - ").next().unwrap_or("")));
+        }
+        // CTest: `12 - name (Failed)`
+        if let Some(rest) = line.strip_suffix(" (Failed)")
+            && let Some((index, name)) = rest.split_once(" - ")
+            && !index.trim().is_empty()
+            && index.trim().chars().all(|c| c.is_ascii_digit())
+        {
+            return Some(named_test(name));
+        }
+        let lower = line.to_lowercase();
+        if lower.contains("test suite failed")
+            || lower.contains("tests failed")
+            || lower.contains("test failed")
+        {
+            generic = true;
+        }
+    }
+
+    generic.then_some(FailureCause::TestFailure { test: None })
+}
+
+fn named_test(name: &str) -> FailureCause {
+    let name = name.trim();
+    FailureCause::TestFailure {
+        test: (!name.is_empty()).then(|| name.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_mismatch_extracts_both_hashes() {
+        let log = "\
+error: hash mismatch in fixed-output derivation '/nix/store/abc-src.drv':
+         specified: sha256-AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=
+            got:    sha256-BBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBB=
+";
+        let cause = analyze_build_log(log, false).unwrap();
+        assert_eq!(
+            cause,
+            FailureCause::HashMismatch {
+                expected: Some("sha256-AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=".into()),
+                got: Some("sha256-BBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBB=".into()),
+            }
+        );
+        assert!(
+            cause
+                .summary()
+                .starts_with("hash mismatch: expected sha256-AAA")
+        );
+    }
+
+    #[test]
+    fn missing_system_feature_lists_required_features() {
+        let log = "error: a 'x86_64-linux' with features {kvm, nixos-test} is required to \
+                   build '/nix/store/abc-vm-test.drv', but I am a 'x86_64-linux' with features {}";
+        assert_eq!(
+            analyze_build_log(log, false),
+            Some(FailureCause::MissingSystemFeature {
+                features: vec!["kvm".into(), "nixos-test".into()],
+            })
+        );
+    }
+
+    #[test]
+    fn resource_exhaustion_is_recognised() {
+        assert_eq!(
+            analyze_build_log("cp: write error: No space left on device", false),
+            Some(FailureCause::DiskFull)
+        );
+        assert_eq!(
+            analyze_build_log(
+                "builder for '/nix/store/x.drv' failed due to signal 9 (Killed)",
+                false
+            ),
+            Some(FailureCause::Oom)
+        );
+    }
+
+    #[test]
+    fn out_of_memory_needs_to_be_the_message() {
+        for log in [
+            "error: out of memory",
+            "Out of memory: Killed process 4242 (cc1plus)",
+            "fatal error: out of memory allocating 65536 bytes",
+            "ld: out of memory.",
+        ] {
+            assert_eq!(
+                analyze_build_log(log, false),
+                Some(FailureCause::Oom),
+                "{log}"
+            );
+        }
+        for log in [
+            "checking whether malloc handles out of memory... yes",
+            "test alloc::tests::reports_out_of_memory ... ok",
+            "configure: out of memory handling enabled",
+        ] {
+            assert_eq!(analyze_build_log(log, false), None, "{log}");
+        }
+    }
+
+    #[test]
+    fn timeout_from_worker_or_log() {
+        assert_eq!(
+            analyze_build_log("test foo ... FAILED", true),
+            Some(FailureCause::Timeout)
+        );
+        assert_eq!(
+            analyze_build_log(
+                "error: building of '/nix/store/x.drv' timed out after 3600 seconds",
+                false
+            ),
+            Some(FailureCause::Timeout)
+        );
+    }
+
+    #[test]
+    fn first_failing_test_is_named() {
+        let rust = "test a::ok ... ok\ntest a::broken ... FAILED\ntest a::also ... FAILED";
+        assert_eq!(
+            analyze_build_log(rust, false).unwrap().summary(),
+            "test failed: a::broken"
+        );
+        let go = "=== RUN   TestParse\n--- FAIL: TestParse (0.01s)";
+        assert_eq!(analyze_build_log(go, false), Some(named_test("TestParse")));
+        let pytest = "FAILED tests/test_io.py::test_read - AssertionError: boom";
+        assert_eq!(
+            analyze_build_log(pytest, false),
+            Some(named_test("tests/test_io.py::test_read"))
+        );
+        let ctest = "The following tests FAILED:\n\t 12 - json_roundtrip (Failed)";
+        assert_eq!(
+            analyze_build_log(ctest, false),
+            Some(named_test("json_roundtrip"))
+        );
+        assert_eq!(
+            analyze_build_log("Test suite failed, see above", false),
+            Some(FailureCause::TestFailure { test: None })
+        );
+    }
+
+    #[test]
+    fn unrecognised_log_has_no_cause() {
+        assert_eq!(
+            analyze_build_log("error: builder failed with exit code 2", false),
+            None
+        );
+    }
+
+    #[test]
+    fn serializes_kind_tagged() {
+        let json = serde_json::to_value(FailureCause::TestFailure {
+            test: Some("a::b".into()),
+        })
+        .unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "kind": "test_failure", "test": "a::b" })
+        );
+        assert_eq!(
+            serde_json::to_value(FailureCause::Oom).unwrap(),
+            serde_json::json!({ "kind": "oom" })
+        );
+    }
+}
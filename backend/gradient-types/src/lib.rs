@@ -6,12 +6,14 @@
 
 pub mod actions;
 pub mod board_events;
+pub mod body_template;
 pub mod build_output_metadata;
 pub mod cached_path_info;
 pub mod cli;
 pub mod config;
 pub mod constants;
 pub mod consts;
+pub mod failure_cause;
 pub mod forge;
 pub mod ids;
 pub mod input;
@@ -21,25 +23,32 @@ pub mod secret;
 pub mod triggers;
 pub mod waiting_reason;
 pub mod wildcard;
+pub mod worker_labels;
+pub mod worker_maintenance;
 
 mod entity_aliases;
 mod io;
 mod nix_cache;
 
-pub use self::actions::{ActionConfig, ActionType, PatchGeneratorKind, PrGranularity, VerifyGate};
+pub use self::actions::{
+    ActionConfig, ActionConfigError, ActionType, PatchGeneratorKind, PrGranularity, VerifyGate,
+    WebRequestMethod,
+};
 pub use self::board_events::BoardEvent;
 pub use self::build_output_metadata::BuildOutputMetadata;
 pub use self::cached_path_info::CachedPathInfo;
 pub use self::cli::{
-    CidrParseError, CreatePermission, DatabaseArgs, EmailArgs, EvalArgs, GitHubAppArgs, LimitsArgs,
-    LoggingArgs, MetricsArgs, NetworkArgs, OidcArgs, ProtoArgs, RegistrationArgs, S3Args, ScimArgs,
-    SecretsArgs, ServerArgs, StorageArgs, in_any, parse_cidr_list,
+    AutoscaleArgs, CidrParseError, CreatePermission, DatabaseArgs, EmailArgs, EvalArgs,
+    GitHubAppArgs, LimitsArgs, LoggingArgs, MetricsArgs, NetworkArgs, OidcArgs, ProtoArgs,
+    RegistrationArgs, S3Args, ScimArgs, SecretsArgs, ServerArgs, StorageArgs, in_any,
+    parse_cidr_list,
 };
 pub use self::config::{
     ConfigError, EmailConfig, GitHubAppConfig, MetricsConfig, NetworkConfig, OidcConfig,
     RuntimeConfig, S3Config, ScimConfig,
 };
 pub use self::consts::*;
+pub use self::failure_cause::{FailureCause, analyze_build_log};
 pub use self::entity_aliases::*;
 pub use self::forge::ForgeType;
 pub use self::ids::*;
@@ -49,8 +58,10 @@ pub use self::log_api::{LogChunkIndex, LogChunkMeta, LogSearchDone, LogSearchHit
 pub use self::nix_cache::*;
 pub use self::secret::{SecretBytes, SecretString};
 pub use self::triggers::{ConcurrencyPolicy, TriggerConfig, TriggerConfigError, TriggerType};
-pub use self::waiting_reason::{EvalCapability, UnmetRequirement, WaitingReason};
+pub use self::waiting_reason::{EvalCapability, QuotaKind, UnmetRequirement, WaitingReason};
 pub use self::wildcard::*;
+pub use self::worker_labels::{Labels, WorkerLabels};
+pub use self::worker_maintenance::MaintenanceWindow;
 
 use chrono::NaiveDateTime;
 use clap::Parser;
@@ -99,6 +110,8 @@ pub struct Cli {
     pub metrics: MetricsArgs,
     #[command(flatten)]
     pub network: NetworkArgs,
+    #[command(flatten)]
+    pub autoscale: AutoscaleArgs,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
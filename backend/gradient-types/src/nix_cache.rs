@@ -9,6 +9,8 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+pub use gradient_entity::cache::NarCompression;
+
 #[derive(Debug, Clone, Serialize)]
 pub struct NixCacheInfo {
     #[serde(rename = "WantMassQuery")]
@@ -36,10 +38,12 @@ pub struct NixPathInfo {
     pub url: String,
     #[serde(rename = "Compression")]
     pub compression: String,
-    #[serde(rename = "FileHash")]
-    pub file_hash: String,
-    #[serde(rename = "FileSize")]
-    pub file_size: u32,
+    /// Hash and size of the file behind `URL`. Unknown, and left out, for a
+    /// NAR transcoded while it streams.
+    #[serde(rename = "FileHash", skip_serializing_if = "Option::is_none")]
+    pub file_hash: Option<String>,
+    #[serde(rename = "FileSize", skip_serializing_if = "Option::is_none")]
+    pub file_size: Option<u64>,
     #[serde(rename = "NarHash")]
     pub nar_hash: String,
     #[serde(rename = "NarSize")]
@@ -62,17 +66,45 @@ fn store_path_name(path: &str) -> &str {
 }
 
 impl NixPathInfo {
+    /// Advertise a narinfo built for the stored zstd NAR in `compression`.
+    /// The URL keeps the stored file's hash, so the NAR endpoint resolves it
+    /// as usual and picks the transcoding by extension. An uncompressed file
+    /// is the NAR itself; an xz file is only known once it has streamed, so
+    /// its `FileHash`/`FileSize` are left out.
+    pub fn with_compression(mut self, compression: NarCompression) -> Self {
+        if let Some(stem) = self.url.strip_suffix(NarCompression::Zstd.extension()) {
+            self.url = format!("{stem}{}", compression.extension());
+        }
+        self.compression = compression.as_str().to_string();
+        match compression {
+            NarCompression::Zstd => {}
+            NarCompression::Xz => {
+                self.file_hash = None;
+                self.file_size = None;
+            }
+            NarCompression::None => {
+                self.file_hash = Some(self.nar_hash.clone());
+                self.file_size = Some(self.nar_size);
+            }
+        }
+        self
+    }
+
     pub fn to_nix_string(&self) -> String {
         let mut out = format!(
-            "StorePath: {}\nURL: {}\nCompression: {}\nFileHash: {}\nFileSize: {}\nNarHash: {}\nNarSize: {}\n",
-            self.store_path,
-            self.url,
-            self.compression,
-            self.file_hash,
-            self.file_size,
-            self.nar_hash,
-            self.nar_size,
+            "StorePath: {}\nURL: {}\nCompression: {}\n",
+            self.store_path, self.url, self.compression,
         );
+        if let Some(file_hash) = &self.file_hash {
+            out.push_str(&format!("FileHash: {}\n", file_hash));
+        }
+        if let Some(file_size) = self.file_size {
+            out.push_str(&format!("FileSize: {}\n", file_size));
+        }
+        out.push_str(&format!(
+            "NarHash: {}\nNarSize: {}\n",
+            self.nar_hash, self.nar_size
+        ));
         if !self.references.is_empty() {
             let refs = self
                 .references
@@ -153,14 +185,17 @@ pub fn parse_narinfo_body(body: &str) -> Result<NixPathInfo, NarInfoParseError>
     let store_path = get("StorePath")?.to_string();
     let url = get("URL")?.to_string();
     let compression = get("Compression")?.to_string();
-    let file_hash = get("FileHash")?.to_string();
-    let file_size_raw = get("FileSize")?;
-    let file_size: u32 = file_size_raw
-        .parse()
-        .map_err(|_| NarInfoParseError::InvalidValue {
-            field: "FileSize",
-            value: file_size_raw.to_string(),
-        })?;
+    let file_hash = kv.get("FileHash").map(|s| s.to_string());
+    let file_size = kv
+        .get("FileSize")
+        .map(|raw| {
+            raw.parse::<u64>()
+                .map_err(|_| NarInfoParseError::InvalidValue {
+                    field: "FileSize",
+                    value: raw.to_string(),
+                })
+        })
+        .transpose()?;
     let nar_hash = get("NarHash")?.to_string();
     let nar_size_raw = get("NarSize")?;
     let nar_size: u64 = nar_size_raw
@@ -228,8 +263,8 @@ mod tests {
             store_path: "/nix/store/abc-hello".into(),
             url: "nar/aa/bbcc.nar.zst".into(),
             compression: "zstd".into(),
-            file_hash: "sha256:fhash".into(),
-            file_size: 1234,
+            file_hash: Some("sha256:fhash".into()),
+            file_size: Some(1234),
             nar_hash: "sha256:nhash".into(),
             nar_size: 5678,
             references: vec!["/nix/store/x-a".into(), "/nix/store/y-b".into()],
@@ -316,6 +351,43 @@ mod tests {
         assert!(sig_idx < ca_idx, "CA must appear after Sig");
     }
 
+    #[test]
+    fn nix_path_info_omits_unknown_file_fields() {
+        let mut pi = path_info();
+        pi.compression = "xz".into();
+        pi.file_hash = None;
+        pi.file_size = None;
+        let s = pi.to_nix_string();
+        assert!(!s.contains("FileHash:"), "{s}");
+        assert!(!s.contains("FileSize:"), "{s}");
+        assert!(s.contains("Compression: xz\nNarHash: sha256:nhash\n"));
+
+        let parsed = parse_narinfo_body(&s).expect("FileHash/FileSize are optional");
+        assert_eq!(parsed.file_hash, None);
+        assert_eq!(parsed.file_size, None);
+    }
+
+    #[test]
+    fn with_compression_rewrites_file_fields() {
+        let mut pi = path_info();
+        pi.url = "nar/fhash.nar.zst".into();
+        assert_eq!(
+            pi.clone().with_compression(NarCompression::Zstd).url,
+            pi.url
+        );
+
+        let none = pi.clone().with_compression(NarCompression::None);
+        assert_eq!(none.url, "nar/fhash.nar");
+        assert_eq!(none.compression, "none");
+        assert_eq!(none.file_hash.as_deref(), Some("sha256:nhash"));
+        assert_eq!(none.file_size, Some(5678));
+
+        let xz = pi.with_compression(NarCompression::Xz);
+        assert_eq!(xz.url, "nar/fhash.nar.xz");
+        assert_eq!(xz.compression, "xz");
+        assert_eq!((xz.file_hash, xz.file_size), (None, None));
+    }
+
     #[test]
     fn build_output_path_deserializes() {
         let json = r#"{"id":"out","outPath":"/nix/store/abc-hello","signatures":["k:sig"]}"#;
@@ -331,8 +403,8 @@ mod tests {
             store_path: "/nix/store/abc-foo".into(),
             url: "nar/xyz.nar.zst".into(),
             compression: "zstd".into(),
-            file_hash: "sha256:aaaa".into(),
-            file_size: 1234,
+            file_hash: Some("sha256:aaaa".into()),
+            file_size: Some(1234),
             nar_hash: "sha256:bbbb".into(),
             nar_size: 5678,
             references: vec!["dep1-foo".into(), "dep2-bar".into()],
@@ -160,6 +160,10 @@ pub struct BuildTask {
     pub timeout_secs: Option<u64>,
     /// Silent (no-output) limit in seconds; `None` = no limit.
     pub max_silent_secs: Option<u64>,
+    /// Largest NAR size in bytes any single output may have (the project's
+    /// `max_output_size`); `None` = no limit. An output over it fails the
+    /// build before anything is uploaded.
+    pub max_output_size: Option<u64>,
 }
 
 /// Severity of a worker-reported evaluation message. Mirrors
@@ -411,6 +415,25 @@ pub struct DiscoveredDerivation {
     pub allow_substitutes: bool,
     pub pname: Option<String>,
     pub substituted: bool,
+    /// `Some` for a Hydra-style aggregate entry point (`_hydraAggregate`):
+    /// the `.drv` paths of its `constituents`. A constituent the worker could
+    /// not resolve is carried by its raw name and counts as failed. `None`
+    /// for every other derivation.
+    pub constituents: Option<Vec<String>>,
+    /// `Some` when the derivation has floating content-addressed outputs,
+    /// which are left out of `outputs` since their paths are only known once
+    /// built.
+    pub floating_outputs: Option<FloatingOutputs>,
+}
+
+/// Floating content-addressed outputs of a discovered derivation, found
+/// through the cache's realisations instead of by store path.
+#[derive(Archive, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[rkyv(derive(Debug, PartialEq))]
+pub struct FloatingOutputs {
+    /// Realisation key: the derivation's hash modulo, `sha256:<base16>`.
+    pub drv_hash: String,
+    pub outputs: Vec<String>,
 }
 
 #[derive(Archive, Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -52,6 +52,14 @@ pub enum TriggerConfig {
     Time {
         cron: String,
     },
+    /// Evaluates the temporary merge commit a forge merge queue tests (GitHub
+    /// `merge_group`, GitLab merge trains). `branches` filters on the branch
+    /// the queue merges into.
+    ReporterMergeQueue {
+        integration_id: IntegrationId,
+        #[serde(default)]
+        branches: Vec<String>,
+    },
 }
 
 fn default_pr_actions() -> Vec<String> {
@@ -79,6 +87,7 @@ impl TriggerConfig {
             Self::ReporterPush { .. } => TriggerType::ReporterPush,
             Self::ReporterPullRequest { .. } => TriggerType::ReporterPullRequest,
             Self::Time { .. } => TriggerType::Time,
+            Self::ReporterMergeQueue { .. } => TriggerType::ReporterMergeQueue,
         }
     }
 
@@ -212,6 +221,7 @@ mod tests {
             (TriggerType::ReporterPush, 1),
             (TriggerType::ReporterPullRequest, 2),
             (TriggerType::Time, 3),
+            (TriggerType::ReporterMergeQueue, 4),
         ] {
             assert_eq!(i16::from(t), n);
             assert_eq!(TriggerType::try_from(n), Ok(t));
@@ -257,4 +267,18 @@ mod tests {
         let parsed = TriggerConfig::parse_row(TriggerType::ReporterPush, &db).unwrap();
         assert_eq!(parsed, cfg);
     }
+
+    #[test]
+    fn reporter_merge_queue_branches_default_empty() {
+        let db = serde_json::json!({ "integration_id": IntegrationId::nil() });
+        let parsed = TriggerConfig::parse_row(TriggerType::ReporterMergeQueue, &db).unwrap();
+        assert_eq!(
+            parsed,
+            TriggerConfig::ReporterMergeQueue {
+                integration_id: IntegrationId::nil(),
+                branches: vec![],
+            }
+        );
+        assert_eq!(parsed.trigger_type(), TriggerType::ReporterMergeQueue);
+    }
 }
@@ -13,6 +13,8 @@
 //! Reasons:
 //! - `Workers` - no connected worker can satisfy the pending builds' arch /
 //!   required-feature combo (build phase; persisted under JSON `kind=workers`).
+//!   When the project or trigger carries a worker selector, only matching
+//!   workers count and the selector is reported alongside.
 //! - `EvalWorkers` - the evaluation is still in a pre-build phase (`Fetching`
 //!   needs a fetch-capable worker; `Queued`/`EvaluatingFlake`/
 //!   `EvaluatingDerivation` need an eval-capable worker) and no connected
@@ -31,9 +33,15 @@
 //!   `closure_complete` gate with no in-flight build to fire a promotion. The
 //!   reconciler self-heals the gate and re-promotes; this reason surfaces the
 //!   stall while recovery is attempted.
+//! - `Quota` - the organisation is at one of its hard per-org limits (concurrent
+//!   builds or evaluations, or the rolling build-minutes budget). The scheduler
+//!   stops dispatching the org's jobs of that kind and recovers the evaluation
+//!   once the org is back under the limit.
 
 use serde::{Deserialize, Serialize};
 
+use crate::worker_labels::Labels;
+
 /// Pre-build capability a stalled evaluation is waiting for a worker to provide.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -44,6 +52,19 @@ pub enum EvalCapability {
     Eval,
 }
 
+/// Per-organisation limit a `Quota` park is waiting on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuotaKind {
+    /// `organization.max_concurrent_builds` builds are already in flight.
+    ConcurrentBuilds,
+    /// `organization.max_concurrent_evaluations` evaluations are already running.
+    ConcurrentEvaluations,
+    /// The org spent its `organization.build_minutes_budget` over the rolling
+    /// window; `used`/`limit` are in minutes.
+    BuildMinutes,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "kind", rename_all = "snake_case")]
 pub enum WaitingReason {
@@ -51,6 +72,14 @@ pub enum WaitingReason {
         unmet: Vec<UnmetRequirement>,
         connected_workers: u32,
         available_architectures: Vec<String>,
+        /// Label selector of the project (narrowed by the firing trigger) the
+        /// pending builds are held to. Empty when unrestricted.
+        #[serde(default, skip_serializing_if = "Labels::is_empty")]
+        worker_selector: Labels,
+        /// Connected workers whose labels match `worker_selector`;
+        /// `available_architectures` and `unmet` consider only these.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        matching_workers: Option<u32>,
     },
     /// Pre-build stall: no connected worker provides `capability`.
     /// `connected_workers` is the total connected pool size (may be > 0 when
@@ -83,6 +112,13 @@ pub enum WaitingReason {
     GraphStuck {
         pending_anchors: u32,
     },
+    /// The organisation hit a hard quota; dispatch of its jobs of this kind is
+    /// held until `used` drops below `limit`.
+    Quota {
+        quota: QuotaKind,
+        limit: u64,
+        used: u64,
+    },
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -122,9 +158,31 @@ impl WaitingReason {
             unmet,
             connected_workers,
             available_architectures,
+            worker_selector: Labels::new(),
+            matching_workers: None,
         }
     }
 
+    /// Attach the selector a `Workers` park was assessed under. The reason was
+    /// computed over the matching workers only, so its worker count moves to
+    /// `matching_workers` and `connected_workers` becomes the whole pool. A
+    /// no-op for other reasons or an empty selector.
+    pub fn with_worker_selector(mut self, selector: &Labels, connected: u32) -> Self {
+        if let Self::Workers {
+            connected_workers,
+            worker_selector,
+            matching_workers,
+            ..
+        } = &mut self
+            && !selector.is_empty()
+        {
+            *matching_workers = Some(*connected_workers);
+            *connected_workers = connected;
+            *worker_selector = selector.clone();
+        }
+        self
+    }
+
     pub fn approval(pr_number: u64, pr_author: impl Into<String>) -> Self {
         Self::Approval {
             pr_number,
@@ -142,6 +200,10 @@ impl WaitingReason {
     pub fn graph_stuck(pending_anchors: u32) -> Self {
         Self::GraphStuck { pending_anchors }
     }
+
+    pub fn quota(quota: QuotaKind, limit: u64, used: u64) -> Self {
+        Self::Quota { quota, limit, used }
+    }
 }
 
 #[cfg(test)]
@@ -164,6 +226,21 @@ mod tests {
         assert_eq!(WaitingReason::from_json(&v).unwrap(), r);
     }
 
+    #[test]
+    fn workers_round_trip_carries_selector() {
+        let selector = Labels::from([("pool".to_string(), "secure".to_string())]);
+        let r = WaitingReason::workers(vec![], 0, vec![]).with_worker_selector(&selector, 4);
+        let v = r.to_json();
+        assert_eq!(v["worker_selector"]["pool"], "secure");
+        assert_eq!(v["matching_workers"], 0);
+        assert_eq!(v["connected_workers"], 4);
+        assert_eq!(WaitingReason::from_json(&v).unwrap(), r);
+
+        let plain = WaitingReason::workers(vec![], 4, vec![]).to_json();
+        assert!(plain.get("worker_selector").is_none());
+        assert!(plain.get("matching_workers").is_none());
+    }
+
     #[test]
     fn approval_round_trip() {
         let r = WaitingReason::approval(42, "octocat");
@@ -190,6 +267,16 @@ mod tests {
         assert_eq!(WaitingReason::from_json(&v).unwrap(), r);
     }
 
+    #[test]
+    fn quota_round_trip() {
+        let r = WaitingReason::quota(QuotaKind::BuildMinutes, 600, 612);
+        let v = r.to_json();
+        assert_eq!(v["kind"], "quota");
+        assert_eq!(v["quota"], "build_minutes");
+        assert_eq!(v["limit"], 600);
+        assert_eq!(WaitingReason::from_json(&v).unwrap(), r);
+    }
+
     #[test]
     fn draining_round_trip() {
         let r = WaitingReason::Draining;
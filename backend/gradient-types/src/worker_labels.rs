@@ -0,0 +1,206 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Free-form `key=value` worker labels and the selectors matched against them.
+//!
+//! A worker advertises labels in `WorkerCapabilities`; an organization can add
+//! its own on the worker's registration. Only registration labels are matched:
+//! a worker can advertise anything, so an advertised label is informational
+//! until an organization admin sets it on the registration. Projects and
+//! triggers carry selectors: a worker takes one of their jobs only when every
+//! selector pair matches the registration labels of the job's organization.
+//! Persisted as a flat JSON object (`{"pool": "secure"}`), NULL when empty.
+
+use std::collections::BTreeMap;
+
+use thiserror::Error;
+
+use crate::ids::OrganizationId;
+
+/// Label or selector set, ordered so persisted JSON and log output are stable.
+pub type Labels = BTreeMap<String, String>;
+
+/// Longest accepted key or value.
+pub const MAX_LABEL_LEN: usize = 63;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum LabelError {
+    #[error("label {0:?} is not in key=value form")]
+    Malformed(String),
+    #[error("label key {0:?} must be 1-63 characters of [A-Za-z0-9._/-]")]
+    InvalidKey(String),
+    #[error("label value {0:?} must be at most 63 characters of [A-Za-z0-9._/-]")]
+    InvalidValue(String),
+}
+
+fn valid_chars(s: &str) -> bool {
+    s.len() <= MAX_LABEL_LEN
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '/' | '-'))
+}
+
+/// Validate one pair: the key is non-empty, the value may be empty.
+pub fn validate_label(key: &str, value: &str) -> Result<(), LabelError> {
+    if key.is_empty() || !valid_chars(key) {
+        return Err(LabelError::InvalidKey(key.to_owned()));
+    }
+    if !valid_chars(value) {
+        return Err(LabelError::InvalidValue(value.to_owned()));
+    }
+    Ok(())
+}
+
+pub fn validate_labels(labels: &Labels) -> Result<(), LabelError> {
+    labels.iter().try_for_each(|(k, v)| validate_label(k, v))
+}
+
+/// Parse one `key=value` item, as given to `GRADIENT_WORKER_LABELS`.
+pub fn parse_label(s: &str) -> Result<(String, String), LabelError> {
+    let (key, value) = s
+        .split_once('=')
+        .ok_or_else(|| LabelError::Malformed(s.to_owned()))?;
+    let (key, value) = (key.trim(), value.trim());
+    validate_label(key, value)?;
+    Ok((key.to_owned(), value.to_owned()))
+}
+
+/// Decode a persisted label column; NULL or a malformed value is empty.
+pub fn labels_from_json(value: Option<&serde_json::Value>) -> Labels {
+    value
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Encode a label set for persistence; empty sets are stored as NULL.
+pub fn labels_to_json(labels: &Labels) -> Option<serde_json::Value> {
+    (!labels.is_empty()).then(|| serde_json::to_value(labels).unwrap_or_default())
+}
+
+/// A project's selector narrowed by the trigger that fired the evaluation;
+/// the trigger wins on a shared key.
+pub fn merge_selector(project: &Labels, trigger: &Labels) -> Labels {
+    let mut merged = project.clone();
+    merged.extend(trigger.iter().map(|(k, v)| (k.clone(), v.clone())));
+    merged
+}
+
+/// Every label a connected worker carries: its own advertised set plus the
+/// per-organization sets from its registrations. Selectors see only the
+/// latter.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WorkerLabels {
+    pub advertised: Labels,
+    pub registered: BTreeMap<OrganizationId, Labels>,
+}
+
+impl WorkerLabels {
+    pub fn empty() -> &'static Self {
+        static EMPTY: WorkerLabels = WorkerLabels {
+            advertised: BTreeMap::new(),
+            registered: BTreeMap::new(),
+        };
+        &EMPTY
+    }
+
+    /// Value of `key` as seen by `org`'s jobs. Advertised labels are never
+    /// consulted: they are the worker's own claim, not the organization's.
+    pub fn get(&self, org: OrganizationId, key: &str) -> Option<&str> {
+        self.registered
+            .get(&org)
+            .and_then(|l| l.get(key))
+            .map(String::as_str)
+    }
+
+    /// Whether every pair of `selector` matches for `org`'s jobs. An empty
+    /// selector matches any worker.
+    pub fn matches(&self, org: OrganizationId, selector: &Labels) -> bool {
+        selector
+            .iter()
+            .all(|(k, v)| self.get(org, k) == Some(v.as_str()))
+    }
+
+    /// The label set `org`'s jobs are matched against.
+    pub fn effective(&self, org: OrganizationId) -> Labels {
+        self.registered.get(&org).cloned().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(pairs: &[(&str, &str)]) -> Labels {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn registration_labels_apply_to_their_org_only() {
+        let org_a = OrganizationId::now_v7();
+        let org_b = OrganizationId::now_v7();
+        let worker = WorkerLabels {
+            advertised: labels(&[("pool", "untrusted"), ("region", "eu")]),
+            registered: BTreeMap::from([(org_a, labels(&[("pool", "secure")]))]),
+        };
+        let secure = labels(&[("pool", "secure")]);
+
+        assert!(worker.matches(org_a, &secure));
+        assert!(!worker.matches(org_b, &secure));
+        assert!(worker.matches(org_b, &Labels::new()));
+        assert_eq!(worker.effective(org_a), secure);
+        assert!(worker.effective(org_b).is_empty());
+    }
+
+    #[test]
+    fn advertised_labels_never_satisfy_a_selector() {
+        let org = OrganizationId::now_v7();
+        let worker = WorkerLabels {
+            advertised: labels(&[("pool", "secure")]),
+            registered: BTreeMap::from([(org, labels(&[("region", "eu")]))]),
+        };
+
+        assert!(!worker.matches(org, &labels(&[("pool", "secure")])));
+        assert!(!worker.matches(OrganizationId::now_v7(), &labels(&[("pool", "secure")])));
+        assert!(worker.matches(org, &labels(&[("region", "eu")])));
+    }
+
+    #[test]
+    fn trigger_selector_overrides_the_project() {
+        let merged = merge_selector(
+            &labels(&[("pool", "secure"), ("region", "eu")]),
+            &labels(&[("pool", "untrusted")]),
+        );
+        assert_eq!(merged, labels(&[("pool", "untrusted"), ("region", "eu")]));
+    }
+
+    #[test]
+    fn parse_label_validates_key_and_value() {
+        assert_eq!(
+            parse_label(" pool = secure "),
+            Ok(("pool".into(), "secure".into()))
+        );
+        assert_eq!(parse_label("gpu="), Ok(("gpu".into(), String::new())));
+        assert!(matches!(
+            parse_label("secure"),
+            Err(LabelError::Malformed(_))
+        ));
+        assert!(matches!(parse_label("=x"), Err(LabelError::InvalidKey(_))));
+        assert!(matches!(
+            parse_label("pool=a b"),
+            Err(LabelError::InvalidValue(_))
+        ));
+    }
+
+    #[test]
+    fn empty_labels_persist_as_null() {
+        assert_eq!(labels_to_json(&Labels::new()), None);
+        let set = labels(&[("pool", "secure")]);
+        assert_eq!(labels_from_json(labels_to_json(&set).as_ref()), set);
+        assert!(labels_from_json(Some(&serde_json::json!([1, 2]))).is_empty());
+    }
+}
@@ -0,0 +1,138 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Scheduled maintenance windows of a worker registration.
+//!
+//! During a window the worker is cordoned for the registering organization,
+//! exactly as if `cordoned` were set: in-flight jobs finish, no new ones are
+//! assigned. A window opens at every firing of a six-field cron expression
+//! (`sec min hour dom mon dow`, UTC) and lasts `duration_secs`. Persisted as a
+//! JSON array on `worker_registration.maintenance_windows`, NULL when empty.
+
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Longest accepted window; anything longer is a cordon, not maintenance.
+pub const MAX_WINDOW_SECS: u64 = 7 * 24 * 3600;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaintenanceWindow {
+    /// Six-field cron expression for the window start, in UTC.
+    pub schedule: String,
+    pub duration_secs: u64,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum MaintenanceWindowError {
+    #[error("invalid maintenance schedule {0:?}: {1}")]
+    InvalidSchedule(String, String),
+    #[error("maintenance window duration must be between 1 and {MAX_WINDOW_SECS} seconds")]
+    InvalidDuration,
+}
+
+impl MaintenanceWindow {
+    pub fn validate(&self) -> Result<(), MaintenanceWindowError> {
+        cron::Schedule::from_str(&self.schedule).map_err(|e| {
+            MaintenanceWindowError::InvalidSchedule(self.schedule.clone(), e.to_string())
+        })?;
+        if self.duration_secs == 0 || self.duration_secs > MAX_WINDOW_SECS {
+            return Err(MaintenanceWindowError::InvalidDuration);
+        }
+        Ok(())
+    }
+
+    /// Whether `now` falls inside a window opened by this schedule. An
+    /// invalid schedule is never active.
+    pub fn is_active(&self, now: NaiveDateTime) -> bool {
+        let Ok(schedule) = cron::Schedule::from_str(&self.schedule) else {
+            return false;
+        };
+        let duration = chrono::Duration::seconds(self.duration_secs.min(MAX_WINDOW_SECS) as i64);
+        let opened_after = DateTime::<Utc>::from_naive_utc_and_offset(now - duration, Utc);
+        let now = DateTime::<Utc>::from_naive_utc_and_offset(now, Utc);
+        schedule
+            .after(&opened_after)
+            .next()
+            .is_some_and(|start| start <= now)
+    }
+}
+
+pub fn validate_windows(windows: &[MaintenanceWindow]) -> Result<(), MaintenanceWindowError> {
+    windows.iter().try_for_each(MaintenanceWindow::validate)
+}
+
+pub fn any_window_active(windows: &[MaintenanceWindow], now: NaiveDateTime) -> bool {
+    windows.iter().any(|w| w.is_active(now))
+}
+
+/// Decode a persisted window column; NULL or a malformed value is empty.
+pub fn windows_from_json(value: Option<&serde_json::Value>) -> Vec<MaintenanceWindow> {
+    value
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Encode windows for persistence; an empty list is stored as NULL.
+pub fn windows_to_json(windows: &[MaintenanceWindow]) -> Option<serde_json::Value> {
+    (!windows.is_empty()).then(|| serde_json::to_value(windows).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    fn nightly() -> MaintenanceWindow {
+        MaintenanceWindow {
+            schedule: "0 0 3 * * *".into(),
+            duration_secs: 3600,
+        }
+    }
+
+    #[test]
+    fn window_is_active_from_start_until_duration_elapses() {
+        let w = nightly();
+        assert!(!w.is_active(at("2026-07-16 02:59:59")));
+        assert!(w.is_active(at("2026-07-16 03:00:00")));
+        assert!(w.is_active(at("2026-07-16 03:59:59")));
+        assert!(!w.is_active(at("2026-07-16 04:00:00")));
+    }
+
+    #[test]
+    fn validation_rejects_bad_schedule_and_duration() {
+        let bad_cron = MaintenanceWindow {
+            schedule: "every night".into(),
+            ..nightly()
+        };
+        assert!(matches!(
+            bad_cron.validate(),
+            Err(MaintenanceWindowError::InvalidSchedule(..))
+        ));
+        let zero = MaintenanceWindow {
+            duration_secs: 0,
+            ..nightly()
+        };
+        assert_eq!(
+            zero.validate(),
+            Err(MaintenanceWindowError::InvalidDuration)
+        );
+        assert_eq!(nightly().validate(), Ok(()));
+    }
+
+    #[test]
+    fn json_round_trip_stores_empty_as_null() {
+        assert_eq!(windows_to_json(&[]), None);
+        let stored = windows_to_json(&[nightly()]);
+        assert_eq!(windows_from_json(stored.as_ref()), vec![nightly()]);
+        assert!(windows_from_json(Some(&serde_json::json!("garbage"))).is_empty());
+    }
+}
@@ -10,3 +10,4 @@ pub mod http_validation;
 pub mod hydra;
 pub mod nix_hash;
 pub mod shutdown;
+pub mod webhook_signature;
@@ -0,0 +1,153 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! HMAC-SHA256 signatures for outbound `send_web_request` deliveries.
+//!
+//! Gradient signs `"{timestamp}.{body}"` with the action's signing secret and
+//! sends three headers alongside the JSON body:
+//!
+//! - [`SIGNATURE_HEADER`]: `sha256=<hex digest>`
+//! - [`TIMESTAMP_HEADER`]: unix seconds at send time
+//! - [`DELIVERY_HEADER`]: the delivery UUID, stable across retries
+//!
+//! A receiver recomputes the digest over the raw request body with
+//! [`verify_signature`], rejects stale timestamps to bound replays, and
+//! de-duplicates on the delivery id to drop retried deliveries it already
+//! processed.
+
+use hmac::{Hmac, KeyInit, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub const SIGNATURE_HEADER: &str = "X-Gradient-Signature";
+pub const TIMESTAMP_HEADER: &str = "X-Gradient-Timestamp";
+pub const DELIVERY_HEADER: &str = "X-Gradient-Delivery";
+
+/// Default accepted clock skew between signing and verification.
+pub const DEFAULT_TOLERANCE_SECS: i64 = 300;
+
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum SignatureError {
+    #[error("signature header must have the form 'sha256=<hex>'")]
+    Malformed,
+    #[error("timestamp header is not a unix timestamp")]
+    InvalidTimestamp,
+    #[error("timestamp is outside the accepted window")]
+    Expired,
+    #[error("signature does not match the payload")]
+    Mismatch,
+}
+
+/// Compute the [`SIGNATURE_HEADER`] value for `body` sent at `timestamp`.
+pub fn sign_payload(secret: &[u8], timestamp: i64, body: &[u8]) -> String {
+    let digest = mac(secret, timestamp, body).finalize().into_bytes();
+    format!("sha256={}", hex::encode(digest))
+}
+
+/// Verify a delivery's signature and timestamp headers against the raw body.
+///
+/// `now` is the receiver's current unix time; deliveries signed more than
+/// `tolerance_secs` before or after it are rejected even when the digest
+/// matches. The digest comparison is constant-time.
+pub fn verify_signature(
+    secret: &[u8],
+    signature_header: &str,
+    timestamp_header: &str,
+    body: &[u8],
+    now: i64,
+    tolerance_secs: i64,
+) -> Result<(), SignatureError> {
+    let timestamp: i64 = timestamp_header
+        .trim()
+        .parse()
+        .map_err(|_| SignatureError::InvalidTimestamp)?;
+    if (now - timestamp).abs() > tolerance_secs {
+        return Err(SignatureError::Expired);
+    }
+
+    let expected = signature_header
+        .trim()
+        .strip_prefix("sha256=")
+        .and_then(|h| hex::decode(h).ok())
+        .ok_or(SignatureError::Malformed)?;
+
+    mac(secret, timestamp, body)
+        .verify_slice(&expected)
+        .map_err(|_| SignatureError::Mismatch)
+}
+
+fn mac(secret: &[u8], timestamp: i64, body: &[u8]) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    mac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &[u8] = b"whsec_test";
+    const BODY: &[u8] = br#"{"event":"build.completed"}"#;
+    const NOW: i64 = 1_760_000_000;
+
+    #[test]
+    fn signature_round_trips() {
+        let sig = sign_payload(SECRET, NOW, BODY);
+        assert!(sig.starts_with("sha256="));
+        assert_eq!(
+            verify_signature(SECRET, &sig, &NOW.to_string(), BODY, NOW + 10, 300),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn tampered_body_is_rejected() {
+        let sig = sign_payload(SECRET, NOW, BODY);
+        let err = verify_signature(SECRET, &sig, &NOW.to_string(), b"{}", NOW, 300);
+        assert_eq!(err, Err(SignatureError::Mismatch));
+    }
+
+    #[test]
+    fn wrong_secret_is_rejected() {
+        let sig = sign_payload(b"other", NOW, BODY);
+        let err = verify_signature(SECRET, &sig, &NOW.to_string(), BODY, NOW, 300);
+        assert_eq!(err, Err(SignatureError::Mismatch));
+    }
+
+    #[test]
+    fn timestamp_is_covered_by_signature() {
+        let sig = sign_payload(SECRET, NOW, BODY);
+        let err = verify_signature(SECRET, &sig, &(NOW + 1).to_string(), BODY, NOW, 300);
+        assert_eq!(err, Err(SignatureError::Mismatch));
+    }
+
+    #[test]
+    fn stale_timestamp_is_rejected() {
+        let sig = sign_payload(SECRET, NOW, BODY);
+        let err = verify_signature(SECRET, &sig, &NOW.to_string(), BODY, NOW + 301, 300);
+        assert_eq!(err, Err(SignatureError::Expired));
+    }
+
+    #[test]
+    fn malformed_headers_are_rejected() {
+        let sig = sign_payload(SECRET, NOW, BODY);
+        assert_eq!(
+            verify_signature(SECRET, &sig, "yesterday", BODY, NOW, 300),
+            Err(SignatureError::InvalidTimestamp)
+        );
+        assert_eq!(
+            verify_signature(SECRET, "md5=abc", &NOW.to_string(), BODY, NOW, 300),
+            Err(SignatureError::Malformed)
+        );
+        assert_eq!(
+            verify_signature(SECRET, "sha256=zz", &NOW.to_string(), BODY, NOW, 300),
+            Err(SignatureError::Malformed)
+        );
+    }
+}
@@ -49,6 +49,8 @@ pub mod events {
     pub const CACHE_DELETE: &str = "cache.delete";
     pub const CACHE_NAR_DELETE: &str = "cache.nar.delete";
     pub const CACHE_NAR_UPLOAD: &str = "cache.nar.upload";
+    pub const CACHE_PIN_CREATE: &str = "cache.pin.create";
+    pub const CACHE_PIN_DELETE: &str = "cache.pin.delete";
     pub const CACHE_ROLE_CREATE: &str = "cache.role.create";
     pub const CACHE_ROLE_UPDATE: &str = "cache.role.update";
     pub const CACHE_ROLE_DELETE: &str = "cache.role.delete";
@@ -4,13 +4,14 @@
  * SPDX-License-Identifier: AGPL-3.0-only
  */
 
-//! `POST /admin/maintenance/deep-gc`
+//! `POST /admin/maintenance/deep-gc`, `POST /admin/maintenance/cache-eviction`,
+//! `POST /admin/maintenance/nar-chunking`
 
 use crate::error::{WebError, WebResult, require_superuser};
 use crate::helpers::ok_json;
 use axum::http::StatusCode;
 use axum::{Extension, Json, extract::State};
-use gradient_cache::cacher::run_deep_gc;
+use gradient_cache::cacher::{run_cache_eviction, run_deep_gc, run_nar_chunking};
 use gradient_core::ServerState;
 use gradient_db::admin_tasks::{self, InsertPendingError};
 use gradient_entity::ids::AdminTaskId;
@@ -51,3 +52,80 @@ pub async fn start_deep_gc(
         }
     }
 }
+
+#[derive(Serialize, Debug)]
+pub struct StartCacheEvictionResponse {
+    pub task_id: AdminTaskId,
+    pub status: &'static str,
+}
+
+pub async fn start_cache_eviction(
+    State(state): State<Arc<ServerState>>,
+    Extension(user): Extension<MUser>,
+) -> WebResult<(StatusCode, Json<BaseResponse<StartCacheEvictionResponse>>)> {
+    require_superuser(&user)?;
+    match admin_tasks::insert_pending(
+        &state.worker_db,
+        AdminTaskKind::CacheEviction,
+        Some(user.id),
+    )
+    .await
+    {
+        Ok(task) => {
+            info!(task_id = %task.id, "cache_eviction: spawning pass");
+            state
+                .shutdown
+                .spawn(run_cache_eviction(Arc::clone(&state), task.id));
+            let body = ok_json(StartCacheEvictionResponse {
+                task_id: task.id,
+                status: "pending",
+            });
+            Ok((StatusCode::ACCEPTED, body))
+        }
+        Err(InsertPendingError::AlreadyActive(id)) => Err(WebError::conflict(format!(
+            "cache_eviction task {id} is already pending or running"
+        ))),
+        Err(InsertPendingError::Db(e)) => {
+            Err(WebError::internal(format!("admin_task insert failed: {e}")))
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct StartNarChunkingResponse {
+    pub task_id: AdminTaskId,
+    pub status: &'static str,
+}
+
+pub async fn start_nar_chunking(
+    State(state): State<Arc<ServerState>>,
+    Extension(user): Extension<MUser>,
+) -> WebResult<(StatusCode, Json<BaseResponse<StartNarChunkingResponse>>)> {
+    require_superuser(&user)?;
+    if !state.config.storage.nar_chunking {
+        return Err(WebError::bad_request(
+            "NAR chunking is disabled (GRADIENT_NAR_CHUNKING)",
+        ));
+    }
+    match admin_tasks::insert_pending(&state.worker_db, AdminTaskKind::NarChunking, Some(user.id))
+        .await
+    {
+        Ok(task) => {
+            info!(task_id = %task.id, "nar_chunking: spawning pass");
+            state
+                .shutdown
+                .spawn(run_nar_chunking(Arc::clone(&state), task.id));
+            let body = ok_json(StartNarChunkingResponse {
+                task_id: task.id,
+                status: "pending",
+            });
+            Ok((StatusCode::ACCEPTED, body))
+        }
+        Err(InsertPendingError::AlreadyActive(id)) => Err(WebError::conflict(format!(
+            "nar_chunking task {id} is already pending or running"
+        ))),
+        Err(InsertPendingError::Db(e)) => {
+            Err(WebError::internal(format!("admin_task insert failed: {e}")))
+        }
+    }
+}
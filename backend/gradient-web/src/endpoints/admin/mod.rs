@@ -28,10 +28,24 @@ use std::sync::Arc;
 pub fn admin_router() -> Router<Arc<ServerState>> {
     Router::new()
         .route("/workers", get(workers::get_workers))
+        .route(
+            "/workers/{worker_id}/drain",
+            get(workers::get_worker_drain)
+                .post(workers::post_worker_drain)
+                .delete(workers::delete_worker_drain),
+        )
         .route("/state", get(state::export_state))
         .route("/github-app/manifest", post(github_app::request_manifest))
         .route("/github-app/credentials", get(github_app::credentials))
         .route("/maintenance/deep-gc", post(maintenance::start_deep_gc))
+        .route(
+            "/maintenance/cache-eviction",
+            post(maintenance::start_cache_eviction),
+        )
+        .route(
+            "/maintenance/nar-chunking",
+            post(maintenance::start_nar_chunking),
+        )
         .route("/draining", post(draining::set_draining))
         .route("/tasks", get(tasks::list_tasks))
         .route("/tasks/{task_id}", get(tasks::get_task))
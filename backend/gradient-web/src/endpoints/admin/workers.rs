@@ -6,5 +6,104 @@
 
 //! `GET /api/v1/admin/workers` - re-exports the existing handler so the route
 //! lives under the admin namespace.
+//!
+//! `/api/v1/admin/workers/{worker_id}/drain` - worker-level cordon. `POST`
+//! cordons the worker for every organization, `DELETE` lifts it, `GET`
+//! reports drain progress. Unlike the per-organization drain this also covers
+//! base workers, which no organization owns.
+
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::{Extension, Json};
+use gradient_core::ServerState;
+use gradient_entity::worker_registration;
+use gradient_scheduler::Scheduler;
+use gradient_types::*;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use serde::Serialize;
+
+use crate::error::{WebError, WebResult, require_superuser};
+use crate::helpers::ok_json;
 
 pub use crate::endpoints::workers::get_workers;
+
+#[derive(Debug, Serialize)]
+pub struct WorkerCordonStatus {
+    pub worker_id: String,
+    /// Cordoned for every organization.
+    pub cordoned: bool,
+    pub connected: bool,
+    /// Jobs of any organization still running on the worker.
+    pub in_flight_jobs: u32,
+    /// Cordoned with no job left.
+    pub drained: bool,
+}
+
+async fn worker_cordon_status(scheduler: &Scheduler, worker_id: String) -> WorkerCordonStatus {
+    let cordoned = scheduler.is_worker_cordoned(&worker_id).await;
+    let in_flight_jobs = scheduler.jobs_on_worker(&worker_id).await;
+    WorkerCordonStatus {
+        connected: scheduler.is_worker_connected(&worker_id).await,
+        cordoned,
+        in_flight_jobs,
+        drained: cordoned && in_flight_jobs == 0,
+        worker_id,
+    }
+}
+
+/// 404 unless `worker_id` is registered by some organization or is a base
+/// worker.
+async fn ensure_known_worker(state: &ServerState, worker_id: &str) -> WebResult<()> {
+    let registered = EWorkerRegistration::find()
+        .filter(worker_registration::Column::WorkerId.eq(worker_id))
+        .one(&state.web_db)
+        .await?
+        .is_some();
+    if registered
+        || gradient_db::base_workers::enabled_base_worker_by_worker_id(&state.web_db, worker_id)
+            .await?
+            .is_some()
+    {
+        return Ok(());
+    }
+    Err(WebError::not_found("worker"))
+}
+
+pub async fn get_worker_drain(
+    state: State<Arc<ServerState>>,
+    Path(worker_id): Path<String>,
+    Extension(user): Extension<MUser>,
+    Extension(scheduler): Extension<Arc<Scheduler>>,
+) -> WebResult<Json<BaseResponse<WorkerCordonStatus>>> {
+    require_superuser(&user)?;
+    ensure_known_worker(&state, &worker_id).await?;
+    Ok(ok_json(worker_cordon_status(&scheduler, worker_id).await))
+}
+
+/// Cordon the worker for every organization and report drain progress.
+/// In-flight jobs finish; poll the GET variant until `drained` turns true.
+pub async fn post_worker_drain(
+    state: State<Arc<ServerState>>,
+    Path(worker_id): Path<String>,
+    Extension(user): Extension<MUser>,
+    Extension(scheduler): Extension<Arc<Scheduler>>,
+) -> WebResult<Json<BaseResponse<WorkerCordonStatus>>> {
+    require_superuser(&user)?;
+    ensure_known_worker(&state, &worker_id).await?;
+    scheduler.set_worker_cordon(&worker_id, true).await;
+    Ok(ok_json(worker_cordon_status(&scheduler, worker_id).await))
+}
+
+/// Lift the worker-level cordon. Per-organization cordons stay in place.
+pub async fn delete_worker_drain(
+    state: State<Arc<ServerState>>,
+    Path(worker_id): Path<String>,
+    Extension(user): Extension<MUser>,
+    Extension(scheduler): Extension<Arc<Scheduler>>,
+) -> WebResult<Json<BaseResponse<WorkerCordonStatus>>> {
+    require_superuser(&user)?;
+    ensure_known_worker(&state, &worker_id).await?;
+    scheduler.set_worker_cordon(&worker_id, false).await;
+    Ok(ok_json(worker_cordon_status(&scheduler, worker_id).await))
+}
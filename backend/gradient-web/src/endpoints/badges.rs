@@ -283,8 +283,34 @@ async fn check_badge_org_access(
     Ok(())
 }
 
+/// Map an entry point's build status onto the badge's evaluation status and
+/// failed flag. An aggregate's `DependencyFailed` means a constituent failed,
+/// so it reads as a failure rather than an abort.
+fn badge_for_build_status(
+    build_status: Option<BuildStatus>,
+    aggregate: bool,
+) -> (EvaluationStatus, bool) {
+    match build_status {
+        Some(BuildStatus::Completed) | Some(BuildStatus::Substituted) => {
+            (EvaluationStatus::Completed, false)
+        }
+        Some(BuildStatus::FailedPermanent) | Some(BuildStatus::FailedTimeout) => {
+            (EvaluationStatus::Failed, true)
+        }
+        Some(BuildStatus::DependencyFailed) if aggregate => (EvaluationStatus::Failed, true),
+        Some(BuildStatus::Aborted) | Some(BuildStatus::DependencyFailed) => {
+            (EvaluationStatus::Aborted, false)
+        }
+        Some(BuildStatus::Building) | Some(BuildStatus::FailedTransient) => {
+            (EvaluationStatus::Building, false)
+        }
+        _ => (EvaluationStatus::Queued, false),
+    }
+}
+
 /// Badge status when `?eval=<attr>` is specified: look up the entry point's
-/// build status in the latest completed evaluation.
+/// build status in the latest completed evaluation. An aggregate reports its
+/// combined status.
 async fn badge_status_for_entry_point(
     state: &Arc<ServerState>,
     project_id: ProjectId,
@@ -310,33 +336,28 @@ async fn badge_status_for_entry_point(
         return Ok((None, false));
     };
 
-    let build_status = EDerivationBuild::find()
-        .filter(CDerivationBuild::Derivation.eq(ep.derivation))
-        .one(&state.web_db)
-        .await?
-        .map(|a| a.status);
-
-    let (eval_status, has_failed) = match build_status {
-        Some(BuildStatus::Completed) | Some(BuildStatus::Substituted) => {
-            (EvaluationStatus::Completed, false)
-        }
-        Some(BuildStatus::FailedPermanent) | Some(BuildStatus::FailedTimeout) => {
-            (EvaluationStatus::Failed, true)
-        }
-        Some(BuildStatus::Aborted) | Some(BuildStatus::DependencyFailed) => {
-            (EvaluationStatus::Aborted, false)
-        }
-        Some(BuildStatus::Building) | Some(BuildStatus::FailedTransient) => {
-            (EvaluationStatus::Building, false)
-        }
-        _ => (EvaluationStatus::Queued, false),
+    let build_status = if ep.aggregate {
+        gradient_db::aggregate_statuses(&state.web_db, ev.id)
+            .await?
+            .into_iter()
+            .find(|a| a.entry_point.id == ep.id)
+            .map(|a| a.status)
+    } else {
+        EDerivationBuild::find()
+            .filter(CDerivationBuild::Derivation.eq(ep.derivation))
+            .one(&state.web_db)
+            .await?
+            .map(|a| a.status)
     };
 
+    let (eval_status, has_failed) = badge_for_build_status(build_status, ep.aggregate);
+
     Ok((Some(eval_status), has_failed))
 }
 
 /// Badge status for the overall project: use the last evaluation's status and
-/// check whether any entry-point builds failed.
+/// check whether any entry-point builds failed. When the evaluation has
+/// aggregates, they are its release gate and decide the badge instead.
 async fn badge_status_for_latest_eval(
     state: &Arc<ServerState>,
     project: &MProject,
@@ -347,6 +368,14 @@ async fn badge_status_for_latest_eval(
 
     let eval = EEvaluation::find_by_id(eval_id).one(&state.web_db).await?;
 
+    if let Some(e) = &eval {
+        let aggregates = gradient_db::aggregate_statuses(&state.web_db, e.id).await?;
+        if let Some(status) = gradient_db::evaluation_aggregate_status(&aggregates) {
+            let (eval_status, has_failed) = badge_for_build_status(Some(status), true);
+            return Ok((Some(eval_status), has_failed));
+        }
+    }
+
     let has_failed = match &eval {
         Some(e) if e.status == EvaluationStatus::Completed => {
             let ep_drv_ids: Vec<DerivationId> = EEntryPoint::find()
@@ -460,6 +489,18 @@ mod tests {
         assert!(!square.contains("linearGradient"));
     }
 
+    #[test]
+    fn aggregate_dependency_failure_is_a_failure() {
+        assert_eq!(
+            badge_for_build_status(Some(BuildStatus::DependencyFailed), true),
+            (EvaluationStatus::Failed, true)
+        );
+        assert_eq!(
+            badge_for_build_status(Some(BuildStatus::DependencyFailed), false),
+            (EvaluationStatus::Aborted, false)
+        );
+    }
+
     #[test]
     fn badge_for_none_is_unknown() {
         let b = badge_for_status(None, false);
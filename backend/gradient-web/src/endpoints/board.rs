@@ -240,6 +240,12 @@ pub struct AttemptSummary {
     pub outcome: i32,
     pub reason: Option<i32>,
     pub failure_message: Option<String>,
+    /// One-line summary of the cause recognised in this attempt's log.
+    pub failure_summary: Option<String>,
+    /// This failure was judged flaky and re-run on another worker.
+    pub flaky: bool,
+    /// This attempt is the re-run of a flaky failure.
+    pub flaky_rerun: bool,
     pub created_at: String,
 }
 
@@ -371,6 +377,12 @@ pub async fn get_dispatched_job(
                 outcome: i32::from(a.outcome),
                 reason: a.reason.map(i32::from),
                 failure_message: a.failure_message,
+                failure_summary: a
+                    .failure_cause
+                    .and_then(|v| serde_json::from_value::<FailureCause>(v).ok())
+                    .map(|c| c.summary()),
+                flaky: a.flaky,
+                flaky_rerun: a.flaky_rerun,
                 created_at: a.created_at.and_utc().to_rfc3339(),
             })
             .collect(),
@@ -720,9 +732,20 @@ pub struct RuleDescription {
     pub description: String,
 }
 
-/// Static catalog of every scoring rule and what it rewards or penalizes, so the
-/// board UI can explain rule names in a help popup without duplicating the text.
-pub async fn get_scoring_rules() -> WebResult<Json<BaseResponse<Vec<RuleDescription>>>> {
+#[derive(Serialize)]
+pub struct ScoringRules {
+    pub rules: Vec<RuleDescription>,
+    /// The policy the scheduler is scoring with, its per-rule weights and veto
+    /// toggles, and any validation errors from the last policy file load.
+    pub policy: gradient_score::PolicyStatus,
+}
+
+/// Catalog of every scoring rule and what it rewards or penalizes, so the board
+/// UI can explain rule names in a help popup without duplicating the text, plus
+/// the active policy so a misconfigured policy file is visible.
+pub async fn get_scoring_rules(
+    Extension(scheduler): Extension<Arc<Scheduler>>,
+) -> WebResult<Json<BaseResponse<ScoringRules>>> {
     let rules = gradient_score::rule_catalog()
         .into_iter()
         .map(|(rule, description)| RuleDescription {
@@ -731,7 +754,10 @@ pub async fn get_scoring_rules() -> WebResult<Json<BaseResponse<Vec<RuleDescript
         })
         .collect();
 
-    Ok(ok_json(rules))
+    Ok(ok_json(ScoringRules {
+        rules,
+        policy: scheduler.scoring_policy_status(),
+    }))
 }
 
 /// Aggregate scoring view over recently dispatched jobs: a score histogram plus
@@ -1231,6 +1257,9 @@ mod tests {
             ram_total_mb: 0,
             disk_speed_mbps: None,
             network_speed_mbps: None,
+            labels: Default::default(),
+            cordoned: Default::default(),
+            worker_cordoned: false,
         }
     }
 
@@ -23,9 +23,11 @@ use gradient_core::ServerState;
 use gradient_entity::metric_rollup::RollupGranularity;
 use gradient_scheduler::Scheduler;
 use gradient_types::*;
-use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+use sea_orm::{ColumnTrait, ConnectionTrait, DatabaseBackend, EntityTrait, QueryFilter, Statement};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
+use uuid::Uuid;
 
 #[derive(Deserialize)]
 pub struct WindowParams {
@@ -173,7 +175,6 @@ pub async fn get_board_upstreams(
         ))
         .await?;
 
-    use std::collections::HashMap;
     struct Agg {
         latency: Vec<SeriesPoint>,
         hits: HashMap<String, f64>,
@@ -371,13 +372,74 @@ pub struct BoardFleetPoint {
     pub build: i64,
 }
 
+/// Live quota state of one organization the caller can see.
+#[derive(Serialize)]
+pub struct BoardOrgQuota {
+    pub organization_name: String,
+    #[serde(flatten)]
+    pub usage: gradient_scheduler::quota::OrgQuotaUsage,
+}
+
+#[derive(Serialize)]
+pub struct BoardFleet {
+    pub points: Vec<BoardFleetPoint>,
+    pub quotas: Vec<BoardOrgQuota>,
+}
+
 pub async fn get_board_fleet(
     State(state): State<Arc<ServerState>>,
     Extension(MaybeUser(maybe_user)): Extension<MaybeUser>,
+    Extension(scheduler): Extension<Arc<Scheduler>>,
     Query(params): Query<WindowParams>,
-) -> WebResult<Json<BaseResponse<Vec<BoardFleetPoint>>>> {
+) -> WebResult<Json<BaseResponse<BoardFleet>>> {
     let scope = MetricsScope::resolve(&state.web_db, &maybe_user).await?;
-    let window = window_clause(&params);
+    let quotas = board_org_quotas(&state, &scheduler, &scope).await?;
+    let points = fleet_points(&state, &scope, window_clause(&params)).await?;
+
+    Ok(ok_json(BoardFleet { points, quotas }))
+}
+
+/// Quota usage of every visible org with a limit, with its name resolved.
+async fn board_org_quotas(
+    state: &Arc<ServerState>,
+    scheduler: &Scheduler,
+    scope: &MetricsScope,
+) -> WebResult<Vec<BoardOrgQuota>> {
+    let usage: Vec<_> = scheduler
+        .org_quota_usage()
+        .await
+        .into_iter()
+        .filter(|u| scope.allows(&Uuid::from(u.organization)))
+        .collect();
+    if usage.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let names: HashMap<OrganizationId, String> = EOrganization::find()
+        .filter(COrganization::Id.is_in(usage.iter().map(|u| u.organization)))
+        .all(&state.web_db)
+        .await?
+        .into_iter()
+        .map(|o| (o.id, o.name))
+        .collect();
+
+    Ok(usage
+        .into_iter()
+        .filter_map(|usage| {
+            let organization_name = names.get(&usage.organization)?.clone();
+            Some(BoardOrgQuota {
+                organization_name,
+                usage,
+            })
+        })
+        .collect())
+}
+
+async fn fleet_points(
+    state: &Arc<ServerState>,
+    scope: &MetricsScope,
+    window: i64,
+) -> WebResult<Vec<BoardFleetPoint>> {
     let mut sql = format!(
         "SELECT date_trunc('hour', at) AS bucket, \
                 count(DISTINCT worker_id) AS connected, \
@@ -392,7 +454,7 @@ pub async fn get_board_fleet(
 
     if let Some(list) = scope.org_in_list() {
         if list.is_empty() {
-            return Ok(ok_json(vec![]));
+            return Ok(vec![]);
         }
 
         sql.push_str(&format!(" AND organization IN ({list})"));
@@ -419,7 +481,7 @@ pub async fn get_board_fleet(
         })
         .collect();
 
-    Ok(ok_json(out))
+    Ok(out)
 }
 
 const DURATION_BANDS: &[&str] = &[
@@ -10,7 +10,7 @@ use crate::helpers::ok_json;
 use axum::extract::{Path, State};
 use axum::{Extension, Json};
 use gradient_core::ServerState;
-use gradient_db::latest_attempt_worker;
+use gradient_db::{latest_attempt_worker, latest_failure_cause};
 use gradient_sources::get_path_from_derivation_output;
 use gradient_types::*;
 use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
@@ -32,6 +32,11 @@ pub struct BuildWithOutputs {
     /// executed this build. `None` if the build never reached a worker.
     pub worker: Option<String>,
     pub output: HashMap<String, String>,
+    /// Structured cause recognised in the failed build's log; `None` unless
+    /// the latest attempt failed with a recognised cause.
+    pub failure_cause: Option<FailureCause>,
+    /// One-line summary of `failure_cause`.
+    pub failure_summary: Option<String>,
     pub created_at: chrono::NaiveDateTime,
     pub updated_at: chrono::NaiveDateTime,
 }
@@ -73,6 +78,10 @@ pub async fn get_build(
         .await
         .ok()
         .flatten();
+    let failure_cause = latest_failure_cause(&state.web_db, anchor.id)
+        .await
+        .ok()
+        .flatten();
 
     let build_with_outputs = BuildWithOutputs {
         id: build_job.id,
@@ -82,6 +91,8 @@ pub async fn get_build(
         architecture: derivation.architecture,
         worker,
         output: outputs,
+        failure_summary: failure_cause.as_ref().map(FailureCause::summary),
+        failure_cause,
         created_at: build_job.created_at,
         updated_at: anchor.updated_at,
     };
@@ -217,14 +217,14 @@ async fn get_nar_by_hash_inner(
     let file_hash_nix32 = strip_hash_algo(&file_hash).to_string();
     let file_size = cached_path_row
         .file_size
-        .or_not_found("FileSize not recorded")? as u32;
+        .or_not_found("FileSize not recorded")? as u64;
 
     Ok(NixPathInfo {
         store_path: path,
         url: format!("nar/{}.nar.zst", file_hash_nix32),
         compression: "zstd".to_string(),
-        file_hash,
-        file_size,
+        file_hash: Some(file_hash),
+        file_size: Some(file_size),
         nar_hash,
         nar_size,
         references,
@@ -282,7 +282,7 @@ async fn get_nar_by_cached_path(
         .ok_or_else(|| WebError::bad_request("Missing file hash"))?;
     let file_size = cached_path_row
         .file_size
-        .ok_or_else(|| WebError::bad_request("Missing file size"))? as u32;
+        .ok_or_else(|| WebError::bad_request("Missing file size"))? as u64;
     let nar_hash = cached_path_row
         .nar_hash
         .as_deref()
@@ -298,8 +298,8 @@ async fn get_nar_by_cached_path(
         store_path: cached_path_row.store_path(),
         url: format!("nar/{}.nar.zst", file_hash_nix32),
         compression: "zstd".to_string(),
-        file_hash,
-        file_size,
+        file_hash: Some(file_hash),
+        file_size: Some(file_size),
         nar_hash,
         nar_size,
         references,
@@ -357,6 +357,19 @@ pub(super) fn cache_client_ip(
     resolve_client_ip(headers, peer_ip, &state.config.network.trusted_proxies)
 }
 
+/// Query extractor for the `?compression=zstd|xz|none` override of the
+/// cache's `nar_compression` on `.narinfo` and `serve` downloads.
+#[derive(Debug, serde::Deserialize)]
+pub struct CompressionQuery {
+    pub compression: Option<NarCompression>,
+}
+
+impl CompressionQuery {
+    pub fn or_cache(&self, cache: &MCache) -> NarCompression {
+        self.compression.unwrap_or(cache.nar_compression)
+    }
+}
+
 /// Query extractor for the `?json` flag used by text-format cache endpoints
 /// (`nix-cache-info`, `gradient-cache-info`, `.narinfo`). Any presence of
 /// `?json` (with or without a value) selects the JSON response variant.
@@ -396,7 +409,8 @@ pub(super) struct DeleteOutcome {
 
 /// Removes a single cache's claim on a NAR. Drops the per-cache signature row,
 /// the per-cache derivation pin, and - if no other cache still holds the path -
-/// the shared `cached_path` row plus the underlying NAR blob.
+/// the shared `cached_path` row plus the underlying NAR blob. Paths in the
+/// closure of a live pin of the cache are refused until the pin goes.
 pub(super) async fn delete_nar_from_cache(
     state: &Arc<ServerState>,
     cache_id: CacheId,
@@ -417,6 +431,15 @@ pub(super) async fn delete_nar_from_cache(
         .await?
         .or_not_found("Nar")?;
 
+    if gradient_db::pinned_paths(&tx)
+        .await?
+        .contains_in(cache_id, hash)
+    {
+        return Err(WebError::conflict(
+            "Path is kept by a pin of this cache; remove the pin first",
+        ));
+    }
+
     ECachedPathSignature::delete_by_id(sig.id).exec(&tx).await?;
 
     let derivation_ids: Vec<DerivationId> = EDerivationOutput::find()
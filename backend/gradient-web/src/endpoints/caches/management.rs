@@ -15,6 +15,7 @@ use axum::Json;
 use axum::extract::{Path, Query, State};
 use chrono::NaiveDateTime;
 use gradient_core::ServerState;
+use gradient_entity::cache::{CacheEvictionPolicy, NarCompression};
 use gradient_entity::cache_upstream::CacheUpstreamKind;
 use gradient_entity::organization_cache::CacheSubscriptionMode;
 use gradient_sources::{format_cache_public_key, generate_signing_key};
@@ -40,6 +41,10 @@ pub struct MakeCacheRequest {
     pub local_priority: Option<i32>,
     #[serde(default)]
     pub max_storage_gb: Option<i32>,
+    #[serde(default)]
+    pub eviction_policy: Option<CacheEvictionPolicy>,
+    #[serde(default)]
+    pub nar_compression: Option<NarCompression>,
 }
 
 #[derive(Serialize)]
@@ -52,6 +57,8 @@ pub struct CacheResponse {
     pub priority: i32,
     pub local_priority: Option<i32>,
     pub max_storage_gb: i32,
+    pub eviction_policy: CacheEvictionPolicy,
+    pub nar_compression: NarCompression,
     pub public_key: String,
     pub public: bool,
     pub created_by: UserId,
@@ -68,6 +75,8 @@ pub struct PatchCacheRequest {
     pub priority: Option<i32>,
     pub local_priority: Option<i32>,
     pub max_storage_gb: Option<i32>,
+    pub eviction_policy: Option<CacheEvictionPolicy>,
+    pub nar_compression: Option<NarCompression>,
 }
 
 fn validate_max_storage_gb(value: i32) -> WebResult<()> {
@@ -167,6 +176,8 @@ pub async fn put(
         created_by: user.id,
         created_at: gradient_types::now(),
         max_storage_gb,
+        eviction_policy: body.eviction_policy.unwrap_or_default(),
+        nar_compression: body.nar_compression.unwrap_or_default(),
         ..Default::default()
     }
     .into_active_model()
@@ -277,6 +288,8 @@ pub async fn get_cache(
         priority: cache.priority,
         local_priority: cache.local_priority,
         max_storage_gb: cache.max_storage_gb,
+        eviction_policy: cache.eviction_policy,
+        nar_compression: cache.nar_compression,
         public_key,
         public: cache.public,
         created_by: cache.created_by,
@@ -353,6 +366,14 @@ pub async fn patch_cache(
         acache.max_storage_gb = Set(max_storage_gb);
     }
 
+    if let Some(eviction_policy) = body.eviction_policy {
+        acache.eviction_policy = Set(eviction_policy);
+    }
+
+    if let Some(nar_compression) = body.nar_compression {
+        acache.nar_compression = Set(nar_compression);
+    }
+
     acache
         .update(&state.web_db)
         .await
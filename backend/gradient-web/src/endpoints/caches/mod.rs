@@ -13,7 +13,9 @@ mod nar;
 mod narinfo;
 mod narlist;
 mod nars;
+mod pins;
 mod proto;
+mod realisation;
 pub mod roles;
 mod serve;
 mod upload;
@@ -33,7 +35,9 @@ pub use self::nars::{
     available as nars_available, delete as nars_delete, list as nars_list, show as nars_show,
     stats as nars_stats,
 };
+pub use self::pins::{delete_cache_pin, get_cache_pins, put_cache_pin};
 pub use self::proto::cache_proto;
+pub use self::realisation::realisation;
 pub use self::serve::serve;
 pub use self::upload::{nar_chunk, nar_finalize, nars_upload};
 pub use self::upstreams::{
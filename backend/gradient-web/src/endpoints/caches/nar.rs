@@ -14,6 +14,7 @@ use axum::http::{HeaderMap, HeaderValue, header};
 use axum::response::Response;
 use gradient_core::ServerState;
 use gradient_sources::get_hash_from_url;
+use gradient_storage::nar_transcode::transcode_stream;
 use gradient_types::*;
 use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter};
 use std::sync::Arc;
@@ -28,9 +29,9 @@ pub async fn nar(
     let path_hash =
         get_hash_from_url(path.clone()).map_err(|e| WebError::bad_request(e.to_string()))?;
 
-    if !(path.ends_with(".nar") || path.contains(".nar.")) {
-        return Err(WebError::not_found("Path"));
-    }
+    // The extension picks the encoding: the narinfo advertised it from the
+    // cache's `nar_compression` (or the client's `?compression=`).
+    let compression = NarCompression::from_nar_path(&path).or_not_found("Path")?;
 
     let client_ip = cache_client_ip(&state, &headers, peer);
     let ctx = CacheContext::load(&state, &headers, client_ip, cache).await?;
@@ -39,15 +40,20 @@ pub async fn nar(
         super::helpers::fetch_nar_stream(&state, &path_hash).await?;
 
     spawn_nar_traffic_metric(Arc::clone(&state), ctx.cache.id, size as i64);
+    spawn_cached_path_access_update(Arc::clone(&state), effective_hash.clone());
     spawn_cache_derivation_fetch_update(Arc::clone(&state), ctx.cache.id, effective_hash);
 
-    Response::builder()
-        .header(
-            header::CONTENT_TYPE,
-            HeaderValue::from_static("application/x-nix-nar"),
-        )
-        .header(header::CONTENT_LENGTH, size)
-        .body(Body::from_stream(stream))
+    let mut builder = Response::builder().header(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/x-nix-nar"),
+    );
+    // Only the stored object's size is known up front; transcoded bodies
+    // are chunked.
+    if compression == NarCompression::Zstd {
+        builder = builder.header(header::CONTENT_LENGTH, size);
+    }
+    builder
+        .body(Body::from_stream(transcode_stream(stream, compression)))
         .map_err(|e| WebError::internal(format!("Failed to build response: {}", e)))
 }
 
@@ -102,7 +108,7 @@ pub(crate) async fn resolve_effective_hash_db<C: ConnectionTrait>(
     let candidates = [format!("blake3:{path_hash}"), format!("sha256:{path_hash}")];
 
     let by_cached_path = ECachedPath::find()
-        .filter(CCachedPath::FileHash.is_in(candidates))
+        .filter(CCachedPath::FileHash.is_in(candidates.clone()))
         .one(db)
         .await?;
 
@@ -110,6 +116,12 @@ pub(crate) async fn resolve_effective_hash_db<C: ConnectionTrait>(
         return Ok(row.hash);
     }
 
+    // A narinfo fetched before its NAR was converted to the chunked layout
+    // still names the flat `file_hash`.
+    if let Some(hash) = gradient_db::chunked_nar_by_flat_file_hash(db, &candidates).await? {
+        return Ok(hash);
+    }
+
     Ok(path_hash.to_string())
 }
 
@@ -120,6 +132,29 @@ fn spawn_nar_traffic_metric(state: Arc<ServerState>, cache_id: CacheId, bytes_le
     });
 }
 
+/// Stamp `cached_path.last_accessed_at` for LRU eviction, spawned after every
+/// served narinfo or NAR. Fire-and-forget on `worker_db`, like the fetch
+/// bookkeeping below. A stamp younger than an hour is left alone: LRU needs no
+/// finer resolution, and a hot path would otherwise rewrite its row on every
+/// hit.
+pub(super) fn spawn_cached_path_access_update(state: Arc<ServerState>, hash: String) {
+    let s = Arc::clone(&state);
+    state.shutdown.spawn(async move {
+        use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+        let now_val = sea_orm::Value::ChronoDateTime(Some(Box::new(gradient_types::now())));
+        let _ = s
+            .worker_db
+            .execute(Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                "UPDATE cached_path SET last_accessed_at = $1 \
+                 WHERE hash = $2 \
+                   AND (last_accessed_at IS NULL OR last_accessed_at < $1 - interval '1 hour')",
+                [now_val, hash.into()],
+            ))
+            .await;
+    });
+}
+
 /// Bookkeeping update spawned after every successful NAR fetch. Uses
 /// `worker_db` (not `web_db`) on purpose: under heavy NAR traffic these
 /// fire-and-forget UPDATEs would otherwise contend with foreground HTTP
@@ -177,6 +212,7 @@ fn build_upstream_nar_url(base_url: &str, path: &str, query: Option<&str>) -> St
 mod tests {
     use super::*;
     use sea_orm::{DatabaseBackend, MockDatabase};
+    use std::collections::BTreeMap;
 
     // Placeholder file hash (nix32 52-char) as it appears in a narinfo URL.
     const FILE_HASH_NIX32: &str = "0mdqa9w1p6cmli6976v4wi0sw9r4p5prkj7lzfd1877wk11c9c73";
@@ -224,6 +260,7 @@ mod tests {
     fn resolve_falls_back_to_url_hash_when_no_match() {
         let db = MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results([Vec::<gradient_entity::cached_path::Model>::new()])
+            .append_query_results([Vec::<BTreeMap<&str, sea_orm::Value>>::new()])
             .into_connection();
 
         let effective = runtime()
@@ -232,6 +269,24 @@ mod tests {
         assert_eq!(effective, FILE_HASH_NIX32);
     }
 
+    /// A NAR URL from a narinfo served before the NAR was chunked names the
+    /// flat `file_hash`, which only `nar_chunked` still remembers.
+    #[test]
+    fn resolve_maps_pre_chunking_file_hash_to_store_hash() {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([Vec::<gradient_entity::cached_path::Model>::new()])
+            .append_query_results([vec![BTreeMap::from([(
+                "hash",
+                sea_orm::Value::from(STORE_HASH),
+            )])]])
+            .into_connection();
+
+        let effective = runtime()
+            .block_on(resolve_effective_hash_db(&db, FILE_HASH_NIX32))
+            .expect("resolve should succeed");
+        assert_eq!(effective, STORE_HASH);
+    }
+
     /// A hash-routed upstream (e.g. `cache.nixos-cuda.org`) needs the
     /// `?hash=<storehash>` query the re-served narinfo carried; dropping it 404s a
     /// NAR the upstream has.
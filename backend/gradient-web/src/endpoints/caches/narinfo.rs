@@ -4,7 +4,7 @@
  * SPDX-License-Identifier: AGPL-3.0-only
  */
 
-use super::helpers::{CacheContext, JsonFlag, cache_client_ip, get_nar_by_hash};
+use super::helpers::{CacheContext, CompressionQuery, JsonFlag, cache_client_ip, get_nar_by_hash};
 use crate::client_ip::OptionalPeer;
 use crate::error::{WebError, WebResult};
 use axum::extract::{Path, Query, State};
@@ -113,6 +113,7 @@ pub async fn path(
     headers: HeaderMap,
     Path((cache, path)): Path<(String, String)>,
     Query(flag): Query<JsonFlag>,
+    Query(compression): Query<CompressionQuery>,
 ) -> WebResult<Response> {
     let path_hash =
         get_hash_from_url(path.clone()).map_err(|e| WebError::bad_request(e.to_string()))?;
@@ -127,11 +128,13 @@ pub async fn path(
     if let Ok(path_info) =
         get_nar_by_hash(Arc::clone(&state), ctx.cache.clone(), path_hash.clone()).await
     {
+        let path_info = path_info.with_compression(compression.or_cache(&ctx.cache));
         let response = if flag.is_set() {
             axum::Json(path_info).into_response()
         } else {
             text_response("text/x-nix-narinfo", path_info.to_nix_string())?.into_response()
         };
+        super::nar::spawn_cached_path_access_update(Arc::clone(&state), path_hash);
         return Ok(with_narinfo_headers(response, "HIT"));
     }
 
@@ -0,0 +1,338 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Cache pins under `/api/v1/caches/{cache}/pins`: named GC roots on a store
+//! path or a whole evaluation (see [`gradient_db::pins`]).
+
+use crate::access::{CacheAccess, Caller, is_org_member, load_cache};
+use crate::audit::{RequestInfo, events, record as audit_record};
+use crate::authorization::{ApiKeyContext, MaybeApiKey, MaybeUser};
+use crate::error::{WebError, WebResult};
+use crate::helpers::{OptionExt, ok_json};
+use crate::permissions::CachePermission;
+use axum::extract::{Path, State};
+use axum::{Extension, Json};
+use chrono::NaiveDateTime;
+use gradient_core::ServerState;
+use gradient_types::input::check_index_name;
+use gradient_types::*;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize)]
+pub struct CreatePinRequest {
+    pub name: String,
+    pub store_path: Option<String>,
+    pub evaluation: Option<EvaluationId>,
+    pub expires_at: Option<NaiveDateTime>,
+}
+
+#[derive(Serialize)]
+pub struct CachePinItem {
+    pub id: CachePinId,
+    pub name: String,
+    pub store_path: Option<String>,
+    pub evaluation: Option<EvaluationId>,
+    pub managed: bool,
+    pub created_by: Option<UserId>,
+    pub created_at: NaiveDateTime,
+    pub expires_at: Option<NaiveDateTime>,
+    pub expired: bool,
+}
+
+impl CachePinItem {
+    fn from_model(pin: MCachePin, now: NaiveDateTime) -> Self {
+        Self {
+            expired: !pin.is_live(now),
+            store_path: pin.store_path().map(|p| p.full()),
+            id: pin.id,
+            name: pin.name,
+            evaluation: pin.evaluation,
+            managed: pin.managed,
+            created_by: pin.created_by,
+            created_at: pin.created_at,
+            expires_at: pin.expires_at,
+        }
+    }
+}
+
+/// What a validated [`CreatePinRequest`] pins.
+#[derive(Debug, PartialEq)]
+enum PinTarget {
+    Path(StorePath),
+    Evaluation(EvaluationId),
+}
+
+fn validate_pin(body: &CreatePinRequest, now: NaiveDateTime) -> Result<PinTarget, WebError> {
+    if check_index_name(&body.name).is_err() {
+        return Err(WebError::invalid_name("Pin Name"));
+    }
+    if body.expires_at.is_some_and(|at| at <= now) {
+        return Err(WebError::bad_request("expires_at must be in the future"));
+    }
+    match (&body.store_path, body.evaluation) {
+        (Some(path), None) => StorePath::parse(path.trim())
+            .map(PinTarget::Path)
+            .map_err(|e| WebError::bad_request(format!("Invalid store path: {}", e))),
+        (None, Some(evaluation)) => Ok(PinTarget::Evaluation(evaluation)),
+        _ => Err(WebError::bad_request(
+            "Exactly one of store_path or evaluation is required",
+        )),
+    }
+}
+
+/// An evaluation may only be pinned by a caller who can see its project, and
+/// only in a cache its project's organization is subscribed to, so a pin
+/// never reaches into another tenant's builds.
+async fn check_pinnable_evaluation(
+    state: &Arc<ServerState>,
+    user: &MUser,
+    api_key: Option<&ApiKeyContext>,
+    cache_id: CacheId,
+    evaluation_id: EvaluationId,
+) -> WebResult<()> {
+    let evaluation = EEvaluation::find_by_id(evaluation_id)
+        .one(&state.web_db)
+        .await?
+        .or_not_found("Evaluation")?;
+    let project_id = evaluation
+        .project
+        .ok_or_else(|| WebError::bad_request("Only project evaluations can be pinned"))?;
+    let project = EProject::find_by_id(project_id)
+        .one(&state.web_db)
+        .await?
+        .or_not_found("Project")?;
+    let organization = EOrganization::find_by_id(project.organization)
+        .one(&state.web_db)
+        .await?
+        .or_not_found("Evaluation")?;
+    if !organization.public && !is_org_member(state, user.id, organization.id, api_key).await? {
+        return Err(WebError::not_found("Evaluation"));
+    }
+    let subscribed = EOrganizationCache::find()
+        .filter(COrganizationCache::Organization.eq(project.organization))
+        .filter(COrganizationCache::Cache.eq(cache_id))
+        .one(&state.web_db)
+        .await?
+        .is_some();
+    if !subscribed {
+        return Err(WebError::bad_request(
+            "The evaluation's organization does not use this cache",
+        ));
+    }
+    Ok(())
+}
+
+pub async fn get_cache_pins(
+    state: State<Arc<ServerState>>,
+    Extension(MaybeUser(maybe_user)): Extension<MaybeUser>,
+    Extension(api_key): Extension<MaybeApiKey>,
+    Path(cache): Path<String>,
+) -> WebResult<Json<BaseResponse<Vec<CachePinItem>>>> {
+    let cache = load_cache(
+        &state,
+        Caller::from_option(&maybe_user),
+        api_key.as_ref(),
+        cache,
+        CacheAccess::Readable,
+    )
+    .await?;
+
+    let now = now();
+    let pins = ECachePin::find()
+        .filter(CCachePin::Cache.eq(cache.id))
+        .order_by_asc(CCachePin::Name)
+        .all(&state.web_db)
+        .await?
+        .into_iter()
+        .map(|p| CachePinItem::from_model(p, now))
+        .collect();
+
+    Ok(ok_json(pins))
+}
+
+pub async fn put_cache_pin(
+    state: State<Arc<ServerState>>,
+    info: RequestInfo,
+    Extension(user): Extension<MUser>,
+    Extension(api_key): Extension<MaybeApiKey>,
+    Path(cache): Path<String>,
+    Json(body): Json<CreatePinRequest>,
+) -> WebResult<Json<BaseResponse<CachePinId>>> {
+    let cache = load_cache(
+        &state,
+        Caller::User(&user),
+        api_key.as_ref(),
+        cache,
+        CacheAccess::Require {
+            permission: CachePermission::WriteStore,
+            reject_managed: false,
+        },
+    )
+    .await?;
+
+    let now = now();
+    let target = validate_pin(&body, now)?;
+
+    let existing = ECachePin::find()
+        .filter(CCachePin::Cache.eq(cache.id))
+        .filter(CCachePin::Name.eq(body.name.as_str()))
+        .one(&state.web_db)
+        .await?;
+    if existing.is_some() {
+        return Err(WebError::already_exists("Pin Name"));
+    }
+
+    let mut pin = MCachePin {
+        id: CachePinId::now_v7(),
+        cache: cache.id,
+        name: body.name.clone(),
+        managed: false,
+        created_by: Some(user.id),
+        created_at: now,
+        expires_at: body.expires_at,
+        ..Default::default()
+    };
+    match &target {
+        PinTarget::Path(path) => {
+            let cached = ECachedPath::find()
+                .filter(CCachedPath::Hash.eq(path.hash()))
+                .one(&state.web_db)
+                .await?
+                .or_not_found("Nar")?;
+            ECachedPathSignature::find()
+                .filter(CCachedPathSignature::CachedPath.eq(cached.id))
+                .filter(CCachedPathSignature::Cache.eq(cache.id))
+                .one(&state.web_db)
+                .await?
+                .or_not_found("Nar")?;
+            pin.hash = Some(cached.hash);
+            pin.package = Some(cached.package);
+        }
+        PinTarget::Evaluation(evaluation) => {
+            check_pinnable_evaluation(&state, &user, api_key.as_ref(), cache.id, *evaluation)
+                .await?;
+            pin.evaluation = Some(*evaluation);
+        }
+    }
+
+    let inserted = pin.into_active_model().insert(&state.web_db).await?;
+    audit_record(
+        &state.web_db,
+        Some(user.id),
+        events::CACHE_PIN_CREATE,
+        &info,
+        Some(serde_json::json!({
+            "cache_id": cache.id.to_string(),
+            "cache_name": cache.name,
+            "pin": inserted.name,
+            "store_path": inserted.store_path().map(|p| p.full()),
+            "evaluation": inserted.evaluation.map(|e| e.to_string()),
+            "expires_at": inserted.expires_at,
+        })),
+    )
+    .await;
+
+    Ok(ok_json(inserted.id))
+}
+
+pub async fn delete_cache_pin(
+    state: State<Arc<ServerState>>,
+    info: RequestInfo,
+    Extension(user): Extension<MUser>,
+    Extension(api_key): Extension<MaybeApiKey>,
+    Path((cache, pin)): Path<(String, String)>,
+) -> WebResult<Json<BaseResponse<String>>> {
+    let cache = load_cache(
+        &state,
+        Caller::User(&user),
+        api_key.as_ref(),
+        cache,
+        CacheAccess::Require {
+            permission: CachePermission::WriteStore,
+            reject_managed: false,
+        },
+    )
+    .await?;
+
+    let record = ECachePin::find()
+        .filter(CCachePin::Cache.eq(cache.id))
+        .filter(CCachePin::Name.eq(pin.as_str()))
+        .one(&state.web_db)
+        .await?
+        .or_not_found("Pin")?;
+    if record.managed {
+        return Err(WebError::forbidden(
+            "Pin is managed by declarative state and cannot be removed here",
+        ));
+    }
+
+    let active: ACachePin = record.into();
+    active.delete(&state.web_db).await?;
+    audit_record(
+        &state.web_db,
+        Some(user.id),
+        events::CACHE_PIN_DELETE,
+        &info,
+        Some(serde_json::json!({
+            "cache_id": cache.id.to_string(),
+            "cache_name": cache.name,
+            "pin": pin,
+        })),
+    )
+    .await;
+
+    Ok(ok_json("Pin removed".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(store_path: Option<&str>, evaluation: Option<EvaluationId>) -> CreatePinRequest {
+        CreatePinRequest {
+            name: "release".into(),
+            store_path: store_path.map(Into::into),
+            evaluation,
+            expires_at: None,
+        }
+    }
+
+    #[test]
+    fn validate_pin_requires_exactly_one_target() {
+        let now = now();
+        let eval = EvaluationId::now_v7();
+        assert!(validate_pin(&request(None, None), now).is_err());
+        assert!(validate_pin(&request(Some("/nix/store/aaaa-hello"), Some(eval)), now).is_err());
+        assert_eq!(
+            validate_pin(&request(None, Some(eval)), now).unwrap(),
+            PinTarget::Evaluation(eval)
+        );
+        assert_eq!(
+            validate_pin(&request(Some("/nix/store/aaaa-hello"), None), now).unwrap(),
+            PinTarget::Path(StorePath::from_parts("aaaa", "hello"))
+        );
+    }
+
+    #[test]
+    fn validate_pin_rejects_bad_name_path_and_past_expiry() {
+        let now = now();
+        let mut bad_name = request(Some("/nix/store/aaaa-hello"), None);
+        bad_name.name = "Not Valid".into();
+        assert!(validate_pin(&bad_name, now).is_err());
+
+        assert!(validate_pin(&request(Some("/nix/store/nodash"), None), now).is_err());
+
+        let mut expired = request(Some("/nix/store/aaaa-hello"), None);
+        expired.expires_at = Some(now - chrono::Duration::hours(1));
+        assert!(validate_pin(&expired, now).is_err());
+        expired.expires_at = Some(now + chrono::Duration::hours(1));
+        assert!(validate_pin(&expired, now).is_ok());
+    }
+}
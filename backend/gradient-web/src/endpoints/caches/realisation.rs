@@ -0,0 +1,178 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! `realisations/<drv hash>!<output>.doi`: where a floating content-addressed
+//! output ended up. Nix asks for it before building such a derivation, and
+//! only substitutes the output when the cache answers with a realisation
+//! signed by a trusted key.
+//!
+//! Realisations are recorded per organization, so a cache only answers with
+//! ones produced by organizations that write into it.
+
+use super::helpers::{CacheContext, cache_client_ip};
+use crate::client_ip::OptionalPeer;
+use crate::error::{WebError, WebResult};
+use crate::helpers::OptionExt;
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, HeaderValue, header};
+use axum::response::{IntoResponse, Response};
+use gradient_core::ServerState;
+use gradient_sources::CacheSigner;
+use gradient_types::*;
+use sea_orm::{ColumnTrait, Condition, EntityTrait, QueryFilter, QueryOrder};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Wire form of a Nix realisation. Fields are declared in key order, which
+/// is what Nix signs.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Realisation {
+    dependent_realisations: BTreeMap<String, String>,
+    id: String,
+    out_path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    signatures: Option<Vec<String>>,
+}
+
+impl Realisation {
+    fn new(row: &MRealisation) -> Self {
+        Self {
+            dependent_realisations: BTreeMap::new(),
+            id: row.id_string(),
+            out_path: row.out_path(),
+            signatures: None,
+        }
+    }
+
+    /// The realisation's JSON without `signatures`.
+    fn fingerprint(&self) -> String {
+        debug_assert!(self.signatures.is_none());
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+/// Split `sha256:<hash>!<output>.doi` into the derivation hash and output.
+fn parse_realisation_id(id: &str) -> Option<(&str, &str)> {
+    let (drv_hash, output) = id.strip_suffix(".doi")?.split_once('!')?;
+    let hash = drv_hash.strip_prefix("sha256:")?;
+    if hash.is_empty() || !hash.bytes().all(|b| b.is_ascii_hexdigit()) || output.is_empty() {
+        return None;
+    }
+    Some((drv_hash, output))
+}
+
+pub async fn realisation(
+    state: State<Arc<ServerState>>,
+    OptionalPeer(peer): OptionalPeer,
+    headers: HeaderMap,
+    Path((cache, id)): Path<(String, String)>,
+) -> WebResult<Response> {
+    let (drv_hash, output) = parse_realisation_id(&id).or_not_found("Realisation")?;
+
+    let client_ip = cache_client_ip(&state, &headers, peer);
+    let ctx = CacheContext::load(&state, &headers, client_ip, cache).await?;
+
+    let writers = gradient_db::cache_writer_organizations(&state.web_db, ctx.cache.id).await?;
+    let candidates = ERealisation::find()
+        .filter(CRealisation::Organization.is_in(writers))
+        .filter(CRealisation::DrvHash.eq(drv_hash))
+        .filter(CRealisation::OutputName.eq(output))
+        .order_by_asc(CRealisation::CreatedAt)
+        .all(&state.web_db)
+        .await?;
+
+    // Access gate: same as the narinfo — the output path must be signed into
+    // this cache, otherwise the realisation would point nowhere.
+    let mut served = None;
+    for candidate in candidates {
+        if signed_into_cache(&state, &candidate.hash, ctx.cache.id).await? {
+            served = Some(candidate);
+            break;
+        }
+    }
+    let row = served.or_not_found("Realisation")?;
+
+    let signer = CacheSigner::from_cache(
+        &state.config.secrets.crypt_secret_file,
+        &ctx.cache,
+        &state.config.server.serve_url,
+    )
+    .map_err(|e| WebError::internal(format!("Failed to load cache key: {}", e)))?;
+
+    let mut realisation = Realisation::new(&row);
+    let signature = signer.sign_fingerprint(&realisation.fingerprint());
+    realisation.signatures = Some(vec![signature]);
+
+    let mut response = axum::Json(realisation).into_response();
+    response.headers_mut().insert(
+        header::ACCESS_CONTROL_ALLOW_ORIGIN,
+        HeaderValue::from_static("*"),
+    );
+    Ok(response)
+}
+
+async fn signed_into_cache(state: &ServerState, hash: &str, cache: CacheId) -> WebResult<bool> {
+    let Some(cached_path) = ECachedPath::find()
+        .filter(CCachedPath::Hash.eq(hash))
+        .one(&state.web_db)
+        .await?
+    else {
+        return Ok(false);
+    };
+    Ok(ECachedPathSignature::find()
+        .filter(
+            Condition::all()
+                .add(CCachedPathSignature::CachedPath.eq(cached_path.id))
+                .add(CCachedPathSignature::Cache.eq(cache))
+                .add(CCachedPathSignature::Signature.is_not_null()),
+        )
+        .one(&state.web_db)
+        .await?
+        .is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_drv_output_ids() {
+        assert_eq!(
+            parse_realisation_id("sha256:0af3!out.doi"),
+            Some(("sha256:0af3", "out"))
+        );
+        assert_eq!(parse_realisation_id("sha256:0af3!out"), None);
+        assert_eq!(parse_realisation_id("sha256:0af3.doi"), None);
+        assert_eq!(parse_realisation_id("sha256:xyz!out.doi"), None);
+        assert_eq!(parse_realisation_id("md5:0af3!out.doi"), None);
+        assert_eq!(parse_realisation_id("sha256:0af3!.doi"), None);
+    }
+
+    #[test]
+    fn fingerprint_is_sorted_json_without_signatures() {
+        let row = MRealisation {
+            drv_hash: "sha256:0af3".into(),
+            output_name: "out".into(),
+            hash: "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".into(),
+            package: "ca-thing".into(),
+            ..Default::default()
+        };
+        let mut realisation = Realisation::new(&row);
+        assert_eq!(
+            realisation.fingerprint(),
+            r#"{"dependentRealisations":{},"id":"sha256:0af3!out","outPath":"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa-ca-thing"}"#
+        );
+
+        realisation.signatures = Some(vec!["key:sig".into()]);
+        assert!(
+            serde_json::to_string(&realisation)
+                .unwrap()
+                .ends_with(r#","signatures":["key:sig"]}"#)
+        );
+    }
+}
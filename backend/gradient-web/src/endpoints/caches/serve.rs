@@ -3,27 +3,41 @@
  * SPDX-License-Identifier: AGPL-3.0-only
  */
 
-use super::helpers::{CacheContext, cache_client_ip, fetch_nar_stream};
+use super::helpers::{CacheContext, CompressionQuery, cache_client_ip, fetch_nar_stream};
 use crate::client_ip::OptionalPeer;
 use crate::error::{WebError, WebResult};
 use axum::body::Body;
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use axum::http::{HeaderMap, HeaderValue, header};
 use axum::response::Response;
+use bytes::Bytes;
+use futures::StreamExt as _;
 use gradient_core::ServerState;
 use gradient_storage::nar_extract::{
     ExtractError, Extracted, extract_path_from_reader, nar_reader_from_stream,
 };
+use gradient_storage::nar_transcode::transcode_stream;
+use gradient_types::NarCompression;
 use std::sync::Arc;
 
+/// Content type and file extension of a directory archive.
+fn tar_encoding(compression: NarCompression) -> (&'static str, &'static str) {
+    match compression {
+        NarCompression::Zstd => ("application/zstd", "tar.zst"),
+        NarCompression::Xz => ("application/x-xz", "tar.xz"),
+        NarCompression::None => ("application/x-tar", "tar"),
+    }
+}
+
 pub async fn serve(
     state: State<Arc<ServerState>>,
     OptionalPeer(peer): OptionalPeer,
     headers: HeaderMap,
     Path((cache, hash, rel_path)): Path<(String, String, String)>,
+    Query(compression): Query<CompressionQuery>,
 ) -> WebResult<Response> {
     let client_ip = cache_client_ip(&state, &headers, peer);
-    let _ctx = CacheContext::load(&state, &headers, client_ip, cache).await?;
+    let ctx = CacheContext::load(&state, &headers, client_ip, cache).await?;
     let (_effective_hash, _size, stream) = fetch_nar_stream(&state, &hash).await?;
     let reader = nar_reader_from_stream(stream);
 
@@ -45,17 +59,23 @@ pub async fn serve(
                 .rsplit('/')
                 .find(|s| !s.is_empty())
                 .unwrap_or("dir");
-            let disp = format!("attachment; filename=\"{}.tar.zst\"", basename);
+            let compression = compression.or_cache(&ctx.cache);
+            let (content_type, extension) = tar_encoding(compression);
+            let disp = format!("attachment; filename=\"{}.{}\"", basename, extension);
+            let body = match compression {
+                NarCompression::Zstd => Body::from(tar_zst),
+                _ => {
+                    let archive = futures::stream::once(async move { Ok(Bytes::from(tar_zst)) });
+                    Body::from_stream(transcode_stream(archive.boxed(), compression))
+                }
+            };
             Response::builder()
-                .header(
-                    header::CONTENT_TYPE,
-                    HeaderValue::from_static("application/zstd"),
-                )
+                .header(header::CONTENT_TYPE, HeaderValue::from_static(content_type))
                 .header(
                     header::CONTENT_DISPOSITION,
                     HeaderValue::from_str(&disp).unwrap_or(HeaderValue::from_static("attachment")),
                 )
-                .body(Body::from(tar_zst))
+                .body(body)
                 .map_err(|e| WebError::internal(format!("Failed to build response: {}", e)))
         }
         Err(ExtractError::NotFound) => Err(WebError::not_found("Path")),
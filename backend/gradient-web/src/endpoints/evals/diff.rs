@@ -0,0 +1,33 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! `GET /evals/{evaluation}/diff`
+//!
+//! Attribute-level diff of an evaluation against the latest finished
+//! evaluation of its PR's target branch. See [`gradient_ci::eval_diff`].
+
+use crate::authorization::{MaybeApiKey, MaybeUser};
+use crate::error::WebResult;
+use crate::helpers::ok_json;
+use axum::extract::{Path, State};
+use axum::{Extension, Json};
+use gradient_ci::eval_diff::{EvalDiff, evaluation_diff};
+use gradient_core::ServerState;
+use gradient_types::*;
+use std::sync::Arc;
+
+use super::EvalAccessContext;
+
+pub async fn get_evaluation_diff(
+    state: State<Arc<ServerState>>,
+    Extension(MaybeUser(maybe_user)): Extension<MaybeUser>,
+    Extension(api_key): Extension<MaybeApiKey>,
+    Path(evaluation_id): Path<EvaluationId>,
+) -> WebResult<Json<BaseResponse<EvalDiff>>> {
+    let ctx = EvalAccessContext::load(&state, evaluation_id, &maybe_user, api_key.as_ref()).await?;
+    let diff = evaluation_diff(&state.web_db, &ctx.evaluation).await?;
+    Ok(ok_json(diff))
+}
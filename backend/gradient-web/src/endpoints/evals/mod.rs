@@ -6,12 +6,14 @@
 
 pub mod actions;
 pub mod artefacts;
+pub mod diff;
 pub mod log;
 pub mod query;
 pub mod types;
 
 pub use self::actions::*;
 pub use self::artefacts::*;
+pub use self::diff::*;
 pub use self::log::*;
 pub use self::query::*;
 pub use self::types::*;
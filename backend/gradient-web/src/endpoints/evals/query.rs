@@ -81,11 +81,22 @@ pub async fn get_evaluation(
                 .into_iter()
                 .map(|a| (a.derivation, a.status))
                 .collect();
+        let aggregate_status: HashMap<EntryPointId, gradient_entity::build::BuildStatus> =
+            if ep_rows.iter().any(|ep| ep.aggregate) {
+                gradient_db::aggregate_statuses(&state.web_db, evaluation.id)
+                    .await?
+                    .into_iter()
+                    .map(|a| (a.entry_point.id, a.status))
+                    .collect()
+            } else {
+                HashMap::new()
+            };
         ep_rows
             .into_iter()
             .map(|ep| {
-                let build_status = status_by_drv
-                    .get(&ep.derivation)
+                let build_status = aggregate_status
+                    .get(&ep.id)
+                    .or_else(|| status_by_drv.get(&ep.derivation))
                     .cloned()
                     .unwrap_or(gradient_entity::build::BuildStatus::Queued)
                     .for_api();
@@ -93,6 +104,7 @@ pub async fn get_evaluation(
                     id: ep.id,
                     eval: ep.eval,
                     build_status,
+                    aggregate: ep.aggregate,
                 }
             })
             .collect()
@@ -98,7 +98,10 @@ pub struct EvaluationTriggerSummary {
 pub struct EntryPointBrief {
     pub id: EntryPointId,
     pub eval: String,
+    /// For an aggregate, the combined status of its own build and its
+    /// constituents.
     pub build_status: gradient_entity::build::BuildStatus,
+    pub aggregate: bool,
 }
 
 #[derive(Serialize, Debug)]
@@ -30,6 +30,9 @@ pub(super) struct PullRequestApprovalContext {
     pub pr_author: Option<String>,
     pub is_fork: Option<bool>,
     pub sender: Option<String>,
+    /// PR target branch; stamped on the evaluation so the PR diff can find
+    /// the base evaluation to compare against.
+    pub base_branch: Option<String>,
 }
 
 /// Handle a GitHub App `check_run.requested_action` event. Verifies the sender
@@ -263,8 +266,9 @@ pub(super) async fn submit_pr_approval_review(
     }
 }
 
-/// Handle a `pull_request_review` webhook: a maintainer's native approving
-/// review releases an approval-gated run for the PR (#369). GitLab is a no-op.
+/// Handle a `pull_request_review` webhook (Bitbucket: PR approval): a
+/// maintainer's native approving review releases an approval-gated run for the
+/// PR (#369). GitLab is a no-op.
 pub(super) async fn handle_pull_request_review(
     state: &Arc<ServerState>,
     forge: ForgeType,
@@ -275,6 +279,8 @@ pub(super) async fn handle_pull_request_review(
     let parsed = match forge {
         ForgeType::GitHub => ParsedPullRequestReviewEvent::from_github(body),
         ForgeType::Gitea | ForgeType::Forgejo => ParsedPullRequestReviewEvent::from_gitea(body),
+        ForgeType::Bitbucket => ParsedPullRequestReviewEvent::from_bitbucket(body),
+        ForgeType::BitbucketServer => ParsedPullRequestReviewEvent::from_bitbucket_server(body),
         ForgeType::GitLab => return,
     };
     let Some(review) = parsed else {
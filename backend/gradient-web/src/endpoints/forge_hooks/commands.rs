@@ -4,7 +4,8 @@
  * SPDX-License-Identifier: AGPL-3.0-only
  */
 
-//! `/gradient` PR-comment command dispatch (Gitea/Forgejo/GitLab + GitHub App).
+//! `/gradient` PR-comment command dispatch (Gitea/Forgejo/GitLab/Bitbucket +
+//! GitHub App).
 
 use super::approval::{
     PullRequestApprovalContext, dispatch_approval_granted, sender_is_trusted,
@@ -13,6 +14,7 @@ use super::approval::{
 use super::fanout::trigger_pr_for_integration;
 use super::installation::resolve_github_app_targets;
 use super::payloads::CommentPayload;
+use super::pr_commands::{cancel_pr_evaluations, post_pr_status, retry_failed_pr_evaluations};
 use gradient_ci::{
     find_approval_gated_eval, set_evaluation_source_comment, unpark_approval,
     unpark_approval_with_wildcard,
@@ -28,7 +30,7 @@ use std::sync::Arc;
 use tracing::{debug, info, warn};
 
 /// The fields `handle_issue_comment` consumes, normalized across the GitHub /
-/// Gitea comment payload and the GitLab Note Hook payload.
+/// Gitea comment payload, the GitLab Note Hook and the Bitbucket comment events.
 struct CommentEvent {
     comment_body: String,
     pr_number: Option<u64>,
@@ -60,6 +62,29 @@ fn parse_comment_event(forge: ForgeType, body: &[u8]) -> Option<CommentEvent> {
                 owner_repo: payload.project.and_then(|p| p.path_with_namespace),
             })
         }
+        // Only PR comment events are routed here, so no action check.
+        ForgeType::Bitbucket => {
+            let comment = payload.comment.unwrap_or_default();
+            Some(CommentEvent {
+                comment_id: comment.id,
+                comment_body: comment.content.and_then(|c| c.raw).unwrap_or_default(),
+                pr_number: payload.pullrequest.and_then(|p| p.id),
+                sender_login: payload.actor.and_then(|a| a.nickname),
+                owner_repo: payload.repository.and_then(|r| r.full_name),
+            })
+        }
+        ForgeType::BitbucketServer => {
+            let comment = payload.comment.unwrap_or_default();
+            let pr = payload.server_pull_request?;
+            let repo = pr.to_ref.repository;
+            Some(CommentEvent {
+                comment_id: comment.id,
+                comment_body: comment.text.unwrap_or_default(),
+                pr_number: pr.id,
+                sender_login: payload.actor.and_then(|a| a.name),
+                owner_repo: Some(format!("{}/{}", repo.project.key, repo.slug)),
+            })
+        }
         _ => {
             // GitHub and Gitea both use `action == "created"`.
             if payload.action.as_deref() != Some("created") {
@@ -80,10 +105,11 @@ fn parse_comment_event(forge: ForgeType, body: &[u8]) -> Option<CommentEvent> {
     }
 }
 
-/// Handle a `/gradient run [wildcard]` or `/gradient approve` comment on a PR.
-/// Both commands are maintainer-only. `integration_id` is `Some` for the
-/// per-integration routes and `None` for the shared GitHub App route (where the
-/// integration is resolved from `installation.id`).
+/// Handle a `/gradient <subcommand>` comment on a PR (see
+/// [`parse_gradient_command`]). All commands are maintainer-only.
+/// `integration_id` is `Some` for the per-integration routes and `None` for the
+/// shared GitHub App route (where the integration is resolved from
+/// `installation.id`).
 pub(super) async fn handle_issue_comment(
     state: &Arc<ServerState>,
     scheduler: &Arc<Scheduler>,
@@ -226,22 +252,23 @@ async fn resolve_wildcard_override(
 }
 
 /// Per-integration inputs for a `/gradient` comment dispatch.
-struct CommentDispatch<'a> {
-    state: &'a Arc<ServerState>,
-    scheduler: &'a Arc<Scheduler>,
+pub(super) struct CommentDispatch<'a> {
+    pub(super) state: &'a Arc<ServerState>,
+    pub(super) scheduler: &'a Arc<Scheduler>,
     integration_id: IntegrationId,
     cmd: &'a GradientCommand,
-    owner: &'a str,
-    repo: &'a str,
-    sender: &'a str,
-    pr_number: u64,
+    pub(super) owner: &'a str,
+    pub(super) repo: &'a str,
+    pub(super) sender: &'a str,
+    pub(super) pr_number: u64,
     wildcard_override: &'a Option<String>,
-    reaction_target: &'a Option<gradient_ci::ReactionTarget>,
+    pub(super) reaction_target: &'a Option<gradient_ci::ReactionTarget>,
 }
 
-/// Run the maintainer trust probe once for the integration, then try the
-/// unpark path and fall back to a fresh `/gradient run`. Returns whether any
-/// action fired.
+/// Run the maintainer trust probe once for the integration, then dispatch the
+/// command: `cancel`, `retry-failed` and `status` act on the PR's existing
+/// evaluations; `run`/`approve` try the unpark path and `run` falls back to a
+/// fresh evaluation. Returns whether any action fired.
 async fn handle_comment_for_integration(ctx: &CommentDispatch<'_>) -> bool {
     let project_ids = match active_project_ids_for_integration(ctx.state, ctx.integration_id).await
     {
@@ -280,6 +307,15 @@ async fn handle_comment_for_integration(ctx: &CommentDispatch<'_>) -> bool {
         return false;
     }
 
+    match ctx.cmd {
+        GradientCommand::Cancel => return cancel_pr_evaluations(ctx, &project_ids).await,
+        GradientCommand::RetryFailed => {
+            return retry_failed_pr_evaluations(ctx, &project_ids).await;
+        }
+        GradientCommand::Status => return post_pr_status(ctx, &project_ids).await,
+        GradientCommand::Run { .. } | GradientCommand::Approve => {}
+    }
+
     if unpark_existing_approvals(ctx, &project_ids).await {
         return true;
     }
@@ -373,13 +409,17 @@ async fn run_fresh_evaluation(ctx: &CommentDispatch<'_>, project_ids: &[ProjectI
         pr_author: None,
         is_fork: Some(snapshot.is_fork),
         sender: Some(ctx.sender.to_string()),
+        base_branch: snapshot.base_branch.clone(),
     };
+    // The eval diff compares against `base_branch`; the comment-triggered
+    // source_comment replaces the fan-out's PR stamp, so carry it here too.
     let source_comment_json = ctx.reaction_target.as_ref().map(|t| {
         serde_json::json!({
             "owner": t.owner,
             "repo": t.repo,
             "pr_number": t.pr_number,
             "comment_id": t.comment_id,
+            "base_branch": snapshot.base_branch,
         })
     });
     let event_repo_urls = [format!("https://github.com/{}/{}", ctx.owner, ctx.repo)];
@@ -422,7 +462,7 @@ async fn run_fresh_evaluation(ctx: &CommentDispatch<'_>, project_ids: &[ProjectI
 
 /// Post a reaction on a PR/MR comment via the given project's reporter.
 /// Best-effort: failures are logged and swallowed.
-async fn fire_reaction_via_project(
+pub(super) async fn fire_reaction_via_project(
     state: &Arc<ServerState>,
     project_id: ProjectId,
     target: &gradient_ci::ReactionTarget,
@@ -537,11 +577,19 @@ pub(super) enum GradientCommand {
     Run { wildcard: Option<String> },
     /// `/gradient approve` - clear the approval gate for this PR (no-op if none).
     Approve,
+    /// `/gradient cancel` - abort the PR's running evaluations.
+    Cancel,
+    /// `/gradient retry-failed` - restart only the failed builds of the PR's
+    /// latest finished evaluation.
+    RetryFailed,
+    /// `/gradient status` - reply with a per-attribute result table.
+    Status,
 }
 
 /// Lift a `/gradient <subcommand>` from a PR comment. The command must be on its
 /// own line; blank lines and `> …` quote-reply lines are skipped, any other
-/// prose disqualifies the comment. Subcommands: `run [wildcard]` and `approve`.
+/// prose disqualifies the comment. Subcommands: `run [wildcard]`, `approve`,
+/// `cancel`, `retry-failed` and `status`; only `run` takes an argument.
 pub(super) fn parse_gradient_command(body: &str) -> Option<GradientCommand> {
     const PREFIX: &str = "/gradient";
 
@@ -575,14 +623,21 @@ pub(super) fn parse_gradient_command(body: &str) -> Option<GradientCommand> {
             });
             continue;
         }
-        if verb.eq_ignore_ascii_case("approve") {
-            if !arg.is_empty() {
-                return None;
-            }
-            found = Some(GradientCommand::Approve);
-            continue;
+        let cmd = if verb.eq_ignore_ascii_case("approve") {
+            GradientCommand::Approve
+        } else if verb.eq_ignore_ascii_case("cancel") {
+            GradientCommand::Cancel
+        } else if verb.eq_ignore_ascii_case("retry-failed") {
+            GradientCommand::RetryFailed
+        } else if verb.eq_ignore_ascii_case("status") {
+            GradientCommand::Status
+        } else {
+            return None;
+        };
+        if !arg.is_empty() {
+            return None;
         }
-        return None;
+        found = Some(cmd);
     }
     found
 }
@@ -699,6 +754,47 @@ mod tests {
         assert_eq!(e.comment_id, Some(123));
     }
 
+    #[test]
+    fn parse_comment_event_bitbucket_cloud() {
+        let e = event_of(
+            ForgeType::Bitbucket,
+            serde_json::json!({
+                "comment": { "id": 314, "content": { "raw": "/gradient run" } },
+                "pullrequest": { "id": 12 },
+                "actor": { "nickname": "bb-user", "display_name": "BB User" },
+                "repository": { "full_name": "acme/widgets" },
+            }),
+        );
+        assert_eq!(e.comment_body, "/gradient run");
+        assert_eq!(e.pr_number, Some(12));
+        assert_eq!(e.sender_login.as_deref(), Some("bb-user"));
+        assert_eq!(e.owner_repo.as_deref(), Some("acme/widgets"));
+        assert_eq!(e.comment_id, Some(314));
+    }
+
+    #[test]
+    fn parse_comment_event_bitbucket_server() {
+        let e = event_of(
+            ForgeType::BitbucketServer,
+            serde_json::json!({
+                "eventKey": "pr:comment:added",
+                "comment": { "id": 21, "text": "/gradient approve" },
+                "pullRequest": {
+                    "id": 3,
+                    "toRef": {
+                        "repository": { "slug": "widgets", "project": { "key": "PROJ" } },
+                    },
+                },
+                "actor": { "name": "jdoe", "displayName": "J. Doe" },
+            }),
+        );
+        assert_eq!(e.comment_body, "/gradient approve");
+        assert_eq!(e.pr_number, Some(3));
+        assert_eq!(e.sender_login.as_deref(), Some("jdoe"));
+        assert_eq!(e.owner_repo.as_deref(), Some("PROJ/widgets"));
+        assert_eq!(e.comment_id, Some(21));
+    }
+
     #[test]
     fn parse_comment_event_github_ignores_non_created_action() {
         let body = serde_json::to_vec(&serde_json::json!({
@@ -809,6 +905,30 @@ mod tests {
         assert!(parse_gradient_command("/gradient approve packages.*").is_none());
     }
 
+    #[test]
+    fn parse_gradient_cancel_retry_failed_status() {
+        assert_eq!(
+            parse_gradient_command("/gradient cancel"),
+            Some(GradientCommand::Cancel)
+        );
+        assert_eq!(
+            parse_gradient_command("/Gradient RETRY-FAILED"),
+            Some(GradientCommand::RetryFailed)
+        );
+        assert_eq!(
+            parse_gradient_command("> is it green?\n\n/gradient status"),
+            Some(GradientCommand::Status)
+        );
+    }
+
+    #[test]
+    fn parse_gradient_eval_commands_reject_trailing_args() {
+        assert!(parse_gradient_command("/gradient cancel now").is_none());
+        assert!(parse_gradient_command("/gradient retry-failed packages.*").is_none());
+        assert!(parse_gradient_command("/gradient status all").is_none());
+        assert!(parse_gradient_command("/gradient retry").is_none());
+    }
+
     #[test]
     fn parse_gradient_rejects_unknown_subcommand() {
         assert!(parse_gradient_command("/gradient yolo").is_none());
@@ -11,12 +11,16 @@ use super::installation::event_repo_matches_project;
 use super::response::{QueuedEvaluation, SkippedProject, WebhookTriggerOutcome};
 use gradient_ci::{ApplyInput, ApplyOutcome, ApprovalInfo, apply_trigger, parse_owner_repo};
 use gradient_core::ServerState;
+use gradient_entity::evaluation::EvaluationStatus;
 use gradient_entity::project_trigger as ept;
+use gradient_forge::ParsedMergeQueueEvent;
 use gradient_scheduler::Scheduler;
 use gradient_types::triggers::{TriggerConfig, TriggerType};
 use gradient_types::*;
 use sea_orm::ActiveValue::Set;
-use sea_orm::{ActiveModelTrait, DbBackend, EntityTrait, Statement, Value};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DbBackend, EntityTrait, QueryFilter, Statement, Value,
+};
 use std::sync::Arc;
 use tracing::{info, warn};
 
@@ -76,6 +80,10 @@ pub(super) async fn trigger_push_for_integration(
         false,
         None,
         None,
+        match ref_kind {
+            PushRefKind::Branch(name) => Some(name.to_owned()),
+            PushRefKind::Tag(_) => None,
+        },
     )
     .await
 }
@@ -139,6 +147,7 @@ pub(super) async fn trigger_pr_for_integration(
         manual,
         wildcard_override,
         source_comment,
+        branch.map(str::to_owned),
     )
     .await
 }
@@ -192,10 +201,112 @@ pub(super) async fn trigger_release_for_integration(
         false,
         None,
         None,
+        None,
+    )
+    .await
+}
+
+/// Fire `reporter_merge_queue` triggers for a queue entry asking for checks.
+/// The evaluation runs on the entry's temporary merge commit and records the
+/// queue ref as its branch.
+pub(super) async fn trigger_merge_queue_for_integration(
+    state: &Arc<ServerState>,
+    scheduler: &Arc<Scheduler>,
+    integration_id: IntegrationId,
+    event: &ParsedMergeQueueEvent,
+) -> WebhookTriggerOutcome {
+    let base_branch = event.base_branch.clone();
+    fan_out_triggers(
+        state,
+        scheduler,
+        integration_id,
+        &event.repository_urls,
+        TriggerType::ReporterMergeQueue,
+        event.commit_hash.clone(),
+        event.commit_message.clone(),
+        event.author_name.clone(),
+        |cfg| match cfg {
+            TriggerConfig::ReporterMergeQueue { branches, .. } => {
+                let matches = match base_branch.as_deref() {
+                    Some(b) => glob_matches(branches, b),
+                    None => branches.is_empty(),
+                };
+                if matches {
+                    FilterResult::Fire
+                } else {
+                    FilterResult::SkipFilter
+                }
+            }
+            _ => FilterResult::Skip,
+        },
+        None,
+        None,
+        false,
+        None,
+        None,
+        Some(event.head_ref.clone()),
     )
     .await
 }
 
+/// Abort the running evaluations of a queue entry that left the queue (merged,
+/// ejected, or its pipeline cancelled). Returns how many were aborted.
+pub(super) async fn abort_merge_queue_for_integration(
+    state: &Arc<ServerState>,
+    scheduler: &Arc<Scheduler>,
+    integration_id: IntegrationId,
+    event: &ParsedMergeQueueEvent,
+) -> usize {
+    let triggers = match load_active_triggers_for_integration(
+        state,
+        integration_id,
+        TriggerType::ReporterMergeQueue,
+    )
+    .await
+    {
+        Ok(rows) => rows,
+        Err(e) => {
+            warn!(error = %e, "load merge-queue triggers for integration");
+            return 0;
+        }
+    };
+
+    let mut aborted = 0;
+    for trig in triggers {
+        let Some(project) = load_trigger_project(state, &trig).await else {
+            continue;
+        };
+        if !event_repo_matches_project(&event.repository_urls, &project.repository) {
+            continue;
+        }
+        let evals = match EEvaluation::find()
+            .filter(CEvaluation::Project.eq(project.id))
+            .filter(CEvaluation::Trigger.eq(trig.id))
+            .filter(CEvaluation::Branch.eq(event.head_ref.as_str()))
+            .filter(CEvaluation::Status.is_in(EvaluationStatus::ACTIVE))
+            .all(&state.web_db)
+            .await
+        {
+            Ok(rows) => rows,
+            Err(e) => {
+                warn!(error = %e, project_id = %project.id, "load merge-queue evaluations");
+                continue;
+            }
+        };
+        for eval in evals {
+            info!(
+                project_id = %project.id,
+                evaluation_id = %eval.id,
+                head_ref = %event.head_ref,
+                "merge-queue entry left the queue; aborting its evaluation"
+            );
+            scheduler.abort_evaluation(eval).await;
+            aborted += 1;
+        }
+    }
+    aborted
+}
+
 enum FilterResult {
     /// Proceed to fire `apply_trigger` (push / release / time / polling).
     Fire,
@@ -226,6 +337,7 @@ async fn fan_out_triggers<F>(
     manual: bool,
     wildcard_override: Option<String>,
     source_comment: Option<serde_json::Value>,
+    branch: Option<String>,
 ) -> WebhookTriggerOutcome
 where
     F: Fn(&TriggerConfig) -> FilterResult,
@@ -239,12 +351,17 @@ where
             }
         };
 
-    // Persist PR number/author on the evaluation for every PR trigger (#391);
-    // a comment-triggered run already carries a richer source_comment.
+    // Persist PR number/author/base on the evaluation for every PR trigger
+    // (#391); a comment-triggered run already carries a richer source_comment.
     let source_comment = source_comment.or_else(|| {
         approval_ctx.as_ref().and_then(|c| {
-            c.pr_number
-                .map(|n| serde_json::json!({ "pr_number": n, "pr_author": c.pr_author }))
+            c.pr_number.map(|n| {
+                serde_json::json!({
+                    "pr_number": n,
+                    "pr_author": c.pr_author,
+                    "base_branch": c.base_branch,
+                })
+            })
         })
     });
 
@@ -298,6 +415,7 @@ where
             repository_override: repository_override.clone(),
             wildcard_override: wildcard_override.clone(),
             source_comment: source_comment.clone(),
+            branch: branch.clone(),
             instance_max_storage_gb: state.config.storage.max_storage_gb,
         };
 
@@ -542,6 +660,7 @@ mod tests {
             pr_author: Some("external".into()),
             is_fork: Some(true),
             sender: Some("maintainer".into()),
+            base_branch: Some("main".into()),
         }
     }
 
@@ -154,8 +154,16 @@ pub(super) fn normalize_repo_url(url: &str) -> String {
     s.to_string()
 }
 
+/// Host-agnostic `owner/repo`, lowercased. Bitbucket Server HTTP clone URLs
+/// (`/scm/proj/repo.git`) collapse to `proj/repo`, the form its comment and
+/// review payloads report.
 fn repo_identity(url: &str) -> Option<String> {
-    parse_owner_repo(url).map(|(owner, repo)| format!("{owner}/{repo}").to_ascii_lowercase())
+    let (owner, repo) = parse_owner_repo(url)?;
+    let path = match repo.split_once('/') {
+        Some(_) if owner.eq_ignore_ascii_case("scm") => repo,
+        _ => format!("{owner}/{repo}"),
+    };
+    Some(path.to_ascii_lowercase())
 }
 
 /// Whether a webhook event from `event_repo_urls` targets a project tracking
@@ -289,6 +297,15 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn event_repo_matches_bitbucket_server_scm_clone_url() {
+        let event = ["https://github.com/PROJ/widgets".to_string()];
+        assert!(event_repo_matches_project(
+            &event,
+            "https://bitbucket.example.com/scm/proj/widgets.git"
+        ));
+    }
+
     #[test]
     fn event_repo_empty_urls_match_every_project() {
         assert!(event_repo_matches_project(
@@ -12,13 +12,14 @@
 //! | Endpoint                                              | Forge          | Auth method             |
 //! |-------------------------------------------------------|----------------|-------------------------|
 //! | `POST /hooks/github`                                  | GitHub App     | `X-Hub-Signature-256`   |
-//! | `POST /hooks/{forge}/{org}/{integration_name}`        | Gitea/Forgejo/GitLab | per-integration secret |
+//! | `POST /hooks/{forge}/{org}/{integration_name}`        | Gitea/Forgejo/GitLab/Bitbucket | per-integration secret |
 
 mod approval;
 mod commands;
 mod fanout;
 mod installation;
 mod payloads;
+mod pr_commands;
 mod response;
 
 pub use response::{QueuedEvaluation, SkippedProject, WebhookResponse, WebhookTriggerOutcome};
@@ -47,10 +48,13 @@ use crate::error::{ErrorCode, WebError, WebResult};
 use crate::helpers::ok_json;
 
 use fanout::{
-    PushRefKind, trigger_pr_for_integration, trigger_push_for_integration,
-    trigger_release_for_integration,
+    PushRefKind, abort_merge_queue_for_integration, trigger_merge_queue_for_integration,
+    trigger_pr_for_integration, trigger_push_for_integration, trigger_release_for_integration,
+};
+use gradient_forge::{
+    AbbreviatedCommit, MergeQueueOutcome, ParsedMergeQueueEvent, ParsedPullRequestEvent,
+    ParsedPushEvent, ParsedReleaseEvent, PushOutcome,
 };
-use gradient_forge::{ParsedPullRequestEvent, ParsedPushEvent, ParsedReleaseEvent, PushOutcome};
 use installation::{handle_github_installation, resolve_github_app_targets};
 
 // ── GitHub App webhook ─────────────────────────────────────────────────────
@@ -151,6 +155,12 @@ pub async fn github_app_webhook(
                 skipped: outcome.skipped,
             }
         }
+        "merge_group" => match ParsedMergeQueueEvent::from_github(&body) {
+            None => return Err(WebError::bad_request("malformed webhook payload")),
+            Some(outcome) => {
+                dispatch_github_app_merge_group(&state, &scheduler, outcome, &body, client_ip).await
+            }
+        },
         "installation" | "installation_repositories" => {
             handle_github_installation(&state, &body).await;
             WebhookResponse::empty(&event)
@@ -305,6 +315,7 @@ fn approval_context_from(parsed: &ParsedPullRequestEvent) -> approval::PullReque
         pr_author: parsed.pr_author.clone(),
         is_fork: parsed.is_fork,
         sender: parsed.sender.clone(),
+        base_branch: parsed.base_branch.clone(),
     }
 }
 
@@ -350,12 +361,110 @@ async fn dispatch_github_app_release(
     combined
 }
 
+/// Route a `merge_group` event to the integrations bound to its installation:
+/// `checks_requested` fires merge-queue triggers, `destroyed` aborts the
+/// entry's running evaluations.
+async fn dispatch_github_app_merge_group(
+    state: &Arc<ServerState>,
+    scheduler: &Arc<Scheduler>,
+    outcome: MergeQueueOutcome,
+    body: &[u8],
+    client_ip: IpAddr,
+) -> WebhookResponse {
+    let event = match &outcome {
+        MergeQueueOutcome::ChecksRequested(e) | MergeQueueOutcome::Destroyed(e) => e,
+        MergeQueueOutcome::Ignored => return WebhookResponse::empty("merge_group"),
+    };
+    let Some(installation_id) = github_installation_id_from_body(body) else {
+        warn!("GitHub App merge_group: missing installation_id");
+        return WebhookResponse::empty("merge_group");
+    };
+    let targets =
+        resolve_github_app_targets(state, installation_id, &event.repository_urls, client_ip).await;
+    if targets.is_empty() {
+        warn!(
+            installation_id,
+            urls = ?event.repository_urls,
+            "GitHub App merge_group: no integration owns a project matching the webhook's repository"
+        );
+        return WebhookResponse::empty("merge_group");
+    }
+    let mut combined = WebhookTriggerOutcome::default();
+    for integration_id in targets {
+        let result = merge_queue_for_integration(state, scheduler, integration_id, &outcome).await;
+        combined.projects_scanned += result.projects_scanned;
+        combined.queued.extend(result.queued);
+        combined.skipped.extend(result.skipped);
+    }
+    WebhookResponse {
+        event: "merge_group".to_string(),
+        repository_urls: event.repository_urls.clone(),
+        projects_scanned: combined.projects_scanned,
+        queued: combined.queued,
+        skipped: combined.skipped,
+    }
+}
+
+/// Fire or abort one integration's merge-queue triggers for a parsed queue event.
+async fn merge_queue_for_integration(
+    state: &Arc<ServerState>,
+    scheduler: &Arc<Scheduler>,
+    integration_id: IntegrationId,
+    outcome: &MergeQueueOutcome,
+) -> WebhookTriggerOutcome {
+    match outcome {
+        MergeQueueOutcome::ChecksRequested(event) => {
+            trigger_merge_queue_for_integration(state, scheduler, integration_id, event).await
+        }
+        MergeQueueOutcome::Destroyed(event) => {
+            abort_merge_queue_for_integration(state, scheduler, integration_id, event).await;
+            WebhookTriggerOutcome::default()
+        }
+        MergeQueueOutcome::Ignored => WebhookTriggerOutcome::default(),
+    }
+}
+
+/// Expand an abbreviated PR head hash (Bitbucket Cloud) through the reporter
+/// of the first project the integration triggers that can resolve it.
+async fn expand_abbreviated_head(
+    state: &Arc<ServerState>,
+    integration_id: IntegrationId,
+    head: &AbbreviatedCommit,
+) -> Option<Vec<u8>> {
+    let project_ids = commands::active_project_ids_for_integration(state, integration_id)
+        .await
+        .ok()?;
+    for project_id in project_ids {
+        let reporter =
+            match gradient_ci::actions::reporter_for_project(&state.ci(), project_id).await {
+                Ok(Some(r)) => r,
+                _ => continue,
+            };
+        match reporter
+            .expand_commit_hash(&head.repo_full_name, &head.hash)
+            .await
+        {
+            Ok(Some(full)) if full.len() == 40 && full.starts_with(&head.hash) => {
+                if let Ok(bytes) = hex::decode(&full) {
+                    return Some(bytes);
+                }
+            }
+            Ok(_) => {}
+            Err(e) => {
+                warn!(error = %e, %project_id, "PR webhook: expanding head commit failed, trying next project");
+            }
+        }
+    }
+    None
+}
+
 // ── Generic forge webhook ──────────────────────────────────────────────────
 
 /// `POST /api/v1/hooks/{forge}/{org_name}/{integration_name}` - receives push,
 /// pull-request, and release events from a named inbound integration.
 ///
-/// The `forge` path segment is one of: `gitea`, `forgejo`, `gitlab`.
+/// The `forge` path segment is one of: `gitea`, `forgejo`, `gitlab`,
+/// `bitbucket`, `bitbucket-server`.
 pub async fn forge_webhook(
     State(state): State<Arc<ServerState>>,
     Extension(scheduler): Extension<Arc<Scheduler>>,
@@ -479,9 +588,26 @@ pub async fn forge_webhook(
             }
         },
         WebhookEventKind::PullRequest => {
-            let Some(parsed) = provider.parse_pull_request_event(&body) else {
+            let Some(mut parsed) = provider.parse_pull_request_event(&body) else {
                 return Err(WebError::bad_request("malformed webhook payload"));
             };
+            if let Some(action) = provider.pull_request_action(raw_event) {
+                parsed.action = action.to_string();
+            }
+            if let Some(head) = parsed.abbreviated_head.take() {
+                let Some(hash) = expand_abbreviated_head(&state, integration_id, &head).await
+                else {
+                    warn!(
+                        org = %org_name,
+                        forge = %forge,
+                        integration = %integration_name,
+                        hash = %head.hash,
+                        "PR webhook: could not expand abbreviated head commit; no project has a reporter for this repository",
+                    );
+                    return Ok(ok_json(WebhookResponse::empty("pull_request")));
+                };
+                parsed.commit_hash = hash;
+            }
             let urls = parsed.repository_urls.clone();
             let approval_ctx = approval_context_from(&parsed);
             let head_clone = parsed.head_repo_clone_url.clone();
@@ -567,6 +693,26 @@ pub async fn forge_webhook(
             .await;
             WebhookResponse::empty("review")
         }
+        WebhookEventKind::MergeQueue => {
+            let Some(outcome) = provider.parse_merge_queue_event(&body) else {
+                return Err(WebError::bad_request("malformed webhook payload"));
+            };
+            let urls = match &outcome {
+                MergeQueueOutcome::ChecksRequested(e) | MergeQueueOutcome::Destroyed(e) => {
+                    e.repository_urls.clone()
+                }
+                MergeQueueOutcome::Ignored => Vec::new(),
+            };
+            let outcome =
+                merge_queue_for_integration(&state, &scheduler, integration_id, &outcome).await;
+            WebhookResponse {
+                event: "merge_queue".to_string(),
+                repository_urls: urls,
+                projects_scanned: outcome.projects_scanned,
+                queued: outcome.queued,
+                skipped: outcome.skipped,
+            }
+        }
         WebhookEventKind::Unknown(name) => WebhookResponse::empty(&name),
     };
 
@@ -90,7 +90,8 @@ pub(super) struct CheckRunSender<'a> {
     pub(super) login: &'a str,
 }
 
-// Issue/PR comment events (GitHub & Gitea) plus the GitLab Note Hook variant.
+// Issue/PR comment events (GitHub & Gitea) plus the GitLab Note Hook and
+// Bitbucket Cloud/Server `comment_created` / `pr:comment:added` variants.
 
 #[derive(Deserialize)]
 pub(super) struct CommentPayload {
@@ -114,6 +115,12 @@ pub(super) struct CommentPayload {
     pub(super) project: Option<GitlabNoteProject>,
     #[serde(default)]
     pub(super) merge_request: Option<GitlabNoteMr>,
+    #[serde(default)]
+    pub(super) pullrequest: Option<BitbucketCommentPr>,
+    #[serde(default, rename = "pullRequest")]
+    pub(super) server_pull_request: Option<BitbucketServerCommentPr>,
+    #[serde(default)]
+    pub(super) actor: Option<CommentSender>,
 }
 
 #[derive(Deserialize, Default)]
@@ -121,6 +128,12 @@ pub(super) struct CommentBody {
     pub(super) body: Option<String>,
     #[serde(default)]
     pub(super) id: Option<i64>,
+    /// Bitbucket Cloud comment text.
+    #[serde(default)]
+    pub(super) content: Option<BitbucketCommentContent>,
+    /// Bitbucket Server comment text.
+    #[serde(default)]
+    pub(super) text: Option<String>,
 }
 
 #[derive(Deserialize, Default)]
@@ -134,6 +147,12 @@ pub(super) struct CommentSender {
     pub(super) login: Option<String>,
     #[serde(default)]
     pub(super) username: Option<String>,
+    /// Bitbucket Cloud account name.
+    #[serde(default)]
+    pub(super) nickname: Option<String>,
+    /// Bitbucket Server user slug.
+    #[serde(default)]
+    pub(super) name: Option<String>,
 }
 
 #[derive(Deserialize, Default)]
@@ -164,6 +183,42 @@ pub(super) struct GitlabNoteMr {
     pub(super) iid: Option<u64>,
 }
 
+#[derive(Deserialize, Default)]
+pub(super) struct BitbucketCommentContent {
+    #[serde(default)]
+    pub(super) raw: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+pub(super) struct BitbucketCommentPr {
+    #[serde(default)]
+    pub(super) id: Option<u64>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct BitbucketServerCommentPr {
+    #[serde(default)]
+    pub(super) id: Option<u64>,
+    #[serde(rename = "toRef")]
+    pub(super) to_ref: BitbucketServerCommentRef,
+}
+
+#[derive(Deserialize)]
+pub(super) struct BitbucketServerCommentRef {
+    pub(super) repository: BitbucketServerCommentRepo,
+}
+
+#[derive(Deserialize)]
+pub(super) struct BitbucketServerCommentRepo {
+    pub(super) slug: String,
+    pub(super) project: BitbucketServerCommentProject,
+}
+
+#[derive(Deserialize)]
+pub(super) struct BitbucketServerCommentProject {
+    pub(super) key: String,
+}
+
 #[cfg(test)]
 mod tests {
     use super::GitHubInstallationPayload;
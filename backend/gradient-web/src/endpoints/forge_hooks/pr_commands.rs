@@ -0,0 +1,434 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! `/gradient cancel`, `/gradient retry-failed` and `/gradient status`: the PR
+//! comment commands that act on evaluations already created for the PR.
+//!
+//! A PR evaluation is found through `evaluation.source_comment->>'pr_number'`,
+//! which every PR-triggered evaluation carries. PR numbers are per repository,
+//! so only projects tracking the commented repository are searched.
+
+use super::commands::{CommentDispatch, fire_reaction_via_project};
+use super::installation::event_repo_matches_project;
+use gradient_core::ServerState;
+use gradient_entity::build::BuildStatus;
+use gradient_types::*;
+use sea_orm::sea_query::Expr;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// Abort every running evaluation of this PR. Reacts 👍 on success, otherwise
+/// replies that nothing was running.
+pub(super) async fn cancel_pr_evaluations(
+    ctx: &CommentDispatch<'_>,
+    project_ids: &[ProjectId],
+) -> bool {
+    let mut cancelled: Option<ProjectId> = None;
+    for project_id in &comment_repo_projects(ctx, project_ids).await {
+        let evals = match pr_evaluations(ctx.state, *project_id, ctx.pr_number).await {
+            Ok(evals) => evals,
+            Err(e) => {
+                warn!(error = %e, %project_id, "/gradient cancel: loading PR evaluations");
+                continue;
+            }
+        };
+        for eval in evals.into_iter().filter(|e| e.status.is_active()) {
+            info!(
+                evaluation_id = %eval.id,
+                pr_number = ctx.pr_number,
+                sender = %ctx.sender,
+                "PR evaluation cancelled via /gradient comment"
+            );
+            ctx.scheduler.abort_evaluation(eval).await;
+            cancelled = Some(*project_id);
+        }
+    }
+
+    match cancelled {
+        Some(project_id) => {
+            if let Some(target) = ctx.reaction_target {
+                fire_reaction_via_project(
+                    ctx.state,
+                    project_id,
+                    target,
+                    gradient_ci::ReactionKind::ThumbsUp,
+                )
+                .await;
+            }
+            true
+        }
+        None => {
+            post_reply_comment(
+                ctx,
+                project_ids,
+                "No running Gradient evaluation to cancel for this pull request.",
+            )
+            .await;
+            false
+        }
+    }
+}
+
+/// Restart the failed builds of the latest finished evaluation of this PR. The
+/// restarted evaluation keeps the PR context, so its commit status and the
+/// final 👍/👎 reaction land on the PR as usual.
+pub(super) async fn retry_failed_pr_evaluations(
+    ctx: &CommentDispatch<'_>,
+    project_ids: &[ProjectId],
+) -> bool {
+    let source_comment_json = ctx.reaction_target.as_ref().map(|t| {
+        serde_json::json!({
+            "owner": t.owner,
+            "repo": t.repo,
+            "pr_number": t.pr_number,
+            "comment_id": t.comment_id,
+        })
+    });
+
+    let mut restarted_any = false;
+    let mut skipped: Vec<String> = Vec::new();
+    for project_id in &comment_repo_projects(ctx, project_ids).await {
+        let Some(eval) = latest_pr_evaluation(ctx.state, *project_id, ctx.pr_number).await else {
+            continue;
+        };
+        let project = match EProject::find_by_id(*project_id)
+            .one(&ctx.state.web_db)
+            .await
+        {
+            Ok(Some(p)) => p,
+            Ok(None) => continue,
+            Err(e) => {
+                warn!(error = %e, %project_id, "/gradient retry-failed: loading project");
+                continue;
+            }
+        };
+        if eval.status.is_active() {
+            skipped.push(format!("`{}` is still running", project.name));
+            continue;
+        }
+
+        match gradient_ci::trigger_restart_evaluation(
+            &ctx.state.web_db,
+            &project,
+            &eval,
+            source_comment_json.clone(),
+        )
+        .await
+        {
+            Ok(restarted) => {
+                info!(
+                    evaluation_id = %restarted.id,
+                    previous = %eval.id,
+                    pr_number = ctx.pr_number,
+                    sender = %ctx.sender,
+                    "PR evaluation restarted via /gradient retry-failed"
+                );
+                restarted_any = true;
+            }
+            Err(gradient_ci::TriggerError::AlreadyInProgress) => {
+                skipped.push(format!(
+                    "`{}` already has an evaluation in progress",
+                    project.name
+                ));
+            }
+            Err(e) => {
+                warn!(error = %e, evaluation_id = %eval.id, "/gradient retry-failed: restart failed");
+            }
+        }
+    }
+
+    if !restarted_any {
+        let body = if skipped.is_empty() {
+            "No finished Gradient evaluation to retry for this pull request.".to_string()
+        } else {
+            format!("Nothing was retried: {}.", skipped.join(", "))
+        };
+        post_reply_comment(ctx, project_ids, &body).await;
+    }
+    restarted_any
+}
+
+/// Reply with a per-attribute result table for the latest evaluation of this
+/// PR in every project.
+pub(super) async fn post_pr_status(ctx: &CommentDispatch<'_>, project_ids: &[ProjectId]) -> bool {
+    let mut sections = Vec::new();
+    for project_id in &comment_repo_projects(ctx, project_ids).await {
+        let Some(eval) = latest_pr_evaluation(ctx.state, *project_id, ctx.pr_number).await else {
+            continue;
+        };
+        match status_section(ctx.state, &eval).await {
+            Ok(Some(section)) => sections.push(section),
+            Ok(None) => {}
+            Err(e) => {
+                warn!(error = %e, evaluation_id = %eval.id, "/gradient status: loading results")
+            }
+        }
+    }
+
+    let body = if sections.is_empty() {
+        "No Gradient evaluation found for this pull request.".to_string()
+    } else {
+        sections.join("\n\n")
+    };
+    post_reply_comment(ctx, project_ids, &body).await
+}
+
+/// The projects of `project_ids` tracking the repository the comment was made
+/// on. An integration can serve several repositories, each with its own PR
+/// numbering, so a command on one repo's PR must not reach another's.
+async fn comment_repo_projects(
+    ctx: &CommentDispatch<'_>,
+    project_ids: &[ProjectId],
+) -> Vec<ProjectId> {
+    let event_repo_urls = [format!("https://github.com/{}/{}", ctx.owner, ctx.repo)];
+    match EProject::find()
+        .filter(CProject::Id.is_in(project_ids.iter().copied()))
+        .all(&ctx.state.web_db)
+        .await
+    {
+        Ok(projects) => projects
+            .into_iter()
+            .filter(|p| event_repo_matches_project(&event_repo_urls, &p.repository))
+            .map(|p| p.id)
+            .collect(),
+        Err(e) => {
+            warn!(error = %e, "/gradient: loading PR command projects");
+            Vec::new()
+        }
+    }
+}
+
+/// All evaluations of `project_id` created for `pr_number`, newest first.
+async fn pr_evaluations(
+    state: &Arc<ServerState>,
+    project_id: ProjectId,
+    pr_number: u64,
+) -> Result<Vec<MEvaluation>, sea_orm::DbErr> {
+    EEvaluation::find()
+        .filter(CEvaluation::Project.eq(project_id))
+        .filter(Expr::cust_with_values(
+            "(source_comment->>'pr_number')::bigint = $1",
+            [pr_number as i64],
+        ))
+        .order_by_desc(CEvaluation::CreatedAt)
+        .all(&state.web_db)
+        .await
+}
+
+async fn latest_pr_evaluation(
+    state: &Arc<ServerState>,
+    project_id: ProjectId,
+    pr_number: u64,
+) -> Option<MEvaluation> {
+    match pr_evaluations(state, project_id, pr_number).await {
+        Ok(evals) => evals.into_iter().next(),
+        Err(e) => {
+            warn!(error = %e, %project_id, "/gradient: loading PR evaluations");
+            None
+        }
+    }
+}
+
+/// Load the entry points of `eval` with their anchor status and render them.
+/// `None` when the project is gone.
+async fn status_section(
+    state: &Arc<ServerState>,
+    eval: &MEvaluation,
+) -> Result<Option<String>, sea_orm::DbErr> {
+    let Some(project_id) = eval.project else {
+        return Ok(None);
+    };
+    let Some(project) = EProject::find_by_id(project_id).one(&state.web_db).await? else {
+        return Ok(None);
+    };
+    let org_name = EOrganization::find_by_id(project.organization)
+        .one(&state.web_db)
+        .await?
+        .map(|o| o.name);
+
+    let entry_points = EEntryPoint::find()
+        .filter(CEntryPoint::Evaluation.eq(eval.id))
+        .order_by_asc(CEntryPoint::Eval)
+        .all(&state.web_db)
+        .await?;
+    let anchors: HashMap<DerivationId, BuildStatus> = EDerivationBuild::find()
+        .filter(CDerivationBuild::Derivation.is_in(entry_points.iter().map(|ep| ep.derivation)))
+        .all(&state.web_db)
+        .await?
+        .into_iter()
+        .map(|b| (b.derivation, b.status))
+        .collect();
+    let rows: Vec<(String, Option<BuildStatus>)> = entry_points
+        .into_iter()
+        .map(|ep| {
+            let status = anchors.get(&ep.derivation).copied();
+            (ep.eval, status)
+        })
+        .collect();
+
+    let details_url = org_name.map(|org| {
+        format!(
+            "{}/organization/{}/log/{}",
+            state.config.server.frontend_url, org, eval.id
+        )
+    });
+    Ok(Some(format_status_section(
+        &project.name,
+        &format!("{:?}", eval.status),
+        details_url.as_deref(),
+        &rows,
+    )))
+}
+
+/// Attribute rows per project in a `/gradient status` reply; the rest are
+/// counted and left to the details page, keeping the comment within forge
+/// size limits on large flakes.
+const MAX_STATUS_ROWS: usize = 100;
+
+/// Render one project's block of the `/gradient status` reply. A `None` status
+/// means the attribute has no build yet.
+pub(super) fn format_status_section(
+    project_name: &str,
+    eval_status: &str,
+    details_url: Option<&str>,
+    rows: &[(String, Option<BuildStatus>)],
+) -> String {
+    let mut out = format!("**{project_name}**: evaluation {eval_status}");
+    if let Some(url) = details_url {
+        out.push_str(&format!(" ([details]({url}))"));
+    }
+    if rows.is_empty() {
+        out.push_str("\n\nNo attributes evaluated yet.");
+        return out;
+    }
+    out.push_str("\n\n| Attribute | Status |\n| --- | --- |");
+    for (attr, status) in rows.iter().take(MAX_STATUS_ROWS) {
+        out.push_str(&format!("\n| `{attr}` | {} |", build_status_label(*status)));
+    }
+    if rows.len() > MAX_STATUS_ROWS {
+        let hidden = rows.len() - MAX_STATUS_ROWS;
+        match details_url {
+            Some(url) => out.push_str(&format!(
+                "\n\n{hidden} more attributes not shown; see the [full list]({url})."
+            )),
+            None => out.push_str(&format!("\n\n{hidden} more attributes not shown.")),
+        }
+    }
+    out
+}
+
+fn build_status_label(status: Option<BuildStatus>) -> &'static str {
+    match status.map(BuildStatus::for_api) {
+        None => "pending",
+        Some(BuildStatus::Created | BuildStatus::Queued) => "queued",
+        Some(BuildStatus::Building) => "building",
+        Some(BuildStatus::Completed) => "succeeded",
+        Some(BuildStatus::Substituted) => "substituted",
+        Some(BuildStatus::FailedPermanent) => "failed",
+        Some(BuildStatus::FailedTransient) => "retrying",
+        Some(BuildStatus::FailedTimeout) => "timed out",
+        Some(BuildStatus::DependencyFailed) => "dependency failed",
+        Some(BuildStatus::Aborted) => "aborted",
+    }
+}
+
+/// Post `body` on the PR via the first project with a usable reporter.
+/// Best-effort; returns whether the comment was posted.
+async fn post_reply_comment(
+    ctx: &CommentDispatch<'_>,
+    project_ids: &[ProjectId],
+    body: &str,
+) -> bool {
+    for project_id in project_ids {
+        let reporter =
+            match gradient_ci::actions::reporter_for_project(&ctx.state.ci(), *project_id).await {
+                Ok(Some(r)) => r,
+                Ok(None) => continue,
+                Err(e) => {
+                    warn!(error = %e, %project_id, "/gradient reply: resolving reporter");
+                    continue;
+                }
+            };
+        match reporter
+            .post_pr_comment(ctx.owner, ctx.repo, ctx.pr_number, body)
+            .await
+        {
+            Ok(()) => return true,
+            Err(e) => {
+                warn!(error = %e, %project_id, "/gradient reply: comment post failed, trying next project");
+            }
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_section_renders_attribute_table() {
+        let rows = vec![
+            (
+                "packages.x86_64-linux.default".to_string(),
+                Some(BuildStatus::Completed),
+            ),
+            (
+                "checks.x86_64-linux.fmt".to_string(),
+                Some(BuildStatus::FailedPermanent),
+            ),
+            (
+                "checks.x86_64-linux.lint".to_string(),
+                Some(BuildStatus::Created),
+            ),
+            ("checks.x86_64-linux.slow".to_string(), None),
+        ];
+        let out = format_status_section(
+            "widgets",
+            "Failed",
+            Some("https://ci.example/organization/acme/log/1"),
+            &rows,
+        );
+        assert_eq!(
+            out,
+            "**widgets**: evaluation Failed ([details](https://ci.example/organization/acme/log/1))\n\n\
+             | Attribute | Status |\n\
+             | --- | --- |\n\
+             | `packages.x86_64-linux.default` | succeeded |\n\
+             | `checks.x86_64-linux.fmt` | failed |\n\
+             | `checks.x86_64-linux.lint` | queued |\n\
+             | `checks.x86_64-linux.slow` | pending |"
+        );
+    }
+
+    #[test]
+    fn status_section_caps_rows_and_links_the_rest() {
+        let rows: Vec<(String, Option<BuildStatus>)> = (0..MAX_STATUS_ROWS + 5)
+            .map(|i| (format!("checks.x86_64-linux.c{i}"), None))
+            .collect();
+        let out = format_status_section(
+            "widgets",
+            "Building",
+            Some("https://ci.example/log/1"),
+            &rows,
+        );
+        assert_eq!(out.matches("| pending |").count(), MAX_STATUS_ROWS);
+        assert!(out.ends_with(
+            "\n\n5 more attributes not shown; see the [full list](https://ci.example/log/1)."
+        ));
+    }
+
+    #[test]
+    fn status_section_without_entry_points() {
+        let out = format_status_section("widgets", "Queued", None, &[]);
+        assert_eq!(
+            out,
+            "**widgets**: evaluation Queued\n\nNo attributes evaluated yet."
+        );
+    }
+}
@@ -22,6 +22,7 @@ pub mod orgs;
 pub mod projects;
 pub mod stats;
 pub mod user;
+pub mod user_notifications;
 pub mod workers;
 
 use crate::error::WebResult;
@@ -120,7 +120,8 @@ pub struct CreateIntegrationRequest {
     pub display_name: Option<String>,
     /// `"inbound"` or `"outbound"`.
     pub kind: String,
-    /// `"gitea"`, `"forgejo"`, `"gitlab"`, or `"github"`.
+    /// `"gitea"`, `"forgejo"`, `"gitlab"`, `"github"`, `"bitbucket"`, or
+    /// `"bitbucket-server"`.
     pub forge_type: String,
     /// Plaintext HMAC secret for inbound integrations.
     pub secret: Option<String>,
@@ -190,7 +191,7 @@ fn parse_kind(s: &str) -> Result<IntegrationKind, WebError> {
 fn parse_forge(s: &str) -> Result<ForgeType, WebError> {
     ForgeType::from_path_segment(s).ok_or_else(|| {
         WebError::bad_request(format!(
-            "Invalid forge type '{}': expected 'gitea', 'forgejo', 'gitlab', or 'github'.",
+            "Invalid forge type '{}': expected 'gitea', 'forgejo', 'gitlab', 'github', 'bitbucket', or 'bitbucket-server'.",
             s
         ))
     })
@@ -7,7 +7,7 @@
 use crate::access::{Caller, OrgAccess, load_org};
 use crate::audit::{RequestInfo, events, record as audit_record};
 use crate::authorization::{MaybeApiKey, MaybeUser};
-use crate::error::{WebError, WebResult, require_create_permission};
+use crate::error::{WebError, WebResult, require_create_permission, require_superuser};
 use crate::helpers::{ok_json, paginate, role_names};
 use crate::permissions::Permission;
 use axum::extract::{Path, Query, State};
@@ -42,6 +42,10 @@ pub struct PatchOrganizationRequest {
     pub display_name: Option<String>,
     pub description: Option<String>,
     pub hide_build_requests: Option<bool>,
+    /// Hard per-org quotas (superuser-only). `0` clears the limit.
+    pub max_concurrent_builds: Option<i32>,
+    pub max_concurrent_evaluations: Option<i32>,
+    pub build_minutes_budget: Option<i32>,
 }
 
 #[derive(Serialize)]
@@ -75,6 +79,9 @@ pub struct OrgResponse {
     /// Whether the server has a GitHub App configured at all.
     pub github_app_available: bool,
     pub role: Option<String>,
+    pub max_concurrent_builds: Option<i32>,
+    pub max_concurrent_evaluations: Option<i32>,
+    pub build_minutes_budget: Option<i32>,
 }
 
 pub async fn get_org_name_available(
@@ -331,6 +338,9 @@ pub async fn get_organization(
         created_at: org.created_at,
         github_app_available: state.config.github_app.clone().is_some(),
         role,
+        max_concurrent_builds: org.max_concurrent_builds,
+        max_concurrent_evaluations: org.max_concurrent_evaluations,
+        build_minutes_budget: org.build_minutes_budget,
     }))
 }
 
@@ -388,6 +398,26 @@ pub async fn patch_organization(
 
     crate::patch_field!(aorganization, body, hide_build_requests);
 
+    // Quotas protect the rest of a shared instance from this org, so only an
+    // instance operator may change them.
+    let quotas = [
+        body.max_concurrent_builds,
+        body.max_concurrent_evaluations,
+        body.build_minutes_budget,
+    ];
+    if quotas.iter().any(Option::is_some) {
+        require_superuser(&user)?;
+        if quotas.iter().flatten().any(|v| *v < 0) {
+            return Err(WebError::bad_request(
+                "Quotas must be positive, or 0 to remove the limit",
+            ));
+        }
+    }
+    let limit = |v: i32| (v > 0).then_some(v);
+    crate::patch_field_with!(aorganization, body, max_concurrent_builds, limit);
+    crate::patch_field_with!(aorganization, body, max_concurrent_evaluations, limit);
+    crate::patch_field_with!(aorganization, body, build_minutes_budget, limit);
+
     let organization = aorganization
         .update(&state.web_db)
         .await
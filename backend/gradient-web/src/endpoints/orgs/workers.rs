@@ -19,8 +19,12 @@ use gradient_entity::{base_worker, organization_base_worker};
 use gradient_scheduler::{Scheduler, WorkerInfo};
 use gradient_types::ids::*;
 use gradient_types::proto::GradientCapabilities;
+use gradient_types::worker_labels::{labels_from_json, labels_to_json, validate_labels};
+use gradient_types::worker_maintenance::{
+    any_window_active, validate_windows, windows_from_json, windows_to_json,
+};
 use gradient_types::{AOrganizationBaseWorker, EBaseWorker, EOrganizationBaseWorker};
-use gradient_types::{BaseResponse, MUser};
+use gradient_types::{BaseResponse, Labels, MUser, MaintenanceWindow};
 use rand::RngExt as _;
 use sea_orm::ActiveValue::Set;
 use sea_orm::{
@@ -58,6 +62,10 @@ pub struct RegisterWorkerRequest {
     /// Per-registration server-side gate for `build`. Defaults to true.
     #[serde(default = "default_true")]
     pub enable_build: bool,
+    /// Labels this org attaches to the worker, matched against its projects'
+    /// worker selectors. The worker's own advertised labels are never matched.
+    #[serde(default)]
+    pub labels: Labels,
 }
 
 #[derive(Serialize)]
@@ -85,6 +93,14 @@ pub struct OrgWorkerEntry {
     pub enable_build: bool,
     /// True for server-level base workers, false for per-org registrations.
     pub is_base: bool,
+    /// Registration labels this org attached to the worker. Always empty for
+    /// base workers.
+    pub labels: Labels,
+    /// Whether this org cordoned the worker: none of its jobs are assigned to
+    /// it until uncordoned. Always false for base workers.
+    pub cordoned: bool,
+    /// Scheduled windows during which this org's cordon applies automatically.
+    pub maintenance_windows: Vec<MaintenanceWindow>,
     /// Present when the worker is currently connected to this server.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub live: Option<WorkerLiveInfo>,
@@ -102,6 +118,13 @@ pub struct PatchWorkerRequest {
     pub enable_eval: Option<bool>,
     /// When present, update the per-registration `build` gate.
     pub enable_build: Option<bool>,
+    /// When present, replace the registration labels. An empty map clears them.
+    pub labels: Option<Labels>,
+    /// When present, cordon (`true`) or uncordon (`false`) the worker for
+    /// this org. In-flight jobs are left to finish.
+    pub cordoned: Option<bool>,
+    /// When present, replace the maintenance windows. An empty list clears them.
+    pub maintenance_windows: Option<Vec<MaintenanceWindow>>,
 }
 
 /// Base workers are server-managed: the only patch a member may apply is the
@@ -112,6 +135,9 @@ fn patch_edits_base_worker_fields(body: &PatchWorkerRequest) -> bool {
         || body.enable_fetch.is_some()
         || body.enable_eval.is_some()
         || body.enable_build.is_some()
+        || body.labels.is_some()
+        || body.cordoned.is_some()
+        || body.maintenance_windows.is_some()
 }
 
 #[derive(Serialize)]
@@ -125,6 +151,14 @@ pub struct WorkerLiveInfo {
     pub max_concurrent_builds: u32,
     pub assigned_job_count: usize,
     pub draining: bool,
+    /// Labels this org's jobs are matched against: the registration labels.
+    pub labels: Labels,
+    /// Labels the worker advertises itself. Informational only; selectors
+    /// never match them.
+    pub advertised_labels: Labels,
+    /// Whether this org's jobs are currently withheld from the worker, by a
+    /// manual cordon or an open maintenance window.
+    pub cordoned: bool,
 }
 
 pub async fn post_org_worker(
@@ -151,6 +185,7 @@ pub async fn post_org_worker(
         .filter(|u| u.get_version() == Some(uuid::Version::Random))
         .ok_or_else(|| WebError::bad_request("worker_id must be a valid UUID v4"))?;
     let worker_id_str = worker_uuid.to_string();
+    validate_labels(&body.labels).map_err(|e| WebError::bad_request(e.to_string()))?;
 
     // Resolve token: use caller-supplied one (after validation) or generate a new one.
     let (token, return_token) = if let Some(provided) = body.token {
@@ -186,6 +221,7 @@ pub async fn post_org_worker(
         enable_fetch: body.enable_fetch,
         enable_eval: body.enable_eval,
         enable_build: body.enable_build,
+        labels: labels_to_json(&body.labels),
         created_by: Some(user.id),
         created_at: gradient_types::now(),
         ..Default::default()
@@ -242,6 +278,9 @@ fn base_worker_entry(
         enable_eval: bw.enable_eval,
         enable_build: bw.enable_build,
         is_base: true,
+        labels: Labels::new(),
+        cordoned: false,
+        maintenance_windows: Vec::new(),
         live,
     }
 }
@@ -304,6 +343,9 @@ pub async fn get_org_workers(
                 max_concurrent_builds: w.max_concurrent_builds,
                 assigned_job_count: w.assigned_job_count,
                 draining: w.draining,
+                labels: w.labels.effective(org.id),
+                advertised_labels: w.labels.advertised.clone(),
+                cordoned: w.cordoned.contains(&org.id),
             })
     };
 
@@ -322,6 +364,9 @@ pub async fn get_org_workers(
                 enable_eval: reg.enable_eval,
                 enable_build: reg.enable_build,
                 is_base: false,
+                labels: labels_from_json(reg.labels.as_ref()),
+                cordoned: reg.cordoned,
+                maintenance_windows: windows_from_json(reg.maintenance_windows.as_ref()),
                 live,
             }
         })
@@ -579,6 +624,13 @@ pub async fn patch_org_worker(
         return Ok(ok_json("ok".to_string()));
     }
 
+    if let Some(ref labels) = body.labels {
+        validate_labels(labels).map_err(|e| WebError::bad_request(e.to_string()))?;
+    }
+    if let Some(ref windows) = body.maintenance_windows {
+        validate_windows(windows).map_err(|e| WebError::bad_request(e.to_string()))?;
+    }
+
     let reg = EWorkerRegistration::find()
         .filter(worker_registration::Column::PeerId.eq(org.id))
         .filter(worker_registration::Column::WorkerId.eq(&worker_id))
@@ -605,6 +657,15 @@ pub async fn patch_org_worker(
     if let Some(v) = body.enable_build {
         active_model.enable_build = Set(v);
     }
+    if let Some(ref labels) = body.labels {
+        active_model.labels = Set(labels_to_json(labels));
+    }
+    if let Some(v) = body.cordoned {
+        active_model.cordoned = Set(v);
+    }
+    if let Some(ref windows) = body.maintenance_windows {
+        active_model.maintenance_windows = Set(windows_to_json(windows));
+    }
     active_model.update(&state.web_db).await?;
 
     // When deactivating: abort in-flight jobs from this org on the worker
@@ -616,10 +677,15 @@ pub async fn patch_org_worker(
             .await;
     }
 
-    // Trigger re-auth so the worker's authorized peer set or negotiated
-    // capabilities are updated (or the worker is kicked if all registrations
-    // are now inactive).
-    if body.active.is_some() || caps_changed {
+    // Trigger re-auth so the worker's authorized peer set, negotiated
+    // capabilities, registration labels or cordon are updated (or the worker
+    // is kicked if all registrations are now inactive).
+    if body.active.is_some()
+        || caps_changed
+        || body.labels.is_some()
+        || body.cordoned.is_some()
+        || body.maintenance_windows.is_some()
+    {
         scheduler.request_reauth(&worker_id).await;
     }
 
@@ -640,6 +706,127 @@ pub async fn patch_org_worker(
     Ok(ok_json(format!("worker '{}' updated", worker_id)))
 }
 
+#[derive(Debug, Serialize)]
+pub struct WorkerDrainStatus {
+    pub worker_id: String,
+    /// Manual cordon set by this org.
+    pub cordoned: bool,
+    /// One of this org's maintenance windows is open right now.
+    pub in_maintenance: bool,
+    pub connected: bool,
+    /// This org's jobs still running on the worker.
+    pub in_flight_jobs: u32,
+    /// Cordoned (manually or by a window) with no job of this org left.
+    pub drained: bool,
+}
+
+/// A worker is drained for an org once it is cordoned for it and the last of
+/// its jobs finished; an uncordoned worker never counts as drained.
+fn worker_drained(cordoned: bool, in_maintenance: bool, in_flight_jobs: u32) -> bool {
+    (cordoned || in_maintenance) && in_flight_jobs == 0
+}
+
+async fn worker_drain_status(
+    scheduler: &Scheduler,
+    reg: &MWorkerRegistration,
+) -> WorkerDrainStatus {
+    let in_maintenance = any_window_active(
+        &windows_from_json(reg.maintenance_windows.as_ref()),
+        gradient_types::now(),
+    );
+    let in_flight_jobs = scheduler
+        .org_jobs_on_worker(&reg.worker_id, reg.peer_id)
+        .await;
+    WorkerDrainStatus {
+        worker_id: reg.worker_id.clone(),
+        cordoned: reg.cordoned,
+        in_maintenance,
+        connected: scheduler.is_worker_connected(&reg.worker_id).await,
+        in_flight_jobs,
+        drained: worker_drained(reg.cordoned, in_maintenance, in_flight_jobs),
+    }
+}
+
+/// Loads this org's registration of `worker_id` for the drain endpoints.
+/// Base workers are shared across orgs and cannot be cordoned by one of them;
+/// a superuser cordons them through `/admin/workers/{worker_id}/drain`.
+async fn drain_registration(
+    state: &ServerState,
+    org: OrganizationId,
+    worker_id: &str,
+) -> WebResult<MWorkerRegistration> {
+    let reg = EWorkerRegistration::find()
+        .filter(worker_registration::Column::PeerId.eq(org))
+        .filter(worker_registration::Column::WorkerId.eq(worker_id))
+        .one(&state.web_db)
+        .await?;
+    if let Some(reg) = reg {
+        return Ok(reg);
+    }
+    if gradient_db::base_workers::enabled_base_worker_by_worker_id(&state.web_db, worker_id)
+        .await?
+        .is_some()
+    {
+        return Err(WebError::conflict(
+            "base workers are cordoned by a superuser via /admin/workers/{worker_id}/drain",
+        ));
+    }
+    Err(WebError::not_found("worker registration"))
+}
+
+/// Cordon the worker for this org and report drain progress. In-flight jobs
+/// finish; poll the GET variant until `drained` turns true.
+pub async fn post_org_worker_drain(
+    state: State<Arc<ServerState>>,
+    Path((organization, worker_id)): Path<(String, String)>,
+    Extension(user): Extension<MUser>,
+    Extension(api_key): Extension<MaybeApiKey>,
+    Extension(scheduler): Extension<Arc<Scheduler>>,
+) -> WebResult<Json<BaseResponse<WorkerDrainStatus>>> {
+    let org = load_org(
+        &state,
+        Caller::User(&user),
+        api_key.as_ref(),
+        organization,
+        OrgAccess::Member {
+            reject_managed: false,
+        },
+    )
+    .await?;
+
+    let mut reg = drain_registration(&state, org.id, &worker_id).await?;
+    if !reg.cordoned {
+        let mut active_model: AWorkerRegistration = reg.into();
+        active_model.cordoned = Set(true);
+        reg = active_model.update(&state.web_db).await?;
+        scheduler.request_reauth(&worker_id).await;
+    }
+
+    Ok(ok_json(worker_drain_status(&scheduler, &reg).await))
+}
+
+pub async fn get_org_worker_drain(
+    state: State<Arc<ServerState>>,
+    Path((organization, worker_id)): Path<(String, String)>,
+    Extension(user): Extension<MUser>,
+    Extension(api_key): Extension<MaybeApiKey>,
+    Extension(scheduler): Extension<Arc<Scheduler>>,
+) -> WebResult<Json<BaseResponse<WorkerDrainStatus>>> {
+    let org = load_org(
+        &state,
+        Caller::User(&user),
+        api_key.as_ref(),
+        organization,
+        OrgAccess::Member {
+            reject_managed: false,
+        },
+    )
+    .await?;
+
+    let reg = drain_registration(&state, org.id, &worker_id).await?;
+    Ok(ok_json(worker_drain_status(&scheduler, &reg).await))
+}
+
 pub async fn delete_org_worker(
     state: State<Arc<ServerState>>,
     Path((organization, worker_id)): Path<(String, String)>,
@@ -713,6 +900,9 @@ mod tests {
             ram_total_mb: 0,
             disk_speed_mbps: None,
             network_speed_mbps: None,
+            labels: Default::default(),
+            cordoned: Default::default(),
+            worker_cordoned: false,
         }
     }
 
@@ -752,6 +942,9 @@ mod tests {
             enable_fetch: None,
             enable_eval: None,
             enable_build: None,
+            labels: None,
+            cordoned: None,
+            maintenance_windows: None,
         }
     }
 
@@ -784,6 +977,18 @@ mod tests {
                 enable_build: Some(false),
                 ..empty_patch()
             },
+            PatchWorkerRequest {
+                labels: Some(Labels::new()),
+                ..empty_patch()
+            },
+            PatchWorkerRequest {
+                cordoned: Some(true),
+                ..empty_patch()
+            },
+            PatchWorkerRequest {
+                maintenance_windows: Some(vec![]),
+                ..empty_patch()
+            },
         ] {
             assert!(patch_edits_base_worker_fields(&body));
         }
@@ -811,6 +1016,17 @@ mod tests {
         assert_eq!(msg, "worker is connected and authorized");
     }
 
+    #[test]
+    fn worker_is_drained_only_when_cordoned_and_idle() {
+        assert!(worker_drained(true, false, 0));
+        assert!(worker_drained(false, true, 0));
+        assert!(!worker_drained(true, true, 2));
+        assert!(
+            !worker_drained(false, false, 0),
+            "uncordoned is never drained"
+        );
+    }
+
     #[test]
     fn base_worker_entry_is_flagged_and_reflects_opt_in() {
         let opted_in = base_worker_entry(base_worker_model(), true, None);
@@ -4,11 +4,13 @@
  * SPDX-License-Identifier: AGPL-3.0-only
  */
 
-//! CRUD endpoints for `project_action` plus test-fire, token regeneration,
-//! and delivery inspection.
+//! CRUD endpoints for `project_action` plus test-fire, test delivery of
+//! `send_web_request` body templates, token regeneration, delivery
+//! inspection, and the dead-letter view with manual redelivery.
 
 use crate::access::{Caller, ProjectAccess, load_project};
 use crate::authorization::MaybeApiKey;
+use crate::endpoints::user::deserialize_optional_field;
 use crate::error::{WebError, WebResult};
 use crate::helpers::{OptionExt, ok_json};
 use crate::permissions::Permission;
@@ -19,7 +21,8 @@ use chrono::Utc;
 use gradient_ci::IntegrationKind;
 use gradient_ci::actions::encrypt_action_secret;
 use gradient_core::ServerState;
-use gradient_types::actions::{ActionConfig, ActionType};
+use gradient_entity::project_action_delivery::DeliveryState;
+use gradient_types::actions::{ActionConfig, ActionType, WebRequestMethod};
 use gradient_types::input::load_secret_bytes;
 use gradient_types::*;
 use gradient_util::http_validation::validate_webhook_url;
@@ -30,6 +33,7 @@ use sea_orm::{
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 pub fn router() -> Router<Arc<ServerState>> {
@@ -42,15 +46,21 @@ pub fn router() -> Router<Arc<ServerState>> {
                 .delete(delete_action),
         )
         .route("/{id}/test", axum::routing::post(test_action))
+        .route("/{id}/test-delivery", axum::routing::post(test_delivery))
         .route(
             "/{id}/regenerate-token",
             axum::routing::post(regenerate_token),
         )
+        .route("/dead-deliveries", axum::routing::get(list_dead_deliveries))
         .route("/{id}/deliveries", axum::routing::get(list_deliveries))
         .route(
             "/{id}/deliveries/{delivery_id}",
             axum::routing::get(get_delivery),
         )
+        .route(
+            "/{id}/deliveries/{delivery_id}/redeliver",
+            axum::routing::post(redeliver_delivery),
+        )
 }
 
 #[derive(Serialize, Debug)]
@@ -94,19 +104,29 @@ fn action_type_to_str(t: ActionType) -> &'static str {
         ActionType::SendWebRequest => "send_web_request",
         ActionType::ForgeStatusReport => "forge_status_report",
         ActionType::OpenPr => "open_pr",
+        ActionType::SendMatrix => "send_matrix",
+        ActionType::SendSlack => "send_slack",
     }
 }
 
 /// Render a stored row as a public response, stripping the encrypted token
-/// from `send_web_request` configs so secrets never leak past the create
-/// call where the plaintext is returned exactly once.
+/// and signing secret from `send_web_request` configs (and the access token
+/// from `send_matrix`) so secrets never leak past the create call where the
+/// plaintext token is returned exactly once.
 fn to_response(m: MProjectAction) -> ActionResponse {
     let at = m.action_type;
     let mut config = m.config;
-    if at == ActionType::SendWebRequest
-        && let Some(obj) = config.as_object_mut()
-    {
-        obj.remove("token");
+    if let Some(obj) = config.as_object_mut() {
+        match at {
+            ActionType::SendWebRequest => {
+                obj.remove("token");
+                obj.remove("signing_secret");
+            }
+            ActionType::SendMatrix => {
+                obj.remove("access_token");
+            }
+            _ => {}
+        }
     }
     let events = m
         .events
@@ -131,6 +151,25 @@ fn to_response(m: MProjectAction) -> ActionResponse {
     }
 }
 
+/// `send_matrix` posts through `/rooms/{room_id}/send`, which only takes a
+/// room id (`!opaque:server`), not an alias.
+fn validate_matrix_target(homeserver: &str, room_id: &str) -> WebResult<()> {
+    validate_webhook_url(homeserver).map_err(|e| WebError::unprocessable_entity(e.to_string()))?;
+    if !room_id.starts_with('!') || !room_id.contains(':') {
+        return Err(WebError::unprocessable_entity(
+            "room_id must be a Matrix room id of the form '!id:server'",
+        ));
+    }
+    Ok(())
+}
+
+/// Encrypt a plaintext action secret with the server's crypt key.
+fn seal_secret(state: &ServerState, plaintext: &str) -> WebResult<String> {
+    let key = load_secret_bytes(&state.config.secrets.crypt_secret_file)
+        .map_err(|e| WebError::internal(e.to_string()))?;
+    encrypt_action_secret(plaintext, key.expose()).map_err(|e| WebError::internal(e.to_string()))
+}
+
 /// `GET /projects/{org}/{project}/actions` - list all actions for the project.
 pub async fn list_actions(
     state: State<Arc<ServerState>>,
@@ -195,11 +234,24 @@ pub async fn create_action(
                 ));
             }
         }
-        ActionConfig::SendWebRequest { url, .. } => {
+        ActionConfig::SendWebRequest { url, .. } | ActionConfig::SendSlack { url, .. } => {
             if let Err(e) = validate_webhook_url(url) {
                 return Err(WebError::unprocessable_entity(e.to_string()));
             }
         }
+        ActionConfig::SendMatrix {
+            homeserver,
+            room_id,
+            access_token,
+            ..
+        } => {
+            validate_matrix_target(homeserver, room_id)?;
+            if access_token.is_none() {
+                return Err(WebError::unprocessable_entity(
+                    "send_matrix requires an access_token",
+                ));
+            }
+        }
         ActionConfig::OpenPr { .. } => {}
     }
 
@@ -210,6 +262,9 @@ pub async fn create_action(
             "send_mail requires at least one recipient",
         ));
     }
+    body.config
+        .validate()
+        .map_err(|e| WebError::unprocessable_entity(e.to_string()))?;
 
     let integration_id = match &body.config {
         ActionConfig::ForgeStatusReport { integration_id }
@@ -252,20 +307,45 @@ pub async fn create_action(
     let (stored_config, plaintext_token) = match body.config.clone() {
         ActionConfig::SendWebRequest {
             url,
-            token: Some(plaintext),
-        } => {
-            let key = load_secret_bytes(&state.config.secrets.crypt_secret_file)
-                .map_err(|e| WebError::internal(e.to_string()))?;
-            let encrypted = encrypt_action_secret(&plaintext, key.expose())
-                .map_err(|e| WebError::internal(e.to_string()))?;
-            (
-                ActionConfig::SendWebRequest {
-                    url,
-                    token: Some(encrypted),
-                },
-                Some(plaintext),
-            )
-        }
+            token,
+            signing_secret,
+            method,
+            headers,
+            body_template,
+        } => (
+            ActionConfig::SendWebRequest {
+                url,
+                token: token
+                    .as_deref()
+                    .map(|t| seal_secret(&state, t))
+                    .transpose()?,
+                signing_secret: signing_secret
+                    .as_deref()
+                    .map(|s| seal_secret(&state, s))
+                    .transpose()?,
+                method,
+                headers,
+                body_template,
+            },
+            token,
+        ),
+        ActionConfig::SendMatrix {
+            homeserver,
+            room_id,
+            access_token,
+            message_template,
+        } => (
+            ActionConfig::SendMatrix {
+                homeserver,
+                room_id,
+                access_token: access_token
+                    .as_deref()
+                    .map(|t| seal_secret(&state, t))
+                    .transpose()?,
+                message_template,
+            },
+            None,
+        ),
         other => (other, None),
     };
 
@@ -301,11 +381,22 @@ pub async fn create_action(
 #[derive(Deserialize, Debug)]
 pub struct UpdateActionRequest {
     pub name: Option<String>,
-    pub config: Option<ActionConfig>,
+    pub config: Option<UpdateActionConfig>,
     pub events: Option<Vec<String>>,
     pub active: Option<bool>,
 }
 
+/// Replacement config of an action. `signing_secret` is pulled out of the
+/// `send_web_request` fields for patch semantics: omit to keep the stored
+/// secret, a string to replace it, `null` to remove it.
+#[derive(Deserialize, Debug)]
+pub struct UpdateActionConfig {
+    #[serde(flatten)]
+    pub config: ActionConfig,
+    #[serde(default, deserialize_with = "deserialize_optional_field")]
+    pub signing_secret: Option<Option<String>>,
+}
+
 pub async fn read_action(
     state: State<Arc<ServerState>>,
     Extension(user): Extension<MUser>,
@@ -360,8 +451,12 @@ pub async fn update_action(
         .or_not_found("Action")?;
 
     let existing_type = row.action_type;
+    let (new_config, signing_secret) = match body.config {
+        Some(update) => (Some(update.config), update.signing_secret),
+        None => (None, None),
+    };
 
-    if let Some(ref new_cfg) = body.config {
+    if let Some(ref new_cfg) = new_config {
         if new_cfg.action_type() != existing_type {
             return Err(WebError::unprocessable_entity(
                 "action_type cannot be changed",
@@ -373,11 +468,16 @@ pub async fn update_action(
                     "send_mail requires at least one recipient",
                 ));
             }
-            ActionConfig::SendWebRequest { url, .. } => {
+            ActionConfig::SendWebRequest { url, .. } | ActionConfig::SendSlack { url, .. } => {
                 if let Err(e) = validate_webhook_url(url) {
                     return Err(WebError::unprocessable_entity(e.to_string()));
                 }
             }
+            ActionConfig::SendMatrix {
+                homeserver,
+                room_id,
+                ..
+            } => validate_matrix_target(homeserver, room_id)?,
             ActionConfig::ForgeStatusReport { integration_id }
             | ActionConfig::OpenPr { integration_id, .. } => {
                 let integration = EIntegration::find()
@@ -417,39 +517,72 @@ pub async fn update_action(
 
     let mut active: AProjectAction = row.into();
 
-    if let Some(new_cfg) = body.config {
-        // For send_web_request, token: None means preserve the existing encrypted token.
+    if let Some(new_cfg) = new_config {
+        // For send_web_request (token / signing_secret) and send_matrix
+        // (access_token), None means preserve the existing encrypted value;
+        // an explicit null `signing_secret` removes it.
+        let existing_config: ActionConfig = serde_json::from_value(active.config.as_ref().clone())
+            .map_err(|e| WebError::internal(e.to_string()))?;
         let stored_cfg = match new_cfg {
-            ActionConfig::SendWebRequest { url, token: None } => {
-                let existing_config: ActionConfig =
-                    serde_json::from_value(active.config.as_ref().clone())
-                        .map_err(|e| WebError::internal(e.to_string()))?;
-                let existing_token =
-                    if let ActionConfig::SendWebRequest { token, .. } = existing_config {
-                        token
-                    } else {
-                        None
-                    };
-                ActionConfig::SendWebRequest {
-                    url,
-                    token: existing_token,
-                }
-            }
             ActionConfig::SendWebRequest {
                 url,
-                token: Some(plaintext),
+                token,
+                method,
+                headers,
+                body_template,
+                ..
             } => {
-                let key = load_secret_bytes(&state.config.secrets.crypt_secret_file)
-                    .map_err(|e| WebError::internal(e.to_string()))?;
-                let encrypted = encrypt_action_secret(&plaintext, key.expose())
-                    .map_err(|e| WebError::internal(e.to_string()))?;
+                let (existing_token, existing_secret) = match existing_config {
+                    ActionConfig::SendWebRequest {
+                        token,
+                        signing_secret,
+                        ..
+                    } => (token, signing_secret),
+                    _ => (None, None),
+                };
                 ActionConfig::SendWebRequest {
                     url,
-                    token: Some(encrypted),
+                    token: match token {
+                        Some(plaintext) => Some(seal_secret(&state, &plaintext)?),
+                        None => existing_token,
+                    },
+                    signing_secret: match signing_secret {
+                        Some(Some(plaintext)) => Some(seal_secret(&state, &plaintext)?),
+                        Some(None) => None,
+                        None => existing_secret,
+                    },
+                    method,
+                    headers,
+                    body_template,
+                }
+            }
+            ActionConfig::SendMatrix {
+                homeserver,
+                room_id,
+                access_token,
+                message_template,
+            } => {
+                let existing_token = match existing_config {
+                    ActionConfig::SendMatrix { access_token, .. } => access_token,
+                    _ => None,
+                };
+                ActionConfig::SendMatrix {
+                    homeserver,
+                    room_id,
+                    access_token: match access_token {
+                        Some(plaintext) => Some(seal_secret(&state, &plaintext)?),
+                        None => existing_token,
+                    },
+                    message_template,
                 }
             }
             other => other,
         };
+        // Validated after the merge so a preserved token still conflicts
+        // with a newly added Authorization header.
+        stored_cfg
+            .validate()
+            .map_err(|e| WebError::unprocessable_entity(e.to_string()))?;
         active.config =
             Set(serde_json::to_value(&stored_cfg).map_err(|e| WebError::internal(e.to_string()))?);
     }
@@ -511,6 +644,72 @@ pub async fn delete_action(
     Ok(ok_json(DeletedResponse { deleted: true }))
 }
 
+/// Synthetic event payload for test fires, keyed like the real payload of
+/// `event` so body templates render as they will in production. Ids are nil.
+/// `synthetic` keeps the delivery out of the retry schedule.
+fn sample_payload(event: &str, project: &MProject) -> JsonValue {
+    const NIL: &str = "00000000-0000-0000-0000-000000000000";
+    let mut payload = if event == "aggregate.updated" {
+        serde_json::json!({
+            "build_id": NIL,
+            "evaluation_id": NIL,
+            "aggregate": "release",
+            "constituent_build_id": NIL,
+        })
+    } else if event.starts_with("build.") {
+        serde_json::json!({
+            "build_id": NIL,
+            "evaluation_id": NIL,
+            "derivation_path": "/nix/store/00000000000000000000000000000000-sample.drv",
+        })
+    } else {
+        serde_json::json!({
+            "evaluation_id": NIL,
+            "project_id": project.id,
+            "repository": project.repository,
+        })
+    };
+    payload["status"] = event.into();
+    payload["evaluation_kind"] = "normal".into();
+    if event == "build.failed" {
+        let cause = FailureCause::Oom;
+        payload["failure_summary"] = cause.summary().into();
+        payload["failure_cause"] = serde_json::json!(cause);
+    }
+    payload["synthetic"] = true.into();
+    payload
+}
+
+/// Real chat and mail deliveries resolve the organization, project and link
+/// from the evaluation; a synthetic payload names no real one, so carry them.
+fn with_sample_message_fields(
+    mut payload: JsonValue,
+    organization: &str,
+    project: &str,
+) -> JsonValue {
+    payload["org"] = organization.into();
+    payload["project"] = project.into();
+    payload["id"] = payload["evaluation_id"].clone();
+    payload["time"] = Utc::now().to_rfc3339().into();
+    payload["link"] = format!(
+        "https://gradient.example/projects/{}/{}",
+        organization, project
+    )
+    .into();
+    payload
+}
+
+/// First subscribed event of an action, or `evaluation.completed`.
+fn first_event(action: &MProjectAction) -> String {
+    action
+        .events
+        .as_array()
+        .and_then(|a| a.first())
+        .and_then(|v| v.as_str())
+        .unwrap_or("evaluation.completed")
+        .to_string()
+}
+
 pub async fn test_action(
     state: State<Arc<ServerState>>,
     Extension(user): Extension<MUser>,
@@ -553,29 +752,14 @@ pub async fn test_action(
         return Ok(ok_json(serde_json::Value::Null));
     }
 
-    let event = action
-        .events
-        .as_array()
-        .and_then(|a| a.first())
-        .and_then(|v| v.as_str())
-        .unwrap_or("evaluation.completed")
-        .to_string();
-
-    let now = chrono::Utc::now();
-    let payload = serde_json::json!({
-        "synthetic": true,
-        "event": event,
-        "org": organization,
-        "project": project,
-        "id": "00000000-0000-0000-0000-000000000000",
-        "status": "ok",
-        "time": now.to_rfc3339(),
-        "link": format!("https://gradient.example/projects/{}/{}", organization, project),
-        "owner": "gradient-test",
-        "repo": project,
-        "sha": "0000000000000000000000000000000000000000",
-        "context": "gradient/test-fire",
-    });
+    let event = first_event(&action);
+
+    let payload = sample_payload(&event, &proj);
+    let payload = if action_type == ActionType::SendWebRequest {
+        payload
+    } else {
+        with_sample_message_fields(payload, &organization, &project)
+    };
 
     gradient_ci::actions::execute_action(&state.ci(), action, &event, payload)
         .await
@@ -584,6 +768,100 @@ pub async fn test_action(
     Ok(ok_json(serde_json::Value::Null))
 }
 
+#[derive(Deserialize, Debug, Default)]
+pub struct TestDeliveryRequest {
+    /// Event to render against; defaults to the action's first event.
+    pub event: Option<String>,
+    /// Only render the request, do not send it.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TestDeliveryResponse {
+    pub event: String,
+    pub method: WebRequestMethod,
+    pub url: String,
+    pub headers: BTreeMap<String, String>,
+    pub body: JsonValue,
+    pub sent: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// `POST /projects/{org}/{project}/actions/{id}/test-delivery` - render a
+/// `send_web_request` action's body template against a sample event and,
+/// unless `dry_run` is set, deliver it. The rendered request is returned
+/// either way so template mistakes are visible without a receiver.
+pub async fn test_delivery(
+    state: State<Arc<ServerState>>,
+    Extension(user): Extension<MUser>,
+    Extension(api_key): Extension<MaybeApiKey>,
+    Path((organization, project, id)): Path<(String, String, ProjectActionId)>,
+    body: Option<Json<TestDeliveryRequest>>,
+) -> WebResult<Json<BaseResponse<TestDeliveryResponse>>> {
+    let Json(body) = body.unwrap_or_default();
+    let (_org, proj) = load_project(
+        &state,
+        Caller::User(&user),
+        api_key.as_ref(),
+        organization.clone(),
+        project.clone(),
+        ProjectAccess::Require {
+            permission: Permission::ManageActions,
+            reject_managed: false,
+        },
+    )
+    .await?;
+
+    let action = EProjectAction::find()
+        .filter(CProjectAction::Id.eq(id))
+        .filter(CProjectAction::Project.eq(proj.id))
+        .one(&state.web_db)
+        .await?
+        .or_not_found("Action")?;
+
+    let cfg: ActionConfig = serde_json::from_value(action.config.clone())
+        .map_err(|e| WebError::internal(e.to_string()))?;
+    let ActionConfig::SendWebRequest {
+        url,
+        method,
+        headers,
+        body_template,
+        ..
+    } = cfg
+    else {
+        return Err(WebError::unprocessable_entity(
+            "test-delivery is only valid for send_web_request actions",
+        ));
+    };
+
+    let event = body.event.unwrap_or_else(|| first_event(&action));
+    let payload = sample_payload(&event, &proj);
+    let rendered =
+        gradient_ci::actions::render_web_request_body(&event, &payload, body_template.as_deref())
+            .map_err(|e| WebError::unprocessable_entity(e.to_string()))?;
+
+    let error = if body.dry_run {
+        None
+    } else {
+        gradient_ci::actions::execute_action(&state.ci(), action, &event, payload)
+            .await
+            .err()
+            .map(|e| format!("{:#}", e))
+    };
+
+    Ok(ok_json(TestDeliveryResponse {
+        event,
+        method,
+        url,
+        headers,
+        body: rendered,
+        sent: !body.dry_run && error.is_none(),
+        error,
+    }))
+}
+
 pub async fn regenerate_token(
     state: State<Arc<ServerState>>,
     Extension(user): Extension<MUser>,
@@ -653,8 +931,12 @@ pub struct DeliveryListQuery {
 #[derive(Serialize, Debug)]
 pub struct DeliveryListItem {
     pub id: ProjectActionDeliveryId,
+    pub action_id: ProjectActionId,
     pub event: String,
     pub success: bool,
+    pub state: DeliveryState,
+    pub attempt: i32,
+    pub next_retry_at: Option<chrono::NaiveDateTime>,
     pub response_status: Option<i32>,
     pub error_message: Option<String>,
     pub duration_ms: i32,
@@ -672,8 +954,12 @@ pub struct DeliveryDetail {
 fn to_delivery_list_item(r: MProjectActionDelivery) -> DeliveryListItem {
     DeliveryListItem {
         id: r.id,
+        action_id: r.action_id,
         event: r.event,
         success: r.success,
+        state: r.state,
+        attempt: r.attempt,
+        next_retry_at: r.next_retry_at,
         response_status: r.response_status,
         error_message: r.error_message,
         duration_ms: r.duration_ms,
@@ -761,3 +1047,103 @@ pub async fn get_delivery(
         item: to_delivery_list_item(r),
     }))
 }
+
+/// `GET /projects/{org}/{project}/actions/dead-deliveries` - deliveries across
+/// every action of the project that exhausted their retry budget, newest first.
+pub async fn list_dead_deliveries(
+    state: State<Arc<ServerState>>,
+    Extension(user): Extension<MUser>,
+    Extension(api_key): Extension<MaybeApiKey>,
+    Path((organization, project)): Path<(String, String)>,
+    Query(q): Query<DeliveryListQuery>,
+) -> WebResult<Json<BaseResponse<Vec<DeliveryListItem>>>> {
+    let (_org, proj) = load_project(
+        &state,
+        Caller::User(&user),
+        api_key.as_ref(),
+        organization,
+        project,
+        ProjectAccess::Member,
+    )
+    .await?;
+
+    let action_ids: Vec<ProjectActionId> = EProjectAction::find()
+        .filter(CProjectAction::Project.eq(proj.id))
+        .all(&state.web_db)
+        .await?
+        .into_iter()
+        .map(|a| a.id)
+        .collect();
+    if action_ids.is_empty() {
+        return Ok(ok_json(Vec::new()));
+    }
+
+    let limit = q.limit.unwrap_or(50).min(200);
+    let offset = q.offset.unwrap_or(0);
+
+    let rows = EProjectActionDelivery::find()
+        .filter(CProjectActionDelivery::ActionId.is_in(action_ids))
+        .filter(CProjectActionDelivery::State.eq(DeliveryState::Dead))
+        .order_by(CProjectActionDelivery::DeliveredAt, Order::Desc)
+        .limit(limit)
+        .offset(offset)
+        .all(&state.web_db)
+        .await?;
+
+    Ok(ok_json(
+        rows.into_iter().map(to_delivery_list_item).collect(),
+    ))
+}
+
+/// `POST /projects/{org}/{project}/actions/{id}/deliveries/{delivery_id}/redeliver`
+/// - replay a dead delivery once, synchronously. The row is updated in place:
+///   delivered on success, still dead (with the new error) otherwise.
+pub async fn redeliver_delivery(
+    state: State<Arc<ServerState>>,
+    Extension(user): Extension<MUser>,
+    Extension(api_key): Extension<MaybeApiKey>,
+    Path((organization, project, action_id, delivery_id)): Path<(
+        String,
+        String,
+        ProjectActionId,
+        ProjectActionDeliveryId,
+    )>,
+) -> WebResult<Json<BaseResponse<DeliveryListItem>>> {
+    let (_org, proj) = load_project(
+        &state,
+        Caller::User(&user),
+        api_key.as_ref(),
+        organization,
+        project,
+        ProjectAccess::Require {
+            permission: Permission::ManageActions,
+            reject_managed: false,
+        },
+    )
+    .await?;
+
+    let action = EProjectAction::find()
+        .filter(CProjectAction::Id.eq(action_id))
+        .filter(CProjectAction::Project.eq(proj.id))
+        .one(&state.web_db)
+        .await?
+        .or_not_found("Action")?;
+
+    let delivery = EProjectActionDelivery::find_by_id(delivery_id)
+        .filter(CProjectActionDelivery::ActionId.eq(action_id))
+        .one(&state.web_db)
+        .await?
+        .or_not_found("Delivery")?;
+
+    if delivery.state != DeliveryState::Dead {
+        return Err(WebError::unprocessable_entity(
+            "only dead deliveries can be redelivered",
+        ));
+    }
+
+    let updated = gradient_ci::actions::redeliver(&state.ci(), &action, delivery)
+        .await
+        .map_err(|e| WebError::internal(format!("redelivery failed: {}", e)))?;
+
+    Ok(ok_json(to_delivery_list_item(updated)))
+}
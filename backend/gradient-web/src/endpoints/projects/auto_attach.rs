@@ -48,6 +48,8 @@ fn public_forge_for_host(host: &str) -> Option<ForgeType> {
     match host {
         "github.com" => Some(ForgeType::GitHub),
         "gitlab.com" => Some(ForgeType::GitLab),
+        "bitbucket.org" => Some(ForgeType::Bitbucket),
+        "codeberg.org" => Some(ForgeType::Forgejo),
         _ => None,
     }
 }
@@ -217,6 +219,18 @@ mod tests {
         assert!(m.outbound.is_some());
     }
 
+    #[test]
+    fn public_bitbucket_and_codeberg_match_by_forge_type() {
+        let integrations = vec![
+            integ(IntegrationKind::Inbound, ForgeType::Bitbucket, None),
+            integ(IntegrationKind::Inbound, ForgeType::Forgejo, None),
+        ];
+        let m = match_integrations_for_repo("git@bitbucket.org:foo/bar.git", &integrations);
+        assert_eq!(m.inbound.map(|i| i.forge_type), Some(ForgeType::Bitbucket));
+        let m = match_integrations_for_repo("https://codeberg.org/foo/bar", &integrations);
+        assert_eq!(m.inbound.map(|i| i.forge_type), Some(ForgeType::Forgejo));
+    }
+
     #[test]
     fn ambiguous_inbound_is_skipped() {
         let integrations = vec![
@@ -5,8 +5,8 @@
  */
 
 use super::{
-    BuildStatusCounts, EntryPointSummary, EvaluationSummary, EvaluationTriggerSummary,
-    ProjectDetailsResponse, QueueSummary,
+    BuildStatusCounts, ConstituentSummary, EntryPointSummary, EvaluationSummary,
+    EvaluationTriggerSummary, ProjectDetailsResponse, QueueSummary,
 };
 use crate::access::{Caller, ProjectAccess, has_permission, is_org_member, load_project};
 use crate::authorization::{MaybeApiKey, MaybeUser};
@@ -30,7 +30,7 @@ use gradient_types::input::vec_to_hex;
 use gradient_types::*;
 use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 #[derive(Deserialize, Default)]
@@ -252,6 +252,7 @@ pub async fn post_project_evaluate(
         None,
         None,
         Some(user.id),
+        None,
     )
     .await
     .map_err(|e| match e {
@@ -459,6 +460,8 @@ struct EntryPointRelatedData {
     has_products: HashMap<DerivationId, bool>,
     build_time_ms: HashMap<DerivationId, Option<i64>>,
     deps: HashMap<EntryPointId, BuildStatusCounts>,
+    aggregates: HashMap<EntryPointId, gradient_db::AggregateStatus>,
+    constituent_paths: HashMap<DerivationId, String>,
 }
 
 impl EntryPointRelatedData {
@@ -616,6 +619,34 @@ impl EntryPointRelatedData {
             })
             .collect();
 
+        let aggregates: HashMap<EntryPointId, gradient_db::AggregateStatus> =
+            if entry_points.iter().any(|ep| ep.aggregate) {
+                gradient_db::aggregate_statuses(db, eval_id)
+                    .await?
+                    .into_iter()
+                    .map(|a| (a.entry_point.id, a))
+                    .collect()
+            } else {
+                HashMap::new()
+            };
+        let constituent_ids: Vec<DerivationId> = aggregates
+            .values()
+            .flat_map(|a| a.constituents.iter().map(|&(drv, _)| drv))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let constituent_paths: HashMap<DerivationId, String> =
+            gradient_db::fetch_in_chunks(&constituent_ids, |chunk| async move {
+                EDerivation::find()
+                    .filter(CDerivation::Id.is_in(chunk))
+                    .all(db)
+                    .await
+            })
+            .await?
+            .into_iter()
+            .map(|d| (d.id, d.drv_path()))
+            .collect();
+
         Ok(Self {
             anchors,
             build_jobs,
@@ -623,10 +654,16 @@ impl EntryPointRelatedData {
             has_products,
             build_time_ms,
             deps,
+            aggregates,
+            constituent_paths,
         })
     }
 
     fn build_summaries(&self, entry_points: &[MEntryPoint]) -> Vec<EntryPointSummary> {
+        let eval_by_drv: HashMap<DerivationId, &str> = entry_points
+            .iter()
+            .map(|ep| (ep.derivation, ep.eval.as_str()))
+            .collect();
         let mut summaries = Vec::new();
         for ep in entry_points {
             let Some(&build_id) = self.build_jobs.get(&ep.derivation) else {
@@ -635,12 +672,24 @@ impl EntryPointRelatedData {
             let Some(drv) = self.derivations.get(&ep.derivation) else {
                 continue;
             };
-            let build_status = self
-                .anchors
-                .get(&ep.derivation)
+            let aggregate = self.aggregates.get(&ep.id);
+            let build_status = aggregate
                 .map(|a| a.status)
+                .or_else(|| self.anchors.get(&ep.derivation).map(|a| a.status))
                 .unwrap_or(BuildStatus::Queued)
                 .for_api();
+            let constituents = aggregate.map(|a| {
+                a.constituents
+                    .iter()
+                    .filter_map(|&(drv, status)| {
+                        Some(ConstituentSummary {
+                            derivation_path: self.constituent_paths.get(&drv)?.clone(),
+                            eval: eval_by_drv.get(&drv).map(|e| e.to_string()),
+                            build_status: status.for_api(),
+                        })
+                    })
+                    .collect()
+            });
             summaries.push(EntryPointSummary {
                 id: ep.id,
                 build_id,
@@ -652,6 +701,8 @@ impl EntryPointRelatedData {
                 build_time_ms: self.build_time_ms.get(&ep.derivation).copied().flatten(),
                 deps: self.deps.get(&ep.id).copied().unwrap_or_default(),
                 deps_total: drv.dep_closure_count,
+                aggregate: ep.aggregate,
+                constituents,
                 created_at: ep.created_at,
             });
         }
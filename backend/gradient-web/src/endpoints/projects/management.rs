@@ -9,6 +9,7 @@ use super::auto_attach;
 use crate::access::{Caller, OrgAccess, ProjectAccess, has_permission, load_org, load_project};
 use crate::audit::{RequestInfo, events, record as audit_record};
 use crate::authorization::{MaybeApiKey, MaybeUser};
+use crate::endpoints::user::deserialize_optional_field;
 use crate::error::{ErrorCode, WebError, WebResult};
 use crate::helpers::{OptionExt, ok_json, paginate};
 use crate::permissions::Permission;
@@ -23,6 +24,7 @@ use gradient_types::consts::*;
 use gradient_types::input::{check_project_name, validate_display_name, vec_to_hex};
 use gradient_types::triggers::{ConcurrencyPolicy, TriggerConfig, TriggerType};
 use gradient_types::wildcard::Wildcard;
+use gradient_types::worker_labels::{labels_from_json, labels_to_json, validate_labels};
 use gradient_types::*;
 use sea_orm::ActiveValue::Set;
 use sea_orm::{
@@ -55,6 +57,17 @@ pub struct PatchProjectRequest {
     pub keep_evaluations: Option<i32>,
     pub concurrency: Option<ConcurrencyPolicy>,
     pub sign_cache: Option<bool>,
+    /// When present, replace the project's worker selector. An empty map
+    /// clears it.
+    pub worker_selector: Option<Labels>,
+    /// Build limits: omit to leave alone, a number to set (`0` = no limit),
+    /// `null` to fall back to the server default.
+    #[serde(default, deserialize_with = "deserialize_optional_field")]
+    pub build_timeout_secs: Option<Option<i64>>,
+    #[serde(default, deserialize_with = "deserialize_optional_field")]
+    pub build_max_silent_secs: Option<Option<i64>>,
+    #[serde(default, deserialize_with = "deserialize_optional_field")]
+    pub max_output_size: Option<Option<i64>>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -174,6 +187,10 @@ pub async fn get(
             created_at: p.created_at,
             managed: p.managed,
             sign_cache: p.sign_cache,
+            worker_selector: labels_from_json(p.worker_selector.as_ref()),
+            build_timeout_secs: p.build_timeout_secs,
+            build_max_silent_secs: p.build_max_silent_secs,
+            max_output_size: p.max_output_size,
             can_edit,
             can_trigger,
         }
@@ -362,6 +379,10 @@ pub async fn get_project(
         keep_evaluations: project.keep_evaluations,
         concurrency: project.concurrency,
         sign_cache: project.sign_cache,
+        worker_selector: labels_from_json(project.worker_selector.as_ref()),
+        build_timeout_secs: project.build_timeout_secs,
+        build_max_silent_secs: project.build_max_silent_secs,
+        max_output_size: project.max_output_size,
         can_edit,
         can_trigger,
     }))
@@ -413,6 +434,18 @@ pub async fn patch_project(
     if let Some(sign_cache) = body.sign_cache {
         patcher.apply_sign_cache(sign_cache);
     }
+    if let Some(selector) = body.worker_selector {
+        patcher.apply_worker_selector(selector)?;
+    }
+    if let Some(secs) = body.build_timeout_secs {
+        patcher.aproject.build_timeout_secs = Set(build_limit("build_timeout_secs", secs)?);
+    }
+    if let Some(secs) = body.build_max_silent_secs {
+        patcher.aproject.build_max_silent_secs = Set(build_limit("build_max_silent_secs", secs)?);
+    }
+    if let Some(bytes) = body.max_output_size {
+        patcher.aproject.max_output_size = Set(build_limit("max_output_size", bytes)?);
+    }
 
     aproject.force_evaluation = Set(true);
     aproject.update(&state.web_db).await?;
@@ -506,6 +539,22 @@ impl<'a> ProjectPatcher<'a> {
     fn apply_sign_cache(&mut self, sign_cache: bool) {
         self.aproject.sign_cache = Set(sign_cache);
     }
+
+    fn apply_worker_selector(&mut self, selector: Labels) -> WebResult<()> {
+        validate_labels(&selector).map_err(|e| WebError::bad_request(e.to_string()))?;
+        self.aproject.worker_selector = Set(labels_to_json(&selector));
+        Ok(())
+    }
+}
+
+/// Validate a patched build limit: unset or non-negative.
+fn build_limit(field: &str, value: Option<i64>) -> WebResult<Option<i64>> {
+    if value.is_some_and(|v| v < 0) {
+        return Err(WebError::bad_request(format!(
+            "{field} must not be negative"
+        )));
+    }
+    Ok(value)
 }
 
 pub async fn delete_project(
@@ -126,6 +126,65 @@ pub async fn get_project_metrics(
     }))
 }
 
+// ── Flaky builds ─────────────────────────────────────────────────────────────
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FlakyBuildStats {
+    /// `pname` of the derivation, else its name.
+    pub name: String,
+    pub runs: i64,
+    pub failures: i64,
+    /// Consecutive final outcomes that differed.
+    pub flips: i64,
+    /// Failures re-run on another worker instead of failing outright.
+    pub reruns: i64,
+    /// Re-runs that then passed.
+    pub rerun_passes: i64,
+    pub last_passed: Option<bool>,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+/// Pass/fail history per derivation name of a project, flakiest first.
+pub async fn get_project_flaky_builds(
+    state: State<Arc<ServerState>>,
+    Extension(MaybeUser(maybe_user)): Extension<MaybeUser>,
+    Extension(api_key): Extension<MaybeApiKey>,
+    Path((organization, project)): Path<(String, String)>,
+) -> WebResult<Json<BaseResponse<Vec<FlakyBuildStats>>>> {
+    let organization = load_org(
+        &state.0,
+        Caller::from_option(&maybe_user),
+        api_key.as_ref(),
+        organization,
+        OrgAccess::Readable { label: "Project" },
+    )
+    .await?;
+
+    let project = EProject::find()
+        .filter(CProject::Organization.eq(organization.id))
+        .filter(CProject::Name.eq(project))
+        .one(&state.web_db)
+        .await?
+        .or_not_found("Project")?;
+
+    let stats = gradient_db::project_flakiness(&state.web_db, project.id)
+        .await?
+        .into_iter()
+        .map(|h| FlakyBuildStats {
+            name: h.name,
+            runs: h.runs,
+            failures: h.failures,
+            flips: h.flips,
+            reruns: h.reruns,
+            rerun_passes: h.rerun_passes,
+            last_passed: h.last_passed,
+            updated_at: h.updated_at,
+        })
+        .collect();
+
+    Ok(ok_json(stats))
+}
+
 // ── Per-entry-point metrics ──────────────────────────────────────────────────
 
 #[derive(Deserialize)]
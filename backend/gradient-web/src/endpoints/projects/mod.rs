@@ -22,7 +22,10 @@ pub use self::management::{
     delete_project_active, get, get_project, get_project_name_available, patch_project,
     post_project_active, post_project_check_repository, post_project_transfer, put,
 };
-pub use self::metrics::{EntryPointMetricsQuery, get_entry_point_metrics, get_project_metrics};
+pub use self::metrics::{
+    EntryPointMetricsQuery, FlakyBuildStats, get_entry_point_metrics, get_project_flaky_builds,
+    get_project_metrics,
+};
 
 use gradient_types::ids::*;
 
@@ -31,7 +34,7 @@ use gradient_types::ids::*;
 use gradient_entity::build::BuildStatus;
 use gradient_entity::evaluation::EvaluationStatus;
 use gradient_types::triggers::ConcurrencyPolicy;
-use gradient_types::{ProjectTriggerId, TriggerType};
+use gradient_types::{Labels, ProjectTriggerId, TriggerType};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -53,6 +56,17 @@ pub struct ProjectResponse {
     pub keep_evaluations: i32,
     pub concurrency: ConcurrencyPolicy,
     pub sign_cache: bool,
+    /// Worker labels every build of this project requires.
+    pub worker_selector: Labels,
+    /// Wall-clock build limit in seconds; `null` uses the server default,
+    /// `0` means no limit.
+    pub build_timeout_secs: Option<i64>,
+    /// Silent-output build limit in seconds; `null` uses the server default,
+    /// `0` means no limit.
+    pub build_max_silent_secs: Option<i64>,
+    /// Largest NAR, in bytes, a single build output may have; `null` or `0`
+    /// means no limit.
+    pub max_output_size: Option<i64>,
     /// Caller holds `Permission::EditProject` - may edit project configuration.
     pub can_edit: bool,
     /// Caller holds `Permission::TriggerEvaluation` - may start/restart/abort
@@ -78,9 +92,23 @@ pub struct EntryPointSummary {
     /// the derivation (content-addressed, reused across evals). `null` for evals
     /// predating the cache.
     pub deps_total: Option<i64>,
+    /// Hydra-style aggregate (`_hydraAggregate`); `build_status` is then the
+    /// combined status of its own build and its constituents.
+    pub aggregate: bool,
+    /// The aggregate's constituents; `null` for ordinary entry points.
+    pub constituents: Option<Vec<ConstituentSummary>>,
     pub created_at: chrono::NaiveDateTime,
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ConstituentSummary {
+    pub derivation_path: String,
+    /// Attribute of the constituent when it is itself an entry point of the
+    /// evaluation.
+    pub eval: Option<String>,
+    pub build_status: gradient_entity::build::BuildStatus,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct EvaluationTriggerSummary {
     pub id: ProjectTriggerId,
@@ -20,6 +20,7 @@ use gradient_core::ServerState;
 use gradient_scheduler::Scheduler;
 use gradient_sources::resolve_head;
 use gradient_types::triggers::{TriggerConfig, TriggerType};
+use gradient_types::worker_labels::{labels_from_json, labels_to_json, validate_labels};
 use gradient_types::*;
 use sea_orm::ActiveValue::Set;
 use sea_orm::{
@@ -66,6 +67,9 @@ pub struct TriggerOut {
     pub trigger_type: TriggerType,
     pub config: serde_json::Value,
     pub active: bool,
+    /// Worker labels required by builds of evaluations this trigger fires,
+    /// merged over the project's selector.
+    pub worker_selector: Labels,
     pub last_fired_at: Option<chrono::NaiveDateTime>,
     pub created_at: chrono::NaiveDateTime,
     pub updated_at: chrono::NaiveDateTime,
@@ -87,6 +91,7 @@ impl TriggerOut {
             trigger_type: m.trigger_type,
             config: m.config,
             active: m.active,
+            worker_selector: labels_from_json(m.worker_selector.as_ref()),
             last_fired_at: m.last_fired_at,
             created_at: m.created_at,
             updated_at: m.updated_at,
@@ -103,7 +108,8 @@ fn trigger_integration_id(
         .ok()
         .and_then(|cfg| match cfg {
             TriggerConfig::ReporterPush { integration_id, .. }
-            | TriggerConfig::ReporterPullRequest { integration_id, .. } => Some(integration_id),
+            | TriggerConfig::ReporterPullRequest { integration_id, .. }
+            | TriggerConfig::ReporterMergeQueue { integration_id, .. } => Some(integration_id),
             _ => None,
         })
 }
@@ -131,6 +137,8 @@ pub struct CreateBody {
     pub config: TriggerConfig,
     #[serde(default = "default_true")]
     pub active: bool,
+    #[serde(default)]
+    pub worker_selector: Labels,
 }
 
 fn default_true() -> bool {
@@ -141,6 +149,9 @@ fn default_true() -> bool {
 pub struct UpdateBody {
     pub config: Option<TriggerConfig>,
     pub active: Option<bool>,
+    /// When present, replace the trigger's worker selector. An empty map
+    /// clears it.
+    pub worker_selector: Option<Labels>,
 }
 
 #[derive(Serialize, Debug)]
@@ -203,6 +214,7 @@ pub async fn create(
     body.config
         .validate()
         .map_err(|e| WebError::bad_request(e.to_string()))?;
+    validate_labels(&body.worker_selector).map_err(|e| WebError::bad_request(e.to_string()))?;
 
     let now = Utc::now().naive_utc();
     let trigger_type = body.config.trigger_type();
@@ -214,6 +226,7 @@ pub async fn create(
         trigger_type,
         config: config_json,
         active: body.active,
+        worker_selector: labels_to_json(&body.worker_selector),
         created_at: now,
         updated_at: now,
         ..Default::default()
@@ -288,6 +301,9 @@ pub async fn update(
         cfg.validate()
             .map_err(|e| WebError::bad_request(e.to_string()))?;
     }
+    if let Some(ref selector) = body.worker_selector {
+        validate_labels(selector).map_err(|e| WebError::bad_request(e.to_string()))?;
+    }
 
     let mut active: AProjectTrigger = row.into();
     if let Some(cfg) = body.config {
@@ -298,6 +314,9 @@ pub async fn update(
     if let Some(a) = body.active {
         active.active = Set(a);
     }
+    if let Some(ref selector) = body.worker_selector {
+        active.worker_selector = Set(labels_to_json(selector));
+    }
     active.updated_at = Set(Utc::now().naive_utc());
 
     let updated = active.update(&state.web_db).await?;
@@ -410,6 +429,7 @@ pub async fn fire_now(
         repository_override: None,
         wildcard_override: None,
         source_comment: None,
+        branch: None,
         instance_max_storage_gb: state.config.storage.max_storage_gb,
     };
 
@@ -43,6 +43,13 @@ pub struct CacheStatsResponse {
     pub total_nar_bytes: i64,
     /// Total number of packages (signed build outputs) in this cache.
     pub total_packages: i64,
+    /// Bytes this cache's NARs occupy in storage: flat NARs plus the distinct
+    /// chunks of chunked ones. Equals `total_bytes` without NAR chunking.
+    pub stored_bytes: i64,
+    /// Packages of this cache stored in the chunked layout.
+    pub chunked_packages: i64,
+    /// `total_bytes / stored_bytes`; `1.0` when nothing is deduplicated.
+    pub dedup_ratio: f64,
     /// Packages/bytes added per minute for the last 60 minutes.
     pub storage_minutes: Vec<StorageMetricPoint>,
     /// Packages/bytes added per hour for the last 24 hours.
@@ -243,6 +250,8 @@ pub async fn get_cache_stats(
         .and_then(|row| row.try_get::<i64>("", "total_packages").ok())
         .unwrap_or(0);
 
+    let dedup = gradient_db::nar_dedup_stats(&state.web_db, cache.id).await?;
+
     let (storage_minutes, storage_hours, storage_days, storage_weeks, minutes, hours, days, weeks) =
         tokio::try_join!(
             aggregate_storage(
@@ -269,6 +278,9 @@ pub async fn get_cache_stats(
         total_bytes,
         total_nar_bytes,
         total_packages,
+        stored_bytes: dedup.stored_bytes(),
+        chunked_packages: dedup.chunked_nars,
+        dedup_ratio: dedup.dedup_ratio(total_bytes),
         storage_minutes,
         storage_hours,
         storage_days,
@@ -78,7 +78,7 @@ pub struct PatchApiKeyRequest {
     pub allowed_ips: Option<Vec<String>>,
 }
 
-fn deserialize_optional_field<'de, T, D>(de: D) -> Result<Option<Option<T>>, D::Error>
+pub(crate) fn deserialize_optional_field<'de, T, D>(de: D) -> Result<Option<Option<T>>, D::Error>
 where
     T: serde::Deserialize<'de>,
     D: serde::Deserializer<'de>,
@@ -0,0 +1,275 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! `/user/notifications`: the caller's own email subscriptions to
+//! evaluation/build events, independent of project actions. A subscription
+//! covers one project or, without one, every project of the caller's
+//! organizations, and can be narrowed to a branch glob and a commit/PR author.
+
+use crate::access::{Caller, ProjectAccess, load_project};
+use crate::authorization::MaybeApiKey;
+use crate::endpoints::user::deserialize_optional_field;
+use crate::error::{WebError, WebResult};
+use crate::helpers::{OptionExt, ok_json};
+use axum::extract::{Path, State};
+use axum::{Extension, Json};
+use gradient_ci::actions::FORGE_STATUS_EVENTS;
+use gradient_core::ServerState;
+use gradient_types::*;
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[derive(Deserialize, Debug)]
+pub struct CreateSubscriptionRequest {
+    /// Organization of `project`; both or neither must be set. Neither
+    /// subscribes to every project in the caller's organizations.
+    pub organization: Option<String>,
+    pub project: Option<String>,
+    pub events: Vec<String>,
+    pub branch: Option<String>,
+    pub author: Option<String>,
+    #[serde(default = "default_true")]
+    pub active: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PatchSubscriptionRequest {
+    pub events: Option<Vec<String>>,
+    /// Omit to leave alone, `null` to clear.
+    #[serde(default, deserialize_with = "deserialize_optional_field")]
+    pub branch: Option<Option<String>>,
+    /// Omit to leave alone, `null` to clear.
+    #[serde(default, deserialize_with = "deserialize_optional_field")]
+    pub author: Option<Option<String>>,
+    pub active: Option<bool>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SubscriptionResponse {
+    pub id: UserNotificationSubscriptionId,
+    pub organization: Option<String>,
+    pub project: Option<String>,
+    pub events: Vec<String>,
+    pub branch: Option<String>,
+    pub author: Option<String>,
+    pub active: bool,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+fn validate_events(events: &[String]) -> WebResult<()> {
+    if events.is_empty() {
+        return Err(WebError::unprocessable_entity(
+            "a subscription needs at least one event",
+        ));
+    }
+    if let Some(unknown) = events
+        .iter()
+        .find(|e| !FORGE_STATUS_EVENTS.contains(&e.as_str()))
+    {
+        return Err(WebError::unprocessable_entity(format!(
+            "unknown event '{}'",
+            unknown
+        )));
+    }
+    Ok(())
+}
+
+/// Trim a filter; blank means "no filter".
+fn normalize_filter(value: Option<String>) -> Option<String> {
+    value.map(|v| v.trim().to_owned()).filter(|v| !v.is_empty())
+}
+
+/// `(organization, project)` names for each subscribed project.
+async fn project_names(
+    state: &ServerState,
+    rows: &[MUserNotificationSubscription],
+) -> WebResult<HashMap<ProjectId, (String, String)>> {
+    let project_ids: Vec<ProjectId> = rows.iter().filter_map(|s| s.project).collect();
+    if project_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let projects = EProject::find()
+        .filter(CProject::Id.is_in(project_ids))
+        .all(&state.web_db)
+        .await?;
+    let orgs: HashMap<OrganizationId, String> = EOrganization::find()
+        .filter(COrganization::Id.is_in(projects.iter().map(|p| p.organization)))
+        .all(&state.web_db)
+        .await?
+        .into_iter()
+        .map(|o| (o.id, o.name))
+        .collect();
+    Ok(projects
+        .into_iter()
+        .map(|p| {
+            let org = orgs.get(&p.organization).cloned().unwrap_or_default();
+            (p.id, (org, p.name))
+        })
+        .collect())
+}
+
+fn to_response(
+    m: MUserNotificationSubscription,
+    names: &HashMap<ProjectId, (String, String)>,
+) -> SubscriptionResponse {
+    let (organization, project) = match m.project.and_then(|id| names.get(&id)) {
+        Some((org, project)) => (Some(org.clone()), Some(project.clone())),
+        None => (None, None),
+    };
+    SubscriptionResponse {
+        id: m.id,
+        organization,
+        project,
+        events: serde_json::from_value(m.events).unwrap_or_default(),
+        branch: m.branch,
+        author: m.author,
+        active: m.active,
+        created_at: m.created_at,
+        updated_at: m.updated_at,
+    }
+}
+
+async fn load_own(
+    state: &ServerState,
+    user: &MUser,
+    id: UserNotificationSubscriptionId,
+) -> WebResult<MUserNotificationSubscription> {
+    EUserNotificationSubscription::find_by_id(id)
+        .filter(CUserNotificationSubscription::User.eq(user.id))
+        .one(&state.web_db)
+        .await?
+        .or_not_found("Subscription")
+}
+
+pub async fn get_subscriptions(
+    state: State<Arc<ServerState>>,
+    Extension(user): Extension<MUser>,
+) -> WebResult<Json<BaseResponse<Vec<SubscriptionResponse>>>> {
+    let rows = EUserNotificationSubscription::find()
+        .filter(CUserNotificationSubscription::User.eq(user.id))
+        .order_by_asc(CUserNotificationSubscription::CreatedAt)
+        .all(&state.web_db)
+        .await?;
+    let names = project_names(&state, &rows).await?;
+    Ok(ok_json(
+        rows.into_iter().map(|m| to_response(m, &names)).collect(),
+    ))
+}
+
+pub async fn post_subscription(
+    state: State<Arc<ServerState>>,
+    Extension(user): Extension<MUser>,
+    Extension(api_key): Extension<MaybeApiKey>,
+    Json(body): Json<CreateSubscriptionRequest>,
+) -> WebResult<Json<BaseResponse<SubscriptionResponse>>> {
+    validate_events(&body.events)?;
+
+    let project = match (body.organization, body.project) {
+        (Some(organization), Some(project)) => {
+            let (_org, proj) = load_project(
+                &state,
+                Caller::User(&user),
+                api_key.as_ref(),
+                organization,
+                project,
+                ProjectAccess::Member,
+            )
+            .await?;
+            Some(proj.id)
+        }
+        (None, None) => None,
+        _ => {
+            return Err(WebError::unprocessable_entity(
+                "organization and project must be set together",
+            ));
+        }
+    };
+
+    let now = gradient_types::now();
+    let m = MUserNotificationSubscription {
+        id: UserNotificationSubscriptionId::now_v7(),
+        user: user.id,
+        project,
+        events: serde_json::to_value(&body.events)
+            .map_err(|e| WebError::internal(e.to_string()))?,
+        branch: normalize_filter(body.branch),
+        author: normalize_filter(body.author),
+        active: body.active,
+        created_at: now,
+        updated_at: now,
+    }
+    .into_active_model()
+    .insert(&state.web_db)
+    .await
+    .map_err(|e| WebError::from_db_err(e, "Subscription"))?;
+
+    let names = project_names(&state, std::slice::from_ref(&m)).await?;
+    Ok(ok_json(to_response(m, &names)))
+}
+
+pub async fn get_subscription(
+    state: State<Arc<ServerState>>,
+    Extension(user): Extension<MUser>,
+    Path(id): Path<UserNotificationSubscriptionId>,
+) -> WebResult<Json<BaseResponse<SubscriptionResponse>>> {
+    let m = load_own(&state, &user, id).await?;
+    let names = project_names(&state, std::slice::from_ref(&m)).await?;
+    Ok(ok_json(to_response(m, &names)))
+}
+
+pub async fn patch_subscription(
+    state: State<Arc<ServerState>>,
+    Extension(user): Extension<MUser>,
+    Path(id): Path<UserNotificationSubscriptionId>,
+    Json(body): Json<PatchSubscriptionRequest>,
+) -> WebResult<Json<BaseResponse<SubscriptionResponse>>> {
+    let row = load_own(&state, &user, id).await?;
+
+    let mut active: AUserNotificationSubscription = row.into();
+    if let Some(events) = body.events {
+        validate_events(&events)?;
+        active.events =
+            Set(serde_json::to_value(&events).map_err(|e| WebError::internal(e.to_string()))?);
+    }
+    if let Some(branch) = body.branch {
+        active.branch = Set(normalize_filter(branch));
+    }
+    if let Some(author) = body.author {
+        active.author = Set(normalize_filter(author));
+    }
+    if let Some(a) = body.active {
+        active.active = Set(a);
+    }
+    active.updated_at = Set(gradient_types::now());
+
+    let m = active
+        .update(&state.web_db)
+        .await
+        .map_err(|e| WebError::from_db_err(e, "Subscription"))?;
+    let names = project_names(&state, std::slice::from_ref(&m)).await?;
+    Ok(ok_json(to_response(m, &names)))
+}
+
+pub async fn delete_subscription(
+    state: State<Arc<ServerState>>,
+    Extension(user): Extension<MUser>,
+    Path(id): Path<UserNotificationSubscriptionId>,
+) -> WebResult<Json<BaseResponse<String>>> {
+    let row = load_own(&state, &user, id).await?;
+    let active: AUserNotificationSubscription = row.into();
+    active.delete(&state.web_db).await?;
+    Ok(ok_json("Subscription deleted".to_string()))
+}
@@ -279,6 +279,10 @@ pub fn create_router(state: Arc<ServerState>) -> Result<Router, InitError> {
             "/orgs/{organization}/workers/{worker_id}/test",
             post(orgs::post_org_worker_test),
         )
+        .route(
+            "/orgs/{organization}/workers/{worker_id}/drain",
+            get(orgs::get_org_worker_drain).post(orgs::post_org_worker_drain),
+        )
         .route(
             "/orgs/{organization}/integrations",
             get(orgs::get_integrations).put(orgs::put_integration),
@@ -407,6 +411,11 @@ pub fn create_router(state: Arc<ServerState>) -> Result<Router, InitError> {
             "/caches/{cache}/upstreams/{id}",
             patch(caches::patch_cache_upstream).delete(caches::delete_cache_upstream),
         )
+        .route("/caches/{cache}/pins", put(caches::put_cache_pin))
+        .route(
+            "/caches/{cache}/pins/{pin}",
+            axum::routing::delete(caches::delete_cache_pin),
+        )
         .route(
             "/caches/{cache}/roles",
             get(caches::roles::get_cache_roles).post(caches::roles::post_cache_role),
@@ -445,6 +454,16 @@ pub fn create_router(state: Arc<ServerState>) -> Result<Router, InitError> {
             "/user/settings",
             get(user::get_settings).patch(user::patch_settings),
         )
+        .route(
+            "/user/notifications",
+            get(user_notifications::get_subscriptions).post(user_notifications::post_subscription),
+        )
+        .route(
+            "/user/notifications/{id}",
+            get(user_notifications::get_subscription)
+                .patch(user_notifications::patch_subscription)
+                .delete(user_notifications::delete_subscription),
+        )
         .route("/auth/cli/info", get(auth::get_cli_device_info))
         .route("/auth/cli/authorize", post(auth::post_cli_device_authorize))
         .route("/auth/cli/deny", post(auth::post_cli_device_deny))
@@ -486,6 +505,10 @@ pub fn create_router(state: Arc<ServerState>) -> Result<Router, InitError> {
             "/projects/{organization}/{project}/entry-point-metrics",
             get(projects::get_entry_point_metrics),
         )
+        .route(
+            "/projects/{organization}/{project}/flaky-builds",
+            get(projects::get_project_flaky_builds),
+        )
         .route(
             "/projects/{organization}/{project}/entry-point-downloads",
             get(projects::get_entry_point_download),
@@ -504,6 +527,7 @@ pub fn create_router(state: Arc<ServerState>) -> Result<Router, InitError> {
             get(evals::get_evaluation_builds),
         )
         .route("/evals/{evaluation}/artefacts", get(evals::get_artefacts))
+        .route("/evals/{evaluation}/diff", get(evals::get_evaluation_diff))
         .route("/evals/{evaluation}/closure", get(builds::get_eval_closure))
         .route(
             "/evals/{evaluation}/flake-graph",
@@ -559,6 +583,7 @@ pub fn create_router(state: Arc<ServerState>) -> Result<Router, InitError> {
             "/caches/{cache}/upstreams",
             get(caches::get_cache_upstreams),
         )
+        .route("/caches/{cache}/pins", get(caches::get_cache_pins))
         .route("/caches/{cache}/stats", get(stats::get_cache_stats))
         .route("/caches/{cache}/nars", get(caches::nars_list))
         .route("/caches/{cache}/nars/stats", get(caches::nars_stats))
@@ -671,6 +696,8 @@ pub fn create_router(state: Arc<ServerState>) -> Result<Router, InitError> {
     scheduler.start();
     gradient_db::retention::start_retention_loop(state.db());
     gradient_db::rollup::start_rollup_loop(state.db());
+    gradient_ci::actions::start_delivery_retry_loop(state.ci());
+    gradient_ci::actions::start_digest_loop(state.ci());
     otlp::start_otlp(Arc::clone(&state), Arc::clone(&scheduler));
     gradient_proto::outbound::start_outbound_loop(Arc::clone(&scheduler));
 
@@ -714,6 +741,7 @@ pub fn create_router(state: Arc<ServerState>) -> Result<Router, InitError> {
         )
         .route("/cache/{cache}/nix-cache-info", get(caches::nix_cache_info))
         .route("/cache/{cache}/{path}", get(caches::path))
+        .route("/cache/{cache}/realisations/{id}", get(caches::realisation))
         .route(
             "/cache/{cache}/nar/upstream/{upstream_id}/{*path}",
             get(caches::upstream_nar),
@@ -13,6 +13,7 @@
 use axum_test::TestServer;
 use gradient_core::ServerState;
 use gradient_db::{WebDb, WorkerDb};
+use gradient_entity::project_action_delivery::DeliveryState;
 use gradient_entity::{
     ids::*, organization_user, project, project_action, project_action_delivery,
 };
@@ -387,6 +388,89 @@ fn create_forge_status_report_rejects_nonempty_events() {
     });
 }
 
+#[test]
+fn create_send_matrix_rejects_room_alias() {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let session_id = SessionId::now_v7();
+        let token = make_token(session_id);
+
+        let db = with_project_edit(with_auth(
+            MockDatabase::new(DatabaseBackend::Postgres),
+            session_id,
+        ));
+
+        let server = make_test_server_with(db.into_connection(), None);
+        let res = server
+            .post(BASE_URL)
+            .add_header("authorization", format!("Bearer {}", token))
+            .json(&json!({
+                "name": "matrix",
+                "config": {
+                    "type": "send_matrix",
+                    "homeserver": "https://matrix.example.org",
+                    "room_id": "#ops:example.org",
+                    "access_token": "syt_secret",
+                },
+                "events": ["evaluation.failed"],
+            }))
+            .await;
+
+        res.assert_status(axum::http::StatusCode::UNPROCESSABLE_ENTITY);
+        let body: Value = res.json();
+        assert_eq!(body["error"], true);
+        assert!(
+            body["message"].as_str().unwrap().contains("room_id"),
+            "expected room_id mention, got: {}",
+            body["message"]
+        );
+    });
+}
+
+#[test]
+fn create_send_web_request_rejects_invalid_body_template() {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let session_id = SessionId::now_v7();
+        let token = make_token(session_id);
+
+        let db = with_project_edit(with_auth(
+            MockDatabase::new(DatabaseBackend::Postgres),
+            session_id,
+        ));
+
+        let server = make_test_server_with(db.into_connection(), None);
+        let res = server
+            .post(BASE_URL)
+            .add_header("authorization", format!("Bearer {}", token))
+            .json(&json!({
+                "name": "hook",
+                "config": {
+                    "type": "send_web_request",
+                    "url": "https://example.com/hook",
+                    "body_template": "{\"summary\": \"{{status\"}",
+                },
+                "events": ["evaluation.failed"],
+            }))
+            .await;
+
+        res.assert_status(axum::http::StatusCode::UNPROCESSABLE_ENTITY);
+        let body: Value = res.json();
+        assert_eq!(body["error"], true);
+        assert!(
+            body["message"].as_str().unwrap().contains("body_template"),
+            "expected body_template mention, got: {}",
+            body["message"]
+        );
+    });
+}
+
 #[test]
 fn read_action_strips_token_from_config() {
     let rt = tokio::runtime::Builder::new_current_thread()
@@ -544,6 +628,57 @@ fn regenerate_token_rejects_non_web_request_action() {
     });
 }
 
+#[test]
+fn test_delivery_dry_run_renders_body_template() {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let session_id = SessionId::now_v7();
+        let token = make_token(session_id);
+
+        let mut row = web_request_action_row();
+        row.config = json!({
+            "type": "send_web_request",
+            "url": "https://example.com/hook",
+            "method": "put",
+            "headers": { "X-Routing-Key": "ops" },
+            "body_template": "{\"summary\": \"{{repository}}: {{event}}\", \"dedup\": \"{{evaluation_id}}\", \"kind\": \"{{evaluation_kind}}\"}",
+        });
+        let db = with_project_edit(with_auth(
+            MockDatabase::new(DatabaseBackend::Postgres),
+            session_id,
+        ))
+        .append_query_results([vec![row]]);
+
+        let server = make_test_server_with(db.into_connection(), None);
+        let url = format!("{}/{}/test-delivery", BASE_URL, action_id());
+        let res = server
+            .post(&url)
+            .add_header("authorization", format!("Bearer {}", token))
+            .json(&json!({ "event": "evaluation.failed", "dry_run": true }))
+            .await;
+
+        res.assert_status_ok();
+        let body: Value = res.json();
+        assert_eq!(body["error"], false);
+        let msg = &body["message"];
+        assert_eq!(msg["method"], "put");
+        assert_eq!(msg["headers"]["X-Routing-Key"], "ops");
+        // The sample is keyed like a real `evaluation.failed` payload.
+        assert_eq!(
+            msg["body"],
+            json!({
+                "summary": "https://github.com/test/repo: evaluation.failed",
+                "dedup": "00000000-0000-0000-0000-000000000000",
+                "kind": "normal",
+            })
+        );
+        assert_eq!(msg["sent"], false);
+    });
+}
+
 fn delivery_id() -> ProjectActionDeliveryId {
     ProjectActionDeliveryId::new(Uuid::parse_str("00000000-0000-0000-0000-0000000000d1").unwrap())
 }
@@ -639,6 +774,82 @@ fn get_delivery_detail_includes_bodies() {
     });
 }
 
+#[test]
+fn list_dead_deliveries_across_project_actions() {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let session_id = SessionId::now_v7();
+        let token = make_token(session_id);
+
+        let dead = project_action_delivery::Model {
+            success: false,
+            response_status: Some(503),
+            state: DeliveryState::Dead,
+            attempt: 8,
+            ..delivery_row()
+        };
+        let db = with_project_member(with_auth(
+            MockDatabase::new(DatabaseBackend::Postgres),
+            session_id,
+        ))
+        .append_query_results([vec![send_mail_action_row()]])
+        .append_query_results([vec![dead]]);
+
+        let server = make_test_server_with(db.into_connection(), None);
+        let url = format!("{}/dead-deliveries", BASE_URL);
+        let res = server
+            .get(&url)
+            .add_header("authorization", format!("Bearer {}", token))
+            .await;
+
+        res.assert_status_ok();
+        let body: Value = res.json();
+        let items = body["message"].as_array().expect("array");
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0]["state"], "dead");
+        assert_eq!(items[0]["attempt"], 8);
+        assert_eq!(items[0]["action_id"], action_id().to_string());
+    });
+}
+
+#[test]
+fn redeliver_rejects_delivery_that_is_not_dead() {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let session_id = SessionId::now_v7();
+        let token = make_token(session_id);
+
+        let db = with_project_edit(with_auth(
+            MockDatabase::new(DatabaseBackend::Postgres),
+            session_id,
+        ))
+        .append_query_results([vec![send_mail_action_row()]])
+        .append_query_results([vec![delivery_row()]]);
+
+        let server = make_test_server_with(db.into_connection(), None);
+        let url = format!(
+            "{}/{}/deliveries/{}/redeliver",
+            BASE_URL,
+            action_id(),
+            delivery_id()
+        );
+        let res = server
+            .post(&url)
+            .add_header("authorization", format!("Bearer {}", token))
+            .await;
+
+        res.assert_status(axum::http::StatusCode::UNPROCESSABLE_ENTITY);
+        let body: Value = res.json();
+        assert_eq!(body["error"], true);
+    });
+}
+
 #[test]
 fn list_deliveries_404_on_unknown_action() {
     let rt = tokio::runtime::Builder::new_current_thread()
@@ -11,6 +11,8 @@
 //!   signature, integration not found, non-matching branch glob is skipped,
 //!   PR event fires, PR action mismatch is skipped, release event fires.
 //! - GitHub App: push fires, ping, installation, not configured.
+//! - Bitbucket Cloud/Server: push fires, unprefixed signature rejected, PR
+//!   update fires a `synchronize` trigger. Forgejo-named headers (Codeberg).
 //!
 //! Uses manual Tokio runtimes because `#[tokio::test]` expands to
 //! `::gradient_core::…` which clashes with the local `core` crate name.
@@ -1266,3 +1268,253 @@ async fn github_app_webhook_no_matching_repo_returns_zero_inner() {
     assert!(msg["queued"].as_array().unwrap().is_empty());
     assert!(msg["skipped"].as_array().unwrap().is_empty());
 }
+
+// ── Bitbucket Cloud - push fires trigger ──────────────────────────────────────
+
+fn project_at(repository: &str) -> gradient_entity::project::Model {
+    project_row_with(project_id(), org_id(), "test-project", repository)
+}
+
+#[test]
+fn bitbucket_webhook_push_fires_trigger() {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    rt.block_on(async { bitbucket_webhook_push_fires_trigger_inner().await });
+}
+
+async fn bitbucket_webhook_push_fires_trigger_inner() {
+    let plaintext_secret = "test-secret-plaintext";
+    let crypt_path = temp_secret_file("this-is-a-32-byte-crypt-key!!!!");
+    let ciphertext = encrypt_webhook_secret(&crypt_path, plaintext_secret).expect("encrypt");
+
+    let push_body = format!(
+        r#"{{
+            "push": {{
+                "changes": [{{
+                    "new": {{
+                        "type": "branch",
+                        "name": "main",
+                        "target": {{
+                            "hash": "{VALID_SHA}",
+                            "message": "Bump flake.lock\n",
+                            "author": {{ "raw": "Jane <jane@example.com>", "user": {{ "display_name": "Jane" }} }}
+                        }}
+                    }}
+                }}]
+            }},
+            "repository": {{
+                "full_name": "acme/repo",
+                "links": {{ "html": {{ "href": "https://bitbucket.org/acme/repo" }} }}
+            }}
+        }}"#
+    );
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![org_row("test-org")]])
+        .append_query_results([vec![integration_row(&ciphertext)]])
+        .append_query_results([vec![trigger_row(reporter_push_trigger(vec!["main"]))]])
+        .append_query_results([vec![project_at("git@bitbucket.org:acme/repo.git")]])
+        .append_query_results([vec![org_row("test-org")]]);
+    let db = apply_trigger_db_chain(db).into_connection();
+
+    let state = make_state(db, Some(crypt_path), None);
+    let router = create_router(state).expect("router");
+    let server = TestServer::new(router);
+
+    let body_bytes: Vec<u8> = push_body.into_bytes();
+    let sig = github_signature(plaintext_secret, &body_bytes);
+
+    let response = server
+        .post("/api/v1/hooks/bitbucket/test-org/my-hook")
+        .add_header("X-Event-Key", "repo:push")
+        .add_header("X-Hub-Signature", &sig)
+        .bytes(body_bytes.into())
+        .await;
+
+    response.assert_status_ok();
+    let json: Value = response.json();
+    assert_eq!(json["error"], false);
+    let msg = &json["message"];
+    assert_eq!(msg["event"], "push");
+    assert_eq!(msg["projects_scanned"], 1);
+    assert_eq!(msg["queued"].as_array().unwrap().len(), 1);
+    assert!(msg["skipped"].as_array().unwrap().is_empty());
+}
+
+// ── Bitbucket Cloud - unprefixed signature → 401 ──────────────────────────────
+
+#[test]
+fn bitbucket_webhook_rejects_unprefixed_signature() {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    rt.block_on(async { bitbucket_webhook_rejects_unprefixed_signature_inner().await });
+}
+
+async fn bitbucket_webhook_rejects_unprefixed_signature_inner() {
+    let plaintext_secret = "test-secret-plaintext";
+    let crypt_path = temp_secret_file("this-is-a-32-byte-crypt-key!!!!");
+    let ciphertext = encrypt_webhook_secret(&crypt_path, plaintext_secret).expect("encrypt");
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![org_row("test-org")]])
+        .append_query_results([vec![integration_row(&ciphertext)]])
+        .into_connection();
+
+    let state = make_state(db, Some(crypt_path), None);
+    let router = create_router(state).expect("router");
+    let server = TestServer::new(router);
+
+    // A correct HMAC without the `sha256=` prefix Bitbucket always sends.
+    let body = br#"{"push":{"changes":[]},"repository":{"full_name":"acme/repo"}}"#;
+    let bare_sig = gitea_signature(plaintext_secret, body);
+
+    let response = server
+        .post("/api/v1/hooks/bitbucket/test-org/my-hook")
+        .add_header("X-Event-Key", "repo:push")
+        .add_header("X-Hub-Signature", &bare_sig)
+        .bytes(body.as_slice().into())
+        .await;
+
+    response.assert_status_unauthorized();
+    let json: Value = response.json();
+    assert_eq!(json["message"], "invalid webhook signature");
+}
+
+// ── Bitbucket Server - PR update maps to `synchronize` ────────────────────────
+
+#[test]
+fn bitbucket_server_webhook_pr_update_fires_synchronize_trigger() {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        bitbucket_server_webhook_pr_update_fires_synchronize_trigger_inner().await
+    });
+}
+
+async fn bitbucket_server_webhook_pr_update_fires_synchronize_trigger_inner() {
+    let plaintext_secret = "test-secret-plaintext";
+    let crypt_path = temp_secret_file("this-is-a-32-byte-crypt-key!!!!");
+    let ciphertext = encrypt_webhook_secret(&crypt_path, plaintext_secret).expect("encrypt");
+
+    let repo = r#"{
+        "slug": "repo",
+        "project": { "key": "ACME" },
+        "links": {
+            "clone": [
+                { "href": "https://bitbucket.example.com/scm/acme/repo.git", "name": "http" },
+                { "href": "ssh://git@bitbucket.example.com:7999/acme/repo.git", "name": "ssh" }
+            ]
+        }
+    }"#;
+    let pr_body = format!(
+        r#"{{
+            "eventKey": "pr:from_ref_updated",
+            "actor": {{ "name": "jdoe" }},
+            "pullRequest": {{
+                "id": 7,
+                "title": "Bump inputs",
+                "author": {{ "user": {{ "name": "jdoe" }} }},
+                "fromRef": {{ "displayId": "bump", "latestCommit": "{VALID_SHA}", "repository": {repo} }},
+                "toRef": {{ "displayId": "main", "latestCommit": "{VALID_SHA}", "repository": {repo} }}
+            }}
+        }}"#
+    );
+
+    // Trigger only fires on `synchronize`, which Bitbucket Server expresses
+    // through the `X-Event-Key` header rather than the payload.
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![org_row("test-org")]])
+        .append_query_results([vec![integration_row(&ciphertext)]])
+        .append_query_results([vec![trigger_row(reporter_pr_trigger(vec!["synchronize"]))]])
+        .append_query_results([vec![project_at(
+            "https://bitbucket.example.com/scm/acme/repo.git",
+        )]])
+        .append_query_results([vec![org_row("test-org")]]);
+    let db = apply_trigger_db_chain(db).into_connection();
+
+    let state = make_state(db, Some(crypt_path), None);
+    let router = create_router(state).expect("router");
+    let server = TestServer::new(router);
+
+    let body_bytes: Vec<u8> = pr_body.into_bytes();
+    let sig = github_signature(plaintext_secret, &body_bytes);
+
+    let response = server
+        .post("/api/v1/hooks/bitbucket-server/test-org/my-hook")
+        .add_header("X-Event-Key", "pr:from_ref_updated")
+        .add_header("X-Hub-Signature", &sig)
+        .bytes(body_bytes.into())
+        .await;
+
+    response.assert_status_ok();
+    let json: Value = response.json();
+    assert_eq!(json["error"], false);
+    let msg = &json["message"];
+    assert_eq!(msg["event"], "pull_request");
+    assert_eq!(msg["projects_scanned"], 1);
+    assert_eq!(msg["queued"].as_array().unwrap().len(), 1);
+    assert!(msg["skipped"].as_array().unwrap().is_empty());
+}
+
+// ── Forgejo (Codeberg) - Forgejo-named headers fire trigger ───────────────────
+
+#[test]
+fn forgejo_webhook_push_with_forgejo_headers_fires_trigger() {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    rt.block_on(async { forgejo_webhook_push_with_forgejo_headers_fires_trigger_inner().await });
+}
+
+async fn forgejo_webhook_push_with_forgejo_headers_fires_trigger_inner() {
+    let plaintext_secret = "test-secret-plaintext";
+    let crypt_path = temp_secret_file("this-is-a-32-byte-crypt-key!!!!");
+    let ciphertext = encrypt_webhook_secret(&crypt_path, plaintext_secret).expect("encrypt");
+
+    let push_body = format!(
+        r#"{{
+            "ref": "refs/heads/main",
+            "after": "{VALID_SHA}",
+            "repository": {{
+                "clone_url": "https://codeberg.org/acme/repo.git",
+                "ssh_url": "git@codeberg.org:acme/repo.git"
+            }}
+        }}"#
+    );
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![org_row("test-org")]])
+        .append_query_results([vec![integration_row(&ciphertext)]])
+        .append_query_results([vec![trigger_row(reporter_push_trigger(vec![]))]])
+        .append_query_results([vec![project_at("https://codeberg.org/acme/repo")]])
+        .append_query_results([vec![org_row("test-org")]]);
+    let db = apply_trigger_db_chain(db).into_connection();
+
+    let state = make_state(db, Some(crypt_path), None);
+    let router = create_router(state).expect("router");
+    let server = TestServer::new(router);
+
+    let body_bytes: Vec<u8> = push_body.into_bytes();
+    let sig = gitea_signature(plaintext_secret, &body_bytes);
+
+    let response = server
+        .post("/api/v1/hooks/forgejo/test-org/my-hook")
+        .add_header("X-Forgejo-Event", "push")
+        .add_header("X-Forgejo-Signature", &sig)
+        .bytes(body_bytes.into())
+        .await;
+
+    response.assert_status_ok();
+    let json: Value = response.json();
+    assert_eq!(json["error"], false);
+    let msg = &json["message"];
+    assert_eq!(msg["projects_scanned"], 1);
+    assert_eq!(msg["queued"].as_array().unwrap().len(), 1);
+}
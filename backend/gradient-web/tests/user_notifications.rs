@@ -0,0 +1,132 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Integration tests for the `/user/notifications` subscription endpoints.
+//!
+//! Pattern: manual Tokio runtime + `axum_test::TestServer` + `MockDatabase`,
+//! with the same session → session → user auth sequence as `triggers.rs`.
+
+use gradient_entity::{ids::*, project, user_notification_subscription};
+use gradient_test_support::fixtures::{org, org_id, project_id, test_date, user, user_id};
+use gradient_test_support::web::{live_session, make_test_server, make_token};
+use gradient_types::SessionId;
+use sea_orm::{DatabaseBackend, MockDatabase};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+fn with_auth(db: MockDatabase, session_id: SessionId) -> MockDatabase {
+    let session = live_session(session_id);
+    db.append_query_results([vec![session.clone()]])
+        .append_query_results([vec![session]])
+        .append_query_results([vec![user()]])
+}
+
+fn subscription_row() -> user_notification_subscription::Model {
+    user_notification_subscription::Model {
+        id: UserNotificationSubscriptionId::new(
+            Uuid::parse_str("00000000-0000-0000-0000-0000000000c1").unwrap(),
+        ),
+        user: user_id(),
+        project: Some(project_id()),
+        events: json!(["evaluation.failed"]),
+        branch: Some("release/*".into()),
+        author: None,
+        active: true,
+        created_at: test_date(),
+        updated_at: test_date(),
+    }
+}
+
+fn project_row() -> project::Model {
+    project::Model {
+        id: project_id(),
+        organization: org_id(),
+        name: "test-project".into(),
+        created_by: user_id(),
+        created_at: test_date(),
+        ..Default::default()
+    }
+}
+
+const BASE_URL: &str = "/api/v1/user/notifications";
+
+#[test]
+fn list_subscriptions_resolves_project_names() {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let session_id = SessionId::now_v7();
+        let token = make_token(session_id);
+
+        let db = with_auth(MockDatabase::new(DatabaseBackend::Postgres), session_id)
+            .append_query_results([vec![subscription_row()]])
+            .append_query_results([vec![project_row()]])
+            .append_query_results([vec![org()]]);
+
+        let server = make_test_server(db.into_connection());
+        let res = server
+            .get(BASE_URL)
+            .add_header("authorization", format!("Bearer {}", token))
+            .await;
+
+        res.assert_status_ok();
+        let body: Value = res.json();
+        let items = body["message"].as_array().expect("message is array");
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0]["organization"], org().name);
+        assert_eq!(items[0]["project"], "test-project");
+        assert_eq!(items[0]["events"], json!(["evaluation.failed"]));
+        assert_eq!(items[0]["branch"], "release/*");
+    });
+}
+
+#[test]
+fn create_subscription_rejects_unknown_event() {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let session_id = SessionId::now_v7();
+        let token = make_token(session_id);
+
+        let db = with_auth(MockDatabase::new(DatabaseBackend::Postgres), session_id);
+
+        let server = make_test_server(db.into_connection());
+        let res = server
+            .post(BASE_URL)
+            .add_header("authorization", format!("Bearer {}", token))
+            .json(&json!({ "events": ["evaluation.exploded"] }))
+            .await;
+
+        res.assert_status(axum::http::StatusCode::UNPROCESSABLE_ENTITY);
+    });
+}
+
+#[test]
+fn create_subscription_requires_organization_with_project() {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let session_id = SessionId::now_v7();
+        let token = make_token(session_id);
+
+        let db = with_auth(MockDatabase::new(DatabaseBackend::Postgres), session_id);
+
+        let server = make_test_server(db.into_connection());
+        let res = server
+            .post(BASE_URL)
+            .add_header("authorization", format!("Bearer {}", token))
+            .json(&json!({ "project": "test-project", "events": ["build.failed"] }))
+            .await;
+
+        res.assert_status(axum::http::StatusCode::UNPROCESSABLE_ENTITY);
+    });
+}
@@ -6,6 +6,7 @@
 
 use clap::Parser;
 use gradient_types::proto::GradientCapabilities;
+use gradient_types::worker_labels::parse_label;
 
 /// Default eval-pool size: host parallelism capped at 16. Each worker may hold
 /// up to `max_eval_rss` resident, so the cap bounds eval memory on big hosts.
@@ -222,6 +223,18 @@ pub struct WorkerConfig {
     #[arg(long, env = "GRADIENT_WORKER_SYSTEM_FEATURES", value_delimiter = ',')]
     pub system_features: Option<Vec<String>>,
 
+    /// Comma-separated `key=value` labels this worker advertises
+    /// (e.g. `pool=secure,region=eu`). Projects select workers by label;
+    /// labels an organization sets on this worker's registration override
+    /// these for that organization's builds.
+    #[arg(
+        long,
+        env = "GRADIENT_WORKER_LABELS",
+        value_delimiter = ',',
+        value_parser = parse_label
+    )]
+    pub labels: Vec<(String, String)>,
+
     /// Override the single-core speed score advertised to the scheduler.
     /// When unset, the worker runs a deterministic micro-benchmark at startup.
     #[arg(long, env = "GRADIENT_WORKER_CPU_CORE_SCORE")]
@@ -449,6 +462,7 @@ mod tests {
             capability_build: false,
             architectures: None,
             system_features: None,
+            labels: vec![],
             cpu_core_score: None,
             build_metrics: true,
             build_cgroup_root: "/sys/fs/cgroup".to_owned(),
@@ -562,6 +576,7 @@ mod tests {
             capability_build: false,
             architectures: None,
             system_features: None,
+            labels: vec![],
             cpu_core_score: None,
             build_metrics: true,
             build_cgroup_root: "/sys/fs/cgroup".to_owned(),
@@ -638,6 +653,7 @@ mod tests {
             capability_build: false,
             architectures: None,
             system_features: None,
+            labels: vec![],
             cpu_core_score: None,
             build_metrics: true,
             build_cgroup_root: "/sys/fs/cgroup".to_owned(),
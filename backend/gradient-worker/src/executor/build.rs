@@ -402,6 +402,9 @@ pub async fn build_derivation(
     let metrics = assemble_build_metrics(cgroup_raw, cpu_usec, build_time_ms, peak_network_mbps);
 
     let (outputs, substituted, _) = realize_result?;
+    if let Some(limit) = task.max_output_size {
+        check_output_sizes(store, &outputs, limit).await?;
+    }
     updater
         .report_build_output(
             task.build_id.clone(),
@@ -414,6 +417,35 @@ pub async fn build_derivation(
     Ok(outputs)
 }
 
+/// Fail the build when any output's NAR exceeds the project's
+/// `max_output_size`. Runs before the outputs are reported or uploaded, so an
+/// oversized output never reaches the cache.
+async fn check_output_sizes(
+    store: &LocalNixStore,
+    outputs: &[BuildOutput],
+    limit: u64,
+) -> Result<(), BuildError> {
+    for o in outputs {
+        let size = store
+            .nar_size(&o.store_path)
+            .await
+            .map_err(BuildError::transient)?;
+        if let Some(e) = output_size_error(&o.name, &o.store_path, size, limit) {
+            return Err(BuildError::permanent(e));
+        }
+    }
+
+    Ok(())
+}
+
+fn output_size_error(name: &str, store_path: &str, size: u64, limit: u64) -> Option<anyhow::Error> {
+    (size > limit).then(|| {
+        anyhow::anyhow!(
+            "output '{name}' ({store_path}) is {size} bytes, over the project's max output size of {limit} bytes"
+        )
+    })
+}
+
 // ── Log helpers ───────────────────────────────────────────────────────────────
 
 /// When a derivation is already built locally the daemon produces no log.
@@ -920,4 +952,15 @@ mod tests {
         assert_eq!(products[0].name, "index.html");
         assert_eq!(products[0].size, Some(13));
     }
+
+    #[test]
+    fn output_over_max_size_fails_with_sizes_in_message() {
+        assert!(output_size_error("out", "/nix/store/aaaa-big", 1024, 1024).is_none());
+
+        let err = output_size_error("out", "/nix/store/aaaa-big", 2048, 1024).unwrap();
+        assert_eq!(
+            err.to_string(),
+            "output 'out' (/nix/store/aaaa-big) is 2048 bytes, over the project's max output size of 1024 bytes"
+        );
+    }
 }
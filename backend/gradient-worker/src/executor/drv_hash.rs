@@ -0,0 +1,266 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+//! Derivation hash modulo: the key a floating content-addressed output's
+//! realisation is stored under (`realisations/sha256:<hash>!<output>.doi`).
+//!
+//! Mirrors Nix's `hashDerivationModulo`. A fixed-output derivation hashes to
+//! its fixed content hash; any other derivation to the SHA-256 of its ATerm
+//! with every input `.drv` path replaced by that input's own hash modulo, so
+//! the hash only changes when what gets built does. The realisation key also
+//! masks the derivation's own output paths and output env vars.
+
+use crate::traits::DrvReader;
+use anyhow::{Context, Result, anyhow};
+use gradient_db::{Derivation, parse_drv};
+use sha2::{Digest as _, Sha256};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Output name → base16 SHA-256 hash modulo.
+type OutputHashes = BTreeMap<String, String>;
+
+/// Computes realisation keys, reading input `.drv` files through a
+/// [`DrvReader`]. Input hashes are memoised, so one hasher shared across an
+/// evaluation reads each `.drv` of the closure at most once.
+pub(super) struct DrvHasher<'a> {
+    drv_reader: &'a dyn DrvReader,
+    memo: HashMap<String, OutputHashes>,
+}
+
+impl<'a> DrvHasher<'a> {
+    pub(super) fn new(drv_reader: &'a dyn DrvReader) -> Self {
+        Self {
+            drv_reader,
+            memo: HashMap::new(),
+        }
+    }
+
+    /// Realisation key of `drv` in `sha256:<base16>` form.
+    pub(super) async fn realisation_hash(&mut self, drv: &Derivation) -> Result<String> {
+        let inputs = self.input_hashes(drv).await?;
+        let hashes = hash_modulo(drv, true, &inputs)?;
+        let hash = hashes
+            .into_values()
+            .next()
+            .ok_or_else(|| anyhow!("derivation has no outputs"))?;
+        Ok(format!("sha256:{hash}"))
+    }
+
+    async fn input_hashes(&mut self, drv: &Derivation) -> Result<HashMap<String, OutputHashes>> {
+        let mut inputs = HashMap::with_capacity(drv.input_derivations.len());
+        for (path, _) in &drv.input_derivations {
+            let hashes = Box::pin(self.path_hash(path)).await?;
+            inputs.insert(path.clone(), hashes);
+        }
+        Ok(inputs)
+    }
+
+    /// Nix's `pathDerivationModulo`: the unmasked hash of an input `.drv`.
+    async fn path_hash(&mut self, path: &str) -> Result<OutputHashes> {
+        if let Some(hashes) = self.memo.get(path) {
+            return Ok(hashes.clone());
+        }
+        let bytes = self
+            .drv_reader
+            .read_drv(path)
+            .await
+            .with_context(|| format!("read input .drv {path}"))?;
+        let drv = parse_drv(&bytes).with_context(|| format!("parse input .drv {path}"))?;
+        let inputs = self.input_hashes(&drv).await?;
+        let hashes = hash_modulo(&drv, false, &inputs)?;
+        self.memo.insert(path.to_string(), hashes.clone());
+        Ok(hashes)
+    }
+}
+
+/// Hash modulo of `drv` per output, given the hashes of its input `.drv`s.
+fn hash_modulo(
+    drv: &Derivation,
+    mask_outputs: bool,
+    inputs: &HashMap<String, OutputHashes>,
+) -> Result<OutputHashes> {
+    let fixed = !drv.outputs.is_empty()
+        && drv
+            .outputs
+            .iter()
+            .all(|o| !o.hash_algo.is_empty() && !o.hash.is_empty());
+    if fixed {
+        return Ok(drv
+            .outputs
+            .iter()
+            .map(|o| {
+                let fingerprint = format!("fixed:out:{}:{}:{}", o.hash_algo, o.hash, o.path);
+                (o.name.clone(), sha256_hex(fingerprint.as_bytes()))
+            })
+            .collect());
+    }
+
+    let mut replaced: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for (path, outputs) in &drv.input_derivations {
+        let hashes = inputs
+            .get(path)
+            .ok_or_else(|| anyhow!("no hash for input derivation {path}"))?;
+        for output in outputs {
+            let hash = hashes
+                .get(output)
+                .ok_or_else(|| anyhow!("input derivation {path} has no output {output}"))?;
+            replaced
+                .entry(hash.clone())
+                .or_default()
+                .insert(output.clone());
+        }
+    }
+
+    let hash = sha256_hex(unparse(drv, mask_outputs, &replaced).as_bytes());
+    Ok(drv
+        .outputs
+        .iter()
+        .map(|o| (o.name.clone(), hash.clone()))
+        .collect())
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// Serialise `drv` back to ATerm with `inputs` in place of its input `.drv`s.
+/// Outputs, input sources and env are sorted the way Nix writes them.
+fn unparse(
+    drv: &Derivation,
+    mask_outputs: bool,
+    inputs: &BTreeMap<String, BTreeSet<String>>,
+) -> String {
+    let mut outputs: Vec<_> = drv.outputs.iter().collect();
+    outputs.sort_by(|a, b| a.name.cmp(&b.name));
+    let outputs = outputs.iter().map(|o| {
+        let path = if mask_outputs { "" } else { o.path.as_str() };
+        format!(
+            "({},{},{},{})",
+            quote(&o.name),
+            quote(path),
+            quote(&o.hash_algo),
+            quote(&o.hash)
+        )
+    });
+    let inputs = inputs
+        .iter()
+        .map(|(hash, outputs)| format!("({},{})", quote(hash), list(outputs.iter())));
+    let sources: BTreeSet<_> = drv.input_sources.iter().collect();
+    let mut env: Vec<_> = drv.environment.iter().collect();
+    env.sort();
+    let env = env.into_iter().map(|(key, value)| {
+        let masked = mask_outputs && drv.outputs.iter().any(|o| &o.name == key);
+        let value = if masked { "" } else { value.as_str() };
+        format!("({},{})", quote(key), quote(value))
+    });
+
+    format!(
+        "Derive([{}],[{}],{},{},{},{},[{}])",
+        outputs.collect::<Vec<_>>().join(","),
+        inputs.collect::<Vec<_>>().join(","),
+        list(sources.into_iter()),
+        quote(&drv.system),
+        quote(&drv.builder),
+        list(drv.args.iter()),
+        env.collect::<Vec<_>>().join(","),
+    )
+}
+
+fn list<'s>(items: impl Iterator<Item = &'s String>) -> String {
+    let items: Vec<String> = items.map(|s| quote(s)).collect();
+    format!("[{}]", items.join(","))
+}
+
+/// ATerm string literal, escaped the way `parse_drv` unescapes it.
+fn quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CA_DRV: &[u8] = br#"Derive([("dev","","r:sha256",""),("out","","r:sha256","")],[("/nix/store/bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb-src.drv",["out"])],["/nix/store/cccccccccccccccccccccccccccccccc-builder.sh"],"x86_64-linux","/nix/store/dddddddddddddddddddddddddddddddd-bash",["-e","/nix/store/cccccccccccccccccccccccccccccccc-builder.sh"],[("dev","/1ril1qzj3wpn1gzvkb2j8gwvzfrjsymq5alb5vwkgp2yqd0hd7jm"),("name","ca\n\"quoted\""),("out","/1rz4g4znpzjwh1xymhjpm42vipw92pr73vdgl6xs1hycac8kf2n9"),("system","x86_64-linux")])"#;
+    const FOD: &[u8] = br#"Derive([("out","/nix/store/eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee-src.tar.gz","sha256","0123abcd")],[],[],"x86_64-linux","builtin:fetchurl",[],[("name","src.tar.gz"),("out","/nix/store/eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee-src.tar.gz")])"#;
+
+    fn fod_inputs() -> HashMap<String, OutputHashes> {
+        let fod = parse_drv(FOD).unwrap();
+        let hashes = hash_modulo(&fod, false, &HashMap::new()).unwrap();
+        HashMap::from([(
+            "/nix/store/bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb-src.drv".to_string(),
+            hashes,
+        )])
+    }
+
+    #[test]
+    fn fixed_output_hashes_its_content_not_its_drv() {
+        let fod = parse_drv(FOD).unwrap();
+        let hashes = hash_modulo(&fod, false, &HashMap::new()).unwrap();
+        assert_eq!(
+            hashes["out"],
+            sha256_hex(
+                b"fixed:out:sha256:0123abcd:/nix/store/eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee-src.tar.gz"
+            )
+        );
+
+        let renamed = parse_drv(
+            &String::from_utf8_lossy(FOD)
+                .replace("builtin:fetchurl", "/bin/curl")
+                .into_bytes(),
+        )
+        .unwrap();
+        assert_eq!(
+            hash_modulo(&renamed, false, &HashMap::new()).unwrap(),
+            hashes
+        );
+    }
+
+    #[test]
+    fn unparse_replaces_inputs_and_masks_outputs() {
+        let drv = parse_drv(CA_DRV).unwrap();
+        let inputs = BTreeMap::from([("abc123".to_string(), BTreeSet::from(["out".to_string()]))]);
+        let aterm = unparse(&drv, true, &inputs);
+        assert!(aterm.starts_with(
+            r#"Derive([("dev","","r:sha256",""),("out","","r:sha256","")],[("abc123",["out"])],"#
+        ));
+        assert!(aterm.contains(r#"("dev",""),("name","ca\n\"quoted\""),("out",""),"#));
+        assert!(!unparse(&drv, false, &inputs).contains(r#"("out",""),"#));
+
+        // Unmasked, the serialisation round-trips through the parser.
+        let reparsed = parse_drv(unparse(&drv, false, &inputs).as_bytes()).unwrap();
+        assert_eq!(reparsed.environment, drv.environment);
+        assert_eq!(reparsed.args, drv.args);
+        assert_eq!(reparsed.outputs, drv.outputs);
+    }
+
+    #[test]
+    fn outputs_share_one_hash_that_follows_inputs() {
+        let drv = parse_drv(CA_DRV).unwrap();
+        let hashes = hash_modulo(&drv, true, &fod_inputs()).unwrap();
+        assert_eq!(hashes.len(), 2);
+        assert_eq!(hashes["dev"], hashes["out"]);
+
+        let mut other = fod_inputs();
+        for hashes in other.values_mut() {
+            hashes.insert("out".into(), sha256_hex(b"different source"));
+        }
+        assert_ne!(hash_modulo(&drv, true, &other).unwrap(), hashes);
+        assert!(hash_modulo(&drv, true, &HashMap::new()).is_err());
+    }
+}
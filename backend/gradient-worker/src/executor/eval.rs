@@ -13,18 +13,19 @@
 //! No database access occurs here - all DB writes are done server-side when the
 //! server receives the `EvalResult` [`JobUpdateKind`].
 
-use std::collections::{HashSet, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 use std::time::Instant;
 
+use super::drv_hash::DrvHasher;
 use crate::worker_pool::{WorkerPoolResolver, budgeted_pool_size};
 use anyhow::{Context, Result};
 use futures::stream::{FuturesUnordered, StreamExt as _};
 use gradient_db::parse_drv;
-use gradient_nix::{DerivationResolver, FlakeDiscovery};
+use gradient_nix::{DerivationResolver, FlakeDiscovery, MetaLimits};
 use gradient_proto::messages::{
     DerivationOutput, DiscoveredDerivation, EvalAttrCost, EvalStatsReport, FlakeJob,
-    FlakeOutputNode, FlakeSource,
+    FlakeOutputNode, FlakeSource, FloatingOutputs,
 };
 use tokio::sync::watch;
 use tracing::{debug, info, warn};
@@ -640,9 +641,18 @@ fn build_discovered_derivation(
         allow_substitutes: drv.allow_substitutes(),
         pname,
         substituted: false,
+        constituents: None,
+        floating_outputs: None,
     }
 }
 
+/// Let an attribute's `meta.timeout`/`meta.maxSilent` override the limits its
+/// derivation declares.
+fn apply_meta_limits(discovered: &mut DiscoveredDerivation, limits: MetaLimits) {
+    discovered.timeout_secs = limits.timeout_secs.or(discovered.timeout_secs);
+    discovered.max_silent_secs = limits.max_silent_secs.or(discovered.max_silent_secs);
+}
+
 /// BFS closure walker.
 ///
 /// Holds the walk state (frontier queue, visited set, accumulation batch)
@@ -653,9 +663,19 @@ fn build_discovered_derivation(
 /// so builds can start queuing while the walk continues.
 struct ClosureWalker<'a> {
     drv_reader: &'a dyn DrvReader,
+    /// Realisation keys of derivations with floating CA outputs.
+    hasher: DrvHasher<'a>,
     batch: Vec<DiscoveredDerivation>,
     visited: HashSet<String>,
     queue: VecDeque<(Option<String>, String)>,
+    /// Root `.drv` path → its attribute's `meta` build limits.
+    root_limits: HashMap<String, MetaLimits>,
+    /// Root attribute → its `.drv` path, for aggregate constituents named by
+    /// job rather than passed as derivations.
+    root_attrs: HashMap<String, String>,
+    /// Aggregate constituents that matched neither an input derivation nor an
+    /// evaluated attribute. Reported as evaluation errors with the last batch.
+    errors: Vec<String>,
     walked: usize,
     start: Instant,
     /// `.drv` paths the walker parsed since the last flush (present in the
@@ -667,7 +687,12 @@ struct ClosureWalker<'a> {
 
 impl<'a> ClosureWalker<'a> {
     /// Initialise the walker with `root_drvs` as the BFS frontier.
-    fn new(drv_reader: &'a dyn DrvReader, root_drvs: &[(String, String)]) -> Self {
+    /// `root_limits` are applied to the matching roots as they are parsed.
+    fn new(
+        drv_reader: &'a dyn DrvReader,
+        root_drvs: &[(String, String)],
+        root_limits: HashMap<String, MetaLimits>,
+    ) -> Self {
         let mut visited = HashSet::new();
         let mut queue = VecDeque::new();
         for (attr, drv) in root_drvs {
@@ -678,9 +703,13 @@ impl<'a> ClosureWalker<'a> {
         info!(roots = root_drvs.len(), "starting closure walk");
         Self {
             drv_reader,
+            hasher: DrvHasher::new(drv_reader),
             batch: Vec::new(),
             visited,
             queue,
+            root_limits,
+            root_attrs: root_drvs.iter().cloned().collect(),
+            errors: Vec::new(),
             walked: 0,
             start: Instant::now(),
             produced_drvs: Vec::new(),
@@ -781,6 +810,8 @@ impl<'a> ClosureWalker<'a> {
                     allow_substitutes: true,
                     pname: None,
                     substituted: true, // already built - skip dispatch
+                    constituents: None,
+                    floating_outputs: None,
                 });
             } else {
                 self.queue.push_back((None, dep));
@@ -789,8 +820,20 @@ impl<'a> ClosureWalker<'a> {
 
         for ((attr, drv_path), drv) in wave.into_iter().zip(parsed_drvs) {
             self.produced_drvs.push(drv_path.clone());
-            self.batch
-                .push(build_discovered_derivation(attr, drv_path, &drv));
+            let mut discovered = build_discovered_derivation(attr, drv_path, &drv);
+            if let Some(limits) = self.root_limits.get(&discovered.drv_path) {
+                apply_meta_limits(&mut discovered, *limits);
+            }
+            discovered.floating_outputs = self.floating_outputs(&discovered.drv_path, &drv).await;
+            if let Some(raw) = drv.hydra_constituents()
+                && !discovered.attr.is_empty()
+            {
+                let constituents = self
+                    .resolve_constituents(&discovered.attr, &drv, raw)
+                    .await?;
+                discovered.constituents = Some(constituents);
+            }
+            self.batch.push(discovered);
             self.walked += 1;
 
             // Heartbeat log so operators can distinguish "slow eval" from "stuck".
@@ -824,6 +867,85 @@ impl<'a> ClosureWalker<'a> {
 
         Ok(())
     }
+
+    /// The floating CA outputs of `drv` with their realisation key. A key
+    /// that cannot be computed (an input `.drv` missing locally) only costs
+    /// the realisation lookup, so it is logged rather than failing the eval.
+    async fn floating_outputs(
+        &mut self,
+        drv_path: &str,
+        drv: &gradient_db::Derivation,
+    ) -> Option<FloatingOutputs> {
+        let outputs: Vec<String> = drv
+            .outputs
+            .iter()
+            .filter(|o| o.path.is_empty())
+            .map(|o| o.name.clone())
+            .collect();
+        if outputs.is_empty() {
+            return None;
+        }
+        match self.hasher.realisation_hash(drv).await {
+            Ok(drv_hash) => Some(FloatingOutputs { drv_hash, outputs }),
+            Err(e) => {
+                warn!(drv = %drv_path, error = %e, "cannot compute realisation key");
+                None
+            }
+        }
+    }
+
+    /// Map an aggregate's raw `constituents` to `.drv` paths. A store path is
+    /// the output of one of the aggregate's input derivations; anything else
+    /// names an evaluated attribute, either in full or relative to the
+    /// aggregate's top-level attribute set (`hydraJobs.tests.unit` for
+    /// `tests.unit` under `hydraJobs.release`). A name that resolves to
+    /// neither is reported as an error and passed through unchanged, so the
+    /// server counts it against the aggregate instead of dropping it.
+    async fn resolve_constituents(
+        &mut self,
+        attr: &str,
+        drv: &gradient_db::Derivation,
+        raw: Vec<String>,
+    ) -> Result<Vec<String>> {
+        let mut by_output: HashMap<String, String> = HashMap::new();
+        if raw.iter().any(|c| c.starts_with('/')) {
+            for (input_drv, _) in &drv.input_derivations {
+                let bytes = self.drv_reader.read_drv(input_drv).await.with_context(|| {
+                    format!("cannot read constituent .drv {input_drv} of aggregate {attr}")
+                })?;
+                let parsed = parse_drv(&bytes).with_context(|| {
+                    format!("cannot parse constituent .drv {input_drv} of aggregate {attr}")
+                })?;
+                for output in parsed.outputs {
+                    by_output.insert(output.path, input_drv.clone());
+                }
+            }
+        }
+
+        let prefix = attr.split('.').next().unwrap_or_default();
+        let mut constituents = Vec::with_capacity(raw.len());
+        for name in raw {
+            let resolved = if name.starts_with('/') {
+                by_output.get(&name)
+            } else {
+                self.root_attrs
+                    .get(&name)
+                    .or_else(|| self.root_attrs.get(&format!("{prefix}.{name}")))
+            };
+            match resolved {
+                Some(drv_path) => constituents.push(drv_path.clone()),
+                None => {
+                    self.errors.push(format!(
+                        "aggregate {attr}: constituent {name} is not an input derivation or an evaluated attribute"
+                    ));
+                    constituents.push(name);
+                }
+            }
+        }
+        constituents.sort_unstable();
+        constituents.dedup();
+        Ok(constituents)
+    }
 }
 
 // ── Orchestrator ──────────────────────────────────────────────────────────────
@@ -1008,9 +1130,15 @@ pub async fn evaluate_derivations_with(
     warnings.extend(resolve_warnings);
 
     let mut root_drvs: Vec<(String, String)> = Vec::new();
+    let mut root_limits: HashMap<String, MetaLimits> = HashMap::new();
     for (attr, result) in resolved {
         match result {
-            Ok((drv_path, _refs)) => root_drvs.push((attr, drv_path)),
+            Ok((drv_path, _refs, limits)) => {
+                if limits != MetaLimits::default() {
+                    root_limits.entry(drv_path.clone()).or_insert(limits);
+                }
+                root_drvs.push((attr, drv_path));
+            }
             Err(e) => errors.push(format!("failed to resolve {attr}: {e}")),
         }
     }
@@ -1035,9 +1163,10 @@ pub async fn evaluate_derivations_with(
     let flake_nodes = flake_nodes_from_roots(&root_drvs);
 
     // ── Step 3+4+5: BFS closure walk with incremental flushes ────────────────
-    let mut walker = ClosureWalker::new(drv_reader, &root_drvs);
+    let mut walker = ClosureWalker::new(drv_reader, &root_drvs, root_limits);
     let mut remaining = walker.walk(updater, abort).await?;
     let remaining_drvs = std::mem::take(&mut walker.produced_drvs);
+    errors.append(&mut walker.errors);
 
     // ── Final flush: remaining derivations + deduplicated warnings/errors ─────
     warnings.sort_unstable();
@@ -1314,6 +1443,106 @@ mod tests {
         assert_eq!(discovered.input_sources, drv.input_sources);
     }
 
+    /// `meta.timeout`/`meta.maxSilent` on the evaluated attribute override the
+    /// entry point's own limits; dependencies keep theirs.
+    #[tokio::test]
+    async fn meta_limits_override_the_entry_point_only() {
+        let fixture = load_store(&fixture_dir());
+        let repo = "https://example.com/repo";
+        let (resolver, drv_reader) = setup_from_fixture(&fixture, repo, "hello");
+        let resolver = resolver.with_meta_limits(
+            repo,
+            "hello",
+            MetaLimits {
+                timeout_secs: Some(7200),
+                max_silent_secs: None,
+            },
+        );
+        let job = make_flake_job(repo);
+        let mut reporter = RecordingJobReporter::new();
+
+        evaluate_derivations_with(
+            &resolver,
+            &drv_reader,
+            &job,
+            None,
+            &mut reporter,
+            &mut never_abort(),
+        )
+        .await
+        .unwrap();
+
+        let all = reporter.all_eval_derivations();
+        let entry = all
+            .iter()
+            .find(|d| d.drv_path == fixture.entry_point)
+            .unwrap();
+        assert_eq!(entry.timeout_secs, Some(7200));
+        assert_eq!(entry.max_silent_secs, None);
+        assert!(
+            all.iter()
+                .filter(|d| d.drv_path != fixture.entry_point)
+                .all(|d| d.timeout_secs != Some(7200))
+        );
+    }
+
+    /// An aggregate's constituents resolve to `.drv` paths whether passed as
+    /// derivations (output paths) or named as jobs; an unknown name is an
+    /// evaluation error and stays in the list so the server fails the
+    /// aggregate.
+    #[tokio::test]
+    async fn aggregate_constituents_resolve_to_drv_paths() {
+        const HELLO_DRV: &str = "/nix/store/aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa-hello.drv";
+        const UNIT_DRV: &str = "/nix/store/cccccccccccccccccccccccccccccccc-unit.drv";
+        const RELEASE_DRV: &str = "/nix/store/eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee-release.drv";
+        let repo = "https://example.com/repo";
+        let resolver = FakeDerivationResolver::new()
+            .with_flake_attrs(
+                repo,
+                vec!["hydraJobs.release".into(), "hydraJobs.tests.unit".into()],
+            )
+            .with_drv_path(repo, "hydraJobs.release", RELEASE_DRV)
+            .with_drv_path(repo, "hydraJobs.tests.unit", UNIT_DRV);
+        let drv_reader = FakeDrvReader::new()
+            .with_drv(HELLO_DRV, br#"Derive([("out","/nix/store/bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb-hello","","")],[],[],"x86_64-linux","/bin/sh",[],[("name","hello")])"#.to_vec())
+            .with_drv(UNIT_DRV, br#"Derive([("out","/nix/store/dddddddddddddddddddddddddddddddd-unit","","")],[],[],"x86_64-linux","/bin/sh",[],[("name","unit")])"#.to_vec())
+            .with_drv(RELEASE_DRV, br#"Derive([("out","/nix/store/ffffffffffffffffffffffffffffffff-release","","")],[("/nix/store/aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa-hello.drv",["out"])],[],"x86_64-linux","/bin/sh",[],[("_hydraAggregate","1"),("constituents","/nix/store/bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb-hello tests.unit docs"),("name","release")])"#.to_vec());
+        let job = make_flake_job(repo);
+        let mut reporter = RecordingJobReporter::new();
+
+        evaluate_derivations_with(
+            &resolver,
+            &drv_reader,
+            &job,
+            None,
+            &mut reporter,
+            &mut never_abort(),
+        )
+        .await
+        .unwrap();
+
+        let all = reporter.all_eval_derivations();
+        let release = all.iter().find(|d| d.drv_path == RELEASE_DRV).unwrap();
+        assert_eq!(
+            release.constituents,
+            Some(vec![
+                HELLO_DRV.to_string(),
+                UNIT_DRV.to_string(),
+                "docs".to_string()
+            ])
+        );
+        assert!(
+            all.iter()
+                .filter(|d| d.drv_path != RELEASE_DRV)
+                .all(|d| d.constituents.is_none())
+        );
+        let Some(ReportedEvent::EvalResult { errors, .. }) = reporter.last_eval_result() else {
+            panic!("no eval result");
+        };
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("constituent docs"), "{errors:?}");
+    }
+
     #[tokio::test]
     async fn test_eval_closure_walk_empty_store() {
         let fixture = load_store(&fixture_dir());
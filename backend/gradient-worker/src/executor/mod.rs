@@ -13,6 +13,7 @@ pub mod build;
 mod build_metrics;
 pub mod compress;
 mod derivation;
+mod drv_hash;
 pub mod eval;
 pub(crate) mod failure;
 pub mod fetch;
@@ -156,6 +156,24 @@ impl LocalNixStore {
             .collect())
     }
 
+    /// NAR size in bytes of `store_path`, from the daemon's path info.
+    pub async fn nar_size(&self, store_path: &str) -> Result<u64> {
+        let base = strip_store_prefix(store_path);
+        let sp = StorePath::from_base_path(base)
+            .map_err(|e| anyhow::anyhow!("invalid store path {store_path}: {e}"))?;
+
+        let mut guard = self.acquire().await?;
+        let info = guard
+            .execute(|client| async move { client.query_path_info(&sp).await })
+            .await
+            .map_err(|e| anyhow::anyhow!("query_path_info failed for {store_path}: {e}"))?
+            .ok_or_else(|| {
+                anyhow::anyhow!("query_path_info: path not in local store: {store_path}")
+            })?;
+
+        Ok(info.nar_size)
+    }
+
     /// Register `gcroot_symlink` as an indirect GC root with the daemon.
     ///
     /// The caller must have already created the symlink on disk; the daemon
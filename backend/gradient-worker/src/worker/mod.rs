@@ -326,6 +326,7 @@ async fn perform_setup(
             cpu_count = host.cpu_count,
             ram_total_mb = host.ram_total_mb,
             cpu_core_score,
+            labels = ?config.labels,
             "advertising build capabilities"
         );
         conn.send(ClientMessage::WorkerCapabilities {
@@ -335,6 +336,7 @@ async fn perform_setup(
             cpu_count: host.cpu_count,
             ram_total_mb: host.ram_total_mb,
             cpu_core_score,
+            labels: config.labels.clone(),
         })
         .await?;
     }
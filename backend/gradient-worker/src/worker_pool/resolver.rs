@@ -11,7 +11,7 @@ use futures::stream::{FuturesUnordered, StreamExt};
 use gradient_db::{Derivation, parse_drv};
 use gradient_eval::ipc::ResolvedItem;
 use gradient_exec::path_utils::nix_store_path;
-use gradient_nix::{DerivationResolver, FlakeDiscovery, ResolvedDerivation};
+use gradient_nix::{DerivationResolver, FlakeDiscovery, MetaLimits, ResolvedDerivation};
 use std::collections::VecDeque;
 use std::future::Future;
 use std::sync::{Arc, Mutex};
@@ -87,7 +87,14 @@ fn entry_point_of(attr: &str, patterns: &[String]) -> String {
 /// Convert a worker's [`ResolvedItem`] into the trait's `(attr, Result)` shape.
 fn item_to_resolved(item: ResolvedItem) -> ResolvedDerivation {
     let result = match (item.drv_path, item.error) {
-        (Some(drv), _) => Ok((drv, item.references)),
+        (Some(drv), _) => Ok((
+            drv,
+            item.references,
+            MetaLimits {
+                timeout_secs: item.timeout_secs,
+                max_silent_secs: item.max_silent_secs,
+            },
+        )),
         (None, Some(msg)) => Err(anyhow::anyhow!(msg)),
         (None, None) => Err(anyhow::anyhow!("eval worker returned empty result")),
     };
@@ -657,6 +664,8 @@ mod tests {
             attr: attr.to_string(),
             drv_path: Some(format!("h-{attr}.drv")),
             references: vec![],
+            timeout_secs: None,
+            max_silent_secs: None,
             error: None,
         }
     }
@@ -14,6 +14,12 @@ pub struct BuildResponse {
     pub architecture: String,
     pub worker: Option<String>,
     pub output: HashMap<String, String>,
+    /// Structured cause recognised in the failed build's log, `kind`-tagged.
+    #[serde(default)]
+    pub failure_cause: Option<serde_json::Value>,
+    /// One-line summary of `failure_cause`.
+    #[serde(default)]
+    pub failure_summary: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -52,6 +52,27 @@ pub struct Upstream {
     pub public_key: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CachePin {
+    pub id: String,
+    pub name: String,
+    pub store_path: Option<String>,
+    pub evaluation: Option<String>,
+    pub managed: bool,
+    pub created_by: Option<String>,
+    pub created_at: String,
+    pub expires_at: Option<String>,
+    pub expired: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CreatePinRequest {
+    pub name: String,
+    pub store_path: Option<String>,
+    pub evaluation: Option<String>,
+    pub expires_at: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct NarSummary {
     pub hash: String,
@@ -362,6 +383,47 @@ impl CachesApi<'_> {
         http::decode(req.send().await?).await
     }
 
+    pub async fn pins(&self, cache: &str) -> Result<Vec<CachePin>, ConnectorError> {
+        let req = http::request(
+            self.0.http(),
+            self.0.base_url(),
+            self.0.token(),
+            Method::GET,
+            &format!("caches/{cache}/pins"),
+            true,
+        )?;
+        http::decode(req.send().await?).await
+    }
+
+    pub async fn add_pin(
+        &self,
+        cache: &str,
+        body: CreatePinRequest,
+    ) -> Result<String, ConnectorError> {
+        let req = http::request(
+            self.0.http(),
+            self.0.base_url(),
+            self.0.token(),
+            Method::PUT,
+            &format!("caches/{cache}/pins"),
+            true,
+        )?
+        .json(&body);
+        http::decode(req.send().await?).await
+    }
+
+    pub async fn delete_pin(&self, cache: &str, name: &str) -> Result<String, ConnectorError> {
+        let req = http::request(
+            self.0.http(),
+            self.0.base_url(),
+            self.0.token(),
+            Method::DELETE,
+            &format!("caches/{cache}/pins/{name}"),
+            true,
+        )?;
+        http::decode(req.send().await?).await
+    }
+
     pub async fn nars_list(
         &self,
         cache: &str,
@@ -9,6 +9,15 @@ pub struct WorkerLiveInfo {
     pub max_concurrent_builds: u32,
     pub assigned_job_count: i32,
     pub draining: bool,
+    #[serde(default)]
+    pub cordoned: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct MaintenanceWindow {
+    /// Six-field cron expression for the window start, in UTC.
+    pub schedule: String,
+    pub duration_secs: u64,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -22,6 +31,10 @@ pub struct Worker {
     pub enable_fetch: bool,
     pub enable_eval: bool,
     pub enable_build: bool,
+    #[serde(default)]
+    pub cordoned: bool,
+    #[serde(default)]
+    pub maintenance_windows: Vec<MaintenanceWindow>,
     pub live: Option<WorkerLiveInfo>,
 }
 
@@ -46,6 +59,18 @@ pub struct PatchWorkerRequest {
     pub enable_fetch: Option<bool>,
     pub enable_eval: Option<bool>,
     pub enable_build: Option<bool>,
+    pub cordoned: Option<bool>,
+    pub maintenance_windows: Option<Vec<MaintenanceWindow>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DrainStatus {
+    pub worker_id: String,
+    pub cordoned: bool,
+    pub in_maintenance: bool,
+    pub connected: bool,
+    pub in_flight_jobs: u32,
+    pub drained: bool,
 }
 
 pub struct WorkersApi<'a>(pub(crate) &'a Client);
@@ -109,4 +134,33 @@ impl WorkersApi<'_> {
         )?;
         http::decode(req.send().await?).await
     }
+
+    /// Cordon the worker for `org` and return its drain progress.
+    pub async fn drain(&self, org: &str, worker_id: &str) -> Result<DrainStatus, ConnectorError> {
+        let req = http::request(
+            self.0.http(),
+            self.0.base_url(),
+            self.0.token(),
+            Method::POST,
+            &format!("orgs/{org}/workers/{worker_id}/drain"),
+            true,
+        )?;
+        http::decode(req.send().await?).await
+    }
+
+    pub async fn drain_status(
+        &self,
+        org: &str,
+        worker_id: &str,
+    ) -> Result<DrainStatus, ConnectorError> {
+        let req = http::request(
+            self.0.http(),
+            self.0.base_url(),
+            self.0.token(),
+            Method::GET,
+            &format!("orgs/{org}/workers/{worker_id}/drain"),
+            true,
+        )?;
+        http::decode(req.send().await?).await
+    }
 }
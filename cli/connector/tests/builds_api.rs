@@ -29,6 +29,37 @@ async fn get_build_returns_response() {
     assert_eq!(build.status, "Succeeded");
 }
 
+#[tokio::test]
+async fn get_build_returns_failure_cause() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/builds/b1"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(ok(serde_json::json!({
+                "id": "b1", "evaluation": "e1", "status": "FailedPermanent",
+                "derivation_path": "/nix/store/abc.drv", "architecture": "x86_64-linux",
+                "output": {},
+                "failure_cause": { "kind": "test_failure", "test": "a::broken" },
+                "failure_summary": "test failed: a::broken",
+                "created_at": "2024-01-01T00:00:00Z", "updated_at": "2024-01-01T00:00:00Z"
+            }))),
+        )
+        .mount(&server)
+        .await;
+
+    let client = Client::builder()
+        .base_url(server.uri())
+        .token("t")
+        .build()
+        .unwrap();
+    let build = client.builds().get("b1").await.unwrap();
+    assert_eq!(
+        build.failure_summary.as_deref(),
+        Some("test failed: a::broken")
+    );
+    assert_eq!(build.failure_cause.unwrap()["kind"], "test_failure");
+}
+
 #[tokio::test]
 async fn download_file_returns_bytes() {
     let server = MockServer::start().await;
@@ -60,3 +60,32 @@ async fn create_worker_returns_response() {
         .unwrap();
     assert_eq!(res.peer_id, "worker-1");
 }
+
+#[tokio::test]
+async fn drain_worker_returns_status() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/orgs/my-org/workers/worker-1/drain"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(ok(serde_json::json!({
+                "worker_id": "worker-1",
+                "cordoned": true,
+                "in_maintenance": false,
+                "connected": true,
+                "in_flight_jobs": 2,
+                "drained": false
+            }))),
+        )
+        .mount(&server)
+        .await;
+
+    let client = Client::builder()
+        .base_url(server.uri())
+        .token("t")
+        .build()
+        .unwrap();
+    let status = client.workers().drain("my-org", "worker-1").await.unwrap();
+    assert!(status.cordoned);
+    assert_eq!(status.in_flight_jobs, 2);
+    assert!(!status.drained);
+}
@@ -7,9 +7,12 @@
 use crate::input::client_from_config;
 use crate::output::{ExitKind, Output, to_exit_kind};
 use clap::Subcommand;
+use connector::builds::BuildResponse;
 
 #[derive(Subcommand, Debug)]
 pub enum Commands {
+    /// Show a build's status and, for a failed build, its recognised cause
+    Show { id: String },
     /// Show a build's dependency graph
     Graph {
         id: String,
@@ -35,6 +38,16 @@ pub enum Commands {
 
 pub async fn handle(cmd: Commands, out: Output) {
     match cmd {
+        Commands::Show { id } => {
+            let client = client_from_config(out);
+            match client.builds().get(&id).await {
+                Ok(b) => {
+                    out.ok(&b);
+                    out.human(build_line(&b));
+                }
+                Err(e) => out.err(to_exit_kind(&e), e),
+            }
+        }
         Commands::Graph { id, interactive } => {
             let client = client_from_config(out);
             match client.builds().graph(&id).await {
@@ -57,7 +70,17 @@ pub async fn handle(cmd: Commands, out: Output) {
             search,
             case,
         } => {
-            crate::commands::builds_log::handle_log(&id, interactive, lines, search, case, out).await
+            crate::commands::builds_log::handle_log(&id, interactive, lines, search, case, out)
+                .await
         }
     }
 }
+
+/// `<id> <status> <derivation>`, followed by the failure cause when one was
+/// recognised, all on one line.
+fn build_line(b: &BuildResponse) -> String {
+    match &b.failure_summary {
+        Some(cause) => format!("{} {} {}: {cause}", b.id, b.status, b.derivation_path),
+        None => format!("{} {} {}", b.id, b.status, b.derivation_path),
+    }
+}
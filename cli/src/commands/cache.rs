@@ -5,6 +5,7 @@
  */
 
 use crate::commands::cache_nar;
+use crate::commands::cache_pin;
 use crate::commands::cache_upload;
 use crate::commands::completion;
 use crate::input::{client_from_config, handle_input};
@@ -74,6 +75,11 @@ pub enum Commands {
         #[command(subcommand)]
         cmd: cache_nar::Commands,
     },
+    /// Pin store paths or evaluations as GC roots
+    Pin {
+        #[command(subcommand)]
+        cmd: cache_pin::Commands,
+    },
     /// Upload NAR(s) to a cache
     Upload(crate::commands::cache_upload::UploadArgs),
 }
@@ -271,6 +277,7 @@ pub async fn handle(cmd: Commands, out: Output) {
         }
 
         Commands::Nar { cmd } => cache_nar::handle(cmd, out).await,
+        Commands::Pin { cmd } => cache_pin::handle(cmd, out).await,
         Commands::Upload(args) => cache_upload::handle(args, out).await,
     }
 }
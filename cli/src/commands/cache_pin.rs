@@ -0,0 +1,109 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+use crate::commands::completion;
+use crate::input::client_from_config;
+use crate::output::{Output, to_exit_kind};
+use clap::{ArgGroup, Subcommand};
+use clap_complete::engine::ArgValueCompleter;
+use connector::caches::CreatePinRequest;
+
+#[derive(Subcommand, Debug)]
+pub enum Commands {
+    /// Pin a store path or a whole evaluation so GC keeps its closure
+    #[command(group(ArgGroup::new("target").required(true).args(["path", "evaluation"])))]
+    Add {
+        #[arg(add = ArgValueCompleter::new(completion::complete_caches))]
+        cache: String,
+        /// Pin name, unique within the cache
+        name: String,
+        /// Store path to pin (must already be in the cache)
+        #[arg(long)]
+        path: Option<String>,
+        /// Evaluation ID whose entry-point outputs to pin
+        #[arg(long)]
+        evaluation: Option<String>,
+        /// Expiry as a UTC timestamp (e.g. 2026-12-31T00:00:00); never expires if omitted
+        #[arg(long = "expires-at")]
+        expires_at: Option<String>,
+    },
+    /// List a cache's pins
+    List {
+        #[arg(add = ArgValueCompleter::new(completion::complete_caches))]
+        cache: String,
+    },
+    /// Remove a pin
+    Remove {
+        #[arg(add = ArgValueCompleter::new(completion::complete_caches))]
+        cache: String,
+        name: String,
+    },
+}
+
+pub async fn handle(cmd: Commands, out: Output) {
+    match cmd {
+        Commands::Add {
+            cache,
+            name,
+            path,
+            evaluation,
+            expires_at,
+        } => {
+            let client = client_from_config(out);
+            let body = CreatePinRequest {
+                name: name.clone(),
+                store_path: path,
+                evaluation,
+                expires_at,
+            };
+            match client.caches().add_pin(&cache, body).await {
+                Ok(id) => {
+                    out.ok(&serde_json::json!({"id": id, "name": name}));
+                    out.human(format!("Pin '{name}' added to cache '{cache}'."));
+                }
+                Err(e) => out.err(to_exit_kind(&e), e),
+            }
+        }
+
+        Commands::List { cache } => {
+            let client = client_from_config(out);
+            match client.caches().pins(&cache).await {
+                Ok(pins) => {
+                    out.ok(&pins);
+                    if pins.is_empty() {
+                        out.human("No pins.");
+                    }
+                    for pin in &pins {
+                        let target = match (&pin.store_path, &pin.evaluation) {
+                            (Some(path), _) => path.clone(),
+                            (None, Some(eval)) => format!("evaluation {eval}"),
+                            (None, None) => "-".to_string(),
+                        };
+                        let expiry = match (&pin.expires_at, pin.expired) {
+                            (Some(at), true) => format!("expired {at}"),
+                            (Some(at), false) => format!("until {at}"),
+                            (None, _) => "no expiry".to_string(),
+                        };
+                        let managed = if pin.managed { "  [managed]" } else { "" };
+                        out.human(format!("{}  {}  {}{}", pin.name, target, expiry, managed));
+                    }
+                }
+                Err(e) => out.err(to_exit_kind(&e), e),
+            }
+        }
+
+        Commands::Remove { cache, name } => {
+            let client = client_from_config(out);
+            match client.caches().delete_pin(&cache, &name).await {
+                Ok(_) => {
+                    out.ok(&serde_json::json!({"removed": true, "name": name}));
+                    out.human(format!("Pin '{name}' removed."));
+                }
+                Err(e) => out.err(to_exit_kind(&e), e),
+            }
+        }
+    }
+}
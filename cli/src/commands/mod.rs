@@ -15,6 +15,7 @@ pub mod build;
 pub mod build_nix;
 pub mod cache;
 pub mod cache_nar;
+pub mod cache_pin;
 pub mod cache_upload;
 pub mod completion;
 pub mod download;
@@ -10,7 +10,11 @@ use crate::input::client_from_config;
 use crate::output::{ExitKind, Output, to_exit_kind};
 use clap::Subcommand;
 use clap_complete::engine::ArgValueCompleter;
-use connector::workers::MakeWorkerRequest;
+use connector::workers::{DrainStatus, MakeWorkerRequest, PatchWorkerRequest};
+use std::time::{Duration, Instant};
+
+/// How often `worker drain --wait` polls the drain status.
+const DRAIN_POLL_SECS: u64 = 5;
 
 #[derive(Subcommand, Debug)]
 pub enum Commands {
@@ -37,6 +41,53 @@ pub enum Commands {
         #[arg(add = ArgValueCompleter::new(completion::complete_workers))]
         worker_id: String,
     },
+    /// Stop assigning the organization's jobs to a worker; running jobs finish
+    Cordon {
+        /// Worker ID to cordon
+        #[arg(add = ArgValueCompleter::new(completion::complete_workers))]
+        worker_id: String,
+    },
+    /// Allow the organization's jobs on a cordoned worker again
+    Uncordon {
+        /// Worker ID to uncordon
+        #[arg(add = ArgValueCompleter::new(completion::complete_workers))]
+        worker_id: String,
+    },
+    /// Cordon a worker and report when its in-flight jobs have finished
+    Drain {
+        /// Worker ID to drain
+        #[arg(add = ArgValueCompleter::new(completion::complete_workers))]
+        worker_id: String,
+        /// Block until the worker is drained instead of printing the current status
+        #[arg(short, long)]
+        wait: bool,
+        /// Give up waiting after this many seconds (0 waits forever)
+        #[arg(long, default_value_t = 0, requires = "wait")]
+        timeout: u64,
+    },
+}
+
+fn selected_organization(out: Output) -> String {
+    match set_get_value(ConfigKey::SelectedOrganization, None, true) {
+        Some(id) => id,
+        _ => out.err(
+            ExitKind::Usage,
+            "Organization is required. Use `gradient organization select <name>`.",
+        ),
+    }
+}
+
+fn drain_summary(status: &DrainStatus) -> String {
+    if status.drained {
+        format!("Worker {} is drained.", status.worker_id)
+    } else if !status.cordoned && !status.in_maintenance {
+        format!("Worker {} is not cordoned.", status.worker_id)
+    } else {
+        format!(
+            "Worker {} is draining: {} job(s) still running.",
+            status.worker_id, status.in_flight_jobs
+        )
+    }
 }
 
 pub async fn handle(cmd: Commands, out: Output) {
@@ -140,5 +191,70 @@ pub async fn handle(cmd: Commands, out: Output) {
                 Err(e) => out.err(to_exit_kind(&e), e),
             }
         }
+
+        Commands::Cordon { worker_id } => set_cordon(worker_id, true, out).await,
+
+        Commands::Uncordon { worker_id } => set_cordon(worker_id, false, out).await,
+
+        Commands::Drain {
+            worker_id,
+            wait,
+            timeout,
+        } => {
+            let organization = selected_organization(out);
+            let client = client_from_config(out);
+
+            let mut status = match client.workers().drain(&organization, &worker_id).await {
+                Ok(status) => status,
+                Err(e) => out.err(to_exit_kind(&e), e),
+            };
+            if wait {
+                let deadline = (timeout > 0).then(|| Instant::now() + Duration::from_secs(timeout));
+                while !status.drained {
+                    out.progress(drain_summary(&status));
+                    if deadline.is_some_and(|d| Instant::now() >= d) {
+                        out.err(
+                            ExitKind::Api,
+                            format!("Timed out waiting for worker {} to drain.", worker_id),
+                        );
+                    }
+                    tokio::time::sleep(Duration::from_secs(DRAIN_POLL_SECS)).await;
+                    status = match client
+                        .workers()
+                        .drain_status(&organization, &worker_id)
+                        .await
+                    {
+                        Ok(status) => status,
+                        Err(e) => out.err(to_exit_kind(&e), e),
+                    };
+                }
+            }
+            out.ok(&status);
+            out.human(drain_summary(&status));
+        }
+    }
+}
+
+async fn set_cordon(worker_id: String, cordoned: bool, out: Output) {
+    let organization = selected_organization(out);
+    let client = client_from_config(out);
+    let body = PatchWorkerRequest {
+        cordoned: Some(cordoned),
+        ..Default::default()
+    };
+    match client
+        .workers()
+        .update(&organization, &worker_id, body)
+        .await
+    {
+        Ok(_) => {
+            out.ok(&serde_json::json!({"cordoned": cordoned}));
+            out.human(if cordoned {
+                "Worker cordoned; running jobs will finish."
+            } else {
+                "Worker uncordoned."
+            });
+        }
+        Err(e) => out.err(to_exit_kind(&e), e),
     }
 }
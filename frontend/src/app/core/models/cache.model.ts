@@ -4,6 +4,10 @@
  * SPDX-License-Identifier: AGPL-3.0-only
  */
 
+export type CacheEvictionPolicy = 'disabled' | 'lru' | 'oldest_first';
+
+export type NarCompression = 'zstd' | 'xz' | 'none';
+
 export interface Cache {
   id: string;
   name: string;
@@ -13,6 +17,8 @@ export interface Cache {
   priority: number;
   local_priority: number | null;
   max_storage_gb: number;
+  eviction_policy: CacheEvictionPolicy;
+  nar_compression: NarCompression;
   public_key?: string;
   public: boolean;
   created_by?: string;
@@ -5,8 +5,14 @@
  */
 
 export type IntegrationKind = 'inbound' | 'outbound';
-export type ForgeType = 'gitea' | 'forgejo' | 'gitlab' | 'github';
-export type InboundForge = 'gitea' | 'forgejo' | 'gitlab';
+export type ForgeType =
+  | 'gitea'
+  | 'forgejo'
+  | 'gitlab'
+  | 'github'
+  | 'bitbucket'
+  | 'bitbucket-server';
+export type InboundForge = Exclude<ForgeType, 'github'>;
 
 export interface Integration {
   id: string;
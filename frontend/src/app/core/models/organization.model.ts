@@ -18,6 +18,10 @@ export interface Organization {
   role?: 'Admin' | 'Write' | 'View';
   running_evaluations?: number;
   github_app_available?: boolean;
+  /** Hard quotas; `null` is unlimited. Only superusers may change them. */
+  max_concurrent_builds?: number | null;
+  max_concurrent_evaluations?: number | null;
+  build_minutes_budget?: number | null;
 }
 
 export interface OrganizationMember {
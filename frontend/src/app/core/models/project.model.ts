@@ -6,6 +6,7 @@
 
 import { BuildStatus, Architecture } from './build.model';
 import { ConcurrencyPolicy, TriggerType } from './trigger.model';
+import { WorkerLabels } from './worker.model';
 
 export interface Project {
   id: string;
@@ -23,6 +24,11 @@ export interface Project {
   keep_evaluations: number;
   concurrency: ConcurrencyPolicy;
   sign_cache: boolean;
+  /** Worker labels every build of this project requires. Empty when unrestricted. */
+  worker_selector: WorkerLabels;
+  build_timeout_secs: number | null;
+  build_max_silent_secs: number | null;
+  max_output_size: number | null;
   created_by?: string;
   created_at?: string;
   managed: boolean;
@@ -71,9 +77,17 @@ export interface EntryPointSummary {
   build_time_ms: number | null;
   deps: BuildStatusCounts;
   deps_total: number | null;
+  aggregate: boolean;
+  constituents: ConstituentSummary[] | null;
   created_at: string;
 }
 
+export interface ConstituentSummary {
+  derivation_path: string;
+  eval: string | null;
+  build_status: BuildStatus;
+}
+
 export interface ProjectDetail {
   id: string;
   name: string;
@@ -126,13 +140,18 @@ export type WaitingReason =
   | ApprovalWaitingReason
   | NoCacheWaitingReason
   | CacheStorageFullWaitingReason
-  | GraphStuckWaitingReason;
+  | GraphStuckWaitingReason
+  | QuotaWaitingReason;
 
 export interface WorkersWaitingReason {
   kind: 'workers';
   unmet: UnmetRequirement[];
   connected_workers: number;
   available_architectures: string[];
+  /** Selector the pending builds are held to; absent when unrestricted. */
+  worker_selector?: WorkerLabels;
+  /** Connected workers matching `worker_selector`; `unmet` considers only these. */
+  matching_workers?: number;
 }
 
 export type EvalCapability = 'fetch' | 'eval';
@@ -162,6 +181,13 @@ export interface GraphStuckWaitingReason {
   pending_anchors: number;
 }
 
+export interface QuotaWaitingReason {
+  kind: 'quota';
+  quota: 'concurrent_builds' | 'concurrent_evaluations' | 'build_minutes';
+  limit: number;
+  used: number;
+}
+
 export interface UnmetRequirement {
   architecture: string;
   required_features: string[];
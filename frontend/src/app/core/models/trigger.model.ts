@@ -5,8 +5,14 @@
  */
 
 import { ForgeType } from './integration.model';
+import { WorkerLabels } from './worker.model';
 
-export type TriggerType = 'polling' | 'reporter_push' | 'reporter_pull_request' | 'time';
+export type TriggerType =
+  | 'polling'
+  | 'reporter_push'
+  | 'reporter_pull_request'
+  | 'reporter_merge_queue'
+  | 'time';
 export type ConcurrencyPolicy = 'hard_abort' | 'soft_abort' | 'all' | 'skip';
 
 /** Inlined integration handle on reporter trigger responses. Mirrors the
@@ -45,6 +51,14 @@ export interface ReporterPullRequestTriggerConfig {
   require_approval?: boolean;
 }
 
+export interface ReporterMergeQueueTriggerConfig {
+  type: 'reporter_merge_queue';
+  integration_id: string;
+  /** Target branches of the queue (GitHub merge group base / GitLab merge
+   *  train target). Empty matches every queue. */
+  branches?: string[];
+}
+
 export interface TimeTriggerConfig {
   type: 'time';
   cron: string;
@@ -54,6 +68,7 @@ export type TriggerConfig =
   | PollingTriggerConfig
   | ReporterPushTriggerConfig
   | ReporterPullRequestTriggerConfig
+  | ReporterMergeQueueTriggerConfig
   | TimeTriggerConfig;
 
 export interface ProjectTrigger {
@@ -62,20 +77,24 @@ export interface ProjectTrigger {
   type: TriggerType;
   config: TriggerConfig;
   active: boolean;
+  /** Merged over the project's worker selector for evaluations this trigger fires. */
+  worker_selector: WorkerLabels;
   last_fired_at: string | null;
   created_at: string;
   updated_at: string;
-  /** Populated by the backend for `reporter_push` / `reporter_pull_request`
-   *  triggers when the referenced integration still exists. */
+  /** Populated by the backend for `reporter_push` / `reporter_pull_request` /
+   *  `reporter_merge_queue` triggers when the referenced integration still exists. */
   integration: TriggerIntegrationRef | null;
 }
 
 export interface CreateTriggerBody {
   config: TriggerConfig;
   active?: boolean;
+  worker_selector?: WorkerLabels;
 }
 
 export interface UpdateTriggerBody {
   config?: TriggerConfig;
   active?: boolean;
+  worker_selector?: WorkerLabels;
 }
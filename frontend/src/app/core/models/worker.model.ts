@@ -13,6 +13,9 @@ export interface GradientCapabilities {
   cache: boolean;
 }
 
+/** Free-form `key: value` worker labels, or a selector over them. */
+export type WorkerLabels = Record<string, string>;
+
 export interface WorkerLiveInfo {
   capabilities: GradientCapabilities;
   architectures: string[];
@@ -20,6 +23,19 @@ export interface WorkerLiveInfo {
   max_concurrent_builds: number;
   assigned_job_count: number;
   draining: boolean;
+  /** Labels this organization's jobs are matched against: the registration's. */
+  labels: WorkerLabels;
+  /** Labels the worker advertises itself; informational, never matched. */
+  advertised_labels: WorkerLabels;
+  /** True while this organization's jobs are withheld: manual cordon or open maintenance window. */
+  cordoned: boolean;
+}
+
+/** Recurring window during which the registration cordons its worker. */
+export interface MaintenanceWindow {
+  /** Six-field cron expression (`sec min hour dom mon dow`, UTC) for the window start. */
+  schedule: string;
+  duration_secs: number;
 }
 
 export interface Worker {
@@ -41,6 +57,11 @@ export interface Worker {
   enable_eval: boolean;
   /** Per-registration server-side gate for `build`. */
   enable_build: boolean;
+  /** Registration labels this organization attached to the worker. Empty for base workers. */
+  labels: WorkerLabels;
+  /** Manual cordon by this organization: no new jobs, running ones finish. False for base workers. */
+  cordoned: boolean;
+  maintenance_windows: MaintenanceWindow[];
   /** Present when the worker is currently connected via proto. */
   live?: WorkerLiveInfo;
 }
@@ -51,6 +72,16 @@ export interface WorkerRegistration {
   token?: string;
 }
 
+export interface WorkerDrainStatus {
+  worker_id: string;
+  cordoned: boolean;
+  in_maintenance: boolean;
+  connected: boolean;
+  in_flight_jobs: number;
+  /** Cordoned with none of this organization's jobs left on the worker. */
+  drained: boolean;
+}
+
 export interface WorkerTestResponse {
   ok: boolean;
   connected: boolean;
@@ -36,6 +36,8 @@ describe('cacheAccessResolver', () => {
     priority: 10,
     local_priority: null,
     max_storage_gb: 0,
+    eviction_policy: 'lru',
+    nar_compression: 'zstd',
     public: false,
     managed: false,
     can_edit: true,
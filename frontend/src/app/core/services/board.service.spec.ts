@@ -8,7 +8,7 @@ import { TestBed } from '@angular/core/testing';
 import { provideHttpClient } from '@angular/common/http';
 import { provideHttpClientTesting } from '@angular/common/http/testing';
 import { HttpTestingController } from '@angular/common/http/testing';
-import { BoardService, ExpensiveEval, FlakeGraphNode, RuleDescription } from './board.service';
+import { BoardService, ExpensiveEval, FlakeGraphNode, RuleDescription, ScoringPolicyStatus } from './board.service';
 import { environment } from '@environments/environment';
 
 const apiUrl = environment.apiUrl;
@@ -31,6 +31,15 @@ const sampleNode: FlakeGraphNode = {
   drv_path: null,
 };
 
+const samplePolicy: ScoringPolicyStatus = {
+  requested: 'fleet',
+  active: 'resource-aware',
+  source: '/etc/gradient/scoring-policies.json',
+  rules: [{ rule: 'WaitTimeRule', weight: 1, veto: true }],
+  errors: ['policy "fleet": unknown rule "NoSuchRule"'],
+  loaded_at: '2026-01-01T00:00:00',
+};
+
 describe('BoardService', () => {
   let service: BoardService;
   let httpMock: HttpTestingController;
@@ -76,7 +85,7 @@ describe('BoardService', () => {
 
     const req = httpMock.expectOne(`${apiUrl}/board/scoring/rules`);
     expect(req.request.method).toBe('GET');
-    req.flush({ error: false, message: rules });
+    req.flush({ error: false, message: { rules, policy: samplePolicy } });
     expect(first).toEqual(rules);
 
     let second: RuleDescription[] | undefined;
@@ -84,4 +93,14 @@ describe('BoardService', () => {
     httpMock.expectNone(`${apiUrl}/board/scoring/rules`);
     expect(second).toEqual(rules);
   });
+
+  it('getScoringPolicy() unwraps the active policy and does not cache it', () => {
+    let result: ScoringPolicyStatus | undefined;
+    service.getScoringPolicy().subscribe((v) => (result = v));
+    httpMock.expectOne(`${apiUrl}/board/scoring/rules`).flush({ error: false, message: { rules: [], policy: samplePolicy } });
+    expect(result).toEqual(samplePolicy);
+
+    service.getScoringPolicy().subscribe();
+    httpMock.expectOne(`${apiUrl}/board/scoring/rules`).flush({ error: false, message: { rules: [], policy: samplePolicy } });
+  });
 });
@@ -5,7 +5,7 @@
  */
 
 import { Injectable, inject } from '@angular/core';
-import { Observable, shareReplay } from 'rxjs';
+import { Observable, map, shareReplay } from 'rxjs';
 import { ApiService } from './api.service';
 
 export interface DispatchedJobSummary {
@@ -86,6 +86,8 @@ export interface JobHistoryView {
   avg_disk_bytes: number; oom_rate: number; samples: number;
 }
 
+export interface JobCriticalPath { remaining_ms: number; eval_longest_ms: number; }
+
 export interface JobContextView {
   kind: 'Build' | 'Eval';
   architecture: string;
@@ -101,6 +103,7 @@ export interface JobContextView {
   prefer_local_build?: boolean;
   is_fixed_output?: boolean;
   history?: JobHistoryView;
+  critical_path?: JobCriticalPath;
   derivations?: DerivationRef[];
   fetch_flake?: boolean;
 }
@@ -140,6 +143,9 @@ export interface DispatchedJobDetail extends DispatchedJobSummary {
     outcome: number;
     reason: number | null;
     failure_message: string | null;
+    failure_summary: string | null;
+    flaky: boolean;
+    flaky_rerun: boolean;
     created_at: string;
   }[];
   passed_over: boolean;
@@ -224,6 +230,26 @@ export interface RuleDescription {
   description: string;
 }
 
+export interface ActiveRule {
+  rule: string;
+  weight: number;
+  veto: boolean;
+}
+
+export interface ScoringPolicyStatus {
+  requested: string;
+  active: string;
+  source: string | null;
+  rules: ActiveRule[];
+  errors: string[];
+  loaded_at: string | null;
+}
+
+export interface ScoringRules {
+  rules: RuleDescription[];
+  policy: ScoringPolicyStatus;
+}
+
 export interface SeriesPoint {
   bucket_start: string;
   count: number;
@@ -286,6 +312,26 @@ export interface BoardFleetPoint {
   build: number;
 }
 
+export type QuotaKind = 'concurrent_builds' | 'concurrent_evaluations' | 'build_minutes';
+
+/// Live quota state of one organization; a `null` limit is unlimited.
+export interface BoardOrgQuota {
+  organization: string;
+  organization_name: string;
+  max_concurrent_builds: number | null;
+  max_concurrent_evaluations: number | null;
+  build_minutes_budget: number | null;
+  active_builds: number;
+  active_evaluations: number;
+  build_minutes_used: number;
+  blocked: QuotaKind[];
+}
+
+export interface BoardFleet {
+  points: BoardFleetPoint[];
+  quotas: BoardOrgQuota[];
+}
+
 export interface ProcessStat {
   resident_memory_bytes: number;
   virtual_memory_bytes: number;
@@ -393,12 +439,21 @@ export class BoardService {
 
   getScoringRules(): Observable<RuleDescription[]> {
     this.scoringRules$ ??= this.api
-      .get<RuleDescription[]>('board/scoring/rules')
-      .pipe(shareReplay({ bufferSize: 1, refCount: false }));
+      .get<ScoringRules>('board/scoring/rules')
+      .pipe(
+        map((r) => r.rules),
+        shareReplay({ bufferSize: 1, refCount: false })
+      );
 
     return this.scoringRules$;
   }
 
+  /// Active scoring policy and its validation errors. Not cached: the policy
+  /// file is hot-reloaded, so each call reflects the current state.
+  getScoringPolicy(): Observable<ScoringPolicyStatus> {
+    return this.api.get<ScoringRules>('board/scoring/rules').pipe(map((r) => r.policy));
+  }
+
   getCache(windowHours = 24): Observable<BoardCacheStats> {
     return this.api.get<BoardCacheStats>(`board/cache?window_hours=${windowHours}`);
   }
@@ -411,8 +466,8 @@ export class BoardService {
     return this.api.get<BoardNetworkStats>(`board/network?window_hours=${windowHours}`);
   }
 
-  getFleet(windowHours = 24): Observable<BoardFleetPoint[]> {
-    return this.api.get<BoardFleetPoint[]>(`board/fleet?window_hours=${windowHours}`);
+  getFleet(windowHours = 24): Observable<BoardFleet> {
+    return this.api.get<BoardFleet>(`board/fleet?window_hours=${windowHours}`);
   }
 
   getHealth(): Observable<BoardHealth> {
@@ -37,6 +37,9 @@ export interface CacheStats {
   total_bytes: number;
   total_nar_bytes: number;
   total_packages: number;
+  stored_bytes: number;
+  chunked_packages: number;
+  dedup_ratio: number;
   storage_minutes: StorageMetricPoint[];
   storage_hours: StorageMetricPoint[];
   storage_days: StorageMetricPoint[];
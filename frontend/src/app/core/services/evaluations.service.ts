@@ -68,10 +68,21 @@ export interface BuildWithOutputs {
   architecture: string;
   worker: string | null;
   output: Record<string, string>;
+  failure_cause: FailureCause | null;
+  failure_summary: string | null;
   created_at: string;
   updated_at: string;
 }
 
+/** Structured cause recognised in a failed build's log, tagged by `kind`. */
+export type FailureCause =
+  | { kind: 'oom' }
+  | { kind: 'hash_mismatch'; expected?: string; got?: string }
+  | { kind: 'disk_full' }
+  | { kind: 'test_failure'; test?: string }
+  | { kind: 'missing_system_feature'; features?: string[] }
+  | { kind: 'timeout' };
+
 export interface PaginatedBuilds {
   builds: BuildItem[];
   total: number;
@@ -7,7 +7,13 @@
 import { Injectable, inject } from '@angular/core';
 import { Observable } from 'rxjs';
 import { ApiService } from './api.service';
-import { Worker, WorkerRegistration, WorkerTestResponse } from '@core/models';
+import {
+  Worker,
+  WorkerDrainStatus,
+  WorkerLabels,
+  WorkerRegistration,
+  WorkerTestResponse,
+} from '@core/models';
 
 export interface WorkerSamplePoint {
   at: string;
@@ -51,6 +57,7 @@ export class WorkersService {
     url?: string,
     token?: string,
     caps?: { enable_fetch: boolean; enable_eval: boolean; enable_build: boolean },
+    labels?: WorkerLabels,
   ): Observable<WorkerRegistration> {
     return this.api.post<WorkerRegistration>(`orgs/${org}/workers`, {
       worker_id: workerId,
@@ -60,6 +67,7 @@ export class WorkersService {
       enable_fetch: caps?.enable_fetch ?? true,
       enable_eval: caps?.enable_eval ?? true,
       enable_build: caps?.enable_build ?? true,
+      labels: labels ?? {},
     });
   }
 
@@ -86,6 +94,19 @@ export class WorkersService {
     return this.api.patch<string>(`orgs/${org}/workers/${workerId}`, body);
   }
 
+  setWorkerCordoned(org: string, workerId: string, cordoned: boolean): Observable<string> {
+    return this.api.patch<string>(`orgs/${org}/workers/${workerId}`, { cordoned });
+  }
+
+  /** Cordon the worker for this org; running jobs finish. */
+  drainWorker(org: string, workerId: string): Observable<WorkerDrainStatus> {
+    return this.api.post<WorkerDrainStatus>(`orgs/${org}/workers/${workerId}/drain`, {});
+  }
+
+  getWorkerDrainStatus(org: string, workerId: string): Observable<WorkerDrainStatus> {
+    return this.api.get<WorkerDrainStatus>(`orgs/${org}/workers/${workerId}/drain`);
+  }
+
   deleteWorker(org: string, workerId: string): Observable<string> {
     return this.api.delete<string>(`orgs/${org}/workers/${workerId}`);
   }
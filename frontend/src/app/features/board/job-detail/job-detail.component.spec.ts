@@ -230,8 +230,8 @@ describe('BoardJobDetailComponent - previous build attempts', () => {
   const WITH_ATTEMPTS: DispatchedJobDetail = {
     ...DETAIL,
     previous_attempts: [
-      { dispatched_job_id: 'dj-a1', substitute: false, outcome: 3, reason: 5, failure_message: 'builder for ... failed with exit code 1', created_at: '2026-06-08T00:00:00Z' },
-      { dispatched_job_id: 'dj-a2', substitute: true,  outcome: 2, reason: null, failure_message: null, created_at: '2026-06-08T00:01:00Z' },
+      { dispatched_job_id: 'dj-a1', substitute: false, outcome: 3, reason: 5, failure_message: 'builder for ... failed with exit code 1', failure_summary: null, flaky: false, flaky_rerun: false, created_at: '2026-06-08T00:00:00Z' },
+      { dispatched_job_id: 'dj-a2', substitute: true,  outcome: 2, reason: null, failure_message: null, failure_summary: null, flaky: false, flaky_rerun: false, created_at: '2026-06-08T00:01:00Z' },
     ],
   };
 
@@ -270,16 +270,44 @@ describe('BoardJobDetailComponent - previous build attempts', () => {
     const singleAttempt: DispatchedJobDetail = {
       ...DETAIL,
       previous_attempts: [
-        { dispatched_job_id: 'dj-a1', substitute: false, outcome: 1, reason: null, failure_message: null, created_at: '2026-06-08T00:00:00Z' },
+        { dispatched_job_id: 'dj-a1', substitute: false, outcome: 1, reason: null, failure_message: null, failure_summary: null, flaky: false, flaky_rerun: false, created_at: '2026-06-08T00:00:00Z' },
       ],
     };
     const el = setup({ getJob: () => of(singleAttempt) }).nativeElement as HTMLElement;
     expect(el.textContent).not.toContain('Previous Build Attempts');
   });
 
+  it('labels a flaky failure and its re-run', () => {
+    const flaky: DispatchedJobDetail = {
+      ...DETAIL,
+      previous_attempts: [
+        { dispatched_job_id: 'dj-a1', substitute: false, outcome: 3, reason: 5, failure_message: 'connection reset', failure_summary: null, flaky: true, flaky_rerun: false, created_at: '2026-06-08T00:00:00Z' },
+        { dispatched_job_id: 'dj-a2', substitute: false, outcome: 1, reason: null, failure_message: null, failure_summary: null, flaky: false, flaky_rerun: true, created_at: '2026-06-08T00:01:00Z' },
+      ],
+    };
+    const el = setup({ getJob: () => of(flaky) }).nativeElement as HTMLElement;
+    const section = el.querySelector('section.attempts') as HTMLElement;
+    expect(section.textContent).toContain('failed (flaky, re-run)');
+    expect(section.textContent).toContain('flaky re-run');
+  });
+
   it('shows the failure message in the reason column', () => {
     const el = setup({ getJob: () => of(WITH_ATTEMPTS) }).nativeElement as HTMLElement;
     const section = el.querySelector('section.attempts') as HTMLElement;
     expect(section.textContent).toContain('builder for ... failed with exit code 1');
   });
+
+  it('prefers the recognised failure cause over the raw message', () => {
+    const analysed: DispatchedJobDetail = {
+      ...DETAIL,
+      previous_attempts: [
+        { dispatched_job_id: 'dj-a1', substitute: false, outcome: 3, reason: 5, failure_message: 'builder for ... failed with exit code 1', failure_summary: 'test failed: a::broken', flaky: false, flaky_rerun: false, created_at: '2026-06-08T00:00:00Z' },
+        { dispatched_job_id: 'dj-a2', substitute: false, outcome: 1, reason: null, failure_message: null, failure_summary: null, flaky: false, flaky_rerun: false, created_at: '2026-06-08T00:01:00Z' },
+      ],
+    };
+    const el = setup({ getJob: () => of(analysed) }).nativeElement as HTMLElement;
+    const section = el.querySelector('section.attempts') as HTMLElement;
+    expect(section.textContent).toContain('test failed: a::broken');
+    expect(section.textContent).not.toContain('failed with exit code 1');
+  });
 });
@@ -156,6 +156,16 @@ interface RuleRow {
           </table>
         }
 
+        @if (j.job_context.kind === 'Build' && j.job_context.critical_path; as cp) {
+          <h3>Critical path</h3>
+          <table class="kv">
+            <tbody>
+              <tr><td class="label">Remaining</td><td class="mono">{{ cp.remaining_ms | number }} ms</td></tr>
+              <tr><td class="label">Evaluation longest</td><td class="mono">{{ cp.eval_longest_ms | number }} ms</td></tr>
+            </tbody>
+          </table>
+        }
+
         @if (j.job_context.derivations?.length) {
           <h3>Derivations</h3>
           <div class="drv-list">
@@ -205,9 +215,9 @@ interface RuleRow {
               @for (a of j.previous_attempts; track a.dispatched_job_id; let i = $index) {
                 <tr class="clickable" [routerLink]="['/board/jobs', a.dispatched_job_id]">
                   <td>{{ i + 1 }}</td>
-                  <td>{{ a.substitute ? 'substitute' : 'build' }}</td>
-                  <td class="mono">{{ attemptOutcome(a.outcome) }}</td>
-                  <td class="mono reason" [title]="a.failure_message ?? ''">{{ a.failure_message ?? '-' }}</td>
+                  <td>{{ a.substitute ? 'substitute' : a.flaky_rerun ? 'flaky re-run' : 'build' }}</td>
+                  <td class="mono">{{ attemptOutcome(a.outcome) }}{{ a.flaky ? ' (flaky, re-run)' : '' }}</td>
+                  <td class="mono reason" [title]="a.failure_message ?? ''">{{ a.failure_summary ?? a.failure_message ?? '-' }}</td>
                   <td>{{ a.created_at | date: 'medium' }}</td>
                   <td>&rsaquo;</td>
                 </tr>
@@ -259,6 +269,9 @@ interface RuleRow {
           <div><span class="label">Status</span><span class="mono">{{ b.status }}</span></div>
           <div><span class="label">Architecture</span><span class="mono">{{ b.architecture }}</span></div>
           <div><span class="label">Worker</span><span class="mono">{{ b.worker ?? '-' }}</span></div>
+          @if (b.failure_summary) {
+            <div class="span2"><span class="label">Failure cause</span><span class="mono">{{ b.failure_summary }}</span></div>
+          }
           <div class="span2"><span class="label">Derivation</span><span class="mono">{{ b.derivation_path }}</span></div>
           <div><span class="label">Created</span><span>{{ b.created_at | date: 'medium' }}</span></div>
           <div><span class="label">Updated</span><span>{{ b.updated_at | date: 'medium' }}</span></div>
@@ -7,7 +7,13 @@
 import { Component, OnInit, inject, signal, computed } from '@angular/core';
 import { CommonModule } from '@angular/common';
 import { PopoverModule, Popover } from 'primeng/popover';
-import { BoardService, MetricPoint, RuleDescription, ScoringSummary } from '@core/services/board.service';
+import {
+  BoardService,
+  MetricPoint,
+  RuleDescription,
+  ScoringPolicyStatus,
+  ScoringSummary,
+} from '@core/services/board.service';
 import { MetricChartComponent } from '@shared/components/metric-chart/metric-chart.component';
 
 @Component({
@@ -37,6 +43,32 @@ import { MetricChartComponent } from '@shared/components/metric-chart/metric-cha
       [colors]="['#6f42c1']"
     ></app-metric-chart>
 
+    @if (policy(); as p) {
+      <h2>Active policy: <span class="mono">{{ p.active }}</span></h2>
+      @if (p.active !== p.requested) {
+        <p class="muted">Configured policy <span class="mono">{{ p.requested }}</span> could not be loaded.</p>
+      }
+      @if (p.errors.length) {
+        <ul class="policy-errors">
+          @for (e of p.errors; track e) {
+            <li>{{ e }}</li>
+          }
+        </ul>
+      }
+      <table class="rules">
+        <thead><tr><th>Rule</th><th class="num">Weight</th><th>Veto</th></tr></thead>
+        <tbody>
+          @for (r of p.rules; track r.rule) {
+            <tr>
+              <td class="mono">{{ r.rule }}</td>
+              <td class="num">{{ r.weight | number: '1.0-2' }}</td>
+              <td>{{ r.veto ? 'on' : 'off' }}</td>
+            </tr>
+          }
+        </tbody>
+      </table>
+    }
+
     <h2>Per-rule mean contribution</h2>
     <table class="rules">
       <thead><tr><th>Rule</th><th class="num">Avg</th><th class="num">Min</th><th class="num">Max</th><th>Weight</th></tr></thead>
@@ -85,6 +117,7 @@ import { MetricChartComponent } from '@shared/components/metric-chart/metric-cha
       .bar { height: 10px; background: #28a745; border-radius: 3px; min-width: 2px; }
       .bar.neg { background: #dc3545; }
       .muted { color: #818181; }
+      .policy-errors { margin: 0 0 0.75rem; padding: 0.75rem 1rem 0.75rem 2rem; background: #2d1b1e; border: 1px solid #dc3545; border-radius: 8px; color: #f0a3aa; font-size: 0.85rem; }
       .help { margin-left: 0.4rem; width: 1.1rem; height: 1.1rem; padding: 0; border-radius: 50%; border: 1px solid #3d444d; background: #2d333b; color: #abb0b4; font-size: 0.7rem; line-height: 1; cursor: pointer; }
       .help:hover { color: #fff; border-color: #6f42c1; }
       .rule-help { max-width: 22rem; }
@@ -99,6 +132,7 @@ export class BoardSchedulerComponent implements OnInit {
   private wait = signal<MetricPoint[]>([]);
   private deps = signal<MetricPoint[]>([]);
   summary = signal<ScoringSummary | null>(null);
+  policy = signal<ScoringPolicyStatus | null>(null);
   private descriptions = signal<Map<string, string>>(new Map());
   activeRule = signal<RuleDescription | null>(null);
 
@@ -138,6 +172,7 @@ export class BoardSchedulerComponent implements OnInit {
     this.board.query('dispatch.wait_ms', 'hour').subscribe((p) => this.wait.set(p));
     this.board.query('deps.wait_ms', 'hour').subscribe((p) => this.deps.set(p));
     this.board.getScoringSummary(24).subscribe((s) => this.summary.set(s));
+    this.board.getScoringPolicy().subscribe((p) => this.policy.set(p));
     this.board
       .getScoringRules()
       .subscribe((rules) => this.descriptions.set(new Map(rules.map((r) => [r.rule, r.description]))));
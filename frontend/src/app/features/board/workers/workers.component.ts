@@ -10,6 +10,7 @@ import {
   BoardService,
   BoardWorker,
   BoardFleetPoint,
+  BoardOrgQuota,
   LoadBucket,
   WorkerLoad,
 } from '@core/services/board.service';
@@ -93,6 +94,25 @@ import { MetricChartComponent } from '@shared/components/metric-chart/metric-cha
         }
       </tbody>
     </table>
+
+    @if (quotas().length > 0) {
+      <table class="workers">
+        <thead>
+          <tr><th>Org</th><th>Builds</th><th>Evaluations</th><th>Build minutes (24h)</th><th>Held on</th></tr>
+        </thead>
+        <tbody>
+          @for (q of quotas(); track q.organization) {
+            <tr>
+              <td class="mono">{{ q.organization_name }}</td>
+              <td>{{ q.active_builds }}/{{ q.max_concurrent_builds ?? '∞' }}</td>
+              <td>{{ q.active_evaluations }}/{{ q.max_concurrent_evaluations ?? '∞' }}</td>
+              <td>{{ q.build_minutes_used }}/{{ q.build_minutes_budget ?? '∞' }}</td>
+              <td [class.held]="q.blocked.length > 0">{{ heldOn(q) }}</td>
+            </tr>
+          }
+        </tbody>
+      </table>
+    }
   `,
   styles: [
     `
@@ -103,6 +123,7 @@ import { MetricChartComponent } from '@shared/components/metric-chart/metric-cha
       th { color: #fff; }
       .mono { font-family: monospace; }
       .muted { color: #818181; }
+      .held { color: #fd7e14; }
     `,
   ],
 })
@@ -110,6 +131,7 @@ export class BoardWorkersComponent implements OnInit {
   private board = inject(BoardService);
   workers = signal<BoardWorker[]>([]);
   fleet = signal<BoardFleetPoint[]>([]);
+  quotas = signal<BoardOrgQuota[]>([]);
   load = signal<WorkerLoad | null>(null);
 
   fleetCats = computed(() => this.fleet().map((p) => p.bucket_start.slice(11, 16)));
@@ -157,9 +179,16 @@ export class BoardWorkersComponent implements OnInit {
     },
   ]);
 
+  heldOn(q: BoardOrgQuota): string {
+    return q.blocked.map((k) => k.replace(/_/g, ' ')).join(', ') || '-';
+  }
+
   ngOnInit(): void {
     this.board.getWorkers().subscribe((w) => this.workers.set(w));
-    this.board.getFleet(24).subscribe((f) => this.fleet.set(f));
+    this.board.getFleet(24).subscribe((f) => {
+      this.fleet.set(f.points);
+      this.quotas.set(f.quotas);
+    });
     this.board.getWorkerLoad().subscribe((l) => this.load.set(l));
   }
 }
@@ -22,6 +22,8 @@ function cacheFor(access: AccessState) {
     active: true,
     priority: 50,
     max_storage_gb: 0,
+    eviction_policy: 'lru',
+    nar_compression: 'zstd',
     public: false,
     managed: access.managed,
     can_edit: access.canEdit,
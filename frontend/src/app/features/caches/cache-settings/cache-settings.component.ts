@@ -17,7 +17,7 @@ import { CachesService } from '@core/services/caches.service';
 import { LoadingSpinnerComponent } from '@shared/components/loading-spinner/loading-spinner.component';
 import { WritableDirective, ManagedDisableDirective } from '@shared/access';
 import { injectCacheAccess } from '@core/resolvers/inject-access';
-import { Cache } from '@core/models';
+import { Cache, CacheEvictionPolicy, NarCompression } from '@core/models';
 
 @Component({
   selector: 'app-cache-settings',
@@ -63,6 +63,8 @@ export class CacheSettingsComponent implements OnInit {
     priority: 50,
     local_priority: null as number | null,
     max_storage_gb: 0,
+    eviction_policy: 'disabled' as CacheEvictionPolicy,
+    nar_compression: 'zstd' as NarCompression,
     public: false,
   };
 
@@ -82,6 +84,8 @@ export class CacheSettingsComponent implements OnInit {
           priority: cache.priority,
           local_priority: cache.local_priority,
           max_storage_gb: cache.max_storage_gb ?? 0,
+          eviction_policy: cache.eviction_policy ?? 'disabled',
+          nar_compression: cache.nar_compression ?? 'zstd',
           public: cache.public,
         };
         this.loading.set(false);
@@ -119,6 +123,8 @@ export class CacheSettingsComponent implements OnInit {
       priority: this.formData.priority,
       local_priority: this.formData.local_priority,
       max_storage_gb: this.formData.max_storage_gb,
+      eviction_policy: this.formData.eviction_policy,
+      nar_compression: this.formData.nar_compression,
     }).subscribe({
       next: () => {
         visibilityCall.subscribe({
@@ -187,6 +187,21 @@ describe('EvaluationLogComponent', () => {
       expect(cmp.formatWaitingReason(reason)).toBe('2 workers are connected, but none can run the evaluation.');
     });
 
+    it('names the worker selector when no connected worker matches it', () => {
+      const { cmp } = setup();
+      const reason = {
+        kind: 'workers',
+        unmet: [],
+        connected_workers: 3,
+        available_architectures: [],
+        worker_selector: { pool: 'secure' },
+        matching_workers: 0,
+      } as const;
+      expect(cmp.formatWaitingReason(reason)).toBe(
+        'No connected worker matches the worker selector pool=secure (3 connected). The evaluation requires:',
+      );
+    });
+
     it('titles a full-cache stall', () => {
       const { cmp } = setup();
       expect(cmp.waitingTitle({ kind: 'cache_storage_full' })).toBe('Cache Storage Full');
@@ -200,5 +215,14 @@ describe('EvaluationLogComponent', () => {
         'Workers are available, but 9 builds are blocked on dependencies. Recovering automatically.',
       );
     });
+
+    it('titles and explains an organization quota hold', () => {
+      const { cmp } = setup();
+      const reason = { kind: 'quota', quota: 'concurrent_evaluations', limit: 2, used: 2 } as const;
+      expect(cmp.waitingTitle(reason)).toBe('Waiting for Organization Quota');
+      expect(cmp.formatWaitingReason(reason)).toBe(
+        'This organization is running 2 of its 2 concurrent evaluations. This evaluation starts once one finishes.',
+      );
+    });
   });
 });
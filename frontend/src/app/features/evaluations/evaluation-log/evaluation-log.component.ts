@@ -38,6 +38,7 @@ import { Evaluation, EvaluationMessage, EvaluationStatus, WaitingReason, Trigger
 import { AuthService } from '@core/services/auth.service';
 import { LoadingSpinnerComponent } from '@shared/components/loading-spinner/loading-spinner.component';
 import { commitLabel, formatEvaluationDuration, isRunningEvaluationStatus, parseUtcTimestamp } from '@shared/evaluation';
+import { formatLabels } from '@shared/text';
 import { ButtonModule } from 'primeng/button';
 import { environment } from '@environments/environment';
 
@@ -1278,6 +1279,13 @@ export class EvaluationLogComponent implements OnInit, OnDestroy {
         const archList = reason.available_architectures.length > 0
           ? reason.available_architectures.join(', ')
           : 'none';
+        if (reason.worker_selector && reason.matching_workers !== undefined) {
+          const selector = formatLabels(reason.worker_selector);
+          if (reason.matching_workers === 0) {
+            return `No connected worker matches the worker selector ${selector} (${reason.connected_workers} connected). The evaluation requires:`;
+          }
+          return `${reason.matching_workers} of ${reason.connected_workers} connected workers match the worker selector ${selector} (${archList}) but cannot satisfy:`;
+        }
         const workerWord = reason.connected_workers === 1 ? 'worker' : 'workers';
         return `${reason.connected_workers} connected ${workerWord} (${archList}) cannot satisfy:`;
       }
@@ -1298,6 +1306,15 @@ export class EvaluationLogComponent implements OnInit, OnDestroy {
         const buildWord = reason.pending_anchors === 1 ? 'build is' : 'builds are';
         return `Workers are available, but ${reason.pending_anchors} ${buildWord} blocked on dependencies. Recovering automatically.`;
       }
+      case 'quota':
+        switch (reason.quota) {
+          case 'concurrent_builds':
+            return `This organization is running ${reason.used} of its ${reason.limit} concurrent builds. Builds resume as running ones finish.`;
+          case 'concurrent_evaluations':
+            return `This organization is running ${reason.used} of its ${reason.limit} concurrent evaluations. This evaluation starts once one finishes.`;
+          case 'build_minutes':
+            return `This organization has used ${reason.used} of its ${reason.limit} build minutes in the last 24 hours. Builds resume as older usage ages out.`;
+        }
     }
   }
 
@@ -1308,6 +1325,7 @@ export class EvaluationLogComponent implements OnInit, OnDestroy {
       case 'no_cache': return 'No Cache Configured';
       case 'cache_storage_full': return 'Cache Storage Full';
       case 'graph_stuck': return 'Recovering Build Graph';
+      case 'quota': return 'Waiting for Organization Quota';
       default: return 'Waiting for Workers';
     }
   }
@@ -1323,6 +1341,7 @@ export class EvaluationLogComponent implements OnInit, OnDestroy {
       case 'polling': return 'Polling';
       case 'reporter_push': return 'Push';
       case 'reporter_pull_request': return 'PR';
+      case 'reporter_merge_queue': return 'Merge queue';
       case 'time': return 'Schedule';
       default: return 'Manual';
     }
@@ -197,6 +197,16 @@ describe('IntegrationsComponent - required webhook events', () => {
     expect(events).toContain('Merge request');
     expect(events).toContain('Comments (note)');
   });
+
+  it('lists pull request and comment events for Bitbucket', async () => {
+    const fixture = setup({ managed: false, canEdit: true, canTrigger: true }, [baseIntegration]);
+    await settled(fixture);
+    const comp = fixture.componentInstance;
+    comp.setInboundForge(baseIntegration.id, 'bitbucket-server');
+    const events = comp.requiredWebhookEvents(baseIntegration.id);
+    expect(events).toContain('source branch updated');
+    expect(events).toContain('Comment added');
+  });
 });
 
 describe('IntegrationsComponent - create github integration', () => {
@@ -93,6 +93,8 @@ export class IntegrationsComponent implements OnInit {
     { label: 'Gitea', value: 'gitea' },
     { label: 'Forgejo', value: 'forgejo' },
     { label: 'GitLab', value: 'gitlab' },
+    { label: 'Bitbucket Cloud', value: 'bitbucket' },
+    { label: 'Bitbucket Server', value: 'bitbucket-server' },
   ];
 
   formData: {
@@ -128,6 +130,8 @@ export class IntegrationsComponent implements OnInit {
     { label: 'Forgejo', value: 'forgejo' },
     { label: 'GitLab', value: 'gitlab' },
     { label: 'GitHub', value: 'github' },
+    { label: 'Bitbucket Cloud', value: 'bitbucket' },
+    { label: 'Bitbucket Server', value: 'bitbucket-server' },
   ]);
 
   allForgeOptions = computed<Option<ForgeType>[]>(() => [
@@ -135,6 +139,8 @@ export class IntegrationsComponent implements OnInit {
     { label: 'Forgejo', value: 'forgejo' },
     { label: 'GitLab', value: 'gitlab' },
     { label: 'GitHub', value: 'github' },
+    { label: 'Bitbucket Cloud', value: 'bitbucket' },
+    { label: 'Bitbucket Server', value: 'bitbucket-server' },
   ]);
 
   ngOnInit(): void {
@@ -162,10 +168,7 @@ export class IntegrationsComponent implements OnInit {
         const map: Record<string, InboundForge> = {};
         for (const i of list) {
           if (i.kind === 'inbound') {
-            map[i.id] =
-              i.forge_type === 'gitea' || i.forge_type === 'forgejo' || i.forge_type === 'gitlab'
-                ? i.forge_type
-                : 'gitea';
+            map[i.id] = i.forge_type === 'github' ? 'gitea' : i.forge_type;
           }
         }
         this.selectedForgeByIntegration.set(map);
@@ -398,9 +401,16 @@ export class IntegrationsComponent implements OnInit {
   }
 
   requiredWebhookEvents(id: string): string {
-    return this.inboundForge(id) === 'gitlab'
-      ? 'Push, Tag push, Merge request, Comments (note), and Releases events'
-      : 'Push, Pull Request, Issue Comment, Pull Request Comment, Pull Request Review, and Release';
+    switch (this.inboundForge(id)) {
+      case 'gitlab':
+        return 'Push, Tag push, Merge request, Comments (note), and Releases events';
+      case 'bitbucket':
+        return 'Repository push, Pull request created, updated, merged, declined, approved, and Comment created';
+      case 'bitbucket-server':
+        return 'Repository push, Pull request opened, source branch updated, merged, declined, approved, and Comment added';
+      default:
+        return 'Push, Pull Request, Issue Comment, Pull Request Comment, Pull Request Review, and Release';
+    }
   }
 
   copyInboundUrl(integration: Integration): void {
@@ -419,6 +429,8 @@ export class IntegrationsComponent implements OnInit {
       case 'forgejo': return 'Forgejo';
       case 'gitlab': return 'GitLab';
       case 'github': return 'GitHub';
+      case 'bitbucket': return 'Bitbucket Cloud';
+      case 'bitbucket-server': return 'Bitbucket Server';
     }
   }
 
@@ -35,6 +35,9 @@ const workerUnmanaged: Worker = {
   enable_fetch: true,
   enable_eval: true,
   enable_build: true,
+  labels: {},
+  cordoned: false,
+  maintenance_windows: [],
 };
 
 const workerManaged: Worker = {
@@ -46,6 +49,9 @@ const workerManaged: Worker = {
   enable_fetch: true,
   enable_eval: true,
   enable_build: true,
+  labels: {},
+  cordoned: false,
+  maintenance_windows: [],
 };
 
 const workerBase: Worker = {
@@ -57,6 +63,9 @@ const workerBase: Worker = {
   enable_fetch: true,
   enable_eval: true,
   enable_build: true,
+  labels: {},
+  cordoned: false,
+  maintenance_windows: [],
 };
 
 function setup(opts: {
@@ -226,3 +235,32 @@ describe('WorkersComponent - base workers', () => {
     );
   });
 });
+
+describe('WorkersComponent - cordon and drain', () => {
+  it('offers Drain on managed registrations but not on base workers', async () => {
+    const fixture = setup({
+      access: { managed: false, canEdit: true, canTrigger: true },
+      workers: [workerManaged, workerBase],
+      caches: [{ id: 'c', name: 'c' }],
+    });
+    await settled(fixture);
+
+    const drain = findAllByText(fixture.nativeElement, 'drain');
+    expect(drain.length).toBe(1);
+    expect(drain[0].disabled).toBe(false);
+  });
+
+  it('shows Uncordon and a Cordoned badge for a cordoned worker', async () => {
+    const fixture = setup({
+      access: { managed: false, canEdit: true, canTrigger: true },
+      workers: [{ ...workerUnmanaged, cordoned: true }],
+      caches: [{ id: 'c', name: 'c' }],
+    });
+    await settled(fixture);
+
+    expect(findByText(fixture.nativeElement, 'uncordon')).not.toBeNull();
+    const badge = (Array.from(fixture.nativeElement.querySelectorAll('.badge')) as HTMLElement[])
+      .find((el) => (el.textContent ?? '').trim() === 'Cordoned');
+    expect(badge, 'Cordoned badge').toBeTruthy();
+  });
+});
@@ -19,6 +19,7 @@ import { OrgAccessService } from '@core/services/org-access.service';
 import { GradientCapabilities, Worker, WorkerRegistration, AccessState } from '@core/models';
 import { LoadingSpinnerComponent } from '@shared/components/loading-spinner/loading-spinner.component';
 import { WritableDirective, ManagedDisableDirective } from '@shared/access';
+import { formatLabels, parseLabels } from '@shared/text';
 
 @Component({
   selector: 'app-workers',
@@ -64,6 +65,12 @@ export class WorkersComponent implements OnInit {
     return worker.is_base ? { ...a, managed: false } : a;
   }
 
+  // A cordon is runtime state that provisioning leaves alone, so it stays
+  // available on managed registrations.
+  cordonAccess(worker: Worker): AccessState {
+    return { ...this.rowAccess(worker), managed: false };
+  }
+
   readonly capLabels: { key: keyof GradientCapabilities; label: string }[] = [
     { key: 'federate', label: 'federate' },
     { key: 'fetch',    label: 'fetch' },
@@ -77,6 +84,7 @@ export class WorkersComponent implements OnInit {
   deletingId = signal<string | null>(null);
   togglingId = signal<string | null>(null);
   testingId = signal<string | null>(null);
+  cordoningId = signal<string | null>(null);
   showRegisterDialog = signal(false);
   showTokenDialog = signal(false);
   showToggleWarningDialog = signal(false);
@@ -98,10 +106,13 @@ export class WorkersComponent implements OnInit {
   newEnableFetch = true;
   newEnableEval = true;
   newEnableBuild = true;
+  newLabels = '';
   newName = '';
   editEnableFetch = true;
   editEnableEval = true;
   editEnableBuild = true;
+  editLabels = '';
+  editLabelsError = signal<string | null>(null);
   capUpdating = signal<string | null>(null);
   lastRegistration = signal<WorkerRegistration | null>(null);
   tokenCopied = signal(false);
@@ -154,12 +165,18 @@ export class WorkersComponent implements OnInit {
     this.newEnableFetch = true;
     this.newEnableEval = true;
     this.newEnableBuild = true;
+    this.newLabels = '';
     this.errorMessage.set(null);
     this.showRegisterDialog.set(true);
   }
 
   registerWorker(): void {
     if (!this.newWorkerId.trim() || !this.newWorkerName.trim()) return;
+    const labels = parseLabels(this.newLabels);
+    if ('error' in labels) {
+      this.errorMessage.set(`Invalid labels: ${labels.error}.`);
+      return;
+    }
     this.registering.set(true);
     this.errorMessage.set(null);
     const url = this.newWorkerUrl.trim() || undefined;
@@ -168,7 +185,7 @@ export class WorkersComponent implements OnInit {
       enable_fetch: this.newEnableFetch,
       enable_eval: this.newEnableEval,
       enable_build: this.newEnableBuild,
-    }).subscribe({
+    }, labels).subscribe({
       next: (reg) => {
         this.registering.set(false);
         this.showRegisterDialog.set(false);
@@ -235,20 +252,39 @@ export class WorkersComponent implements OnInit {
     this.pendingToggleWorker.set(null);
   }
 
+  /** Effective labels of a connected worker for this org, as `key=value` text. */
+  labelText(worker: Worker): string {
+    return formatLabels(worker.live?.labels);
+  }
+
+  /** Labels a connected worker advertises itself, as `key=value` text. */
+  advertisedLabelText(worker: Worker): string {
+    return formatLabels(worker.live?.advertised_labels);
+  }
+
   openRenameDialog(worker: Worker): void {
     this.renamingWorker.set(worker);
     this.newName = worker.display_name;
     this.editEnableFetch = worker.enable_fetch;
     this.editEnableEval = worker.enable_eval;
     this.editEnableBuild = worker.enable_build;
+    this.editLabels = formatLabels(worker.labels);
+    this.editLabelsError.set(null);
     this.showRenameDialog.set(true);
   }
 
   confirmRename(): void {
     const worker = this.renamingWorker();
     if (!worker || !this.newName.trim()) return;
+    const labels = worker.is_base ? null : parseLabels(this.editLabels);
+    if (labels && 'error' in labels) {
+      this.editLabelsError.set(labels.error);
+      return;
+    }
+    this.editLabelsError.set(null);
     this.renaming.set(true);
     const body: any = { display_name: this.newName.trim() };
+    if (labels && formatLabels(labels) !== formatLabels(worker.labels)) body.labels = labels;
     if (this.editEnableFetch !== worker.enable_fetch) body.enable_fetch = this.editEnableFetch;
     if (this.editEnableEval !== worker.enable_eval) body.enable_eval = this.editEnableEval;
     if (this.editEnableBuild !== worker.enable_build) body.enable_build = this.editEnableBuild;
@@ -293,6 +329,43 @@ export class WorkersComponent implements OnInit {
     });
   }
 
+  /** Drain a worker (cordon, running jobs finish) or lift an existing cordon. */
+  toggleCordon(worker: Worker): void {
+    this.cordoningId.set(worker.worker_id);
+    const done = () => {
+      this.cordoningId.set(null);
+      this.loadWorkers();
+    };
+    const fail = (err: any) => {
+      this.cordoningId.set(null);
+      this.messageService.add({
+        severity: 'error',
+        summary: worker.cordoned ? 'Uncordon failed' : 'Drain failed',
+        detail: err?.message || 'Failed to update worker.',
+      });
+    };
+    if (worker.cordoned) {
+      this.workersService.setWorkerCordoned(this.orgName, worker.worker_id, false).subscribe({
+        next: done,
+        error: fail,
+      });
+      return;
+    }
+    this.workersService.drainWorker(this.orgName, worker.worker_id).subscribe({
+      next: (s) => {
+        this.messageService.add({
+          severity: 'info',
+          summary: 'Worker cordoned',
+          detail: s.drained
+            ? 'No jobs of this organization are running on it.'
+            : `${s.in_flight_jobs} running job${s.in_flight_jobs === 1 ? '' : 's'} will finish first.`,
+        });
+        done();
+      },
+      error: fail,
+    });
+  }
+
   toggleCapability(worker: Worker, cap: 'fetch' | 'eval' | 'build', enabled: boolean): void {
     const key = `${worker.worker_id}:${cap}`;
     this.capUpdating.set(key);
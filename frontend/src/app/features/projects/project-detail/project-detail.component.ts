@@ -298,6 +298,7 @@ export class ProjectDetailComponent implements OnInit, OnDestroy {
       case 'polling': return 'Polling';
       case 'reporter_push': return 'Push';
       case 'reporter_pull_request': return e.pr_number ? `PR #${e.pr_number}` : 'PR';
+      case 'reporter_merge_queue': return 'Merge queue';
       case 'time': return 'Schedule';
       default: return 'Manual';
     }
@@ -34,6 +34,10 @@ function projectFor(c: AccessCase) {
     keep_evaluations: 30,
     concurrency: 'soft_abort' as const,
     sign_cache: true,
+    worker_selector: {},
+    build_timeout_secs: null,
+    build_max_silent_secs: null,
+    max_output_size: null,
     managed: c.managed,
     can_edit: c.canEdit,
     can_trigger: c.canTrigger ?? c.canEdit,
@@ -21,6 +21,7 @@ import { LoadingSpinnerComponent } from '@shared/components/loading-spinner/load
 import { WritableDirective, ManagedDisableDirective } from '@shared/access';
 import { ConcurrencyPolicy, Project } from '@core/models';
 import { injectProjectAccess } from '@core/resolvers/inject-access';
+import { formatLabels, parseLabels } from '@shared/text';
 
 @Component({
   selector: 'app-project-settings',
@@ -62,6 +63,7 @@ export class ProjectSettingsComponent implements OnInit {
   showTransferDialog = signal(false);
   errorMessage = signal<string | null>(null);
   saveSuccess = signal(false);
+  workerSelectorText = '';
   transferOrgName = '';
   transferError = signal<string | null>(null);
   transferSuccess = signal(false);
@@ -79,6 +81,9 @@ export class ProjectSettingsComponent implements OnInit {
     keep_evaluations: number;
     concurrency: ConcurrencyPolicy;
     sign_cache: boolean;
+    build_timeout_secs: number | null;
+    build_max_silent_secs: number | null;
+    max_output_size: number | null;
   } = {
     display_name: '',
     description: '',
@@ -87,6 +92,9 @@ export class ProjectSettingsComponent implements OnInit {
     keep_evaluations: 30,
     concurrency: 'soft_abort',
     sign_cache: true,
+    build_timeout_secs: null,
+    build_max_silent_secs: null,
+    max_output_size: null,
   };
 
   concurrencyOptions: { label: string; value: ConcurrencyPolicy; disabled?: boolean }[] = [
@@ -123,7 +131,11 @@ export class ProjectSettingsComponent implements OnInit {
           keep_evaluations: project.keep_evaluations,
           concurrency: project.concurrency,
           sign_cache: project.sign_cache,
+          build_timeout_secs: project.build_timeout_secs,
+          build_max_silent_secs: project.build_max_silent_secs,
+          max_output_size: project.max_output_size,
         };
+        this.workerSelectorText = formatLabels(project.worker_selector);
         this.loading.set(false);
       },
       error: (error) => {
@@ -134,10 +146,16 @@ export class ProjectSettingsComponent implements OnInit {
   }
 
   saveSettings(): void {
+    const workerSelector = parseLabels(this.workerSelectorText);
+    if ('error' in workerSelector) {
+      this.errorMessage.set(`Invalid worker selector: ${workerSelector.error}.`);
+      return;
+    }
     this.saving.set(true);
     this.errorMessage.set(null);
     this.saveSuccess.set(false);
-    this.projectsService.updateProject(this.orgName, this.projectName, this.formData).subscribe({
+    const data = { ...this.formData, worker_selector: workerSelector };
+    this.projectsService.updateProject(this.orgName, this.projectName, data).subscribe({
       next: () => {
         this.saving.set(false);
         this.saveSuccess.set(true);
@@ -130,6 +130,7 @@ export class ProjectTriggersComponent implements OnInit {
     { label: 'Polling', value: 'polling' },
     { label: 'Push (reporter)', value: 'reporter_push' },
     { label: 'Pull Request (reporter)', value: 'reporter_pull_request' },
+    { label: 'Merge Queue (reporter)', value: 'reporter_merge_queue' },
     { label: 'Time (cron)', value: 'time' },
   ];
 
@@ -231,6 +232,12 @@ export class ProjectTriggersComponent implements OnInit {
         cfg.require_approval = this.form.require_approval !== false;
         return cfg;
       }
+      case 'reporter_merge_queue': {
+        const cfg: any = { type: 'reporter_merge_queue', integration_id: this.form.integration_id };
+        const branches = this.splitList(this.form.branches);
+        if (branches.length) cfg.branches = branches;
+        return cfg;
+      }
       case 'time':
         return { type: 'time', cron: this.form.cron.trim() };
     }
@@ -311,6 +318,7 @@ export class ProjectTriggersComponent implements OnInit {
       case 'polling': return 'Polling';
       case 'reporter_push': return 'Push';
       case 'reporter_pull_request': return 'Pull Request';
+      case 'reporter_merge_queue': return 'Merge Queue';
       case 'time': return 'Cron';
     }
   }
@@ -337,6 +345,11 @@ export class ProjectTriggersComponent implements OnInit {
         if (cfg.require_approval === false) parts.push('approval gate off');
         return parts.join(' / ');
       }
+      case 'reporter_merge_queue': {
+        const parts: string[] = [`from ${this.integrationLabel(trigger)}`];
+        if (cfg.branches?.length) parts.push(`branches: ${cfg.branches.join(', ')}`);
+        return parts.join(' / ');
+      }
       case 'time':
         return cfg.cron ?? '';
     }
@@ -5,3 +5,4 @@
  */
 
 export * from './slug';
+export * from './labels';
@@ -0,0 +1,26 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+import { formatLabels, parseLabels } from './labels';
+
+describe('worker labels', () => {
+  it('round-trips key=value text', () => {
+    const parsed = parseLabels(' pool=secure , region=eu,');
+    expect(parsed).toEqual({ pool: 'secure', region: 'eu' });
+    expect(formatLabels(parsed as Record<string, string>)).toBe('pool=secure, region=eu');
+  });
+
+  it('treats blank text as no labels', () => {
+    expect(parseLabels('   ')).toEqual({});
+    expect(formatLabels(undefined)).toBe('');
+  });
+
+  it('rejects items without = or with invalid characters', () => {
+    expect(parseLabels('pool')).toEqual({ error: '"pool" is not in key=value form' });
+    expect('error' in parseLabels('=x')).toBe(true);
+    expect('error' in parseLabels('pool=a b')).toBe(true);
+  });
+});
@@ -0,0 +1,34 @@
+/*
+ * SPDX-FileCopyrightText: 2026 Wavelens GmbH <info@wavelens.io>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+import { WorkerLabels } from '@core/models';
+
+const LABEL_PART = /^[A-Za-z0-9._/-]{0,63}$/;
+
+// Worker labels and selectors are edited as `key=value, key=value` text; the
+// server enforces the same key/value charset and length.
+export function formatLabels(labels: WorkerLabels | null | undefined): string {
+  return Object.entries(labels ?? {})
+    .map(([key, value]) => `${key}=${value}`)
+    .join(', ');
+}
+
+export function parseLabels(text: string): WorkerLabels | { error: string } {
+  const labels: WorkerLabels = {};
+  for (const item of text.split(',').map((s) => s.trim()).filter((s) => s.length > 0)) {
+    const eq = item.indexOf('=');
+    if (eq < 0) {
+      return { error: `"${item}" is not in key=value form` };
+    }
+    const key = item.slice(0, eq).trim();
+    const value = item.slice(eq + 1).trim();
+    if (key.length === 0 || !LABEL_PART.test(key) || !LABEL_PART.test(value)) {
+      return { error: `"${item}" may only use letters, digits and . _ / - (up to 63 each)` };
+    }
+    labels[key] = value;
+  }
+  return labels;
+}
@@ -148,6 +148,35 @@
         '';
       };
 
+      max_concurrent_builds = mkOption {
+        type = types.nullOr types.ints.positive;
+        default = null;
+        description = ''
+          Hard cap on this organization's builds in flight at once. The
+          scheduler holds further builds until one finishes. `null` is
+          unlimited.
+        '';
+      };
+
+      max_concurrent_evaluations = mkOption {
+        type = types.nullOr types.ints.positive;
+        default = null;
+        description = ''
+          Hard cap on this organization's evaluations running at once. Further
+          evaluations wait with a quota reason. `null` is unlimited.
+        '';
+      };
+
+      build_minutes_budget = mkOption {
+        type = types.nullOr types.ints.positive;
+        default = null;
+        description = ''
+          Build minutes this organization may spend per rolling 24 hours. Once
+          spent, its builds are held until older usage ages out of the window.
+          `null` is unlimited.
+        '';
+      };
+
       created_by = mkOption {
         type = types.str;
         description = "Username of the user who created this organization";
@@ -292,6 +321,52 @@
         '';
       };
 
+      worker_selector = mkOption {
+        type = types.attrsOf types.str;
+        default = { };
+        description = ''
+          Worker labels a worker must carry to evaluate this project and
+          build its derivations. Every pair must match a registration label
+          (`services.gradient.state.workers.<name>.labels`) exactly; labels
+          the worker advertises itself are never matched.
+        '';
+        example = literalExpression ''
+          { pool = "secure"; }
+        '';
+      };
+
+      build_timeout_secs = mkOption {
+        type = types.nullOr types.ints.unsigned;
+        default = null;
+        description = ''
+          Wall-clock limit in seconds for each build of this project.
+          `null` uses the server default, `0` disables the limit. A
+          derivation's `timeout` attribute or the evaluated attribute's
+          `meta.timeout` takes precedence.
+        '';
+      };
+
+      build_max_silent_secs = mkOption {
+        type = types.nullOr types.ints.unsigned;
+        default = null;
+        description = ''
+          Silent-output limit in seconds for each build of this project.
+          `null` uses the server default, `0` disables the limit. A
+          derivation's `maxSilent` attribute or the evaluated attribute's
+          `meta.maxSilent` takes precedence.
+        '';
+      };
+
+      max_output_size = mkOption {
+        type = types.nullOr types.ints.unsigned;
+        default = null;
+        description = ''
+          Largest NAR size in bytes any single build output of this project
+          may have. A larger output fails the build permanently and is not
+          uploaded. `null` or `0` means no limit.
+        '';
+      };
+
       triggers = mkOption {
         type = types.nullOr (types.listOf triggerType);
         default = null;
@@ -353,7 +428,11 @@
 
           Token files for `send_web_request` actions must live at the systemd
           credential path
-          `''${GRADIENT_CREDENTIALS_DIR}/gradient_action_''${name}_token`.
+          `''${GRADIENT_CREDENTIALS_DIR}/gradient_action_''${name}_token`,
+          signing secrets at
+          `''${GRADIENT_CREDENTIALS_DIR}/gradient_action_''${name}_signing_secret`,
+          and `send_matrix` access tokens at
+          `''${GRADIENT_CREDENTIALS_DIR}/gradient_action_''${name}_access_token`.
         '';
         example = literalExpression ''
           [
@@ -434,7 +513,7 @@
       };
 
       forge_type = mkOption {
-        type = types.enum [ "gitea" "forgejo" "gitlab" "github" ];
+        type = types.enum [ "gitea" "forgejo" "gitlab" "github" "bitbucket" "bitbucket-server" ];
         description = ''
           Which forge this integration targets. For inbound integrations this
           is display metadata only - a single inbound row can serve
@@ -502,7 +581,7 @@
   triggerType = types.submodule ({ name, ... }: {
     options = {
       type = mkOption {
-        type = types.enum [ "polling" "reporter_push" "reporter_pull_request" "time" ];
+        type = types.enum [ "polling" "reporter_push" "reporter_pull_request" "reporter_merge_queue" "time" ];
         description = "Trigger kind. Drives which `config` shape is expected and how the dispatch loop fires it.";
       };
 
@@ -511,7 +590,8 @@
         default = null;
         description = ''
           Name of an inbound integration in the same organization that backs
-          this trigger. Required for `reporter_push` and `reporter_pull_request`;
+          this trigger. Required for `reporter_push`, `reporter_pull_request`
+          and `reporter_merge_queue`;
           ignored for `polling` and `time`. Must name an integration in
           `services.gradient.state.integrations` or a GitHub App row auto-seeded
           when the App is installed on the org.
@@ -527,6 +607,7 @@
           - `polling`: `{ interval_secs = 300; branch = "main"; }` (minimum 10 seconds; `branch` optional, defaults to remote HEAD)
           - `reporter_push`: `{ branches = [ "main" "release/*" ]; tags = [ ]; releases_only = false; }`
           - `reporter_pull_request`: `{ branches = [ ]; actions = [ "opened" "synchronize" "reopened" ]; require_approval = true; }`
+          - `reporter_merge_queue`: `{ branches = [ "main" ]; }`
           - `time`: `{ cron = "0 0 2 * * *"; }` (six-field: sec min hour dom mon dow, UTC)
 
           Empty `branches`/`tags`/`actions` lists mean "match all".
@@ -547,6 +628,19 @@
         default = true;
         description = "Whether the trigger is active. Inactive triggers are stored but never fire.";
       };
+
+      worker_selector = mkOption {
+        type = types.attrsOf types.str;
+        default = { };
+        description = ''
+          Worker selector for builds of evaluations fired by this trigger,
+          merged over the project's `worker_selector` (the trigger wins on a
+          shared key).
+        '';
+        example = literalExpression ''
+          { pool = "untrusted"; }
+        '';
+      };
     };
   });
 
@@ -562,7 +656,7 @@
       };
 
       type = mkOption {
-        type = types.enum [ "send_mail" "send_web_request" "forge_status_report" "open_pr" ];
+        type = types.enum [ "send_mail" "send_web_request" "send_matrix" "send_slack" "forge_status_report" "open_pr" ];
         description = "Action kind. Drives which `config` shape is expected.";
       };
 
@@ -586,8 +680,10 @@
         description = ''
           Type-specific configuration. Shape depends on `type`:
 
-          - `send_mail`: `{ recipients = [ "ops@example.com" ]; subject_template = null; }`
+          - `send_mail`: `{ recipients = [ "ops@example.com" ]; subject_template = null; digest_window_secs = null; }` (set `digest_window_secs` to mail one summary per window instead of one mail per event)
           - `send_web_request`: `{ url = "https://hooks.example.com/gradient"; token_file = "/etc/gradient/secrets/<name>-token"; }`
+          - `send_matrix`: `{ homeserver = "https://matrix.example.org"; room_id = "!abc:example.org"; access_token_file = "/etc/gradient/secrets/<name>-matrix-token"; message_template = null; }`
+          - `send_slack`: `{ url = "https://hooks.slack.com/services/..."; message_template = null; }` (any Slack-compatible incoming webhook)
           - `forge_status_report`: `{ integration = "gitea-prod"; }` (name of an outbound integration in the same organization)
           - `open_pr`: opens a pull request on the forge with the result of a
             generator (currently `flake_lock`, which updates `flake.lock`).
@@ -615,7 +711,25 @@
           For `send_web_request`, omit `token_file` to send unauthenticated
           requests. When set, the token is read from the systemd credential
           file `gradient_action_''${name}_token` and stored encrypted with
-          the server's crypt key.
+          the server's crypt key. Set `signing_secret_file` to sign every
+          delivery with an `X-Gradient-Signature` HMAC-SHA256 header; the
+          secret is read from `gradient_action_''${name}_signing_secret` and
+          stored encrypted the same way.
+
+          `send_web_request` also accepts `method` (`"post"`, the default,
+          `"put"` or `"patch"`), `headers` (an attribute set of extra request
+          headers; `Host`, `Content-Length` and the `X-Gradient-*` headers
+          are reserved) and `body_template`, a JSON document sent instead of
+          the raw event payload. String values in the template may contain
+          `{{path}}` placeholders: `{{event}}` or a dotted path into the event
+          payload such as `{{evaluation_id}}`. A string that is exactly one
+          placeholder keeps the referenced value's JSON type.
+
+          `send_matrix` and `send_slack` post a message rendered from
+          `message_template`. It supports the placeholders `{event}`, `{org}`,
+          `{project}`, `{status}` (evaluation status), `{failed_builds}` and
+          `{link}`. The Matrix access token is read from
+          `gradient_action_''${name}_access_token`.
         '';
         example = literalExpression ''
           { recipients = [ "ops@example.com" ]; }
@@ -672,6 +786,31 @@
     };
   };
 
+  cachePinType = types.submodule {
+    options = {
+      name = mkOption {
+        type = types.str;
+        description = "Pin name, unique within the cache.";
+      };
+      store_path = mkOption {
+        type = types.nullOr types.str;
+        default = null;
+        description = "Store path whose runtime closure GC keeps. Set this or `evaluation`.";
+      };
+      evaluation = mkOption {
+        type = types.nullOr types.str;
+        default = null;
+        description = "Evaluation ID whose entry-point outputs GC keeps, along with the evaluation itself. Set this or `store_path`.";
+      };
+      expires_at = mkOption {
+        type = types.nullOr types.str;
+        default = null;
+        example = "2027-01-01T00:00:00";
+        description = "UTC timestamp after which the pin stops protecting its closure. `null` never expires.";
+      };
+    };
+  };
+
   cacheType = types.submodule ({ config, name, ... }: {
     options = {
       name = mkOption {
@@ -726,6 +865,28 @@
         '';
       };
 
+      eviction_policy = mkOption {
+        type = types.enum [ "lru" "oldest_first" "disabled" ];
+        default = "disabled";
+        description = ''
+          How the cache frees space once it reaches `max_storage_gb`: evict
+          the least recently served (`lru`) or the oldest (`oldest_first`)
+          paths no retained evaluation references, down to 90% of the limit.
+          `disabled` (the default) leaves new evaluations parked until space
+          is freed.
+        '';
+      };
+
+      nar_compression = mkOption {
+        type = types.enum [ "zstd" "xz" "none" ];
+        default = "zstd";
+        description = ''
+          Compression the cache serves NARs in. NARs are stored as zstd;
+          `xz` and `none` are transcoded while streaming, for clients
+          without zstd support.
+        '';
+      };
+
       signing_key_file = mkOption {
         type = types.str;
         description = "Path to file containing the Nix cache signing key";
@@ -776,6 +937,16 @@
         description = "Custom roles available on this cache.";
       };
 
+      pins = mkOption {
+        type = types.listOf cachePinType;
+        default = [];
+        description = ''
+          Named GC roots on this cache. Replaced on every state apply; pins
+          created through the API are kept unless a declared pin takes
+          their name.
+        '';
+      };
+
       public = mkOption {
         type = types.bool;
         default = false;
@@ -872,6 +1043,34 @@
         default = true;
         description = "Global enable for a base worker. When false the base worker is unavailable to every organization. Ignored for non-base workers.";
       };
+
+      labels = mkOption {
+        type = types.attrsOf types.str;
+        default = { };
+        description = "Labels attached to this worker's registrations. They apply only to jobs of the registering organizations and are the only labels worker selectors match. Not allowed on base workers.";
+        example = literalExpression ''
+          { pool = "secure"; }
+        '';
+      };
+
+      maintenance_windows = mkOption {
+        type = types.listOf (types.submodule {
+          options = {
+            schedule = mkOption {
+              type = types.str;
+              description = "Six-field cron expression (`sec min hour dom mon dow`, UTC) at which the window opens.";
+              example = "0 0 3 * * Sun";
+            };
+            duration_secs = mkOption {
+              type = types.ints.between 1 604800;
+              description = "How long the window stays open, in seconds.";
+              example = 7200;
+            };
+          };
+        });
+        default = [ ];
+        description = "Scheduled maintenance windows. While one is open, the registering organizations' jobs are not assigned to this worker; jobs already running finish. Not allowed on base workers.";
+      };
     };
   });
 
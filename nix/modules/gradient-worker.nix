@@ -161,6 +161,17 @@ in {
         example = [ "nixos-test" "benchmark" "big-parallel" ];
       };
 
+      labels = lib.mkOption {
+        description = ''
+          Free-form `key = value` labels this worker advertises. They are
+          informational: project worker selectors only match labels an
+          organization sets on this worker's registration.
+        '';
+        type = lib.types.attrsOf lib.types.str;
+        default = { };
+        example = { pool = "secure"; region = "eu"; };
+      };
+
       cpuCoreScore = lib.mkOption {
         description = "Override the advertised single-core speed score (higher is faster). When null, the worker benchmarks the host at startup.";
         type = lib.types.nullOr lib.types.ints.positive;
@@ -478,6 +489,8 @@ in {
           GRADIENT_WORKER_ARCHITECTURES = lib.concatStringsSep "," cfg.settings.architectures;
         } // lib.optionalAttrs (cfg.settings.systemFeatures != []) {
           GRADIENT_WORKER_SYSTEM_FEATURES = lib.concatStringsSep "," cfg.settings.systemFeatures;
+        } // lib.optionalAttrs (cfg.settings.labels != { }) {
+          GRADIENT_WORKER_LABELS = lib.concatStringsSep "," (lib.mapAttrsToList (k: v: "${k}=${v}") cfg.settings.labels);
         } // lib.optionalAttrs (cfg.settings.maxBuildCores != null) {
           GRADIENT_WORKER_MAX_BUILD_CORES = toString cfg.settings.maxBuildCores;
         } // lib.optionalAttrs (cfg.settings.cpuCoreScore != null) {
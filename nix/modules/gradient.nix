@@ -18,6 +18,10 @@
     has_access_token_file = int.access_token_file != null;
   }) cfg.state.integrations;
 
+  scoringPoliciesFile = pkgs.writers.writeJSON "gradient-scoring-policies.json" {
+    policies = cfg.settings.schedulerScoringPolicies;
+  };
+
   stateJsonFile = pkgs.writers.writeJSON "gradient-state.json" (cfg.state // {
     integrations = augmentedIntegrations;
   });
@@ -56,6 +60,20 @@
         "gradient_action_${action.name}_token:${tokenFile}"
     ) project.actions
   ) cfg.state.projects);
+  actionSigningSecretFiles = lib.concatLists (lib.mapAttrsToList (_: project:
+    lib.concatMap (action:
+      let secretFile = action.config.signing_secret_file or null; in
+      lib.optional (action.type == "send_web_request" && secretFile != null)
+        "gradient_action_${action.name}_signing_secret:${secretFile}"
+    ) project.actions
+  ) cfg.state.projects);
+  actionAccessTokenFiles = lib.concatLists (lib.mapAttrsToList (_: project:
+    lib.concatMap (action:
+      let tokenFile = action.config.access_token_file or null; in
+      lib.optional (action.type == "send_matrix" && tokenFile != null)
+        "gradient_action_${action.name}_access_token:${tokenFile}"
+    ) project.actions
+  ) cfg.state.projects);
 in {
   # disabledModules = [
   #   "services/gradient/default.nix"
@@ -417,6 +435,72 @@ in {
         };
       };
 
+      autoscale = {
+        enable = lib.mkEnableOption "queue-pressure worker autoscaling hooks";
+        webhookUrl = lib.mkOption {
+          description = ''
+            URL each scaling decision is POSTed to as JSON. Exactly one of
+            `webhookUrl` and `command` must be set.
+          '';
+          type = lib.types.nullOr lib.types.str;
+          default = null;
+        };
+
+        webhookTokenFile = lib.mkOption {
+          description = "File holding a bearer token sent with every autoscale webhook request";
+          type = lib.types.nullOr lib.types.path;
+          default = null;
+        };
+
+        command = lib.mkOption {
+          description = ''
+            Shell command run with each scaling decision as JSON on stdin. A
+            non-zero exit is logged and retried on the next tick.
+          '';
+          type = lib.types.nullOr lib.types.str;
+          default = null;
+        };
+
+        interval = lib.mkOption {
+          description = "Seconds between scaling decisions";
+          type = lib.types.ints.positive;
+          default = 30;
+        };
+
+        idleSecs = lib.mkOption {
+          description = ''
+            Seconds a scalable worker must sit without jobs, and without pending
+            work it could take, before it is drained for scale-down. It is only
+            listed for release once its in-flight builds have finished.
+          '';
+          type = lib.types.ints.unsigned;
+          default = 600;
+        };
+
+        newWorkerSlots = lib.mkOption {
+          description = "Build slots a newly started worker is assumed to offer when sizing a scale-up";
+          type = lib.types.ints.positive;
+          default = 4;
+        };
+
+        maxWorkers = lib.mkOption {
+          description = "Upper bound on the desired worker count of any one (architecture, feature set) pool";
+          type = lib.types.ints.positive;
+          default = 10;
+        };
+
+        workerSelector = lib.mkOption {
+          description = ''
+            Advertised labels marking the workers the autoscaler may drain and
+            release. Empty treats every worker as scalable; other workers still
+            count as capacity.
+          '';
+          type = lib.types.attrsOf lib.types.str;
+          default = { };
+          example = { pool = "ephemeral"; };
+        };
+      };
+
       settings = {
         enableRegistration = lib.mkEnableOption "self-service user registration (when disabled, accounts are provisioned only via OIDC or state)" // { default = true; };
         sentryDsn = lib.mkOption {
@@ -580,6 +664,25 @@ in {
           default = 3600;
         };
 
+        flakyMaxReruns = lib.mkOption {
+          description = "Re-runs on another worker granted to a build whose failure looks flaky before it is final. `0` disables flaky re-runs.";
+          type = lib.types.ints.unsigned;
+          default = 1;
+        };
+
+        flakyLogPatterns = lib.mkOption {
+          description = "Case-insensitive substrings that mark a build failure as flaky when found in the tail of the build log.";
+          type = lib.types.listOf lib.types.str;
+          default = [ ];
+          example = [ "Connection reset by peer" "Resource temporarily unavailable" ];
+        };
+
+        flakyFlipRatePercent = lib.mkOption {
+          description = "Share (percent) of consecutive outcomes that must flip between pass and fail before a derivation name is considered flaky.";
+          type = lib.types.ints.between 1 100;
+          default = 20;
+        };
+
         schedulerScoringPolicy = lib.mkOption {
           description = ''
             Scheduler scoring policy for ranking queued jobs against a
@@ -587,12 +690,46 @@ in {
             dependency count, anti-starvation, builtin de-prioritization and
             fetch-worker reservation; `resource-aware` (the default) also adds
             RAM/OOM-fit, CPU affinity, preferLocalBuild affinity and per-org
-            fair-share.
+            fair-share. Any policy defined in `schedulerScoringPolicyFile` may
+            be named as well.
           '';
-          type = lib.types.enum [ "simple" "resource-aware" ];
+          type = lib.types.str;
           default = "resource-aware";
         };
 
+        schedulerScoringPolicies = lib.mkOption {
+          description = ''
+            Declarative scoring policies, rendered to
+            `schedulerScoringPolicyFile`. Each policy has a `name`, an optional
+            built-in to `extends`, optional `uses_history`, and `rules` keyed
+            by rule name with `enabled`, `weight` and `veto`.
+          '';
+          type = lib.types.listOf (lib.types.attrsOf lib.types.anything);
+          default = [ ];
+          example = lib.literalExpression ''
+            [{
+              name = "mixed-fleet";
+              extends = "resource-aware";
+              rules = {
+                ResourceFitRule.weight = 2.0;
+                RescoreWaitRule.veto = false;
+                DiskAffinityRule.enabled = false;
+              };
+            }]
+          '';
+        };
+
+        schedulerScoringPolicyFile = lib.mkOption {
+          description = ''
+            JSON file of declarative scoring policies, re-read when it changes.
+            Point this at a mutable path to tune weights without restarting the
+            server; validation errors are shown on the board.
+          '';
+          type = lib.types.nullOr (lib.types.either lib.types.path lib.types.str);
+          default = if cfg.settings.schedulerScoringPolicies != [ ] then scoringPoliciesFile else null;
+          defaultText = lib.literalExpression "if config.services.gradient.settings.schedulerScoringPolicies != [ ] then <generated JSON> else null";
+        };
+
         maxRequestSize = lib.mkOption {
           description = ''
             Maximum size in bytes of an HTTP request body for most endpoints.
@@ -712,6 +849,18 @@ in {
           default = false;
         };
 
+        narChunking = lib.mkOption {
+          description = "Convert stored NARs to the deduplicated chunked layout: each cache-maintenance pass splits flat NARs into content-defined chunks, stores every distinct chunk once and serves the NAR reassembled. Uploads still land flat and are converted afterwards.";
+          type = lib.types.bool;
+          default = false;
+        };
+
+        narChunkingThresholdBytes = lib.mkOption {
+          description = "Compressed size in bytes below which a NAR stays flat when narChunking is enabled.";
+          type = lib.types.ints.unsigned;
+          default = 1048576;
+        };
+
         narUploadGraceHours = lib.mkOption {
           description = "Grace period in hours before the orphan-files GC reclaims a NAR object no database row references (covers the upload commit window).";
           type = lib.types.ints.unsigned;
@@ -850,6 +999,10 @@ in {
         assertion = !(cfg.reverseProxy.nginx.enable && cfg.reverseProxy.caddy.enable);
         message = "You can only use one reverse proxy at a time";
       }
+      {
+        assertion = cfg.autoscale.enable -> ((cfg.autoscale.webhookUrl != null) != (cfg.autoscale.command != null));
+        message = "autoscale requires exactly one of webhookUrl and command";
+      }
     ];
 
     systemd.services.gradient-server = {
@@ -902,9 +1055,11 @@ in {
           "gradient_github_app_webhook_secret:${cfg.githubApp.webhookSecretFile}"
         ] ++ lib.optional (cfg.metricsTokenFile != null)
           "gradient_metrics_token:${cfg.metricsTokenFile}"
+        ++ lib.optional (cfg.autoscale.enable && cfg.autoscale.webhookTokenFile != null)
+          "gradient_autoscale_webhook_token:${cfg.autoscale.webhookTokenFile}"
         ++ userPasswordFiles ++ orgPrivateKeyFiles ++ cacheSigningKeyFiles ++ apiKeyFiles
           ++ workerTokenFiles ++ integrationSecretFiles ++ integrationTokenFiles
-          ++ actionTokenFiles;
+          ++ actionTokenFiles ++ actionSigningSecretFiles ++ actionAccessTokenFiles;
       };
 
       unitConfig = {
@@ -955,6 +1110,8 @@ in {
         GRADIENT_BUILD_RETRY_BACKOFF_SECS = toString cfg.settings.buildRetryBackoffSecs;
         GRADIENT_BUILD_DEFAULT_TIMEOUT_SECS = toString cfg.settings.buildDefaultTimeoutSecs;
         GRADIENT_BUILD_DEFAULT_MAX_SILENT_SECS = toString cfg.settings.buildDefaultMaxSilentSecs;
+        GRADIENT_FLAKY_MAX_RERUNS = toString cfg.settings.flakyMaxReruns;
+        GRADIENT_FLAKY_FLIP_RATE_PERCENT = toString cfg.settings.flakyFlipRatePercent;
         GRADIENT_SCHEDULER_SCORING_POLICY = cfg.settings.schedulerScoringPolicy;
         GRADIENT_MAX_REQUEST_SIZE = toString cfg.settings.maxRequestSize;
         GRADIENT_MAX_NAR_UPLOAD_SIZE = toString cfg.settings.maxNarUploadSize;
@@ -971,6 +1128,8 @@ in {
         GRADIENT_CACHE_MAINTENANCE_INTERVAL_SECS = toString cfg.settings.cacheMaintenanceIntervalSecs;
         GRADIENT_SIGN_SWEEP_INTERVAL_SECS = toString cfg.settings.signSweepIntervalSecs;
         GRADIENT_NAR_VERIFY_DIGEST = lib.boolToString cfg.settings.narVerifyDigest;
+        GRADIENT_NAR_CHUNKING = lib.boolToString cfg.settings.narChunking;
+        GRADIENT_NAR_CHUNKING_THRESHOLD_BYTES = toString cfg.settings.narChunkingThresholdBytes;
         GRADIENT_NAR_UPLOAD_GRACE_HOURS = toString cfg.settings.narUploadGraceHours;
         GRADIENT_GC_WEDGED_EVAL_HOURS = toString cfg.settings.gcWedgedEvalHours;
         GRADIENT_NAR_STORAGE_OPEN_TIMEOUT_SECS = toString cfg.settings.narStorageOpenTimeoutSecs;
@@ -991,6 +1150,10 @@ in {
         GRADIENT_PR_COMMIT_NAME = cfg.settings.prCommitName;
       } // lib.optionalAttrs (cfg.settings.prCommitEmail != null) {
         GRADIENT_PR_COMMIT_EMAIL = cfg.settings.prCommitEmail;
+      } // lib.optionalAttrs (cfg.settings.flakyLogPatterns != [ ]) {
+        GRADIENT_FLAKY_LOG_PATTERNS = builtins.concatStringsSep "," cfg.settings.flakyLogPatterns;
+      } // lib.optionalAttrs (cfg.settings.schedulerScoringPolicyFile != null) {
+        GRADIENT_SCHEDULER_SCORING_POLICY_FILE = toString cfg.settings.schedulerScoringPolicyFile;
       } // lib.optionalAttrs (cfg.settings.sentryDsn != null) {
         GRADIENT_SENTRY_DSN = cfg.settings.sentryDsn;
       } // lib.optionalAttrs (cfg.settings.logLevel.cache != null) {
@@ -1037,6 +1200,19 @@ in {
         GRADIENT_GITHUB_APP_WEBHOOK_SECRET_FILE = "%d/gradient_github_app_webhook_secret";
       } // lib.optionalAttrs (cfg.metricsTokenFile != null) {
         GRADIENT_METRICS_TOKEN_FILE = "%d/gradient_metrics_token";
+      } // lib.optionalAttrs cfg.autoscale.enable {
+        GRADIENT_AUTOSCALE_INTERVAL = toString cfg.autoscale.interval;
+        GRADIENT_AUTOSCALE_IDLE_SECS = toString cfg.autoscale.idleSecs;
+        GRADIENT_AUTOSCALE_NEW_WORKER_SLOTS = toString cfg.autoscale.newWorkerSlots;
+        GRADIENT_AUTOSCALE_MAX_WORKERS = toString cfg.autoscale.maxWorkers;
+      } // lib.optionalAttrs (cfg.autoscale.enable && cfg.autoscale.workerSelector != { }) {
+        GRADIENT_AUTOSCALE_WORKER_SELECTOR = lib.concatStringsSep "," (lib.mapAttrsToList (k: v: "${k}=${v}") cfg.autoscale.workerSelector);
+      } // lib.optionalAttrs (cfg.autoscale.enable && cfg.autoscale.webhookUrl != null) {
+        GRADIENT_AUTOSCALE_WEBHOOK_URL = cfg.autoscale.webhookUrl;
+      } // lib.optionalAttrs (cfg.autoscale.enable && cfg.autoscale.webhookTokenFile != null) {
+        GRADIENT_AUTOSCALE_WEBHOOK_TOKEN_FILE = "%d/gradient_autoscale_webhook_token";
+      } // lib.optionalAttrs (cfg.autoscale.enable && cfg.autoscale.command != null) {
+        GRADIENT_AUTOSCALE_COMMAND = cfg.autoscale.command;
       } // lib.optionalAttrs (cfg.settings.otlpEndpoint != null) {
         GRADIENT_OTLP_ENDPOINT = cfg.settings.otlpEndpoint;
       };